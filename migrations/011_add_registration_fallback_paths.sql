@@ -0,0 +1,30 @@
+-- Order in which paths are processed by selection (lower runs first)
+ALTER TABLE registration_paths ADD COLUMN selection_order INTEGER NOT NULL DEFAULT 0;
+
+-- Create registration_fallback_paths table
+-- Ranked alternative paths (within the same period) an applicant is
+-- reconsidered in when they fall outside the quota of their primary path.
+CREATE TABLE registration_fallback_paths (
+    id SERIAL PRIMARY KEY,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    path_id INTEGER NOT NULL REFERENCES registration_paths(id) ON DELETE CASCADE,
+    priority INTEGER NOT NULL,
+    path_data JSONB NOT NULL DEFAULT '{}',
+    selection_score DOUBLE PRECISION,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_fallback_path UNIQUE (registration_id, path_id),
+    CONSTRAINT unique_fallback_priority UNIQUE (registration_id, priority)
+);
+
+-- Create indexes
+CREATE INDEX idx_fallback_paths_registration_id ON registration_fallback_paths(registration_id);
+CREATE INDEX idx_fallback_paths_path_id ON registration_fallback_paths(path_id);
+
+-- Create trigger for updated_at
+CREATE TRIGGER update_registration_fallback_paths_updated_at BEFORE UPDATE ON registration_fallback_paths
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Path the applicant was finally accepted in (primary or one of the fallbacks)
+ALTER TABLE registrations ADD COLUMN accepted_path_id INTEGER REFERENCES registration_paths(id);
+CREATE INDEX idx_registrations_accepted_path_id ON registrations(accepted_path_id);
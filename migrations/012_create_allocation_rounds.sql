@@ -0,0 +1,69 @@
+-- Create allocation_rounds table
+-- A centralized (dinas-run) allocation across the periods of several schools
+-- of the same level and academic year. Each student gets at most one seat.
+CREATE TABLE allocation_rounds (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    academic_year VARCHAR(20) NOT NULL,
+    level VARCHAR(10) NOT NULL CHECK (level IN ('SD', 'SMP', 'SMA', 'SMK')),
+    choice_limit INTEGER NOT NULL DEFAULT 3 CHECK (choice_limit > 0),
+    status VARCHAR(20) NOT NULL DEFAULT 'draft' CHECK (status IN ('draft', 'open', 'closed', 'allocated')),
+    allocated_at TIMESTAMPTZ,
+    created_by INTEGER REFERENCES users(id),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Periods participating in a round (a period belongs to at most one round)
+CREATE TABLE allocation_round_periods (
+    round_id INTEGER NOT NULL REFERENCES allocation_rounds(id) ON DELETE CASCADE,
+    period_id INTEGER NOT NULL REFERENCES periods(id) ON DELETE CASCADE,
+    PRIMARY KEY (round_id, period_id),
+    CONSTRAINT unique_round_period UNIQUE (period_id)
+);
+
+-- Ranked school choices submitted by parents, one registration per school
+CREATE TABLE allocation_choices (
+    id SERIAL PRIMARY KEY,
+    round_id INTEGER NOT NULL REFERENCES allocation_rounds(id) ON DELETE CASCADE,
+    user_id INTEGER NOT NULL REFERENCES users(id),
+    student_nisn VARCHAR(20) NOT NULL,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    choice_rank INTEGER NOT NULL CHECK (choice_rank > 0),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_choice_registration UNIQUE (round_id, registration_id),
+    CONSTRAINT unique_choice_rank UNIQUE (round_id, student_nisn, choice_rank)
+);
+
+-- Final outcome per student with an explanation of every choice considered
+CREATE TABLE allocation_results (
+    id SERIAL PRIMARY KEY,
+    round_id INTEGER NOT NULL REFERENCES allocation_rounds(id) ON DELETE CASCADE,
+    student_nisn VARCHAR(20) NOT NULL,
+    student_name VARCHAR(255) NOT NULL,
+    status VARCHAR(20) NOT NULL CHECK (status IN ('placed', 'unplaced')),
+    registration_id INTEGER REFERENCES registrations(id) ON DELETE SET NULL,
+    school_id INTEGER REFERENCES schools(id),
+    path_id INTEGER REFERENCES registration_paths(id),
+    choice_rank INTEGER,
+    selection_score DOUBLE PRECISION,
+    explanation TEXT NOT NULL,
+    details JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_round_student UNIQUE (round_id, student_nisn)
+);
+
+-- Create indexes
+CREATE INDEX idx_allocation_rounds_status ON allocation_rounds(status);
+CREATE INDEX idx_allocation_choices_round_nisn ON allocation_choices(round_id, student_nisn);
+CREATE INDEX idx_allocation_choices_user_id ON allocation_choices(user_id);
+CREATE INDEX idx_allocation_results_round_id ON allocation_results(round_id);
+CREATE INDEX idx_allocation_results_school_id ON allocation_results(school_id);
+
+-- Create triggers for updated_at
+CREATE TRIGGER update_allocation_rounds_updated_at BEFORE UPDATE ON allocation_rounds
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_allocation_choices_updated_at BEFORE UPDATE ON allocation_choices
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,36 @@
+-- Create regions table
+-- Education office (dinas pendidikan) hierarchy above schools: province > regency/city
+CREATE TABLE regions (
+    id SERIAL PRIMARY KEY,
+    parent_id INTEGER REFERENCES regions(id) ON DELETE RESTRICT,
+    level VARCHAR(20) NOT NULL CHECK (level IN ('province', 'regency')),
+    code VARCHAR(20) UNIQUE NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT region_parent_level CHECK (
+        (level = 'province' AND parent_id IS NULL) OR
+        (level = 'regency' AND parent_id IS NOT NULL)
+    )
+);
+
+-- Create indexes
+CREATE INDEX idx_regions_parent_id ON regions(parent_id);
+CREATE INDEX idx_regions_level ON regions(level);
+
+-- Create trigger for updated_at
+CREATE TRIGGER update_regions_updated_at BEFORE UPDATE ON regions
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Schools belong to a region
+ALTER TABLE schools ADD COLUMN region_id INTEGER REFERENCES regions(id);
+CREATE INDEX idx_schools_region_id ON schools(region_id);
+
+-- Region admins are scoped to a region (and its sub-regions)
+ALTER TABLE users ADD COLUMN region_id INTEGER REFERENCES regions(id);
+CREATE INDEX idx_users_region_id ON users(region_id);
+
+ALTER TABLE users DROP CONSTRAINT users_role_check;
+ALTER TABLE users ADD CONSTRAINT users_role_check
+    CHECK (role IN ('super_admin', 'region_admin', 'school_admin', 'parent'));
+
@@ -0,0 +1,39 @@
+-- How cross-school duplicates are handled for registrations in a period
+ALTER TABLE periods ADD COLUMN duplicate_policy VARCHAR(10) NOT NULL DEFAULT 'warn'
+    CHECK (duplicate_policy IN ('block', 'warn', 'allow'));
+
+-- Registrations withdrawn because the student enrolled at another school
+ALTER TABLE registrations DROP CONSTRAINT registrations_status_check;
+ALTER TABLE registrations ADD CONSTRAINT registrations_status_check
+    CHECK (status IN (
+        'draft', 'submitted', 'verified', 'rejected',
+        'accepted', 'enrolled', 'expired', 'withdrawn'
+    ));
+
+-- Create duplicate_flags table
+-- Review queue of registrations that share a student NISN or parent NIK
+-- with another active registration in the same academic year.
+CREATE TABLE duplicate_flags (
+    id SERIAL PRIMARY KEY,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    matched_registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    match_type VARCHAR(20) NOT NULL CHECK (match_type IN ('nisn', 'parent_nik')),
+    status VARCHAR(20) NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'dismissed', 'confirmed')),
+    notes TEXT,
+    reviewed_by INTEGER REFERENCES users(id),
+    reviewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_duplicate_flag UNIQUE (registration_id, matched_registration_id, match_type)
+);
+
+-- Create indexes
+CREATE INDEX idx_duplicate_flags_registration_id ON duplicate_flags(registration_id);
+CREATE INDEX idx_duplicate_flags_matched_registration_id ON duplicate_flags(matched_registration_id);
+CREATE INDEX idx_duplicate_flags_status ON duplicate_flags(status);
+CREATE INDEX idx_registrations_student_nisn ON registrations(student_nisn);
+CREATE INDEX idx_registrations_parent_nik ON registrations(parent_nik);
+
+-- Create trigger for updated_at
+CREATE TRIGGER update_duplicate_flags_updated_at BEFORE UPDATE ON duplicate_flags
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
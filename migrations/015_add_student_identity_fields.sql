@@ -0,0 +1,4 @@
+-- Student NIK and the region code of the student's address, cross-checked
+-- against the data encoded in the NIK
+ALTER TABLE registrations ADD COLUMN student_nik VARCHAR(16);
+ALTER TABLE registrations ADD COLUMN student_region_code VARCHAR(20);
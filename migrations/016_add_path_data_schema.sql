@@ -0,0 +1,3 @@
+-- Schema (JSON Schema subset) describing the path_data applicants submit for
+-- a registration path. NULL falls back to the built-in schema of the path type.
+ALTER TABLE registration_paths ADD COLUMN path_data_schema JSONB;
@@ -0,0 +1,26 @@
+-- Create registration_grades table
+-- Per-semester, per-subject rapor grades entered for a registration
+CREATE TABLE registration_grades (
+    id SERIAL PRIMARY KEY,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    semester INTEGER NOT NULL CHECK (semester BETWEEN 1 AND 12),
+    subject VARCHAR(50) NOT NULL,
+    score DOUBLE PRECISION NOT NULL CHECK (score >= 0 AND score <= 100),
+    -- Set when an admin checked the semester against the uploaded rapor document
+    document_id INTEGER REFERENCES documents(id) ON DELETE SET NULL,
+    verified_by INTEGER REFERENCES users(id),
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_registration_grade UNIQUE (registration_id, semester, subject)
+);
+
+-- Create indexes
+CREATE INDEX idx_registration_grades_registration_id ON registration_grades(registration_id);
+
+-- Create trigger for updated_at
+CREATE TRIGGER update_registration_grades_updated_at BEFORE UPDATE ON registration_grades
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Rapor average computed by the server from the grades above
+ALTER TABLE registrations ADD COLUMN rapor_average DOUBLE PRECISION;
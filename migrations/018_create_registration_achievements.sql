@@ -0,0 +1,33 @@
+-- Create registration_achievements table
+-- Competition achievements entered for a registration, each verified separately
+CREATE TABLE registration_achievements (
+    id SERIAL PRIMARY KEY,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    competition_name VARCHAR(255) NOT NULL,
+    level VARCHAR(20) NOT NULL CHECK (level IN ('school', 'regency', 'province', 'national', 'international')),
+    -- NULL for participants/finalists without a rank
+    rank INTEGER CHECK (rank >= 1),
+    year INTEGER NOT NULL,
+    participation VARCHAR(20) NOT NULL DEFAULT 'individual' CHECK (participation IN ('individual', 'team')),
+    -- The sertifikat_prestasi document proving the achievement
+    document_id INTEGER REFERENCES documents(id) ON DELETE SET NULL,
+    verification_status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (verification_status IN ('pending', 'approved', 'rejected')),
+    verification_notes TEXT,
+    verified_by INTEGER REFERENCES users(id),
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create indexes
+CREATE INDEX idx_registration_achievements_registration_id ON registration_achievements(registration_id);
+
+-- Create trigger for updated_at
+CREATE TRIGGER update_registration_achievements_updated_at BEFORE UPDATE ON registration_achievements
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Point table converting achievements into points, NULL uses the default table
+ALTER TABLE periods ADD COLUMN achievement_point_table JSONB;
+
+-- Points of the approved achievements computed with the period point table
+ALTER TABLE registrations ADD COLUMN achievement_points DOUBLE PRECISION;
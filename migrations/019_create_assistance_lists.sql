@@ -0,0 +1,59 @@
+-- Create assistance_lists table
+-- Imported social-assistance beneficiary lists (DTKS/PKH/KIP), owned by a school or a region
+CREATE TABLE assistance_lists (
+    id SERIAL PRIMARY KEY,
+    program VARCHAR(10) NOT NULL CHECK (program IN ('dtks', 'pkh', 'kip')),
+    school_id INTEGER REFERENCES schools(id) ON DELETE CASCADE,
+    region_id INTEGER REFERENCES regions(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    row_count INTEGER NOT NULL DEFAULT 0,
+    imported_by INTEGER REFERENCES users(id),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT assistance_list_owner CHECK ((school_id IS NULL) <> (region_id IS NULL))
+);
+
+-- Create assistance_beneficiaries table
+CREATE TABLE assistance_beneficiaries (
+    id SERIAL PRIMARY KEY,
+    list_id INTEGER NOT NULL REFERENCES assistance_lists(id) ON DELETE CASCADE,
+    nik VARCHAR(16),
+    kip_number VARCHAR(30),
+    name VARCHAR(255),
+    CONSTRAINT assistance_beneficiary_identifier CHECK (nik IS NOT NULL OR kip_number IS NOT NULL)
+);
+
+-- Create assistance_matches table
+-- Registrations matched to a beneficiary, confirmed or rejected by a verifier
+CREATE TABLE assistance_matches (
+    id SERIAL PRIMARY KEY,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    beneficiary_id INTEGER NOT NULL REFERENCES assistance_beneficiaries(id) ON DELETE CASCADE,
+    program VARCHAR(10) NOT NULL,
+    matched_on VARCHAR(20) NOT NULL CHECK (matched_on IN ('student_nik', 'parent_nik', 'kip_number')),
+    status VARCHAR(20) NOT NULL DEFAULT 'matched' CHECK (status IN ('matched', 'verified', 'rejected')),
+    notes TEXT,
+    verified_by INTEGER REFERENCES users(id),
+    verified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_assistance_match UNIQUE (registration_id, beneficiary_id)
+);
+
+-- Create indexes
+CREATE INDEX idx_assistance_lists_school_id ON assistance_lists(school_id);
+CREATE INDEX idx_assistance_lists_region_id ON assistance_lists(region_id);
+CREATE INDEX idx_assistance_beneficiaries_list_id ON assistance_beneficiaries(list_id);
+CREATE INDEX idx_assistance_beneficiaries_nik ON assistance_beneficiaries(nik);
+CREATE INDEX idx_assistance_beneficiaries_kip_number ON assistance_beneficiaries(kip_number);
+CREATE INDEX idx_assistance_matches_registration_id ON assistance_matches(registration_id);
+
+-- Create triggers for updated_at
+CREATE TRIGGER update_assistance_lists_updated_at BEFORE UPDATE ON assistance_lists
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_assistance_matches_updated_at BEFORE UPDATE ON assistance_matches
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Programs with a verified match, the only ones granting afirmasi bonuses
+ALTER TABLE registrations ADD COLUMN verified_assistance_programs TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,3 @@
+-- Age requirements of a period: reference date, minimum/maximum age and an
+-- optional age-based priority or score. NULL uses the defaults of the period level.
+ALTER TABLE periods ADD COLUMN age_rules JSONB;
@@ -0,0 +1,60 @@
+-- Create period_majors table
+-- Competency programs (jurusan) of an SMK period, each with its own capacity
+CREATE TABLE period_majors (
+    id SERIAL PRIMARY KEY,
+    period_id INTEGER NOT NULL REFERENCES periods(id) ON DELETE CASCADE,
+    code VARCHAR(20) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    capacity INTEGER NOT NULL CHECK (capacity > 0),
+    description TEXT,
+    -- Extra admission criteria, e.g. a minimum subject grade or a color-blindness check
+    criteria JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_period_major_code UNIQUE (period_id, code)
+);
+
+-- Ranked major choices of a registration
+CREATE TABLE registration_major_choices (
+    id SERIAL PRIMARY KEY,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    major_id INTEGER NOT NULL REFERENCES period_majors(id) ON DELETE CASCADE,
+    priority INTEGER NOT NULL CHECK (priority >= 1),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_registration_major UNIQUE (registration_id, major_id),
+    CONSTRAINT unique_registration_major_priority UNIQUE (registration_id, priority)
+);
+
+-- Results of major requirements checked by verifiers, shared by all majors using the same key
+CREATE TABLE registration_requirement_checks (
+    id SERIAL PRIMARY KEY,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    requirement_key VARCHAR(50) NOT NULL,
+    passed BOOLEAN NOT NULL,
+    notes TEXT,
+    verified_by INTEGER REFERENCES users(id),
+    verified_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_registration_requirement UNIQUE (registration_id, requirement_key)
+);
+
+-- Create indexes
+CREATE INDEX idx_period_majors_period_id ON period_majors(period_id);
+CREATE INDEX idx_registration_major_choices_registration_id ON registration_major_choices(registration_id);
+CREATE INDEX idx_registration_major_choices_major_id ON registration_major_choices(major_id);
+CREATE INDEX idx_registration_requirement_checks_registration_id ON registration_requirement_checks(registration_id);
+
+-- Create triggers for updated_at
+CREATE TRIGGER update_period_majors_updated_at BEFORE UPDATE ON period_majors
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_registration_major_choices_updated_at BEFORE UPDATE ON registration_major_choices
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_registration_requirement_checks_updated_at BEFORE UPDATE ON registration_requirement_checks
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Major the student was accepted into by the selection
+ALTER TABLE registrations ADD COLUMN accepted_major_id INTEGER REFERENCES period_majors(id) ON DELETE SET NULL;
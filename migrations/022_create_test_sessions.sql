@@ -0,0 +1,50 @@
+-- Create test_sessions table
+-- Entrance test or interview slots of a period, optionally limited to one path
+CREATE TABLE test_sessions (
+    id SERIAL PRIMARY KEY,
+    period_id INTEGER NOT NULL REFERENCES periods(id) ON DELETE CASCADE,
+    path_id INTEGER REFERENCES registration_paths(id) ON DELETE CASCADE,
+    test_type VARCHAR(20) NOT NULL CHECK (test_type IN ('written', 'interview')),
+    name VARCHAR(255) NOT NULL,
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ NOT NULL,
+    room VARCHAR(100) NOT NULL,
+    capacity INTEGER NOT NULL CHECK (capacity > 0),
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT test_session_time CHECK (ends_at > starts_at)
+);
+
+-- Create test_bookings table
+-- One booking per registration and test type, with attendance and score once recorded
+CREATE TABLE test_bookings (
+    id SERIAL PRIMARY KEY,
+    session_id INTEGER NOT NULL REFERENCES test_sessions(id) ON DELETE CASCADE,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    test_type VARCHAR(20) NOT NULL,
+    seat_number INTEGER NOT NULL,
+    attended BOOLEAN,
+    score DOUBLE PRECISION CHECK (score >= 0 AND score <= 100),
+    notes TEXT,
+    recorded_by INTEGER REFERENCES users(id),
+    recorded_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_test_booking_type UNIQUE (registration_id, test_type),
+    CONSTRAINT unique_test_booking_seat UNIQUE (session_id, seat_number)
+);
+
+-- Create indexes
+CREATE INDEX idx_test_sessions_period_id ON test_sessions(period_id);
+CREATE INDEX idx_test_bookings_session_id ON test_bookings(session_id);
+CREATE INDEX idx_test_bookings_registration_id ON test_bookings(registration_id);
+
+-- Create triggers for updated_at
+CREATE TRIGGER update_test_sessions_updated_at BEFORE UPDATE ON test_sessions
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TRIGGER update_test_bookings_updated_at BEFORE UPDATE ON test_bookings
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Average score of the attended tests, used by the scoring engine
+ALTER TABLE registrations ADD COLUMN test_score DOUBLE PRECISION;
@@ -0,0 +1,15 @@
+-- Automated period lifecycle: periods created from now on are activated at
+-- registration_start and announced at announcement_at by the scheduler.
+-- Existing periods keep being managed manually.
+ALTER TABLE periods ADD COLUMN auto_lifecycle BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE periods ALTER COLUMN auto_lifecycle SET DEFAULT TRUE;
+
+-- Time the results are published automatically
+ALTER TABLE periods ADD COLUMN announcement_at TIMESTAMPTZ;
+
+CREATE INDEX idx_periods_auto_lifecycle ON periods(status) WHERE auto_lifecycle;
+
+-- Admin-granted extension of the registration deadline for a single registration
+ALTER TABLE registrations ADD COLUMN deadline_extended_until TIMESTAMPTZ;
+ALTER TABLE registrations ADD COLUMN deadline_extended_by INTEGER REFERENCES users(id);
+ALTER TABLE registrations ADD COLUMN deadline_extension_reason TEXT;
@@ -0,0 +1,20 @@
+-- IANA timezone of each school; deadlines are entered in school-local time
+ALTER TABLE schools ADD COLUMN timezone VARCHAR(64) NOT NULL DEFAULT 'Asia/Jakarta';
+
+-- Period deadlines as precise instants: the registration window opens at the
+-- start of registration_start and closes at closing_time on registration_end,
+-- the re-enrollment deadline closes at closing_time as well (school-local)
+ALTER TABLE periods ADD COLUMN closing_time TIME NOT NULL DEFAULT '23:59:59';
+ALTER TABLE periods ADD COLUMN registration_opens_at TIMESTAMPTZ;
+ALTER TABLE periods ADD COLUMN registration_closes_at TIMESTAMPTZ;
+ALTER TABLE periods ADD COLUMN reenrollment_closes_at TIMESTAMPTZ;
+
+UPDATE periods p
+SET registration_opens_at = p.registration_start::TIMESTAMP AT TIME ZONE s.timezone,
+    registration_closes_at = (p.registration_end + p.closing_time) AT TIME ZONE s.timezone,
+    reenrollment_closes_at = (p.reenrollment_deadline + p.closing_time) AT TIME ZONE s.timezone
+FROM schools s
+WHERE s.id = p.school_id;
+
+ALTER TABLE periods ALTER COLUMN registration_opens_at SET NOT NULL;
+ALTER TABLE periods ALTER COLUMN registration_closes_at SET NOT NULL;
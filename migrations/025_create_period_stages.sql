@@ -0,0 +1,29 @@
+-- Create period_stages table
+-- Ordered registration rounds (tahap) of a period, each with its own window
+-- and announcement. Deadlines are resolved in the school's timezone.
+CREATE TABLE period_stages (
+    id SERIAL PRIMARY KEY,
+    period_id INTEGER NOT NULL REFERENCES periods(id) ON DELETE CASCADE,
+    stage_number INTEGER NOT NULL CHECK (stage_number > 0),
+    name VARCHAR(255) NOT NULL,
+    registration_start DATE NOT NULL,
+    registration_end DATE NOT NULL,
+    registration_opens_at TIMESTAMPTZ NOT NULL,
+    registration_closes_at TIMESTAMPTZ NOT NULL,
+    announcement_at TIMESTAMPTZ,
+    announcement_date DATE,
+    created_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT unique_period_stage_number UNIQUE (period_id, stage_number),
+    CONSTRAINT period_stage_window CHECK (registration_end >= registration_start)
+);
+
+CREATE INDEX idx_period_stages_period_id ON period_stages(period_id);
+
+CREATE TRIGGER update_period_stages_updated_at BEFORE UPDATE ON period_stages
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Paths run in exactly one stage; paths without a stage follow the period window
+ALTER TABLE registration_paths ADD COLUMN stage_id INTEGER REFERENCES period_stages(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_registration_paths_stage_id ON registration_paths(stage_id);
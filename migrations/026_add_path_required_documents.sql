@@ -0,0 +1,5 @@
+-- Document types applicants of a registration path must upload (kartu_keluarga, akta_kelahiran, ...)
+ALTER TABLE registration_paths ADD COLUMN required_documents TEXT[] NOT NULL DEFAULT '{}';
+
+-- Period a draft was cloned from, to carry settings into a new academic year
+ALTER TABLE periods ADD COLUMN cloned_from_id INTEGER REFERENCES periods(id) ON DELETE SET NULL;
@@ -0,0 +1,28 @@
+-- Registrations sent back to the parent for corrections ("perlu perbaikan")
+ALTER TABLE registrations DROP CONSTRAINT registrations_status_check;
+ALTER TABLE registrations ADD CONSTRAINT registrations_status_check
+    CHECK (status IN (
+        'draft', 'submitted', 'revision_requested', 'verified', 'rejected',
+        'accepted', 'enrolled', 'expired', 'withdrawn'
+    ));
+
+-- Revision requests: the flagged fields and documents with the verifier's notes,
+-- the flagged values at request time and what the parent changed on resubmission
+CREATE TABLE registration_revisions (
+    id SERIAL PRIMARY KEY,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    requested_by INTEGER NOT NULL REFERENCES users(id),
+    notes TEXT,
+    fields JSONB NOT NULL DEFAULT '[]',
+    documents JSONB NOT NULL DEFAULT '[]',
+    snapshot JSONB NOT NULL DEFAULT '{}',
+    changes JSONB,
+    resubmitted_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_registration_revisions_registration_id ON registration_revisions(registration_id);
+
+-- At most one open revision request per registration
+CREATE UNIQUE INDEX idx_registration_revisions_open
+    ON registration_revisions(registration_id) WHERE resubmitted_at IS NULL;
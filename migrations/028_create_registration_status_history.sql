@@ -0,0 +1,21 @@
+-- What happens when a parent withdraws an accepted registration: whether the
+-- freed seat goes to the next applicant on the waitlist, and whether payments
+-- already made are refunded
+ALTER TABLE periods ADD COLUMN waitlist_policy VARCHAR(10) NOT NULL DEFAULT 'none'
+    CHECK (waitlist_policy IN ('none', 'promote'));
+ALTER TABLE periods ADD COLUMN refund_policy VARCHAR(20) NOT NULL DEFAULT 'none'
+    CHECK (refund_policy IN ('none', 'full', 'before_announcement'));
+
+-- Status changes of registrations with who made them and why
+CREATE TABLE registration_status_history (
+    id SERIAL PRIMARY KEY,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    from_status VARCHAR(20) NOT NULL,
+    to_status VARCHAR(20) NOT NULL,
+    reason TEXT,
+    changed_by INTEGER REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_registration_status_history_registration_id
+    ON registration_status_history(registration_id);
@@ -0,0 +1,62 @@
+-- Days after the announcement during which parents may appeal (sanggahan),
+-- 0 disables appeals for the period
+ALTER TABLE periods ADD COLUMN appeal_window_days INTEGER NOT NULL DEFAULT 3
+    CHECK (appeal_window_days >= 0);
+
+-- Create appeals table
+-- Appeals against an announced result, e.g. a wrongly computed score or distance
+CREATE TABLE appeals (
+    id SERIAL PRIMARY KEY,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    filed_by INTEGER NOT NULL REFERENCES users(id),
+    category VARCHAR(20) NOT NULL CHECK (category IN ('score', 'distance', 'other')),
+    reason TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'submitted' CHECK (status IN (
+        'submitted', 'in_review', 'upheld', 'dismissed'
+    )),
+    response TEXT,
+    score_before DOUBLE PRECISION,
+    score_after DOUBLE PRECISION,
+    resolved_by INTEGER REFERENCES users(id),
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Evidence uploaded with an appeal
+CREATE TABLE appeal_documents (
+    id SERIAL PRIMARY KEY,
+    appeal_id INTEGER NOT NULL REFERENCES appeals(id) ON DELETE CASCADE,
+    file_url TEXT NOT NULL,
+    file_name VARCHAR(255) NOT NULL,
+    file_size BIGINT NOT NULL,
+    mime_type VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Every action taken on an appeal
+CREATE TABLE appeal_events (
+    id SERIAL PRIMARY KEY,
+    appeal_id INTEGER NOT NULL REFERENCES appeals(id) ON DELETE CASCADE,
+    action VARCHAR(20) NOT NULL CHECK (action IN (
+        'filed', 'triaged', 'rescored', 'upheld', 'dismissed'
+    )),
+    notes TEXT,
+    details JSONB,
+    actor_id INTEGER REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create indexes
+CREATE INDEX idx_appeals_registration_id ON appeals(registration_id);
+CREATE INDEX idx_appeals_status ON appeals(status);
+CREATE INDEX idx_appeal_documents_appeal_id ON appeal_documents(appeal_id);
+CREATE INDEX idx_appeal_events_appeal_id ON appeal_events(appeal_id);
+
+-- At most one unresolved appeal per registration
+CREATE UNIQUE INDEX idx_appeals_open
+    ON appeals(registration_id) WHERE status IN ('submitted', 'in_review');
+
+-- Create trigger for updated_at
+CREATE TRIGGER update_appeals_updated_at BEFORE UPDATE ON appeals
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,29 @@
+-- When a registration was (re)submitted, the age of the verification backlog.
+-- Older submissions never recorded it.
+UPDATE registrations SET submitted_at = updated_at WHERE submitted_at IS NULL AND status <> 'draft';
+
+-- Verifier a submitted registration was assigned to by an admin
+ALTER TABLE registrations ADD COLUMN assigned_verifier_id INTEGER REFERENCES users(id);
+ALTER TABLE registrations ADD COLUMN assigned_at TIMESTAMPTZ;
+
+-- Time-limited claims of registrations by verifiers. A claim is active until
+-- it is released or expires; completed claims feed the throughput metrics.
+CREATE TABLE verification_locks (
+    id SERIAL PRIMARY KEY,
+    registration_id INTEGER NOT NULL REFERENCES registrations(id) ON DELETE CASCADE,
+    verifier_id INTEGER NOT NULL REFERENCES users(id),
+    locked_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMPTZ NOT NULL,
+    released_at TIMESTAMPTZ,
+    release_reason VARCHAR(20) CHECK (release_reason IN ('completed', 'released', 'expired')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Create indexes
+CREATE INDEX idx_registrations_assigned_verifier_id ON registrations(assigned_verifier_id);
+CREATE INDEX idx_verification_locks_registration_id ON verification_locks(registration_id);
+CREATE INDEX idx_verification_locks_verifier_id ON verification_locks(verifier_id);
+
+-- At most one unreleased claim per registration
+CREATE UNIQUE INDEX idx_verification_locks_active
+    ON verification_locks(registration_id) WHERE released_at IS NULL;
@@ -0,0 +1,4 @@
+-- What happens to a submitted registration when a verifier rejects one of
+-- its path's required documents: send it back for revision or reject it
+ALTER TABLE periods ADD COLUMN document_rejection_policy VARCHAR(20) NOT NULL DEFAULT 'revision'
+    CHECK (document_rejection_policy IN ('revision', 'reject'));
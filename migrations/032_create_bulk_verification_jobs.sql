@@ -0,0 +1,49 @@
+-- Audit revision requests alongside verifications and rejections
+ALTER TABLE audit_logs DROP CONSTRAINT audit_logs_action_check;
+ALTER TABLE audit_logs ADD CONSTRAINT audit_logs_action_check CHECK (action IN (
+    'create', 'update', 'delete', 'login', 'logout',
+    'verify', 'reject', 'approve', 'payment', 'request_revision'
+));
+
+-- Create bulk_verification_jobs table
+-- One verification action applied to a set of registrations or documents
+CREATE TABLE bulk_verification_jobs (
+    id SERIAL PRIMARY KEY,
+    school_id INTEGER NOT NULL REFERENCES schools(id) ON DELETE CASCADE,
+    requested_by INTEGER NOT NULL REFERENCES users(id),
+    target VARCHAR(20) NOT NULL CHECK (target IN ('registration', 'document')),
+    action VARCHAR(20) NOT NULL CHECK (action IN (
+        'verify', 'reject', 'request_revision', 'approve'
+    )),
+    notes TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN (
+        'pending', 'running', 'completed', 'failed'
+    )),
+    total INTEGER NOT NULL DEFAULT 0,
+    succeeded INTEGER NOT NULL DEFAULT 0,
+    failed INTEGER NOT NULL DEFAULT 0,
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Outcome of the action on each registration or document of a job
+CREATE TABLE bulk_verification_items (
+    id SERIAL PRIMARY KEY,
+    job_id INTEGER NOT NULL REFERENCES bulk_verification_jobs(id) ON DELETE CASCADE,
+    entity_id INTEGER NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN (
+        'pending', 'succeeded', 'failed'
+    )),
+    error TEXT,
+    processed_at TIMESTAMPTZ
+);
+
+-- Create indexes
+CREATE INDEX idx_bulk_verification_jobs_school_id ON bulk_verification_jobs(school_id);
+CREATE INDEX idx_bulk_verification_items_job_id ON bulk_verification_items(job_id);
+
+-- Create trigger for updated_at
+CREATE TRIGGER update_bulk_verification_jobs_updated_at BEFORE UPDATE ON bulk_verification_jobs
+    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
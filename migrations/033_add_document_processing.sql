@@ -0,0 +1,34 @@
+-- Document processing pipeline: metadata stripping, thumbnails and previews
+-- for images, page count and first page size for PDFs
+ALTER TABLE documents
+    ADD COLUMN processing_status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (processing_status IN (
+        'pending', 'processed', 'failed', 'skipped'
+    )),
+    ADD COLUMN processing_error TEXT,
+    ADD COLUMN processed_at TIMESTAMPTZ,
+    -- Size as uploaded, when an oversized photo was recompressed
+    ADD COLUMN original_file_size BIGINT,
+    ADD COLUMN page_count INTEGER,
+    -- Image dimensions, or the rendered size of a PDF's first page
+    ADD COLUMN processing_metadata JSONB;
+
+-- Documents uploaded before the pipeline existed are not processed
+UPDATE documents SET processing_status = 'skipped';
+
+-- Files derived from a document, stored next to the original
+CREATE TABLE document_artifacts (
+    id SERIAL PRIMARY KEY,
+    document_id INTEGER NOT NULL REFERENCES documents(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL CHECK (kind IN ('thumbnail', 'preview')),
+    file_url TEXT NOT NULL,
+    file_size BIGINT NOT NULL,
+    mime_type VARCHAR(100) NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (document_id, kind)
+);
+
+-- Create indexes
+CREATE INDEX idx_document_artifacts_document_id ON document_artifacts(document_id);
+CREATE INDEX idx_documents_processing_pending ON documents(created_at) WHERE processing_status = 'pending';
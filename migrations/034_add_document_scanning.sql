@@ -0,0 +1,17 @@
+-- Malware scanning: documents stay quarantined until scanned, infected
+-- files are rejected
+ALTER TABLE documents
+    ADD COLUMN scan_status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (scan_status IN (
+        'pending', 'clean', 'infected', 'skipped'
+    )),
+    -- Signature reported by the scanner for infected files
+    ADD COLUMN scan_signature VARCHAR(255),
+    -- Last scanner failure while the document is still quarantined
+    ADD COLUMN scan_error TEXT,
+    ADD COLUMN scanned_at TIMESTAMPTZ;
+
+-- Documents uploaded before scanning existed stay pending and are scanned by
+-- the scheduler
+
+-- Create indexes
+CREATE INDEX idx_documents_scan_pending ON documents(created_at) WHERE scan_status = 'pending';
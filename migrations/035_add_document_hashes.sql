@@ -0,0 +1,22 @@
+-- Content hashes of uploaded documents, to find the same scan reused by
+-- other registrations
+ALTER TABLE documents
+    -- SHA-256 of the file as uploaded
+    ADD COLUMN content_hash VARCHAR(64),
+    -- 64-bit difference hash of images, close for rescaled or recompressed copies
+    ADD COLUMN perceptual_hash BIGINT;
+
+-- Documents matched by hash are flagged in the duplicate review queue
+ALTER TABLE duplicate_flags DROP CONSTRAINT duplicate_flags_match_type_check;
+ALTER TABLE duplicate_flags ADD CONSTRAINT duplicate_flags_match_type_check
+    CHECK (match_type IN ('nisn', 'parent_nik', 'document_content', 'document_image'));
+
+ALTER TABLE duplicate_flags
+    ADD COLUMN document_id INTEGER REFERENCES documents(id) ON DELETE CASCADE,
+    ADD COLUMN matched_document_id INTEGER REFERENCES documents(id) ON DELETE CASCADE;
+
+-- Create indexes
+CREATE INDEX idx_documents_content_hash ON documents(content_hash);
+CREATE INDEX idx_documents_perceptual_hash ON documents(perceptual_hash) WHERE perceptual_hash IS NOT NULL;
+CREATE INDEX idx_duplicate_flags_document_id ON duplicate_flags(document_id);
+CREATE INDEX idx_duplicate_flags_matched_document_id ON duplicate_flags(matched_document_id);
@@ -0,0 +1,16 @@
+-- Retries of unfinished documents back off and stop after a few attempts, so
+-- files that keep failing do not hold up newer uploads
+ALTER TABLE documents
+    -- Pipeline runs that left the scan or processing unfinished
+    ADD COLUMN processing_attempts INTEGER NOT NULL DEFAULT 0,
+    -- Earliest time the scheduler retries the document
+    ADD COLUMN next_attempt_at TIMESTAMPTZ;
+
+-- Documents that could not be scanned after the last attempt stay quarantined
+ALTER TABLE documents DROP CONSTRAINT documents_scan_status_check;
+ALTER TABLE documents ADD CONSTRAINT documents_scan_status_check
+    CHECK (scan_status IN ('pending', 'clean', 'infected', 'skipped', 'failed'));
+
+-- Create indexes
+CREATE INDEX idx_documents_next_attempt_at ON documents(next_attempt_at)
+    WHERE scan_status = 'pending' OR processing_status = 'pending';
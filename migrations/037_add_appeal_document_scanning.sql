@@ -0,0 +1,20 @@
+-- Appeal evidence is scanned for malware like registration documents and
+-- stays hidden until it is found clean
+ALTER TABLE appeal_documents
+    ADD COLUMN scan_status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (scan_status IN (
+        'pending', 'clean', 'infected', 'skipped', 'failed'
+    )),
+    -- Signature reported by the scanner for infected files
+    ADD COLUMN scan_signature VARCHAR(255),
+    -- Last scanner failure while the file is still quarantined
+    ADD COLUMN scan_error TEXT,
+    ADD COLUMN scanned_at TIMESTAMPTZ,
+    -- Scans that failed, and the earliest time the scheduler retries
+    ADD COLUMN scan_attempts INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN next_scan_at TIMESTAMPTZ;
+
+-- Evidence filed before scanning existed stays pending and is scanned by the
+-- scheduler
+
+-- Create indexes
+CREATE INDEX idx_appeal_documents_scan_pending ON appeal_documents(created_at) WHERE scan_status = 'pending';
@@ -0,0 +1,9 @@
+-- Bulk jobs are leased by the worker running them. A job whose lease ran out,
+-- for example because the server restarted, is picked up again by the
+-- scheduler and resumes from its pending items.
+ALTER TABLE bulk_verification_jobs
+    ADD COLUMN lease_expires_at TIMESTAMPTZ;
+
+-- Create indexes
+CREATE INDEX idx_bulk_verification_jobs_unfinished ON bulk_verification_jobs(created_at)
+    WHERE status IN ('pending', 'running');
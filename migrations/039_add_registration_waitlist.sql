@@ -0,0 +1,12 @@
+-- Applicants rejected only because the quota was full wait for seats freed
+-- by withdrawals, independently of the wording of their rejection reason
+ALTER TABLE registrations
+    ADD COLUMN waitlisted BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE registrations SET waitlisted = TRUE
+WHERE status = 'rejected'
+  AND rejection_reason = 'Quota penuh. Anda berada di luar kuota yang tersedia.';
+
+-- Create indexes
+CREATE INDEX idx_registrations_waitlisted ON registrations(period_id)
+    WHERE waitlisted;
@@ -0,0 +1,746 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    routing::{get, post, put},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::middleware::auth::{auth_middleware, AuthUser};
+use crate::api::middleware::rbac::require_super_admin;
+use crate::models::allocation::{AllocationChoice, AllocationResult, AllocationRound};
+use crate::repositories::allocation_repo::AllocationRepository;
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::repositories::school_repo::SchoolRepository;
+use crate::services::allocation_service::{AllocationService, AllocationSummary, ChoiceExplanation};
+use crate::utils::error::{AppError, AppResult};
+use crate::AppState;
+
+pub fn routes(state: AppState) -> Router<AppState> {
+    // Dinas routes (super admin only)
+    let admin_routes = Router::new()
+        .route("/", get(list_rounds).post(create_round))
+        .route("/:id/periods", put(update_round_periods))
+        .route("/:id/open", post(open_round))
+        .route("/:id/close", post(close_round))
+        .route("/:id/run", post(run_allocation))
+        .route("/:id/results", get(list_results))
+        .route_layer(middleware::from_fn(require_super_admin))
+        .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
+
+    // Routes for every authenticated user (parents submit choices and read their result)
+    let user_routes = Router::new()
+        .route("/open", get(list_open_rounds))
+        .route("/:id", get(get_round))
+        .route("/:id/choices", get(get_choices).put(submit_choices))
+        .route("/:id/results/:student_nisn", get(get_student_result))
+        .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
+
+    admin_routes.merge(user_routes)
+}
+
+fn allocation_service(state: &AppState) -> AllocationService {
+    AllocationService::new(
+        AllocationRepository::new(state.db.clone()),
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+        SchoolRepository::new(state.db.clone()),
+    )
+}
+
+/// Request untuk membuat putaran alokasi terpusat
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateAllocationRoundRequest {
+    /// Nama putaran alokasi
+    #[schema(example = "PPDB SMP Kota Bandung 2024")]
+    name: String,
+
+    /// Tahun ajaran
+    #[schema(example = "2024/2025")]
+    academic_year: String,
+
+    /// Jenjang pendidikan (SD/SMP/SMA/SMK)
+    #[schema(example = "SMP")]
+    level: String,
+
+    /// Jumlah maksimal pilihan sekolah per siswa
+    #[serde(default = "default_choice_limit")]
+    #[schema(example = 3)]
+    choice_limit: i32,
+
+    /// ID periode sekolah yang ikut dalam putaran ini
+    #[schema(example = json!([1, 2, 3]))]
+    period_ids: Vec<i32>,
+}
+
+fn default_choice_limit() -> i32 {
+    3
+}
+
+/// Request untuk mengganti periode peserta putaran alokasi
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateRoundPeriodsRequest {
+    /// ID periode sekolah yang ikut dalam putaran ini
+    #[schema(example = json!([1, 2, 3]))]
+    period_ids: Vec<i32>,
+}
+
+/// Request pilihan sekolah berurutan
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SubmitChoicesRequest {
+    /// NISN siswa
+    #[schema(example = "0012345678")]
+    student_nisn: String,
+
+    /// ID pendaftaran di tiap sekolah, urut dari pilihan pertama
+    #[schema(example = json!([12, 7, 30]))]
+    registration_ids: Vec<i32>,
+}
+
+/// Query untuk pilihan sekolah siswa
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ChoicesQuery {
+    /// NISN siswa
+    #[schema(example = "0012345678")]
+    student_nisn: String,
+}
+
+/// Query untuk list hasil alokasi
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListAllocationResultsQuery {
+    /// Filter status (placed/unplaced)
+    #[schema(example = "placed")]
+    status: Option<String>,
+
+    /// Filter sekolah tempat diterima
+    #[schema(example = 1)]
+    school_id: Option<i32>,
+
+    /// Nomor halaman
+    #[serde(default = "default_page")]
+    #[schema(example = 1)]
+    page: i64,
+
+    /// Jumlah item per halaman
+    #[serde(default = "default_page_size")]
+    #[schema(example = 50)]
+    page_size: i64,
+}
+
+fn default_page() -> i64 {
+    1
+}
+
+fn default_page_size() -> i64 {
+    50
+}
+
+/// Response data putaran alokasi
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AllocationRoundResponse {
+    /// ID putaran
+    #[schema(example = 1)]
+    id: i32,
+
+    /// Nama putaran
+    #[schema(example = "PPDB SMP Kota Bandung 2024")]
+    name: String,
+
+    /// Tahun ajaran
+    #[schema(example = "2024/2025")]
+    academic_year: String,
+
+    /// Jenjang pendidikan
+    #[schema(example = "SMP")]
+    level: String,
+
+    /// Jumlah maksimal pilihan sekolah
+    #[schema(example = 3)]
+    choice_limit: i32,
+
+    /// Status (draft/open/closed/allocated)
+    #[schema(example = "open")]
+    status: String,
+
+    /// Waktu alokasi dijalankan
+    #[schema(value_type = Option<String>, example = "2024-07-01T10:00:00Z")]
+    allocated_at: Option<DateTime<Utc>>,
+
+    /// ID periode peserta
+    #[schema(example = json!([1, 2, 3]))]
+    period_ids: Vec<i32>,
+
+    /// Waktu dibuat
+    #[schema(value_type = String, example = "2024-01-01T00:00:00Z")]
+    created_at: DateTime<Utc>,
+}
+
+impl AllocationRoundResponse {
+    fn new(round: AllocationRound, period_ids: Vec<i32>) -> Self {
+        Self {
+            id: round.id,
+            name: round.name,
+            academic_year: round.academic_year,
+            level: round.level,
+            choice_limit: round.choice_limit,
+            status: round.status,
+            allocated_at: round.allocated_at,
+            period_ids,
+            created_at: round.created_at,
+        }
+    }
+}
+
+/// Response pilihan sekolah
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AllocationChoiceResponse {
+    /// Urutan pilihan
+    #[schema(example = 1)]
+    choice_rank: i32,
+
+    /// ID pendaftaran
+    #[schema(example = 12)]
+    registration_id: i32,
+
+    /// NISN siswa
+    #[schema(example = "0012345678")]
+    student_nisn: String,
+}
+
+impl From<AllocationChoice> for AllocationChoiceResponse {
+    fn from(choice: AllocationChoice) -> Self {
+        Self {
+            choice_rank: choice.choice_rank,
+            registration_id: choice.registration_id,
+            student_nisn: choice.student_nisn,
+        }
+    }
+}
+
+/// Response hasil alokasi per siswa
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AllocationResultResponse {
+    /// NISN siswa
+    #[schema(example = "0012345678")]
+    student_nisn: String,
+
+    /// Nama siswa
+    #[schema(example = "Ahmad Fauzi")]
+    student_name: String,
+
+    /// Status (placed/unplaced)
+    #[schema(example = "placed")]
+    status: String,
+
+    /// ID pendaftaran yang diterima
+    #[schema(example = 12)]
+    registration_id: Option<i32>,
+
+    /// ID sekolah tempat diterima
+    #[schema(example = 1)]
+    school_id: Option<i32>,
+
+    /// ID jalur tempat diterima
+    #[schema(example = 2)]
+    path_id: Option<i32>,
+
+    /// Urutan pilihan sekolah yang diterima
+    #[schema(example = 2)]
+    choice_rank: Option<i32>,
+
+    /// Skor di jalur tempat diterima
+    #[schema(example = 82.5)]
+    selection_score: Option<f64>,
+
+    /// Penjelasan hasil alokasi
+    #[schema(example = "Diterima di pilihan ke-2: SMP Negeri 2 - Jalur Zonasi (skor 82.50).")]
+    explanation: String,
+
+    /// Rincian hasil setiap pilihan
+    details: Vec<ChoiceExplanation>,
+}
+
+impl From<AllocationResult> for AllocationResultResponse {
+    fn from(result: AllocationResult) -> Self {
+        Self {
+            student_nisn: result.student_nisn,
+            student_name: result.student_name,
+            status: result.status,
+            registration_id: result.registration_id,
+            school_id: result.school_id,
+            path_id: result.path_id,
+            choice_rank: result.choice_rank,
+            selection_score: result.selection_score,
+            explanation: result.explanation,
+            details: serde_json::from_value(result.details).unwrap_or_default(),
+        }
+    }
+}
+
+/// Response list hasil alokasi dengan pagination
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListAllocationResultsResponse {
+    /// Daftar hasil alokasi
+    results: Vec<AllocationResultResponse>,
+
+    /// Total data
+    #[schema(example = 500)]
+    total: i64,
+
+    /// Halaman saat ini
+    #[schema(example = 1)]
+    page: i64,
+
+    /// Jumlah item per halaman
+    #[schema(example = 50)]
+    page_size: i64,
+
+    /// Total halaman
+    #[schema(example = 10)]
+    total_pages: i64,
+}
+
+/// Response jalankan alokasi
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RunAllocationResponse {
+    /// Pesan hasil
+    #[schema(example = "Allocation completed successfully. 420 placed, 80 unplaced")]
+    message: String,
+
+    /// Ringkasan hasil alokasi
+    result: AllocationSummary,
+}
+
+/// Mendapatkan daftar putaran alokasi
+///
+/// Hanya dapat diakses oleh super admin (dinas).
+#[utoipa::path(
+    get,
+    path = "/api/allocations",
+    tag = "Allocations",
+    responses(
+        (status = 200, description = "Daftar putaran berhasil diambil", body = Vec<AllocationRoundResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_rounds(State(state): State<AppState>) -> AppResult<Json<Vec<AllocationRoundResponse>>> {
+    let service = allocation_service(&state);
+
+    let rounds = service.list_rounds(None).await?;
+
+    let mut responses = Vec::with_capacity(rounds.len());
+    for round in rounds {
+        let period_ids = service.get_round_period_ids(round.id).await?;
+        responses.push(AllocationRoundResponse::new(round, period_ids));
+    }
+
+    Ok(Json(responses))
+}
+
+/// Mendapatkan daftar putaran alokasi yang sedang dibuka
+///
+/// Digunakan orang tua untuk mengetahui putaran tempat pilihan sekolah dapat diajukan.
+#[utoipa::path(
+    get,
+    path = "/api/allocations/open",
+    tag = "Allocations",
+    responses(
+        (status = 200, description = "Daftar putaran berhasil diambil", body = Vec<AllocationRoundResponse>),
+        (status = 401, description = "Tidak terautentikasi")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_open_rounds(State(state): State<AppState>) -> AppResult<Json<Vec<AllocationRoundResponse>>> {
+    let service = allocation_service(&state);
+
+    let rounds = service.list_rounds(Some("open".to_string())).await?;
+
+    let mut responses = Vec::with_capacity(rounds.len());
+    for round in rounds {
+        let period_ids = service.get_round_period_ids(round.id).await?;
+        responses.push(AllocationRoundResponse::new(round, period_ids));
+    }
+
+    Ok(Json(responses))
+}
+
+/// Membuat putaran alokasi terpusat
+///
+/// Semua periode peserta harus memiliki tahun ajaran dan jenjang yang sama.
+#[utoipa::path(
+    post,
+    path = "/api/allocations",
+    tag = "Allocations",
+    request_body = CreateAllocationRoundRequest,
+    responses(
+        (status = 201, description = "Putaran berhasil dibuat", body = AllocationRoundResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 409, description = "Periode sudah tergabung di putaran lain")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn create_round(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<CreateAllocationRoundRequest>,
+) -> AppResult<(StatusCode, Json<AllocationRoundResponse>)> {
+    let service = allocation_service(&state);
+
+    let period_ids = payload.period_ids.clone();
+    let round = service
+        .create_round(
+            payload.name,
+            payload.academic_year,
+            payload.level,
+            payload.choice_limit,
+            payload.period_ids,
+            auth_user.id,
+        )
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AllocationRoundResponse::new(round, period_ids)),
+    ))
+}
+
+/// Mendapatkan detail putaran alokasi
+#[utoipa::path(
+    get,
+    path = "/api/allocations/{id}",
+    tag = "Allocations",
+    params(
+        ("id" = i32, Path, description = "ID putaran alokasi")
+    ),
+    responses(
+        (status = 200, description = "Putaran berhasil diambil", body = AllocationRoundResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 404, description = "Putaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_round(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<AllocationRoundResponse>> {
+    let service = allocation_service(&state);
+
+    let round = service.get_round(id).await?;
+    let period_ids = service.get_round_period_ids(id).await?;
+
+    Ok(Json(AllocationRoundResponse::new(round, period_ids)))
+}
+
+/// Mengganti periode peserta putaran alokasi (hanya status draft)
+#[utoipa::path(
+    put,
+    path = "/api/allocations/{id}/periods",
+    tag = "Allocations",
+    params(
+        ("id" = i32, Path, description = "ID putaran alokasi")
+    ),
+    request_body = UpdateRoundPeriodsRequest,
+    responses(
+        (status = 200, description = "Periode berhasil diperbarui", body = AllocationRoundResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Putaran tidak ditemukan"),
+        (status = 409, description = "Periode sudah tergabung di putaran lain")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn update_round_periods(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateRoundPeriodsRequest>,
+) -> AppResult<Json<AllocationRoundResponse>> {
+    let service = allocation_service(&state);
+
+    service.update_round_periods(id, payload.period_ids).await?;
+
+    let round = service.get_round(id).await?;
+    let period_ids = service.get_round_period_ids(id).await?;
+
+    Ok(Json(AllocationRoundResponse::new(round, period_ids)))
+}
+
+/// Membuka putaran alokasi untuk pengajuan pilihan sekolah
+#[utoipa::path(
+    post,
+    path = "/api/allocations/{id}/open",
+    tag = "Allocations",
+    params(
+        ("id" = i32, Path, description = "ID putaran alokasi")
+    ),
+    responses(
+        (status = 200, description = "Putaran berhasil dibuka", body = AllocationRoundResponse),
+        (status = 400, description = "Status putaran tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Putaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn open_round(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<AllocationRoundResponse>> {
+    let service = allocation_service(&state);
+
+    let round = service.open_round(id).await?;
+    let period_ids = service.get_round_period_ids(id).await?;
+
+    Ok(Json(AllocationRoundResponse::new(round, period_ids)))
+}
+
+/// Menutup pengajuan pilihan sekolah
+#[utoipa::path(
+    post,
+    path = "/api/allocations/{id}/close",
+    tag = "Allocations",
+    params(
+        ("id" = i32, Path, description = "ID putaran alokasi")
+    ),
+    responses(
+        (status = 200, description = "Putaran berhasil ditutup", body = AllocationRoundResponse),
+        (status = 400, description = "Status putaran tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Putaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn close_round(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<AllocationRoundResponse>> {
+    let service = allocation_service(&state);
+
+    let round = service.close_round(id).await?;
+    let period_ids = service.get_round_period_ids(id).await?;
+
+    Ok(Json(AllocationRoundResponse::new(round, period_ids)))
+}
+
+/// Menjalankan alokasi terpusat
+///
+/// Alokasi deferred acceptance (siswa mengajukan) atas ranking dan kuota jalur setiap sekolah.
+/// Setiap siswa mendapat paling banyak satu kursi. Skor tiap sekolah harus sudah dihitung.
+#[utoipa::path(
+    post,
+    path = "/api/allocations/{id}/run",
+    tag = "Allocations",
+    params(
+        ("id" = i32, Path, description = "ID putaran alokasi")
+    ),
+    responses(
+        (status = 200, description = "Alokasi berhasil dijalankan", body = RunAllocationResponse),
+        (status = 400, description = "Putaran belum ditutup"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Putaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn run_allocation(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<RunAllocationResponse>> {
+    let service = allocation_service(&state);
+
+    let result = service.run_allocation(id, auth_user.id).await?;
+
+    Ok(Json(RunAllocationResponse {
+        message: format!(
+            "Allocation completed successfully. {} placed, {} unplaced",
+            result.placed, result.unplaced
+        ),
+        result,
+    }))
+}
+
+/// Mendapatkan daftar hasil alokasi
+#[utoipa::path(
+    get,
+    path = "/api/allocations/{id}/results",
+    tag = "Allocations",
+    params(
+        ("id" = i32, Path, description = "ID putaran alokasi"),
+        ListAllocationResultsQuery
+    ),
+    responses(
+        (status = 200, description = "Hasil alokasi berhasil diambil", body = ListAllocationResultsResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Putaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_results(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(query): Query<ListAllocationResultsQuery>,
+) -> AppResult<Json<ListAllocationResultsResponse>> {
+    let service = allocation_service(&state);
+
+    let (results, total) = service
+        .list_results(id, query.status, query.school_id, query.page, query.page_size)
+        .await?;
+
+    let total_pages = (total as f64 / query.page_size as f64).ceil() as i64;
+
+    Ok(Json(ListAllocationResultsResponse {
+        results: results.into_iter().map(|r| r.into()).collect(),
+        total,
+        page: query.page,
+        page_size: query.page_size,
+        total_pages,
+    }))
+}
+
+/// Mengajukan pilihan sekolah berurutan
+///
+/// Orang tua memilih pendaftaran yang sudah dibuat di tiap sekolah, urut dari pilihan pertama.
+/// Pengajuan baru menggantikan pilihan sebelumnya.
+#[utoipa::path(
+    put,
+    path = "/api/allocations/{id}/choices",
+    tag = "Allocations",
+    params(
+        ("id" = i32, Path, description = "ID putaran alokasi")
+    ),
+    request_body = SubmitChoicesRequest,
+    responses(
+        (status = 200, description = "Pilihan berhasil disimpan", body = Vec<AllocationChoiceResponse>),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Putaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn submit_choices(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<SubmitChoicesRequest>,
+) -> AppResult<Json<Vec<AllocationChoiceResponse>>> {
+    if auth_user.role != "parent" {
+        return Err(AppError::Forbidden(
+            "Only parents can submit school choices".to_string(),
+        ));
+    }
+
+    let service = allocation_service(&state);
+
+    let choices = service
+        .submit_choices(id, auth_user.id, payload.student_nisn, payload.registration_ids)
+        .await?;
+
+    Ok(Json(choices.into_iter().map(|c| c.into()).collect()))
+}
+
+/// Mendapatkan pilihan sekolah siswa
+#[utoipa::path(
+    get,
+    path = "/api/allocations/{id}/choices",
+    tag = "Allocations",
+    params(
+        ("id" = i32, Path, description = "ID putaran alokasi"),
+        ChoicesQuery
+    ),
+    responses(
+        (status = 200, description = "Pilihan berhasil diambil", body = Vec<AllocationChoiceResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Putaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_choices(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Query(query): Query<ChoicesQuery>,
+) -> AppResult<Json<Vec<AllocationChoiceResponse>>> {
+    let service = allocation_service(&state);
+
+    let choices = service.get_choices(id, &query.student_nisn).await?;
+
+    // Check permission
+    if auth_user.role == "parent" && choices.iter().any(|c| c.user_id != auth_user.id) {
+        return Err(AppError::Forbidden(
+            "You don't have permission to view these choices".to_string(),
+        ));
+    }
+
+    Ok(Json(choices.into_iter().map(|c| c.into()).collect()))
+}
+
+/// Mendapatkan hasil alokasi dan penjelasannya untuk satu siswa
+#[utoipa::path(
+    get,
+    path = "/api/allocations/{id}/results/{student_nisn}",
+    tag = "Allocations",
+    params(
+        ("id" = i32, Path, description = "ID putaran alokasi"),
+        ("student_nisn" = String, Path, description = "NISN siswa")
+    ),
+    responses(
+        (status = 200, description = "Hasil alokasi berhasil diambil", body = AllocationResultResponse),
+        (status = 400, description = "Alokasi belum dijalankan"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Hasil tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_student_result(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((id, student_nisn)): Path<(i32, String)>,
+) -> AppResult<Json<AllocationResultResponse>> {
+    let service = allocation_service(&state);
+
+    let result = service
+        .get_student_result(id, &student_nisn, auth_user.id, &auth_user.role)
+        .await?;
+
+    Ok(Json(result.into()))
+}
@@ -46,6 +46,15 @@ pub struct CheckResultQuery {
     student_nisn: String,
 }
 
+/// Query tahap untuk seleksi dan pengumuman
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StageQuery {
+    /// ID tahap (default: tahap pertama yang belum diumumkan)
+    #[schema(example = 1)]
+    stage_id: Option<i32>,
+}
+
 /// Response jalankan seleksi
 #[derive(Debug, Serialize, ToSchema)]
 pub struct RunSelectionResponse {
@@ -77,7 +86,8 @@ pub struct AnnounceResultsResponse {
     path = "/api/announcements/periods/{period_id}/run-selection",
     tag = "Selection",
     params(
-        ("period_id" = i32, Path, description = "ID periode")
+        ("period_id" = i32, Path, description = "ID periode"),
+        StageQuery
     ),
     responses(
         (status = 200, description = "Seleksi berhasil dijalankan", body = RunSelectionResponse),
@@ -93,6 +103,7 @@ async fn run_selection(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Path(period_id): Path<i32>,
+    Query(query): Query<StageQuery>,
 ) -> AppResult<Json<RunSelectionResponse>> {
 
     // Create announcement service
@@ -102,7 +113,7 @@ async fn run_selection(
 
     // Run selection
     let result = announcement_service
-        .run_selection(period_id, auth_user.id)
+        .run_selection(period_id, query.stage_id, auth_user.id)
         .await?;
 
     Ok(Json(RunSelectionResponse {
@@ -123,7 +134,8 @@ async fn run_selection(
     path = "/api/announcements/periods/{period_id}/announce",
     tag = "Selection",
     params(
-        ("period_id" = i32, Path, description = "ID periode")
+        ("period_id" = i32, Path, description = "ID periode"),
+        StageQuery
     ),
     responses(
         (status = 200, description = "Hasil berhasil diumumkan", body = AnnounceResultsResponse),
@@ -139,6 +151,7 @@ async fn announce_results(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Path(period_id): Path<i32>,
+    Query(query): Query<StageQuery>,
 ) -> AppResult<Json<AnnounceResultsResponse>> {
 
     // Create announcement service
@@ -148,7 +161,7 @@ async fn announce_results(
 
     // Announce results
     let result = announcement_service
-        .announce_results(period_id, auth_user.id)
+        .announce_results(period_id, query.stage_id, Some(auth_user.id))
         .await?;
 
     Ok(Json(AnnounceResultsResponse {
@@ -0,0 +1,669 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::middleware::auth::{auth_middleware, AuthUser};
+use crate::api::middleware::rbac::require_school_admin;
+use crate::api::registrations::document_service;
+use crate::models::appeal::{Appeal, AppealDetail, AppealDocument, AppealEvent, NewAppealDocument};
+use crate::models::registration::Registration;
+use crate::repositories::appeal_repo::AppealRepository;
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::services::appeal_service::AppealService;
+use crate::services::selection_service::SelectionService;
+use crate::utils::error::{AppError, AppResult};
+use crate::AppState;
+
+pub fn routes(state: AppState) -> Router<AppState> {
+    // Admin routes (triage and decisions)
+    let admin_routes = Router::new()
+        .route("/", get(list_appeals))
+        .route("/:id/triage", post(triage_appeal))
+        .route("/:id/rescore", post(rescore_appeal))
+        .route("/:id/resolve", post(resolve_appeal))
+        .route_layer(middleware::from_fn(require_school_admin))
+        .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
+
+    // Routes for every authenticated user (parents file and follow their appeals)
+    let user_routes = Router::new()
+        .route("/:id", get(get_appeal))
+        .route(
+            "/registrations/:registration_id",
+            get(list_registration_appeals).post(file_appeal),
+        )
+        .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
+
+    admin_routes.merge(user_routes)
+}
+
+fn appeal_service(state: &AppState) -> AppealService {
+    AppealService::new(
+        AppealRepository::new(state.db.clone()),
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+        SelectionService::new(
+            RegistrationRepository::new(state.db.clone()),
+            PeriodRepository::new(state.db.clone()),
+        ),
+        document_service(state),
+    )
+}
+
+/// Super admin handles every appeal, school admins only their school's
+fn admin_scope(auth_user: &AuthUser) -> AppResult<Option<i32>> {
+    if auth_user.role == "super_admin" {
+        return Ok(None);
+    }
+
+    auth_user.school_id.map(Some).ok_or_else(|| {
+        AppError::Authentication("User must be associated with a school".to_string())
+    })
+}
+
+/// Parents only reach appeals on their own registrations, school admins those
+/// of their school
+fn check_registration_access(auth_user: &AuthUser, registration: &Registration) -> AppResult<()> {
+    let allowed = match auth_user.role.as_str() {
+        "super_admin" => true,
+        "school_admin" => auth_user.school_id == Some(registration.school_id),
+        _ => registration.user_id == auth_user.id,
+    };
+
+    if !allowed {
+        return Err(AppError::Forbidden(
+            "You don't have permission to access appeals of this registration".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Query untuk daftar sanggahan
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListAppealsQuery {
+    /// Nomor halaman
+    #[serde(default = "default_page")]
+    #[schema(example = 1)]
+    page: i64,
+
+    /// Jumlah item per halaman
+    #[serde(default = "default_page_size")]
+    #[schema(example = 10)]
+    page_size: i64,
+
+    /// Filter berdasarkan periode
+    #[schema(example = 1)]
+    period_id: Option<i32>,
+
+    /// Filter berdasarkan status (submitted/in_review/upheld/dismissed)
+    #[schema(example = "submitted")]
+    status: Option<String>,
+}
+
+fn default_page() -> i64 {
+    1
+}
+
+fn default_page_size() -> i64 {
+    10
+}
+
+/// Dokumen bukti sanggahan
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AppealDocumentRequest {
+    /// URL file yang sudah diupload
+    #[schema(example = "https://storage.example.com/appeals/surat_domisili.pdf")]
+    file_url: String,
+
+    /// Nama file
+    #[schema(example = "surat_domisili.pdf")]
+    file_name: String,
+
+    /// Ukuran file dalam bytes
+    #[schema(example = 512000)]
+    file_size: i64,
+
+    /// MIME type file
+    #[schema(example = "application/pdf")]
+    mime_type: String,
+}
+
+/// Request untuk mengajukan sanggahan
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FileAppealRequest {
+    /// Kategori sanggahan (score/distance/other)
+    #[schema(example = "distance")]
+    category: String,
+
+    /// Alasan sanggahan
+    #[schema(example = "Jarak rumah ke sekolah seharusnya 850 meter, bukan 2,3 km")]
+    reason: String,
+
+    /// Dokumen bukti (opsional, maksimal 5 file)
+    #[serde(default)]
+    documents: Vec<AppealDocumentRequest>,
+}
+
+/// Request untuk meninjau sanggahan
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TriageAppealRequest {
+    /// Catatan peninjau (opsional)
+    #[schema(example = "Koordinat rumah akan dicek ulang")]
+    notes: Option<String>,
+}
+
+/// Request untuk memutuskan sanggahan
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResolveAppealRequest {
+    /// Keputusan (upheld/dismissed)
+    #[schema(example = "upheld")]
+    status: String,
+
+    /// Tanggapan untuk orang tua
+    #[schema(example = "Jarak telah diperbaiki dan skor dihitung ulang")]
+    response: String,
+}
+
+/// Response sanggahan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AppealResponse {
+    /// ID sanggahan
+    #[schema(example = 1)]
+    id: i32,
+
+    /// ID pendaftaran
+    #[schema(example = 1)]
+    registration_id: i32,
+
+    /// ID pengguna yang mengajukan
+    #[schema(example = 5)]
+    filed_by: i32,
+
+    /// Kategori sanggahan (score/distance/other)
+    #[schema(example = "distance")]
+    category: String,
+
+    /// Alasan sanggahan
+    #[schema(example = "Jarak rumah ke sekolah seharusnya 850 meter, bukan 2,3 km")]
+    reason: String,
+
+    /// Status sanggahan (submitted/in_review/upheld/dismissed)
+    #[schema(example = "in_review")]
+    status: String,
+
+    /// Tanggapan admin
+    #[schema(example = "Jarak telah diperbaiki dan skor dihitung ulang")]
+    response: Option<String>,
+
+    /// Skor seleksi sebelum dihitung ulang
+    #[schema(example = 72.5)]
+    score_before: Option<f64>,
+
+    /// Skor seleksi setelah dihitung ulang
+    #[schema(example = 81.0)]
+    score_after: Option<f64>,
+
+    /// ID admin yang memutuskan
+    #[schema(example = 2)]
+    resolved_by: Option<i32>,
+
+    /// Waktu keputusan
+    #[schema(value_type = Option<String>, example = "2024-07-07T08:00:00Z")]
+    resolved_at: Option<DateTime<Utc>>,
+
+    /// Waktu pengajuan
+    #[schema(value_type = String, example = "2024-07-05T08:00:00Z")]
+    created_at: DateTime<Utc>,
+}
+
+impl From<Appeal> for AppealResponse {
+    fn from(appeal: Appeal) -> Self {
+        Self {
+            id: appeal.id,
+            registration_id: appeal.registration_id,
+            filed_by: appeal.filed_by,
+            category: appeal.category,
+            reason: appeal.reason,
+            status: appeal.status,
+            response: appeal.response,
+            score_before: appeal.score_before,
+            score_after: appeal.score_after,
+            resolved_by: appeal.resolved_by,
+            resolved_at: appeal.resolved_at,
+            created_at: appeal.created_at,
+        }
+    }
+}
+
+/// Dokumen bukti sanggahan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AppealDocumentResponse {
+    /// ID dokumen
+    #[schema(example = 1)]
+    id: i32,
+
+    /// URL file (kosong selama file belum lolos pemindaian malware)
+    #[schema(example = "https://storage.example.com/appeals/surat_domisili.pdf")]
+    file_url: Option<String>,
+
+    /// Nama file
+    #[schema(example = "surat_domisili.pdf")]
+    file_name: String,
+
+    /// Ukuran file dalam bytes
+    #[schema(example = 512000)]
+    file_size: i64,
+
+    /// MIME type file
+    #[schema(example = "application/pdf")]
+    mime_type: String,
+
+    /// Status pemindaian malware: pending (dikarantina), clean, infected, skipped,
+    /// failed (tidak dapat dipindai)
+    #[schema(example = "clean")]
+    scan_status: String,
+}
+
+impl From<AppealDocument> for AppealDocumentResponse {
+    fn from(document: AppealDocument) -> Self {
+        // Quarantined and infected files are never served
+        let file_url = document.is_servable().then_some(document.file_url);
+
+        Self {
+            id: document.id,
+            file_url,
+            file_name: document.file_name,
+            file_size: document.file_size,
+            mime_type: document.mime_type,
+            scan_status: document.scan_status,
+        }
+    }
+}
+
+/// Catatan tindakan pada sanggahan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AppealEventResponse {
+    /// Tindakan (filed/triaged/rescored/upheld/dismissed)
+    #[schema(example = "rescored")]
+    action: String,
+
+    /// Catatan
+    #[schema(example = "Koordinat rumah akan dicek ulang")]
+    notes: Option<String>,
+
+    /// Detail tindakan, misalnya hasil hitung ulang skor
+    #[schema(value_type = Option<Object>)]
+    details: Option<serde_json::Value>,
+
+    /// ID pengguna yang melakukan tindakan
+    #[schema(example = 2)]
+    actor_id: Option<i32>,
+
+    /// Waktu tindakan
+    #[schema(value_type = String, example = "2024-07-06T08:00:00Z")]
+    created_at: DateTime<Utc>,
+}
+
+impl From<AppealEvent> for AppealEventResponse {
+    fn from(event: AppealEvent) -> Self {
+        Self {
+            action: event.action,
+            notes: event.notes,
+            details: event.details,
+            actor_id: event.actor_id,
+            created_at: event.created_at,
+        }
+    }
+}
+
+/// Response detail sanggahan beserta bukti dan riwayat tindakan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AppealDetailResponse {
+    /// Data sanggahan
+    appeal: AppealResponse,
+
+    /// Dokumen bukti
+    documents: Vec<AppealDocumentResponse>,
+
+    /// Riwayat tindakan dari yang terlama
+    events: Vec<AppealEventResponse>,
+}
+
+impl From<AppealDetail> for AppealDetailResponse {
+    fn from(detail: AppealDetail) -> Self {
+        Self {
+            appeal: detail.appeal.into(),
+            documents: detail.documents.into_iter().map(|d| d.into()).collect(),
+            events: detail.events.into_iter().map(|e| e.into()).collect(),
+        }
+    }
+}
+
+/// Response daftar sanggahan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListAppealsResponse {
+    /// Daftar sanggahan
+    appeals: Vec<AppealResponse>,
+
+    /// Total data
+    #[schema(example = 5)]
+    total: i64,
+
+    /// Halaman saat ini
+    #[schema(example = 1)]
+    page: i64,
+
+    /// Jumlah item per halaman
+    #[schema(example = 10)]
+    page_size: i64,
+
+    /// Total halaman
+    #[schema(example = 1)]
+    total_pages: i64,
+}
+
+/// Mengajukan sanggahan atas hasil seleksi
+///
+/// Endpoint ini digunakan orang tua yang menilai skor atau jarak pendaftarannya
+/// salah dihitung. Sanggahan hanya dapat diajukan untuk pendaftaran yang sudah
+/// diumumkan (diterima atau ditolak) selama masa sanggah periode, dan setiap
+/// pendaftaran hanya boleh memiliki satu sanggahan yang belum diputuskan.
+#[utoipa::path(
+    post,
+    path = "/api/appeals/registrations/{registration_id}",
+    tag = "Appeals",
+    params(
+        ("registration_id" = i32, Path, description = "ID pendaftaran")
+    ),
+    request_body = FileAppealRequest,
+    responses(
+        (status = 201, description = "Sanggahan berhasil diajukan", body = AppealDetailResponse),
+        (status = 400, description = "Request tidak valid atau masa sanggah sudah berakhir"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan"),
+        (status = 409, description = "Masih ada sanggahan yang belum diputuskan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn file_appeal(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(registration_id): Path<i32>,
+    Json(payload): Json<FileAppealRequest>,
+) -> AppResult<(StatusCode, Json<AppealDetailResponse>)> {
+    // Only the parent who registered can appeal
+    let registration = RegistrationRepository::new(state.db.clone())
+        .find_by_id(registration_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))?;
+    if registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to appeal this registration".to_string(),
+        ));
+    }
+
+    let documents = payload
+        .documents
+        .into_iter()
+        .map(|d| NewAppealDocument {
+            file_url: d.file_url,
+            file_name: d.file_name,
+            file_size: d.file_size,
+            mime_type: d.mime_type,
+        })
+        .collect();
+
+    let detail = appeal_service(&state)
+        .file_appeal(
+            registration_id,
+            auth_user.id,
+            payload.category,
+            payload.reason,
+            documents,
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(detail.into())))
+}
+
+/// Mendapatkan sanggahan sebuah pendaftaran
+#[utoipa::path(
+    get,
+    path = "/api/appeals/registrations/{registration_id}",
+    tag = "Appeals",
+    params(
+        ("registration_id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Daftar sanggahan berhasil diambil", body = Vec<AppealResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_registration_appeals(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(registration_id): Path<i32>,
+) -> AppResult<Json<Vec<AppealResponse>>> {
+    let registration = RegistrationRepository::new(state.db.clone())
+        .find_by_id(registration_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))?;
+    check_registration_access(&auth_user, &registration)?;
+
+    let appeals = appeal_service(&state)
+        .list_registration_appeals(registration_id)
+        .await?;
+
+    Ok(Json(appeals.into_iter().map(|a| a.into()).collect()))
+}
+
+/// Mendapatkan detail sanggahan
+///
+/// Endpoint ini mengembalikan sanggahan beserta dokumen bukti dan riwayat
+/// seluruh tindakan, termasuk hasil hitung ulang skor.
+#[utoipa::path(
+    get,
+    path = "/api/appeals/{id}",
+    tag = "Appeals",
+    params(
+        ("id" = i32, Path, description = "ID sanggahan")
+    ),
+    responses(
+        (status = 200, description = "Detail sanggahan berhasil diambil", body = AppealDetailResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Sanggahan tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_appeal(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<AppealDetailResponse>> {
+    let appeal_service = appeal_service(&state);
+
+    let registration = appeal_service.get_appeal_registration(id).await?;
+    check_registration_access(&auth_user, &registration)?;
+
+    let detail = appeal_service.get_appeal(id, None).await?;
+
+    Ok(Json(detail.into()))
+}
+
+/// Mendapatkan antrean sanggahan
+///
+/// Sanggahan yang belum diputuskan ditampilkan lebih dulu, dari yang terlama.
+#[utoipa::path(
+    get,
+    path = "/api/appeals",
+    tag = "Appeals",
+    params(ListAppealsQuery),
+    responses(
+        (status = 200, description = "Daftar sanggahan berhasil diambil", body = ListAppealsResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_appeals(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ListAppealsQuery>,
+) -> AppResult<Json<ListAppealsResponse>> {
+    let school_id = admin_scope(&auth_user)?;
+
+    let (appeals, total) = appeal_service(&state)
+        .list_appeals(
+            school_id,
+            query.period_id,
+            query.status,
+            query.page,
+            query.page_size,
+        )
+        .await?;
+
+    let total_pages = (total as f64 / query.page_size as f64).ceil() as i64;
+
+    Ok(Json(ListAppealsResponse {
+        appeals: appeals.into_iter().map(|a| a.into()).collect(),
+        total,
+        page: query.page,
+        page_size: query.page_size,
+        total_pages,
+    }))
+}
+
+/// Meninjau sanggahan
+///
+/// Sanggahan yang baru diajukan ditandai `in_review` sebelum diputuskan.
+#[utoipa::path(
+    post,
+    path = "/api/appeals/{id}/triage",
+    tag = "Appeals",
+    params(
+        ("id" = i32, Path, description = "ID sanggahan")
+    ),
+    request_body = TriageAppealRequest,
+    responses(
+        (status = 200, description = "Sanggahan sedang ditinjau", body = AppealDetailResponse),
+        (status = 400, description = "Sanggahan sudah ditinjau"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Sanggahan tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn triage_appeal(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<TriageAppealRequest>,
+) -> AppResult<Json<AppealDetailResponse>> {
+    let school_id = admin_scope(&auth_user)?;
+
+    let detail = appeal_service(&state)
+        .triage_appeal(id, school_id, payload.notes, auth_user.id)
+        .await?;
+
+    Ok(Json(detail.into()))
+}
+
+/// Menghitung ulang skor pendaftaran yang disanggah
+///
+/// Skor seleksi pendaftaran dihitung ulang dari data terbaru. Kursi tidak
+/// dialokasikan ulang; posisi skor baru di jalurnya dicatat pada riwayat
+/// sanggahan sebagai bahan keputusan.
+#[utoipa::path(
+    post,
+    path = "/api/appeals/{id}/rescore",
+    tag = "Appeals",
+    params(
+        ("id" = i32, Path, description = "ID sanggahan")
+    ),
+    responses(
+        (status = 200, description = "Skor berhasil dihitung ulang", body = AppealDetailResponse),
+        (status = 400, description = "Sanggahan sudah diputuskan"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Sanggahan tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn rescore_appeal(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<AppealDetailResponse>> {
+    let school_id = admin_scope(&auth_user)?;
+
+    let detail = appeal_service(&state)
+        .rescore_appeal(id, school_id, auth_user.id)
+        .await?;
+
+    Ok(Json(detail.into()))
+}
+
+/// Memutuskan sanggahan
+///
+/// Sanggahan dikabulkan (`upheld`) atau ditolak (`dismissed`) dengan tanggapan
+/// untuk orang tua.
+#[utoipa::path(
+    post,
+    path = "/api/appeals/{id}/resolve",
+    tag = "Appeals",
+    params(
+        ("id" = i32, Path, description = "ID sanggahan")
+    ),
+    request_body = ResolveAppealRequest,
+    responses(
+        (status = 200, description = "Sanggahan berhasil diputuskan", body = AppealDetailResponse),
+        (status = 400, description = "Keputusan tidak valid atau sanggahan sudah diputuskan"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Sanggahan tidak ditemukan"),
+        (status = 409, description = "Status sanggahan berubah saat diproses")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn resolve_appeal(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<ResolveAppealRequest>,
+) -> AppResult<Json<AppealDetailResponse>> {
+    let school_id = admin_scope(&auth_user)?;
+
+    let detail = appeal_service(&state)
+        .resolve_appeal(id, school_id, payload.status, payload.response, auth_user.id)
+        .await?;
+
+    Ok(Json(detail.into()))
+}
@@ -0,0 +1,254 @@
+use axum::{
+    extract::{DefaultBodyLimit, Path, Query, State},
+    http::StatusCode,
+    middleware,
+    routing::{delete, get},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::middleware::auth::{auth_middleware, AuthUser};
+use crate::models::assistance::AssistanceList;
+use crate::repositories::assistance_repo::AssistanceRepository;
+use crate::repositories::region_repo::RegionRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::services::assistance_service::AssistanceService;
+use crate::utils::error::AppResult;
+use crate::AppState;
+
+/// Beneficiary exports of a whole region can be large
+const MAX_IMPORT_SIZE: usize = 20 * 1024 * 1024;
+
+pub fn routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(list_assistance_lists)
+                .post(import_assistance_list)
+                .layer(DefaultBodyLimit::max(MAX_IMPORT_SIZE)),
+        )
+        .route("/:id", delete(delete_assistance_list))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ))
+}
+
+fn assistance_service(state: &AppState) -> AssistanceService {
+    AssistanceService::new(
+        AssistanceRepository::new(state.db.clone()),
+        RegistrationRepository::new(state.db.clone()),
+        RegionRepository::new(state.db.clone()),
+    )
+}
+
+/// Query untuk impor daftar penerima bantuan
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportAssistanceListQuery {
+    /// Program bantuan (dtks/pkh/kip)
+    #[schema(example = "dtks")]
+    program: String,
+
+    /// Nama daftar
+    #[schema(example = "DTKS Kota Bandung Mei 2024")]
+    name: String,
+
+    /// ID sekolah pemilik daftar (super admin)
+    #[schema(example = 1)]
+    school_id: Option<i32>,
+
+    /// ID wilayah pemilik daftar (admin wilayah atau super admin)
+    #[schema(example = 2)]
+    region_id: Option<i32>,
+}
+
+/// Query untuk list daftar penerima bantuan
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListAssistanceListsQuery {
+    /// Filter program bantuan (dtks/pkh/kip)
+    #[schema(example = "kip")]
+    program: Option<String>,
+}
+
+/// Response daftar penerima bantuan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AssistanceListResponse {
+    /// ID daftar
+    #[schema(example = 1)]
+    id: i32,
+
+    /// Program bantuan (dtks/pkh/kip)
+    #[schema(example = "dtks")]
+    program: String,
+
+    /// ID sekolah pemilik daftar
+    #[schema(example = 1)]
+    school_id: Option<i32>,
+
+    /// ID wilayah pemilik daftar
+    #[schema(example = 2)]
+    region_id: Option<i32>,
+
+    /// Nama daftar
+    #[schema(example = "DTKS Kota Bandung Mei 2024")]
+    name: String,
+
+    /// Jumlah penerima
+    #[schema(example = 15230)]
+    row_count: i32,
+
+    /// ID pengguna yang mengimpor
+    #[schema(example = 2)]
+    imported_by: Option<i32>,
+
+    /// Waktu impor
+    #[schema(value_type = String, example = "2024-05-20T08:00:00Z")]
+    created_at: DateTime<Utc>,
+}
+
+impl From<AssistanceList> for AssistanceListResponse {
+    fn from(list: AssistanceList) -> Self {
+        Self {
+            id: list.id,
+            program: list.program,
+            school_id: list.school_id,
+            region_id: list.region_id,
+            name: list.name,
+            row_count: list.row_count,
+            imported_by: list.imported_by,
+            created_at: list.created_at,
+        }
+    }
+}
+
+/// Response impor daftar penerima bantuan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportAssistanceListResponse {
+    /// Daftar yang diimpor
+    list: AssistanceListResponse,
+
+    /// Jumlah kecocokan baru dengan pendaftar afirmasi
+    #[schema(example = 12)]
+    new_matches: u64,
+}
+
+/// Impor daftar penerima bantuan sosial (CSV)
+///
+/// Endpoint ini mengimpor daftar penerima DTKS/PKH/KIP dari file CSV (dipisah koma atau
+/// titik koma). Baris pertama berisi header dengan kolom `nik` dan/atau `kip_number`
+/// (alias: `no_kip`, `nomor_kip`), serta kolom `nama` opsional. Setelah impor, pendaftar
+/// jalur afirmasi yang sudah disubmit di sekolah terkait dicocokkan otomatis berdasarkan
+/// NIK siswa, NIK orang tua, atau nomor KIP.
+#[utoipa::path(
+    post,
+    path = "/api/assistance-lists",
+    tag = "Assistance Lists",
+    params(ImportAssistanceListQuery),
+    request_body(content = String, content_type = "text/csv", description = "Isi file CSV"),
+    responses(
+        (status = 201, description = "Daftar berhasil diimpor", body = ImportAssistanceListResponse),
+        (status = 400, description = "CSV tidak valid", body = crate::api::docs::ValidationErrorResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn import_assistance_list(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ImportAssistanceListQuery>,
+    body: String,
+) -> AppResult<(StatusCode, Json<ImportAssistanceListResponse>)> {
+    let assistance_service = assistance_service(&state);
+
+    let scope = assistance_service
+        .resolve_scope(&auth_user.role, auth_user.school_id, auth_user.region_id)
+        .await?;
+    let owner = AssistanceService::resolve_owner(&scope, query.school_id, query.region_id)?;
+
+    let result = assistance_service
+        .import_list(owner, query.program, query.name, &body, auth_user.id)
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ImportAssistanceListResponse {
+            list: result.list.into(),
+            new_matches: result.new_matches,
+        }),
+    ))
+}
+
+/// Mendapatkan daftar penerima bantuan yang sudah diimpor
+#[utoipa::path(
+    get,
+    path = "/api/assistance-lists",
+    tag = "Assistance Lists",
+    params(ListAssistanceListsQuery),
+    responses(
+        (status = 200, description = "Daftar berhasil diambil", body = Vec<AssistanceListResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_assistance_lists(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ListAssistanceListsQuery>,
+) -> AppResult<Json<Vec<AssistanceListResponse>>> {
+    let assistance_service = assistance_service(&state);
+
+    let scope = assistance_service
+        .resolve_scope(&auth_user.role, auth_user.school_id, auth_user.region_id)
+        .await?;
+
+    let lists = assistance_service.list_lists(&scope, query.program).await?;
+
+    Ok(Json(lists.into_iter().map(|l| l.into()).collect()))
+}
+
+/// Hapus daftar penerima bantuan
+///
+/// Kecocokan yang berasal dari daftar ini ikut terhapus dan bonus afirmasi pendaftar
+/// terkait dihitung ulang.
+#[utoipa::path(
+    delete,
+    path = "/api/assistance-lists/{id}",
+    tag = "Assistance Lists",
+    params(
+        ("id" = i32, Path, description = "ID daftar")
+    ),
+    responses(
+        (status = 204, description = "Daftar berhasil dihapus"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Daftar tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn delete_assistance_list(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    let assistance_service = assistance_service(&state);
+
+    let scope = assistance_service
+        .resolve_scope(&auth_user.role, auth_user.school_id, auth_user.region_id)
+        .await?;
+
+    assistance_service.delete_list(id, &scope).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -49,6 +49,9 @@ API untuk sistem penerimaan siswa baru berbasis multi-tenant SaaS.
 - 📄 Document upload & verification
 - 🎯 Automatic scoring & ranking
 - 📊 Selection process & announcement
+- ⚖️ Appeals (sanggahan) after announcement
+- 🗺️ Centralized multi-school allocation
+- 🔁 Cross-school duplicate registration detection
 
 ## Authentication
 Most endpoints require JWT Bearer token authentication.
@@ -59,6 +62,7 @@ Most endpoints require JWT Bearer token authentication.
 
 ## Roles & Permissions
 - **SuperAdmin**: Full access to all schools
+- **RegionAdmin**: Read access to schools in own region (province/regency)
 - **SchoolAdmin**: Access to own school data only
 - **Parent**: Access to own registrations only
 
@@ -99,6 +103,16 @@ All data is automatically filtered by school_id based on user's role.
         crate::api::schools::deactivate_school,
         crate::api::schools::activate_school,
         
+        // Region endpoints
+        crate::api::regions::create_region,
+        crate::api::regions::update_region,
+        crate::api::regions::list_regions,
+        crate::api::regions::get_region,
+        crate::api::regions::list_region_schools,
+        crate::api::regions::get_region_stats,
+        crate::api::regions::get_region_duplicates,
+        crate::api::regions::get_region_calendar,
+        
         // User endpoints
         crate::api::users::list_users,
         crate::api::users::create_user,
@@ -117,10 +131,21 @@ All data is automatically filtered by school_id based on user's role.
         crate::api::periods::delete_period,
         crate::api::periods::activate_period,
         crate::api::periods::close_period,
+        crate::api::periods::clone_period,
         crate::api::periods::get_paths,
         crate::api::periods::create_path,
         crate::api::periods::update_path,
         crate::api::periods::delete_path,
+        crate::api::periods::get_path_schema,
+        crate::api::periods::get_achievement_point_table,
+        crate::api::periods::get_majors,
+        crate::api::periods::create_major,
+        crate::api::periods::update_major,
+        crate::api::periods::delete_major,
+        crate::api::periods::get_stages,
+        crate::api::periods::create_stage,
+        crate::api::periods::update_stage,
+        crate::api::periods::delete_stage,
         
         // Registration endpoints
         crate::api::registrations::list_registrations,
@@ -128,6 +153,28 @@ All data is automatically filtered by school_id based on user's role.
         crate::api::registrations::get_registration,
         crate::api::registrations::update_registration,
         crate::api::registrations::submit_registration,
+        crate::api::registrations::list_fallback_paths,
+        crate::api::registrations::list_revisions,
+        crate::api::registrations::withdraw_registration,
+        crate::api::registrations::list_status_history,
+        crate::api::registrations::get_grades,
+        crate::api::registrations::update_grades,
+        crate::api::registrations::get_achievements,
+        crate::api::registrations::update_achievements,
+        crate::api::registrations::get_major_choices,
+        crate::api::registrations::update_major_choices,
+        crate::api::registrations::list_test_bookings,
+        crate::api::registrations::book_test_session,
+        crate::api::registrations::cancel_test_booking,
+        crate::api::registrations::get_test_card,
+        crate::api::registrations::print_test_card,
+        crate::api::test_sessions::list_test_sessions,
+        crate::api::test_sessions::create_test_session,
+        crate::api::test_sessions::update_test_session,
+        crate::api::test_sessions::delete_test_session,
+        crate::api::test_sessions::list_test_bookings,
+        crate::api::test_sessions::record_test_results,
+        crate::api::test_sessions::auto_assign_test_sessions,
         crate::api::registrations::list_documents,
         crate::api::registrations::upload_document,
         crate::api::registrations::delete_document,
@@ -144,12 +191,60 @@ All data is automatically filtered by school_id based on user's role.
         crate::api::announcements::get_selection_summary,
         crate::api::announcements::check_result,
         
+        // Appeal endpoints
+        crate::api::appeals::file_appeal,
+        crate::api::appeals::list_registration_appeals,
+        crate::api::appeals::get_appeal,
+        crate::api::appeals::list_appeals,
+        crate::api::appeals::triage_appeal,
+        crate::api::appeals::rescore_appeal,
+        crate::api::appeals::resolve_appeal,
+        
+        // Allocation endpoints
+        crate::api::allocations::list_rounds,
+        crate::api::allocations::list_open_rounds,
+        crate::api::allocations::create_round,
+        crate::api::allocations::get_round,
+        crate::api::allocations::update_round_periods,
+        crate::api::allocations::open_round,
+        crate::api::allocations::close_round,
+        crate::api::allocations::run_allocation,
+        crate::api::allocations::list_results,
+        crate::api::allocations::submit_choices,
+        crate::api::allocations::get_choices,
+        crate::api::allocations::get_student_result,
+        
         // Verification endpoints
         crate::api::verifications::get_pending_verifications,
         crate::api::verifications::get_verification_stats,
         crate::api::verifications::verify_registration,
         crate::api::verifications::reject_registration,
+        crate::api::verifications::request_revision,
+        crate::api::verifications::enroll_registration,
+        crate::api::verifications::verify_grades,
+        crate::api::verifications::verify_achievement,
+        crate::api::verifications::get_assistance_matches,
+        crate::api::verifications::review_assistance_match,
+        crate::api::verifications::record_requirement_check,
+        crate::api::verifications::extend_deadline,
         crate::api::verifications::verify_document,
+        crate::api::verifications::claim_next_registration,
+        crate::api::verifications::claim_registration,
+        crate::api::verifications::release_claim,
+        crate::api::verifications::assign_registrations,
+        crate::api::verifications::get_queue_metrics,
+        crate::api::verifications::bulk_registrations,
+        crate::api::verifications::bulk_documents,
+        crate::api::verifications::get_bulk_job,
+        
+        // Duplicate detection endpoints
+        crate::api::duplicates::list_duplicate_flags,
+        crate::api::duplicates::get_duplicate_flag,
+        crate::api::duplicates::get_document_clusters,
+        crate::api::duplicates::review_duplicate_flag,
+        crate::api::assistance::import_assistance_list,
+        crate::api::assistance::list_assistance_lists,
+        crate::api::assistance::delete_assistance_list,
     ),
     components(
         schemas(
@@ -180,6 +275,20 @@ All data is automatically filtered by school_id based on user's role.
             crate::api::schools::SchoolResponse,
             crate::api::schools::ListSchoolsResponse,
             
+            // Region DTOs
+            crate::api::regions::CreateRegionRequest,
+            crate::api::regions::UpdateRegionRequest,
+            crate::api::regions::ListRegionsQuery,
+            crate::api::regions::RegionReportQuery,
+            crate::api::regions::RegionResponse,
+            crate::services::region_service::RegionStats,
+            crate::services::region_service::SchoolRegistrationStats,
+            crate::services::region_service::DuplicateStudent,
+            crate::services::region_service::DuplicateRegistration,
+            crate::services::region_service::RegionCalendar,
+            crate::services::region_service::CalendarEntry,
+            crate::services::region_service::CalendarConflict,
+            
             // User DTOs
             crate::api::users::CreateUserRequest,
             crate::api::users::UpdateUserRequest,
@@ -194,7 +303,17 @@ All data is automatically filtered by school_id based on user's role.
             crate::api::periods::UpdatePathRequest,
             crate::api::periods::PeriodResponse,
             crate::api::periods::PeriodWithPathsResponse,
+            crate::api::periods::ClonePeriodRequest,
+            crate::api::periods::ClonePeriodResponse,
             crate::api::periods::PathResponse,
+            crate::api::periods::PathSchemaResponse,
+            crate::api::periods::AchievementPointTableResponse,
+            crate::api::periods::CreateMajorRequest,
+            crate::api::periods::UpdateMajorRequest,
+            crate::api::periods::MajorResponse,
+            crate::api::periods::CreateStageRequest,
+            crate::api::periods::UpdateStageRequest,
+            crate::api::periods::StageResponse,
             crate::api::periods::ListPeriodsResponse,
             crate::api::periods::ListPeriodsQuery,
             crate::api::periods::MessageResponse,
@@ -202,9 +321,45 @@ All data is automatically filtered by school_id based on user's role.
             // Registration DTOs
             crate::api::registrations::CreateRegistrationRequest,
             crate::api::registrations::UpdateRegistrationRequest,
+            crate::api::registrations::FallbackPathRequest,
             crate::api::registrations::UploadDocumentRequest,
             crate::api::registrations::RegistrationResponse,
             crate::api::registrations::DocumentResponse,
+            crate::api::registrations::DocumentArtifactResponse,
+            crate::api::registrations::DocumentDuplicateResponse,
+            crate::api::registrations::FallbackPathResponse,
+            crate::api::registrations::RevisionResponse,
+            crate::api::registrations::WithdrawRegistrationRequest,
+            crate::api::registrations::WithdrawRegistrationResponse,
+            crate::api::registrations::StatusChangeResponse,
+            crate::api::registrations::RevisionFieldItem,
+            crate::api::registrations::RevisionDocumentItem,
+            crate::api::registrations::GradeEntryRequest,
+            crate::api::registrations::UpdateGradesRequest,
+            crate::services::grade_service::GradeSummary,
+            crate::api::registrations::UpdateMajorChoicesRequest,
+            crate::services::major_service::MajorChoiceSummary,
+            crate::services::major_service::MajorChoiceResult,
+            crate::services::major_service::CriterionResult,
+            crate::api::registrations::BookTestSessionRequest,
+            crate::api::test_sessions::ListTestSessionsQuery,
+            crate::api::test_sessions::CreateTestSessionRequest,
+            crate::api::test_sessions::UpdateTestSessionRequest,
+            crate::api::test_sessions::AutoAssignTestSessionsRequest,
+            crate::api::test_sessions::TestResultEntryRequest,
+            crate::api::test_sessions::RecordTestResultsRequest,
+            crate::api::test_sessions::TestSessionResponse,
+            crate::api::test_sessions::TestBookingResponse,
+            crate::services::test_service::TestCard,
+            crate::services::test_service::TestCardSession,
+            crate::services::test_service::AutoAssignResult,
+            crate::services::grade_service::MissingGrade,
+            crate::services::grade_service::SemesterGrades,
+            crate::services::grade_service::SubjectGrade,
+            crate::api::registrations::AchievementEntryRequest,
+            crate::api::registrations::UpdateAchievementsRequest,
+            crate::services::achievement_service::AchievementSummary,
+            crate::services::achievement_service::AchievementResult,
             crate::api::registrations::ListRegistrationsResponse,
             crate::api::registrations::ListRegistrationsQuery,
             crate::api::registrations::MessageResponse,
@@ -221,22 +376,91 @@ All data is automatically filtered by school_id based on user's role.
             crate::api::announcements::RunSelectionResponse,
             crate::api::announcements::AnnounceResultsResponse,
             crate::api::announcements::CheckResultQuery,
+            crate::api::announcements::StageQuery,
             crate::services::announcement_service::SelectionResult,
             crate::services::announcement_service::AnnouncementResult,
             crate::services::announcement_service::ResultCheckResponse,
             crate::services::announcement_service::SelectionSummary,
             crate::services::announcement_service::PathSelectionSummary,
+            crate::services::announcement_service::MajorSelectionSummary,
+            crate::services::appeal_service::AppealStats,
+            
+            // Appeal DTOs
+            crate::api::appeals::ListAppealsQuery,
+            crate::api::appeals::AppealDocumentRequest,
+            crate::api::appeals::FileAppealRequest,
+            crate::api::appeals::TriageAppealRequest,
+            crate::api::appeals::ResolveAppealRequest,
+            crate::api::appeals::AppealResponse,
+            crate::api::appeals::AppealDocumentResponse,
+            crate::api::appeals::AppealEventResponse,
+            crate::api::appeals::AppealDetailResponse,
+            crate::api::appeals::ListAppealsResponse,
+            
+            // Allocation DTOs
+            crate::api::allocations::CreateAllocationRoundRequest,
+            crate::api::allocations::UpdateRoundPeriodsRequest,
+            crate::api::allocations::SubmitChoicesRequest,
+            crate::api::allocations::ChoicesQuery,
+            crate::api::allocations::ListAllocationResultsQuery,
+            crate::api::allocations::AllocationRoundResponse,
+            crate::api::allocations::AllocationChoiceResponse,
+            crate::api::allocations::AllocationResultResponse,
+            crate::api::allocations::ListAllocationResultsResponse,
+            crate::api::allocations::RunAllocationResponse,
+            crate::services::allocation_service::AllocationSummary,
+            crate::services::allocation_service::ChoiceExplanation,
             
             // Verification DTOs
             crate::api::verifications::PendingVerificationsQuery,
             crate::api::verifications::StatsQuery,
             crate::api::verifications::RejectRegistrationRequest,
+            crate::api::verifications::RequestRevisionRequest,
+            crate::api::verifications::RequestRevisionResponse,
             crate::api::verifications::VerifyDocumentRequest,
+            crate::api::verifications::DocumentVerificationResponse,
             crate::api::verifications::RegistrationResponse,
             crate::api::verifications::PendingVerificationsResponse,
             crate::api::verifications::MessageResponse,
+            crate::api::verifications::EnrollmentResponse,
+            crate::api::verifications::VerifyGradesRequest,
+            crate::api::verifications::VerifyAchievementRequest,
+            crate::api::verifications::ReviewAssistanceMatchRequest,
+            crate::api::verifications::RecordRequirementCheckRequest,
+            crate::api::verifications::ExtendDeadlineRequest,
+            crate::api::verifications::AgeCheckResponse,
+            crate::api::verifications::AssistanceMatchResponse,
+            crate::api::verifications::ClaimNextQuery,
+            crate::api::verifications::QueueMetricsQuery,
+            crate::api::verifications::AssignRegistrationsRequest,
+            crate::api::verifications::ClaimResponse,
+            crate::api::verifications::ClaimedRegistrationResponse,
+            crate::api::verifications::BulkRegistrationsRequest,
+            crate::api::verifications::BulkDocumentsRequest,
+            crate::api::verifications::BulkItemResponse,
+            crate::api::verifications::BulkJobResponse,
+            crate::services::verification_service::AssignmentSummary,
+            crate::services::verification_service::VerifierAssignment,
+            crate::services::verification_service::VerificationQueueMetrics,
+            crate::services::verification_service::VerificationBacklog,
+            crate::services::verification_service::VerifierThroughput,
             crate::services::verification_service::VerificationStats,
             
+            // Duplicate detection DTOs
+            crate::api::duplicates::ListDuplicateFlagsQuery,
+            crate::api::duplicates::ReviewDuplicateFlagRequest,
+            crate::api::duplicates::DuplicateRegistrationSummary,
+            crate::api::duplicates::DuplicateFlagResponse,
+            crate::api::duplicates::ListDuplicateFlagsResponse,
+            crate::api::duplicates::DocumentClustersQuery,
+            crate::api::duplicates::ClusterDocumentResponse,
+            crate::api::duplicates::DocumentClusterResponse,
+            crate::api::duplicates::DocumentClustersResponse,
+            crate::api::assistance::ImportAssistanceListQuery,
+            crate::api::assistance::ListAssistanceListsQuery,
+            crate::api::assistance::AssistanceListResponse,
+            crate::api::assistance::ImportAssistanceListResponse,
+            
             // Health check
             crate::api::health::HealthResponse,
             
@@ -255,11 +479,17 @@ All data is automatically filtered by school_id based on user's role.
         (name = "System", description = "System health and status endpoints"),
         (name = "Authentication", description = "User authentication and authorization"),
         (name = "Schools", description = "School management (SuperAdmin only)"),
+        (name = "Regions", description = "Region (dinas pendidikan) oversight of schools"),
         (name = "Users", description = "User management"),
         (name = "Periods", description = "PPDB period and registration path management"),
         (name = "Registrations", description = "Student registration and document management"),
         (name = "Selection", description = "Selection scoring, ranking, and announcement"),
+        (name = "Appeals", description = "Appeals (sanggahan) against announced selection results"),
+        (name = "Tests", description = "Entrance test and interview scheduling and results"),
+        (name = "Allocations", description = "Centralized multi-school allocation rounds"),
         (name = "Verifications", description = "Document and registration verification"),
+        (name = "Duplicates", description = "Cross-school duplicate registration review"),
+        (name = "Assistance Lists", description = "Social-assistance (DTKS/PKH/KIP) lists for afirmasi eligibility"),
     ),
     modifiers(&SecurityAddon)
 )]
@@ -0,0 +1,474 @@
+use axum::{
+    extract::{Path, Query, State},
+    middleware,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::middleware::auth::{auth_middleware, AuthUser};
+use crate::api::middleware::rbac::require_school_admin;
+use crate::models::duplicate::{ClusterDocument, DocumentCluster, DuplicateFlagDetail};
+use crate::repositories::duplicate_repo::DuplicateRepository;
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::services::duplicate_service::DuplicateService;
+use crate::utils::error::{AppError, AppResult};
+use crate::AppState;
+
+pub fn routes(state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_duplicate_flags))
+        .route("/document-clusters", get(get_document_clusters))
+        .route("/:id", get(get_duplicate_flag))
+        .route("/:id/review", post(review_duplicate_flag))
+        .route_layer(middleware::from_fn(require_school_admin))
+        .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware))
+}
+
+fn duplicate_service(state: &AppState) -> DuplicateService {
+    DuplicateService::new(
+        DuplicateRepository::new(state.db.clone()),
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    )
+}
+
+/// Super admin reviews every flag, school admins only flags touching their school
+fn review_scope(auth_user: &AuthUser) -> AppResult<Option<i32>> {
+    if auth_user.role == "super_admin" {
+        return Ok(None);
+    }
+
+    auth_user.school_id.map(Some).ok_or_else(|| {
+        AppError::Authentication("User must be associated with a school".to_string())
+    })
+}
+
+/// Query untuk daftar temuan pendaftaran ganda
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListDuplicateFlagsQuery {
+    /// Nomor halaman
+    #[serde(default = "default_page")]
+    #[schema(example = 1)]
+    page: i64,
+
+    /// Jumlah item per halaman
+    #[serde(default = "default_page_size")]
+    #[schema(example = 10)]
+    page_size: i64,
+
+    /// Filter berdasarkan status (open/dismissed/confirmed)
+    #[schema(example = "open")]
+    status: Option<String>,
+
+    /// Filter berdasarkan jenis kecocokan (nisn/parent_nik/document_content/document_image)
+    #[schema(example = "nisn")]
+    match_type: Option<String>,
+}
+
+fn default_page() -> i64 {
+    1
+}
+
+fn default_page_size() -> i64 {
+    10
+}
+
+/// Request untuk meninjau temuan pendaftaran ganda
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReviewDuplicateFlagRequest {
+    /// Hasil tinjauan (dismissed/confirmed)
+    #[schema(example = "confirmed")]
+    status: String,
+
+    /// Catatan peninjau (opsional)
+    #[schema(example = "Siswa yang sama terdaftar di dua sekolah")]
+    notes: Option<String>,
+}
+
+/// Ringkasan pendaftaran yang terlibat dalam temuan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicateRegistrationSummary {
+    /// ID pendaftaran
+    #[schema(example = 1)]
+    id: i32,
+
+    /// ID sekolah
+    #[schema(example = 1)]
+    school_id: i32,
+
+    /// NISN siswa
+    #[schema(example = "0012345678")]
+    student_nisn: String,
+
+    /// Nama siswa
+    #[schema(example = "Ahmad Fauzi")]
+    student_name: String,
+
+    /// Status pendaftaran
+    #[schema(example = "submitted")]
+    status: String,
+}
+
+/// Response temuan pendaftaran ganda
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicateFlagResponse {
+    /// ID temuan
+    #[schema(example = 1)]
+    id: i32,
+
+    /// Jenis kecocokan (nisn/parent_nik/document_content/document_image)
+    #[schema(example = "nisn")]
+    match_type: String,
+
+    /// ID dokumen yang memicu temuan (khusus kecocokan dokumen)
+    #[schema(example = 101)]
+    document_id: Option<i32>,
+
+    /// ID dokumen pendaftaran lain yang cocok (khusus kecocokan dokumen)
+    #[schema(example = 87)]
+    matched_document_id: Option<i32>,
+
+    /// Status tinjauan (open/dismissed/confirmed)
+    #[schema(example = "open")]
+    status: String,
+
+    /// Pendaftaran yang memicu temuan
+    registration: DuplicateRegistrationSummary,
+
+    /// Pendaftaran lain yang cocok
+    matched_registration: DuplicateRegistrationSummary,
+
+    /// Catatan peninjau
+    #[schema(example = "Siswa yang sama terdaftar di dua sekolah")]
+    notes: Option<String>,
+
+    /// ID admin peninjau
+    #[schema(example = 2)]
+    reviewed_by: Option<i32>,
+
+    /// Waktu peninjauan
+    #[schema(value_type = Option<String>, example = "2024-06-10T08:00:00Z")]
+    reviewed_at: Option<DateTime<Utc>>,
+
+    /// Waktu temuan dibuat
+    #[schema(value_type = String, example = "2024-06-05T08:00:00Z")]
+    created_at: DateTime<Utc>,
+}
+
+impl From<DuplicateFlagDetail> for DuplicateFlagResponse {
+    fn from(detail: DuplicateFlagDetail) -> Self {
+        Self {
+            id: detail.flag.id,
+            match_type: detail.flag.match_type,
+            document_id: detail.flag.document_id,
+            matched_document_id: detail.flag.matched_document_id,
+            status: detail.flag.status,
+            registration: DuplicateRegistrationSummary {
+                id: detail.flag.registration_id,
+                school_id: detail.registration_school_id,
+                student_nisn: detail.registration_student_nisn,
+                student_name: detail.registration_student_name,
+                status: detail.registration_status,
+            },
+            matched_registration: DuplicateRegistrationSummary {
+                id: detail.flag.matched_registration_id,
+                school_id: detail.matched_school_id,
+                student_nisn: detail.matched_student_nisn,
+                student_name: detail.matched_student_name,
+                status: detail.matched_status,
+            },
+            notes: detail.flag.notes,
+            reviewed_by: detail.flag.reviewed_by,
+            reviewed_at: detail.flag.reviewed_at,
+            created_at: detail.flag.created_at,
+        }
+    }
+}
+
+/// Response daftar temuan pendaftaran ganda
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListDuplicateFlagsResponse {
+    /// Daftar temuan
+    flags: Vec<DuplicateFlagResponse>,
+
+    /// Total data
+    #[schema(example = 5)]
+    total: i64,
+
+    /// Halaman saat ini
+    #[schema(example = 1)]
+    page: i64,
+
+    /// Jumlah item per halaman
+    #[schema(example = 10)]
+    page_size: i64,
+
+    /// Total halaman
+    #[schema(example = 1)]
+    total_pages: i64,
+}
+
+/// Query laporan klaster dokumen ganda
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DocumentClustersQuery {
+    /// ID periode PPDB
+    #[schema(example = 1)]
+    period_id: i32,
+}
+
+/// Dokumen dalam klaster beserta pendaftarannya
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClusterDocumentResponse {
+    /// ID dokumen
+    #[schema(example = 101)]
+    document_id: i32,
+
+    /// Tipe dokumen
+    #[schema(example = "kartu_keluarga")]
+    document_type: String,
+
+    /// ID pendaftaran
+    #[schema(example = 34)]
+    registration_id: i32,
+
+    /// ID sekolah
+    #[schema(example = 1)]
+    school_id: i32,
+
+    /// NISN siswa
+    #[schema(example = "0012345678")]
+    student_nisn: String,
+
+    /// Nama siswa
+    #[schema(example = "Ahmad Fauzi")]
+    student_name: String,
+
+    /// Status pendaftaran
+    #[schema(example = "submitted")]
+    registration_status: String,
+}
+
+impl From<ClusterDocument> for ClusterDocumentResponse {
+    fn from(document: ClusterDocument) -> Self {
+        Self {
+            document_id: document.document_id,
+            document_type: document.document_type,
+            registration_id: document.registration_id,
+            school_id: document.school_id,
+            student_nisn: document.student_nisn,
+            student_name: document.student_name,
+            registration_status: document.registration_status,
+        }
+    }
+}
+
+/// Klaster dokumen yang sama atau mirip di beberapa pendaftaran
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DocumentClusterResponse {
+    /// Dokumen dalam klaster
+    documents: Vec<ClusterDocumentResponse>,
+
+    /// Jenis kecocokan yang menghubungkan dokumen
+    #[schema(example = json!(["document_content"]))]
+    match_types: Vec<String>,
+
+    /// Jumlah pendaftaran dalam klaster
+    #[schema(example = 3)]
+    registration_count: usize,
+
+    /// Jumlah sekolah dalam klaster
+    #[schema(example = 2)]
+    school_count: usize,
+
+    /// Jumlah temuan yang belum ditinjau
+    #[schema(example = 2)]
+    open_flags: i64,
+}
+
+impl From<DocumentCluster> for DocumentClusterResponse {
+    fn from(cluster: DocumentCluster) -> Self {
+        let mut registrations: Vec<i32> = cluster.documents.iter().map(|d| d.registration_id).collect();
+        registrations.sort_unstable();
+        registrations.dedup();
+        let mut schools: Vec<i32> = cluster.documents.iter().map(|d| d.school_id).collect();
+        schools.sort_unstable();
+        schools.dedup();
+
+        Self {
+            registration_count: registrations.len(),
+            school_count: schools.len(),
+            documents: cluster.documents.into_iter().map(|d| d.into()).collect(),
+            match_types: cluster.match_types,
+            open_flags: cluster.open_flags,
+        }
+    }
+}
+
+/// Response laporan klaster dokumen ganda
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DocumentClustersResponse {
+    /// ID periode
+    #[schema(example = 1)]
+    period_id: i32,
+
+    /// Klaster, terbesar lebih dulu
+    clusters: Vec<DocumentClusterResponse>,
+}
+
+/// Mendapatkan antrean tinjauan pendaftaran ganda
+///
+/// Endpoint ini mengembalikan pendaftaran yang memiliki NISN siswa atau NIK orang tua
+/// yang sama dengan pendaftaran aktif lain di tahun ajaran yang sama, lintas sekolah,
+/// serta pendaftaran dengan dokumen yang isinya sama atau gambarnya mirip.
+#[utoipa::path(
+    get,
+    path = "/api/duplicates",
+    tag = "Duplicates",
+    params(ListDuplicateFlagsQuery),
+    responses(
+        (status = 200, description = "Daftar temuan berhasil diambil", body = ListDuplicateFlagsResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_duplicate_flags(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ListDuplicateFlagsQuery>,
+) -> AppResult<Json<ListDuplicateFlagsResponse>> {
+    let school_id = review_scope(&auth_user)?;
+
+    let (flags, total) = duplicate_service(&state)
+        .list_flags(
+            school_id,
+            query.status,
+            query.match_type,
+            query.page,
+            query.page_size,
+        )
+        .await?;
+
+    let total_pages = (total as f64 / query.page_size as f64).ceil() as i64;
+
+    Ok(Json(ListDuplicateFlagsResponse {
+        flags: flags.into_iter().map(|f| f.into()).collect(),
+        total,
+        page: query.page,
+        page_size: query.page_size,
+        total_pages,
+    }))
+}
+
+/// Laporan klaster dokumen ganda dalam periode
+///
+/// Endpoint ini mengelompokkan dokumen yang isinya sama atau gambarnya mirip di
+/// beberapa pendaftaran, misalnya satu sertifikat atau KK yang dipakai untuk beberapa
+/// anak berbeda. Hanya klaster yang melibatkan pendaftaran di periode ini yang ditampilkan.
+#[utoipa::path(
+    get,
+    path = "/api/duplicates/document-clusters",
+    tag = "Duplicates",
+    params(DocumentClustersQuery),
+    responses(
+        (status = 200, description = "Laporan berhasil diambil", body = DocumentClustersResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Periode tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_document_clusters(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<DocumentClustersQuery>,
+) -> AppResult<Json<DocumentClustersResponse>> {
+    let school_id = review_scope(&auth_user)?;
+
+    let clusters = duplicate_service(&state)
+        .document_clusters(query.period_id, school_id)
+        .await?;
+
+    Ok(Json(DocumentClustersResponse {
+        period_id: query.period_id,
+        clusters: clusters.into_iter().map(|c| c.into()).collect(),
+    }))
+}
+
+/// Mendapatkan detail temuan pendaftaran ganda
+#[utoipa::path(
+    get,
+    path = "/api/duplicates/{id}",
+    tag = "Duplicates",
+    params(
+        ("id" = i32, Path, description = "ID temuan")
+    ),
+    responses(
+        (status = 200, description = "Detail temuan berhasil diambil", body = DuplicateFlagResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Temuan tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_duplicate_flag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<DuplicateFlagResponse>> {
+    let school_id = review_scope(&auth_user)?;
+
+    let flag = duplicate_service(&state).get_flag(id, school_id).await?;
+
+    Ok(Json(flag.into()))
+}
+
+/// Meninjau temuan pendaftaran ganda
+///
+/// Temuan ditandai `dismissed` bila bukan duplikat (misalnya saudara kandung)
+/// atau `confirmed` bila benar merupakan pendaftaran ganda.
+#[utoipa::path(
+    post,
+    path = "/api/duplicates/{id}/review",
+    tag = "Duplicates",
+    params(
+        ("id" = i32, Path, description = "ID temuan")
+    ),
+    request_body = ReviewDuplicateFlagRequest,
+    responses(
+        (status = 200, description = "Temuan berhasil ditinjau", body = DuplicateFlagResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Temuan tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn review_duplicate_flag(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<ReviewDuplicateFlagRequest>,
+) -> AppResult<Json<DuplicateFlagResponse>> {
+    let school_id = review_scope(&auth_user)?;
+
+    let flag = duplicate_service(&state)
+        .review_flag(id, school_id, payload.status, payload.notes, auth_user.id)
+        .await?;
+
+    Ok(Json(flag.into()))
+}
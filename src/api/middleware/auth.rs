@@ -14,6 +14,7 @@ pub struct AuthUser {
     pub email: String,
     pub role: String,
     pub school_id: Option<i32>,
+    pub region_id: Option<i32>,
 }
 
 pub async fn auth_middleware(
@@ -51,6 +52,7 @@ pub async fn auth_middleware(
         email: claims.email,
         role: claims.role,
         school_id: claims.school_id,
+        region_id: claims.region_id,
     };
 
     // Insert user into request extensions
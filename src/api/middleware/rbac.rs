@@ -15,6 +15,19 @@ pub async fn require_super_admin(req: Request, next: Next) -> Result<Response, S
     Ok(next.run(req).await)
 }
 
+pub async fn require_region_admin(req: Request, next: Next) -> Result<Response, StatusCode> {
+    let auth_user = req
+        .extensions()
+        .get::<AuthUser>()
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if auth_user.role != "region_admin" && auth_user.role != "super_admin" {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(next.run(req).await)
+}
+
 pub async fn require_school_admin(req: Request, next: Next) -> Result<Response, StatusCode> {
     let auth_user = req
         .extensions()
@@ -1,15 +1,21 @@
 use axum::Router;
 
+pub mod allocations;
 pub mod announcements;
+pub mod appeals;
+pub mod assistance;
 pub mod auth;
 pub mod docs;
+pub mod duplicates;
 pub mod health;
 pub mod middleware;
 pub mod periods;
+pub mod regions;
 pub mod registrations;
 pub mod schemas;
 pub mod schools;
 pub mod selection;
+pub mod test_sessions;
 pub mod users;
 pub mod verifications;
 
@@ -18,12 +24,18 @@ use crate::AppState;
 pub fn routes(state: AppState) -> Router {
     Router::new()
         .nest("/auth", auth::routes(state.clone()))
+        .nest("/regions", regions::routes(state.clone()))
         .nest("/schools", schools::routes(state.clone()))
         .nest("/users", users::routes(state.clone()))
         .nest("/periods", periods::routes(state.clone()))
         .nest("/registrations", registrations::routes(state.clone()))
         .nest("/verifications", verifications::routes(state.clone()))
+        .nest("/duplicates", duplicates::routes(state.clone()))
+        .nest("/assistance-lists", assistance::routes(state.clone()))
+        .nest("/test-sessions", test_sessions::routes(state.clone()))
         .nest("/selection", selection::routes(state.clone()))
         .nest("/announcements", announcements::routes(state.clone()))
+        .nest("/appeals", appeals::routes(state.clone()))
+        .nest("/allocations", allocations::routes(state.clone()))
         .with_state(state)
 }
@@ -5,28 +5,46 @@ use axum::{
     routing::{delete, get, post, put},
     Extension, Json, Router,
 };
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use crate::api::middleware::auth::{auth_middleware, AuthUser};
 use crate::api::middleware::rbac::require_school_admin;
-use crate::models::period::{Period, RegistrationPath};
+use crate::models::achievement::AchievementPointTable;
+use crate::models::age_rule::AgeRules;
+use crate::models::major::PeriodMajor;
+use crate::models::period::{Period, PeriodStage, RegistrationPath};
 use crate::repositories::period_repo::PeriodRepository;
-use crate::services::period_service::PeriodService;
+use crate::services::period_service::{ClonedPeriod, PeriodService};
+use crate::services::scoring_service;
 use crate::utils::error::{AppError, AppResult};
 use crate::AppState;
 
 pub fn routes(state: AppState) -> Router<AppState> {
+    // Path data schemas and point tables are needed by parents to fill in the registration form
+    let public_routes = Router::new()
+        .route("/paths/:path_id/schema", get(get_path_schema))
+        .route("/:id/achievement-point-table", get(get_achievement_point_table))
+        .route("/:id/majors", get(get_majors))
+        .route("/:id/stages", get(get_stages))
+        .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
+
     Router::new()
         .route("/", get(list_periods).post(create_period))
         .route("/:id", get(get_period).put(update_period).delete(delete_period))
         .route("/:id/activate", post(activate_period))
         .route("/:id/close", post(close_period))
+        .route("/:id/clone", post(clone_period))
         .route("/:id/paths", get(get_paths).post(create_path))
         .route("/paths/:path_id", put(update_path).delete(delete_path))
+        .route("/:id/majors", post(create_major))
+        .route("/majors/:major_id", put(update_major).delete(delete_major))
+        .route("/:id/stages", post(create_stage))
+        .route("/stages/:stage_id", put(update_stage).delete(delete_stage))
         .route_layer(middleware::from_fn(require_school_admin))
         .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware))
+        .merge(public_routes)
 }
 
 /// Request untuk membuat periode PPDB baru
@@ -48,10 +66,71 @@ pub struct CreatePeriodRequest {
     #[schema(value_type = String, example = "2024-07-31")]
     end_date: NaiveDate,
     
+    /// Tanggal mulai pendaftaran (opsional, default tanggal mulai periode)
+    #[schema(value_type = Option<String>, example = "2024-06-01")]
+    registration_start: Option<NaiveDate>,
+    
+    /// Tanggal akhir pendaftaran (opsional, default tanggal akhir periode)
+    #[schema(value_type = Option<String>, example = "2024-06-30")]
+    registration_end: Option<NaiveDate>,
+    
+    /// Waktu pengumuman otomatis (opsional, setelah pendaftaran ditutup)
+    #[schema(value_type = Option<String>, example = "2024-07-05T08:00:00Z")]
+    announcement_at: Option<DateTime<Utc>>,
+    
     /// Batas waktu daftar ulang (opsional)
     #[schema(value_type = Option<String>, example = "2024-08-15")]
     reenrollment_deadline: Option<NaiveDate>,
     
+    /// Jam penutupan pendaftaran dan daftar ulang menurut zona waktu sekolah
+    /// (opsional, default: 23:59:59)
+    #[schema(value_type = Option<String>, example = "16:00:00")]
+    closing_time: Option<NaiveTime>,
+    
+    /// Aktivasi dan pengumuman otomatis sesuai jadwal (opsional, default: true)
+    #[schema(example = true)]
+    auto_lifecycle: Option<bool>,
+    
+    /// Kebijakan pendaftaran ganda lintas sekolah (block/warn/allow, default: warn)
+    #[schema(example = "warn")]
+    duplicate_policy: Option<String>,
+    
+    /// Kebijakan kursi pendaftar diterima yang mengundurkan diri: `none` (kursi
+    /// dibiarkan kosong) atau `promote` (diberikan ke peringkat berikutnya yang
+    /// tidak masuk kuota), default: none
+    #[schema(example = "promote")]
+    waitlist_policy: Option<String>,
+    
+    /// Kebijakan pengembalian pembayaran saat pendaftar mengundurkan diri
+    /// (none/full/before_announcement, default: none)
+    #[schema(example = "before_announcement")]
+    refund_policy: Option<String>,
+    
+    /// Lama masa sanggah setelah pengumuman dalam hari, 0 untuk menutup sanggahan
+    /// (opsional, default: 3)
+    #[schema(example = 3)]
+    appeal_window_days: Option<i32>,
+    
+    /// Tindak lanjut saat dokumen wajib ditolak verifikator: dikembalikan untuk
+    /// perbaikan atau pendaftaran ditolak (revision/reject, default: revision)
+    #[schema(example = "revision")]
+    document_rejection_policy: Option<String>,
+    
+    /// Tabel poin prestasi (opsional, default tabel poin bawaan)
+    #[schema(value_type = Option<Object>)]
+    achievement_point_table: Option<serde_json::Value>,
+    
+    /// Aturan usia: `reference_date`, `min_age`, `max_age`, `priority_age`
+    /// (`{"years": 7, "months": 0}`), `priority_bonus`, `points_per_month`, dan
+    /// `max_age_points` (opsional, default sesuai jenjang)
+    #[schema(value_type = Option<Object>, example = json!({
+        "reference_date": "2024-07-01",
+        "min_age": {"years": 6, "months": 0},
+        "priority_age": {"years": 7, "months": 0},
+        "priority_bonus": 10
+    }))]
+    age_rules: Option<serde_json::Value>,
+    
     /// Daftar jalur pendaftaran
     paths: Vec<CreatePathRequest>,
 }
@@ -78,6 +157,21 @@ pub struct CreatePathRequest {
     /// Konfigurasi penilaian dalam format JSON
     #[schema(value_type = Object)]
     scoring_config: serde_json::Value,
+    
+    /// Urutan pemrosesan jalur saat seleksi (lebih kecil diproses lebih dulu)
+    #[serde(default)]
+    #[schema(example = 1)]
+    selection_order: i32,
+    
+    /// Skema data jalur (subset JSON Schema) untuk `path_data` pendaftar (opsional,
+    /// default mengikuti tipe jalur)
+    #[schema(value_type = Option<Object>)]
+    path_data_schema: Option<serde_json::Value>,
+    
+    /// Jenis dokumen yang wajib diunggah pendaftar jalur ini
+    #[serde(default)]
+    #[schema(example = json!(["kartu_keluarga", "akta_kelahiran"]))]
+    required_documents: Vec<String>,
 }
 
 /// Request untuk update periode PPDB
@@ -91,13 +185,62 @@ pub struct UpdatePeriodRequest {
     #[schema(value_type = Option<String>, example = "2024-07-31")]
     end_date: Option<NaiveDate>,
     
+    /// Tanggal mulai pendaftaran (opsional)
+    #[schema(value_type = Option<String>, example = "2024-06-01")]
+    registration_start: Option<NaiveDate>,
+    
+    /// Tanggal akhir pendaftaran (opsional)
+    #[schema(value_type = Option<String>, example = "2024-06-30")]
+    registration_end: Option<NaiveDate>,
+    
     /// Tanggal pengumuman (opsional)
     #[schema(value_type = Option<String>, example = "2024-08-01")]
     announcement_date: Option<NaiveDate>,
     
+    /// Waktu pengumuman otomatis (opsional)
+    #[schema(value_type = Option<String>, example = "2024-07-05T08:00:00Z")]
+    announcement_at: Option<DateTime<Utc>>,
+    
     /// Batas waktu daftar ulang (opsional)
     #[schema(value_type = Option<String>, example = "2024-08-15")]
     reenrollment_deadline: Option<NaiveDate>,
+    
+    /// Jam penutupan pendaftaran dan daftar ulang menurut zona waktu sekolah (opsional)
+    #[schema(value_type = Option<String>, example = "16:00:00")]
+    closing_time: Option<NaiveTime>,
+    
+    /// Aktivasi dan pengumuman otomatis sesuai jadwal (opsional)
+    #[schema(example = false)]
+    auto_lifecycle: Option<bool>,
+    
+    /// Kebijakan pendaftaran ganda lintas sekolah (block/warn/allow, opsional)
+    #[schema(example = "block")]
+    duplicate_policy: Option<String>,
+    
+    /// Kebijakan kursi pendaftar diterima yang mengundurkan diri (none/promote, opsional)
+    #[schema(example = "promote")]
+    waitlist_policy: Option<String>,
+    
+    /// Kebijakan pengembalian pembayaran saat pendaftar mengundurkan diri
+    /// (none/full/before_announcement, opsional)
+    #[schema(example = "full")]
+    refund_policy: Option<String>,
+    
+    /// Lama masa sanggah setelah pengumuman dalam hari (opsional)
+    #[schema(example = 5)]
+    appeal_window_days: Option<i32>,
+    
+    /// Tindak lanjut saat dokumen wajib ditolak verifikator (revision/reject, opsional)
+    #[schema(example = "reject")]
+    document_rejection_policy: Option<String>,
+    
+    /// Tabel poin prestasi (opsional)
+    #[schema(value_type = Option<Object>)]
+    achievement_point_table: Option<serde_json::Value>,
+    
+    /// Aturan usia (opsional)
+    #[schema(value_type = Option<Object>)]
+    age_rules: Option<serde_json::Value>,
 }
 
 /// Request untuk update jalur pendaftaran
@@ -118,6 +261,18 @@ pub struct UpdatePathRequest {
     /// Konfigurasi penilaian dalam format JSON (opsional)
     #[schema(value_type = Option<Object>)]
     scoring_config: Option<serde_json::Value>,
+    
+    /// Urutan pemrosesan jalur saat seleksi (opsional)
+    #[schema(example = 2)]
+    selection_order: Option<i32>,
+    
+    /// Skema data jalur untuk `path_data` pendaftar (opsional)
+    #[schema(value_type = Option<Object>)]
+    path_data_schema: Option<serde_json::Value>,
+    
+    /// Jenis dokumen wajib (opsional, menggantikan daftar sebelumnya)
+    #[schema(example = json!(["kartu_keluarga", "akta_kelahiran"]))]
+    required_documents: Option<Vec<String>>,
 }
 
 /// Query parameters untuk list periode
@@ -194,14 +349,79 @@ pub struct PeriodResponse {
     #[schema(value_type = Option<String>, example = "2024-08-01")]
     announcement_date: Option<NaiveDate>,
     
+    /// Waktu pengumuman otomatis
+    #[schema(value_type = Option<String>, example = "2024-07-05T08:00:00Z")]
+    announcement_at: Option<DateTime<Utc>>,
+    
     /// Batas waktu daftar ulang
     #[schema(value_type = Option<String>, example = "2024-08-15")]
     reenrollment_deadline: Option<NaiveDate>,
     
+    /// Jam penutupan pendaftaran dan daftar ulang menurut zona waktu sekolah
+    #[schema(value_type = String, example = "23:59:59")]
+    closing_time: NaiveTime,
+    
+    /// Waktu pendaftaran dibuka
+    #[schema(value_type = String, example = "2024-05-31T17:00:00Z")]
+    registration_opens_at: DateTime<Utc>,
+    
+    /// Waktu pendaftaran ditutup
+    #[schema(value_type = String, example = "2024-06-30T16:59:59Z")]
+    registration_closes_at: DateTime<Utc>,
+    
+    /// Waktu batas daftar ulang berakhir
+    #[schema(value_type = Option<String>, example = "2024-08-15T16:59:59Z")]
+    reenrollment_closes_at: Option<DateTime<Utc>>,
+    
+    /// Aktivasi dan pengumuman otomatis sesuai jadwal
+    #[schema(example = true)]
+    auto_lifecycle: bool,
+    
+    /// ID periode sumber jika periode ini hasil salinan
+    #[schema(example = json!(null))]
+    cloned_from_id: Option<i32>,
+    
     /// Status periode (draft/active/closed)
     #[schema(example = "active")]
     status: String,
     
+    /// Kebijakan pendaftaran ganda lintas sekolah (block/warn/allow)
+    #[schema(example = "warn")]
+    duplicate_policy: String,
+    
+    /// Kebijakan kursi pendaftar diterima yang mengundurkan diri (none/promote)
+    #[schema(example = "none")]
+    waitlist_policy: String,
+    
+    /// Kebijakan pengembalian pembayaran saat pendaftar mengundurkan diri
+    /// (none/full/before_announcement)
+    #[schema(example = "none")]
+    refund_policy: String,
+    
+    /// Lama masa sanggah setelah pengumuman dalam hari
+    #[schema(example = 3)]
+    appeal_window_days: i32,
+    
+    /// Tindak lanjut saat dokumen wajib ditolak verifikator (revision/reject)
+    #[schema(example = "revision")]
+    document_rejection_policy: String,
+    
+    /// Tabel poin prestasi yang berlaku
+    #[schema(value_type = Object)]
+    achievement_point_table: serde_json::Value,
+    
+    /// Aturan usia yang berlaku, dengan tanggal acuan yang sudah ditentukan
+    #[schema(value_type = Object, example = json!({
+        "reference_date": "2024-07-01",
+        "min_age": null,
+        "max_age": {"years": 21, "months": 11},
+        "priority_age": null,
+        "priority_bonus": 0,
+        "points_per_month": 0,
+        "max_age_points": null
+    }))]
+    age_rules: serde_json::Value,
+    
     /// Waktu pembuatan
     #[schema(value_type = String, example = "2024-01-01T00:00:00Z")]
     created_at: DateTime<Utc>,
@@ -213,6 +433,8 @@ pub struct PeriodResponse {
 
 impl From<Period> for PeriodResponse {
     fn from(period: Period) -> Self {
+        let age_rules = effective_age_rules(&period);
+
         Self {
             id: period.id,
             school_id: period.school_id,
@@ -223,8 +445,22 @@ impl From<Period> for PeriodResponse {
             registration_start: period.registration_start,
             registration_end: period.registration_end,
             announcement_date: period.announcement_date,
+            announcement_at: period.announcement_at,
             reenrollment_deadline: period.reenrollment_deadline,
+            closing_time: period.closing_time,
+            registration_opens_at: period.registration_opens_at,
+            registration_closes_at: period.registration_closes_at,
+            reenrollment_closes_at: period.reenrollment_closes_at,
+            cloned_from_id: period.cloned_from_id,
+            auto_lifecycle: period.auto_lifecycle,
             status: period.status,
+            duplicate_policy: period.duplicate_policy,
+            waitlist_policy: period.waitlist_policy,
+            refund_policy: period.refund_policy,
+            appeal_window_days: period.appeal_window_days,
+            document_rejection_policy: period.document_rejection_policy,
+            achievement_point_table: effective_point_table(&period.achievement_point_table),
+            age_rules,
             created_at: period.created_at,
             updated_at: period.updated_at,
         }
@@ -241,6 +477,56 @@ pub struct PeriodWithPathsResponse {
     paths: Vec<PathResponse>,
 }
 
+/// Request untuk menyalin periode ke tahun ajaran baru
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ClonePeriodRequest {
+    /// Tahun ajaran periode baru (format: YYYY/YYYY)
+    #[schema(example = "2025/2026")]
+    academic_year: String,
+
+    /// Tanggal mulai periode baru (opsional). Jika diisi, semua tanggal disusun
+    /// ulang dengan selisih yang sama dari tanggal ini; jika tidak, tanggal
+    /// digeser sesuai selisih tahun ajaran.
+    #[schema(value_type = Option<String>, example = "2025-05-01")]
+    start_date: Option<NaiveDate>,
+}
+
+/// Response hasil salin periode
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClonePeriodResponse {
+    /// Periode baru (draft)
+    period: PeriodResponse,
+
+    /// Jalur pendaftaran hasil salinan
+    paths: Vec<PathResponse>,
+
+    /// Tahap pendaftaran hasil salinan
+    stages: Vec<StageResponse>,
+
+    /// Jurusan hasil salinan (SMK)
+    majors: Vec<MajorResponse>,
+
+    /// Hal yang perlu diperiksa sebelum periode diaktifkan
+    #[schema(example = json!(["Path 'Jalur Prestasi' has no required documents"]))]
+    warnings: Vec<String>,
+}
+
+impl From<ClonedPeriod> for ClonePeriodResponse {
+    fn from(cloned: ClonedPeriod) -> Self {
+        Self {
+            stages: cloned
+                .stages
+                .into_iter()
+                .map(|stage| StageResponse::new(stage, &cloned.paths))
+                .collect(),
+            period: cloned.period.into(),
+            paths: cloned.paths.into_iter().map(|p| p.into()).collect(),
+            majors: cloned.majors.into_iter().map(|m| m.into()).collect(),
+            warnings: cloned.warnings,
+        }
+    }
+}
+
 /// Response data jalur pendaftaran
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PathResponse {
@@ -272,6 +558,22 @@ pub struct PathResponse {
     #[schema(value_type = Object)]
     scoring_config: serde_json::Value,
     
+    /// Urutan pemrosesan jalur saat seleksi
+    #[schema(example = 1)]
+    selection_order: i32,
+    
+    /// Skema data jalur yang berlaku untuk `path_data` pendaftar
+    #[schema(value_type = Object)]
+    path_data_schema: serde_json::Value,
+    
+    /// ID tahap yang memuat jalur ini (kosong jika periode tidak bertahap)
+    #[schema(example = 1)]
+    stage_id: Option<i32>,
+    
+    /// Jenis dokumen yang wajib diunggah pendaftar jalur ini
+    #[schema(example = json!(["kartu_keluarga", "akta_kelahiran"]))]
+    required_documents: Vec<String>,
+    
     /// Waktu pembuatan
     #[schema(value_type = String, example = "2024-01-01T00:00:00Z")]
     created_at: DateTime<Utc>,
@@ -283,6 +585,8 @@ pub struct PathResponse {
 
 impl From<RegistrationPath> for PathResponse {
     fn from(path: RegistrationPath) -> Self {
+        let path_data_schema = scoring_service::path_data_schema(&path);
+
         Self {
             id: path.id,
             period_id: path.period_id,
@@ -291,12 +595,296 @@ impl From<RegistrationPath> for PathResponse {
             quota: path.quota,
             description: path.description,
             scoring_config: path.scoring_config,
+            selection_order: path.selection_order,
+            path_data_schema,
+            stage_id: path.stage_id,
+            required_documents: path.required_documents,
             created_at: path.created_at,
             updated_at: path.updated_at,
         }
     }
 }
 
+/// Response skema data jalur
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PathSchemaResponse {
+    /// ID jalur
+    #[schema(example = 1)]
+    path_id: i32,
+    
+    /// Tipe jalur
+    #[schema(example = "zonasi")]
+    path_type: String,
+    
+    /// Nama jalur
+    #[schema(example = "Jalur Zonasi")]
+    name: String,
+    
+    /// Skema `path_data` (subset JSON Schema: type, properties, required, minimum,
+    /// maximum, minLength, maxLength, enum, additionalProperties)
+    #[schema(value_type = Object, example = json!({
+        "type": "object",
+        "required": ["distance_km"],
+        "properties": {
+            "distance_km": {"type": "number", "minimum": 0, "title": "Jarak rumah ke sekolah (km)"}
+        }
+    }))]
+    path_data_schema: serde_json::Value,
+}
+
+/// The period's achievement point table, or the default one
+/// Request untuk membuat jurusan (SMK)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateMajorRequest {
+    /// Kode jurusan (unik per periode)
+    #[schema(example = "TKJ")]
+    code: String,
+
+    /// Nama jurusan
+    #[schema(example = "Teknik Komputer dan Jaringan")]
+    name: String,
+
+    /// Daya tampung jurusan
+    #[schema(example = 72)]
+    capacity: i32,
+
+    /// Deskripsi jurusan
+    #[schema(example = "Kompetensi keahlian jaringan komputer")]
+    description: Option<String>,
+
+    /// Persyaratan tambahan jurusan
+    #[schema(value_type = Option<Vec<Object>>, example = json!([
+        {"type": "subject_grade", "subject": "matematika", "min_average": 75},
+        {"type": "requirement", "key": "color_vision", "label": "Tidak buta warna"}
+    ]))]
+    criteria: Option<serde_json::Value>,
+}
+
+/// Request untuk update jurusan
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMajorRequest {
+    /// Nama jurusan
+    #[schema(example = "Teknik Komputer dan Jaringan")]
+    name: Option<String>,
+
+    /// Daya tampung jurusan
+    #[schema(example = 72)]
+    capacity: Option<i32>,
+
+    /// Deskripsi jurusan
+    #[schema(example = "Kompetensi keahlian jaringan komputer")]
+    description: Option<String>,
+
+    /// Persyaratan tambahan jurusan
+    #[schema(value_type = Option<Vec<Object>>)]
+    criteria: Option<serde_json::Value>,
+}
+
+/// Response data jurusan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MajorResponse {
+    /// ID jurusan
+    #[schema(example = 3)]
+    id: i32,
+
+    /// ID periode
+    #[schema(example = 1)]
+    period_id: i32,
+
+    /// Kode jurusan
+    #[schema(example = "TKJ")]
+    code: String,
+
+    /// Nama jurusan
+    #[schema(example = "Teknik Komputer dan Jaringan")]
+    name: String,
+
+    /// Daya tampung jurusan
+    #[schema(example = 72)]
+    capacity: i32,
+
+    /// Deskripsi jurusan
+    #[schema(example = "Kompetensi keahlian jaringan komputer")]
+    description: Option<String>,
+
+    /// Persyaratan tambahan jurusan
+    #[schema(value_type = Vec<Object>)]
+    criteria: serde_json::Value,
+
+    /// Waktu pembuatan
+    #[schema(value_type = String, example = "2024-01-01T00:00:00Z")]
+    created_at: DateTime<Utc>,
+}
+
+impl From<PeriodMajor> for MajorResponse {
+    fn from(major: PeriodMajor) -> Self {
+        Self {
+            id: major.id,
+            period_id: major.period_id,
+            code: major.code,
+            name: major.name,
+            capacity: major.capacity,
+            description: major.description,
+            criteria: major.criteria,
+            created_at: major.created_at,
+        }
+    }
+}
+
+/// Request untuk membuat tahap pendaftaran
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateStageRequest {
+    /// Nama tahap
+    #[schema(example = "Tahap 1")]
+    name: String,
+
+    /// Tanggal mulai pendaftaran tahap (dalam rentang pendaftaran periode)
+    #[schema(value_type = String, example = "2024-06-01")]
+    registration_start: NaiveDate,
+
+    /// Tanggal akhir pendaftaran tahap
+    #[schema(value_type = String, example = "2024-06-10")]
+    registration_end: NaiveDate,
+
+    /// Waktu pengumuman hasil tahap
+    #[schema(value_type = Option<String>, example = "2024-06-15T01:00:00Z")]
+    announcement_at: Option<DateTime<Utc>>,
+
+    /// ID jalur yang dibuka pada tahap ini
+    #[schema(example = json!([1, 2]))]
+    path_ids: Vec<i32>,
+}
+
+/// Request untuk update tahap pendaftaran
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateStageRequest {
+    /// Nama tahap
+    #[schema(example = "Tahap 1")]
+    name: Option<String>,
+
+    /// Tanggal mulai pendaftaran tahap
+    #[schema(value_type = Option<String>, example = "2024-06-01")]
+    registration_start: Option<NaiveDate>,
+
+    /// Tanggal akhir pendaftaran tahap
+    #[schema(value_type = Option<String>, example = "2024-06-10")]
+    registration_end: Option<NaiveDate>,
+
+    /// Waktu pengumuman hasil tahap
+    #[schema(value_type = Option<String>, example = "2024-06-15T01:00:00Z")]
+    announcement_at: Option<DateTime<Utc>>,
+
+    /// ID jalur yang dibuka pada tahap ini (menggantikan daftar sebelumnya)
+    #[schema(example = json!([1, 2]))]
+    path_ids: Option<Vec<i32>>,
+}
+
+/// Response data tahap pendaftaran
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StageResponse {
+    /// ID tahap
+    #[schema(example = 1)]
+    id: i32,
+
+    /// ID periode
+    #[schema(example = 1)]
+    period_id: i32,
+
+    /// Urutan tahap
+    #[schema(example = 1)]
+    stage_number: i32,
+
+    /// Nama tahap
+    #[schema(example = "Tahap 1")]
+    name: String,
+
+    /// Tanggal mulai pendaftaran tahap
+    #[schema(value_type = String, example = "2024-06-01")]
+    registration_start: NaiveDate,
+
+    /// Tanggal akhir pendaftaran tahap
+    #[schema(value_type = String, example = "2024-06-10")]
+    registration_end: NaiveDate,
+
+    /// Waktu pendaftaran tahap dibuka (zona waktu sekolah)
+    #[schema(value_type = String, example = "2024-05-31T17:00:00Z")]
+    registration_opens_at: DateTime<Utc>,
+
+    /// Waktu pendaftaran tahap ditutup (jam penutupan periode)
+    #[schema(value_type = String, example = "2024-06-10T16:59:59Z")]
+    registration_closes_at: DateTime<Utc>,
+
+    /// Waktu pengumuman hasil tahap
+    #[schema(value_type = Option<String>, example = "2024-06-15T01:00:00Z")]
+    announcement_at: Option<DateTime<Utc>>,
+
+    /// Tanggal hasil tahap diumumkan
+    #[schema(value_type = Option<String>, example = "2024-06-15")]
+    announcement_date: Option<NaiveDate>,
+
+    /// ID jalur yang dibuka pada tahap ini
+    #[schema(example = json!([1, 2]))]
+    path_ids: Vec<i32>,
+}
+
+impl StageResponse {
+    fn new(stage: PeriodStage, paths: &[RegistrationPath]) -> Self {
+        Self {
+            path_ids: paths
+                .iter()
+                .filter(|p| p.stage_id == Some(stage.id))
+                .map(|p| p.id)
+                .collect(),
+            id: stage.id,
+            period_id: stage.period_id,
+            stage_number: stage.stage_number,
+            name: stage.name,
+            registration_start: stage.registration_start,
+            registration_end: stage.registration_end,
+            registration_opens_at: stage.registration_opens_at,
+            registration_closes_at: stage.registration_closes_at,
+            announcement_at: stage.announcement_at,
+            announcement_date: stage.announcement_date,
+        }
+    }
+}
+
+fn effective_point_table(table: &Option<serde_json::Value>) -> serde_json::Value {
+    table.clone().unwrap_or_else(|| {
+        serde_json::to_value(AchievementPointTable::default()).unwrap_or_default()
+    })
+}
+
+/// The period's age rules, or the defaults of its level
+fn effective_age_rules(period: &Period) -> serde_json::Value {
+    AgeRules::for_period(period)
+        .ok()
+        .and_then(|rules| serde_json::to_value(rules).ok())
+        .unwrap_or_default()
+}
+
+/// Response tabel poin prestasi periode
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AchievementPointTableResponse {
+    /// ID periode
+    #[schema(example = 1)]
+    period_id: i32,
+    
+    /// Tabel poin: `points` per tingkat dan peringkat ("1", "2", "3", "participant"),
+    /// `team_multiplier`, `level_caps`, `best_n`, dan `max_points`
+    #[schema(value_type = Object, example = json!({
+        "points": {
+            "national": {"1": 80, "2": 70, "3": 60, "participant": 30},
+            "province": {"1": 60, "2": 50, "3": 40, "participant": 15}
+        },
+        "team_multiplier": 0.8,
+        "level_caps": {"school": 20},
+        "best_n": 3,
+        "max_points": 100
+    }))]
+    achievement_point_table: serde_json::Value,
+}
+
 /// Response list periode dengan pagination
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ListPeriodsResponse {
@@ -378,7 +966,19 @@ async fn create_period(
             payload.level,
             payload.start_date,
             payload.end_date,
+            payload.registration_start,
+            payload.registration_end,
+            payload.announcement_at,
             payload.reenrollment_deadline,
+            payload.closing_time,
+            payload.duplicate_policy,
+            payload.waitlist_policy,
+            payload.refund_policy,
+            payload.appeal_window_days,
+            payload.document_rejection_policy,
+            payload.achievement_point_table,
+            payload.age_rules,
+            payload.auto_lifecycle,
         )
         .await?;
 
@@ -393,6 +993,9 @@ async fn create_period(
                 path_req.quota,
                 path_req.description,
                 path_req.scoring_config,
+                path_req.selection_order,
+                path_req.path_data_schema,
+                path_req.required_documents,
             )
             .await?;
         paths.push(path.into());
@@ -534,8 +1137,20 @@ async fn update_period(
             id,
             payload.start_date,
             payload.end_date,
+            payload.registration_start,
+            payload.registration_end,
             payload.announcement_date,
+            payload.announcement_at,
             payload.reenrollment_deadline,
+            payload.closing_time,
+            payload.duplicate_policy,
+            payload.waitlist_policy,
+            payload.refund_policy,
+            payload.appeal_window_days,
+            payload.document_rejection_policy,
+            payload.achievement_point_table,
+            payload.age_rules,
+            payload.auto_lifecycle,
         )
         .await?;
 
@@ -646,6 +1261,52 @@ async fn close_period(
     Ok(Json(period.into()))
 }
 
+/// Salin periode ke tahun ajaran baru
+///
+/// Endpoint ini menyalin periode beserta jalur, kuota, konfigurasi penilaian,
+/// dokumen wajib, jurusan, dan tahap ke tahun ajaran baru sebagai draft.
+/// Response memuat daftar hal yang perlu diperiksa sebelum aktivasi.
+#[utoipa::path(
+    post,
+    path = "/api/periods/{id}/clone",
+    tag = "Periods",
+    params(
+        ("id" = i32, Path, description = "ID periode sumber")
+    ),
+    request_body = ClonePeriodRequest,
+    responses(
+        (status = 201, description = "Periode berhasil disalin", body = ClonePeriodResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Periode tidak ditemukan"),
+        (status = 409, description = "Periode untuk tahun ajaran dan jenjang ini sudah ada")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn clone_period(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<ClonePeriodRequest>,
+) -> AppResult<(StatusCode, Json<ClonePeriodResponse>)> {
+    let school_id = auth_user.school_id.ok_or_else(|| {
+        AppError::Authentication("User must be associated with a school".to_string())
+    })?;
+
+    // Create period service
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let period_service = PeriodService::new(period_repo);
+
+    let cloned = period_service
+        .clone_period(id, school_id, payload.academic_year, payload.start_date)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(cloned.into())))
+}
+
 /// Mendapatkan daftar jalur pendaftaran
 ///
 /// Endpoint ini mengembalikan daftar jalur pendaftaran untuk periode tertentu.
@@ -724,6 +1385,9 @@ async fn create_path(
             payload.quota,
             payload.description,
             payload.scoring_config,
+            payload.selection_order,
+            payload.path_data_schema,
+            payload.required_documents,
         )
         .await?;
 
@@ -770,6 +1434,9 @@ async fn update_path(
             payload.quota,
             payload.description,
             payload.scoring_config,
+            payload.selection_order,
+            payload.path_data_schema,
+            payload.required_documents,
         )
         .await?;
 
@@ -811,3 +1478,397 @@ async fn delete_path(
         message: "Registration path deleted successfully".to_string(),
     }))
 }
+
+/// Mendapatkan skema data jalur pendaftaran
+///
+/// Endpoint ini mengembalikan skema `path_data` yang divalidasi saat pendaftaran dibuat,
+/// diupdate, dan disubmit, sehingga frontend dapat menampilkan formulir jalur.
+#[utoipa::path(
+    get,
+    path = "/api/periods/paths/{path_id}/schema",
+    tag = "Periods",
+    params(
+        ("path_id" = i32, Path, description = "ID jalur pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Skema jalur berhasil diambil", body = PathSchemaResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 404, description = "Jalur tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_path_schema(
+    State(state): State<AppState>,
+    Path(path_id): Path<i32>,
+) -> AppResult<Json<PathSchemaResponse>> {
+    // Create period service
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let period_service = PeriodService::new(period_repo);
+
+    let path = period_service.get_path(path_id).await?;
+
+    Ok(Json(PathSchemaResponse {
+        path_id: path.id,
+        path_data_schema: scoring_service::path_data_schema(&path),
+        path_type: path.path_type,
+        name: path.name,
+    }))
+}
+
+/// Mendapatkan tabel poin prestasi periode
+///
+/// Endpoint ini mengembalikan tabel yang mengubah prestasi pendaftar menjadi poin untuk
+/// jalur prestasi, termasuk batas poin per tingkat dan aturan N prestasi terbaik.
+#[utoipa::path(
+    get,
+    path = "/api/periods/{id}/achievement-point-table",
+    tag = "Periods",
+    params(
+        ("id" = i32, Path, description = "ID periode")
+    ),
+    responses(
+        (status = 200, description = "Tabel poin berhasil diambil", body = AchievementPointTableResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 404, description = "Periode tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_achievement_point_table(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<AchievementPointTableResponse>> {
+    // Create period service
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let period_service = PeriodService::new(period_repo);
+
+    let period = period_service.get_period(id).await?;
+
+    Ok(Json(AchievementPointTableResponse {
+        period_id: period.id,
+        achievement_point_table: effective_point_table(&period.achievement_point_table),
+    }))
+}
+
+/// Mendapatkan daftar jurusan periode (SMK)
+///
+/// Endpoint ini mengembalikan jurusan yang dapat dipilih pendaftar beserta daya tampung
+/// dan persyaratan tambahannya.
+#[utoipa::path(
+    get,
+    path = "/api/periods/{id}/majors",
+    tag = "Periods",
+    params(
+        ("id" = i32, Path, description = "ID periode")
+    ),
+    responses(
+        (status = 200, description = "Daftar jurusan berhasil diambil", body = Vec<MajorResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 404, description = "Periode tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_majors(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<Vec<MajorResponse>>> {
+    // Create period service
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let period_service = PeriodService::new(period_repo);
+
+    let majors = period_service.get_majors_by_period(id).await?;
+
+    Ok(Json(majors.into_iter().map(|m| m.into()).collect()))
+}
+
+/// Membuat jurusan baru
+///
+/// Endpoint ini digunakan untuk menambahkan jurusan ke periode SMK yang masih draft.
+/// Persyaratan `subject_grade` diperiksa otomatis dari nilai rapor, sedangkan
+/// persyaratan `requirement` (misalnya tes buta warna) dicatat oleh verifikator.
+#[utoipa::path(
+    post,
+    path = "/api/periods/{id}/majors",
+    tag = "Periods",
+    params(
+        ("id" = i32, Path, description = "ID periode")
+    ),
+    request_body = CreateMajorRequest,
+    responses(
+        (status = 201, description = "Jurusan berhasil dibuat", body = MajorResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Periode tidak ditemukan"),
+        (status = 409, description = "Kode jurusan sudah digunakan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn create_major(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CreateMajorRequest>,
+) -> AppResult<(StatusCode, Json<MajorResponse>)> {
+    // Create period service
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let period_service = PeriodService::new(period_repo);
+
+    let major = period_service
+        .create_major(
+            id,
+            payload.code,
+            payload.name,
+            payload.capacity,
+            payload.description,
+            payload.criteria,
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(major.into())))
+}
+
+/// Update jurusan
+#[utoipa::path(
+    put,
+    path = "/api/periods/majors/{major_id}",
+    tag = "Periods",
+    params(
+        ("major_id" = i32, Path, description = "ID jurusan")
+    ),
+    request_body = UpdateMajorRequest,
+    responses(
+        (status = 200, description = "Jurusan berhasil diupdate", body = MajorResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Jurusan tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn update_major(
+    State(state): State<AppState>,
+    Path(major_id): Path<i32>,
+    Json(payload): Json<UpdateMajorRequest>,
+) -> AppResult<Json<MajorResponse>> {
+    // Create period service
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let period_service = PeriodService::new(period_repo);
+
+    let major = period_service
+        .update_major(
+            major_id,
+            payload.name,
+            payload.capacity,
+            payload.description,
+            payload.criteria,
+        )
+        .await?;
+
+    Ok(Json(major.into()))
+}
+
+/// Hapus jurusan
+#[utoipa::path(
+    delete,
+    path = "/api/periods/majors/{major_id}",
+    tag = "Periods",
+    params(
+        ("major_id" = i32, Path, description = "ID jurusan")
+    ),
+    responses(
+        (status = 200, description = "Jurusan berhasil dihapus", body = MessageResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Jurusan tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn delete_major(
+    State(state): State<AppState>,
+    Path(major_id): Path<i32>,
+) -> AppResult<Json<MessageResponse>> {
+    // Create period service
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let period_service = PeriodService::new(period_repo);
+
+    period_service.delete_major(major_id).await?;
+
+    Ok(Json(MessageResponse {
+        message: "Major deleted successfully".to_string(),
+    }))
+}
+
+/// Mendapatkan daftar tahap pendaftaran
+///
+/// Endpoint ini mengembalikan tahap-tahap periode secara berurutan beserta jalur
+/// yang dibuka pada masing-masing tahap.
+#[utoipa::path(
+    get,
+    path = "/api/periods/{id}/stages",
+    tag = "Periods",
+    params(
+        ("id" = i32, Path, description = "ID periode")
+    ),
+    responses(
+        (status = 200, description = "Daftar tahap berhasil diambil", body = Vec<StageResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 404, description = "Periode tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_stages(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<Vec<StageResponse>>> {
+    // Create period service
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let period_service = PeriodService::new(period_repo);
+
+    let (stages, paths) = period_service.get_stages_by_period(id).await?;
+
+    Ok(Json(
+        stages
+            .into_iter()
+            .map(|stage| StageResponse::new(stage, &paths))
+            .collect(),
+    ))
+}
+
+/// Membuat tahap pendaftaran baru
+///
+/// Endpoint ini digunakan untuk menambahkan tahap ke periode yang masih draft.
+/// Tahap baru ditempatkan setelah tahap terakhir; sisa kuota tahap sebelumnya
+/// dialihkan ke tahap berikutnya saat seleksi.
+#[utoipa::path(
+    post,
+    path = "/api/periods/{id}/stages",
+    tag = "Periods",
+    params(
+        ("id" = i32, Path, description = "ID periode")
+    ),
+    request_body = CreateStageRequest,
+    responses(
+        (status = 201, description = "Tahap berhasil dibuat", body = StageResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Periode tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn create_stage(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(payload): Json<CreateStageRequest>,
+) -> AppResult<(StatusCode, Json<StageResponse>)> {
+    // Create period service
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let period_service = PeriodService::new(period_repo);
+
+    let (stage, paths) = period_service
+        .create_stage(
+            id,
+            payload.name,
+            payload.registration_start,
+            payload.registration_end,
+            payload.announcement_at,
+            payload.path_ids,
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(StageResponse::new(stage, &paths))))
+}
+
+/// Update tahap pendaftaran
+#[utoipa::path(
+    put,
+    path = "/api/periods/stages/{stage_id}",
+    tag = "Periods",
+    params(
+        ("stage_id" = i32, Path, description = "ID tahap")
+    ),
+    request_body = UpdateStageRequest,
+    responses(
+        (status = 200, description = "Tahap berhasil diupdate", body = StageResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Tahap tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn update_stage(
+    State(state): State<AppState>,
+    Path(stage_id): Path<i32>,
+    Json(payload): Json<UpdateStageRequest>,
+) -> AppResult<Json<StageResponse>> {
+    // Create period service
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let period_service = PeriodService::new(period_repo);
+
+    let (stage, paths) = period_service
+        .update_stage(
+            stage_id,
+            payload.name,
+            payload.registration_start,
+            payload.registration_end,
+            payload.announcement_at,
+            payload.path_ids,
+        )
+        .await?;
+
+    Ok(Json(StageResponse::new(stage, &paths)))
+}
+
+/// Hapus tahap pendaftaran
+///
+/// Hanya tahap terakhir dari periode draft yang dapat dihapus.
+#[utoipa::path(
+    delete,
+    path = "/api/periods/stages/{stage_id}",
+    tag = "Periods",
+    params(
+        ("stage_id" = i32, Path, description = "ID tahap")
+    ),
+    responses(
+        (status = 200, description = "Tahap berhasil dihapus", body = MessageResponse),
+        (status = 400, description = "Tahap tidak dapat dihapus"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Tahap tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn delete_stage(
+    State(state): State<AppState>,
+    Path(stage_id): Path<i32>,
+) -> AppResult<Json<MessageResponse>> {
+    // Create period service
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let period_service = PeriodService::new(period_repo);
+
+    period_service.delete_stage(stage_id).await?;
+
+    Ok(Json(MessageResponse {
+        message: "Stage deleted successfully".to_string(),
+    }))
+}
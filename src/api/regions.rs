@@ -0,0 +1,432 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    routing::{get, post, put},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::middleware::auth::{auth_middleware, AuthUser};
+use crate::api::middleware::rbac::{require_region_admin, require_super_admin};
+use crate::api::schools::SchoolResponse;
+use crate::models::region::Region;
+use crate::repositories::region_repo::RegionRepository;
+use crate::services::region_service::{DuplicateStudent, RegionCalendar, RegionService, RegionStats};
+use crate::utils::error::AppResult;
+use crate::AppState;
+
+pub fn routes(state: AppState) -> Router<AppState> {
+    // Region management (super admin only)
+    let admin_routes = Router::new()
+        .route("/", post(create_region))
+        .route("/:id", put(update_region))
+        .route_layer(middleware::from_fn(require_super_admin))
+        .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
+
+    // Region oversight (region admin within their region, super admin everywhere)
+    let region_routes = Router::new()
+        .route("/", get(list_regions))
+        .route("/:id", get(get_region))
+        .route("/:id/schools", get(list_region_schools))
+        .route("/:id/stats", get(get_region_stats))
+        .route("/:id/duplicates", get(get_region_duplicates))
+        .route("/:id/calendar", get(get_region_calendar))
+        .route_layer(middleware::from_fn(require_region_admin))
+        .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
+
+    admin_routes.merge(region_routes)
+}
+
+/// Request untuk membuat wilayah
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateRegionRequest {
+    /// ID provinsi induk (wajib untuk kabupaten/kota)
+    #[schema(example = 1)]
+    parent_id: Option<i32>,
+
+    /// Tingkat wilayah (province/regency)
+    #[schema(example = "regency")]
+    level: String,
+
+    /// Kode wilayah (kode Kemendagri)
+    #[schema(example = "32.73")]
+    code: String,
+
+    /// Nama wilayah
+    #[schema(example = "Kota Bandung")]
+    name: String,
+}
+
+/// Request untuk update wilayah
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateRegionRequest {
+    /// Kode wilayah (opsional)
+    #[schema(example = "32.73")]
+    code: Option<String>,
+
+    /// Nama wilayah (opsional)
+    #[schema(example = "Kota Bandung")]
+    name: Option<String>,
+}
+
+/// Query untuk list wilayah
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListRegionsQuery {
+    /// Filter wilayah induk
+    #[schema(example = 1)]
+    parent_id: Option<i32>,
+
+    /// Filter tingkat wilayah (province/regency)
+    #[schema(example = "regency")]
+    level: Option<String>,
+}
+
+/// Query filter periode untuk laporan wilayah
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RegionReportQuery {
+    /// Tahun ajaran
+    #[schema(example = "2024/2025")]
+    academic_year: Option<String>,
+
+    /// Jenjang pendidikan (SD/SMP/SMA/SMK)
+    #[schema(example = "SMP")]
+    level: Option<String>,
+}
+
+/// Response data wilayah
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RegionResponse {
+    /// ID wilayah
+    #[schema(example = 2)]
+    id: i32,
+
+    /// ID wilayah induk
+    #[schema(example = 1)]
+    parent_id: Option<i32>,
+
+    /// Tingkat wilayah (province/regency)
+    #[schema(example = "regency")]
+    level: String,
+
+    /// Kode wilayah
+    #[schema(example = "32.73")]
+    code: String,
+
+    /// Nama wilayah
+    #[schema(example = "Kota Bandung")]
+    name: String,
+
+    /// Waktu dibuat
+    #[schema(value_type = String, example = "2024-01-01T00:00:00Z")]
+    created_at: DateTime<Utc>,
+}
+
+impl From<Region> for RegionResponse {
+    fn from(region: Region) -> Self {
+        Self {
+            id: region.id,
+            parent_id: region.parent_id,
+            level: region.level,
+            code: region.code,
+            name: region.name,
+            created_at: region.created_at,
+        }
+    }
+}
+
+/// Membuat wilayah baru (provinsi atau kabupaten/kota)
+///
+/// Hanya dapat diakses oleh super admin.
+#[utoipa::path(
+    post,
+    path = "/api/regions",
+    tag = "Regions",
+    request_body = CreateRegionRequest,
+    responses(
+        (status = 201, description = "Wilayah berhasil dibuat", body = RegionResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 409, description = "Kode wilayah sudah digunakan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn create_region(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateRegionRequest>,
+) -> AppResult<(StatusCode, Json<RegionResponse>)> {
+    let region_service = RegionService::new(RegionRepository::new(state.db.clone()));
+
+    let region = region_service
+        .create_region(payload.parent_id, payload.level, payload.code, payload.name)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(region.into())))
+}
+
+/// Update data wilayah
+///
+/// Hanya dapat diakses oleh super admin.
+#[utoipa::path(
+    put,
+    path = "/api/regions/{id}",
+    tag = "Regions",
+    params(
+        ("id" = i32, Path, description = "ID wilayah")
+    ),
+    request_body = UpdateRegionRequest,
+    responses(
+        (status = 200, description = "Wilayah berhasil diupdate", body = RegionResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Wilayah tidak ditemukan"),
+        (status = 409, description = "Kode wilayah sudah digunakan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn update_region(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateRegionRequest>,
+) -> AppResult<Json<RegionResponse>> {
+    let region_service = RegionService::new(RegionRepository::new(state.db.clone()));
+
+    let region = region_service
+        .update_region(id, payload.code, payload.name)
+        .await?;
+
+    Ok(Json(region.into()))
+}
+
+/// Mendapatkan daftar wilayah
+///
+/// Super admin melihat semua wilayah, admin wilayah hanya wilayahnya dan sub-wilayahnya.
+#[utoipa::path(
+    get,
+    path = "/api/regions",
+    tag = "Regions",
+    params(ListRegionsQuery),
+    responses(
+        (status = 200, description = "Daftar wilayah berhasil diambil", body = Vec<RegionResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_regions(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ListRegionsQuery>,
+) -> AppResult<Json<Vec<RegionResponse>>> {
+    let region_service = RegionService::new(RegionRepository::new(state.db.clone()));
+
+    let regions = region_service
+        .list_regions(&auth_user.role, auth_user.region_id, query.parent_id, query.level)
+        .await?;
+
+    Ok(Json(regions.into_iter().map(|r| r.into()).collect()))
+}
+
+/// Mendapatkan detail wilayah
+#[utoipa::path(
+    get,
+    path = "/api/regions/{id}",
+    tag = "Regions",
+    params(
+        ("id" = i32, Path, description = "ID wilayah")
+    ),
+    responses(
+        (status = 200, description = "Wilayah berhasil diambil", body = RegionResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Wilayah tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_region(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<RegionResponse>> {
+    let region_service = RegionService::new(RegionRepository::new(state.db.clone()));
+
+    // Check permission
+    region_service
+        .resolve_scope(&auth_user.role, auth_user.region_id, id)
+        .await?;
+
+    let region = region_service.get_region(id).await?;
+
+    Ok(Json(region.into()))
+}
+
+/// Mendapatkan daftar sekolah di wilayah (termasuk sub-wilayah)
+#[utoipa::path(
+    get,
+    path = "/api/regions/{id}/schools",
+    tag = "Regions",
+    params(
+        ("id" = i32, Path, description = "ID wilayah")
+    ),
+    responses(
+        (status = 200, description = "Daftar sekolah berhasil diambil", body = Vec<SchoolResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Wilayah tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_region_schools(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<Vec<SchoolResponse>>> {
+    let region_service = RegionService::new(RegionRepository::new(state.db.clone()));
+
+    let scope = region_service
+        .resolve_scope(&auth_user.role, auth_user.region_id, id)
+        .await?;
+
+    let schools = region_service.list_schools(&scope).await?;
+
+    Ok(Json(schools.into_iter().map(|s| s.into()).collect()))
+}
+
+/// Statistik pendaftaran dan seleksi wilayah
+///
+/// Agregasi jumlah pendaftaran per status dan kuota untuk semua sekolah di wilayah.
+#[utoipa::path(
+    get,
+    path = "/api/regions/{id}/stats",
+    tag = "Regions",
+    params(
+        ("id" = i32, Path, description = "ID wilayah"),
+        RegionReportQuery
+    ),
+    responses(
+        (status = 200, description = "Statistik berhasil diambil", body = RegionStats),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Wilayah tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_region_stats(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Query(query): Query<RegionReportQuery>,
+) -> AppResult<Json<RegionStats>> {
+    let region_service = RegionService::new(RegionRepository::new(state.db.clone()));
+
+    let scope = region_service
+        .resolve_scope(&auth_user.role, auth_user.region_id, id)
+        .await?;
+    let region = region_service.get_region(id).await?;
+
+    let stats = region_service
+        .get_stats(&region, &scope, query.academic_year, query.level)
+        .await?;
+
+    Ok(Json(stats))
+}
+
+/// Deteksi pendaftaran ganda lintas sekolah
+///
+/// Mengembalikan siswa (berdasarkan NISN) yang memiliki pendaftaran aktif di lebih dari satu
+/// sekolah di wilayah, termasuk yang diterima di lebih dari satu sekolah.
+#[utoipa::path(
+    get,
+    path = "/api/regions/{id}/duplicates",
+    tag = "Regions",
+    params(
+        ("id" = i32, Path, description = "ID wilayah"),
+        RegionReportQuery
+    ),
+    responses(
+        (status = 200, description = "Daftar pendaftaran ganda berhasil diambil", body = Vec<DuplicateStudent>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Wilayah tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_region_duplicates(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Query(query): Query<RegionReportQuery>,
+) -> AppResult<Json<Vec<DuplicateStudent>>> {
+    let region_service = RegionService::new(RegionRepository::new(state.db.clone()));
+
+    let scope = region_service
+        .resolve_scope(&auth_user.role, auth_user.region_id, id)
+        .await?;
+
+    let duplicates = region_service
+        .find_duplicates(&scope, query.academic_year, query.level)
+        .await?;
+
+    Ok(Json(duplicates))
+}
+
+/// Kalender periode PPDB wilayah
+///
+/// Jadwal periode semua sekolah di wilayah beserta jadwal yang perlu dikoordinasikan
+/// (pengumuman sebelum sekolah lain menutup pendaftaran, batas daftar ulang sebelum
+/// pengumuman sekolah lain).
+#[utoipa::path(
+    get,
+    path = "/api/regions/{id}/calendar",
+    tag = "Regions",
+    params(
+        ("id" = i32, Path, description = "ID wilayah"),
+        RegionReportQuery
+    ),
+    responses(
+        (status = 200, description = "Kalender berhasil diambil", body = RegionCalendar),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Wilayah tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_region_calendar(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Query(query): Query<RegionReportQuery>,
+) -> AppResult<Json<RegionCalendar>> {
+    let region_service = RegionService::new(RegionRepository::new(state.db.clone()));
+
+    let scope = region_service
+        .resolve_scope(&auth_user.role, auth_user.region_id, id)
+        .await?;
+
+    let calendar = region_service
+        .get_calendar(&scope, query.academic_year, query.level)
+        .await?;
+
+    Ok(Json(calendar))
+}
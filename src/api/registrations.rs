@@ -2,6 +2,7 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     middleware,
+    response::Html,
     routing::{delete, get, post},
     Extension, Json, Router,
 };
@@ -10,11 +11,32 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use crate::api::middleware::auth::{auth_middleware, AuthUser};
-use crate::models::registration::{Document, Registration};
+use crate::api::test_sessions::{test_service, TestBookingResponse};
+use crate::models::achievement::NewAchievement;
+use crate::models::duplicate::DocumentDuplicate;
+use crate::models::registration::{
+    Document, DocumentArtifact, DocumentDetail, Registration, RegistrationFallbackPath, RegistrationRevision,
+    RegistrationStatusChange, RevisionDocument, RevisionField, WithdrawalResult,
+};
+use crate::repositories::appeal_repo::AppealRepository;
+use crate::repositories::assistance_repo::AssistanceRepository;
+use crate::repositories::audit_repo::AuditRepository;
+use crate::repositories::document_repo::DocumentRepository;
+use crate::repositories::duplicate_repo::DuplicateRepository;
 use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::region_repo::RegionRepository;
 use crate::repositories::registration_repo::RegistrationRepository;
-use crate::services::registration_service::RegistrationService;
+use crate::services::duplicate_service::DuplicateService;
+use crate::services::assistance_service::AssistanceService;
+use crate::services::document_service::DocumentService;
+use crate::services::achievement_service::{AchievementService, AchievementSummary};
+use crate::services::grade_service::{GradeInput, GradeService, GradeSummary};
+use crate::services::major_service::{MajorChoiceSummary, MajorService};
+use crate::services::test_service::{render_test_card_html, TestCard};
+use crate::services::registration_service::{FallbackPathInput, RegistrationService};
 use crate::utils::error::{AppError, AppResult};
+use crate::utils::scanner::scanner_from_config;
+use crate::utils::storage::Storage;
 use crate::AppState;
 
 pub fn routes(state: AppState) -> Router<AppState> {
@@ -22,11 +44,56 @@ pub fn routes(state: AppState) -> Router<AppState> {
         .route("/", get(list_registrations).post(create_registration))
         .route("/:id", get(get_registration).put(update_registration))
         .route("/:id/submit", post(submit_registration))
+        .route("/:id/fallback-paths", get(list_fallback_paths))
+        .route("/:id/revisions", get(list_revisions))
+        .route("/:id/withdraw", post(withdraw_registration))
+        .route("/:id/history", get(list_status_history))
+        .route("/:id/grades", get(get_grades).put(update_grades))
+        .route("/:id/achievements", get(get_achievements).put(update_achievements))
+        .route("/:id/majors", get(get_major_choices).put(update_major_choices))
+        .route("/:id/tests", get(list_test_bookings).post(book_test_session))
+        .route("/:id/tests/:session_id", delete(cancel_test_booking))
+        .route("/:id/test-card", get(get_test_card))
+        .route("/:id/test-card/print", get(print_test_card))
         .route("/:id/documents", get(list_documents).post(upload_document))
         .route("/:id/documents/:doc_id", delete(delete_document))
         .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware))
 }
 
+pub fn registration_service(state: &AppState) -> RegistrationService {
+    let duplicate_service = DuplicateService::new(
+        DuplicateRepository::new(state.db.clone()),
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    );
+
+    let assistance_service = AssistanceService::new(
+        AssistanceRepository::new(state.db.clone()),
+        RegistrationRepository::new(state.db.clone()),
+        RegionRepository::new(state.db.clone()),
+    );
+
+    RegistrationService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+        duplicate_service,
+        assistance_service,
+        document_service(state),
+    )
+}
+
+pub fn document_service(state: &AppState) -> DocumentService {
+    DocumentService::new(
+        DocumentRepository::new(state.db.clone()),
+        AuditRepository::new(state.db.clone()),
+        DuplicateRepository::new(state.db.clone()),
+        AppealRepository::new(state.db.clone()),
+        Storage::new(&state.config),
+        scanner_from_config(&state.config),
+        state.config.recompress_oversized_photos,
+    )
+}
+
 /// Request untuk membuat pendaftaran baru
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateRegistrationRequest {
@@ -74,6 +141,14 @@ pub struct CreateRegistrationRequest {
     #[schema(example = "ahmad@example.com")]
     student_email: Option<String>,
     
+    /// NIK siswa (opsional, dicocokkan dengan tanggal lahir, jenis kelamin, dan wilayah)
+    #[schema(example = "3201015501100001")]
+    student_nik: Option<String>,
+    
+    /// Kode wilayah alamat siswa (kode Kemendagri provinsi/kabupaten/kecamatan, opsional)
+    #[schema(example = "32.01")]
+    student_region_code: Option<String>,
+    
     /// Nama orang tua/wali
     #[schema(example = "Budi Santoso")]
     parent_name: String,
@@ -109,6 +184,36 @@ pub struct CreateRegistrationRequest {
     /// Data khusus jalur pendaftaran (JSON)
     #[schema(value_type = Object)]
     path_data: serde_json::Value,
+    
+    /// Jalur cadangan berurutan jika tidak lolos kuota jalur utama (opsional)
+    #[serde(default)]
+    fallback_paths: Vec<FallbackPathRequest>,
+}
+
+/// Jalur cadangan beserta data tambahan yang dibutuhkan jalur tersebut
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FallbackPathRequest {
+    /// ID jalur cadangan (harus dalam periode yang sama)
+    #[schema(example = 2)]
+    path_id: i32,
+    
+    /// Data khusus jalur cadangan (JSON)
+    #[serde(default = "empty_path_data")]
+    #[schema(value_type = Object)]
+    path_data: serde_json::Value,
+}
+
+impl From<FallbackPathRequest> for FallbackPathInput {
+    fn from(req: FallbackPathRequest) -> Self {
+        Self {
+            path_id: req.path_id,
+            path_data: req.path_data,
+        }
+    }
+}
+
+fn empty_path_data() -> serde_json::Value {
+    serde_json::json!({})
 }
 
 /// Request untuk update pendaftaran
@@ -146,6 +251,14 @@ pub struct UpdateRegistrationRequest {
     #[schema(example = "ahmad@example.com")]
     student_email: Option<String>,
     
+    /// NIK siswa (opsional)
+    #[schema(example = "3201015501100001")]
+    student_nik: Option<String>,
+    
+    /// Kode wilayah alamat siswa (opsional)
+    #[schema(example = "32.01")]
+    student_region_code: Option<String>,
+    
     /// Nama orang tua/wali (opsional)
     #[schema(example = "Budi Santoso")]
     parent_name: Option<String>,
@@ -169,6 +282,9 @@ pub struct UpdateRegistrationRequest {
     /// Data khusus jalur pendaftaran (opsional)
     #[schema(value_type = Option<Object>)]
     path_data: Option<serde_json::Value>,
+    
+    /// Jalur cadangan berurutan, menggantikan daftar sebelumnya (opsional)
+    fallback_paths: Option<Vec<FallbackPathRequest>>,
 }
 
 /// Request untuk upload dokumen
@@ -293,6 +409,14 @@ pub struct RegistrationResponse {
     #[schema(example = "ahmad@example.com")]
     student_email: Option<String>,
     
+    /// NIK siswa
+    #[schema(example = "3201015501100001")]
+    student_nik: Option<String>,
+    
+    /// Kode wilayah alamat siswa
+    #[schema(example = "32.01")]
+    student_region_code: Option<String>,
+    
     /// Nama orang tua/wali
     #[schema(example = "Budi Santoso")]
     parent_name: String,
@@ -337,6 +461,30 @@ pub struct RegistrationResponse {
     #[schema(example = 10)]
     ranking: Option<i32>,
     
+    /// ID jalur tempat pendaftar diterima (jalur utama atau cadangan)
+    #[schema(example = 2)]
+    accepted_path_id: Option<i32>,
+    
+    /// ID jurusan tempat pendaftar diterima (SMK)
+    #[schema(example = 3)]
+    accepted_major_id: Option<i32>,
+    
+    /// Rata-rata rapor yang dihitung server dari nilai per semester
+    #[schema(example = 86.25)]
+    rapor_average: Option<f64>,
+    
+    /// Poin prestasi yang sudah diverifikasi
+    #[schema(example = 60.0)]
+    achievement_points: Option<f64>,
+    
+    /// Nilai tes masuk (rata-rata tes yang dihadiri)
+    #[schema(example = 78.5)]
+    test_score: Option<f64>,
+    
+    /// Program bantuan sosial yang sudah terverifikasi (dtks/pkh/kip)
+    #[schema(example = json!(["kip"]))]
+    verified_assistance_programs: Vec<String>,
+    
     /// Status pendaftaran
     #[schema(example = "submitted")]
     status: String,
@@ -345,6 +493,10 @@ pub struct RegistrationResponse {
     #[schema(example = "Dokumen tidak lengkap")]
     rejection_reason: Option<String>,
     
+    /// Batas waktu perpanjangan dari admin setelah pendaftaran ditutup
+    #[schema(value_type = Option<String>, example = "2024-07-03T16:59:59Z")]
+    deadline_extended_until: Option<DateTime<Utc>>,
+    
     /// Waktu pembuatan
     #[schema(value_type = String, example = "2024-01-01T00:00:00Z")]
     created_at: DateTime<Utc>,
@@ -354,6 +506,189 @@ pub struct RegistrationResponse {
     updated_at: DateTime<Utc>,
 }
 
+/// Data pendaftaran yang ditandai untuk diperbaiki
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RevisionFieldItem {
+    /// Nama field (contoh: parent_nik, student_address, path_data)
+    #[schema(example = "parent_nik")]
+    pub field: String,
+
+    /// Catatan perbaikan
+    #[schema(example = "NIK tidak sesuai dengan KK")]
+    pub note: String,
+}
+
+impl From<RevisionFieldItem> for RevisionField {
+    fn from(item: RevisionFieldItem) -> Self {
+        Self {
+            field: item.field,
+            note: item.note,
+        }
+    }
+}
+
+/// Dokumen yang ditandai untuk diganti
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RevisionDocumentItem {
+    /// ID dokumen
+    #[schema(example = 10)]
+    pub document_id: i32,
+
+    /// Catatan perbaikan
+    #[schema(example = "Foto KK buram, unggah ulang dengan jelas")]
+    pub note: String,
+}
+
+impl From<RevisionDocumentItem> for RevisionDocument {
+    fn from(item: RevisionDocumentItem) -> Self {
+        Self {
+            document_id: item.document_id,
+            note: item.note,
+        }
+    }
+}
+
+/// Response permintaan perbaikan pendaftaran
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RevisionResponse {
+    /// ID permintaan perbaikan
+    #[schema(example = 1)]
+    id: i32,
+
+    /// ID pendaftaran
+    #[schema(example = 1)]
+    registration_id: i32,
+
+    /// Catatan umum verifikator
+    #[schema(example = "Mohon perbaiki data berikut")]
+    notes: Option<String>,
+
+    /// Data yang harus diperbaiki
+    fields: Vec<RevisionFieldItem>,
+
+    /// Dokumen yang harus diganti
+    documents: Vec<RevisionDocumentItem>,
+
+    /// Perubahan saat dikirim ulang: `fields` (field, before, after) dan
+    /// `documents` (document_id, document_type, replaced_by)
+    #[schema(value_type = Option<Object>, example = json!({
+        "fields": [{"field": "parent_nik", "before": "3273010101800001", "after": "3273010101800002"}],
+        "documents": [{"document_id": 10, "document_type": "kartu_keluarga", "replaced_by": [12]}]
+    }))]
+    changes: Option<serde_json::Value>,
+
+    /// ID verifikator yang meminta perbaikan
+    #[schema(example = 2)]
+    requested_by: i32,
+
+    /// Waktu permintaan perbaikan
+    #[schema(value_type = String, example = "2024-06-10T10:00:00Z")]
+    created_at: DateTime<Utc>,
+
+    /// Waktu pendaftaran dikirim ulang
+    #[schema(value_type = Option<String>, example = "2024-06-11T08:00:00Z")]
+    resubmitted_at: Option<DateTime<Utc>>,
+}
+
+impl From<RegistrationRevision> for RevisionResponse {
+    fn from(revision: RegistrationRevision) -> Self {
+        Self {
+            fields: revision
+                .flagged_fields()
+                .into_iter()
+                .map(|f| RevisionFieldItem {
+                    field: f.field,
+                    note: f.note,
+                })
+                .collect(),
+            documents: revision
+                .flagged_documents()
+                .into_iter()
+                .map(|d| RevisionDocumentItem {
+                    document_id: d.document_id,
+                    note: d.note,
+                })
+                .collect(),
+            id: revision.id,
+            registration_id: revision.registration_id,
+            notes: revision.notes,
+            changes: revision.changes,
+            requested_by: revision.requested_by,
+            created_at: revision.created_at,
+            resubmitted_at: revision.resubmitted_at,
+        }
+    }
+}
+
+/// Request pengunduran diri pendaftaran
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WithdrawRegistrationRequest {
+    /// Alasan mengundurkan diri
+    #[schema(example = "Diterima di sekolah lain")]
+    reason: String,
+}
+
+/// Response pengunduran diri pendaftaran
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WithdrawRegistrationResponse {
+    /// Data pendaftaran setelah mengundurkan diri
+    registration: RegistrationResponse,
+
+    /// ID pendaftaran dari daftar tunggu yang menempati kursi yang dilepas
+    #[schema(example = 42)]
+    promoted_registration_id: Option<i32>,
+
+    /// ID pembayaran yang dikembalikan sesuai kebijakan refund periode
+    #[schema(example = json!([7]))]
+    refunded_payment_ids: Vec<i32>,
+}
+
+impl From<WithdrawalResult> for WithdrawRegistrationResponse {
+    fn from(result: WithdrawalResult) -> Self {
+        Self {
+            registration: result.registration.into(),
+            promoted_registration_id: result.promoted.map(|r| r.id),
+            refunded_payment_ids: result.refunded_payment_ids,
+        }
+    }
+}
+
+/// Response riwayat status pendaftaran
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StatusChangeResponse {
+    /// Status sebelumnya
+    #[schema(example = "accepted")]
+    from_status: String,
+
+    /// Status baru
+    #[schema(example = "withdrawn")]
+    to_status: String,
+
+    /// Alasan perubahan status
+    #[schema(example = "Diterima di sekolah lain")]
+    reason: Option<String>,
+
+    /// ID pengguna yang mengubah status (kosong jika oleh sistem)
+    #[schema(example = 5)]
+    changed_by: Option<i32>,
+
+    /// Waktu perubahan status
+    #[schema(value_type = String, example = "2024-07-05T09:00:00Z")]
+    created_at: DateTime<Utc>,
+}
+
+impl From<RegistrationStatusChange> for StatusChangeResponse {
+    fn from(change: RegistrationStatusChange) -> Self {
+        Self {
+            from_status: change.from_status,
+            to_status: change.to_status,
+            reason: change.reason,
+            changed_by: change.changed_by,
+            created_at: change.created_at,
+        }
+    }
+}
+
 impl From<Registration> for RegistrationResponse {
     fn from(reg: Registration) -> Self {
         Self {
@@ -372,6 +707,8 @@ impl From<Registration> for RegistrationResponse {
             student_address: reg.student_address,
             student_phone: reg.student_phone,
             student_email: reg.student_email,
+            student_nik: reg.student_nik,
+            student_region_code: reg.student_region_code,
             parent_name: reg.parent_name,
             parent_nik: reg.parent_nik,
             parent_phone: reg.parent_phone,
@@ -383,8 +720,15 @@ impl From<Registration> for RegistrationResponse {
             path_data: reg.path_data,
             selection_score: reg.selection_score,
             ranking: reg.ranking,
+            accepted_path_id: reg.accepted_path_id,
+            accepted_major_id: reg.accepted_major_id,
+            rapor_average: reg.rapor_average,
+            achievement_points: reg.achievement_points,
+            test_score: reg.test_score,
+            verified_assistance_programs: reg.verified_assistance_programs,
             status: reg.status,
             rejection_reason: reg.rejection_reason,
+            deadline_extended_until: reg.deadline_extended_until,
             created_at: reg.created_at,
             updated_at: reg.updated_at,
         }
@@ -406,9 +750,10 @@ pub struct DocumentResponse {
     #[schema(example = "akta_lahir")]
     document_type: String,
     
-    /// URL file
+    /// URL file, kosong selama file dikarantina menunggu pemindaian malware
+    /// atau bila file terinfeksi
     #[schema(example = "https://storage.example.com/documents/akta_123.pdf")]
-    file_url: String,
+    file_url: Option<String>,
     
     /// Nama file
     #[schema(example = "akta_lahir.pdf")]
@@ -430,6 +775,54 @@ pub struct DocumentResponse {
     #[schema(example = "Dokumen sudah sesuai")]
     verification_notes: Option<String>,
     
+    /// ID verifikator yang memeriksa dokumen
+    #[schema(example = 2)]
+    verified_by: Option<i32>,
+    
+    /// Waktu dokumen diperiksa
+    #[schema(value_type = Option<String>, example = "2024-06-11T03:00:00Z")]
+    verified_at: Option<DateTime<Utc>>,
+    
+    /// Dokumen pendaftaran lain dengan isi yang sama atau gambar yang mirip
+    /// (hanya untuk admin)
+    duplicates: Vec<DocumentDuplicateResponse>,
+    
+    /// Status pemrosesan file: pending, processed, failed, skipped
+    #[schema(example = "processed")]
+    processing_status: String,
+    
+    /// Alasan pemrosesan file gagal
+    #[schema(example = "Validation error: File is not a valid image")]
+    processing_error: Option<String>,
+    
+    /// Ukuran file asli sebelum foto dikompres ulang
+    #[schema(example = 4194304)]
+    original_file_size: Option<i64>,
+    
+    /// Jumlah halaman (khusus PDF)
+    #[schema(example = 2)]
+    page_count: Option<i32>,
+    
+    /// Ukuran gambar, atau ukuran dan orientasi halaman pertama PDF
+    #[schema(value_type = Option<Object>)]
+    processing_metadata: Option<serde_json::Value>,
+    
+    /// Thumbnail dan pratinjau dokumen
+    artifacts: Vec<DocumentArtifactResponse>,
+    
+    /// Status pemindaian malware: pending (dikarantina), clean, infected, skipped,
+    /// failed (tidak dapat dipindai, harus diunggah ulang)
+    #[schema(example = "clean")]
+    scan_status: String,
+    
+    /// Nama malware yang terdeteksi
+    #[schema(example = "Win.Test.EICAR_HDB-1")]
+    scan_signature: Option<String>,
+    
+    /// Waktu file dipindai
+    #[schema(value_type = Option<String>, example = "2024-06-10T03:00:05Z")]
+    scanned_at: Option<DateTime<Utc>>,
+    
     /// Waktu pembuatan
     #[schema(value_type = String, example = "2024-01-01T00:00:00Z")]
     created_at: DateTime<Utc>,
@@ -441,22 +834,245 @@ pub struct DocumentResponse {
 
 impl From<Document> for DocumentResponse {
     fn from(doc: Document) -> Self {
+        // Quarantined and infected files are never served
+        let file_url = doc.is_servable().then_some(doc.file_url);
+
         Self {
             id: doc.id,
             registration_id: doc.registration_id,
             document_type: doc.document_type,
-            file_url: doc.file_url,
+            file_url,
             file_name: doc.file_name,
             file_size: doc.file_size,
             mime_type: doc.mime_type,
             verification_status: doc.verification_status,
             verification_notes: doc.verification_notes,
+            verified_by: doc.verified_by,
+            verified_at: doc.verified_at,
+            duplicates: Vec::new(),
+            processing_status: doc.processing_status,
+            processing_error: doc.processing_error,
+            original_file_size: doc.original_file_size,
+            page_count: doc.page_count,
+            processing_metadata: doc.processing_metadata,
+            artifacts: Vec::new(),
+            scan_status: doc.scan_status,
+            scan_signature: doc.scan_signature,
+            scanned_at: doc.scanned_at,
             created_at: doc.created_at,
             updated_at: doc.updated_at,
         }
     }
 }
 
+impl From<DocumentDetail> for DocumentResponse {
+    fn from(detail: DocumentDetail) -> Self {
+        let artifacts = if detail.document.is_servable() {
+            detail.artifacts.into_iter().map(|a| a.into()).collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            artifacts,
+            duplicates: detail.duplicates.into_iter().map(|d| d.into()).collect(),
+            ..detail.document.into()
+        }
+    }
+}
+
+/// Kecocokan dokumen dengan dokumen pendaftaran lain
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DocumentDuplicateResponse {
+    /// ID temuan di antrean tinjauan pendaftaran ganda
+    #[schema(example = 12)]
+    flag_id: i32,
+    
+    /// Jenis kecocokan (document_content/document_image)
+    #[schema(example = "document_content")]
+    match_type: String,
+    
+    /// Status tinjauan (open/dismissed/confirmed)
+    #[schema(example = "open")]
+    status: String,
+    
+    /// ID pendaftaran lain
+    #[schema(example = 34)]
+    matched_registration_id: i32,
+    
+    /// ID dokumen pendaftaran lain
+    #[schema(example = 101)]
+    matched_document_id: i32,
+    
+    /// ID sekolah pendaftaran lain
+    #[schema(example = 2)]
+    matched_school_id: i32,
+}
+
+impl From<DocumentDuplicate> for DocumentDuplicateResponse {
+    fn from(duplicate: DocumentDuplicate) -> Self {
+        Self {
+            flag_id: duplicate.flag_id,
+            match_type: duplicate.match_type,
+            status: duplicate.status,
+            matched_registration_id: duplicate.matched_registration_id,
+            matched_document_id: duplicate.matched_document_id,
+            matched_school_id: duplicate.matched_school_id,
+        }
+    }
+}
+
+/// Thumbnail atau pratinjau yang dibuat dari dokumen
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DocumentArtifactResponse {
+    /// Jenis: thumbnail atau preview
+    #[schema(example = "thumbnail")]
+    kind: String,
+    
+    /// URL file
+    #[schema(example = "https://storage.example.com/documents/akta_123.thumbnail.jpg")]
+    file_url: String,
+    
+    /// Ukuran file dalam bytes
+    #[schema(example = 24576)]
+    file_size: i64,
+    
+    /// MIME type file
+    #[schema(example = "image/jpeg")]
+    mime_type: String,
+    
+    /// Lebar dalam piksel
+    #[schema(example = 320)]
+    width: i32,
+    
+    /// Tinggi dalam piksel
+    #[schema(example = 240)]
+    height: i32,
+}
+
+impl From<DocumentArtifact> for DocumentArtifactResponse {
+    fn from(artifact: DocumentArtifact) -> Self {
+        Self {
+            kind: artifact.kind,
+            file_url: artifact.file_url,
+            file_size: artifact.file_size,
+            mime_type: artifact.mime_type,
+            width: artifact.width,
+            height: artifact.height,
+        }
+    }
+}
+
+/// Response data jalur cadangan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FallbackPathResponse {
+    /// ID jalur cadangan
+    #[schema(example = 2)]
+    path_id: i32,
+    
+    /// Urutan prioritas (1 = cadangan pertama)
+    #[schema(example = 1)]
+    priority: i32,
+    
+    /// Data khusus jalur cadangan
+    #[schema(value_type = Object)]
+    path_data: serde_json::Value,
+    
+    /// Skor seleksi di jalur cadangan (terisi saat seleksi)
+    #[schema(example = 78.5)]
+    selection_score: Option<f64>,
+}
+
+impl From<RegistrationFallbackPath> for FallbackPathResponse {
+    fn from(fallback: RegistrationFallbackPath) -> Self {
+        Self {
+            path_id: fallback.path_id,
+            priority: fallback.priority,
+            path_data: fallback.path_data,
+            selection_score: fallback.selection_score,
+        }
+    }
+}
+
+/// Nilai rapor satu mata pelajaran pada satu semester
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GradeEntryRequest {
+    /// Semester (1-12)
+    #[schema(example = 1)]
+    semester: i32,
+    
+    /// Mata pelajaran
+    #[schema(example = "matematika")]
+    subject: String,
+    
+    /// Nilai (0-100)
+    #[schema(example = 88.0)]
+    score: f64,
+}
+
+/// Request untuk menyimpan nilai rapor
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateGradesRequest {
+    /// Seluruh nilai rapor (menggantikan nilai yang sudah tersimpan)
+    grades: Vec<GradeEntryRequest>,
+}
+
+/// Data satu prestasi lomba
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AchievementEntryRequest {
+    /// Nama lomba
+    #[schema(example = "Olimpiade Sains Nasional Matematika")]
+    competition_name: String,
+    
+    /// Tingkat (school/regency/province/national/international)
+    #[schema(example = "province")]
+    level: String,
+    
+    /// Peringkat (kosong untuk peserta/finalis)
+    #[schema(example = 1)]
+    rank: Option<i32>,
+    
+    /// Tahun lomba
+    #[schema(example = 2023)]
+    year: i32,
+    
+    /// Keikutsertaan (individual/team, default: individual)
+    #[serde(default = "default_participation")]
+    #[schema(example = "individual")]
+    participation: String,
+    
+    /// ID dokumen sertifikat_prestasi (wajib sebelum submit)
+    #[schema(example = 4)]
+    document_id: Option<i32>,
+}
+
+fn default_participation() -> String {
+    "individual".to_string()
+}
+
+/// Request untuk menyimpan prestasi
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateAchievementsRequest {
+    /// Seluruh prestasi (menggantikan prestasi yang sudah tersimpan)
+    achievements: Vec<AchievementEntryRequest>,
+}
+
+/// Request untuk menyimpan pilihan jurusan
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMajorChoicesRequest {
+    /// ID jurusan sesuai urutan prioritas (maksimal 3)
+    #[schema(example = json!([3, 5]))]
+    major_ids: Vec<i32>,
+}
+
+/// Request untuk memesan sesi tes
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BookTestSessionRequest {
+    /// ID sesi tes
+    #[schema(example = 1)]
+    session_id: i32,
+}
+
 /// Response list pendaftaran dengan pagination
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ListRegistrationsResponse {
@@ -499,7 +1115,7 @@ pub struct MessageResponse {
     request_body = CreateRegistrationRequest,
     responses(
         (status = 201, description = "Pendaftaran berhasil dibuat", body = RegistrationResponse),
-        (status = 400, description = "Request tidak valid"),
+        (status = 400, description = "Request tidak valid", body = crate::api::docs::ValidationErrorResponse),
         (status = 401, description = "Tidak terautentikasi"),
         (status = 404, description = "Periode atau jalur tidak ditemukan")
     ),
@@ -522,9 +1138,7 @@ async fn create_registration(
     })?;
 
     // Create registration service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let period_repo = PeriodRepository::new(state.db.clone());
-    let registration_service = RegistrationService::new(registration_repo, period_repo);
+    let registration_service = registration_service(&state);
 
     // Create registration
     let registration = registration_service
@@ -542,6 +1156,8 @@ async fn create_registration(
             payload.student_address,
             payload.student_phone,
             payload.student_email,
+            payload.student_nik,
+            payload.student_region_code,
             payload.parent_name,
             payload.parent_nik,
             payload.parent_phone,
@@ -551,6 +1167,7 @@ async fn create_registration(
             payload.previous_school_npsn,
             payload.previous_school_address,
             payload.path_data,
+            payload.fallback_paths.into_iter().map(Into::into).collect(),
         )
         .await?;
 
@@ -582,9 +1199,7 @@ async fn list_registrations(
 ) -> AppResult<Json<ListRegistrationsResponse>> {
 
     // Create registration service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let period_repo = PeriodRepository::new(state.db.clone());
-    let registration_service = RegistrationService::new(registration_repo, period_repo);
+    let registration_service = registration_service(&state);
 
     // List registrations based on role
     let (registrations, total) = if auth_user.role == "parent" {
@@ -650,9 +1265,7 @@ async fn get_registration(
 ) -> AppResult<Json<RegistrationResponse>> {
 
     // Create registration service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let period_repo = PeriodRepository::new(state.db.clone());
-    let registration_service = RegistrationService::new(registration_repo, period_repo);
+    let registration_service = registration_service(&state);
 
     // Get registration
     let registration = registration_service.get_registration(id).await?;
@@ -670,7 +1283,8 @@ async fn get_registration(
 /// Update pendaftaran
 ///
 /// Endpoint ini digunakan untuk mengupdate data pendaftaran.
-/// Hanya dapat dilakukan oleh pemilik pendaftaran dan sebelum status submitted.
+/// Hanya dapat dilakukan oleh pemilik pendaftaran dan sebelum status submitted,
+/// atau pada field yang ditandai saat pendaftaran dikembalikan untuk perbaikan.
 #[utoipa::path(
     put,
     path = "/api/registrations/{id}",
@@ -681,7 +1295,7 @@ async fn get_registration(
     request_body = UpdateRegistrationRequest,
     responses(
         (status = 200, description = "Pendaftaran berhasil diupdate", body = RegistrationResponse),
-        (status = 400, description = "Request tidak valid"),
+        (status = 400, description = "Request tidak valid", body = crate::api::docs::ValidationErrorResponse),
         (status = 401, description = "Tidak terautentikasi"),
         (status = 403, description = "Tidak memiliki akses"),
         (status = 404, description = "Pendaftaran tidak ditemukan")
@@ -698,9 +1312,7 @@ async fn update_registration(
 ) -> AppResult<Json<RegistrationResponse>> {
 
     // Create registration service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let period_repo = PeriodRepository::new(state.db.clone());
-    let registration_service = RegistrationService::new(registration_repo, period_repo);
+    let registration_service = registration_service(&state);
 
     // Check if registration belongs to user
     let registration = registration_service.get_registration(id).await?;
@@ -722,12 +1334,17 @@ async fn update_registration(
             payload.student_address,
             payload.student_phone,
             payload.student_email,
+            payload.student_nik,
+            payload.student_region_code,
             payload.parent_name,
             payload.parent_nik,
             payload.parent_phone,
             payload.parent_occupation,
             payload.parent_income,
             payload.path_data,
+            payload
+                .fallback_paths
+                .map(|paths| paths.into_iter().map(Into::into).collect()),
         )
         .await?;
 
@@ -737,7 +1354,9 @@ async fn update_registration(
 /// Submit pendaftaran
 ///
 /// Endpoint ini digunakan untuk submit pendaftaran setelah semua data lengkap.
-/// Setelah disubmit, pendaftaran tidak dapat diubah lagi.
+/// Setelah disubmit, pendaftaran tidak dapat diubah lagi, kecuali dikembalikan
+/// verifikator untuk perbaikan. Pendaftaran yang sudah diperbaiki dikirim ulang
+/// lewat endpoint ini dan kembali ke antrean verifikasi.
 #[utoipa::path(
     post,
     path = "/api/registrations/{id}/submit",
@@ -762,9 +1381,7 @@ async fn submit_registration(
 ) -> AppResult<Json<RegistrationResponse>> {
 
     // Create registration service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let period_repo = PeriodRepository::new(state.db.clone());
-    let registration_service = RegistrationService::new(registration_repo, period_repo);
+    let registration_service = registration_service(&state);
 
     // Check if registration belongs to user
     let registration = registration_service.get_registration(id).await?;
@@ -780,36 +1397,708 @@ async fn submit_registration(
     Ok(Json(submitted_registration.into()))
 }
 
-/// Mendapatkan daftar dokumen pendaftaran
+/// Mendapatkan riwayat permintaan perbaikan pendaftaran
 ///
-/// Endpoint ini mengembalikan daftar dokumen yang sudah diupload untuk pendaftaran.
+/// Endpoint ini mengembalikan permintaan perbaikan dari yang terbaru. Selama status
+/// `revision_requested`, hanya field dan dokumen yang ditandai yang dapat diubah.
 #[utoipa::path(
     get,
-    path = "/api/registrations/{registration_id}/documents",
+    path = "/api/registrations/{id}/revisions",
     tag = "Registrations",
     params(
-        ("registration_id" = i32, Path, description = "ID pendaftaran")
+        ("id" = i32, Path, description = "ID pendaftaran")
     ),
     responses(
-        (status = 200, description = "Daftar dokumen berhasil diambil", body = Vec<DocumentResponse>),
+        (status = 200, description = "Riwayat perbaikan berhasil diambil", body = Vec<RevisionResponse>),
         (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
         (status = 404, description = "Pendaftaran tidak ditemukan")
     ),
     security(
         ("bearer_auth" = [])
     )
 )]
-async fn list_documents(
+async fn list_revisions(
     State(state): State<AppState>,
-    Path(registration_id): Path<i32>,
-) -> AppResult<Json<Vec<DocumentResponse>>> {
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<Vec<RevisionResponse>>> {
     // Create registration service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let period_repo = PeriodRepository::new(state.db.clone());
-    let registration_service = RegistrationService::new(registration_repo, period_repo);
+    let registration_service = registration_service(&state);
 
-    // List documents
-    let documents = registration_service.list_documents(registration_id).await?;
+    // Check permission
+    let registration = registration_service.get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to view this registration".to_string(),
+        ));
+    }
+
+    let revisions = registration_service.list_revisions(id).await?;
+
+    Ok(Json(revisions.into_iter().map(|r| r.into()).collect()))
+}
+
+/// Mengundurkan diri dari pendaftaran
+///
+/// Endpoint ini digunakan orang tua untuk membatalkan pendaftaran yang belum
+/// final (draft, submitted, revision_requested, verified, atau accepted). Kursi
+/// pendaftar yang sudah diterima dilepas dan, jika periode memakai kebijakan
+/// `promote`, diberikan ke pendaftar berikutnya di daftar tunggu. Pembayaran
+/// dikembalikan sesuai kebijakan refund periode. Pendaftaran yang mengundurkan
+/// diri tidak lagi menerima pemberitahuan.
+#[utoipa::path(
+    post,
+    path = "/api/registrations/{id}/withdraw",
+    tag = "Registrations",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    request_body = WithdrawRegistrationRequest,
+    responses(
+        (status = 200, description = "Pendaftaran berhasil dibatalkan", body = WithdrawRegistrationResponse),
+        (status = 400, description = "Alasan kosong atau status pendaftaran sudah final"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan"),
+        (status = 409, description = "Status pendaftaran berubah saat diproses")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn withdraw_registration(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<WithdrawRegistrationRequest>,
+) -> AppResult<Json<WithdrawRegistrationResponse>> {
+    // Create registration service
+    let registration_service = registration_service(&state);
+
+    // Check if registration belongs to user
+    let registration = registration_service.get_registration(id).await?;
+    if registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to withdraw this registration".to_string(),
+        ));
+    }
+
+    let result = registration_service
+        .withdraw_registration(id, payload.reason, auth_user.id)
+        .await?;
+
+    Ok(Json(result.into()))
+}
+
+/// Mendapatkan riwayat status pendaftaran
+///
+/// Endpoint ini mengembalikan perubahan status pendaftaran dari yang terlama,
+/// termasuk pengunduran diri dan penerimaan dari daftar tunggu.
+#[utoipa::path(
+    get,
+    path = "/api/registrations/{id}/history",
+    tag = "Registrations",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Riwayat status berhasil diambil", body = Vec<StatusChangeResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_status_history(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<Vec<StatusChangeResponse>>> {
+    // Create registration service
+    let registration_service = registration_service(&state);
+
+    // Check permission
+    let registration = registration_service.get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to view this registration".to_string(),
+        ));
+    }
+
+    let history = registration_service.list_status_history(id).await?;
+
+    Ok(Json(history.into_iter().map(|c| c.into()).collect()))
+}
+
+/// Mendapatkan daftar jalur cadangan pendaftaran
+///
+/// Endpoint ini mengembalikan jalur cadangan berurutan beserta skor seleksinya.
+#[utoipa::path(
+    get,
+    path = "/api/registrations/{id}/fallback-paths",
+    tag = "Registrations",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Daftar jalur cadangan berhasil diambil", body = Vec<FallbackPathResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_fallback_paths(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<Vec<FallbackPathResponse>>> {
+    // Create registration service
+    let registration_service = registration_service(&state);
+
+    // Check permission
+    let registration = registration_service.get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to view this registration".to_string(),
+        ));
+    }
+
+    let fallback_paths = registration_service.list_fallback_paths(id).await?;
+
+    Ok(Json(fallback_paths.into_iter().map(|f| f.into()).collect()))
+}
+
+/// Mendapatkan nilai rapor pendaftaran
+///
+/// Endpoint ini mengembalikan nilai rapor per semester beserta rata-rata yang dihitung
+/// server berdasarkan mata pelajaran dan semester yang ditetapkan jalur.
+#[utoipa::path(
+    get,
+    path = "/api/registrations/{id}/grades",
+    tag = "Registrations",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Nilai rapor berhasil diambil", body = GradeSummary),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_grades(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<GradeSummary>> {
+    // Check permission
+    let registration = registration_service(&state).get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to view this registration".to_string(),
+        ));
+    }
+
+    let grade_service = GradeService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    );
+
+    let summary = grade_service.get_summary(id).await?;
+
+    Ok(Json(summary))
+}
+
+/// Menyimpan nilai rapor pendaftaran
+///
+/// Endpoint ini menggantikan seluruh nilai rapor pendaftaran berstatus draft. Rata-rata
+/// rapor yang dipakai untuk skor jalur prestasi dihitung ulang oleh server.
+#[utoipa::path(
+    put,
+    path = "/api/registrations/{id}/grades",
+    tag = "Registrations",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    request_body = UpdateGradesRequest,
+    responses(
+        (status = 200, description = "Nilai rapor berhasil disimpan", body = GradeSummary),
+        (status = 400, description = "Nilai tidak valid", body = crate::api::docs::ValidationErrorResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn update_grades(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateGradesRequest>,
+) -> AppResult<Json<GradeSummary>> {
+    // Check permission
+    let registration = registration_service(&state).get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to update this registration".to_string(),
+        ));
+    }
+
+    let grade_service = GradeService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    );
+
+    let grades = payload
+        .grades
+        .into_iter()
+        .map(|g| GradeInput {
+            semester: g.semester,
+            subject: g.subject,
+            score: g.score,
+        })
+        .collect();
+
+    let summary = grade_service.save_grades(id, grades).await?;
+
+    Ok(Json(summary))
+}
+
+/// Mendapatkan prestasi pendaftaran
+///
+/// Endpoint ini mengembalikan prestasi lomba beserta poin menurut tabel poin periode.
+#[utoipa::path(
+    get,
+    path = "/api/registrations/{id}/achievements",
+    tag = "Registrations",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Prestasi berhasil diambil", body = AchievementSummary),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_achievements(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<AchievementSummary>> {
+    // Check permission
+    let registration = registration_service(&state).get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to view this registration".to_string(),
+        ));
+    }
+
+    let achievement_service = AchievementService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    );
+
+    let summary = achievement_service.get_summary(id).await?;
+
+    Ok(Json(summary))
+}
+
+/// Menyimpan prestasi pendaftaran
+///
+/// Endpoint ini menggantikan seluruh prestasi pendaftaran berstatus draft. Setiap prestasi
+/// dihubungkan ke dokumen `sertifikat_prestasi` dan diverifikasi admin satu per satu.
+#[utoipa::path(
+    put,
+    path = "/api/registrations/{id}/achievements",
+    tag = "Registrations",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    request_body = UpdateAchievementsRequest,
+    responses(
+        (status = 200, description = "Prestasi berhasil disimpan", body = AchievementSummary),
+        (status = 400, description = "Prestasi tidak valid", body = crate::api::docs::ValidationErrorResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn update_achievements(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateAchievementsRequest>,
+) -> AppResult<Json<AchievementSummary>> {
+    // Check permission
+    let registration = registration_service(&state).get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to update this registration".to_string(),
+        ));
+    }
+
+    let achievement_service = AchievementService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    );
+
+    let achievements = payload
+        .achievements
+        .into_iter()
+        .map(|a| NewAchievement {
+            competition_name: a.competition_name.trim().to_string(),
+            level: a.level,
+            rank: a.rank,
+            year: a.year,
+            participation: a.participation,
+            document_id: a.document_id,
+        })
+        .collect();
+
+    let summary = achievement_service.save_achievements(id, achievements).await?;
+
+    Ok(Json(summary))
+}
+
+/// Mendapatkan pilihan jurusan pendaftaran (SMK)
+///
+/// Endpoint ini mengembalikan pilihan jurusan sesuai prioritas beserta hasil pemeriksaan
+/// persyaratan tiap jurusan.
+#[utoipa::path(
+    get,
+    path = "/api/registrations/{id}/majors",
+    tag = "Registrations",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Pilihan jurusan berhasil diambil", body = MajorChoiceSummary),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_major_choices(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<MajorChoiceSummary>> {
+    // Check permission
+    let registration = registration_service(&state).get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to view this registration".to_string(),
+        ));
+    }
+
+    let major_service = MajorService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    );
+
+    let summary = major_service.get_summary(id).await?;
+
+    Ok(Json(summary))
+}
+
+/// Menyimpan pilihan jurusan pendaftaran (SMK)
+///
+/// Endpoint ini menggantikan pilihan jurusan pendaftaran berstatus draft. Saat seleksi,
+/// pendaftar ditempatkan pada pilihan jurusan pertama yang persyaratannya terpenuhi dan
+/// daya tampungnya masih tersedia.
+#[utoipa::path(
+    put,
+    path = "/api/registrations/{id}/majors",
+    tag = "Registrations",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    request_body = UpdateMajorChoicesRequest,
+    responses(
+        (status = 200, description = "Pilihan jurusan berhasil disimpan", body = MajorChoiceSummary),
+        (status = 400, description = "Pilihan tidak valid", body = crate::api::docs::ValidationErrorResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn update_major_choices(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateMajorChoicesRequest>,
+) -> AppResult<Json<MajorChoiceSummary>> {
+    // Check permission
+    let registration = registration_service(&state).get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to update this registration".to_string(),
+        ));
+    }
+
+    let major_service = MajorService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    );
+
+    let summary = major_service.save_choices(id, payload.major_ids).await?;
+
+    Ok(Json(summary))
+}
+
+/// Mendapatkan jadwal tes pendaftaran
+#[utoipa::path(
+    get,
+    path = "/api/registrations/{id}/tests",
+    tag = "Tests",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Jadwal tes berhasil diambil", body = Vec<TestBookingResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_test_bookings(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<Vec<TestBookingResponse>>> {
+    // Check permission
+    let registration = registration_service(&state).get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to view this registration".to_string(),
+        ));
+    }
+
+    let bookings = test_service(&state).get_registration_bookings(id).await?;
+
+    Ok(Json(bookings.into_iter().map(|b| b.into()).collect()))
+}
+
+/// Memesan sesi tes
+///
+/// Endpoint ini memesan kursi pada sesi tes yang belum dimulai. Pemesanan jenis tes yang
+/// sama sebelumnya dipindahkan ke sesi baru selama hasilnya belum dicatat.
+#[utoipa::path(
+    post,
+    path = "/api/registrations/{id}/tests",
+    tag = "Tests",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    request_body = BookTestSessionRequest,
+    responses(
+        (status = 201, description = "Sesi berhasil dipesan", body = TestBookingResponse),
+        (status = 400, description = "Sesi tidak dapat dipesan"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran atau sesi tidak ditemukan"),
+        (status = 409, description = "Sesi sudah penuh")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn book_test_session(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<BookTestSessionRequest>,
+) -> AppResult<(StatusCode, Json<TestBookingResponse>)> {
+    // Check permission
+    let registration = registration_service(&state).get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to update this registration".to_string(),
+        ));
+    }
+
+    let booking = test_service(&state)
+        .book_session(id, payload.session_id)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(booking.into())))
+}
+
+/// Membatalkan pemesanan sesi tes
+#[utoipa::path(
+    delete,
+    path = "/api/registrations/{id}/tests/{session_id}",
+    tag = "Tests",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran"),
+        ("session_id" = i32, Path, description = "ID sesi tes")
+    ),
+    responses(
+        (status = 204, description = "Pemesanan berhasil dibatalkan"),
+        (status = 400, description = "Sesi sudah dimulai"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pemesanan tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn cancel_test_booking(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((id, session_id)): Path<(i32, i32)>,
+) -> AppResult<StatusCode> {
+    // Check permission
+    let registration = registration_service(&state).get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to update this registration".to_string(),
+        ));
+    }
+
+    test_service(&state).cancel_booking(id, session_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Mendapatkan kartu peserta tes
+#[utoipa::path(
+    get,
+    path = "/api/registrations/{id}/test-card",
+    tag = "Tests",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Kartu peserta berhasil diambil", body = TestCard),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Belum ada jadwal tes")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_test_card(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<TestCard>> {
+    // Check permission
+    let registration = registration_service(&state).get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to view this registration".to_string(),
+        ));
+    }
+
+    let card = test_service(&state).get_test_card(id).await?;
+
+    Ok(Json(card))
+}
+
+/// Cetak kartu peserta tes
+///
+/// Endpoint ini mengembalikan kartu peserta tes dalam format HTML siap cetak.
+#[utoipa::path(
+    get,
+    path = "/api/registrations/{id}/test-card/print",
+    tag = "Tests",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Kartu peserta siap cetak", body = String, content_type = "text/html"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Belum ada jadwal tes")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn print_test_card(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Html<String>> {
+    // Check permission
+    let registration = registration_service(&state).get_registration(id).await?;
+    if auth_user.role == "parent" && registration.user_id != auth_user.id {
+        return Err(AppError::Forbidden(
+            "You don't have permission to view this registration".to_string(),
+        ));
+    }
+
+    let card = test_service(&state).get_test_card(id).await?;
+
+    Ok(Html(render_test_card_html(&card)))
+}
+
+/// Mendapatkan daftar dokumen pendaftaran
+///
+/// Endpoint ini mengembalikan daftar dokumen yang sudah diupload untuk pendaftaran.
+#[utoipa::path(
+    get,
+    path = "/api/registrations/{registration_id}/documents",
+    tag = "Registrations",
+    params(
+        ("registration_id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Daftar dokumen berhasil diambil", body = Vec<DocumentResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_documents(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(registration_id): Path<i32>,
+) -> AppResult<Json<Vec<DocumentResponse>>> {
+    // Create registration service
+    let registration_service = registration_service(&state);
+
+    // List documents
+    let mut documents = registration_service.list_documents(registration_id).await?;
+
+    // Matches with other families' documents are for verifiers only
+    if auth_user.role == "parent" {
+        for document in &mut documents {
+            document.duplicates.clear();
+        }
+    }
 
     Ok(Json(documents.into_iter().map(|d| d.into()).collect()))
 }
@@ -818,6 +2107,8 @@ async fn list_documents(
 ///
 /// Endpoint ini digunakan untuk menambahkan dokumen ke pendaftaran.
 /// File harus sudah diupload ke storage terlebih dahulu, endpoint ini hanya menyimpan metadata.
+/// Foto di atas 2MB dikompres ulang bila memungkinkan. Dokumen dikarantina sampai
+/// lolos pemindaian malware; metadata EXIF dihapus dan thumbnail dibuat di latar belakang.
 #[utoipa::path(
     post,
     path = "/api/registrations/{registration_id}/documents",
@@ -842,9 +2133,7 @@ async fn upload_document(
     Json(payload): Json<UploadDocumentRequest>,
 ) -> AppResult<(StatusCode, Json<DocumentResponse>)> {
     // Create registration service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let period_repo = PeriodRepository::new(state.db.clone());
-    let registration_service = RegistrationService::new(registration_repo, period_repo);
+    let registration_service = registration_service(&state);
 
     // Upload document
     let document = registration_service
@@ -889,9 +2178,7 @@ async fn delete_document(
 ) -> AppResult<Json<MessageResponse>> {
 
     // Create registration service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let period_repo = PeriodRepository::new(state.db.clone());
-    let registration_service = RegistrationService::new(registration_repo, period_repo);
+    let registration_service = registration_service(&state);
 
     // Delete document
     registration_service.delete_document(doc_id, auth_user.id).await?;
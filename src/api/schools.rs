@@ -69,6 +69,15 @@ pub struct CreateSchoolRequest {
     /// Logo URL
     #[schema(example = "https://example.com/logo.png", format = "uri")]
     logo_url: Option<String>,
+    
+    /// Region (regency/city) the school belongs to
+    #[schema(example = 1)]
+    region_id: Option<i32>,
+    
+    /// IANA timezone used for period deadlines, e.g. Asia/Jakarta (WIB),
+    /// Asia/Makassar (WITA) or Asia/Jayapura (WIT). Defaults to Asia/Jakarta
+    #[schema(example = "Asia/Makassar")]
+    timezone: Option<String>,
 }
 
 /// Update school request
@@ -101,6 +110,15 @@ pub struct UpdateSchoolRequest {
     /// Logo URL
     #[schema(example = "https://example.com/logo.png", format = "uri")]
     logo_url: Option<String>,
+    
+    /// Region (regency/city) the school belongs to
+    #[schema(example = 1)]
+    region_id: Option<i32>,
+    
+    /// IANA timezone used for period deadlines, e.g. Asia/Jakarta (WIB),
+    /// Asia/Makassar (WITA) or Asia/Jayapura (WIT)
+    #[schema(example = "Asia/Makassar")]
+    timezone: Option<String>,
 }
 
 /// List schools query parameters
@@ -182,6 +200,14 @@ pub struct SchoolResponse {
     /// School status (active, inactive)
     #[schema(example = "active")]
     status: String,
+    
+    /// Region (regency/city) the school belongs to
+    #[schema(example = 1)]
+    region_id: Option<i32>,
+    
+    /// IANA timezone used for period deadlines
+    #[schema(example = "Asia/Jakarta")]
+    timezone: String,
 }
 
 impl From<School> for SchoolResponse {
@@ -196,6 +222,8 @@ impl From<School> for SchoolResponse {
             email: school.email,
             logo_url: school.logo_url,
             status: school.status,
+            region_id: school.region_id,
+            timezone: school.timezone,
         }
     }
 }
@@ -305,6 +333,8 @@ pub async fn create_school(
             payload.phone,
             payload.email,
             payload.logo_url,
+            payload.region_id,
+            payload.timezone,
         )
         .await?;
 
@@ -471,6 +501,8 @@ pub async fn update_school(
             payload.phone,
             payload.email,
             payload.logo_url,
+            payload.region_id,
+            payload.timezone,
         )
         .await?;
 
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use axum::{
     extract::{Path, Query, State},
     middleware,
@@ -106,6 +108,14 @@ pub struct RankingResponse {
     /// Status pendaftaran
     #[schema(example = "accepted")]
     status: String,
+    
+    /// ID jurusan tempat diterima (SMK)
+    #[schema(example = 3)]
+    accepted_major_id: Option<i32>,
+    
+    /// Nama jurusan tempat diterima (SMK)
+    #[schema(example = "Teknik Komputer dan Jaringan")]
+    accepted_major_name: Option<String>,
 }
 
 impl From<crate::models::registration::Registration> for RankingResponse {
@@ -118,6 +128,8 @@ impl From<crate::models::registration::Registration> for RankingResponse {
             selection_score: reg.selection_score,
             ranking: reg.ranking,
             status: reg.status,
+            accepted_major_id: reg.accepted_major_id,
+            accepted_major_name: None,
         }
     }
 }
@@ -267,8 +279,27 @@ async fn get_rankings(
 
     let total = rankings.len();
 
+    // Show the major each accepted applicant was placed in
+    let major_names: HashMap<i32, String> = PeriodRepository::new(state.db.clone())
+        .find_majors_by_period(period_id)
+        .await?
+        .into_iter()
+        .map(|m| (m.id, m.name))
+        .collect();
+
+    let rankings = rankings
+        .into_iter()
+        .map(|r| {
+            let mut ranking = RankingResponse::from(r);
+            ranking.accepted_major_name = ranking
+                .accepted_major_id
+                .and_then(|id| major_names.get(&id).cloned());
+            ranking
+        })
+        .collect();
+
     Ok(Json(RankingsResponse {
-        rankings: rankings.into_iter().map(|r| r.into()).collect(),
+        rankings,
         total,
         page: query.page,
         page_size: query.page_size,
@@ -0,0 +1,607 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    middleware,
+    routing::{get, post, put},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::middleware::auth::{auth_middleware, AuthUser};
+use crate::api::middleware::rbac::require_school_admin;
+use crate::models::test_session::{
+    TestBooking, TestBookingDetail, TestResultInput, TestSession, TestSessionWithBookings,
+};
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::repositories::school_repo::SchoolRepository;
+use crate::repositories::test_repo::TestRepository;
+use crate::services::test_service::{AutoAssignResult, TestService};
+use crate::utils::error::{AppError, AppResult};
+use crate::AppState;
+
+pub fn routes(state: AppState) -> Router<AppState> {
+    let public_routes = Router::new()
+        .route("/", get(list_test_sessions))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ));
+
+    Router::new()
+        .route("/", post(create_test_session))
+        .route("/auto-assign", post(auto_assign_test_sessions))
+        .route("/:id", put(update_test_session).delete(delete_test_session))
+        .route("/:id/bookings", get(list_test_bookings))
+        .route("/:id/results", post(record_test_results))
+        .route_layer(middleware::from_fn(require_school_admin))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            auth_middleware,
+        ))
+        .merge(public_routes)
+}
+
+pub fn test_service(state: &AppState) -> TestService {
+    TestService::new(
+        TestRepository::new(state.db.clone()),
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+        SchoolRepository::new(state.db.clone()),
+    )
+}
+
+/// Sekolah admin, kosong untuk super admin
+fn admin_school_id(auth_user: &AuthUser) -> AppResult<Option<i32>> {
+    if auth_user.role == "super_admin" {
+        Ok(None)
+    } else {
+        Ok(Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?))
+    }
+}
+
+/// Query untuk list sesi tes
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListTestSessionsQuery {
+    /// ID periode
+    #[schema(example = 1)]
+    period_id: i32,
+
+    /// Filter jalur (sesi untuk semua jalur ikut ditampilkan)
+    #[schema(example = 2)]
+    path_id: Option<i32>,
+
+    /// Filter jenis tes (written/interview)
+    #[schema(example = "written")]
+    test_type: Option<String>,
+}
+
+/// Request untuk membuat sesi tes
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateTestSessionRequest {
+    /// ID periode
+    #[schema(example = 1)]
+    period_id: i32,
+
+    /// ID jalur (kosong jika sesi berlaku untuk semua jalur)
+    #[schema(example = 2)]
+    path_id: Option<i32>,
+
+    /// Jenis tes (written/interview)
+    #[schema(example = "written")]
+    test_type: String,
+
+    /// Nama sesi
+    #[schema(example = "Tes Tertulis Sesi 1")]
+    name: String,
+
+    /// Waktu mulai
+    #[schema(value_type = String, example = "2024-06-20T08:00:00Z")]
+    starts_at: DateTime<Utc>,
+
+    /// Waktu selesai
+    #[schema(value_type = String, example = "2024-06-20T10:00:00Z")]
+    ends_at: DateTime<Utc>,
+
+    /// Ruangan
+    #[schema(example = "Ruang 12")]
+    room: String,
+
+    /// Kapasitas peserta
+    #[schema(example = 30)]
+    capacity: i32,
+}
+
+/// Request untuk update sesi tes
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateTestSessionRequest {
+    /// Nama sesi
+    #[schema(example = "Tes Tertulis Sesi 1")]
+    name: Option<String>,
+
+    /// Waktu mulai
+    #[schema(value_type = Option<String>, example = "2024-06-20T08:00:00Z")]
+    starts_at: Option<DateTime<Utc>>,
+
+    /// Waktu selesai
+    #[schema(value_type = Option<String>, example = "2024-06-20T10:00:00Z")]
+    ends_at: Option<DateTime<Utc>>,
+
+    /// Ruangan
+    #[schema(example = "Ruang 12")]
+    room: Option<String>,
+
+    /// Kapasitas peserta (tidak boleh kurang dari kursi yang sudah dipesan)
+    #[schema(example = 30)]
+    capacity: Option<i32>,
+}
+
+/// Request untuk penjadwalan otomatis
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AutoAssignTestSessionsRequest {
+    /// ID periode
+    #[schema(example = 1)]
+    period_id: i32,
+
+    /// Jenis tes (written/interview)
+    #[schema(example = "written")]
+    test_type: String,
+
+    /// Hanya pendaftar jalur ini (opsional)
+    #[schema(example = 2)]
+    path_id: Option<i32>,
+}
+
+/// Hasil tes satu pendaftar
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TestResultEntryRequest {
+    /// ID pendaftaran
+    #[schema(example = 1)]
+    registration_id: i32,
+
+    /// Hadir
+    #[schema(example = true)]
+    attended: bool,
+
+    /// Nilai tes 0-100 (kosong jika tidak hadir)
+    #[schema(example = 78.5)]
+    score: Option<f64>,
+
+    /// Catatan
+    #[schema(example = "Datang terlambat 10 menit")]
+    notes: Option<String>,
+}
+
+/// Request untuk mencatat kehadiran dan nilai tes
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RecordTestResultsRequest {
+    /// Hasil tes per pendaftar
+    results: Vec<TestResultEntryRequest>,
+}
+
+/// Response sesi tes
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TestSessionResponse {
+    /// ID sesi
+    #[schema(example = 1)]
+    id: i32,
+
+    /// ID periode
+    #[schema(example = 1)]
+    period_id: i32,
+
+    /// ID jalur (kosong jika untuk semua jalur)
+    #[schema(example = 2)]
+    path_id: Option<i32>,
+
+    /// Jenis tes (written/interview)
+    #[schema(example = "written")]
+    test_type: String,
+
+    /// Nama sesi
+    #[schema(example = "Tes Tertulis Sesi 1")]
+    name: String,
+
+    /// Waktu mulai
+    #[schema(value_type = String, example = "2024-06-20T08:00:00Z")]
+    starts_at: DateTime<Utc>,
+
+    /// Waktu selesai
+    #[schema(value_type = String, example = "2024-06-20T10:00:00Z")]
+    ends_at: DateTime<Utc>,
+
+    /// Ruangan
+    #[schema(example = "Ruang 12")]
+    room: String,
+
+    /// Kapasitas peserta
+    #[schema(example = 30)]
+    capacity: i32,
+
+    /// Jumlah kursi yang sudah dipesan
+    #[schema(example = 12)]
+    booked: i64,
+
+    /// Sisa kursi
+    #[schema(example = 18)]
+    remaining: i64,
+}
+
+impl From<TestSessionWithBookings> for TestSessionResponse {
+    fn from(s: TestSessionWithBookings) -> Self {
+        let session = s.session;
+        Self {
+            id: session.id,
+            period_id: session.period_id,
+            path_id: session.path_id,
+            test_type: session.test_type,
+            name: session.name,
+            starts_at: session.starts_at,
+            ends_at: session.ends_at,
+            room: session.room,
+            capacity: session.capacity,
+            booked: s.booked,
+            remaining: (session.capacity as i64 - s.booked).max(0),
+        }
+    }
+}
+
+impl From<TestSession> for TestSessionResponse {
+    fn from(session: TestSession) -> Self {
+        TestSessionWithBookings { session, booked: 0 }.into()
+    }
+}
+
+/// Response pemesanan sesi tes
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TestBookingResponse {
+    /// ID pemesanan
+    #[schema(example = 1)]
+    id: i32,
+
+    /// ID sesi
+    #[schema(example = 1)]
+    session_id: i32,
+
+    /// ID pendaftaran
+    #[schema(example = 1)]
+    registration_id: i32,
+
+    /// Jenis tes (written/interview)
+    #[schema(example = "written")]
+    test_type: String,
+
+    /// Nomor kursi
+    #[schema(example = 7)]
+    seat_number: i32,
+
+    /// Kehadiran (kosong jika belum dicatat)
+    #[schema(example = true)]
+    attended: Option<bool>,
+
+    /// Nilai tes
+    #[schema(example = 78.5)]
+    score: Option<f64>,
+
+    /// Catatan
+    #[schema(example = "Datang terlambat 10 menit")]
+    notes: Option<String>,
+
+    /// Waktu pencatatan hasil
+    #[schema(value_type = Option<String>, example = "2024-06-20T11:00:00Z")]
+    recorded_at: Option<DateTime<Utc>>,
+
+    /// Nomor pendaftaran
+    #[schema(example = "PPDB-2024-001")]
+    registration_number: Option<String>,
+
+    /// Nama siswa
+    #[schema(example = "Ahmad Fauzi")]
+    student_name: Option<String>,
+
+    /// NISN siswa
+    #[schema(example = "0012345678")]
+    student_nisn: Option<String>,
+}
+
+impl From<TestBooking> for TestBookingResponse {
+    fn from(booking: TestBooking) -> Self {
+        Self {
+            id: booking.id,
+            session_id: booking.session_id,
+            registration_id: booking.registration_id,
+            test_type: booking.test_type,
+            seat_number: booking.seat_number,
+            attended: booking.attended,
+            score: booking.score,
+            notes: booking.notes,
+            recorded_at: booking.recorded_at,
+            registration_number: None,
+            student_name: None,
+            student_nisn: None,
+        }
+    }
+}
+
+impl From<TestBookingDetail> for TestBookingResponse {
+    fn from(detail: TestBookingDetail) -> Self {
+        Self {
+            registration_number: detail.registration_number,
+            student_name: Some(detail.student_name),
+            student_nisn: Some(detail.student_nisn),
+            ..detail.booking.into()
+        }
+    }
+}
+
+/// Mendapatkan daftar sesi tes periode
+///
+/// Endpoint ini mengembalikan jadwal tes tertulis dan wawancara beserta sisa kursi,
+/// sehingga orang tua dapat memilih sesi.
+#[utoipa::path(
+    get,
+    path = "/api/test-sessions",
+    tag = "Tests",
+    params(ListTestSessionsQuery),
+    responses(
+        (status = 200, description = "Daftar sesi berhasil diambil", body = Vec<TestSessionResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 404, description = "Periode tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_test_sessions(
+    State(state): State<AppState>,
+    Query(query): Query<ListTestSessionsQuery>,
+) -> AppResult<Json<Vec<TestSessionResponse>>> {
+    let sessions = test_service(&state)
+        .list_sessions(query.period_id, query.path_id, query.test_type)
+        .await?;
+
+    Ok(Json(sessions.into_iter().map(|s| s.into()).collect()))
+}
+
+/// Membuat sesi tes
+///
+/// Endpoint ini digunakan admin sekolah untuk menjadwalkan tes tertulis atau wawancara
+/// pada periode tertentu, untuk semua jalur atau satu jalur saja.
+#[utoipa::path(
+    post,
+    path = "/api/test-sessions",
+    tag = "Tests",
+    request_body = CreateTestSessionRequest,
+    responses(
+        (status = 201, description = "Sesi berhasil dibuat", body = TestSessionResponse),
+        (status = 400, description = "Request tidak valid", body = crate::api::docs::ValidationErrorResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Periode tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn create_test_session(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<CreateTestSessionRequest>,
+) -> AppResult<(StatusCode, Json<TestSessionResponse>)> {
+    let session = test_service(&state)
+        .create_session(
+            payload.period_id,
+            payload.path_id,
+            payload.test_type,
+            payload.name,
+            payload.starts_at,
+            payload.ends_at,
+            payload.room,
+            payload.capacity,
+            admin_school_id(&auth_user)?,
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(session.into())))
+}
+
+/// Update sesi tes
+#[utoipa::path(
+    put,
+    path = "/api/test-sessions/{id}",
+    tag = "Tests",
+    params(
+        ("id" = i32, Path, description = "ID sesi")
+    ),
+    request_body = UpdateTestSessionRequest,
+    responses(
+        (status = 200, description = "Sesi berhasil diupdate", body = TestSessionResponse),
+        (status = 400, description = "Request tidak valid", body = crate::api::docs::ValidationErrorResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Sesi tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn update_test_session(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateTestSessionRequest>,
+) -> AppResult<Json<TestSessionResponse>> {
+    let test_service = test_service(&state);
+
+    test_service
+        .update_session(
+            id,
+            payload.name,
+            payload.starts_at,
+            payload.ends_at,
+            payload.room,
+            payload.capacity,
+            admin_school_id(&auth_user)?,
+        )
+        .await?;
+
+    let session = test_service.get_session(id).await?;
+
+    Ok(Json(session.into()))
+}
+
+/// Hapus sesi tes
+///
+/// Sesi yang sudah memiliki peserta tidak dapat dihapus.
+#[utoipa::path(
+    delete,
+    path = "/api/test-sessions/{id}",
+    tag = "Tests",
+    params(
+        ("id" = i32, Path, description = "ID sesi")
+    ),
+    responses(
+        (status = 204, description = "Sesi berhasil dihapus"),
+        (status = 400, description = "Sesi sudah memiliki peserta"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Sesi tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn delete_test_session(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    test_service(&state)
+        .delete_session(id, admin_school_id(&auth_user)?)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Mendapatkan daftar peserta sesi tes
+///
+/// Endpoint ini mengembalikan daftar hadir sesi, diurutkan berdasarkan nomor kursi.
+#[utoipa::path(
+    get,
+    path = "/api/test-sessions/{id}/bookings",
+    tag = "Tests",
+    params(
+        ("id" = i32, Path, description = "ID sesi")
+    ),
+    responses(
+        (status = 200, description = "Daftar peserta berhasil diambil", body = Vec<TestBookingResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Sesi tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn list_test_bookings(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<Vec<TestBookingResponse>>> {
+    let bookings = test_service(&state)
+        .get_session_bookings(id, admin_school_id(&auth_user)?)
+        .await?;
+
+    Ok(Json(bookings.into_iter().map(|b| b.into()).collect()))
+}
+
+/// Catat kehadiran dan nilai tes
+///
+/// Endpoint ini mencatat kehadiran dan nilai banyak peserta sekaligus. Nilai tes
+/// pendaftar (rata-rata tes yang dihadiri) dihitung ulang dan dapat dipakai sebagai
+/// komponen skor dengan `test_weight` pada konfigurasi skor jalur.
+#[utoipa::path(
+    post,
+    path = "/api/test-sessions/{id}/results",
+    tag = "Tests",
+    params(
+        ("id" = i32, Path, description = "ID sesi")
+    ),
+    request_body = RecordTestResultsRequest,
+    responses(
+        (status = 200, description = "Hasil tes berhasil dicatat", body = Vec<TestBookingResponse>),
+        (status = 400, description = "Hasil tidak valid", body = crate::api::docs::ValidationErrorResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Sesi tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn record_test_results(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<RecordTestResultsRequest>,
+) -> AppResult<Json<Vec<TestBookingResponse>>> {
+    let results = payload
+        .results
+        .into_iter()
+        .map(|r| TestResultInput {
+            registration_id: r.registration_id,
+            attended: r.attended,
+            score: r.score,
+            notes: r.notes,
+        })
+        .collect();
+
+    let bookings = test_service(&state)
+        .record_results(id, results, admin_school_id(&auth_user)?, auth_user.id)
+        .await?;
+
+    Ok(Json(bookings.into_iter().map(|b| b.into()).collect()))
+}
+
+/// Penjadwalan tes otomatis
+///
+/// Endpoint ini menempatkan pendaftar (submitted/verified) yang belum memiliki jadwal
+/// jenis tes tersebut ke sesi terdekat yang masih memiliki kursi, sesuai urutan
+/// pendaftaran.
+#[utoipa::path(
+    post,
+    path = "/api/test-sessions/auto-assign",
+    tag = "Tests",
+    request_body = AutoAssignTestSessionsRequest,
+    responses(
+        (status = 200, description = "Penjadwalan selesai", body = AutoAssignResult),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Periode tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn auto_assign_test_sessions(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<AutoAssignTestSessionsRequest>,
+) -> AppResult<Json<AutoAssignResult>> {
+    let result = test_service(&state)
+        .auto_assign(
+            payload.period_id,
+            payload.test_type,
+            payload.path_id,
+            admin_school_id(&auth_user)?,
+        )
+        .await?;
+
+    Ok(Json(result))
+}
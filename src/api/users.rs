@@ -67,13 +67,17 @@ pub struct CreateUserRequest {
     #[schema(example = "3201234567890123", min_length = 16, max_length = 16)]
     nik: Option<String>,
     
-    /// User role (super_admin, school_admin, parent)
+    /// User role (super_admin, region_admin, school_admin, parent)
     #[schema(example = "school_admin")]
     role: String,
     
     /// School ID (required for school_admin and parent, null for super_admin)
     #[schema(example = 1)]
     school_id: Option<i32>,
+    
+    /// Region ID (required for region_admin)
+    #[schema(example = 1)]
+    region_id: Option<i32>,
 }
 
 /// Update user request
@@ -177,6 +181,9 @@ pub struct UserResponse {
     #[schema(example = 1)]
     pub school_id: Option<i32>,
     
+    #[schema(example = 1)]
+    pub region_id: Option<i32>,
+    
     #[schema(example = true)]
     pub email_verified: bool,
 }
@@ -191,6 +198,7 @@ impl From<User> for UserResponse {
             nik: user.nik,
             role: user.role,
             school_id: user.school_id,
+            region_id: user.region_id,
             email_verified: user.email_verified,
         }
     }
@@ -269,12 +277,28 @@ pub async fn create_user(
     Json(payload): Json<CreateUserRequest>,
 ) -> AppResult<(StatusCode, Json<UserResponse>)> {
     // Validate role
-    if !["super_admin", "school_admin", "parent"].contains(&payload.role.as_str()) {
+    if !["super_admin", "region_admin", "school_admin", "parent"].contains(&payload.role.as_str()) {
         return Err(AppError::Validation("Invalid role".to_string()));
     }
 
+    // Only SuperAdmin can create region admins
+    let region_id = if payload.role == "region_admin" {
+        if auth_user.role != "super_admin" {
+            return Err(AppError::Forbidden(
+                "Only super admin can create region admins".to_string(),
+            ));
+        }
+        Some(payload.region_id.ok_or_else(|| {
+            AppError::Validation("region_id is required for region_admin".to_string())
+        })?)
+    } else {
+        None
+    };
+
     // Determine school_id based on role
-    let school_id = if auth_user.role == "super_admin" {
+    let school_id = if payload.role == "region_admin" {
+        None
+    } else if auth_user.role == "super_admin" {
         payload.school_id
     } else {
         auth_user.school_id
@@ -294,6 +318,7 @@ pub async fn create_user(
             payload.phone,
             payload.nik,
             payload.role,
+            region_id,
         )
         .await?;
 
@@ -1,5 +1,6 @@
 use axum::{
     extract::{Path, Query, State},
+    http::StatusCode,
     middleware,
     routing::{get, post},
     Extension, Json, Router,
@@ -9,8 +10,31 @@ use utoipa::ToSchema;
 
 use crate::api::middleware::auth::{auth_middleware, AuthUser};
 use crate::api::middleware::rbac::require_school_admin;
+use crate::api::registrations::{
+    registration_service, DocumentResponse, RevisionDocumentItem, RevisionFieldItem,
+    RevisionResponse,
+};
+use crate::models::age_rule::AgeEvaluation;
+use crate::models::assistance::AssistanceMatchDetail;
+use crate::repositories::assistance_repo::AssistanceRepository;
+use crate::repositories::audit_repo::AuditRepository;
+use crate::repositories::duplicate_repo::DuplicateRepository;
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::region_repo::RegionRepository;
 use crate::repositories::registration_repo::RegistrationRepository;
-use crate::services::verification_service::{VerificationService, VerificationStats};
+use crate::services::achievement_service::{AchievementService, AchievementSummary};
+use crate::services::assistance_service::AssistanceService;
+use crate::services::duplicate_service::DuplicateService;
+use crate::services::grade_service::{GradeService, GradeSummary};
+use crate::services::major_service::{MajorChoiceSummary, MajorService};
+use crate::models::verification::{
+    BulkTarget, BulkVerificationItem, BulkVerificationJob, VerificationLock,
+};
+use crate::repositories::verification_repo::VerificationRepository;
+use crate::services::verification_service::{
+    AssignmentSummary, BulkFilter, VerificationQueueMetrics, VerificationService,
+    VerificationStats,
+};
 use crate::utils::error::{AppError, AppResult};
 use crate::AppState;
 
@@ -18,13 +42,41 @@ pub fn routes(state: AppState) -> Router<AppState> {
     Router::new()
         .route("/pending", get(get_pending_verifications))
         .route("/stats", get(get_verification_stats))
+        .route("/queue/claim", post(claim_next_registration))
+        .route("/queue/assign", post(assign_registrations))
+        .route("/queue/metrics", get(get_queue_metrics))
+        .route("/bulk/registrations", post(bulk_registrations))
+        .route("/bulk/documents", post(bulk_documents))
+        .route("/bulk/:job_id", get(get_bulk_job))
+        .route("/:id/claim", post(claim_registration))
+        .route("/:id/release", post(release_claim))
         .route("/:id/verify", post(verify_registration))
         .route("/:id/reject", post(reject_registration))
+        .route("/:id/request-revision", post(request_revision))
+        .route("/:id/enroll", post(enroll_registration))
+        .route("/:id/grades/:semester/verify", post(verify_grades))
         .route("/documents/:doc_id/verify", post(verify_document))
+        .route("/achievements/:achievement_id/verify", post(verify_achievement))
+        .route("/:id/assistance-matches", get(get_assistance_matches))
+        .route(
+            "/assistance-matches/:match_id/review",
+            post(review_assistance_match),
+        )
+        .route("/:id/requirements/:key", post(record_requirement_check))
+        .route("/:id/deadline-extension", post(extend_deadline))
         .route_layer(middleware::from_fn(require_school_admin))
         .route_layer(middleware::from_fn_with_state(state.clone(), auth_middleware))
 }
 
+fn verification_service(state: &AppState) -> VerificationService {
+    VerificationService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+        VerificationRepository::new(state.db.clone()),
+        AuditRepository::new(state.db.clone()),
+    )
+}
+
 /// Query untuk pending verifications
 #[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
 #[into_params(parameter_in = Query)]
@@ -73,6 +125,32 @@ pub struct RejectRegistrationRequest {
     reason: String,
 }
 
+/// Request untuk mengembalikan pendaftaran agar diperbaiki
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RequestRevisionRequest {
+    /// Catatan umum untuk orang tua (opsional)
+    #[schema(example = "Mohon perbaiki data berikut sebelum 15 Juni")]
+    notes: Option<String>,
+
+    /// Data yang harus diperbaiki beserta catatannya
+    #[serde(default)]
+    fields: Vec<RevisionFieldItem>,
+
+    /// Dokumen yang harus diganti beserta catatannya
+    #[serde(default)]
+    documents: Vec<RevisionDocumentItem>,
+}
+
+/// Response permintaan perbaikan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RequestRevisionResponse {
+    /// Pendaftaran dengan status `revision_requested`
+    registration: RegistrationResponse,
+
+    /// Permintaan perbaikan yang dibuat
+    revision: RevisionResponse,
+}
+
 /// Request untuk verifikasi dokumen
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct VerifyDocumentRequest {
@@ -80,11 +158,146 @@ pub struct VerifyDocumentRequest {
     #[schema(example = "approved")]
     verification_status: String,
     
-    /// Catatan verifikasi (opsional)
+    /// Catatan verifikasi (wajib saat menolak dokumen wajib jalur)
     #[schema(example = "Dokumen sudah sesuai")]
     verification_notes: Option<String>,
 }
 
+/// Response verifikasi dokumen
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DocumentVerificationResponse {
+    /// Dokumen yang diperiksa
+    document: DocumentResponse,
+    
+    /// Pendaftaran setelah keputusan dokumen
+    registration: RegistrationResponse,
+    
+    /// Tindak lanjut pendaftaran saat dokumen wajib ditolak (revision/reject)
+    #[schema(example = "revision")]
+    outcome: Option<String>,
+}
+
+/// Request untuk verifikasi nilai rapor satu semester
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VerifyGradesRequest {
+    /// ID dokumen rapor yang dicocokkan (opsional)
+    #[schema(example = 3)]
+    document_id: Option<i32>,
+}
+
+/// Request untuk verifikasi prestasi
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct VerifyAchievementRequest {
+    /// Status verifikasi (approved/rejected)
+    #[schema(example = "approved")]
+    verification_status: String,
+    
+    /// Catatan verifikasi (opsional)
+    #[schema(example = "Sertifikat sesuai dengan data prestasi")]
+    verification_notes: Option<String>,
+}
+
+/// Request untuk meninjau kecocokan data bantuan sosial
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReviewAssistanceMatchRequest {
+    /// Hasil tinjauan (verified/rejected)
+    #[schema(example = "verified")]
+    status: String,
+
+    /// Catatan tinjauan (opsional)
+    #[schema(example = "Nama dan alamat sesuai dengan KK")]
+    notes: Option<String>,
+}
+
+/// Request untuk mencatat hasil pemeriksaan persyaratan jurusan
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RecordRequirementCheckRequest {
+    /// Persyaratan terpenuhi
+    #[schema(example = true)]
+    passed: bool,
+
+    /// Catatan pemeriksaan (opsional)
+    #[schema(example = "Lulus tes Ishihara, surat keterangan dokter terlampir")]
+    notes: Option<String>,
+}
+
+/// Kecocokan pendaftar dengan daftar penerima bantuan sosial
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AssistanceMatchResponse {
+    /// ID kecocokan
+    #[schema(example = 1)]
+    id: i32,
+
+    /// ID pendaftaran
+    #[schema(example = 1)]
+    registration_id: i32,
+
+    /// Program bantuan (dtks/pkh/kip)
+    #[schema(example = "kip")]
+    program: String,
+
+    /// Data yang cocok (student_nik/parent_nik/kip_number)
+    #[schema(example = "student_nik")]
+    matched_on: String,
+
+    /// Status kecocokan (matched/verified/rejected)
+    #[schema(example = "matched")]
+    status: String,
+
+    /// Catatan tinjauan
+    #[schema(example = "Nama dan alamat sesuai dengan KK")]
+    notes: Option<String>,
+
+    /// ID daftar penerima bantuan
+    #[schema(example = 1)]
+    list_id: i32,
+
+    /// Nama daftar penerima bantuan
+    #[schema(example = "KIP Kota Bandung 2024")]
+    list_name: String,
+
+    /// NIK penerima pada daftar
+    #[schema(example = "3273010101100001")]
+    beneficiary_nik: Option<String>,
+
+    /// Nomor KIP penerima pada daftar
+    #[schema(example = "KIP1234567890")]
+    beneficiary_kip_number: Option<String>,
+
+    /// Nama penerima pada daftar
+    #[schema(example = "Ahmad Fauzi")]
+    beneficiary_name: Option<String>,
+
+    /// ID admin yang meninjau
+    #[schema(example = 2)]
+    verified_by: Option<i32>,
+
+    /// Waktu tinjauan
+    #[schema(value_type = Option<String>, example = "2024-06-05T10:00:00Z")]
+    verified_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<AssistanceMatchDetail> for AssistanceMatchResponse {
+    fn from(detail: AssistanceMatchDetail) -> Self {
+        let m = detail.assistance_match;
+        Self {
+            id: m.id,
+            registration_id: m.registration_id,
+            program: m.program,
+            matched_on: m.matched_on,
+            status: m.status,
+            notes: m.notes,
+            list_id: detail.list_id,
+            list_name: detail.list_name,
+            beneficiary_nik: detail.beneficiary_nik,
+            beneficiary_kip_number: detail.beneficiary_kip_number,
+            beneficiary_name: detail.beneficiary_name,
+            verified_by: m.verified_by,
+            verified_at: m.verified_at,
+        }
+    }
+}
+
 /// Response data pendaftaran (simplified)
 #[derive(Debug, Serialize, ToSchema)]
 pub struct RegistrationResponse {
@@ -140,6 +353,27 @@ pub struct RegistrationResponse {
     #[schema(example = "Dokumen tidak lengkap")]
     rejection_reason: Option<String>,
     
+    /// Batas waktu perpanjangan pendaftaran
+    #[schema(value_type = Option<String>, example = "2024-07-03T16:59:59Z")]
+    deadline_extended_until: Option<chrono::DateTime<chrono::Utc>>,
+    
+    /// Hasil pemeriksaan usia terhadap aturan usia periode
+    age_check: Option<AgeCheckResponse>,
+    
+    /// Perbaikan terakhir beserta perubahannya, jika pendaftaran dikirim ulang
+    resubmission: Option<RevisionResponse>,
+    
+    /// Waktu pendaftaran dikirim (terakhir)
+    #[schema(value_type = Option<String>, example = "2024-06-10T08:00:00Z")]
+    submitted_at: Option<chrono::DateTime<chrono::Utc>>,
+    
+    /// ID verifikator yang ditugaskan
+    #[schema(example = 2)]
+    assigned_verifier_id: Option<i32>,
+    
+    /// Klaim verifikator yang sedang mengerjakan pendaftaran
+    claim: Option<ClaimResponse>,
+    
     /// Waktu pembuatan
     #[schema(value_type = String, example = "2024-01-01T00:00:00Z")]
     created_at: chrono::DateTime<chrono::Utc>,
@@ -165,12 +399,69 @@ impl From<crate::models::registration::Registration> for RegistrationResponse {
             parent_phone: reg.parent_phone,
             status: reg.status,
             rejection_reason: reg.rejection_reason,
+            deadline_extended_until: reg.deadline_extended_until,
+            age_check: None,
+            resubmission: None,
+            submitted_at: reg.submitted_at,
+            assigned_verifier_id: reg.assigned_verifier_id,
+            claim: None,
             created_at: reg.created_at,
             updated_at: reg.updated_at,
         }
     }
 }
 
+/// Hasil pemeriksaan usia siswa
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AgeCheckResponse {
+    /// Tanggal acuan perhitungan usia
+    #[schema(value_type = String, example = "2024-07-01")]
+    reference_date: chrono::NaiveDate,
+    
+    /// Usia (tahun) pada tanggal acuan
+    #[schema(example = 7)]
+    age_years: Option<u32>,
+    
+    /// Sisa bulan usia pada tanggal acuan
+    #[schema(example = 2)]
+    age_months: Option<u32>,
+    
+    /// Memenuhi usia minimal dan maksimal
+    #[schema(example = true)]
+    eligible: bool,
+    
+    /// Memenuhi usia minimal
+    #[schema(example = true)]
+    meets_minimum: bool,
+    
+    /// Tidak melebihi usia maksimal
+    #[schema(example = true)]
+    meets_maximum: bool,
+    
+    /// Mendapat prioritas usia
+    #[schema(example = true)]
+    priority: bool,
+    
+    /// Poin usia yang ditambahkan ke skor seleksi
+    #[schema(example = 10.0)]
+    age_score: f64,
+}
+
+impl From<AgeEvaluation> for AgeCheckResponse {
+    fn from(evaluation: AgeEvaluation) -> Self {
+        Self {
+            reference_date: evaluation.reference_date,
+            age_years: evaluation.age.map(|age| age.years),
+            age_months: evaluation.age.map(|age| age.months),
+            eligible: evaluation.is_eligible(),
+            meets_minimum: evaluation.meets_minimum,
+            meets_maximum: evaluation.meets_maximum,
+            priority: evaluation.priority,
+            age_score: evaluation.score,
+        }
+    }
+}
+
 /// Response pending verifications
 #[derive(Debug, Serialize, ToSchema)]
 pub struct PendingVerificationsResponse {
@@ -194,6 +485,102 @@ pub struct PendingVerificationsResponse {
     total_pages: i64,
 }
 
+/// Response daftar ulang
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EnrollmentResponse {
+    /// Pendaftaran yang telah daftar ulang
+    registration: RegistrationResponse,
+    
+    /// ID pendaftaran siswa di sekolah lain yang otomatis ditarik
+    #[schema(example = json!([12, 15]))]
+    withdrawn_registration_ids: Vec<i32>,
+}
+
+/// Request untuk perpanjangan batas waktu pendaftaran
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExtendDeadlineRequest {
+    /// Batas waktu baru untuk melengkapi dan mengirim pendaftaran
+    #[schema(value_type = String, example = "2024-07-03T23:59:59+07:00")]
+    until: chrono::DateTime<chrono::Utc>,
+    
+    /// Alasan perpanjangan
+    #[schema(example = "Kendala jaringan saat unggah dokumen")]
+    reason: String,
+}
+
+/// Query untuk mengambil pendaftaran berikutnya dari antrean
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ClaimNextQuery {
+    /// Filter berdasarkan ID periode
+    #[schema(example = 1)]
+    period_id: Option<i32>,
+    
+    /// Filter berdasarkan ID jalur
+    #[schema(example = 1)]
+    path_id: Option<i32>,
+}
+
+/// Query untuk metrik antrean verifikasi
+#[derive(Debug, Deserialize, ToSchema, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QueueMetricsQuery {
+    /// ID periode
+    #[schema(example = 1)]
+    period_id: i32,
+}
+
+/// Request untuk membagikan pendaftaran ke verifikator
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AssignRegistrationsRequest {
+    /// ID periode
+    #[schema(example = 1)]
+    period_id: i32,
+    
+    /// Cara pembagian (path/round_robin)
+    #[schema(example = "round_robin")]
+    strategy: String,
+    
+    /// ID jalur (wajib untuk `path`, opsional untuk `round_robin`)
+    #[schema(example = 1)]
+    path_id: Option<i32>,
+    
+    /// ID verifikator (admin sekolah)
+    #[schema(example = json!([2, 3]))]
+    verifier_ids: Vec<i32>,
+}
+
+/// Klaim verifikator atas sebuah pendaftaran
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClaimResponse {
+    /// ID pendaftaran
+    #[schema(example = 1)]
+    registration_id: i32,
+    
+    /// ID verifikator
+    #[schema(example = 2)]
+    verifier_id: i32,
+    
+    /// Waktu klaim
+    #[schema(value_type = String, example = "2024-06-10T08:00:00Z")]
+    locked_at: chrono::DateTime<chrono::Utc>,
+    
+    /// Klaim dilepas otomatis setelah waktu ini
+    #[schema(value_type = String, example = "2024-06-10T08:30:00Z")]
+    expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<VerificationLock> for ClaimResponse {
+    fn from(lock: VerificationLock) -> Self {
+        Self {
+            registration_id: lock.registration_id,
+            verifier_id: lock.verifier_id,
+            locked_at: lock.locked_at,
+            expires_at: lock.expires_at,
+        }
+    }
+}
+
 /// Response pesan sukses
 #[derive(Debug, Serialize, ToSchema)]
 pub struct MessageResponse {
@@ -204,7 +591,9 @@ pub struct MessageResponse {
 
 /// Mendapatkan daftar pendaftaran yang menunggu verifikasi
 ///
-/// Endpoint ini mengembalikan daftar pendaftaran yang perlu diverifikasi oleh admin sekolah.
+/// Endpoint ini mengembalikan daftar pendaftaran yang perlu diverifikasi oleh admin sekolah,
+/// beserta hasil pemeriksaan usia siswa terhadap aturan usia periode. Pendaftaran yang
+/// dikirim ulang setelah perbaikan menyertakan perubahan datanya.
 #[utoipa::path(
     get,
     path = "/api/verifications/pending",
@@ -230,8 +619,7 @@ async fn get_pending_verifications(
     })?;
 
     // Create verification service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let verification_service = VerificationService::new(registration_repo);
+    let verification_service = verification_service(&state);
 
     // Get pending verifications
     let (registrations, total) = verification_service
@@ -247,7 +635,15 @@ async fn get_pending_verifications(
     let total_pages = (total as f64 / query.page_size as f64).ceil() as i64;
 
     Ok(Json(PendingVerificationsResponse {
-        registrations: registrations.into_iter().map(|r| r.into()).collect(),
+        registrations: registrations
+            .into_iter()
+            .map(|pending| RegistrationResponse {
+                age_check: Some(pending.age_check.into()),
+                resubmission: pending.resubmission.map(|r| r.into()),
+                claim: pending.claim.map(|c| c.into()),
+                ..pending.registration.into()
+            })
+            .collect(),
         total,
         page: query.page,
         page_size: query.page_size,
@@ -283,8 +679,7 @@ async fn get_verification_stats(
     })?;
 
     // Create verification service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let verification_service = VerificationService::new(registration_repo);
+    let verification_service = verification_service(&state);
 
     // Get statistics
     let stats = verification_service
@@ -308,7 +703,8 @@ async fn get_verification_stats(
         (status = 200, description = "Pendaftaran berhasil diverifikasi", body = RegistrationResponse),
         (status = 401, description = "Tidak terautentikasi"),
         (status = 403, description = "Tidak memiliki akses"),
-        (status = 404, description = "Pendaftaran tidak ditemukan")
+        (status = 404, description = "Pendaftaran tidak ditemukan"),
+        (status = 409, description = "Pendaftaran sedang diklaim verifikator lain")
     ),
     security(
         ("bearer_auth" = [])
@@ -321,8 +717,7 @@ async fn verify_registration(
 ) -> AppResult<Json<RegistrationResponse>> {
 
     // Create verification service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let verification_service = VerificationService::new(registration_repo);
+    let verification_service = verification_service(&state);
 
     // Verify registration
     let verified_registration = verification_service
@@ -348,7 +743,8 @@ async fn verify_registration(
         (status = 400, description = "Request tidak valid"),
         (status = 401, description = "Tidak terautentikasi"),
         (status = 403, description = "Tidak memiliki akses"),
-        (status = 404, description = "Pendaftaran tidak ditemukan")
+        (status = 404, description = "Pendaftaran tidak ditemukan"),
+        (status = 409, description = "Pendaftaran sedang diklaim verifikator lain")
     ),
     security(
         ("bearer_auth" = [])
@@ -367,8 +763,7 @@ async fn reject_registration(
 
 
     // Create verification service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let verification_service = VerificationService::new(registration_repo);
+    let verification_service = verification_service(&state);
 
     // Reject registration
     let rejected_registration = verification_service
@@ -378,9 +773,117 @@ async fn reject_registration(
     Ok(Json(rejected_registration.into()))
 }
 
+/// Daftar ulang siswa yang diterima
+///
+/// Endpoint ini menandai pendaftaran yang diterima sebagai `enrolled`. Pendaftaran aktif
+/// lain dengan NISN yang sama pada tahun ajaran yang sama di sekolah lain otomatis
+/// ditarik (`withdrawn`) sehingga kursinya dapat diberikan kepada pendaftar lain.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/{id}/enroll",
+    tag = "Verifications",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Daftar ulang berhasil", body = EnrollmentResponse),
+        (status = 400, description = "Pendaftaran belum diterima"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan"),
+        (status = 409, description = "Siswa sudah daftar ulang di sekolah lain")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn enroll_registration(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<EnrollmentResponse>> {
+    let school_id = if auth_user.role == "super_admin" {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
+
+    // Create duplicate service
+    let duplicate_service = DuplicateService::new(
+        DuplicateRepository::new(state.db.clone()),
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    );
+
+    // Enroll and withdraw the student's other registrations
+    let result = duplicate_service
+        .enroll_registration(id, school_id, auth_user.id)
+        .await?;
+
+    Ok(Json(EnrollmentResponse {
+        registration: result.registration.into(),
+        withdrawn_registration_ids: result.withdrawn_registration_ids,
+    }))
+}
+
+/// Verifikasi nilai rapor satu semester
+///
+/// Endpoint ini menandai nilai rapor satu semester sudah dicocokkan dengan dokumen
+/// rapor yang diupload pendaftar.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/{id}/grades/{semester}/verify",
+    tag = "Verifications",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran"),
+        ("semester" = i32, Path, description = "Semester")
+    ),
+    request_body = VerifyGradesRequest,
+    responses(
+        (status = 200, description = "Nilai semester berhasil diverifikasi", body = GradeSummary),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran atau nilai semester tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn verify_grades(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((id, semester)): Path<(i32, i32)>,
+    Json(payload): Json<VerifyGradesRequest>,
+) -> AppResult<Json<GradeSummary>> {
+    let school_id = if auth_user.role == "super_admin" {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
+
+    // Create grade service
+    let grade_service = GradeService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    );
+
+    let summary = grade_service
+        .verify_semester(id, semester, payload.document_id, school_id, auth_user.id)
+        .await?;
+
+    Ok(Json(summary))
+}
+
 /// Verifikasi dokumen
 ///
 /// Endpoint ini digunakan untuk memverifikasi atau menolak dokumen pendaftaran.
+/// Menolak dokumen wajib jalur langsung menindaklanjuti pendaftaran sesuai kebijakan
+/// periode: dikembalikan untuk perbaikan (`revision`) atau ditolak (`reject`).
 #[utoipa::path(
     post,
     path = "/api/verifications/documents/{doc_id}/verify",
@@ -390,11 +893,12 @@ async fn reject_registration(
     ),
     request_body = VerifyDocumentRequest,
     responses(
-        (status = 200, description = "Dokumen berhasil diverifikasi", body = MessageResponse),
+        (status = 200, description = "Dokumen berhasil diverifikasi", body = DocumentVerificationResponse),
         (status = 400, description = "Request tidak valid"),
         (status = 401, description = "Tidak terautentikasi"),
         (status = 403, description = "Tidak memiliki akses"),
-        (status = 404, description = "Dokumen tidak ditemukan")
+        (status = 404, description = "Dokumen tidak ditemukan"),
+        (status = 409, description = "Pendaftaran sedang diklaim verifikator lain")
     ),
     security(
         ("bearer_auth" = [])
@@ -405,28 +909,877 @@ async fn verify_document(
     Extension(auth_user): Extension<AuthUser>,
     Path(doc_id): Path<i32>,
     Json(payload): Json<VerifyDocumentRequest>,
-) -> AppResult<Json<MessageResponse>> {
+) -> AppResult<Json<DocumentVerificationResponse>> {
     // Validate verification status
     if !["approved", "rejected"].contains(&payload.verification_status.as_str()) {
         return Err(AppError::Validation("Invalid verification status".to_string()));
     }
 
+    let school_id = if auth_user.role == "super_admin" {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
 
     // Create verification service
-    let registration_repo = RegistrationRepository::new(state.db.clone());
-    let verification_service = VerificationService::new(registration_repo);
+    let verification_service = verification_service(&state);
 
     // Verify document
-    verification_service
+    let result = verification_service
         .verify_document(
             doc_id,
             payload.verification_status,
             payload.verification_notes,
+            school_id,
             auth_user.id,
         )
         .await?;
 
-    Ok(Json(MessageResponse {
-        message: "Document verification status updated successfully".to_string(),
+    Ok(Json(DocumentVerificationResponse {
+        document: result.document.into(),
+        registration: result.registration.into(),
+        outcome: result.outcome.map(|policy| policy.as_str().to_string()),
     }))
 }
+
+/// Verifikasi prestasi
+///
+/// Endpoint ini menyetujui atau menolak satu prestasi berdasarkan sertifikatnya. Poin
+/// prestasi pendaftar untuk seleksi dihitung ulang dari prestasi yang disetujui.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/achievements/{achievement_id}/verify",
+    tag = "Verifications",
+    params(
+        ("achievement_id" = i32, Path, description = "ID prestasi")
+    ),
+    request_body = VerifyAchievementRequest,
+    responses(
+        (status = 200, description = "Prestasi berhasil diverifikasi", body = AchievementSummary),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Prestasi tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn verify_achievement(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(achievement_id): Path<i32>,
+    Json(payload): Json<VerifyAchievementRequest>,
+) -> AppResult<Json<AchievementSummary>> {
+    let school_id = if auth_user.role == "super_admin" {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
+
+    // Create achievement service
+    let achievement_service = AchievementService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    );
+
+    let summary = achievement_service
+        .verify_achievement(
+            achievement_id,
+            payload.verification_status,
+            payload.verification_notes,
+            school_id,
+            auth_user.id,
+        )
+        .await?;
+
+    Ok(Json(summary))
+}
+
+fn assistance_service(state: &AppState) -> AssistanceService {
+    AssistanceService::new(
+        AssistanceRepository::new(state.db.clone()),
+        RegistrationRepository::new(state.db.clone()),
+        RegionRepository::new(state.db.clone()),
+    )
+}
+
+/// Kecocokan data bantuan sosial pendaftar
+///
+/// Endpoint ini menampilkan hasil pencocokan otomatis pendaftar jalur afirmasi dengan
+/// daftar DTKS/PKH/KIP yang diimpor. Bonus afirmasi hanya diberikan untuk kecocokan
+/// yang sudah diverifikasi.
+#[utoipa::path(
+    get,
+    path = "/api/verifications/{id}/assistance-matches",
+    tag = "Verifications",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Kecocokan berhasil diambil", body = Vec<AssistanceMatchResponse>),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_assistance_matches(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<Vec<AssistanceMatchResponse>>> {
+    let school_id = if auth_user.role == "super_admin" {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
+
+    let matches = assistance_service(&state)
+        .list_matches(id, school_id)
+        .await?;
+
+    Ok(Json(matches.into_iter().map(|m| m.into()).collect()))
+}
+
+/// Tinjau kecocokan data bantuan sosial
+///
+/// Endpoint ini digunakan verifikator untuk mengonfirmasi atau menolak kecocokan
+/// pendaftar dengan daftar penerima bantuan. Program bantuan terverifikasi pendaftar
+/// dihitung ulang setelah tinjauan.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/assistance-matches/{match_id}/review",
+    tag = "Verifications",
+    params(
+        ("match_id" = i32, Path, description = "ID kecocokan")
+    ),
+    request_body = ReviewAssistanceMatchRequest,
+    responses(
+        (status = 200, description = "Kecocokan berhasil ditinjau", body = Vec<AssistanceMatchResponse>),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Kecocokan tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn review_assistance_match(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(match_id): Path<i32>,
+    Json(payload): Json<ReviewAssistanceMatchRequest>,
+) -> AppResult<Json<Vec<AssistanceMatchResponse>>> {
+    let school_id = if auth_user.role == "super_admin" {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
+
+    let matches = assistance_service(&state)
+        .review_match(match_id, payload.status, payload.notes, school_id, auth_user.id)
+        .await?;
+
+    Ok(Json(matches.into_iter().map(|m| m.into()).collect()))
+}
+
+/// Catat hasil pemeriksaan persyaratan jurusan
+///
+/// Endpoint ini digunakan verifikator untuk mencatat hasil persyaratan jurusan yang
+/// diperiksa manual, misalnya tes buta warna. Persyaratan yang belum dicatat dianggap
+/// belum terpenuhi saat seleksi.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/{id}/requirements/{key}",
+    tag = "Verifications",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran"),
+        ("key" = String, Path, description = "Kunci persyaratan, misalnya color_vision")
+    ),
+    request_body = RecordRequirementCheckRequest,
+    responses(
+        (status = 200, description = "Hasil pemeriksaan berhasil dicatat", body = MajorChoiceSummary),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn record_requirement_check(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((id, key)): Path<(i32, String)>,
+    Json(payload): Json<RecordRequirementCheckRequest>,
+) -> AppResult<Json<MajorChoiceSummary>> {
+    let school_id = if auth_user.role == "super_admin" {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
+
+    let major_service = MajorService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+    );
+
+    let summary = major_service
+        .record_requirement_check(id, key, payload.passed, payload.notes, school_id, auth_user.id)
+        .await?;
+
+    Ok(Json(summary))
+}
+
+/// Memperpanjang batas waktu pendaftaran
+///
+/// Endpoint ini memberi pendaftar berstatus draft tambahan waktu untuk melengkapi dan
+/// mengirim pendaftaran setelah jadwal pendaftaran periode ditutup.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/{id}/deadline-extension",
+    tag = "Verifications",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    request_body = ExtendDeadlineRequest,
+    responses(
+        (status = 200, description = "Batas waktu berhasil diperpanjang", body = RegistrationResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn extend_deadline(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<ExtendDeadlineRequest>,
+) -> AppResult<Json<RegistrationResponse>> {
+    let school_id = if auth_user.role == "super_admin" {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
+
+    let registration = registration_service(&state)
+        .extend_deadline(id, payload.until, payload.reason, school_id, auth_user.id)
+        .await?;
+
+    Ok(Json(registration.into()))
+}
+
+/// Kembalikan pendaftaran untuk diperbaiki
+///
+/// Endpoint ini digunakan untuk mengembalikan pendaftaran yang sudah disubmit kepada
+/// orang tua dengan catatan per data dan per dokumen, sebagai ganti penolakan.
+/// Dokumen yang ditandai otomatis berstatus `rejected`.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/{id}/request-revision",
+    tag = "Verifications",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    request_body = RequestRevisionRequest,
+    responses(
+        (status = 200, description = "Pendaftaran dikembalikan untuk perbaikan", body = RequestRevisionResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan"),
+        (status = 409, description = "Pendaftaran sedang diklaim verifikator lain")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn request_revision(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+    Json(payload): Json<RequestRevisionRequest>,
+) -> AppResult<Json<RequestRevisionResponse>> {
+    let school_id = if auth_user.role == "super_admin" {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
+
+    // Create verification service
+    let verification_service = verification_service(&state);
+
+    let (registration, revision) = verification_service
+        .request_revision(
+            id,
+            payload.notes,
+            payload.fields.into_iter().map(|f| f.into()).collect(),
+            payload.documents.into_iter().map(|d| d.into()).collect(),
+            school_id,
+            auth_user.id,
+        )
+        .await?;
+
+    Ok(Json(RequestRevisionResponse {
+        registration: registration.into(),
+        revision: revision.into(),
+    }))
+}
+
+/// Response klaim pendaftaran dari antrean
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ClaimedRegistrationResponse {
+    /// Pendaftaran yang diklaim
+    registration: RegistrationResponse,
+    
+    /// Klaim verifikator
+    claim: ClaimResponse,
+}
+
+/// Ambil pendaftaran berikutnya dari antrean verifikasi
+///
+/// Endpoint ini mengklaim pendaftaran berikutnya untuk verifikator: klaim yang masih
+/// dipegang, lalu pendaftaran yang ditugaskan kepadanya, lalu pendaftaran terlama yang
+/// belum ditugaskan. Klaim berlaku selama waktu tertentu dan dilepas otomatis jika habis.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/queue/claim",
+    tag = "Verifications",
+    params(ClaimNextQuery),
+    responses(
+        (status = 200, description = "Pendaftaran berhasil diklaim", body = ClaimedRegistrationResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Antrean verifikasi kosong")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn claim_next_registration(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<ClaimNextQuery>,
+) -> AppResult<Json<ClaimedRegistrationResponse>> {
+    let school_id = auth_user.school_id.ok_or_else(|| {
+        AppError::Authentication("User must be associated with a school".to_string())
+    })?;
+
+    let (registration, lock) = verification_service(&state)
+        .claim_next(
+            school_id,
+            auth_user.id,
+            query.period_id,
+            query.path_id,
+            state.config.verification_lock_minutes,
+        )
+        .await?;
+
+    Ok(Json(ClaimedRegistrationResponse {
+        registration: registration.into(),
+        claim: lock.into(),
+    }))
+}
+
+/// Klaim pendaftaran tertentu
+///
+/// Endpoint ini mengklaim satu pendaftaran untuk diverifikasi, atau memperpanjang klaim
+/// milik verifikator sendiri.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/{id}/claim",
+    tag = "Verifications",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Pendaftaran berhasil diklaim", body = ClaimResponse),
+        (status = 400, description = "Pendaftaran tidak menunggu verifikasi"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran tidak ditemukan"),
+        (status = 409, description = "Pendaftaran sedang diklaim verifikator lain")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn claim_registration(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<ClaimResponse>> {
+    let school_id = if auth_user.role == "super_admin" {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
+
+    let lock = verification_service(&state)
+        .claim_registration(id, school_id, auth_user.id, state.config.verification_lock_minutes)
+        .await?;
+
+    Ok(Json(lock.into()))
+}
+
+/// Lepas klaim pendaftaran
+///
+/// Endpoint ini melepas klaim verifikator sehingga pendaftaran kembali ke antrean.
+/// Super admin dapat melepas klaim milik verifikator mana pun.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/{id}/release",
+    tag = "Verifications",
+    params(
+        ("id" = i32, Path, description = "ID pendaftaran")
+    ),
+    responses(
+        (status = 200, description = "Klaim berhasil dilepas", body = MessageResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Pendaftaran atau klaim tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn release_claim(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<MessageResponse>> {
+    let is_super_admin = auth_user.role == "super_admin";
+    let school_id = if is_super_admin {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
+
+    verification_service(&state)
+        .release_claim(id, school_id, auth_user.id, is_super_admin)
+        .await?;
+
+    Ok(Json(MessageResponse {
+        message: "Claim released successfully".to_string(),
+    }))
+}
+
+/// Bagikan pendaftaran ke verifikator
+///
+/// Endpoint ini membagikan pendaftaran yang menunggu verifikasi dan belum ditugaskan.
+/// Strategi `path` menugaskan seluruh pendaftaran satu jalur ke verifikator yang dipilih
+/// secara bergiliran, strategi `round_robin` membagikan pendaftaran terlama lebih dulu.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/queue/assign",
+    tag = "Verifications",
+    request_body = AssignRegistrationsRequest,
+    responses(
+        (status = 200, description = "Pendaftaran berhasil dibagikan", body = AssignmentSummary),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Periode atau jalur tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn assign_registrations(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<AssignRegistrationsRequest>,
+) -> AppResult<Json<AssignmentSummary>> {
+    let school_id = auth_user.school_id.ok_or_else(|| {
+        AppError::Authentication("User must be associated with a school".to_string())
+    })?;
+
+    let summary = verification_service(&state)
+        .assign_registrations(
+            school_id,
+            payload.period_id,
+            payload.strategy,
+            payload.path_id,
+            payload.verifier_ids,
+            auth_user.id,
+        )
+        .await?;
+
+    Ok(Json(summary))
+}
+
+/// Metrik antrean verifikasi
+///
+/// Endpoint ini mengembalikan umur antrean verifikasi dan kinerja per verifikator
+/// (jumlah ditugaskan, diklaim, diselesaikan, klaim kedaluwarsa, dan rata-rata durasi).
+#[utoipa::path(
+    get,
+    path = "/api/verifications/queue/metrics",
+    tag = "Verifications",
+    params(QueueMetricsQuery),
+    responses(
+        (status = 200, description = "Metrik antrean berhasil diambil", body = VerificationQueueMetrics),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Periode tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_queue_metrics(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<QueueMetricsQuery>,
+) -> AppResult<Json<VerificationQueueMetrics>> {
+    let school_id = auth_user.school_id.ok_or_else(|| {
+        AppError::Authentication("User must be associated with a school".to_string())
+    })?;
+
+    let metrics = verification_service(&state)
+        .get_queue_metrics(school_id, query.period_id)
+        .await?;
+
+    Ok(Json(metrics))
+}
+
+/// Request aksi massal pada pendaftaran
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkRegistrationsRequest {
+    /// Aksi (verify/reject/request_revision)
+    #[schema(example = "verify")]
+    action: String,
+    
+    /// ID pendaftaran yang dipilih; kosongkan untuk memakai filter periode
+    #[schema(example = json!([12, 13, 14]))]
+    registration_ids: Option<Vec<i32>>,
+    
+    /// Semua pendaftaran berstatus `submitted` pada periode ini
+    #[schema(example = 1)]
+    period_id: Option<i32>,
+    
+    /// Batasi filter periode pada satu jalur (opsional)
+    #[schema(example = 1)]
+    path_id: Option<i32>,
+    
+    /// Alasan penolakan (wajib untuk `reject`, minimal 10 karakter) atau catatan perbaikan
+    #[schema(example = "Data tidak memenuhi persyaratan jalur")]
+    notes: Option<String>,
+}
+
+/// Request aksi massal pada dokumen
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkDocumentsRequest {
+    /// Aksi (approve/reject)
+    #[schema(example = "approve")]
+    action: String,
+    
+    /// ID dokumen yang dipilih; kosongkan untuk memakai filter periode
+    #[schema(example = json!([31, 32]))]
+    document_ids: Option<Vec<i32>>,
+    
+    /// Semua dokumen `pending` dari pendaftaran `submitted` pada periode ini
+    #[schema(example = 1)]
+    period_id: Option<i32>,
+    
+    /// Batasi filter periode pada satu jalur (opsional)
+    #[schema(example = 1)]
+    path_id: Option<i32>,
+    
+    /// Batasi filter periode pada satu tipe dokumen (opsional)
+    #[schema(example = "kartu_keluarga")]
+    document_type: Option<String>,
+    
+    /// Catatan verifikasi (wajib untuk `reject`, minimal 10 karakter)
+    #[schema(example = "Dokumen tidak terbaca")]
+    notes: Option<String>,
+}
+
+/// Hasil aksi massal pada satu pendaftaran atau dokumen
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkItemResponse {
+    /// ID pendaftaran atau dokumen
+    #[schema(example = 12)]
+    entity_id: i32,
+    
+    /// Status (pending/succeeded/failed)
+    #[schema(example = "failed")]
+    status: String,
+    
+    /// Alasan kegagalan
+    #[schema(example = "Validation error: Required documents are still pending verification: ijazah")]
+    error: Option<String>,
+    
+    /// Waktu diproses
+    #[schema(value_type = Option<String>, example = "2024-06-10T08:00:00Z")]
+    processed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<BulkVerificationItem> for BulkItemResponse {
+    fn from(item: BulkVerificationItem) -> Self {
+        Self {
+            entity_id: item.entity_id,
+            status: item.status,
+            error: item.error,
+            processed_at: item.processed_at,
+        }
+    }
+}
+
+/// Laporan aksi massal
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkJobResponse {
+    /// ID job
+    #[schema(example = 1)]
+    id: i32,
+    
+    /// Sasaran (registration/document)
+    #[schema(example = "registration")]
+    target: String,
+    
+    /// Aksi
+    #[schema(example = "verify")]
+    action: String,
+    
+    /// Alasan atau catatan yang diterapkan ke setiap item
+    notes: Option<String>,
+    
+    /// Status job (pending/running/completed/failed)
+    #[schema(example = "completed")]
+    status: String,
+    
+    /// Jumlah item
+    #[schema(example = 40)]
+    total: i32,
+    
+    /// Jumlah item berhasil
+    #[schema(example = 38)]
+    succeeded: i32,
+    
+    /// Jumlah item gagal
+    #[schema(example = 2)]
+    failed: i32,
+    
+    /// Hasil per item
+    items: Vec<BulkItemResponse>,
+    
+    /// Waktu mulai diproses
+    #[schema(value_type = Option<String>, example = "2024-06-10T08:00:00Z")]
+    started_at: Option<chrono::DateTime<chrono::Utc>>,
+    
+    /// Waktu selesai diproses
+    #[schema(value_type = Option<String>, example = "2024-06-10T08:00:05Z")]
+    finished_at: Option<chrono::DateTime<chrono::Utc>>,
+    
+    /// Waktu pembuatan
+    #[schema(value_type = String, example = "2024-06-10T08:00:00Z")]
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl BulkJobResponse {
+    fn new(job: BulkVerificationJob, items: Vec<BulkVerificationItem>) -> Self {
+        Self {
+            id: job.id,
+            target: job.target,
+            action: job.action,
+            notes: job.notes,
+            status: job.status,
+            total: job.total,
+            succeeded: job.succeeded,
+            failed: job.failed,
+            items: items.into_iter().map(|i| i.into()).collect(),
+            started_at: job.started_at,
+            finished_at: job.finished_at,
+            created_at: job.created_at,
+        }
+    }
+}
+
+/// Run a bulk job and report it: finished jobs with 200, background ones with 202
+async fn dispatch_bulk_job(
+    state: &AppState,
+    job: BulkVerificationJob,
+) -> AppResult<(StatusCode, Json<BulkJobResponse>)> {
+    let job = verification_service(state).dispatch_bulk_job(job).await?;
+    let (job, items) = verification_service(state)
+        .get_bulk_job(job.id, Some(job.school_id))
+        .await?;
+
+    let status = if job.finished_at.is_some() {
+        StatusCode::OK
+    } else {
+        StatusCode::ACCEPTED
+    };
+
+    Ok((status, Json(BulkJobResponse::new(job, items))))
+}
+
+/// Aksi massal pada pendaftaran
+///
+/// Endpoint ini memverifikasi, menolak, atau mengembalikan untuk perbaikan sekumpulan
+/// pendaftaran, dipilih dengan daftar ID atau filter periode. Setiap pendaftaran melalui
+/// pemeriksaan yang sama dengan aksi satuan dan dicatat di audit log. Pengembalian massal
+/// menandai dokumen yang sudah ditolak. Hingga 50 item diproses langsung (200), selebihnya
+/// di latar belakang (202) dan hasilnya dapat dipantau melalui `GET /bulk/{job_id}`.
+#[utoipa::path(
+    post,
+    path = "/api/verifications/bulk/registrations",
+    tag = "Verifications",
+    request_body = BulkRegistrationsRequest,
+    responses(
+        (status = 200, description = "Aksi massal selesai", body = BulkJobResponse),
+        (status = 202, description = "Aksi massal diproses di latar belakang", body = BulkJobResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Periode tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn bulk_registrations(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<BulkRegistrationsRequest>,
+) -> AppResult<(StatusCode, Json<BulkJobResponse>)> {
+    let school_id = auth_user.school_id.ok_or_else(|| {
+        AppError::Authentication("User must be associated with a school".to_string())
+    })?;
+
+    let job = verification_service(&state)
+        .create_bulk_job(
+            school_id,
+            auth_user.id,
+            BulkTarget::Registration,
+            payload.action,
+            payload.registration_ids,
+            BulkFilter {
+                period_id: payload.period_id,
+                path_id: payload.path_id,
+                document_type: None,
+            },
+            payload.notes,
+        )
+        .await?;
+
+    dispatch_bulk_job(&state, job).await
+}
+
+/// Aksi massal pada dokumen
+///
+/// Endpoint ini menyetujui atau menolak sekumpulan dokumen, dipilih dengan daftar ID atau
+/// filter periode. Menolak dokumen wajib jalur menindaklanjuti pendaftarannya sesuai
+/// kebijakan periode. Hingga 50 item diproses langsung (200), selebihnya di latar
+/// belakang (202).
+#[utoipa::path(
+    post,
+    path = "/api/verifications/bulk/documents",
+    tag = "Verifications",
+    request_body = BulkDocumentsRequest,
+    responses(
+        (status = 200, description = "Aksi massal selesai", body = BulkJobResponse),
+        (status = 202, description = "Aksi massal diproses di latar belakang", body = BulkJobResponse),
+        (status = 400, description = "Request tidak valid"),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Periode tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn bulk_documents(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<BulkDocumentsRequest>,
+) -> AppResult<(StatusCode, Json<BulkJobResponse>)> {
+    let school_id = auth_user.school_id.ok_or_else(|| {
+        AppError::Authentication("User must be associated with a school".to_string())
+    })?;
+
+    let job = verification_service(&state)
+        .create_bulk_job(
+            school_id,
+            auth_user.id,
+            BulkTarget::Document,
+            payload.action,
+            payload.document_ids,
+            BulkFilter {
+                period_id: payload.period_id,
+                path_id: payload.path_id,
+                document_type: payload.document_type,
+            },
+            payload.notes,
+        )
+        .await?;
+
+    dispatch_bulk_job(&state, job).await
+}
+
+/// Laporan aksi massal
+///
+/// Endpoint ini mengembalikan status aksi massal beserta hasil setiap item.
+#[utoipa::path(
+    get,
+    path = "/api/verifications/bulk/{job_id}",
+    tag = "Verifications",
+    params(
+        ("job_id" = i32, Path, description = "ID job aksi massal")
+    ),
+    responses(
+        (status = 200, description = "Laporan berhasil diambil", body = BulkJobResponse),
+        (status = 401, description = "Tidak terautentikasi"),
+        (status = 403, description = "Tidak memiliki akses"),
+        (status = 404, description = "Job tidak ditemukan")
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+async fn get_bulk_job(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(job_id): Path<i32>,
+) -> AppResult<Json<BulkJobResponse>> {
+    let school_id = if auth_user.role == "super_admin" {
+        None
+    } else {
+        Some(auth_user.school_id.ok_or_else(|| {
+            AppError::Authentication("User must be associated with a school".to_string())
+        })?)
+    };
+
+    let (job, items) = verification_service(&state)
+        .get_bulk_job(job_id, school_id)
+        .await?;
+
+    Ok(Json(BulkJobResponse::new(job, items)))
+}
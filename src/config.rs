@@ -1,6 +1,9 @@
 use anyhow::Result;
 use serde::Deserialize;
 
+/// Accepted values of MALWARE_SCANNER
+pub const MALWARE_SCANNERS: &[&str] = &["clamd", "none"];
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     // Server
@@ -39,6 +42,22 @@ pub struct Config {
 
     // CORS
     pub allowed_origins: Vec<String>,
+
+    // Period lifecycle scheduler
+    pub scheduler_enabled: bool,
+    pub scheduler_interval_secs: u64,
+
+    // Verification queue
+    pub verification_lock_minutes: i64,
+
+    // Document processing
+    pub document_bucket: String,
+    pub recompress_oversized_photos: bool,
+
+    // Malware scanning
+    pub malware_scanner: String,
+    pub clamd_address: String,
+    pub clamd_timeout_secs: u64,
 }
 
 impl Config {
@@ -93,8 +112,43 @@ impl Config {
                 .split(',')
                 .map(|s| s.trim().to_string())
                 .collect(),
+
+            scheduler_enabled: std::env::var("SCHEDULER_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            scheduler_interval_secs: std::env::var("SCHEDULER_INTERVAL_SECS")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()?,
+
+            verification_lock_minutes: std::env::var("VERIFICATION_LOCK_MINUTES")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()?,
+
+            document_bucket: std::env::var("DOCUMENT_BUCKET")
+                .unwrap_or_else(|_| "documents".to_string()),
+            recompress_oversized_photos: std::env::var("RECOMPRESS_OVERSIZED_PHOTOS")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+
+            malware_scanner: std::env::var("MALWARE_SCANNER")
+                .unwrap_or_else(|_| "clamd".to_string()),
+            clamd_address: std::env::var("CLAMD_ADDRESS")
+                .unwrap_or_else(|_| "127.0.0.1:3310".to_string()),
+            clamd_timeout_secs: std::env::var("CLAMD_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()?,
         };
 
+        if !MALWARE_SCANNERS.contains(&config.malware_scanner.as_str()) {
+            anyhow::bail!(
+                "MALWARE_SCANNER must be one of {:?}, got '{}'",
+                MALWARE_SCANNERS,
+                config.malware_scanner
+            );
+        }
+
         Ok(config)
     }
 }
@@ -4,6 +4,7 @@ pub mod dto;
 pub mod integrations;
 pub mod models;
 pub mod repositories;
+pub mod scheduler;
 pub mod services;
 pub mod utils;
 
@@ -8,7 +8,7 @@ use utoipa_swagger_ui::SwaggerUi;
 use utoipa_rapidoc::RapiDoc;
 use utoipa_redoc::{Redoc, Servable};
 
-use ppdb_backend::{api, AppState, Config};
+use ppdb_backend::{api, scheduler, AppState, Config};
 use ppdb_backend::api::docs::ApiDoc;
 
 #[tokio::main]
@@ -53,6 +53,12 @@ async fn main() -> anyhow::Result<()> {
         config: config.clone(),
     };
 
+    // Activate periods and publish announcements on schedule
+    if config.scheduler_enabled {
+        scheduler::spawn(app_state.clone());
+        tracing::info!("Period lifecycle scheduler started");
+    }
+
     // Build router
     let app = Router::new()
         .route("/", get(root))
@@ -0,0 +1,223 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegistrationAchievement {
+    pub id: i32,
+    pub registration_id: i32,
+    pub competition_name: String,
+    pub level: String,
+    pub rank: Option<i32>,
+    pub year: i32,
+    pub participation: String,
+    pub document_id: Option<i32>,
+    pub verification_status: String,
+    pub verification_notes: Option<String>,
+    pub verified_by: Option<i32>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Achievement entered by the applicant, before it is stored
+#[derive(Debug, Clone)]
+pub struct NewAchievement {
+    pub competition_name: String,
+    pub level: String,
+    pub rank: Option<i32>,
+    pub year: i32,
+    pub participation: String,
+    pub document_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AchievementLevel {
+    School,
+    Regency,
+    Province,
+    National,
+    International,
+}
+
+impl AchievementLevel {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AchievementLevel::School => "school",
+            AchievementLevel::Regency => "regency",
+            AchievementLevel::Province => "province",
+            AchievementLevel::National => "national",
+            AchievementLevel::International => "international",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "school" => Some(AchievementLevel::School),
+            "regency" => Some(AchievementLevel::Regency),
+            "province" => Some(AchievementLevel::Province),
+            "national" => Some(AchievementLevel::National),
+            "international" => Some(AchievementLevel::International),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Participation {
+    Individual,
+    Team,
+}
+
+impl Participation {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Participation::Individual => "individual",
+            Participation::Team => "team",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "individual" => Some(Participation::Individual),
+            "team" => Some(Participation::Team),
+            _ => None,
+        }
+    }
+}
+
+/// Rank key used for participants, finalists and ranks without their own entry
+pub const PARTICIPANT_RANK: &str = "participant";
+
+/// Converts achievements into points. Stored per period in
+/// `periods.achievement_point_table`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AchievementPointTable {
+    /// Points per level, keyed by rank ("1", "2", "3", ...) and "participant"
+    pub points: BTreeMap<String, BTreeMap<String, f64>>,
+    /// Multiplier applied to team achievements
+    #[serde(default = "default_team_multiplier")]
+    pub team_multiplier: f64,
+    /// Maximum points counted from a single level
+    #[serde(default)]
+    pub level_caps: BTreeMap<String, f64>,
+    /// Only the N highest scoring achievements are counted
+    #[serde(default)]
+    pub best_n: Option<usize>,
+    /// Maximum total points
+    #[serde(default)]
+    pub max_points: Option<f64>,
+}
+
+fn default_team_multiplier() -> f64 {
+    1.0
+}
+
+impl Default for AchievementPointTable {
+    fn default() -> Self {
+        let level = |points: [f64; 4]| {
+            ["1", "2", "3", PARTICIPANT_RANK]
+                .iter()
+                .zip(points)
+                .map(|(rank, points)| (rank.to_string(), points))
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        let points = [
+            ("international", level([100.0, 90.0, 80.0, 50.0])),
+            ("national", level([80.0, 70.0, 60.0, 30.0])),
+            ("province", level([60.0, 50.0, 40.0, 15.0])),
+            ("regency", level([40.0, 30.0, 20.0, 5.0])),
+            ("school", level([20.0, 15.0, 10.0, 0.0])),
+        ]
+        .into_iter()
+        .map(|(level, points)| (level.to_string(), points))
+        .collect();
+
+        Self {
+            points,
+            team_multiplier: 0.8,
+            level_caps: BTreeMap::from([("school".to_string(), 20.0)]),
+            best_n: Some(3),
+            max_points: Some(100.0),
+        }
+    }
+}
+
+impl AchievementPointTable {
+    /// The period's table, or the default one when none is configured
+    pub fn from_config(config: Option<&serde_json::Value>) -> Result<Self, String> {
+        match config {
+            Some(config) => {
+                let table: Self = serde_json::from_value(config.clone())
+                    .map_err(|e| format!("Invalid achievement point table: {}", e))?;
+                table.validate()?;
+                Ok(table)
+            }
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (level, ranks) in &self.points {
+            if AchievementLevel::from_str(level).is_none() {
+                return Err(format!("Unknown achievement level '{}'", level));
+            }
+
+            for (rank, points) in ranks {
+                if rank != PARTICIPANT_RANK && rank.parse::<u32>().map(|r| r == 0).unwrap_or(true) {
+                    return Err(format!(
+                        "Rank '{}' of level '{}' must be a positive number or '{}'",
+                        rank, level, PARTICIPANT_RANK
+                    ));
+                }
+
+                if *points < 0.0 {
+                    return Err(format!("Points of level '{}' must not be negative", level));
+                }
+            }
+        }
+
+        if !(0.0..=1.0).contains(&self.team_multiplier) {
+            return Err("Team multiplier must be between 0 and 1".to_string());
+        }
+
+        for (level, cap) in &self.level_caps {
+            if AchievementLevel::from_str(level).is_none() || *cap < 0.0 {
+                return Err(format!("Invalid cap for level '{}'", level));
+            }
+        }
+
+        if self.best_n == Some(0) {
+            return Err("best_n must be at least 1".to_string());
+        }
+
+        if self.max_points.map(|m| m < 0.0).unwrap_or(false) {
+            return Err("max_points must not be negative".to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Points of a single achievement before best-N and caps are applied
+    pub fn points_for(&self, level: &str, rank: Option<i32>, participation: &str) -> f64 {
+        let Some(ranks) = self.points.get(level) else {
+            return 0.0;
+        };
+
+        let points = rank
+            .and_then(|rank| ranks.get(&rank.to_string()))
+            .or_else(|| ranks.get(PARTICIPANT_RANK))
+            .copied()
+            .unwrap_or(0.0);
+
+        if participation == Participation::Team.as_str() {
+            points * self.team_multiplier
+        } else {
+            points
+        }
+    }
+}
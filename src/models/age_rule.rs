@@ -0,0 +1,225 @@
+use std::fmt;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::models::period::{Level, Period};
+
+/// Age in completed years and months
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(deny_unknown_fields)]
+pub struct Age {
+    pub years: u32,
+    #[serde(default)]
+    pub months: u32,
+}
+
+impl Age {
+    pub fn new(years: u32, months: u32) -> Self {
+        Self { years, months }
+    }
+
+    pub fn total_months(&self) -> u32 {
+        self.years * 12 + self.months
+    }
+
+    /// Age reached on `reference`, None when born after it
+    pub fn on(birth_date: NaiveDate, reference: NaiveDate) -> Option<Self> {
+        if birth_date > reference {
+            return None;
+        }
+
+        let mut months = (reference.year() - birth_date.year()) * 12 + reference.month() as i32
+            - birth_date.month() as i32;
+        if reference.day() < birth_date.day() {
+            months -= 1;
+        }
+
+        let months = months.max(0) as u32;
+        Some(Self::new(months / 12, months % 12))
+    }
+}
+
+impl fmt::Display for Age {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} years {} months", self.years, self.months)
+    }
+}
+
+/// Age requirements of a period. Stored per period in `periods.age_rules`,
+/// NULL uses the regulation defaults of the period level.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AgeRules {
+    /// Date ages are computed on, default 1 July of the academic year
+    #[serde(default)]
+    pub reference_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub min_age: Option<Age>,
+    #[serde(default)]
+    pub max_age: Option<Age>,
+    /// Applicants at least this old are prioritised
+    #[serde(default)]
+    pub priority_age: Option<Age>,
+    /// Points added to the selection score of prioritised applicants
+    #[serde(default)]
+    pub priority_bonus: f64,
+    /// Points added to the selection score per month of age above the minimum age
+    #[serde(default)]
+    pub points_per_month: f64,
+    /// Maximum points from `points_per_month`
+    #[serde(default)]
+    pub max_age_points: Option<f64>,
+}
+
+/// Result of checking a birth date against the age rules
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AgeEvaluation {
+    pub reference_date: NaiveDate,
+    /// None when the birth date is after the reference date
+    pub age: Option<Age>,
+    pub meets_minimum: bool,
+    pub meets_maximum: bool,
+    pub priority: bool,
+    /// Points added to the selection score
+    pub score: f64,
+}
+
+impl AgeEvaluation {
+    pub fn is_eligible(&self) -> bool {
+        self.age.is_some() && self.meets_minimum && self.meets_maximum
+    }
+}
+
+impl AgeRules {
+    /// Defaults following the PPDB regulation: SD from 6 years with priority
+    /// for 7 year olds, SMP up to 15 years and SMA/SMK up to 21 years. A
+    /// maximum of "15 years" admits students who have not turned 16 yet.
+    pub fn for_level(level: &str) -> Self {
+        match Level::from_str(level) {
+            Some(Level::SD) => Self {
+                min_age: Some(Age::new(6, 0)),
+                priority_age: Some(Age::new(7, 0)),
+                ..Self::default()
+            },
+            Some(Level::SMP) => Self {
+                max_age: Some(Age::new(15, 11)),
+                ..Self::default()
+            },
+            Some(Level::SMA) | Some(Level::SMK) => Self {
+                max_age: Some(Age::new(21, 11)),
+                ..Self::default()
+            },
+            None => Self::default(),
+        }
+    }
+
+    /// The period's rules, or the defaults of its level, with the reference
+    /// date resolved
+    pub fn for_period(period: &Period) -> Result<Self, String> {
+        let mut rules = match period.age_rules {
+            Some(ref config) => Self::from_config(config)?,
+            None => Self::for_level(&period.level),
+        };
+
+        if rules.reference_date.is_none() {
+            rules.reference_date = Some(
+                default_reference_date(&period.academic_year).unwrap_or(period.registration_start),
+            );
+        }
+
+        Ok(rules)
+    }
+
+    pub fn from_config(config: &serde_json::Value) -> Result<Self, String> {
+        let rules: Self = serde_json::from_value(config.clone())
+            .map_err(|e| format!("Invalid age rules: {}", e))?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, age) in [
+            ("min_age", self.min_age),
+            ("max_age", self.max_age),
+            ("priority_age", self.priority_age),
+        ] {
+            if age.map(|a| a.months >= 12).unwrap_or(false) {
+                return Err(format!("Months of {} must be between 0 and 11", name));
+            }
+        }
+
+        if let (Some(min), Some(max)) = (self.min_age, self.max_age) {
+            if min > max {
+                return Err("min_age must not be greater than max_age".to_string());
+            }
+        }
+
+        if let Some(priority) = self.priority_age {
+            if self.min_age.map(|min| priority < min).unwrap_or(false)
+                || self.max_age.map(|max| priority > max).unwrap_or(false)
+            {
+                return Err("priority_age must be between min_age and max_age".to_string());
+            }
+        }
+
+        if self.priority_bonus < 0.0 || self.points_per_month < 0.0 {
+            return Err("Age points must not be negative".to_string());
+        }
+
+        if self.max_age_points.map(|m| m < 0.0).unwrap_or(false) {
+            return Err("max_age_points must not be negative".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn evaluate(&self, birth_date: NaiveDate) -> AgeEvaluation {
+        let reference_date = self.reference_date.unwrap_or(birth_date);
+        let age = Age::on(birth_date, reference_date);
+
+        let meets_minimum = match (age, self.min_age) {
+            (Some(age), Some(min)) => age >= min,
+            (age, None) => age.is_some(),
+            (None, Some(_)) => false,
+        };
+        let meets_maximum = match (age, self.max_age) {
+            (Some(age), Some(max)) => age <= max,
+            _ => true,
+        };
+        let priority = match (age, self.priority_age) {
+            (Some(age), Some(priority)) => age >= priority,
+            _ => false,
+        };
+
+        let mut score = 0.0;
+        if let Some(age) = age.filter(|_| meets_minimum && meets_maximum) {
+            if priority {
+                score += self.priority_bonus;
+            }
+
+            let months_above_minimum =
+                age.total_months() - self.min_age.map(|min| min.total_months()).unwrap_or(0);
+            let age_points = months_above_minimum as f64 * self.points_per_month;
+            score += self
+                .max_age_points
+                .map(|max| age_points.min(max))
+                .unwrap_or(age_points);
+        }
+
+        AgeEvaluation {
+            reference_date,
+            age,
+            meets_minimum,
+            meets_maximum,
+            priority,
+            score,
+        }
+    }
+}
+
+/// 1 July of the first year of an academic year such as "2024/2025"
+pub fn default_reference_date(academic_year: &str) -> Option<NaiveDate> {
+    let year = academic_year.get(..4)?.parse::<i32>().ok()?;
+    NaiveDate::from_ymd_opt(year, 7, 1)
+}
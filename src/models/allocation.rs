@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AllocationRound {
+    pub id: i32,
+    pub name: String,
+    pub academic_year: String,
+    pub level: String,
+    pub choice_limit: i32,
+    pub status: String,
+    pub allocated_at: Option<DateTime<Utc>>,
+    pub created_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AllocationChoice {
+    pub id: i32,
+    pub round_id: i32,
+    pub user_id: i32,
+    pub student_nisn: String,
+    pub registration_id: i32,
+    pub choice_rank: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AllocationResult {
+    pub id: i32,
+    pub round_id: i32,
+    pub student_nisn: String,
+    pub student_name: String,
+    pub status: String,
+    pub registration_id: Option<i32>,
+    pub school_id: Option<i32>,
+    pub path_id: Option<i32>,
+    pub choice_rank: Option<i32>,
+    pub selection_score: Option<f64>,
+    pub explanation: String,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AllocationRoundStatus {
+    Draft,
+    Open,
+    Closed,
+    Allocated,
+}
+
+impl AllocationRoundStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AllocationRoundStatus::Draft => "draft",
+            AllocationRoundStatus::Open => "open",
+            AllocationRoundStatus::Closed => "closed",
+            AllocationRoundStatus::Allocated => "allocated",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "draft" => Some(AllocationRoundStatus::Draft),
+            "open" => Some(AllocationRoundStatus::Open),
+            "closed" => Some(AllocationRoundStatus::Closed),
+            "allocated" => Some(AllocationRoundStatus::Allocated),
+            _ => None,
+        }
+    }
+}
+
+/// Status an allocation run gives a verified registration
+#[derive(Debug, Clone)]
+pub struct RegistrationOutcome {
+    pub registration_id: i32,
+    pub status: &'static str,
+    pub accepted_path_id: Option<i32>,
+    pub ranking: Option<i32>,
+    pub rejection_reason: Option<&'static str>,
+    pub waitlisted: bool,
+}
+
+/// Allocation outcome computed by a run, before it is stored
+#[derive(Debug, Clone)]
+pub struct NewAllocationResult {
+    pub student_nisn: String,
+    pub student_name: String,
+    pub status: String,
+    pub registration_id: Option<i32>,
+    pub school_id: Option<i32>,
+    pub path_id: Option<i32>,
+    pub choice_rank: Option<i32>,
+    pub selection_score: Option<f64>,
+    pub explanation: String,
+    pub details: serde_json::Value,
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Appeal (sanggahan) against an announced selection result
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Appeal {
+    pub id: i32,
+    pub registration_id: i32,
+    pub filed_by: i32,
+    pub category: String,
+    pub reason: String,
+    pub status: String,
+    pub response: Option<String>,
+    /// Selection scores before and after a re-score, if one was run
+    pub score_before: Option<f64>,
+    pub score_after: Option<f64>,
+    pub resolved_by: Option<i32>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AppealDocument {
+    pub id: i32,
+    pub appeal_id: i32,
+    pub file_url: String,
+    pub file_name: String,
+    pub file_size: i64,
+    pub mime_type: String,
+    // Malware scanning
+    pub scan_status: String,
+    pub scan_signature: Option<String>,
+    pub scan_error: Option<String>,
+    pub scanned_at: Option<DateTime<Utc>>,
+    pub scan_attempts: i32,
+    pub next_scan_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AppealDocument {
+    /// Whether the file may be served: it was scanned and found clean, or
+    /// scanning is disabled
+    pub fn is_servable(&self) -> bool {
+        matches!(self.scan_status.as_str(), "clean" | "skipped")
+    }
+}
+
+/// Evidence file uploaded with a new appeal
+#[derive(Debug, Clone)]
+pub struct NewAppealDocument {
+    pub file_url: String,
+    pub file_name: String,
+    pub file_size: i64,
+    pub mime_type: String,
+}
+
+/// Logged action on an appeal
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AppealEvent {
+    pub id: i32,
+    pub appeal_id: i32,
+    pub action: String,
+    pub notes: Option<String>,
+    pub details: Option<serde_json::Value>,
+    pub actor_id: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Appeal with its evidence and decision log
+#[derive(Debug, Clone)]
+pub struct AppealDetail {
+    pub appeal: Appeal,
+    pub documents: Vec<AppealDocument>,
+    pub events: Vec<AppealEvent>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AppealCategory {
+    Score,
+    Distance,
+    Other,
+}
+
+impl AppealCategory {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AppealCategory::Score => "score",
+            AppealCategory::Distance => "distance",
+            AppealCategory::Other => "other",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "score" => Some(AppealCategory::Score),
+            "distance" => Some(AppealCategory::Distance),
+            "other" => Some(AppealCategory::Other),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AppealStatus {
+    Submitted,
+    InReview,
+    Upheld,
+    Dismissed,
+}
+
+impl AppealStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AppealStatus::Submitted => "submitted",
+            AppealStatus::InReview => "in_review",
+            AppealStatus::Upheld => "upheld",
+            AppealStatus::Dismissed => "dismissed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "submitted" => Some(AppealStatus::Submitted),
+            "in_review" => Some(AppealStatus::InReview),
+            "upheld" => Some(AppealStatus::Upheld),
+            "dismissed" => Some(AppealStatus::Dismissed),
+            _ => None,
+        }
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        matches!(self, AppealStatus::Upheld | AppealStatus::Dismissed)
+    }
+}
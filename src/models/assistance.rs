@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AssistanceList {
+    pub id: i32,
+    pub program: String,
+    pub school_id: Option<i32>,
+    pub region_id: Option<i32>,
+    pub name: String,
+    pub row_count: i32,
+    pub imported_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One row of an imported list
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewBeneficiary {
+    pub nik: Option<String>,
+    pub kip_number: Option<String>,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AssistanceMatch {
+    pub id: i32,
+    pub registration_id: i32,
+    pub beneficiary_id: i32,
+    pub program: String,
+    pub matched_on: String,
+    pub status: String,
+    pub notes: Option<String>,
+    pub verified_by: Option<i32>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Match joined with the list and beneficiary it was found in, for verifiers
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AssistanceMatchDetail {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub assistance_match: AssistanceMatch,
+    pub list_id: i32,
+    pub list_name: String,
+    pub beneficiary_nik: Option<String>,
+    pub beneficiary_kip_number: Option<String>,
+    pub beneficiary_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AssistanceProgram {
+    /// Data Terpadu Kesejahteraan Sosial
+    Dtks,
+    /// Program Keluarga Harapan
+    Pkh,
+    /// Kartu Indonesia Pintar
+    Kip,
+}
+
+impl AssistanceProgram {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AssistanceProgram::Dtks => "dtks",
+            AssistanceProgram::Pkh => "pkh",
+            AssistanceProgram::Kip => "kip",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "dtks" => Some(AssistanceProgram::Dtks),
+            "pkh" => Some(AssistanceProgram::Pkh),
+            "kip" => Some(AssistanceProgram::Kip),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AssistanceMatchStatus {
+    Matched,
+    Verified,
+    Rejected,
+}
+
+impl AssistanceMatchStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AssistanceMatchStatus::Matched => "matched",
+            AssistanceMatchStatus::Verified => "verified",
+            AssistanceMatchStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "matched" => Some(AssistanceMatchStatus::Matched),
+            "verified" => Some(AssistanceMatchStatus::Verified),
+            "rejected" => Some(AssistanceMatchStatus::Rejected),
+            _ => None,
+        }
+    }
+}
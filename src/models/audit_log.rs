@@ -1 +1,32 @@
-// Audit log model - Coming soon
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum AuditAction {
+    Verify,
+    Reject,
+    Approve,
+    RequestRevision,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AuditAction::Verify => "verify",
+            AuditAction::Reject => "reject",
+            AuditAction::Approve => "approve",
+            AuditAction::RequestRevision => "request_revision",
+        }
+    }
+}
+
+/// Audit trail entry of a change made by a user
+#[derive(Debug, Clone)]
+pub struct NewAuditLog {
+    pub school_id: Option<i32>,
+    pub user_id: Option<i32>,
+    pub entity_type: &'static str,
+    pub entity_id: i32,
+    pub action: AuditAction,
+    pub old_value: Option<serde_json::Value>,
+    pub new_value: Option<serde_json::Value>,
+}
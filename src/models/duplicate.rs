@@ -0,0 +1,169 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DuplicateFlag {
+    pub id: i32,
+    pub registration_id: i32,
+    pub matched_registration_id: i32,
+    pub match_type: String,
+    /// Documents sharing a hash, for document matches
+    pub document_id: Option<i32>,
+    pub matched_document_id: Option<i32>,
+    pub status: String,
+    pub notes: Option<String>,
+    pub reviewed_by: Option<i32>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Flag joined with a summary of both registrations, for the review queue
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DuplicateFlagDetail {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub flag: DuplicateFlag,
+    pub registration_school_id: i32,
+    pub registration_student_nisn: String,
+    pub registration_student_name: String,
+    pub registration_status: String,
+    pub matched_school_id: i32,
+    pub matched_student_nisn: String,
+    pub matched_student_name: String,
+    pub matched_status: String,
+}
+
+/// Document of another registration with the same content or a similar image
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DocumentHashCandidate {
+    pub document_id: i32,
+    pub registration_id: i32,
+    pub content_hash: Option<String>,
+    pub perceptual_hash: Option<i64>,
+}
+
+/// Flag on one of a registration's documents, seen from that document
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DocumentDuplicate {
+    pub flag_id: i32,
+    pub document_id: i32,
+    pub match_type: String,
+    pub status: String,
+    pub matched_registration_id: i32,
+    pub matched_document_id: i32,
+    pub matched_school_id: i32,
+}
+
+/// Pair of matching documents, an edge of a duplicate cluster
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DocumentFlagEdge {
+    pub document_id: i32,
+    pub matched_document_id: i32,
+    pub match_type: String,
+    pub status: String,
+}
+
+/// Document in a duplicate cluster with its registration
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ClusterDocument {
+    pub document_id: i32,
+    pub document_type: String,
+    pub registration_id: i32,
+    pub school_id: i32,
+    pub student_nisn: String,
+    pub student_name: String,
+    pub registration_status: String,
+}
+
+/// Documents linked by hash matches across registrations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentCluster {
+    pub documents: Vec<ClusterDocument>,
+    pub match_types: Vec<String>,
+    pub open_flags: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum DuplicatePolicy {
+    Block,
+    Warn,
+    Allow,
+}
+
+impl DuplicatePolicy {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DuplicatePolicy::Block => "block",
+            DuplicatePolicy::Warn => "warn",
+            DuplicatePolicy::Allow => "allow",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "block" => Some(DuplicatePolicy::Block),
+            "warn" => Some(DuplicatePolicy::Warn),
+            "allow" => Some(DuplicatePolicy::Allow),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum DuplicateMatchType {
+    Nisn,
+    ParentNik,
+    /// Identical files
+    DocumentContent,
+    /// Images that look the same
+    DocumentImage,
+}
+
+impl DuplicateMatchType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DuplicateMatchType::Nisn => "nisn",
+            DuplicateMatchType::ParentNik => "parent_nik",
+            DuplicateMatchType::DocumentContent => "document_content",
+            DuplicateMatchType::DocumentImage => "document_image",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "nisn" => Some(DuplicateMatchType::Nisn),
+            "parent_nik" => Some(DuplicateMatchType::ParentNik),
+            "document_content" => Some(DuplicateMatchType::DocumentContent),
+            "document_image" => Some(DuplicateMatchType::DocumentImage),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DuplicateFlagStatus {
+    Open,
+    Dismissed,
+    Confirmed,
+}
+
+impl DuplicateFlagStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DuplicateFlagStatus::Open => "open",
+            DuplicateFlagStatus::Dismissed => "dismissed",
+            DuplicateFlagStatus::Confirmed => "confirmed",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "open" => Some(DuplicateFlagStatus::Open),
+            "dismissed" => Some(DuplicateFlagStatus::Dismissed),
+            "confirmed" => Some(DuplicateFlagStatus::Confirmed),
+            _ => None,
+        }
+    }
+}
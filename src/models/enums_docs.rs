@@ -15,6 +15,10 @@ pub enum UserRole {
     #[serde(rename = "super_admin")]
     SuperAdmin,
     
+    /// Region (dinas pendidikan) administrator with read access to schools in the region
+    #[serde(rename = "region_admin")]
+    RegionAdmin,
+    
     /// School administrator with access to own school only
     #[serde(rename = "school_admin")]
     SchoolAdmin,
@@ -109,6 +113,10 @@ pub enum RegistrationStatus {
     #[serde(rename = "submitted")]
     Submitted,
     
+    /// Registration was sent back to the parent for corrections
+    #[serde(rename = "revision_requested")]
+    RevisionRequested,
+    
     /// Registration has been verified by admin
     #[serde(rename = "verified")]
     Verified,
@@ -128,6 +136,10 @@ pub enum RegistrationStatus {
     /// Registration expired (didn't complete re-enrollment)
     #[serde(rename = "expired")]
     Expired,
+    
+    /// Registration withdrawn by the parent or because the student enrolled at another school
+    #[serde(rename = "withdrawn")]
+    Withdrawn,
 }
 
 /// Document type for registration
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PeriodMajor {
+    pub id: i32,
+    pub period_id: i32,
+    pub code: String,
+    pub name: String,
+    pub capacity: i32,
+    pub description: Option<String>,
+    pub criteria: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegistrationMajorChoice {
+    pub id: i32,
+    pub registration_id: i32,
+    pub major_id: i32,
+    pub priority: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegistrationRequirementCheck {
+    pub id: i32,
+    pub registration_id: i32,
+    pub requirement_key: String,
+    pub passed: bool,
+    pub notes: Option<String>,
+    pub verified_by: Option<i32>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Extra admission criterion of a major, stored in `period_majors.criteria`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MajorCriterion {
+    /// Minimum average of a rapor subject over the entered semesters
+    SubjectGrade { subject: String, min_average: f64 },
+    /// Requirement checked by a verifier, e.g. "color_vision" for a color-blindness test
+    Requirement { key: String, label: String },
+}
+
+impl MajorCriterion {
+    pub fn parse_list(criteria: &serde_json::Value) -> Result<Vec<Self>, String> {
+        let criteria: Vec<Self> = serde_json::from_value(criteria.clone())
+            .map_err(|e| format!("Invalid major criteria: {}", e))?;
+
+        for criterion in &criteria {
+            match criterion {
+                MajorCriterion::SubjectGrade {
+                    subject,
+                    min_average,
+                } => {
+                    if subject.trim().is_empty() {
+                        return Err("Subject of a subject_grade criterion is required".to_string());
+                    }
+                    if !(0.0..=100.0).contains(min_average) {
+                        return Err(format!(
+                            "Minimum average of '{}' must be between 0 and 100",
+                            subject
+                        ));
+                    }
+                }
+                MajorCriterion::Requirement { key, label } => {
+                    if !is_requirement_key(key) {
+                        return Err(format!(
+                            "Requirement key '{}' must be 1-50 lowercase letters, digits or underscores",
+                            key
+                        ));
+                    }
+                    if label.trim().is_empty() {
+                        return Err(format!("Label of requirement '{}' is required", key));
+                    }
+                }
+            }
+        }
+
+        Ok(criteria)
+    }
+}
+
+pub fn is_requirement_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= 50
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
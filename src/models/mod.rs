@@ -1,7 +1,17 @@
 pub mod user;
 pub mod school;
+pub mod region;
 pub mod period;
+pub mod age_rule;
 pub mod registration;
+pub mod achievement;
+pub mod major;
+pub mod test_session;
+pub mod assistance;
+pub mod allocation;
+pub mod duplicate;
+pub mod appeal;
+pub mod verification;
 pub mod payment;
 pub mod audit_log;
 pub mod enums_docs;
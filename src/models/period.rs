@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, Default)]
 pub struct Period {
     pub id: i32,
     pub school_id: i32,
@@ -15,10 +15,33 @@ pub struct Period {
     pub announcement_date: Option<chrono::NaiveDate>,
     pub reenrollment_deadline: Option<chrono::NaiveDate>,
     pub status: String,
+    pub duplicate_policy: String,
+    pub achievement_point_table: Option<serde_json::Value>,
+    pub age_rules: Option<serde_json::Value>,
+    pub auto_lifecycle: bool,
+    pub announcement_at: Option<DateTime<Utc>>,
+    pub closing_time: chrono::NaiveTime,
+    pub registration_opens_at: DateTime<Utc>,
+    pub registration_closes_at: DateTime<Utc>,
+    pub reenrollment_closes_at: Option<DateTime<Utc>>,
+    pub cloned_from_id: Option<i32>,
+    pub waitlist_policy: String,
+    pub refund_policy: String,
+    pub appeal_window_days: i32,
+    pub document_rejection_policy: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Deadlines of a period resolved in the school's timezone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeriodDeadlines {
+    pub closing_time: chrono::NaiveTime,
+    pub registration_opens_at: DateTime<Utc>,
+    pub registration_closes_at: DateTime<Utc>,
+    pub reenrollment_closes_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PeriodStatus {
     Draft,
@@ -45,6 +68,85 @@ impl PeriodStatus {
     }
 }
 
+/// What happens to the seat of an accepted registration that is withdrawn
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum WaitlistPolicy {
+    /// The seat stays free
+    None,
+    /// The best-ranked applicant rejected for lack of quota takes the seat
+    Promote,
+}
+
+impl WaitlistPolicy {
+    pub fn as_str(&self) -> &str {
+        match self {
+            WaitlistPolicy::None => "none",
+            WaitlistPolicy::Promote => "promote",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "none" => Some(WaitlistPolicy::None),
+            "promote" => Some(WaitlistPolicy::Promote),
+            _ => None,
+        }
+    }
+}
+
+/// Whether payments of a withdrawn registration are refunded
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RefundPolicy {
+    None,
+    Full,
+    /// Refunded only when withdrawn before the results are announced
+    BeforeAnnouncement,
+}
+
+impl RefundPolicy {
+    pub fn as_str(&self) -> &str {
+        match self {
+            RefundPolicy::None => "none",
+            RefundPolicy::Full => "full",
+            RefundPolicy::BeforeAnnouncement => "before_announcement",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "none" => Some(RefundPolicy::None),
+            "full" => Some(RefundPolicy::Full),
+            "before_announcement" => Some(RefundPolicy::BeforeAnnouncement),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of a submitted registration when a required document is rejected
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DocumentRejectionPolicy {
+    /// Sent back to the parent to replace the document
+    Revision,
+    Reject,
+}
+
+impl DocumentRejectionPolicy {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DocumentRejectionPolicy::Revision => "revision",
+            DocumentRejectionPolicy::Reject => "reject",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "revision" => Some(DocumentRejectionPolicy::Revision),
+            "reject" => Some(DocumentRejectionPolicy::Reject),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Level {
     SD,
@@ -83,6 +185,27 @@ pub struct RegistrationPath {
     pub quota: i32,
     pub description: Option<String>,
     pub scoring_config: serde_json::Value,
+    pub selection_order: i32,
+    pub path_data_schema: Option<serde_json::Value>,
+    pub stage_id: Option<i32>,
+    pub required_documents: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Registration round (tahap) of a period
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PeriodStage {
+    pub id: i32,
+    pub period_id: i32,
+    pub stage_number: i32,
+    pub name: String,
+    pub registration_start: chrono::NaiveDate,
+    pub registration_end: chrono::NaiveDate,
+    pub registration_opens_at: DateTime<Utc>,
+    pub registration_closes_at: DateTime<Utc>,
+    pub announcement_at: Option<DateTime<Utc>>,
+    pub announcement_date: Option<chrono::NaiveDate>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
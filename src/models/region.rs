@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Region {
+    pub id: i32,
+    pub parent_id: Option<i32>,
+    pub level: String,
+    pub code: String,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RegionLevel {
+    Province,
+    Regency,
+}
+
+impl RegionLevel {
+    pub fn as_str(&self) -> &str {
+        match self {
+            RegionLevel::Province => "province",
+            RegionLevel::Regency => "regency",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "province" => Some(RegionLevel::Province),
+            "regency" => Some(RegionLevel::Regency),
+            _ => None,
+        }
+    }
+}
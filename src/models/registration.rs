@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::models::duplicate::DocumentDuplicate;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, Default)]
 pub struct Registration {
     pub id: i32,
@@ -21,6 +23,8 @@ pub struct Registration {
     pub student_address: String,
     pub student_phone: Option<String>,
     pub student_email: Option<String>,
+    pub student_nik: Option<String>,
+    pub student_region_code: Option<String>,
     
     // Parent data
     pub parent_name: String,
@@ -40,24 +44,191 @@ pub struct Registration {
     // Selection data
     pub selection_score: Option<f64>,
     pub ranking: Option<i32>,
+    pub accepted_path_id: Option<i32>,
+    pub accepted_major_id: Option<i32>,
+    pub rapor_average: Option<f64>,
+    pub achievement_points: Option<f64>,
+    pub test_score: Option<f64>,
+    pub verified_assistance_programs: Vec<String>,
     
     // Status
     pub status: String,
     pub rejection_reason: Option<String>,
+    /// Rejected only because the quota was full; next in line for freed seats
+    pub waitlisted: bool,
+    
+    // Deadline extension
+    pub deadline_extended_until: Option<DateTime<Utc>>,
+    pub deadline_extended_by: Option<i32>,
+    pub deadline_extension_reason: Option<String>,
+    
+    // Verification queue
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub assigned_verifier_id: Option<i32>,
+    pub assigned_at: Option<DateTime<Utc>>,
     
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Registration {
+    /// Status history reason of the waitlisted registration that takes over
+    /// the seat of this withdrawn one
+    pub fn seat_handover_reason(&self) -> String {
+        format!(
+            "Menggantikan pendaftar {} yang mengundurkan diri.",
+            self.registration_number
+                .clone()
+                .unwrap_or_else(|| self.id.to_string())
+        )
+    }
+
+    /// Withdrawn registrations get no further emails
+    pub fn receives_notifications(&self) -> bool {
+        self.status != RegistrationStatus::Withdrawn.as_str()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegistrationFallbackPath {
+    pub id: i32,
+    pub registration_id: i32,
+    pub path_id: i32,
+    pub priority: i32,
+    pub path_data: serde_json::Value,
+    pub selection_score: Option<f64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegistrationGrade {
+    pub id: i32,
+    pub registration_id: i32,
+    pub semester: i32,
+    pub subject: String,
+    pub score: f64,
+    pub document_id: Option<i32>,
+    pub verified_by: Option<i32>,
+    pub verified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request to correct a submitted registration ("perlu perbaikan")
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegistrationRevision {
+    pub id: i32,
+    pub registration_id: i32,
+    pub requested_by: i32,
+    pub notes: Option<String>,
+    /// Flagged fields: [{"field": ..., "note": ...}]
+    pub fields: serde_json::Value,
+    /// Flagged documents: [{"document_id": ..., "note": ...}]
+    pub documents: serde_json::Value,
+    /// Values of the flagged items when the revision was requested
+    pub snapshot: serde_json::Value,
+    /// What the parent changed, recorded on resubmission
+    pub changes: Option<serde_json::Value>,
+    pub resubmitted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Status change of a registration
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegistrationStatusChange {
+    pub id: i32,
+    pub registration_id: i32,
+    pub from_status: String,
+    pub to_status: String,
+    pub reason: Option<String>,
+    /// None when the change was made by the system
+    pub changed_by: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What a withdrawal entails under the period's policies
+#[derive(Debug, Clone, PartialEq)]
+pub struct WithdrawalPlan {
+    pub refund: bool,
+    /// Path whose freed seat goes to the next applicant on the waitlist
+    pub promote_path_id: Option<i32>,
+}
+
+/// Outcome of a withdrawal
+#[derive(Debug, Clone)]
+pub struct WithdrawalResult {
+    pub registration: Registration,
+    /// Waitlisted registration that took over the freed seat
+    pub promoted: Option<Registration>,
+    pub refunded_payment_ids: Vec<i32>,
+}
+
+/// Field of a registration flagged for revision
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RevisionField {
+    pub field: String,
+    pub note: String,
+}
+
+/// Document of a registration flagged for revision
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RevisionDocument {
+    pub document_id: i32,
+    pub note: String,
+}
+
+/// Flagged field as it was before and after the revision
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+/// Flagged document and the documents uploaded to replace it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DocumentReplacement {
+    pub document_id: i32,
+    pub document_type: String,
+    pub replaced_by: Vec<i32>,
+}
+
+/// What the parent changed in response to a revision request
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct RevisionChanges {
+    pub fields: Vec<FieldChange>,
+    pub documents: Vec<DocumentReplacement>,
+}
+
+impl RegistrationRevision {
+    pub fn flagged_fields(&self) -> Vec<RevisionField> {
+        serde_json::from_value(self.fields.clone()).unwrap_or_default()
+    }
+
+    pub fn flagged_documents(&self) -> Vec<RevisionDocument> {
+        serde_json::from_value(self.documents.clone()).unwrap_or_default()
+    }
+
+    /// Type of a flagged document, kept in the snapshot in case it was deleted
+    pub fn flagged_document_type(&self, document_id: i32) -> Option<String> {
+        self.snapshot["documents"][document_id.to_string()]["document_type"]
+            .as_str()
+            .map(|t| t.to_string())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RegistrationStatus {
     Draft,
     Submitted,
+    RevisionRequested,
     Verified,
     Rejected,
     Accepted,
     Enrolled,
     Expired,
+    Withdrawn,
 }
 
 impl RegistrationStatus {
@@ -65,11 +236,13 @@ impl RegistrationStatus {
         match self {
             RegistrationStatus::Draft => "draft",
             RegistrationStatus::Submitted => "submitted",
+            RegistrationStatus::RevisionRequested => "revision_requested",
             RegistrationStatus::Verified => "verified",
             RegistrationStatus::Rejected => "rejected",
             RegistrationStatus::Accepted => "accepted",
             RegistrationStatus::Enrolled => "enrolled",
             RegistrationStatus::Expired => "expired",
+            RegistrationStatus::Withdrawn => "withdrawn",
         }
     }
 
@@ -77,14 +250,28 @@ impl RegistrationStatus {
         match s {
             "draft" => Some(RegistrationStatus::Draft),
             "submitted" => Some(RegistrationStatus::Submitted),
+            "revision_requested" => Some(RegistrationStatus::RevisionRequested),
             "verified" => Some(RegistrationStatus::Verified),
             "rejected" => Some(RegistrationStatus::Rejected),
             "accepted" => Some(RegistrationStatus::Accepted),
             "enrolled" => Some(RegistrationStatus::Enrolled),
             "expired" => Some(RegistrationStatus::Expired),
+            "withdrawn" => Some(RegistrationStatus::Withdrawn),
             _ => None,
         }
     }
+
+    /// Final statuses can no longer change; a registration can be withdrawn
+    /// from any other status
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            RegistrationStatus::Rejected
+                | RegistrationStatus::Enrolled
+                | RegistrationStatus::Expired
+                | RegistrationStatus::Withdrawn
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -98,10 +285,77 @@ pub struct Document {
     pub mime_type: String,
     pub verification_status: String,
     pub verification_notes: Option<String>,
+    pub verified_by: Option<i32>,
+    pub verified_at: Option<DateTime<Utc>>,
+    // Processing pipeline
+    pub processing_status: String,
+    pub processing_error: Option<String>,
+    pub processed_at: Option<DateTime<Utc>>,
+    pub original_file_size: Option<i64>,
+    pub page_count: Option<i32>,
+    pub processing_metadata: Option<serde_json::Value>,
+    // Malware scanning
+    pub scan_status: String,
+    pub scan_signature: Option<String>,
+    pub scan_error: Option<String>,
+    pub scanned_at: Option<DateTime<Utc>>,
+    // Duplicate detection
+    pub content_hash: Option<String>,
+    pub perceptual_hash: Option<i64>,
+    // Scheduler retries
+    pub processing_attempts: i32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Document {
+    /// Whether the file may be served: it was scanned and found clean, or
+    /// scanning is disabled
+    pub fn is_servable(&self) -> bool {
+        matches!(self.scan_status.as_str(), "clean" | "skipped")
+    }
+
+    /// Whether the scan or processing still has to run
+    pub fn is_unfinished(&self) -> bool {
+        self.scan_status == "pending" || self.processing_status == "pending"
+    }
+}
+
+/// Thumbnail or preview derived from a document
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DocumentArtifact {
+    pub id: i32,
+    pub document_id: i32,
+    pub kind: String,
+    pub file_url: String,
+    pub file_size: i64,
+    pub mime_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Document with its thumbnail and preview, and matches with other
+/// registrations' documents
+#[derive(Debug, Clone)]
+pub struct DocumentDetail {
+    pub document: Document,
+    pub artifacts: Vec<DocumentArtifact>,
+    pub duplicates: Vec<DocumentDuplicate>,
+}
+
+/// Artifact produced by the pipeline, before it is recorded
+#[derive(Debug, Clone)]
+pub struct NewDocumentArtifact {
+    pub kind: &'static str,
+    pub file_url: String,
+    pub file_size: i64,
+    pub mime_type: String,
+    pub width: i32,
+    pub height: i32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DocumentType {
     KartuKeluarga,
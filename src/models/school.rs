@@ -13,6 +13,8 @@ pub struct School {
     pub email: Option<String>,
     pub logo_url: Option<String>,
     pub status: String,
+    pub region_id: Option<i32>,
+    pub timezone: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
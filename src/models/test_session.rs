@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+pub const TEST_TYPES: [&str; 2] = ["written", "interview"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TestSession {
+    pub id: i32,
+    pub period_id: i32,
+    pub path_id: Option<i32>,
+    pub test_type: String,
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub room: String,
+    pub capacity: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Session with the number of seats taken
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TestSessionWithBookings {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub session: TestSession,
+    pub booked: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TestBooking {
+    pub id: i32,
+    pub session_id: i32,
+    pub registration_id: i32,
+    pub test_type: String,
+    pub seat_number: i32,
+    pub attended: Option<bool>,
+    pub score: Option<f64>,
+    pub notes: Option<String>,
+    pub recorded_by: Option<i32>,
+    pub recorded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Booking with the student it belongs to, for attendance lists
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TestBookingDetail {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub booking: TestBooking,
+    pub registration_number: Option<String>,
+    pub student_name: String,
+    pub student_nisn: String,
+}
+
+/// Result of one applicant entered by an admin
+#[derive(Debug, Clone)]
+pub struct TestResultInput {
+    pub registration_id: i32,
+    pub attended: bool,
+    pub score: Option<f64>,
+    pub notes: Option<String>,
+}
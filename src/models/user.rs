@@ -6,6 +6,7 @@ use sqlx::FromRow;
 #[serde(rename_all = "snake_case")]
 pub enum UserRole {
     SuperAdmin,
+    RegionAdmin,
     SchoolAdmin,
     Parent,
 }
@@ -26,6 +27,7 @@ pub struct User {
     pub reset_password_token: Option<String>,
     pub reset_password_expires: Option<NaiveDateTime>,
     pub last_login_at: Option<NaiveDateTime>,
+    pub region_id: Option<i32>,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
 }
@@ -34,6 +36,7 @@ impl User {
     pub fn get_role(&self) -> UserRole {
         match self.role.as_str() {
             "super_admin" => UserRole::SuperAdmin,
+            "region_admin" => UserRole::RegionAdmin,
             "school_admin" => UserRole::SchoolAdmin,
             "parent" => UserRole::Parent,
             _ => UserRole::Parent,
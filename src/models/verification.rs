@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Claim of a registration by a verifier, active until released or expired
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct VerificationLock {
+    pub id: i32,
+    pub registration_id: i32,
+    pub verifier_id: i32,
+    pub locked_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub released_at: Option<DateTime<Utc>>,
+    pub release_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl VerificationLock {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.released_at.is_none() && self.expires_at > now
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum LockReleaseReason {
+    Completed,
+    Released,
+    Expired,
+}
+
+impl LockReleaseReason {
+    pub fn as_str(&self) -> &str {
+        match self {
+            LockReleaseReason::Completed => "completed",
+            LockReleaseReason::Released => "released",
+            LockReleaseReason::Expired => "expired",
+        }
+    }
+}
+
+/// How an admin distributes waiting registrations over verifiers
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum AssignmentStrategy {
+    /// Every waiting registration of one path goes to one verifier
+    Path,
+    /// Waiting registrations are dealt out in turn, oldest first
+    RoundRobin,
+}
+
+impl AssignmentStrategy {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AssignmentStrategy::Path => "path",
+            AssignmentStrategy::RoundRobin => "round_robin",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "path" => Some(AssignmentStrategy::Path),
+            "round_robin" => Some(AssignmentStrategy::RoundRobin),
+            _ => None,
+        }
+    }
+}
+
+/// One verification action applied to a set of registrations or documents
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BulkVerificationJob {
+    pub id: i32,
+    pub school_id: i32,
+    pub requested_by: i32,
+    pub target: String,
+    pub action: String,
+    /// Rejection reason or revision note applied to every item
+    pub notes: Option<String>,
+    pub status: String,
+    pub total: i32,
+    pub succeeded: i32,
+    pub failed: i32,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Until when the worker running the job holds it
+    pub lease_expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Outcome of a bulk action on one registration or document
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BulkVerificationItem {
+    pub id: i32,
+    pub job_id: i32,
+    pub entity_id: i32,
+    pub status: String,
+    pub error: Option<String>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum BulkTarget {
+    Registration,
+    Document,
+}
+
+impl BulkTarget {
+    pub fn as_str(&self) -> &str {
+        match self {
+            BulkTarget::Registration => "registration",
+            BulkTarget::Document => "document",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "registration" => Some(BulkTarget::Registration),
+            "document" => Some(BulkTarget::Document),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum BulkAction {
+    Verify,
+    Reject,
+    RequestRevision,
+    /// Approve a document
+    Approve,
+}
+
+impl BulkAction {
+    pub fn as_str(&self) -> &str {
+        match self {
+            BulkAction::Verify => "verify",
+            BulkAction::Reject => "reject",
+            BulkAction::RequestRevision => "request_revision",
+            BulkAction::Approve => "approve",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "verify" => Some(BulkAction::Verify),
+            "reject" => Some(BulkAction::Reject),
+            "request_revision" => Some(BulkAction::RequestRevision),
+            "approve" => Some(BulkAction::Approve),
+            _ => None,
+        }
+    }
+
+    /// Whether the action applies to the target
+    pub fn applies_to(&self, target: BulkTarget) -> bool {
+        match target {
+            BulkTarget::Registration => matches!(
+                self,
+                BulkAction::Verify | BulkAction::Reject | BulkAction::RequestRevision
+            ),
+            BulkTarget::Document => matches!(self, BulkAction::Approve | BulkAction::Reject),
+        }
+    }
+}
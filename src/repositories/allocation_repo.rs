@@ -0,0 +1,376 @@
+use sqlx::PgPool;
+
+use crate::models::allocation::{
+    AllocationChoice, AllocationResult, AllocationRound, NewAllocationResult, RegistrationOutcome,
+};
+use crate::utils::error::AppResult;
+
+pub struct AllocationRepository {
+    pool: PgPool,
+}
+
+impl AllocationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // Round methods
+    pub async fn create_round(
+        &self,
+        name: &str,
+        academic_year: &str,
+        level: &str,
+        choice_limit: i32,
+        created_by: i32,
+    ) -> AppResult<AllocationRound> {
+        let round = sqlx::query_as::<_, AllocationRound>(
+            r#"
+            INSERT INTO allocation_rounds (name, academic_year, level, choice_limit, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(name)
+        .bind(academic_year)
+        .bind(level)
+        .bind(choice_limit)
+        .bind(created_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(round)
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> AppResult<Option<AllocationRound>> {
+        let round = sqlx::query_as::<_, AllocationRound>(
+            r#"
+            SELECT * FROM allocation_rounds WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(round)
+    }
+
+    pub async fn find_all(&self, status: Option<String>) -> AppResult<Vec<AllocationRound>> {
+        let rounds = sqlx::query_as::<_, AllocationRound>(
+            r#"
+            SELECT * FROM allocation_rounds
+            WHERE ($1::VARCHAR IS NULL OR status = $1)
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(status)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rounds)
+    }
+
+    pub async fn find_by_period(&self, period_id: i32) -> AppResult<Option<AllocationRound>> {
+        let round = sqlx::query_as::<_, AllocationRound>(
+            r#"
+            SELECT r.* FROM allocation_rounds r
+            JOIN allocation_round_periods rp ON rp.round_id = r.id
+            WHERE rp.period_id = $1
+            "#,
+        )
+        .bind(period_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(round)
+    }
+
+    pub async fn update_status(&self, id: i32, status: &str) -> AppResult<AllocationRound> {
+        let round = sqlx::query_as::<_, AllocationRound>(
+            r#"
+            UPDATE allocation_rounds
+            SET status = $2,
+                allocated_at = CASE WHEN $2 = 'allocated' THEN NOW() ELSE allocated_at END,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(round)
+    }
+
+    pub async fn set_periods(&self, round_id: i32, period_ids: &[i32]) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM allocation_round_periods WHERE round_id = $1")
+            .bind(round_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for period_id in period_ids {
+            sqlx::query(
+                r#"
+                INSERT INTO allocation_round_periods (round_id, period_id)
+                VALUES ($1, $2)
+                "#,
+            )
+            .bind(round_id)
+            .bind(period_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn find_period_ids(&self, round_id: i32) -> AppResult<Vec<i32>> {
+        let period_ids = sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT period_id FROM allocation_round_periods
+            WHERE round_id = $1
+            ORDER BY period_id
+            "#,
+        )
+        .bind(round_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(period_ids)
+    }
+
+    // Choice methods
+    pub async fn replace_choices(
+        &self,
+        round_id: i32,
+        user_id: i32,
+        student_nisn: &str,
+        registration_ids: &[i32],
+    ) -> AppResult<Vec<AllocationChoice>> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM allocation_choices WHERE round_id = $1 AND student_nisn = $2")
+            .bind(round_id)
+            .bind(student_nisn)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut created = Vec::with_capacity(registration_ids.len());
+        for (index, registration_id) in registration_ids.iter().enumerate() {
+            let choice = sqlx::query_as::<_, AllocationChoice>(
+                r#"
+                INSERT INTO allocation_choices (round_id, user_id, student_nisn, registration_id, choice_rank)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING *
+                "#,
+            )
+            .bind(round_id)
+            .bind(user_id)
+            .bind(student_nisn)
+            .bind(registration_id)
+            .bind((index + 1) as i32)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            created.push(choice);
+        }
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    pub async fn find_choices_by_student(
+        &self,
+        round_id: i32,
+        student_nisn: &str,
+    ) -> AppResult<Vec<AllocationChoice>> {
+        let choices = sqlx::query_as::<_, AllocationChoice>(
+            r#"
+            SELECT * FROM allocation_choices
+            WHERE round_id = $1 AND student_nisn = $2
+            ORDER BY choice_rank
+            "#,
+        )
+        .bind(round_id)
+        .bind(student_nisn)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(choices)
+    }
+
+    pub async fn find_choices_by_round(&self, round_id: i32) -> AppResult<Vec<AllocationChoice>> {
+        let choices = sqlx::query_as::<_, AllocationChoice>(
+            r#"
+            SELECT * FROM allocation_choices
+            WHERE round_id = $1
+            ORDER BY student_nisn, choice_rank
+            "#,
+        )
+        .bind(round_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(choices)
+    }
+
+    // Result methods
+    /// Store the results of a run, apply the outcomes to the registrations
+    /// and mark the round allocated, in one transaction
+    pub async fn store_allocation(
+        &self,
+        round_id: i32,
+        results: &[NewAllocationResult],
+        outcomes: &[RegistrationOutcome],
+    ) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM allocation_results WHERE round_id = $1")
+            .bind(round_id)
+            .execute(&mut *tx)
+            .await?;
+
+        for result in results {
+            sqlx::query(
+                r#"
+                INSERT INTO allocation_results (
+                    round_id, student_nisn, student_name, status, registration_id,
+                    school_id, path_id, choice_rank, selection_score, explanation, details
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                "#,
+            )
+            .bind(round_id)
+            .bind(&result.student_nisn)
+            .bind(&result.student_name)
+            .bind(&result.status)
+            .bind(result.registration_id)
+            .bind(result.school_id)
+            .bind(result.path_id)
+            .bind(result.choice_rank)
+            .bind(result.selection_score)
+            .bind(&result.explanation)
+            .bind(&result.details)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        for outcome in outcomes {
+            sqlx::query(
+                r#"
+                UPDATE registrations
+                SET status = $2,
+                    accepted_path_id = $3,
+                    accepted_major_id = NULL,
+                    ranking = COALESCE($4, ranking),
+                    rejection_reason = $5,
+                    waitlisted = $6,
+                    updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(outcome.registration_id)
+            .bind(outcome.status)
+            .bind(outcome.accepted_path_id)
+            .bind(outcome.ranking)
+            .bind(outcome.rejection_reason)
+            .bind(outcome.waitlisted)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE allocation_rounds
+            SET status = 'allocated',
+                allocated_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(round_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn find_results(
+        &self,
+        round_id: i32,
+        status: Option<String>,
+        school_id: Option<i32>,
+        page_size: i64,
+        offset: i64,
+    ) -> AppResult<Vec<AllocationResult>> {
+        let results = sqlx::query_as::<_, AllocationResult>(
+            r#"
+            SELECT * FROM allocation_results
+            WHERE round_id = $1
+              AND ($2::VARCHAR IS NULL OR status = $2)
+              AND ($3::INTEGER IS NULL OR school_id = $3)
+            ORDER BY student_name
+            LIMIT $4 OFFSET $5
+            "#,
+        )
+        .bind(round_id)
+        .bind(status)
+        .bind(school_id)
+        .bind(page_size)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(results)
+    }
+
+    pub async fn count_results(
+        &self,
+        round_id: i32,
+        status: Option<String>,
+        school_id: Option<i32>,
+    ) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM allocation_results
+            WHERE round_id = $1
+              AND ($2::VARCHAR IS NULL OR status = $2)
+              AND ($3::INTEGER IS NULL OR school_id = $3)
+            "#,
+        )
+        .bind(round_id)
+        .bind(status)
+        .bind(school_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    pub async fn find_result_by_student(
+        &self,
+        round_id: i32,
+        student_nisn: &str,
+    ) -> AppResult<Option<AllocationResult>> {
+        let result = sqlx::query_as::<_, AllocationResult>(
+            r#"
+            SELECT * FROM allocation_results
+            WHERE round_id = $1 AND student_nisn = $2
+            "#,
+        )
+        .bind(round_id)
+        .bind(student_nisn)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,397 @@
+use sqlx::{PgConnection, PgPool};
+
+use crate::models::appeal::{Appeal, AppealDocument, AppealEvent, NewAppealDocument};
+use crate::utils::error::{AppError, AppResult};
+
+pub struct AppealRepository {
+    pool: PgPool,
+}
+
+impl AppealRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// File an appeal with its evidence and log it
+    pub async fn create_appeal(
+        &self,
+        registration_id: i32,
+        filed_by: i32,
+        category: &str,
+        reason: &str,
+        documents: &[NewAppealDocument],
+    ) -> AppResult<(Appeal, Vec<AppealDocument>)> {
+        let mut tx = self.pool.begin().await?;
+
+        let appeal = sqlx::query_as::<_, Appeal>(
+            r#"
+            INSERT INTO appeals (registration_id, filed_by, category, reason)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(registration_id)
+        .bind(filed_by)
+        .bind(category)
+        .bind(reason)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                AppError::Conflict("An appeal for this registration is still open".to_string())
+            }
+            _ => AppError::Database(e),
+        })?;
+
+        let mut saved = Vec::with_capacity(documents.len());
+        for document in documents {
+            let document = sqlx::query_as::<_, AppealDocument>(
+                r#"
+                INSERT INTO appeal_documents (appeal_id, file_url, file_name, file_size, mime_type)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING *
+                "#,
+            )
+            .bind(appeal.id)
+            .bind(&document.file_url)
+            .bind(&document.file_name)
+            .bind(document.file_size)
+            .bind(&document.mime_type)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            saved.push(document);
+        }
+
+        insert_event(&mut tx, appeal.id, "filed", None, None, Some(filed_by)).await?;
+
+        tx.commit().await?;
+
+        Ok((appeal, saved))
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> AppResult<Option<Appeal>> {
+        let appeal = sqlx::query_as::<_, Appeal>("SELECT * FROM appeals WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(appeal)
+    }
+
+    pub async fn find_by_registration(&self, registration_id: i32) -> AppResult<Vec<Appeal>> {
+        let appeals = sqlx::query_as::<_, Appeal>(
+            r#"
+            SELECT * FROM appeals
+            WHERE registration_id = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(appeals)
+    }
+
+    pub async fn find_documents(&self, appeal_id: i32) -> AppResult<Vec<AppealDocument>> {
+        let documents = sqlx::query_as::<_, AppealDocument>(
+            "SELECT * FROM appeal_documents WHERE appeal_id = $1 ORDER BY id",
+        )
+        .bind(appeal_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(documents)
+    }
+
+    pub async fn find_document_by_id(&self, id: i32) -> AppResult<Option<AppealDocument>> {
+        let document =
+            sqlx::query_as::<_, AppealDocument>("SELECT * FROM appeal_documents WHERE id = $1")
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(document)
+    }
+
+    /// Evidence still waiting for a scan after the given number of minutes,
+    /// skipping files whose retry is not due yet
+    pub async fn find_stale_unscanned(&self, minutes: i32, limit: i64) -> AppResult<Vec<i32>> {
+        let ids = sqlx::query_scalar(
+            r#"
+            SELECT id FROM appeal_documents
+            WHERE scan_status = 'pending'
+              AND created_at < NOW() - make_interval(mins => $1)
+              AND (next_scan_at IS NULL OR next_scan_at <= NOW())
+            ORDER BY scan_attempts, created_at
+            LIMIT $2
+            "#,
+        )
+        .bind(minutes)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Record the scan verdict of an evidence file
+    pub async fn record_document_scan(
+        &self,
+        id: i32,
+        scan_status: &str,
+        signature: Option<&str>,
+    ) -> AppResult<AppealDocument> {
+        let document = sqlx::query_as::<_, AppealDocument>(
+            r#"
+            UPDATE appeal_documents
+            SET scan_status = $2,
+                scan_signature = $3,
+                scan_error = NULL,
+                scanned_at = NOW(),
+                next_scan_at = NULL
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(scan_status)
+        .bind(signature)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(document)
+    }
+
+    /// Keep an evidence file quarantined after a failed scan and schedule the
+    /// retry, doubling the delay each time; the last attempt marks it failed
+    pub async fn record_document_scan_error(
+        &self,
+        id: i32,
+        error: &str,
+        backoff_minutes: i32,
+        max_attempts: i32,
+    ) -> AppResult<AppealDocument> {
+        let document = sqlx::query_as::<_, AppealDocument>(
+            r#"
+            UPDATE appeal_documents
+            SET scan_error = $2,
+                scan_attempts = scan_attempts + 1,
+                next_scan_at = CASE
+                    WHEN scan_attempts + 1 >= $4 THEN NULL
+                    ELSE NOW() + make_interval(mins => $3 * (1 << scan_attempts))
+                END,
+                scan_status = CASE
+                    WHEN scan_attempts + 1 >= $4 THEN 'failed'
+                    ELSE scan_status
+                END
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .bind(backoff_minutes)
+        .bind(max_attempts)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(document)
+    }
+
+    pub async fn find_events(&self, appeal_id: i32) -> AppResult<Vec<AppealEvent>> {
+        let events = sqlx::query_as::<_, AppealEvent>(
+            "SELECT * FROM appeal_events WHERE appeal_id = $1 ORDER BY created_at, id",
+        )
+        .bind(appeal_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(events)
+    }
+
+    /// Appeals on registrations of the school (all schools when none is
+    /// given), oldest unresolved first
+    pub async fn find_appeals(
+        &self,
+        school_id: Option<i32>,
+        period_id: Option<i32>,
+        status: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<Appeal>> {
+        let appeals = sqlx::query_as::<_, Appeal>(
+            r#"
+            SELECT a.* FROM appeals a
+            JOIN registrations r ON r.id = a.registration_id
+            WHERE ($1::INTEGER IS NULL OR r.school_id = $1)
+              AND ($2::INTEGER IS NULL OR r.period_id = $2)
+              AND ($3::VARCHAR IS NULL OR a.status = $3)
+            ORDER BY a.resolved_at IS NOT NULL, a.created_at
+            LIMIT $4 OFFSET $5
+            "#,
+        )
+        .bind(school_id)
+        .bind(period_id)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(appeals)
+    }
+
+    pub async fn count_appeals(
+        &self,
+        school_id: Option<i32>,
+        period_id: Option<i32>,
+        status: Option<String>,
+    ) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM appeals a
+            JOIN registrations r ON r.id = a.registration_id
+            WHERE ($1::INTEGER IS NULL OR r.school_id = $1)
+              AND ($2::INTEGER IS NULL OR r.period_id = $2)
+              AND ($3::VARCHAR IS NULL OR a.status = $3)
+            "#,
+        )
+        .bind(school_id)
+        .bind(period_id)
+        .bind(status)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Take a submitted appeal into review. Returns None when it is no longer
+    /// submitted.
+    pub async fn triage(
+        &self,
+        id: i32,
+        notes: Option<&str>,
+        actor_id: i32,
+    ) -> AppResult<Option<Appeal>> {
+        let mut tx = self.pool.begin().await?;
+
+        let appeal = sqlx::query_as::<_, Appeal>(
+            r#"
+            UPDATE appeals
+            SET status = 'in_review', updated_at = NOW()
+            WHERE id = $1 AND status = 'submitted'
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if appeal.is_some() {
+            insert_event(&mut tx, id, "triaged", notes, None, Some(actor_id)).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(appeal)
+    }
+
+    /// Store the registration's selection score before and after a re-score
+    pub async fn record_rescore(
+        &self,
+        id: i32,
+        score_before: Option<f64>,
+        score_after: f64,
+        details: &serde_json::Value,
+        actor_id: i32,
+    ) -> AppResult<Appeal> {
+        let mut tx = self.pool.begin().await?;
+
+        let appeal = sqlx::query_as::<_, Appeal>(
+            r#"
+            UPDATE appeals
+            SET score_before = COALESCE(score_before, $2),
+                score_after = $3,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(score_before)
+        .bind(score_after)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        insert_event(&mut tx, id, "rescored", None, Some(details), Some(actor_id)).await?;
+
+        tx.commit().await?;
+
+        Ok(appeal)
+    }
+
+    /// Decide an unresolved appeal. Returns None when it was already resolved.
+    pub async fn resolve(
+        &self,
+        id: i32,
+        status: &str,
+        response: &str,
+        resolved_by: i32,
+    ) -> AppResult<Option<Appeal>> {
+        let mut tx = self.pool.begin().await?;
+
+        let appeal = sqlx::query_as::<_, Appeal>(
+            r#"
+            UPDATE appeals
+            SET status = $2,
+                response = $3,
+                resolved_by = $4,
+                resolved_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1 AND status IN ('submitted', 'in_review')
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(response)
+        .bind(resolved_by)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        if appeal.is_some() {
+            insert_event(&mut tx, id, status, Some(response), None, Some(resolved_by)).await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(appeal)
+    }
+}
+
+async fn insert_event(
+    conn: &mut PgConnection,
+    appeal_id: i32,
+    action: &str,
+    notes: Option<&str>,
+    details: Option<&serde_json::Value>,
+    actor_id: Option<i32>,
+) -> AppResult<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO appeal_events (appeal_id, action, notes, details, actor_id)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(appeal_id)
+    .bind(action)
+    .bind(notes)
+    .bind(details)
+    .bind(actor_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,305 @@
+use sqlx::PgPool;
+
+use crate::models::assistance::{
+    AssistanceList, AssistanceMatch, AssistanceMatchDetail, NewBeneficiary,
+};
+use crate::utils::error::AppResult;
+
+pub struct AssistanceRepository {
+    pool: PgPool,
+}
+
+impl AssistanceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Store a list and all of its rows in one transaction
+    pub async fn create_list(
+        &self,
+        program: &str,
+        school_id: Option<i32>,
+        region_id: Option<i32>,
+        name: &str,
+        imported_by: i32,
+        beneficiaries: &[NewBeneficiary],
+    ) -> AppResult<AssistanceList> {
+        let mut tx = self.pool.begin().await?;
+
+        let list = sqlx::query_as::<_, AssistanceList>(
+            r#"
+            INSERT INTO assistance_lists (program, school_id, region_id, name, row_count, imported_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(program)
+        .bind(school_id)
+        .bind(region_id)
+        .bind(name)
+        .bind(beneficiaries.len() as i32)
+        .bind(imported_by)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let niks: Vec<Option<String>> = beneficiaries.iter().map(|b| b.nik.clone()).collect();
+        let kip_numbers: Vec<Option<String>> =
+            beneficiaries.iter().map(|b| b.kip_number.clone()).collect();
+        let names: Vec<Option<String>> = beneficiaries.iter().map(|b| b.name.clone()).collect();
+
+        sqlx::query(
+            r#"
+            INSERT INTO assistance_beneficiaries (list_id, nik, kip_number, name)
+            SELECT $1, * FROM UNNEST($2::VARCHAR[], $3::VARCHAR[], $4::VARCHAR[])
+            "#,
+        )
+        .bind(list.id)
+        .bind(&niks)
+        .bind(&kip_numbers)
+        .bind(&names)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(list)
+    }
+
+    pub async fn find_list_by_id(&self, id: i32) -> AppResult<Option<AssistanceList>> {
+        let list = sqlx::query_as::<_, AssistanceList>(
+            r#"
+            SELECT * FROM assistance_lists WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(list)
+    }
+
+    /// Lists owned by the given school or any of the given regions.
+    /// Without filters all lists are returned.
+    pub async fn find_lists(
+        &self,
+        school_id: Option<i32>,
+        region_ids: Option<&[i32]>,
+        program: Option<String>,
+    ) -> AppResult<Vec<AssistanceList>> {
+        let lists = sqlx::query_as::<_, AssistanceList>(
+            r#"
+            SELECT * FROM assistance_lists
+            WHERE (($1::INTEGER IS NULL AND $2::INTEGER[] IS NULL)
+                   OR school_id = $1
+                   OR region_id = ANY($2))
+              AND ($3::VARCHAR IS NULL OR program = $3)
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(school_id)
+        .bind(region_ids)
+        .bind(program)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(lists)
+    }
+
+    pub async fn delete_list(&self, id: i32) -> AppResult<()> {
+        sqlx::query("DELETE FROM assistance_lists WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Registrations with a match in the list
+    pub async fn find_matched_registration_ids(&self, list_id: i32) -> AppResult<Vec<i32>> {
+        let ids = sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT DISTINCT m.registration_id FROM assistance_matches m
+            JOIN assistance_beneficiaries b ON b.id = m.beneficiary_id
+            WHERE b.list_id = $1
+            "#,
+        )
+        .bind(list_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Submitted afirmasi registrations (primary or fallback path) of the schools
+    pub async fn find_afirmasi_registration_ids(&self, school_ids: &[i32]) -> AppResult<Vec<i32>> {
+        let ids = sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT r.id FROM registrations r
+            JOIN registration_paths p ON p.id = r.path_id
+            WHERE r.school_id = ANY($1)
+              AND r.status IN ('submitted', 'verified')
+              AND (p.path_type = 'afirmasi' OR EXISTS (
+                  SELECT 1 FROM registration_fallback_paths f
+                  JOIN registration_paths fp ON fp.id = f.path_id
+                  WHERE f.registration_id = r.id AND fp.path_type = 'afirmasi'
+              ))
+            ORDER BY r.id
+            "#,
+        )
+        .bind(school_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Schools covered by a list owned by these regions
+    pub async fn find_school_ids_in_regions(&self, region_ids: &[i32]) -> AppResult<Vec<i32>> {
+        let ids = sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT id FROM schools WHERE region_id = ANY($1)
+            "#,
+        )
+        .bind(region_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Match a registration by student NIK, parent NIK or KIP number against
+    /// the lists of its school and of the regions above the school.
+    /// Returns the number of new matches.
+    pub async fn match_registration(&self, registration_id: i32) -> AppResult<u64> {
+        let result = sqlx::query(
+            r#"
+            WITH RECURSIVE reg AS (
+                SELECT r.id, r.school_id, r.student_nik, r.parent_nik,
+                       COALESCE(
+                           NULLIF(TRIM(r.path_data->>'kip_number'), ''),
+                           (SELECT NULLIF(TRIM(f.path_data->>'kip_number'), '')
+                            FROM registration_fallback_paths f
+                            WHERE f.registration_id = r.id AND f.path_data ? 'kip_number'
+                            ORDER BY f.priority LIMIT 1)
+                       ) AS kip_number,
+                       s.region_id
+                FROM registrations r
+                JOIN schools s ON s.id = r.school_id
+                WHERE r.id = $1
+            ),
+            up AS (
+                SELECT region_id AS id FROM reg WHERE region_id IS NOT NULL
+                UNION
+                SELECT rg.parent_id FROM regions rg JOIN up ON rg.id = up.id
+                WHERE rg.parent_id IS NOT NULL
+            )
+            INSERT INTO assistance_matches (registration_id, beneficiary_id, program, matched_on)
+            SELECT reg.id, b.id, l.program,
+                   CASE
+                       WHEN b.nik = reg.student_nik THEN 'student_nik'
+                       WHEN b.nik = reg.parent_nik THEN 'parent_nik'
+                       ELSE 'kip_number'
+                   END
+            FROM reg
+            JOIN assistance_lists l ON l.school_id = reg.school_id OR l.region_id IN (SELECT id FROM up)
+            JOIN assistance_beneficiaries b ON b.list_id = l.id
+            WHERE b.nik = reg.student_nik
+               OR b.nik = reg.parent_nik
+               OR b.kip_number = reg.kip_number
+            ON CONFLICT (registration_id, beneficiary_id) DO NOTHING
+            "#,
+        )
+        .bind(registration_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn find_matches_by_registration(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<AssistanceMatchDetail>> {
+        let matches = sqlx::query_as::<_, AssistanceMatchDetail>(
+            r#"
+            SELECT m.*, l.id AS list_id, l.name AS list_name,
+                   b.nik AS beneficiary_nik, b.kip_number AS beneficiary_kip_number,
+                   b.name AS beneficiary_name
+            FROM assistance_matches m
+            JOIN assistance_beneficiaries b ON b.id = m.beneficiary_id
+            JOIN assistance_lists l ON l.id = b.list_id
+            WHERE m.registration_id = $1
+            ORDER BY m.id
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(matches)
+    }
+
+    pub async fn find_match_by_id(&self, id: i32) -> AppResult<Option<AssistanceMatch>> {
+        let assistance_match = sqlx::query_as::<_, AssistanceMatch>(
+            r#"
+            SELECT * FROM assistance_matches WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(assistance_match)
+    }
+
+    pub async fn review_match(
+        &self,
+        id: i32,
+        status: &str,
+        notes: Option<&str>,
+        verified_by: i32,
+    ) -> AppResult<AssistanceMatch> {
+        let assistance_match = sqlx::query_as::<_, AssistanceMatch>(
+            r#"
+            UPDATE assistance_matches
+            SET status = $2,
+                notes = $3,
+                verified_by = $4,
+                verified_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(notes)
+        .bind(verified_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(assistance_match)
+    }
+
+    /// Recompute the programs a registration has a verified match for
+    pub async fn refresh_verified_programs(&self, registration_id: i32) -> AppResult<Vec<String>> {
+        let programs = sqlx::query_scalar::<_, Vec<String>>(
+            r#"
+            UPDATE registrations
+            SET verified_assistance_programs = ARRAY(
+                    SELECT DISTINCT program FROM assistance_matches
+                    WHERE registration_id = $1 AND status = 'verified'
+                    ORDER BY program
+                ),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING verified_assistance_programs
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(programs)
+    }
+}
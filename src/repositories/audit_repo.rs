@@ -0,0 +1,34 @@
+use sqlx::PgPool;
+
+use crate::models::audit_log::NewAuditLog;
+use crate::utils::error::AppResult;
+
+pub struct AuditRepository {
+    pool: PgPool,
+}
+
+impl AuditRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, entry: &NewAuditLog) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_logs (school_id, user_id, entity_type, entity_id, action, old_value, new_value)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(entry.school_id)
+        .bind(entry.user_id)
+        .bind(entry.entity_type)
+        .bind(entry.entity_id)
+        .bind(entry.action.as_str())
+        .bind(&entry.old_value)
+        .bind(&entry.new_value)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
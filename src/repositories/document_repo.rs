@@ -0,0 +1,295 @@
+use sqlx::PgPool;
+
+use crate::models::registration::{Document, DocumentArtifact, NewDocumentArtifact};
+use crate::utils::error::AppResult;
+
+pub struct DocumentRepository {
+    pub pool: PgPool,
+}
+
+impl DocumentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> AppResult<Option<Document>> {
+        let document = sqlx::query_as::<_, Document>("SELECT * FROM documents WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(document)
+    }
+
+    /// Documents still waiting for a scan or processing after the given number of minutes
+    pub async fn find_stale_pending(&self, minutes: i32, limit: i64) -> AppResult<Vec<i32>> {
+        let ids = sqlx::query_scalar(
+            r#"
+            SELECT id FROM documents
+            WHERE (processing_status = 'pending' OR scan_status = 'pending')
+              AND created_at < NOW() - make_interval(mins => $1)
+              AND (next_attempt_at IS NULL OR next_attempt_at <= NOW())
+            ORDER BY processing_attempts, created_at
+            LIMIT $2
+            "#,
+        )
+        .bind(minutes)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Record the pipeline's results: the sanitized original, PDF page count,
+    /// metadata and derived artifacts
+    pub async fn record_processed(
+        &self,
+        id: i32,
+        file_url: &str,
+        file_size: i64,
+        mime_type: &str,
+        page_count: Option<i32>,
+        metadata: &serde_json::Value,
+        artifacts: &[NewDocumentArtifact],
+    ) -> AppResult<Document> {
+        let mut tx = self.pool.begin().await?;
+
+        for artifact in artifacts {
+            sqlx::query(
+                r#"
+                INSERT INTO document_artifacts (document_id, kind, file_url, file_size, mime_type, width, height)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (document_id, kind) DO UPDATE
+                SET file_url = EXCLUDED.file_url,
+                    file_size = EXCLUDED.file_size,
+                    mime_type = EXCLUDED.mime_type,
+                    width = EXCLUDED.width,
+                    height = EXCLUDED.height,
+                    created_at = NOW()
+                "#,
+            )
+            .bind(id)
+            .bind(artifact.kind)
+            .bind(&artifact.file_url)
+            .bind(artifact.file_size)
+            .bind(&artifact.mime_type)
+            .bind(artifact.width)
+            .bind(artifact.height)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let document = sqlx::query_as::<_, Document>(
+            r#"
+            UPDATE documents
+            SET file_url = $2,
+                file_size = $3,
+                mime_type = $4,
+                page_count = $5,
+                processing_metadata = $6,
+                processing_status = 'processed',
+                processing_error = NULL,
+                processed_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(file_url)
+        .bind(file_size)
+        .bind(mime_type)
+        .bind(page_count)
+        .bind(metadata)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(document)
+    }
+
+    pub async fn find_school_id(&self, id: i32) -> AppResult<Option<i32>> {
+        let school_id = sqlx::query_scalar(
+            r#"
+            SELECT r.school_id FROM documents d
+            JOIN registrations r ON r.id = d.registration_id
+            WHERE d.id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(school_id)
+    }
+
+    /// Release a document from quarantine, scanned clean or with scanning disabled
+    pub async fn record_scan(&self, id: i32, scan_status: &str) -> AppResult<Document> {
+        let document = sqlx::query_as::<_, Document>(
+            r#"
+            UPDATE documents
+            SET scan_status = $2,
+                scan_error = NULL,
+                scanned_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(scan_status)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(document)
+    }
+
+    /// Keep a document quarantined after the scanner failed, to be retried
+    pub async fn record_scan_error(&self, id: i32, error: &str) -> AppResult<Document> {
+        let document = sqlx::query_as::<_, Document>(
+            r#"
+            UPDATE documents
+            SET scan_error = $2,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(document)
+    }
+
+    /// Reject an infected document; it is never processed
+    pub async fn record_infected(&self, id: i32, signature: &str, notes: &str) -> AppResult<Document> {
+        let document = sqlx::query_as::<_, Document>(
+            r#"
+            UPDATE documents
+            SET scan_status = 'infected',
+                scan_signature = $2,
+                scan_error = NULL,
+                scanned_at = NOW(),
+                verification_status = 'rejected',
+                verification_notes = $3,
+                processing_status = CASE
+                    WHEN processing_status = 'processed' THEN processing_status
+                    ELSE 'skipped'
+                END,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(signature)
+        .bind(notes)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(document)
+    }
+
+    pub async fn record_hashes(
+        &self,
+        id: i32,
+        content_hash: &str,
+        perceptual_hash: Option<i64>,
+    ) -> AppResult<Document> {
+        let document = sqlx::query_as::<_, Document>(
+            r#"
+            UPDATE documents
+            SET content_hash = $2,
+                perceptual_hash = $3,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(content_hash)
+        .bind(perceptual_hash)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(document)
+    }
+
+    pub async fn record_failed(&self, id: i32, error: &str) -> AppResult<Document> {
+        let document = sqlx::query_as::<_, Document>(
+            r#"
+            UPDATE documents
+            SET processing_status = 'failed',
+                processing_error = $2,
+                processed_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(document)
+    }
+
+    /// Count a pipeline run that left the document unfinished and schedule
+    /// the next retry, doubling the delay each time. After the last attempt
+    /// whatever is still pending is marked failed.
+    pub async fn record_attempt(
+        &self,
+        id: i32,
+        backoff_minutes: i32,
+        max_attempts: i32,
+    ) -> AppResult<Document> {
+        let document = sqlx::query_as::<_, Document>(
+            r#"
+            UPDATE documents
+            SET processing_attempts = processing_attempts + 1,
+                next_attempt_at = CASE
+                    WHEN processing_attempts + 1 >= $3 THEN NULL
+                    ELSE NOW() + make_interval(mins => $2 * (1 << processing_attempts))
+                END,
+                scan_status = CASE
+                    WHEN processing_attempts + 1 >= $3 AND scan_status = 'pending' THEN 'failed'
+                    ELSE scan_status
+                END,
+                processing_status = CASE
+                    WHEN processing_attempts + 1 >= $3 AND processing_status = 'pending' THEN 'failed'
+                    ELSE processing_status
+                END,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(backoff_minutes)
+        .bind(max_attempts)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(document)
+    }
+
+    pub async fn find_artifacts(&self, document_ids: &[i32]) -> AppResult<Vec<DocumentArtifact>> {
+        let artifacts = sqlx::query_as::<_, DocumentArtifact>(
+            r#"
+            SELECT * FROM document_artifacts
+            WHERE document_id = ANY($1)
+            ORDER BY document_id, kind
+            "#,
+        )
+        .bind(document_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(artifacts)
+    }
+}
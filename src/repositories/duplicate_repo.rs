@@ -0,0 +1,451 @@
+use sqlx::PgPool;
+
+use crate::models::duplicate::{
+    ClusterDocument, DocumentDuplicate, DocumentFlagEdge, DocumentHashCandidate, DuplicateFlag,
+    DuplicateFlagDetail,
+};
+use crate::models::registration::{Registration, WithdrawalPlan, WithdrawalResult};
+use crate::repositories::registration_repo;
+use crate::utils::error::{AppError, AppResult};
+
+pub struct DuplicateRepository {
+    pool: PgPool,
+}
+
+impl DuplicateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Submitted (or further) registrations in the academic year sharing the
+    /// student NISN or the parent NIK, across all schools
+    pub async fn find_candidates(
+        &self,
+        academic_year: &str,
+        student_nisn: &str,
+        parent_nik: &str,
+        exclude_registration_id: Option<i32>,
+    ) -> AppResult<Vec<Registration>> {
+        let registrations = sqlx::query_as::<_, Registration>(
+            r#"
+            SELECT r.* FROM registrations r
+            JOIN periods p ON p.id = r.period_id
+            WHERE p.academic_year = $1
+              AND (r.student_nisn = $2 OR r.parent_nik = $3)
+              AND r.status IN ('submitted', 'verified', 'accepted', 'enrolled')
+              AND ($4::INTEGER IS NULL OR r.id <> $4)
+            ORDER BY r.created_at
+            "#,
+        )
+        .bind(academic_year)
+        .bind(student_nisn)
+        .bind(parent_nik)
+        .bind(exclude_registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(registrations)
+    }
+
+    /// Other active registrations of the same student in the academic year
+    pub async fn find_active_by_nisn(
+        &self,
+        academic_year: &str,
+        student_nisn: &str,
+        exclude_registration_id: i32,
+    ) -> AppResult<Vec<Registration>> {
+        let registrations = sqlx::query_as::<_, Registration>(
+            r#"
+            SELECT r.* FROM registrations r
+            JOIN periods p ON p.id = r.period_id
+            WHERE p.academic_year = $1
+              AND r.student_nisn = $2
+              AND r.status NOT IN ('rejected', 'expired', 'withdrawn')
+              AND r.id <> $3
+            ORDER BY r.created_at
+            "#,
+        )
+        .bind(academic_year)
+        .bind(student_nisn)
+        .bind(exclude_registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(registrations)
+    }
+
+    /// Store flags, skipping pairs that were already flagged
+    pub async fn create_flags(
+        &self,
+        registration_id: i32,
+        matches: &[(i32, String)],
+    ) -> AppResult<Vec<DuplicateFlag>> {
+        let mut tx = self.pool.begin().await?;
+        let mut flags = Vec::with_capacity(matches.len());
+
+        for (matched_registration_id, match_type) in matches {
+            let flag = sqlx::query_as::<_, DuplicateFlag>(
+                r#"
+                INSERT INTO duplicate_flags (registration_id, matched_registration_id, match_type)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (registration_id, matched_registration_id, match_type) DO NOTHING
+                RETURNING *
+                "#,
+            )
+            .bind(registration_id)
+            .bind(matched_registration_id)
+            .bind(match_type)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            if let Some(flag) = flag {
+                flags.push(flag);
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(flags)
+    }
+
+    /// Documents of other students' active registrations in the same academic
+    /// year with the same content hash or a perceptual hash within
+    /// `max_distance` bits. Documents of the same student are expected to match.
+    pub async fn find_document_candidates(
+        &self,
+        document_id: i32,
+        content_hash: &str,
+        perceptual_hash: Option<i64>,
+        max_distance: i32,
+    ) -> AppResult<Vec<DocumentHashCandidate>> {
+        let candidates = sqlx::query_as::<_, DocumentHashCandidate>(
+            r#"
+            WITH source AS (
+                SELECT r.id AS registration_id, r.student_nisn, p.academic_year
+                FROM documents d
+                JOIN registrations r ON r.id = d.registration_id
+                JOIN periods p ON p.id = r.period_id
+                WHERE d.id = $1
+            )
+            SELECT d.id AS document_id, d.registration_id, d.content_hash, d.perceptual_hash
+            FROM documents d
+            JOIN registrations r ON r.id = d.registration_id
+            JOIN periods p ON p.id = r.period_id
+            JOIN source s ON s.academic_year = p.academic_year
+            WHERE r.id <> s.registration_id
+              AND r.student_nisn <> s.student_nisn
+              AND r.status NOT IN ('expired', 'withdrawn')
+              AND d.scan_status <> 'infected'
+              AND (
+                  d.content_hash = $2
+                  OR ($3::BIGINT IS NOT NULL
+                      AND d.perceptual_hash IS NOT NULL
+                      AND bit_count((d.perceptual_hash # $3)::BIT(64)) <= $4)
+              )
+            ORDER BY d.created_at, d.id
+            "#,
+        )
+        .bind(document_id)
+        .bind(content_hash)
+        .bind(perceptual_hash)
+        .bind(max_distance)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(candidates)
+    }
+
+    /// Store flags for a document, as (matched_registration_id,
+    /// matched_document_id, match_type), skipping pairs already flagged
+    pub async fn create_document_flags(
+        &self,
+        registration_id: i32,
+        document_id: i32,
+        matches: &[(i32, i32, String)],
+    ) -> AppResult<Vec<DuplicateFlag>> {
+        let mut tx = self.pool.begin().await?;
+        let mut flags = Vec::with_capacity(matches.len());
+
+        for (matched_registration_id, matched_document_id, match_type) in matches {
+            let flag = sqlx::query_as::<_, DuplicateFlag>(
+                r#"
+                INSERT INTO duplicate_flags (registration_id, matched_registration_id, match_type, document_id, matched_document_id)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (registration_id, matched_registration_id, match_type) DO NOTHING
+                RETURNING *
+                "#,
+            )
+            .bind(registration_id)
+            .bind(matched_registration_id)
+            .bind(match_type)
+            .bind(document_id)
+            .bind(matched_document_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+            if let Some(flag) = flag {
+                flags.push(flag);
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(flags)
+    }
+
+    /// Document flags touching the given documents, from either side
+    pub async fn find_document_duplicates(&self, document_ids: &[i32]) -> AppResult<Vec<DocumentDuplicate>> {
+        let duplicates = sqlx::query_as::<_, DocumentDuplicate>(
+            r#"
+            SELECT f.id AS flag_id,
+                   d.id AS document_id,
+                   f.match_type,
+                   f.status,
+                   m.id AS matched_registration_id,
+                   CASE WHEN f.document_id = d.id THEN f.matched_document_id ELSE f.document_id END AS matched_document_id,
+                   m.school_id AS matched_school_id
+            FROM duplicate_flags f
+            JOIN documents d ON d.id = f.document_id OR d.id = f.matched_document_id
+            JOIN registrations m ON m.id = CASE
+                WHEN f.document_id = d.id THEN f.matched_registration_id
+                ELSE f.registration_id
+            END
+            WHERE d.id = ANY($1)
+              AND f.match_type IN ('document_content', 'document_image')
+            ORDER BY f.created_at
+            "#,
+        )
+        .bind(document_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(duplicates)
+    }
+
+    /// Document flags where either registration belongs to the period
+    pub async fn find_document_flag_edges(&self, period_id: i32) -> AppResult<Vec<DocumentFlagEdge>> {
+        let edges = sqlx::query_as::<_, DocumentFlagEdge>(
+            r#"
+            SELECT f.document_id, f.matched_document_id, f.match_type, f.status
+            FROM duplicate_flags f
+            JOIN registrations r ON r.id = f.registration_id
+            JOIN registrations m ON m.id = f.matched_registration_id
+            WHERE f.match_type IN ('document_content', 'document_image')
+              AND f.document_id IS NOT NULL
+              AND f.matched_document_id IS NOT NULL
+              AND (r.period_id = $1 OR m.period_id = $1)
+            "#,
+        )
+        .bind(period_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(edges)
+    }
+
+    pub async fn find_cluster_documents(&self, document_ids: &[i32]) -> AppResult<Vec<ClusterDocument>> {
+        let documents = sqlx::query_as::<_, ClusterDocument>(
+            r#"
+            SELECT d.id AS document_id,
+                   d.document_type,
+                   r.id AS registration_id,
+                   r.school_id,
+                   r.student_nisn,
+                   r.student_name,
+                   r.status AS registration_status
+            FROM documents d
+            JOIN registrations r ON r.id = d.registration_id
+            WHERE d.id = ANY($1)
+            ORDER BY d.id
+            "#,
+        )
+        .bind(document_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(documents)
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> AppResult<Option<DuplicateFlagDetail>> {
+        let flag = sqlx::query_as::<_, DuplicateFlagDetail>(
+            r#"
+            SELECT f.*,
+                   r.school_id AS registration_school_id,
+                   r.student_nisn AS registration_student_nisn,
+                   r.student_name AS registration_student_name,
+                   r.status AS registration_status,
+                   m.school_id AS matched_school_id,
+                   m.student_nisn AS matched_student_nisn,
+                   m.student_name AS matched_student_name,
+                   m.status AS matched_status
+            FROM duplicate_flags f
+            JOIN registrations r ON r.id = f.registration_id
+            JOIN registrations m ON m.id = f.matched_registration_id
+            WHERE f.id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(flag)
+    }
+
+    /// Flags where either registration belongs to the school (all flags when
+    /// no school is given)
+    pub async fn find_flags(
+        &self,
+        school_id: Option<i32>,
+        status: Option<String>,
+        match_type: Option<String>,
+        limit: i64,
+        offset: i64,
+    ) -> AppResult<Vec<DuplicateFlagDetail>> {
+        let flags = sqlx::query_as::<_, DuplicateFlagDetail>(
+            r#"
+            SELECT f.*,
+                   r.school_id AS registration_school_id,
+                   r.student_nisn AS registration_student_nisn,
+                   r.student_name AS registration_student_name,
+                   r.status AS registration_status,
+                   m.school_id AS matched_school_id,
+                   m.student_nisn AS matched_student_nisn,
+                   m.student_name AS matched_student_name,
+                   m.status AS matched_status
+            FROM duplicate_flags f
+            JOIN registrations r ON r.id = f.registration_id
+            JOIN registrations m ON m.id = f.matched_registration_id
+            WHERE ($1::INTEGER IS NULL OR r.school_id = $1 OR m.school_id = $1)
+              AND ($2::VARCHAR IS NULL OR f.status = $2)
+              AND ($3::VARCHAR IS NULL OR f.match_type = $3)
+            ORDER BY f.created_at DESC
+            LIMIT $4 OFFSET $5
+            "#,
+        )
+        .bind(school_id)
+        .bind(status)
+        .bind(match_type)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(flags)
+    }
+
+    pub async fn count_flags(
+        &self,
+        school_id: Option<i32>,
+        status: Option<String>,
+        match_type: Option<String>,
+    ) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM duplicate_flags f
+            JOIN registrations r ON r.id = f.registration_id
+            JOIN registrations m ON m.id = f.matched_registration_id
+            WHERE ($1::INTEGER IS NULL OR r.school_id = $1 OR m.school_id = $1)
+              AND ($2::VARCHAR IS NULL OR f.status = $2)
+              AND ($3::VARCHAR IS NULL OR f.match_type = $3)
+            "#,
+        )
+        .bind(school_id)
+        .bind(status)
+        .bind(match_type)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    pub async fn review_flag(
+        &self,
+        id: i32,
+        status: &str,
+        notes: Option<&str>,
+        reviewed_by: i32,
+    ) -> AppResult<DuplicateFlag> {
+        let flag = sqlx::query_as::<_, DuplicateFlag>(
+            r#"
+            UPDATE duplicate_flags
+            SET status = $2,
+                notes = $3,
+                reviewed_by = $4,
+                reviewed_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(notes)
+        .bind(reviewed_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(flag)
+    }
+
+    /// Enroll a registration and withdraw the student's other registrations
+    /// in one transaction. Each withdrawal takes the regular withdrawal path:
+    /// it is logged, payments are settled and freed seats go to the waitlist.
+    /// Fails with a conflict when the registration is no longer accepted.
+    pub async fn enroll_registration(
+        &self,
+        registration_id: i32,
+        withdrawals: &[(Registration, WithdrawalPlan)],
+        reason: &str,
+        enrolled_by: i32,
+    ) -> AppResult<(Registration, Vec<WithdrawalResult>)> {
+        let mut tx = self.pool.begin().await?;
+
+        let enrolled = sqlx::query_as::<_, Registration>(
+            r#"
+            UPDATE registrations
+            SET status = 'enrolled',
+                updated_at = NOW()
+            WHERE id = $1 AND status = 'accepted'
+            RETURNING *
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| {
+            AppError::Conflict("Registration status changed, please try again".to_string())
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO registration_status_history (registration_id, from_status, to_status, changed_by)
+            VALUES ($1, 'accepted', 'enrolled', $2)
+            "#,
+        )
+        .bind(registration_id)
+        .bind(enrolled_by)
+        .execute(&mut *tx)
+        .await?;
+
+        let mut results = Vec::with_capacity(withdrawals.len());
+        for (registration, plan) in withdrawals {
+            let result = registration_repo::withdraw_and_promote(
+                &mut tx,
+                registration,
+                plan,
+                reason,
+                enrolled_by,
+            )
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict("Registration status changed, please try again".to_string())
+            })?;
+
+            results.push(result);
+        }
+
+        tx.commit().await?;
+
+        Ok((enrolled, results))
+    }
+}
@@ -1,4 +1,13 @@
+pub mod allocation_repo;
+pub mod appeal_repo;
+pub mod assistance_repo;
+pub mod audit_repo;
+pub mod document_repo;
+pub mod duplicate_repo;
 pub mod period_repo;
+pub mod region_repo;
+pub mod test_repo;
 pub mod registration_repo;
 pub mod school_repo;
 pub mod user_repo;
+pub mod verification_repo;
@@ -1,8 +1,11 @@
 use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
 use sqlx::PgPool;
 
-use crate::models::period::{Period, RegistrationPath};
-use crate::utils::error::AppResult;
+use crate::models::major::PeriodMajor;
+use crate::models::period::{Period, PeriodDeadlines, PeriodStage, RegistrationPath};
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::timezone::school_timezone;
 
 pub struct PeriodRepository {
     pool: PgPool,
@@ -20,13 +23,24 @@ impl PeriodRepository {
         level: &str,
         start_date: NaiveDate,
         end_date: NaiveDate,
+        registration_start: NaiveDate,
+        registration_end: NaiveDate,
+        announcement_at: Option<DateTime<Utc>>,
         reenrollment_deadline: Option<NaiveDate>,
+        duplicate_policy: &str,
+        waitlist_policy: &str,
+        refund_policy: &str,
+        appeal_window_days: i32,
+        document_rejection_policy: &str,
+        achievement_point_table: Option<&serde_json::Value>,
+        age_rules: Option<&serde_json::Value>,
+        auto_lifecycle: bool,
+        deadlines: &PeriodDeadlines,
     ) -> AppResult<Period> {
-        // Use start_date and end_date for registration dates as well
         let period = sqlx::query_as::<_, Period>(
             r#"
-            INSERT INTO periods (school_id, academic_year, level, start_date, end_date, registration_start, registration_end, reenrollment_deadline, status)
-            VALUES ($1, $2, $3, $4, $5, $4, $5, $6, 'draft')
+            INSERT INTO periods (school_id, academic_year, level, start_date, end_date, registration_start, registration_end, announcement_at, reenrollment_deadline, duplicate_policy, waitlist_policy, refund_policy, appeal_window_days, document_rejection_policy, achievement_point_table, age_rules, auto_lifecycle, closing_time, registration_opens_at, registration_closes_at, reenrollment_closes_at, status)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, 'draft')
             RETURNING *
             "#,
         )
@@ -35,7 +49,22 @@ impl PeriodRepository {
         .bind(level)
         .bind(start_date)
         .bind(end_date)
+        .bind(registration_start)
+        .bind(registration_end)
+        .bind(announcement_at)
         .bind(reenrollment_deadline)
+        .bind(duplicate_policy)
+        .bind(waitlist_policy)
+        .bind(refund_policy)
+        .bind(appeal_window_days)
+        .bind(document_rejection_policy)
+        .bind(achievement_point_table)
+        .bind(age_rules)
+        .bind(auto_lifecycle)
+        .bind(deadlines.closing_time)
+        .bind(deadlines.registration_opens_at)
+        .bind(deadlines.registration_closes_at)
+        .bind(deadlines.reenrollment_closes_at)
         .fetch_one(&self.pool)
         .await?;
 
@@ -175,16 +204,43 @@ impl PeriodRepository {
         id: i32,
         start_date: Option<NaiveDate>,
         end_date: Option<NaiveDate>,
+        registration_start: Option<NaiveDate>,
+        registration_end: Option<NaiveDate>,
         announcement_date: Option<NaiveDate>,
+        announcement_at: Option<DateTime<Utc>>,
         reenrollment_deadline: Option<NaiveDate>,
+        duplicate_policy: Option<&str>,
+        waitlist_policy: Option<&str>,
+        refund_policy: Option<&str>,
+        appeal_window_days: Option<i32>,
+        document_rejection_policy: Option<&str>,
+        achievement_point_table: Option<&serde_json::Value>,
+        age_rules: Option<&serde_json::Value>,
+        auto_lifecycle: Option<bool>,
+        deadlines: &PeriodDeadlines,
     ) -> AppResult<Period> {
         let period = sqlx::query_as::<_, Period>(
             r#"
             UPDATE periods 
             SET start_date = COALESCE($2, start_date),
                 end_date = COALESCE($3, end_date),
-                announcement_date = COALESCE($4, announcement_date),
-                reenrollment_deadline = COALESCE($5, reenrollment_deadline),
+                registration_start = COALESCE($4, registration_start),
+                registration_end = COALESCE($5, registration_end),
+                announcement_date = COALESCE($6, announcement_date),
+                announcement_at = COALESCE($7, announcement_at),
+                reenrollment_deadline = COALESCE($8, reenrollment_deadline),
+                duplicate_policy = COALESCE($9, duplicate_policy),
+                waitlist_policy = COALESCE($10, waitlist_policy),
+                refund_policy = COALESCE($11, refund_policy),
+                appeal_window_days = COALESCE($12, appeal_window_days),
+                document_rejection_policy = COALESCE($13, document_rejection_policy),
+                achievement_point_table = COALESCE($14, achievement_point_table),
+                age_rules = COALESCE($15, age_rules),
+                auto_lifecycle = COALESCE($16, auto_lifecycle),
+                closing_time = $17,
+                registration_opens_at = $18,
+                registration_closes_at = $19,
+                reenrollment_closes_at = $20,
                 updated_at = NOW()
             WHERE id = $1
             RETURNING *
@@ -193,14 +249,58 @@ impl PeriodRepository {
         .bind(id)
         .bind(start_date)
         .bind(end_date)
+        .bind(registration_start)
+        .bind(registration_end)
         .bind(announcement_date)
+        .bind(announcement_at)
         .bind(reenrollment_deadline)
+        .bind(duplicate_policy)
+        .bind(waitlist_policy)
+        .bind(refund_policy)
+        .bind(appeal_window_days)
+        .bind(document_rejection_policy)
+        .bind(achievement_point_table)
+        .bind(age_rules)
+        .bind(auto_lifecycle)
+        .bind(deadlines.closing_time)
+        .bind(deadlines.registration_opens_at)
+        .bind(deadlines.registration_closes_at)
+        .bind(deadlines.reenrollment_closes_at)
         .fetch_one(&self.pool)
         .await?;
 
         Ok(period)
     }
 
+    /// Timezone of the school, in which period deadlines are entered and shown
+    pub async fn find_school_timezone(&self, school_id: i32) -> AppResult<Tz> {
+        let timezone: Option<String> =
+            sqlx::query_scalar("SELECT timezone FROM schools WHERE id = $1")
+                .bind(school_id)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        let timezone =
+            timezone.ok_or_else(|| AppError::NotFound("School not found".to_string()))?;
+
+        Ok(school_timezone(&timezone))
+    }
+
+    /// Draft and active periods managed by the lifecycle scheduler
+    pub async fn find_auto_lifecycle_periods(&self) -> AppResult<Vec<Period>> {
+        let periods = sqlx::query_as::<_, Period>(
+            r#"
+            SELECT * FROM periods
+            WHERE auto_lifecycle AND status IN ('draft', 'active')
+            ORDER BY registration_start, id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(periods)
+    }
+
     pub async fn update_status(&self, id: i32, status: &str) -> AppResult<Period> {
         let period = sqlx::query_as::<_, Period>(
             r#"
@@ -218,6 +318,22 @@ impl PeriodRepository {
         Ok(period)
     }
 
+    pub async fn set_cloned_from(&self, id: i32, source_id: i32) -> AppResult<Period> {
+        let period = sqlx::query_as::<_, Period>(
+            r#"
+            UPDATE periods SET cloned_from_id = $2, updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(source_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(period)
+    }
+
     pub async fn delete_period(&self, id: i32) -> AppResult<()> {
         sqlx::query("DELETE FROM periods WHERE id = $1")
             .bind(id)
@@ -236,11 +352,14 @@ impl PeriodRepository {
         quota: i32,
         description: Option<&str>,
         scoring_config: serde_json::Value,
+        selection_order: i32,
+        path_data_schema: Option<serde_json::Value>,
+        required_documents: &[String],
     ) -> AppResult<RegistrationPath> {
         let path = sqlx::query_as::<_, RegistrationPath>(
             r#"
-            INSERT INTO registration_paths (period_id, path_type, name, quota, description, scoring_config)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO registration_paths (period_id, path_type, name, quota, description, scoring_config, selection_order, path_data_schema, required_documents)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             RETURNING *
             "#,
         )
@@ -250,6 +369,9 @@ impl PeriodRepository {
         .bind(quota)
         .bind(description)
         .bind(scoring_config)
+        .bind(selection_order)
+        .bind(path_data_schema)
+        .bind(required_documents)
         .fetch_one(&self.pool)
         .await?;
 
@@ -259,7 +381,7 @@ impl PeriodRepository {
     pub async fn find_paths_by_period(&self, period_id: i32) -> AppResult<Vec<RegistrationPath>> {
         let paths = sqlx::query_as::<_, RegistrationPath>(
             r#"
-            SELECT * FROM registration_paths WHERE period_id = $1 ORDER BY id
+            SELECT * FROM registration_paths WHERE period_id = $1 ORDER BY selection_order, id
             "#,
         )
         .bind(period_id)
@@ -289,6 +411,9 @@ impl PeriodRepository {
         quota: Option<i32>,
         description: Option<&str>,
         scoring_config: Option<serde_json::Value>,
+        selection_order: Option<i32>,
+        path_data_schema: Option<serde_json::Value>,
+        required_documents: Option<&[String]>,
     ) -> AppResult<RegistrationPath> {
         let path = sqlx::query_as::<_, RegistrationPath>(
             r#"
@@ -297,6 +422,9 @@ impl PeriodRepository {
                 quota = COALESCE($3, quota),
                 description = COALESCE($4, description),
                 scoring_config = COALESCE($5, scoring_config),
+                selection_order = COALESCE($6, selection_order),
+                path_data_schema = COALESCE($7, path_data_schema),
+                required_documents = COALESCE($8, required_documents),
                 updated_at = NOW()
             WHERE id = $1
             RETURNING *
@@ -307,6 +435,9 @@ impl PeriodRepository {
         .bind(quota)
         .bind(description)
         .bind(scoring_config)
+        .bind(selection_order)
+        .bind(path_data_schema)
+        .bind(required_documents)
         .fetch_one(&self.pool)
         .await?;
 
@@ -321,4 +452,297 @@ impl PeriodRepository {
 
         Ok(())
     }
+
+    pub async fn is_in_allocation_round(&self, period_id: i32) -> AppResult<bool> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM allocation_round_periods WHERE period_id = $1)",
+        )
+        .bind(period_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    pub async fn has_test_sessions(&self, period_id: i32) -> AppResult<bool> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM test_sessions WHERE period_id = $1)",
+        )
+        .bind(period_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
+    // Major methods
+    pub async fn create_major(
+        &self,
+        period_id: i32,
+        code: &str,
+        name: &str,
+        capacity: i32,
+        description: Option<&str>,
+        criteria: &serde_json::Value,
+    ) -> AppResult<PeriodMajor> {
+        let major = sqlx::query_as::<_, PeriodMajor>(
+            r#"
+            INSERT INTO period_majors (period_id, code, name, capacity, description, criteria)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(period_id)
+        .bind(code)
+        .bind(name)
+        .bind(capacity)
+        .bind(description)
+        .bind(criteria)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(major)
+    }
+
+    pub async fn find_majors_by_period(&self, period_id: i32) -> AppResult<Vec<PeriodMajor>> {
+        let majors = sqlx::query_as::<_, PeriodMajor>(
+            r#"
+            SELECT * FROM period_majors WHERE period_id = $1 ORDER BY code
+            "#,
+        )
+        .bind(period_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(majors)
+    }
+
+    pub async fn find_major_by_id(&self, id: i32) -> AppResult<Option<PeriodMajor>> {
+        let major = sqlx::query_as::<_, PeriodMajor>(
+            r#"
+            SELECT * FROM period_majors WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(major)
+    }
+
+    pub async fn find_major_by_code(
+        &self,
+        period_id: i32,
+        code: &str,
+    ) -> AppResult<Option<PeriodMajor>> {
+        let major = sqlx::query_as::<_, PeriodMajor>(
+            r#"
+            SELECT * FROM period_majors WHERE period_id = $1 AND code = $2
+            "#,
+        )
+        .bind(period_id)
+        .bind(code)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(major)
+    }
+
+    pub async fn update_major(
+        &self,
+        id: i32,
+        name: Option<&str>,
+        capacity: Option<i32>,
+        description: Option<&str>,
+        criteria: Option<&serde_json::Value>,
+    ) -> AppResult<PeriodMajor> {
+        let major = sqlx::query_as::<_, PeriodMajor>(
+            r#"
+            UPDATE period_majors
+            SET name = COALESCE($2, name),
+                capacity = COALESCE($3, capacity),
+                description = COALESCE($4, description),
+                criteria = COALESCE($5, criteria),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(name)
+        .bind(capacity)
+        .bind(description)
+        .bind(criteria)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(major)
+    }
+
+    pub async fn delete_major(&self, id: i32) -> AppResult<()> {
+        sqlx::query("DELETE FROM period_majors WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // Stage methods
+    pub async fn create_stage(
+        &self,
+        period_id: i32,
+        stage_number: i32,
+        name: &str,
+        registration_start: NaiveDate,
+        registration_end: NaiveDate,
+        registration_opens_at: DateTime<Utc>,
+        registration_closes_at: DateTime<Utc>,
+        announcement_at: Option<DateTime<Utc>>,
+    ) -> AppResult<PeriodStage> {
+        let stage = sqlx::query_as::<_, PeriodStage>(
+            r#"
+            INSERT INTO period_stages (period_id, stage_number, name, registration_start, registration_end, registration_opens_at, registration_closes_at, announcement_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(period_id)
+        .bind(stage_number)
+        .bind(name)
+        .bind(registration_start)
+        .bind(registration_end)
+        .bind(registration_opens_at)
+        .bind(registration_closes_at)
+        .bind(announcement_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                AppError::Conflict("Stage number already exists in this period".to_string())
+            }
+            _ => AppError::Database(e),
+        })?;
+
+        Ok(stage)
+    }
+
+    pub async fn find_stages_by_period(&self, period_id: i32) -> AppResult<Vec<PeriodStage>> {
+        let stages = sqlx::query_as::<_, PeriodStage>(
+            r#"
+            SELECT * FROM period_stages WHERE period_id = $1 ORDER BY stage_number
+            "#,
+        )
+        .bind(period_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(stages)
+    }
+
+    pub async fn find_stage_by_id(&self, id: i32) -> AppResult<Option<PeriodStage>> {
+        let stage = sqlx::query_as::<_, PeriodStage>(
+            r#"
+            SELECT * FROM period_stages WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(stage)
+    }
+
+    pub async fn update_stage(
+        &self,
+        id: i32,
+        name: &str,
+        registration_start: NaiveDate,
+        registration_end: NaiveDate,
+        registration_opens_at: DateTime<Utc>,
+        registration_closes_at: DateTime<Utc>,
+        announcement_at: Option<DateTime<Utc>>,
+    ) -> AppResult<PeriodStage> {
+        let stage = sqlx::query_as::<_, PeriodStage>(
+            r#"
+            UPDATE period_stages
+            SET name = $2,
+                registration_start = $3,
+                registration_end = $4,
+                registration_opens_at = $5,
+                registration_closes_at = $6,
+                announcement_at = $7,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(name)
+        .bind(registration_start)
+        .bind(registration_end)
+        .bind(registration_opens_at)
+        .bind(registration_closes_at)
+        .bind(announcement_at)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(stage)
+    }
+
+    pub async fn delete_stage(&self, id: i32) -> AppResult<()> {
+        sqlx::query("DELETE FROM period_stages WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Replace the paths that run in a stage
+    pub async fn set_stage_paths(
+        &self,
+        stage_id: i32,
+        period_id: i32,
+        path_ids: &[i32],
+    ) -> AppResult<Vec<RegistrationPath>> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "UPDATE registration_paths SET stage_id = NULL, updated_at = NOW() WHERE stage_id = $1",
+        )
+        .bind(stage_id)
+        .execute(&mut *tx)
+        .await?;
+
+        let paths = sqlx::query_as::<_, RegistrationPath>(
+            r#"
+            UPDATE registration_paths
+            SET stage_id = $1, updated_at = NOW()
+            WHERE period_id = $2 AND id = ANY($3)
+            RETURNING *
+            "#,
+        )
+        .bind(stage_id)
+        .bind(period_id)
+        .bind(path_ids)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(paths)
+    }
+
+    pub async fn set_stage_announced(&self, id: i32, announcement_date: NaiveDate) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE period_stages SET announcement_date = $2, updated_at = NOW() WHERE id = $1",
+        )
+        .bind(id)
+        .bind(announcement_date)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
 }
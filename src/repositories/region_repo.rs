@@ -0,0 +1,142 @@
+use sqlx::PgPool;
+
+use crate::models::region::Region;
+use crate::models::school::School;
+use crate::utils::error::{AppError, AppResult};
+
+pub struct RegionRepository {
+    pub pool: PgPool,
+}
+
+impl RegionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_region(
+        &self,
+        parent_id: Option<i32>,
+        level: &str,
+        code: &str,
+        name: &str,
+    ) -> AppResult<Region> {
+        let region = sqlx::query_as::<_, Region>(
+            r#"
+            INSERT INTO regions (parent_id, level, code, name)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(parent_id)
+        .bind(level)
+        .bind(code)
+        .bind(name)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                AppError::Conflict("Region with this code already exists".to_string())
+            }
+            _ => AppError::Database(e),
+        })?;
+
+        Ok(region)
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> AppResult<Option<Region>> {
+        let region = sqlx::query_as::<_, Region>(
+            r#"
+            SELECT * FROM regions WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(region)
+    }
+
+    pub async fn find_all(
+        &self,
+        parent_id: Option<i32>,
+        level: Option<String>,
+    ) -> AppResult<Vec<Region>> {
+        let regions = sqlx::query_as::<_, Region>(
+            r#"
+            SELECT * FROM regions
+            WHERE ($1::INTEGER IS NULL OR parent_id = $1)
+              AND ($2::VARCHAR IS NULL OR level = $2)
+            ORDER BY code
+            "#,
+        )
+        .bind(parent_id)
+        .bind(level)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(regions)
+    }
+
+    pub async fn update_region(
+        &self,
+        id: i32,
+        code: Option<&str>,
+        name: Option<&str>,
+    ) -> AppResult<Region> {
+        let region = sqlx::query_as::<_, Region>(
+            r#"
+            UPDATE regions
+            SET code = COALESCE($2, code),
+                name = COALESCE($3, name),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(code)
+        .bind(name)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                AppError::Conflict("Region with this code already exists".to_string())
+            }
+            _ => AppError::Database(e),
+        })?;
+
+        Ok(region)
+    }
+
+    /// IDs of a region and all of its sub-regions
+    pub async fn find_scope_ids(&self, region_id: i32) -> AppResult<Vec<i32>> {
+        let ids = sqlx::query_scalar::<_, i32>(
+            r#"
+            WITH RECURSIVE scope AS (
+                SELECT id FROM regions WHERE id = $1
+                UNION
+                SELECT r.id FROM regions r JOIN scope s ON r.parent_id = s.id
+            )
+            SELECT id FROM scope
+            "#,
+        )
+        .bind(region_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    pub async fn find_schools(&self, region_ids: &[i32]) -> AppResult<Vec<School>> {
+        let schools = sqlx::query_as::<_, School>(
+            r#"
+            SELECT * FROM schools WHERE region_id = ANY($1) ORDER BY name
+            "#,
+        )
+        .bind(region_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(schools)
+    }
+}
@@ -1,7 +1,12 @@
 use chrono::{DateTime, Utc};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
-use crate::models::registration::{Document, Registration};
+use crate::models::achievement::{NewAchievement, RegistrationAchievement};
+use crate::models::major::{RegistrationMajorChoice, RegistrationRequirementCheck};
+use crate::models::registration::{
+    Document, Registration, RegistrationFallbackPath, RegistrationGrade, RegistrationRevision,
+    RegistrationStatusChange, WithdrawalPlan, WithdrawalResult,
+};
 use crate::utils::error::AppResult;
 
 pub struct RegistrationRepository {
@@ -28,6 +33,8 @@ impl RegistrationRepository {
         student_address: &str,
         student_phone: Option<&str>,
         student_email: Option<&str>,
+        student_nik: Option<&str>,
+        student_region_code: Option<&str>,
         parent_name: &str,
         parent_nik: &str,
         parent_phone: &str,
@@ -44,11 +51,12 @@ impl RegistrationRepository {
                 school_id, user_id, period_id, path_id,
                 student_nisn, student_name, student_gender, student_birth_place, student_birth_date,
                 student_religion, student_address, student_phone, student_email,
+                student_nik, student_region_code,
                 parent_name, parent_nik, parent_phone, parent_occupation, parent_income,
                 previous_school_name, previous_school_npsn, previous_school_address,
                 path_data, status
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, 'draft')
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, 'draft')
             RETURNING *
             "#,
         )
@@ -65,6 +73,8 @@ impl RegistrationRepository {
         .bind(student_address)
         .bind(student_phone)
         .bind(student_email)
+        .bind(student_nik)
+        .bind(student_region_code)
         .bind(parent_name)
         .bind(parent_nik)
         .bind(parent_phone)
@@ -167,6 +177,21 @@ impl RegistrationRepository {
         Ok(registrations)
     }
 
+    pub async fn find_by_periods(&self, period_ids: &[i32]) -> AppResult<Vec<Registration>> {
+        let registrations = sqlx::query_as::<_, Registration>(
+            r#"
+            SELECT * FROM registrations 
+            WHERE period_id = ANY($1)
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(period_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(registrations)
+    }
+
     pub async fn count_by_school(
         &self,
         school_id: i32,
@@ -220,6 +245,8 @@ impl RegistrationRepository {
         student_address: Option<&str>,
         student_phone: Option<&str>,
         student_email: Option<&str>,
+        student_nik: Option<&str>,
+        student_region_code: Option<&str>,
         parent_name: Option<&str>,
         parent_nik: Option<&str>,
         parent_phone: Option<&str>,
@@ -238,12 +265,14 @@ impl RegistrationRepository {
                 student_address = COALESCE($7, student_address),
                 student_phone = COALESCE($8, student_phone),
                 student_email = COALESCE($9, student_email),
-                parent_name = COALESCE($10, parent_name),
-                parent_nik = COALESCE($11, parent_nik),
-                parent_phone = COALESCE($12, parent_phone),
-                parent_occupation = COALESCE($13, parent_occupation),
-                parent_income = COALESCE($14, parent_income),
-                path_data = COALESCE($15, path_data),
+                student_nik = COALESCE($10, student_nik),
+                student_region_code = COALESCE($11, student_region_code),
+                parent_name = COALESCE($12, parent_name),
+                parent_nik = COALESCE($13, parent_nik),
+                parent_phone = COALESCE($14, parent_phone),
+                parent_occupation = COALESCE($15, parent_occupation),
+                parent_income = COALESCE($16, parent_income),
+                path_data = COALESCE($17, path_data),
                 updated_at = NOW()
             WHERE id = $1
             RETURNING *
@@ -258,6 +287,8 @@ impl RegistrationRepository {
         .bind(student_address)
         .bind(student_phone)
         .bind(student_email)
+        .bind(student_nik)
+        .bind(student_region_code)
         .bind(parent_name)
         .bind(parent_nik)
         .bind(parent_phone)
@@ -281,6 +312,8 @@ impl RegistrationRepository {
             UPDATE registrations 
             SET status = $2, 
                 rejection_reason = $3,
+                waitlisted = FALSE,
+                submitted_at = CASE WHEN $2 = 'submitted' THEN NOW() ELSE submitted_at END,
                 updated_at = NOW()
             WHERE id = $1
             RETURNING *
@@ -295,6 +328,157 @@ impl RegistrationRepository {
         Ok(registration)
     }
 
+    pub async fn set_selection_outcome(
+        &self,
+        id: i32,
+        status: &str,
+        accepted_path_id: Option<i32>,
+        accepted_major_id: Option<i32>,
+        ranking: Option<i32>,
+        rejection_reason: Option<&str>,
+        waitlisted: bool,
+    ) -> AppResult<Registration> {
+        let registration = sqlx::query_as::<_, Registration>(
+            r#"
+            UPDATE registrations 
+            SET status = $2,
+                accepted_path_id = $3,
+                accepted_major_id = $4,
+                ranking = COALESCE($5, ranking),
+                rejection_reason = $6,
+                waitlisted = $7,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .bind(accepted_path_id)
+        .bind(accepted_major_id)
+        .bind(ranking)
+        .bind(rejection_reason)
+        .bind(waitlisted)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(registration)
+    }
+
+    pub async fn set_deadline_extension(
+        &self,
+        id: i32,
+        until: DateTime<Utc>,
+        reason: &str,
+        extended_by: i32,
+    ) -> AppResult<Registration> {
+        let registration = sqlx::query_as::<_, Registration>(
+            r#"
+            UPDATE registrations 
+            SET deadline_extended_until = $2,
+                deadline_extension_reason = $3,
+                deadline_extended_by = $4,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(until)
+        .bind(reason)
+        .bind(extended_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(registration)
+    }
+
+    /// Seats taken per path by accepted or enrolled registrations
+    pub async fn count_accepted_by_path(
+        &self,
+        period_id: i32,
+        path_ids: &[i32],
+    ) -> AppResult<Vec<(i32, i64)>> {
+        let counts = sqlx::query_as::<_, (i32, i64)>(
+            r#"
+            SELECT accepted_path_id, COUNT(*) FROM registrations
+            WHERE period_id = $1
+              AND status IN ('accepted', 'enrolled')
+              AND accepted_path_id = ANY($2)
+            GROUP BY accepted_path_id
+            "#,
+        )
+        .bind(period_id)
+        .bind(path_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// Seats taken per major by registrations of paths outside the given ones
+    pub async fn count_accepted_by_major_outside_paths(
+        &self,
+        period_id: i32,
+        path_ids: &[i32],
+    ) -> AppResult<Vec<(i32, i64)>> {
+        let counts = sqlx::query_as::<_, (i32, i64)>(
+            r#"
+            SELECT accepted_major_id, COUNT(*) FROM registrations
+            WHERE period_id = $1
+              AND status IN ('accepted', 'enrolled')
+              AND accepted_major_id IS NOT NULL
+              AND NOT (path_id = ANY($2))
+            GROUP BY accepted_major_id
+            "#,
+        )
+        .bind(period_id)
+        .bind(path_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// NISNs of students already accepted through paths outside the given ones
+    pub async fn find_accepted_nisns_outside_paths(
+        &self,
+        period_id: i32,
+        path_ids: &[i32],
+    ) -> AppResult<Vec<String>> {
+        let nisns = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT DISTINCT student_nisn FROM registrations
+            WHERE period_id = $1
+              AND status IN ('accepted', 'enrolled')
+              AND NOT (path_id = ANY($2))
+            "#,
+        )
+        .bind(period_id)
+        .bind(path_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(nisns)
+    }
+
+    pub async fn is_accepted_in_period(&self, period_id: i32, student_nisn: &str) -> AppResult<bool> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM registrations
+                WHERE period_id = $1 AND student_nisn = $2 AND status IN ('accepted', 'enrolled')
+            )
+            "#,
+        )
+        .bind(period_id)
+        .bind(student_nisn)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(exists)
+    }
+
     pub async fn set_registration_number(&self, id: i32, registration_number: &str) -> AppResult<Registration> {
         let registration = sqlx::query_as::<_, Registration>(
             r#"
@@ -330,6 +514,475 @@ impl RegistrationRepository {
         Ok(registration_number)
     }
 
+    // Fallback path methods
+    pub async fn replace_fallback_paths(
+        &self,
+        registration_id: i32,
+        fallback_paths: &[(i32, serde_json::Value)],
+    ) -> AppResult<Vec<RegistrationFallbackPath>> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM registration_fallback_paths WHERE registration_id = $1")
+            .bind(registration_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut created = Vec::with_capacity(fallback_paths.len());
+        for (index, (path_id, path_data)) in fallback_paths.iter().enumerate() {
+            let fallback = sqlx::query_as::<_, RegistrationFallbackPath>(
+                r#"
+                INSERT INTO registration_fallback_paths (registration_id, path_id, priority, path_data)
+                VALUES ($1, $2, $3, $4)
+                RETURNING *
+                "#,
+            )
+            .bind(registration_id)
+            .bind(path_id)
+            .bind((index + 1) as i32)
+            .bind(path_data)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            created.push(fallback);
+        }
+
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    pub async fn find_fallback_paths_by_registration(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<RegistrationFallbackPath>> {
+        let fallback_paths = sqlx::query_as::<_, RegistrationFallbackPath>(
+            r#"
+            SELECT * FROM registration_fallback_paths 
+            WHERE registration_id = $1 
+            ORDER BY priority
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(fallback_paths)
+    }
+
+    pub async fn find_fallback_paths_by_period(
+        &self,
+        period_id: i32,
+    ) -> AppResult<Vec<RegistrationFallbackPath>> {
+        let fallback_paths = sqlx::query_as::<_, RegistrationFallbackPath>(
+            r#"
+            SELECT f.* FROM registration_fallback_paths f
+            JOIN registrations r ON r.id = f.registration_id
+            WHERE r.period_id = $1
+            ORDER BY f.registration_id, f.priority
+            "#,
+        )
+        .bind(period_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(fallback_paths)
+    }
+
+    pub async fn update_fallback_score(&self, id: i32, selection_score: Option<f64>) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE registration_fallback_paths 
+            SET selection_score = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(selection_score)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    // Grade methods
+    /// Replace all grades of a registration and store the recomputed average
+    pub async fn replace_grades(
+        &self,
+        registration_id: i32,
+        grades: &[(i32, String, f64)],
+        rapor_average: Option<f64>,
+    ) -> AppResult<Vec<RegistrationGrade>> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM registration_grades WHERE registration_id = $1")
+            .bind(registration_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut saved = Vec::with_capacity(grades.len());
+        for (semester, subject, score) in grades {
+            let grade = sqlx::query_as::<_, RegistrationGrade>(
+                r#"
+                INSERT INTO registration_grades (registration_id, semester, subject, score)
+                VALUES ($1, $2, $3, $4)
+                RETURNING *
+                "#,
+            )
+            .bind(registration_id)
+            .bind(semester)
+            .bind(subject)
+            .bind(score)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            saved.push(grade);
+        }
+
+        sqlx::query("UPDATE registrations SET rapor_average = $2, updated_at = NOW() WHERE id = $1")
+            .bind(registration_id)
+            .bind(rapor_average)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(saved)
+    }
+
+    pub async fn find_grades_by_registration(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<RegistrationGrade>> {
+        let grades = sqlx::query_as::<_, RegistrationGrade>(
+            r#"
+            SELECT * FROM registration_grades
+            WHERE registration_id = $1
+            ORDER BY semester, subject
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(grades)
+    }
+
+    /// Mark every grade of a semester as checked against the rapor document
+    pub async fn verify_grade_semester(
+        &self,
+        registration_id: i32,
+        semester: i32,
+        document_id: Option<i32>,
+        verified_by: i32,
+    ) -> AppResult<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE registration_grades
+            SET document_id = $3,
+                verified_by = $4,
+                verified_at = NOW(),
+                updated_at = NOW()
+            WHERE registration_id = $1 AND semester = $2
+            "#,
+        )
+        .bind(registration_id)
+        .bind(semester)
+        .bind(document_id)
+        .bind(verified_by)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // Achievement methods
+    /// Replace all achievements of a registration; no approved achievements remain
+    pub async fn replace_achievements(
+        &self,
+        registration_id: i32,
+        achievements: &[NewAchievement],
+    ) -> AppResult<Vec<RegistrationAchievement>> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM registration_achievements WHERE registration_id = $1")
+            .bind(registration_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut saved = Vec::with_capacity(achievements.len());
+        for achievement in achievements {
+            let achievement = sqlx::query_as::<_, RegistrationAchievement>(
+                r#"
+                INSERT INTO registration_achievements (registration_id, competition_name, level, rank, year, participation, document_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                RETURNING *
+                "#,
+            )
+            .bind(registration_id)
+            .bind(&achievement.competition_name)
+            .bind(&achievement.level)
+            .bind(achievement.rank)
+            .bind(achievement.year)
+            .bind(&achievement.participation)
+            .bind(achievement.document_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            saved.push(achievement);
+        }
+
+        sqlx::query("UPDATE registrations SET achievement_points = NULL, updated_at = NOW() WHERE id = $1")
+            .bind(registration_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(saved)
+    }
+
+    pub async fn find_achievements_by_registration(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<RegistrationAchievement>> {
+        let achievements = sqlx::query_as::<_, RegistrationAchievement>(
+            r#"
+            SELECT * FROM registration_achievements
+            WHERE registration_id = $1
+            ORDER BY id
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(achievements)
+    }
+
+    pub async fn find_achievement_by_id(
+        &self,
+        id: i32,
+    ) -> AppResult<Option<RegistrationAchievement>> {
+        let achievement = sqlx::query_as::<_, RegistrationAchievement>(
+            r#"
+            SELECT * FROM registration_achievements WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(achievement)
+    }
+
+    /// Achievements are verified one by one against their certificate
+    pub async fn verify_achievement(
+        &self,
+        id: i32,
+        verification_status: &str,
+        verification_notes: Option<&str>,
+        verified_by: i32,
+    ) -> AppResult<RegistrationAchievement> {
+        let achievement = sqlx::query_as::<_, RegistrationAchievement>(
+            r#"
+            UPDATE registration_achievements
+            SET verification_status = $2,
+                verification_notes = $3,
+                verified_by = $4,
+                verified_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(verification_status)
+        .bind(verification_notes)
+        .bind(verified_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(achievement)
+    }
+
+    pub async fn update_achievement_points(
+        &self,
+        registration_id: i32,
+        achievement_points: Option<f64>,
+    ) -> AppResult<()> {
+        sqlx::query("UPDATE registrations SET achievement_points = $2, updated_at = NOW() WHERE id = $1")
+            .bind(registration_id)
+            .bind(achievement_points)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // Major methods
+    /// Replace the ranked major choices of a registration, in priority order
+    pub async fn replace_major_choices(
+        &self,
+        registration_id: i32,
+        major_ids: &[i32],
+    ) -> AppResult<Vec<RegistrationMajorChoice>> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM registration_major_choices WHERE registration_id = $1")
+            .bind(registration_id)
+            .execute(&mut *tx)
+            .await?;
+
+        let mut saved = Vec::with_capacity(major_ids.len());
+        for (index, major_id) in major_ids.iter().enumerate() {
+            let choice = sqlx::query_as::<_, RegistrationMajorChoice>(
+                r#"
+                INSERT INTO registration_major_choices (registration_id, major_id, priority)
+                VALUES ($1, $2, $3)
+                RETURNING *
+                "#,
+            )
+            .bind(registration_id)
+            .bind(major_id)
+            .bind(index as i32 + 1)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            saved.push(choice);
+        }
+
+        tx.commit().await?;
+
+        Ok(saved)
+    }
+
+    pub async fn find_major_choices_by_registration(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<RegistrationMajorChoice>> {
+        let choices = sqlx::query_as::<_, RegistrationMajorChoice>(
+            r#"
+            SELECT * FROM registration_major_choices
+            WHERE registration_id = $1
+            ORDER BY priority
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(choices)
+    }
+
+    /// Major choices of every registration in a period, by registration and priority
+    pub async fn find_major_choices_by_period(
+        &self,
+        period_id: i32,
+    ) -> AppResult<Vec<RegistrationMajorChoice>> {
+        let choices = sqlx::query_as::<_, RegistrationMajorChoice>(
+            r#"
+            SELECT c.* FROM registration_major_choices c
+            JOIN registrations r ON r.id = c.registration_id
+            WHERE r.period_id = $1
+            ORDER BY c.registration_id, c.priority
+            "#,
+        )
+        .bind(period_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(choices)
+    }
+
+    pub async fn upsert_requirement_check(
+        &self,
+        registration_id: i32,
+        requirement_key: &str,
+        passed: bool,
+        notes: Option<&str>,
+        verified_by: i32,
+    ) -> AppResult<RegistrationRequirementCheck> {
+        let check = sqlx::query_as::<_, RegistrationRequirementCheck>(
+            r#"
+            INSERT INTO registration_requirement_checks (registration_id, requirement_key, passed, notes, verified_by, verified_at)
+            VALUES ($1, $2, $3, $4, $5, NOW())
+            ON CONFLICT (registration_id, requirement_key) DO UPDATE
+            SET passed = EXCLUDED.passed,
+                notes = EXCLUDED.notes,
+                verified_by = EXCLUDED.verified_by,
+                verified_at = NOW(),
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(registration_id)
+        .bind(requirement_key)
+        .bind(passed)
+        .bind(notes)
+        .bind(verified_by)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(check)
+    }
+
+    pub async fn find_requirement_checks_by_period(
+        &self,
+        period_id: i32,
+    ) -> AppResult<Vec<RegistrationRequirementCheck>> {
+        let checks = sqlx::query_as::<_, RegistrationRequirementCheck>(
+            r#"
+            SELECT k.* FROM registration_requirement_checks k
+            JOIN registrations r ON r.id = k.registration_id
+            WHERE r.period_id = $1
+            "#,
+        )
+        .bind(period_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(checks)
+    }
+
+    pub async fn find_requirement_checks_by_registration(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<RegistrationRequirementCheck>> {
+        let checks = sqlx::query_as::<_, RegistrationRequirementCheck>(
+            r#"
+            SELECT * FROM registration_requirement_checks
+            WHERE registration_id = $1
+            ORDER BY requirement_key
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(checks)
+    }
+
+    /// Average score per subject over the semesters of each registration in a period
+    pub async fn find_subject_averages_by_period(
+        &self,
+        period_id: i32,
+    ) -> AppResult<Vec<(i32, String, f64)>> {
+        let averages = sqlx::query_as::<_, (i32, String, f64)>(
+            r#"
+            SELECT g.registration_id, g.subject, AVG(g.score)
+            FROM registration_grades g
+            JOIN registrations r ON r.id = g.registration_id
+            WHERE r.period_id = $1
+            GROUP BY g.registration_id, g.subject
+            "#,
+        )
+        .bind(period_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(averages)
+    }
+
     // Document methods
     pub async fn create_document(
         &self,
@@ -339,11 +992,12 @@ impl RegistrationRepository {
         file_name: &str,
         file_size: i64,
         mime_type: &str,
+        original_file_size: Option<i64>,
     ) -> AppResult<Document> {
         let document = sqlx::query_as::<_, Document>(
             r#"
-            INSERT INTO documents (registration_id, document_type, file_url, file_name, file_size, mime_type, verification_status)
-            VALUES ($1, $2, $3, $4, $5, $6, 'pending')
+            INSERT INTO documents (registration_id, document_type, file_url, file_name, file_size, mime_type, original_file_size, verification_status)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, 'pending')
             RETURNING *
             "#,
         )
@@ -353,6 +1007,7 @@ impl RegistrationRepository {
         .bind(file_name)
         .bind(file_size)
         .bind(mime_type)
+        .bind(original_file_size)
         .fetch_one(&self.pool)
         .await?;
 
@@ -390,12 +1045,15 @@ impl RegistrationRepository {
         id: i32,
         verification_status: &str,
         verification_notes: Option<&str>,
+        verified_by: i32,
     ) -> AppResult<Document> {
         let document = sqlx::query_as::<_, Document>(
             r#"
             UPDATE documents 
             SET verification_status = $2, 
                 verification_notes = $3,
+                verified_by = $4,
+                verified_at = NOW(),
                 updated_at = NOW()
             WHERE id = $1
             RETURNING *
@@ -404,12 +1062,191 @@ impl RegistrationRepository {
         .bind(id)
         .bind(verification_status)
         .bind(verification_notes)
+        .bind(verified_by)
         .fetch_one(&self.pool)
         .await?;
 
         Ok(document)
     }
 
+    // Withdrawal methods
+    /// Withdraw a registration still in the status it was read with and hand
+    /// its seat to the waitlist in one transaction, see [`withdraw_and_promote`]
+    pub async fn withdraw_registration(
+        &self,
+        registration: &Registration,
+        plan: &WithdrawalPlan,
+        reason: &str,
+        withdrawn_by: i32,
+    ) -> AppResult<Option<WithdrawalResult>> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = withdraw_and_promote(&mut tx, registration, plan, reason, withdrawn_by).await?;
+        if result.is_some() {
+            tx.commit().await?;
+        }
+
+        Ok(result)
+    }
+
+    pub async fn find_status_history(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<RegistrationStatusChange>> {
+        let history = sqlx::query_as::<_, RegistrationStatusChange>(
+            r#"
+            SELECT * FROM registration_status_history 
+            WHERE registration_id = $1
+            ORDER BY created_at, id
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(history)
+    }
+
+    // Revision methods
+    /// Open a revision request and send the registration back to the parent
+    pub async fn create_revision(
+        &self,
+        registration_id: i32,
+        requested_by: i32,
+        notes: Option<&str>,
+        fields: &serde_json::Value,
+        documents: &serde_json::Value,
+        snapshot: &serde_json::Value,
+    ) -> AppResult<(Registration, RegistrationRevision)> {
+        let mut tx = self.pool.begin().await?;
+
+        let revision = sqlx::query_as::<_, RegistrationRevision>(
+            r#"
+            INSERT INTO registration_revisions (registration_id, requested_by, notes, fields, documents, snapshot)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(registration_id)
+        .bind(requested_by)
+        .bind(notes)
+        .bind(fields)
+        .bind(documents)
+        .bind(snapshot)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let registration = sqlx::query_as::<_, Registration>(
+            r#"
+            UPDATE registrations 
+            SET status = 'revision_requested', updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok((registration, revision))
+    }
+
+    /// Revision request still waiting for the parent
+    pub async fn find_open_revision(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Option<RegistrationRevision>> {
+        let revision = sqlx::query_as::<_, RegistrationRevision>(
+            r#"
+            SELECT * FROM registration_revisions 
+            WHERE registration_id = $1 AND resubmitted_at IS NULL
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(revision)
+    }
+
+    pub async fn find_revisions_by_registration(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<RegistrationRevision>> {
+        let revisions = sqlx::query_as::<_, RegistrationRevision>(
+            r#"
+            SELECT * FROM registration_revisions 
+            WHERE registration_id = $1 
+            ORDER BY created_at DESC, id DESC
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(revisions)
+    }
+
+    /// Latest resubmitted revision of each registration
+    pub async fn find_latest_resubmissions(
+        &self,
+        registration_ids: &[i32],
+    ) -> AppResult<Vec<RegistrationRevision>> {
+        let revisions = sqlx::query_as::<_, RegistrationRevision>(
+            r#"
+            SELECT DISTINCT ON (registration_id) * FROM registration_revisions 
+            WHERE registration_id = ANY($1) AND resubmitted_at IS NOT NULL
+            ORDER BY registration_id, resubmitted_at DESC
+            "#,
+        )
+        .bind(registration_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(revisions)
+    }
+
+    /// Close a revision request with the parent's changes and return the
+    /// registration to the verification queue
+    pub async fn complete_revision(
+        &self,
+        revision_id: i32,
+        registration_id: i32,
+        changes: &serde_json::Value,
+    ) -> AppResult<Registration> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE registration_revisions 
+            SET changes = $2, resubmitted_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(revision_id)
+        .bind(changes)
+        .execute(&mut *tx)
+        .await?;
+
+        let registration = sqlx::query_as::<_, Registration>(
+            r#"
+            UPDATE registrations 
+            SET status = 'submitted', submitted_at = NOW(), updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(registration)
+    }
+
     pub async fn delete_document(&self, id: i32) -> AppResult<()> {
         sqlx::query("DELETE FROM documents WHERE id = $1")
             .bind(id)
@@ -419,3 +1256,227 @@ impl RegistrationRepository {
         Ok(())
     }
 }
+
+/// Withdraw a registration still in `from_status`, recording the change.
+/// Test bookings without results are cancelled, pending payments expire and
+/// paid ones are marked refunded when `refund` is set. Returns None when the
+/// status changed in the meantime.
+pub async fn withdraw(
+    conn: &mut PgConnection,
+    id: i32,
+    from_status: &str,
+    reason: &str,
+    withdrawn_by: i32,
+    refund: bool,
+) -> AppResult<Option<(Registration, Vec<i32>)>> {
+    let registration = sqlx::query_as::<_, Registration>(
+        r#"
+        UPDATE registrations 
+        SET status = 'withdrawn',
+            rejection_reason = $3,
+            updated_at = NOW()
+        WHERE id = $1 AND status = $2
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(from_status)
+    .bind(reason)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    let registration = match registration {
+        Some(registration) => registration,
+        None => return Ok(None),
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO registration_status_history (registration_id, from_status, to_status, reason, changed_by)
+        VALUES ($1, $2, 'withdrawn', $3, $4)
+        "#,
+    )
+    .bind(id)
+    .bind(from_status)
+    .bind(reason)
+    .bind(withdrawn_by)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query("DELETE FROM test_bookings WHERE registration_id = $1 AND attended IS NULL")
+        .bind(id)
+        .execute(&mut *conn)
+        .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE payments 
+        SET status = 'expired', expired_at = NOW(), updated_at = NOW()
+        WHERE registration_id = $1 AND status = 'pending'
+        "#,
+    )
+    .bind(id)
+    .execute(&mut *conn)
+    .await?;
+
+    let refunded_payment_ids = if refund {
+        sqlx::query_scalar::<_, i32>(
+            r#"
+            UPDATE payments 
+            SET status = 'refunded', updated_at = NOW()
+            WHERE registration_id = $1 AND status = 'paid'
+            RETURNING id
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&mut *conn)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    Ok(Some((registration, refunded_payment_ids)))
+}
+
+/// Withdraw a registration still in the status it was read with, see
+/// [`withdraw`], and when the plan says so give its seat to the best-ranked
+/// waitlisted applicant. Returns None when the status changed in the meantime.
+pub async fn withdraw_and_promote(
+    conn: &mut PgConnection,
+    registration: &Registration,
+    plan: &WithdrawalPlan,
+    reason: &str,
+    withdrawn_by: i32,
+) -> AppResult<Option<WithdrawalResult>> {
+    let withdrawn = withdraw(
+        &mut *conn,
+        registration.id,
+        &registration.status,
+        reason,
+        withdrawn_by,
+        plan.refund,
+    )
+    .await?;
+
+    let (withdrawn, refunded_payment_ids) = match withdrawn {
+        Some(withdrawn) => withdrawn,
+        None => return Ok(None),
+    };
+
+    let mut promoted = None;
+    if let Some(path_id) = plan.promote_path_id {
+        let candidate = find_waitlist_candidate(
+            &mut *conn,
+            registration.period_id,
+            path_id,
+            registration.accepted_major_id,
+        )
+        .await?;
+
+        if let Some(candidate) = candidate {
+            promoted = promote_from_waitlist(
+                &mut *conn,
+                candidate.id,
+                path_id,
+                registration.accepted_major_id,
+                &registration.seat_handover_reason(),
+            )
+            .await?;
+        }
+    }
+
+    Ok(Some(WithdrawalResult {
+        registration: withdrawn,
+        promoted,
+        refunded_payment_ids,
+    }))
+}
+
+/// Best-ranked waitlisted applicant who chose
+/// the path, as first choice or fallback, and the major if given. Students
+/// already accepted elsewhere in the period are skipped. The row stays
+/// locked until the transaction ends; rows locked by a concurrent withdrawal
+/// are skipped so two freed seats never go to the same applicant.
+pub async fn find_waitlist_candidate(
+    conn: &mut PgConnection,
+    period_id: i32,
+    path_id: i32,
+    major_id: Option<i32>,
+) -> AppResult<Option<Registration>> {
+    let registration = sqlx::query_as::<_, Registration>(
+        r#"
+        SELECT r.* FROM registrations r
+        LEFT JOIN registration_fallback_paths f
+          ON f.registration_id = r.id AND f.path_id = $2
+        WHERE r.period_id = $1
+          AND r.status = 'rejected'
+          AND r.waitlisted
+          AND ((r.path_id = $2 AND r.selection_score IS NOT NULL) OR f.selection_score IS NOT NULL)
+          AND ($3::INTEGER IS NULL OR EXISTS (
+              SELECT 1 FROM registration_major_choices c
+              WHERE c.registration_id = r.id AND c.major_id = $3
+          ))
+          AND NOT EXISTS (
+              SELECT 1 FROM registrations o
+              WHERE o.period_id = r.period_id
+                AND o.student_nisn = r.student_nisn
+                AND o.status IN ('accepted', 'enrolled')
+          )
+        ORDER BY CASE WHEN r.path_id = $2 THEN r.selection_score ELSE f.selection_score END DESC,
+                 r.created_at
+        LIMIT 1
+        FOR UPDATE OF r SKIP LOCKED
+        "#,
+    )
+    .bind(period_id)
+    .bind(path_id)
+    .bind(major_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    Ok(registration)
+}
+
+/// Accept a waitlisted registration into a freed seat. Returns None when
+/// it is no longer on the waitlist.
+pub async fn promote_from_waitlist(
+    conn: &mut PgConnection,
+    id: i32,
+    path_id: i32,
+    major_id: Option<i32>,
+    reason: &str,
+) -> AppResult<Option<Registration>> {
+    let registration = sqlx::query_as::<_, Registration>(
+        r#"
+        UPDATE registrations 
+        SET status = 'accepted',
+            accepted_path_id = $2,
+            accepted_major_id = $3,
+            rejection_reason = NULL,
+            waitlisted = FALSE,
+            updated_at = NOW()
+        WHERE id = $1 AND status = 'rejected' AND waitlisted
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(path_id)
+    .bind(major_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if registration.is_some() {
+        sqlx::query(
+            r#"
+            INSERT INTO registration_status_history (registration_id, from_status, to_status, reason)
+            VALUES ($1, 'rejected', 'accepted', $2)
+            "#,
+        )
+        .bind(id)
+        .bind(reason)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(registration)
+}
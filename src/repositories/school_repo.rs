@@ -99,11 +99,13 @@ impl SchoolRepository {
         address: &str,
         phone: Option<&str>,
         email: Option<&str>,
+        region_id: Option<i32>,
+        timezone: &str,
     ) -> AppResult<School> {
         let school = sqlx::query_as::<_, School>(
             r#"
-            INSERT INTO schools (name, npsn, code, address, phone, email)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO schools (name, npsn, code, address, phone, email, region_id, timezone)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING *
             "#,
         )
@@ -113,12 +115,17 @@ impl SchoolRepository {
         .bind(address)
         .bind(phone)
         .bind(email)
+        .bind(region_id)
+        .bind(timezone)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| match e {
             sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
                 AppError::Conflict("School with this NPSN or code already exists".to_string())
             }
+            sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                AppError::Validation("Region not found".to_string())
+            }
             _ => AppError::Database(e),
         })?;
 
@@ -132,7 +139,11 @@ impl SchoolRepository {
         address: Option<&str>,
         phone: Option<&str>,
         email: Option<&str>,
+        region_id: Option<i32>,
+        timezone: Option<&str>,
     ) -> AppResult<School> {
+        let mut tx = self.pool.begin().await?;
+
         let school = sqlx::query_as::<_, School>(
             r#"
             UPDATE schools
@@ -140,6 +151,8 @@ impl SchoolRepository {
                 address = COALESCE($3, address),
                 phone = COALESCE($4, phone),
                 email = COALESCE($5, email),
+                region_id = COALESCE($6, region_id),
+                timezone = COALESCE($7, timezone),
                 updated_at = CURRENT_TIMESTAMP
             WHERE id = $1
             RETURNING *
@@ -150,8 +163,51 @@ impl SchoolRepository {
         .bind(address)
         .bind(phone)
         .bind(email)
-        .fetch_one(&self.pool)
-        .await?;
+        .bind(region_id)
+        .bind(timezone)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                AppError::Validation("Region not found".to_string())
+            }
+            _ => AppError::Database(e),
+        })?;
+
+        // Deadlines of open periods keep their local dates and times
+        if timezone.is_some() {
+            sqlx::query(
+                r#"
+                UPDATE periods
+                SET registration_opens_at = registration_start::TIMESTAMP AT TIME ZONE $2,
+                    registration_closes_at = (registration_end + closing_time) AT TIME ZONE $2,
+                    reenrollment_closes_at = (reenrollment_deadline + closing_time) AT TIME ZONE $2,
+                    updated_at = NOW()
+                WHERE school_id = $1 AND status IN ('draft', 'active')
+                "#,
+            )
+            .bind(id)
+            .bind(&school.timezone)
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query(
+                r#"
+                UPDATE period_stages st
+                SET registration_opens_at = st.registration_start::TIMESTAMP AT TIME ZONE $2,
+                    registration_closes_at = (st.registration_end + p.closing_time) AT TIME ZONE $2,
+                    updated_at = NOW()
+                FROM periods p
+                WHERE p.id = st.period_id AND p.school_id = $1 AND p.status IN ('draft', 'active')
+                "#,
+            )
+            .bind(id)
+            .bind(&school.timezone)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
 
         Ok(school)
     }
@@ -0,0 +1,320 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::models::test_session::{
+    TestBooking, TestBookingDetail, TestResultInput, TestSession, TestSessionWithBookings,
+};
+use crate::utils::error::AppResult;
+
+pub struct TestRepository {
+    pool: PgPool,
+}
+
+impl TestRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // Session methods
+
+    pub async fn create_session(
+        &self,
+        period_id: i32,
+        path_id: Option<i32>,
+        test_type: &str,
+        name: &str,
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+        room: &str,
+        capacity: i32,
+    ) -> AppResult<TestSession> {
+        let session = sqlx::query_as::<_, TestSession>(
+            r#"
+            INSERT INTO test_sessions (period_id, path_id, test_type, name, starts_at, ends_at, room, capacity)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(period_id)
+        .bind(path_id)
+        .bind(test_type)
+        .bind(name)
+        .bind(starts_at)
+        .bind(ends_at)
+        .bind(room)
+        .bind(capacity)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    /// Sessions of a period with their booked seats. With a path, sessions
+    /// open to every path are included.
+    pub async fn find_sessions_by_period(
+        &self,
+        period_id: i32,
+        path_id: Option<i32>,
+        test_type: Option<&str>,
+    ) -> AppResult<Vec<TestSessionWithBookings>> {
+        let sessions = sqlx::query_as::<_, TestSessionWithBookings>(
+            r#"
+            SELECT s.*, (SELECT COUNT(*) FROM test_bookings b WHERE b.session_id = s.id) AS booked
+            FROM test_sessions s
+            WHERE s.period_id = $1
+              AND ($2::INTEGER IS NULL OR s.path_id IS NULL OR s.path_id = $2)
+              AND ($3::VARCHAR IS NULL OR s.test_type = $3)
+            ORDER BY s.starts_at, s.id
+            "#,
+        )
+        .bind(period_id)
+        .bind(path_id)
+        .bind(test_type)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sessions)
+    }
+
+    pub async fn find_session_by_id(&self, id: i32) -> AppResult<Option<TestSessionWithBookings>> {
+        let session = sqlx::query_as::<_, TestSessionWithBookings>(
+            r#"
+            SELECT s.*, (SELECT COUNT(*) FROM test_bookings b WHERE b.session_id = s.id) AS booked
+            FROM test_sessions s
+            WHERE s.id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    pub async fn update_session(
+        &self,
+        id: i32,
+        name: Option<&str>,
+        starts_at: Option<DateTime<Utc>>,
+        ends_at: Option<DateTime<Utc>>,
+        room: Option<&str>,
+        capacity: Option<i32>,
+    ) -> AppResult<TestSession> {
+        let session = sqlx::query_as::<_, TestSession>(
+            r#"
+            UPDATE test_sessions
+            SET name = COALESCE($2, name),
+                starts_at = COALESCE($3, starts_at),
+                ends_at = COALESCE($4, ends_at),
+                room = COALESCE($5, room),
+                capacity = COALESCE($6, capacity),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(name)
+        .bind(starts_at)
+        .bind(ends_at)
+        .bind(room)
+        .bind(capacity)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(session)
+    }
+
+    pub async fn delete_session(&self, id: i32) -> AppResult<()> {
+        sqlx::query("DELETE FROM test_sessions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // Booking methods
+
+    /// Book the next free seat of a session, replacing the registration's
+    /// booking of the same test type. The session row is locked so concurrent
+    /// bookings cannot exceed the capacity. Returns None when the session is full.
+    pub async fn book(
+        &self,
+        session_id: i32,
+        registration_id: i32,
+    ) -> AppResult<Option<TestBooking>> {
+        let mut tx = self.pool.begin().await?;
+
+        let (capacity, test_type): (i32, String) = sqlx::query_as(
+            "SELECT capacity, test_type FROM test_sessions WHERE id = $1 FOR UPDATE",
+        )
+        .bind(session_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM test_bookings WHERE registration_id = $1 AND test_type = $2")
+            .bind(registration_id)
+            .bind(&test_type)
+            .execute(&mut *tx)
+            .await?;
+
+        let booked: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM test_bookings WHERE session_id = $1")
+                .bind(session_id)
+                .fetch_one(&mut *tx)
+                .await?;
+
+        if booked >= capacity as i64 {
+            return Ok(None);
+        }
+
+        let booking = sqlx::query_as::<_, TestBooking>(
+            r#"
+            INSERT INTO test_bookings (session_id, registration_id, test_type, seat_number)
+            SELECT $1, $2, $3, COALESCE(MAX(seat_number), 0) + 1
+            FROM test_bookings WHERE session_id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(session_id)
+        .bind(registration_id)
+        .bind(&test_type)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(booking))
+    }
+
+    pub async fn find_bookings_by_registration(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<TestBooking>> {
+        let bookings = sqlx::query_as::<_, TestBooking>(
+            r#"
+            SELECT b.* FROM test_bookings b
+            JOIN test_sessions s ON s.id = b.session_id
+            WHERE b.registration_id = $1
+            ORDER BY s.starts_at
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(bookings)
+    }
+
+    pub async fn find_bookings_by_session(
+        &self,
+        session_id: i32,
+    ) -> AppResult<Vec<TestBookingDetail>> {
+        let bookings = sqlx::query_as::<_, TestBookingDetail>(
+            r#"
+            SELECT b.*, r.registration_number, r.student_name, r.student_nisn
+            FROM test_bookings b
+            JOIN registrations r ON r.id = b.registration_id
+            WHERE b.session_id = $1
+            ORDER BY b.seat_number
+            "#,
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(bookings)
+    }
+
+    pub async fn delete_booking(&self, session_id: i32, registration_id: i32) -> AppResult<bool> {
+        let result =
+            sqlx::query("DELETE FROM test_bookings WHERE session_id = $1 AND registration_id = $2")
+                .bind(session_id)
+                .bind(registration_id)
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Registrations of a period still waiting for a seat of the given test
+    /// type, in order of registration
+    pub async fn find_unbooked_registrations(
+        &self,
+        period_id: i32,
+        path_id: Option<i32>,
+        test_type: &str,
+    ) -> AppResult<Vec<(i32, i32)>> {
+        let registrations = sqlx::query_as::<_, (i32, i32)>(
+            r#"
+            SELECT r.id, r.path_id FROM registrations r
+            WHERE r.period_id = $1
+              AND r.status IN ('submitted', 'verified')
+              AND ($2::INTEGER IS NULL OR r.path_id = $2)
+              AND NOT EXISTS (
+                  SELECT 1 FROM test_bookings b
+                  WHERE b.registration_id = r.id AND b.test_type = $3
+              )
+            ORDER BY r.created_at, r.id
+            "#,
+        )
+        .bind(period_id)
+        .bind(path_id)
+        .bind(test_type)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(registrations)
+    }
+
+    /// Store attendance and scores of a session and refresh the test score of
+    /// the affected registrations in one transaction
+    pub async fn record_results(
+        &self,
+        session_id: i32,
+        results: &[TestResultInput],
+        recorded_by: i32,
+    ) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for result in results {
+            sqlx::query(
+                r#"
+                UPDATE test_bookings
+                SET attended = $3, score = $4, notes = $5, recorded_by = $6, recorded_at = NOW(), updated_at = NOW()
+                WHERE session_id = $1 AND registration_id = $2
+                "#,
+            )
+            .bind(session_id)
+            .bind(result.registration_id)
+            .bind(result.attended)
+            .bind(result.score)
+            .bind(&result.notes)
+            .bind(recorded_by)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let registration_ids: Vec<i32> = results.iter().map(|r| r.registration_id).collect();
+
+        sqlx::query(
+            r#"
+            UPDATE registrations r
+            SET test_score = (
+                    SELECT AVG(b.score) FROM test_bookings b
+                    WHERE b.registration_id = r.id AND b.attended AND b.score IS NOT NULL
+                ),
+                updated_at = NOW()
+            WHERE r.id = ANY($1)
+            "#,
+        )
+        .bind(&registration_ids)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
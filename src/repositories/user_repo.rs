@@ -21,11 +21,12 @@ impl UserRepository {
         phone: Option<&str>,
         nik: Option<&str>,
         role: &str,
+        region_id: Option<i32>,
     ) -> AppResult<User> {
         let user = sqlx::query_as::<_, User>(
             r#"
-            INSERT INTO users (school_id, email, password_hash, full_name, phone, nik, role)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            INSERT INTO users (school_id, email, password_hash, full_name, phone, nik, role, region_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING *
             "#,
         )
@@ -36,6 +37,7 @@ impl UserRepository {
         .bind(phone)
         .bind(nik)
         .bind(role)
+        .bind(region_id)
         .persistent(false)  // Disable prepared statement
         .fetch_one(&self.pool)
         .await
@@ -43,6 +45,9 @@ impl UserRepository {
             sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
                 AppError::Conflict("Email already exists".to_string())
             }
+            sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+                AppError::Validation("School or region not found".to_string())
+            }
             _ => AppError::Database(e),
         })?;
 
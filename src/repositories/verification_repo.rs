@@ -0,0 +1,590 @@
+use sqlx::{PgConnection, PgPool};
+
+use crate::models::registration::Registration;
+use crate::models::verification::{BulkVerificationItem, BulkVerificationJob, VerificationLock};
+use crate::utils::error::{AppError, AppResult};
+
+pub struct VerificationRepository {
+    pub pool: PgPool,
+}
+
+impl VerificationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Claim the next submitted registration for a verifier: the claim they
+    /// already hold, else their oldest assigned registration, else the oldest
+    /// unassigned one nobody has claimed. Rows claimed concurrently are skipped.
+    pub async fn claim_next(
+        &self,
+        school_id: i32,
+        verifier_id: i32,
+        period_id: Option<i32>,
+        path_id: Option<i32>,
+        lock_minutes: i64,
+    ) -> AppResult<Option<(Registration, VerificationLock)>> {
+        let mut tx = self.pool.begin().await?;
+
+        expire_locks(&mut tx, None).await?;
+
+        let held = sqlx::query_as::<_, VerificationLock>(
+            r#"
+            SELECT l.* FROM verification_locks l
+            JOIN registrations r ON r.id = l.registration_id
+            WHERE l.verifier_id = $1
+              AND l.released_at IS NULL
+              AND r.school_id = $2
+              AND r.status = 'submitted'
+              AND ($3::INTEGER IS NULL OR r.period_id = $3)
+              AND ($4::INTEGER IS NULL OR r.path_id = $4)
+            ORDER BY l.locked_at
+            LIMIT 1
+            "#,
+        )
+        .bind(verifier_id)
+        .bind(school_id)
+        .bind(period_id)
+        .bind(path_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let registration_id = match held {
+            Some(lock) => lock.registration_id,
+            None => {
+                let next: Option<i32> = sqlx::query_scalar(
+                    r#"
+                    SELECT r.id FROM registrations r
+                    WHERE r.school_id = $1
+                      AND r.status = 'submitted'
+                      AND ($3::INTEGER IS NULL OR r.period_id = $3)
+                      AND ($4::INTEGER IS NULL OR r.path_id = $4)
+                      AND (r.assigned_verifier_id IS NULL OR r.assigned_verifier_id = $2)
+                      AND NOT EXISTS (
+                          SELECT 1 FROM verification_locks l
+                          WHERE l.registration_id = r.id AND l.released_at IS NULL
+                      )
+                    ORDER BY r.assigned_verifier_id IS NULL, r.submitted_at NULLS LAST, r.id
+                    LIMIT 1
+                    FOR UPDATE SKIP LOCKED
+                    "#,
+                )
+                .bind(school_id)
+                .bind(verifier_id)
+                .bind(period_id)
+                .bind(path_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+                match next {
+                    Some(id) => id,
+                    None => return Ok(None),
+                }
+            }
+        };
+
+        let lock = lock_registration(&mut tx, registration_id, verifier_id, lock_minutes).await?;
+
+        let registration =
+            sqlx::query_as::<_, Registration>("SELECT * FROM registrations WHERE id = $1")
+                .bind(registration_id)
+                .fetch_one(&mut *tx)
+                .await?;
+
+        tx.commit().await?;
+
+        Ok(Some((registration, lock)))
+    }
+
+    /// Claim one registration, or extend the verifier's own claim on it
+    pub async fn claim(
+        &self,
+        registration_id: i32,
+        verifier_id: i32,
+        lock_minutes: i64,
+    ) -> AppResult<VerificationLock> {
+        let mut tx = self.pool.begin().await?;
+
+        expire_locks(&mut tx, Some(registration_id)).await?;
+        let lock = lock_registration(&mut tx, registration_id, verifier_id, lock_minutes).await?;
+
+        tx.commit().await?;
+
+        Ok(lock)
+    }
+
+    /// Unreleased, unexpired claim on a registration
+    pub async fn find_active_lock(&self, registration_id: i32) -> AppResult<Option<VerificationLock>> {
+        let lock = sqlx::query_as::<_, VerificationLock>(
+            r#"
+            SELECT * FROM verification_locks
+            WHERE registration_id = $1 AND released_at IS NULL AND expires_at > NOW()
+            "#,
+        )
+        .bind(registration_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(lock)
+    }
+
+    /// Active claims on the given registrations
+    pub async fn find_active_locks(&self, registration_ids: &[i32]) -> AppResult<Vec<VerificationLock>> {
+        let locks = sqlx::query_as::<_, VerificationLock>(
+            r#"
+            SELECT * FROM verification_locks
+            WHERE registration_id = ANY($1) AND released_at IS NULL AND expires_at > NOW()
+            "#,
+        )
+        .bind(registration_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(locks)
+    }
+
+    /// Release the claim on a registration. Without a verifier any holder's
+    /// claim is released.
+    pub async fn release(
+        &self,
+        registration_id: i32,
+        verifier_id: Option<i32>,
+        reason: &str,
+    ) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE verification_locks
+            SET released_at = NOW(), release_reason = $3
+            WHERE registration_id = $1
+              AND released_at IS NULL
+              AND ($2::INTEGER IS NULL OR verifier_id = $2)
+            "#,
+        )
+        .bind(registration_id)
+        .bind(verifier_id)
+        .bind(reason)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Close the verifier's claim as completed. A decision made without a
+    /// claim is recorded as an instant one so it still counts as throughput.
+    pub async fn complete(&self, registration_id: i32, verifier_id: i32) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let completed = sqlx::query(
+            r#"
+            UPDATE verification_locks
+            SET released_at = NOW(), release_reason = 'completed'
+            WHERE registration_id = $1 AND verifier_id = $2 AND released_at IS NULL
+            "#,
+        )
+        .bind(registration_id)
+        .bind(verifier_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if completed.rows_affected() == 0 {
+            sqlx::query(
+                r#"
+                INSERT INTO verification_locks (registration_id, verifier_id, expires_at, released_at, release_reason)
+                VALUES ($1, $2, NOW(), NOW(), 'completed')
+                "#,
+            )
+            .bind(registration_id)
+            .bind(verifier_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // Another verifier's claim on a decided registration is moot
+        sqlx::query(
+            r#"
+            UPDATE verification_locks
+            SET released_at = NOW(), release_reason = 'released'
+            WHERE registration_id = $1 AND released_at IS NULL
+            "#,
+        )
+        .bind(registration_id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Release every claim whose time ran out
+    pub async fn release_expired(&self) -> AppResult<u64> {
+        let mut conn = self.pool.acquire().await?;
+
+        expire_locks(&mut conn, None).await
+    }
+
+    /// Submitted registrations of a period without a verifier, oldest first
+    pub async fn find_unassigned(
+        &self,
+        school_id: i32,
+        period_id: i32,
+        path_id: Option<i32>,
+    ) -> AppResult<Vec<i32>> {
+        let ids = sqlx::query_scalar(
+            r#"
+            SELECT id FROM registrations
+            WHERE school_id = $1
+              AND period_id = $2
+              AND ($3::INTEGER IS NULL OR path_id = $3)
+              AND status = 'submitted'
+              AND assigned_verifier_id IS NULL
+            ORDER BY submitted_at NULLS LAST, id
+            "#,
+        )
+        .bind(school_id)
+        .bind(period_id)
+        .bind(path_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// The given users that are admins of the school
+    pub async fn find_school_verifiers(
+        &self,
+        school_id: i32,
+        user_ids: &[i32],
+    ) -> AppResult<Vec<i32>> {
+        let ids = sqlx::query_scalar(
+            r#"
+            SELECT id FROM users
+            WHERE id = ANY($1) AND school_id = $2 AND role = 'school_admin'
+            "#,
+        )
+        .bind(user_ids)
+        .bind(school_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Assign registrations to verifiers, given as (registration_id, verifier_id)
+    pub async fn assign(&self, assignments: &[(i32, i32)]) -> AppResult<u64> {
+        let (registration_ids, verifier_ids): (Vec<i32>, Vec<i32>) =
+            assignments.iter().copied().unzip();
+
+        let result = sqlx::query(
+            r#"
+            UPDATE registrations r
+            SET assigned_verifier_id = a.verifier_id, assigned_at = NOW(), updated_at = NOW()
+            FROM UNNEST($1::INTEGER[], $2::INTEGER[]) AS a(registration_id, verifier_id)
+            WHERE r.id = a.registration_id AND r.status = 'submitted'
+            "#,
+        )
+        .bind(&registration_ids)
+        .bind(&verifier_ids)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // Bulk verification methods
+    /// Submitted registrations of a period, oldest first
+    pub async fn find_submitted_registration_ids(
+        &self,
+        school_id: i32,
+        period_id: i32,
+        path_id: Option<i32>,
+    ) -> AppResult<Vec<i32>> {
+        let ids = sqlx::query_scalar(
+            r#"
+            SELECT id FROM registrations
+            WHERE school_id = $1
+              AND period_id = $2
+              AND ($3::INTEGER IS NULL OR path_id = $3)
+              AND status = 'submitted'
+            ORDER BY submitted_at NULLS LAST, id
+            "#,
+        )
+        .bind(school_id)
+        .bind(period_id)
+        .bind(path_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Pending documents of a period's submitted registrations
+    pub async fn find_pending_document_ids(
+        &self,
+        school_id: i32,
+        period_id: i32,
+        path_id: Option<i32>,
+        document_type: Option<&str>,
+    ) -> AppResult<Vec<i32>> {
+        let ids = sqlx::query_scalar(
+            r#"
+            SELECT d.id FROM documents d
+            JOIN registrations r ON r.id = d.registration_id
+            WHERE r.school_id = $1
+              AND r.period_id = $2
+              AND ($3::INTEGER IS NULL OR r.path_id = $3)
+              AND ($4::VARCHAR IS NULL OR d.document_type = $4)
+              AND r.status = 'submitted'
+              AND d.verification_status = 'pending'
+            ORDER BY r.submitted_at NULLS LAST, d.registration_id, d.id
+            "#,
+        )
+        .bind(school_id)
+        .bind(period_id)
+        .bind(path_id)
+        .bind(document_type)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Create a bulk job with a pending item per registration or document
+    pub async fn create_bulk_job(
+        &self,
+        school_id: i32,
+        requested_by: i32,
+        target: &str,
+        action: &str,
+        notes: Option<&str>,
+        entity_ids: &[i32],
+    ) -> AppResult<BulkVerificationJob> {
+        let mut tx = self.pool.begin().await?;
+
+        let job = sqlx::query_as::<_, BulkVerificationJob>(
+            r#"
+            INSERT INTO bulk_verification_jobs (school_id, requested_by, target, action, notes, total)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#,
+        )
+        .bind(school_id)
+        .bind(requested_by)
+        .bind(target)
+        .bind(action)
+        .bind(notes)
+        .bind(entity_ids.len() as i32)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO bulk_verification_items (job_id, entity_id)
+            SELECT $1, entity_id FROM UNNEST($2::INTEGER[]) WITH ORDINALITY AS t(entity_id, position)
+            ORDER BY position
+            "#,
+        )
+        .bind(job.id)
+        .bind(entity_ids)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(job)
+    }
+
+    pub async fn find_bulk_job(&self, id: i32) -> AppResult<Option<BulkVerificationJob>> {
+        let job = sqlx::query_as::<_, BulkVerificationJob>(
+            "SELECT * FROM bulk_verification_jobs WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    pub async fn find_bulk_items(&self, job_id: i32) -> AppResult<Vec<BulkVerificationItem>> {
+        let items = sqlx::query_as::<_, BulkVerificationItem>(
+            "SELECT * FROM bulk_verification_items WHERE job_id = $1 ORDER BY id",
+        )
+        .bind(job_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(items)
+    }
+
+    /// Lease a pending job, or a running one whose worker's lease ran out,
+    /// and mark it running. Returns None when another worker holds it.
+    pub async fn claim_bulk_job(
+        &self,
+        id: i32,
+        lease_minutes: i32,
+    ) -> AppResult<Option<BulkVerificationJob>> {
+        let job = sqlx::query_as::<_, BulkVerificationJob>(
+            r#"
+            UPDATE bulk_verification_jobs
+            SET status = 'running',
+                started_at = COALESCE(started_at, NOW()),
+                lease_expires_at = NOW() + make_interval(mins => $2)
+            WHERE id = $1
+              AND (status = 'pending'
+                   OR (status = 'running'
+                       AND (lease_expires_at IS NULL OR lease_expires_at <= NOW())))
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(lease_minutes)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    /// Jobs no worker is running: left pending for longer than the lease, or
+    /// running with an expired lease
+    pub async fn find_abandoned_bulk_jobs(&self, lease_minutes: i32) -> AppResult<Vec<i32>> {
+        let ids = sqlx::query_scalar(
+            r#"
+            SELECT id FROM bulk_verification_jobs
+            WHERE (status = 'pending' AND created_at < NOW() - make_interval(mins => $1))
+               OR (status = 'running'
+                   AND (lease_expires_at IS NULL OR lease_expires_at <= NOW()))
+            ORDER BY created_at
+            "#,
+        )
+        .bind(lease_minutes)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(ids)
+    }
+
+    /// Record the outcome of one item, count it on its job and extend the
+    /// job's lease
+    pub async fn finish_bulk_item(
+        &self,
+        item_id: i32,
+        error: Option<&str>,
+        lease_minutes: i32,
+    ) -> AppResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let job_id: i32 = sqlx::query_scalar(
+            r#"
+            UPDATE bulk_verification_items
+            SET status = CASE WHEN $2::TEXT IS NULL THEN 'succeeded' ELSE 'failed' END,
+                error = $2,
+                processed_at = NOW()
+            WHERE id = $1
+            RETURNING job_id
+            "#,
+        )
+        .bind(item_id)
+        .bind(error)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE bulk_verification_jobs
+            SET succeeded = succeeded + CASE WHEN $2 THEN 0 ELSE 1 END,
+                failed = failed + CASE WHEN $2 THEN 1 ELSE 0 END,
+                lease_expires_at = NOW() + make_interval(mins => $3)
+            WHERE id = $1
+            "#,
+        )
+        .bind(job_id)
+        .bind(error.is_some())
+        .bind(lease_minutes)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn finish_bulk_job(&self, id: i32, status: &str) -> AppResult<BulkVerificationJob> {
+        let job = sqlx::query_as::<_, BulkVerificationJob>(
+            r#"
+            UPDATE bulk_verification_jobs
+            SET status = $2, finished_at = NOW(), lease_expires_at = NULL
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(id)
+        .bind(status)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+}
+
+/// Mark claims past their expiry as expired, for one registration or all
+async fn expire_locks(conn: &mut PgConnection, registration_id: Option<i32>) -> AppResult<u64> {
+    let result = sqlx::query(
+        r#"
+        UPDATE verification_locks
+        SET released_at = expires_at, release_reason = 'expired'
+        WHERE released_at IS NULL
+          AND expires_at <= NOW()
+          AND ($1::INTEGER IS NULL OR registration_id = $1)
+        "#,
+    )
+    .bind(registration_id)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Lock a registration for the verifier, extending their own unexpired claim
+async fn lock_registration(
+    conn: &mut PgConnection,
+    registration_id: i32,
+    verifier_id: i32,
+    lock_minutes: i64,
+) -> AppResult<VerificationLock> {
+    let extended = sqlx::query_as::<_, VerificationLock>(
+        r#"
+        UPDATE verification_locks
+        SET expires_at = NOW() + make_interval(mins => $3::INTEGER)
+        WHERE registration_id = $1 AND verifier_id = $2 AND released_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(registration_id)
+    .bind(verifier_id)
+    .bind(lock_minutes)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if let Some(lock) = extended {
+        return Ok(lock);
+    }
+
+    sqlx::query_as::<_, VerificationLock>(
+        r#"
+        INSERT INTO verification_locks (registration_id, verifier_id, expires_at)
+        VALUES ($1, $2, NOW() + make_interval(mins => $3::INTEGER))
+        RETURNING *
+        "#,
+    )
+    .bind(registration_id)
+    .bind(verifier_id)
+    .bind(lock_minutes)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => AppError::Conflict(
+            "Registration is being verified by another verifier".to_string(),
+        ),
+        _ => AppError::Database(e),
+    })
+}
@@ -0,0 +1,280 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::period::{Period, PeriodStage};
+use crate::repositories::appeal_repo::AppealRepository;
+use crate::repositories::audit_repo::AuditRepository;
+use crate::repositories::document_repo::DocumentRepository;
+use crate::repositories::duplicate_repo::DuplicateRepository;
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::repositories::verification_repo::VerificationRepository;
+use crate::services::announcement_service::AnnouncementService;
+use crate::services::document_service::DocumentService;
+use crate::services::period_service::PeriodService;
+use crate::services::verification_service::VerificationService;
+use crate::utils::error::AppResult;
+use crate::utils::scanner::scanner_from_config;
+use crate::utils::storage::Storage;
+use crate::AppState;
+
+/// Step of the period lifecycle that is due
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleAction {
+    Activate,
+    /// Announce the period, or the given stage of it
+    Announce { stage_id: Option<i32> },
+}
+
+/// Run the period lifecycle in the background. Submissions close by
+/// themselves at the end of the registration window.
+pub fn spawn(state: AppState) -> tokio::task::JoinHandle<()> {
+    let interval_secs = state.config.scheduler_interval_secs.max(1);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_secs));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = run_once(&state, Utc::now()).await {
+                tracing::error!("Period lifecycle run failed: {}", e);
+            }
+        }
+    })
+}
+
+/// Apply every lifecycle step that is due at `now`
+pub async fn run_once(state: &AppState, now: DateTime<Utc>) -> AppResult<()> {
+    let period_repo = PeriodRepository::new(state.db.clone());
+    let periods = period_repo.find_auto_lifecycle_periods().await?;
+
+    for period in periods {
+        let has_paths = !period_repo
+            .find_paths_by_period(period.id)
+            .await?
+            .is_empty();
+
+        let stages = period_repo.find_stages_by_period(period.id).await?;
+
+        match lifecycle_action(&period, &stages, has_paths, now) {
+            Some(LifecycleAction::Activate) => {
+                let period_service = PeriodService::new(PeriodRepository::new(state.db.clone()));
+                match period_service.activate_period(period.id).await {
+                    Ok(_) => tracing::info!("Period {} activated by the scheduler", period.id),
+                    Err(e) => tracing::warn!("Could not activate period {}: {}", period.id, e),
+                }
+            }
+            Some(LifecycleAction::Announce { stage_id }) => {
+                let announcement_service = AnnouncementService::new(
+                    RegistrationRepository::new(state.db.clone()),
+                    PeriodRepository::new(state.db.clone()),
+                );
+                if let Err(e) = announcement_service
+                    .announce_results(period.id, stage_id, None)
+                    .await
+                {
+                    tracing::warn!("Could not announce results of period {}: {}", period.id, e);
+                }
+            }
+            None => {}
+        }
+    }
+
+    // Hand verification claims whose time ran out back to the queue
+    let released = VerificationRepository::new(state.db.clone())
+        .release_expired()
+        .await?;
+    if released > 0 {
+        tracing::info!("Released {} expired verification claims", released);
+    }
+
+    // Resume bulk verification jobs whose worker stopped
+    let verification_service = VerificationService::new(
+        RegistrationRepository::new(state.db.clone()),
+        PeriodRepository::new(state.db.clone()),
+        VerificationRepository::new(state.db.clone()),
+        AuditRepository::new(state.db.clone()),
+    );
+    let resumed = verification_service.resume_abandoned_bulk_jobs().await?;
+    if resumed > 0 {
+        tracing::info!("Resuming {} abandoned bulk verification jobs", resumed);
+    }
+
+    // Scan and process documents and appeal evidence whose background run
+    // never finished
+    let document_service = DocumentService::new(
+        DocumentRepository::new(state.db.clone()),
+        AuditRepository::new(state.db.clone()),
+        DuplicateRepository::new(state.db.clone()),
+        AppealRepository::new(state.db.clone()),
+        Storage::new(&state.config),
+        scanner_from_config(&state.config),
+        state.config.recompress_oversized_photos,
+    );
+    let processed = document_service.process_stale().await?;
+    if processed > 0 {
+        tracing::info!("Processed {} pending documents", processed);
+    }
+
+    Ok(())
+}
+
+/// A draft period with paths opens when its registration window starts; an
+/// active period announces its results once the announcement time passed,
+/// stage by stage when it has stages. Both compare instants, so the school's
+/// timezone is already accounted for.
+pub fn lifecycle_action(
+    period: &Period,
+    stages: &[PeriodStage],
+    has_paths: bool,
+    now: DateTime<Utc>,
+) -> Option<LifecycleAction> {
+    if !period.auto_lifecycle {
+        return None;
+    }
+
+    match period.status.as_str() {
+        "draft"
+            if has_paths
+                && period.registration_opens_at <= now
+                && now <= period.registration_closes_at =>
+        {
+            Some(LifecycleAction::Activate)
+        }
+        "active" if !stages.is_empty() => stages
+            .iter()
+            .filter(|s| s.announcement_date.is_none())
+            .min_by_key(|s| s.stage_number)
+            .filter(|s| s.announcement_at.is_some_and(|at| at <= now))
+            .map(|s| LifecycleAction::Announce {
+                stage_id: Some(s.id),
+            }),
+        "active"
+            if period.announcement_date.is_none()
+                && period.announcement_at.is_some_and(|at| at <= now) =>
+        {
+            Some(LifecycleAction::Announce { stage_id: None })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn period(status: &str) -> Period {
+        Period {
+            status: status.to_string(),
+            auto_lifecycle: true,
+            announcement_at: Some("2024-07-05T01:00:00Z".parse().unwrap()),
+            registration_opens_at: "2024-05-31T16:00:00Z".parse().unwrap(),
+            registration_closes_at: "2024-06-30T15:59:59Z".parse().unwrap(),
+            ..Default::default()
+        }
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_draft_period_activates_within_window() {
+        let draft = period("draft");
+
+        // Window in WITA: opens 1 June 00:00, closes 30 June 23:59:59
+        assert_eq!(
+            lifecycle_action(&draft, &[], true, at("2024-05-31T16:00:00Z")),
+            Some(LifecycleAction::Activate)
+        );
+        assert_eq!(
+            lifecycle_action(&draft, &[], false, at("2024-05-31T16:00:00Z")),
+            None
+        );
+        assert_eq!(
+            lifecycle_action(&draft, &[], true, at("2024-05-31T15:59:59Z")),
+            None
+        );
+        assert_eq!(
+            lifecycle_action(&draft, &[], true, at("2024-06-30T16:00:00Z")),
+            None
+        );
+
+        let manual = Period {
+            auto_lifecycle: false,
+            ..draft
+        };
+        assert_eq!(
+            lifecycle_action(&manual, &[], true, at("2024-06-10T00:00:00Z")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_active_period_announces_once() {
+        let active = period("active");
+
+        assert_eq!(
+            lifecycle_action(&active, &[], true, at("2024-07-05T00:59:59Z")),
+            None
+        );
+        assert_eq!(
+            lifecycle_action(&active, &[], true, at("2024-07-05T01:00:00Z")),
+            Some(LifecycleAction::Announce { stage_id: None })
+        );
+
+        let announced = Period {
+            announcement_date: NaiveDate::from_ymd_opt(2024, 7, 5),
+            ..active
+        };
+        assert_eq!(
+            lifecycle_action(&announced, &[], true, at("2024-07-06T00:00:00Z")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stages_announce_in_order() {
+        let active = period("active");
+        let stage = |id: i32, stage_number: i32, announcement_at: &str, announced: bool| PeriodStage {
+            id,
+            period_id: active.id,
+            stage_number,
+            name: format!("Tahap {}", stage_number),
+            registration_start: active.registration_start,
+            registration_end: active.registration_end,
+            registration_opens_at: active.registration_opens_at,
+            registration_closes_at: active.registration_closes_at,
+            announcement_at: Some(at(announcement_at)),
+            announcement_date: announced.then(|| NaiveDate::from_ymd_opt(2024, 6, 15).unwrap()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let stages = vec![
+            stage(7, 1, "2024-06-15T01:00:00Z", false),
+            stage(8, 2, "2024-07-05T01:00:00Z", false),
+        ];
+        assert_eq!(
+            lifecycle_action(&active, &stages, true, at("2024-06-15T01:00:00Z")),
+            Some(LifecycleAction::Announce { stage_id: Some(7) })
+        );
+
+        let stages = vec![
+            stage(7, 1, "2024-06-15T01:00:00Z", true),
+            stage(8, 2, "2024-07-05T01:00:00Z", false),
+        ];
+        assert_eq!(
+            lifecycle_action(&active, &stages, true, at("2024-06-20T00:00:00Z")),
+            None
+        );
+        assert_eq!(
+            lifecycle_action(&active, &stages, true, at("2024-07-05T01:00:00Z")),
+            Some(LifecycleAction::Announce { stage_id: Some(8) })
+        );
+    }
+}
@@ -0,0 +1,529 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Datelike, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::models::achievement::{
+    AchievementLevel, AchievementPointTable, NewAchievement, Participation, RegistrationAchievement,
+};
+use crate::models::registration::{Registration, VerificationStatus};
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::utils::error::{AppError, AppResult, FieldError};
+
+/// Oldest competition year accepted on the form
+const MIN_ACHIEVEMENT_YEAR: i32 = 2000;
+
+pub struct AchievementService {
+    registration_repo: RegistrationRepository,
+    period_repo: PeriodRepository,
+}
+
+impl AchievementService {
+    pub fn new(registration_repo: RegistrationRepository, period_repo: PeriodRepository) -> Self {
+        Self {
+            registration_repo,
+            period_repo,
+        }
+    }
+
+    async fn get_registration(&self, id: i32) -> AppResult<Registration> {
+        self.registration_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))
+    }
+
+    async fn point_table(&self, registration: &Registration) -> AppResult<AchievementPointTable> {
+        let period = self
+            .period_repo
+            .find_by_id(registration.period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+
+        AchievementPointTable::from_config(period.achievement_point_table.as_ref())
+            .map_err(AppError::Internal)
+    }
+
+    pub async fn get_summary(&self, registration_id: i32) -> AppResult<AchievementSummary> {
+        let registration = self.get_registration(registration_id).await?;
+        let table = self.point_table(&registration).await?;
+        let achievements = self
+            .registration_repo
+            .find_achievements_by_registration(registration_id)
+            .await?;
+
+        Ok(build_summary(registration_id, &table, achievements))
+    }
+
+    pub async fn save_achievements(
+        &self,
+        registration_id: i32,
+        achievements: Vec<NewAchievement>,
+    ) -> AppResult<AchievementSummary> {
+        let registration = self.get_registration(registration_id).await?;
+
+        if registration.status != "draft" {
+            return Err(AppError::Validation(
+                "Can only update achievements of registrations in draft status".to_string(),
+            ));
+        }
+
+        let mut errors = validate_achievements(&achievements, Utc::now().year());
+
+        // Each certificate must be a sertifikat_prestasi of this registration
+        for (index, achievement) in achievements.iter().enumerate() {
+            let Some(document_id) = achievement.document_id else {
+                continue;
+            };
+
+            let valid = self
+                .registration_repo
+                .find_document_by_id(document_id)
+                .await?
+                .map(|d| {
+                    d.registration_id == registration_id && d.document_type == "sertifikat_prestasi"
+                })
+                .unwrap_or(false);
+
+            if !valid {
+                errors.push(FieldError::new(
+                    &format!("achievements[{}].document_id", index),
+                    "Must be a sertifikat_prestasi document of this registration",
+                ));
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(AppError::FieldValidation(errors));
+        }
+
+        let table = self.point_table(&registration).await?;
+        let saved = self
+            .registration_repo
+            .replace_achievements(registration_id, &achievements)
+            .await?;
+
+        Ok(build_summary(registration_id, &table, saved))
+    }
+
+    /// Approve or reject one achievement and recompute the points used for selection
+    pub async fn verify_achievement(
+        &self,
+        id: i32,
+        verification_status: String,
+        verification_notes: Option<String>,
+        school_id: Option<i32>,
+        admin_id: i32,
+    ) -> AppResult<AchievementSummary> {
+        match VerificationStatus::from_str(&verification_status) {
+            Some(VerificationStatus::Approved) | Some(VerificationStatus::Rejected) => {}
+            _ => {
+                return Err(AppError::Validation(
+                    "Verification status must be 'approved' or 'rejected'".to_string(),
+                ))
+            }
+        }
+
+        let achievement = self
+            .registration_repo
+            .find_achievement_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Achievement not found".to_string()))?;
+
+        let registration = self.get_registration(achievement.registration_id).await?;
+
+        if let Some(school_id) = school_id {
+            if registration.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have permission to verify this registration".to_string(),
+                ));
+            }
+        }
+
+        if !["submitted", "verified"].contains(&registration.status.as_str()) {
+            return Err(AppError::Validation(
+                "Can only verify achievements of submitted registrations".to_string(),
+            ));
+        }
+
+        self.registration_repo
+            .verify_achievement(
+                id,
+                &verification_status,
+                verification_notes.as_deref(),
+                admin_id,
+            )
+            .await?;
+
+        let table = self.point_table(&registration).await?;
+        let achievements = self
+            .registration_repo
+            .find_achievements_by_registration(registration.id)
+            .await?;
+
+        let approved: Vec<RegistrationAchievement> = achievements
+            .iter()
+            .filter(|a| a.verification_status == VerificationStatus::Approved.as_str())
+            .cloned()
+            .collect();
+        let (verified_points, _) = count_points(&table, &approved);
+
+        self.registration_repo
+            .update_achievement_points(registration.id, Some(verified_points))
+            .await?;
+
+        tracing::info!(
+            "Achievement {} of registration {} marked as {} by admin {}",
+            id,
+            registration.id,
+            verification_status,
+            admin_id
+        );
+
+        Ok(build_summary(registration.id, &table, achievements))
+    }
+}
+
+/// Ringkasan prestasi pendaftaran
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AchievementSummary {
+    /// ID pendaftaran
+    #[schema(example = 1)]
+    pub registration_id: i32,
+
+    /// Poin dari prestasi yang belum ditolak (perkiraan sebelum verifikasi)
+    #[schema(example = 75.0)]
+    pub claimed_points: f64,
+
+    /// Poin dari prestasi yang sudah disetujui, dipakai untuk seleksi
+    #[schema(example = 60.0)]
+    pub verified_points: f64,
+
+    /// Daftar prestasi
+    pub achievements: Vec<AchievementResult>,
+}
+
+/// Prestasi beserta poinnya
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AchievementResult {
+    /// ID prestasi
+    #[schema(example = 1)]
+    pub id: i32,
+
+    /// Nama lomba
+    #[schema(example = "Olimpiade Sains Nasional Matematika")]
+    pub competition_name: String,
+
+    /// Tingkat (school/regency/province/national/international)
+    #[schema(example = "province")]
+    pub level: String,
+
+    /// Peringkat (kosong untuk peserta/finalis)
+    #[schema(example = 1)]
+    pub rank: Option<i32>,
+
+    /// Tahun lomba
+    #[schema(example = 2023)]
+    pub year: i32,
+
+    /// Keikutsertaan (individual/team)
+    #[schema(example = "individual")]
+    pub participation: String,
+
+    /// ID dokumen sertifikat prestasi
+    #[schema(example = 4)]
+    pub document_id: Option<i32>,
+
+    /// Status verifikasi (pending/approved/rejected)
+    #[schema(example = "pending")]
+    pub verification_status: String,
+
+    /// Catatan verifikasi
+    #[schema(example = "Sertifikat sesuai")]
+    pub verification_notes: Option<String>,
+
+    /// ID admin yang memverifikasi
+    #[schema(example = 2)]
+    pub verified_by: Option<i32>,
+
+    /// Waktu verifikasi
+    #[schema(value_type = Option<String>, example = "2024-06-10T08:00:00Z")]
+    pub verified_at: Option<DateTime<Utc>>,
+
+    /// Poin menurut tabel poin periode
+    #[schema(example = 60.0)]
+    pub points: f64,
+
+    /// Poin yang dihitung setelah aturan N terbaik dan batas poin
+    #[schema(example = 60.0)]
+    pub counted_points: f64,
+}
+
+pub fn validate_achievements(
+    achievements: &[NewAchievement],
+    current_year: i32,
+) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    for (index, achievement) in achievements.iter().enumerate() {
+        let field = |name: &str| format!("achievements[{}].{}", index, name);
+
+        let name_length = achievement.competition_name.trim().chars().count();
+        if name_length == 0 || name_length > 255 {
+            errors.push(FieldError::new(
+                &field("competition_name"),
+                "Must be between 1 and 255 characters",
+            ));
+        }
+
+        if AchievementLevel::from_str(&achievement.level).is_none() {
+            errors.push(FieldError::new(
+                &field("level"),
+                "Must be one of school, regency, province, national, international",
+            ));
+        }
+
+        if achievement.rank.map(|rank| rank < 1).unwrap_or(false) {
+            errors.push(FieldError::new(&field("rank"), "Must be at least 1"));
+        }
+
+        if !(MIN_ACHIEVEMENT_YEAR..=current_year).contains(&achievement.year) {
+            errors.push(FieldError::new(
+                &field("year"),
+                format!(
+                    "Must be between {} and {}",
+                    MIN_ACHIEVEMENT_YEAR, current_year
+                ),
+            ));
+        }
+
+        if Participation::from_str(&achievement.participation).is_none() {
+            errors.push(FieldError::new(
+                &field("participation"),
+                "Must be 'individual' or 'team'",
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Total points of the given achievements. Achievements are counted from the
+/// highest scoring down: only the best N count, and the per-level caps and
+/// the overall maximum clip what each one adds. Returns the total and the
+/// points counted per achievement id.
+pub fn count_points(
+    table: &AchievementPointTable,
+    achievements: &[RegistrationAchievement],
+) -> (f64, HashMap<i32, f64>) {
+    let mut scored: Vec<(&RegistrationAchievement, f64)> = achievements
+        .iter()
+        .map(|a| (a, table.points_for(&a.level, a.rank, &a.participation)))
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.id.cmp(&b.0.id)));
+
+    let mut counted = HashMap::new();
+    let mut level_totals: BTreeMap<&str, f64> = BTreeMap::new();
+    let mut total = 0.0;
+
+    for (index, (achievement, points)) in scored.into_iter().enumerate() {
+        let mut points = points;
+
+        if table.best_n.map(|n| index >= n).unwrap_or(false) {
+            points = 0.0;
+        }
+
+        let level_total = level_totals
+            .entry(achievement.level.as_str())
+            .or_insert(0.0);
+        if let Some(cap) = table.level_caps.get(&achievement.level) {
+            points = points.min((cap - *level_total).max(0.0));
+        }
+
+        if let Some(max_points) = table.max_points {
+            points = points.min((max_points - total).max(0.0));
+        }
+
+        *level_total += points;
+        total += points;
+        counted.insert(achievement.id, points);
+    }
+
+    (total, counted)
+}
+
+fn build_summary(
+    registration_id: i32,
+    table: &AchievementPointTable,
+    achievements: Vec<RegistrationAchievement>,
+) -> AchievementSummary {
+    let rejected = VerificationStatus::Rejected.as_str();
+    let approved = VerificationStatus::Approved.as_str();
+
+    let claimed: Vec<RegistrationAchievement> = achievements
+        .iter()
+        .filter(|a| a.verification_status != rejected)
+        .cloned()
+        .collect();
+    let verified: Vec<RegistrationAchievement> = achievements
+        .iter()
+        .filter(|a| a.verification_status == approved)
+        .cloned()
+        .collect();
+
+    let (claimed_points, counted) = count_points(table, &claimed);
+    let (verified_points, _) = count_points(table, &verified);
+
+    let achievements = achievements
+        .into_iter()
+        .map(|a| AchievementResult {
+            points: table.points_for(&a.level, a.rank, &a.participation),
+            counted_points: counted.get(&a.id).copied().unwrap_or(0.0),
+            id: a.id,
+            competition_name: a.competition_name,
+            level: a.level,
+            rank: a.rank,
+            year: a.year,
+            participation: a.participation,
+            document_id: a.document_id,
+            verification_status: a.verification_status,
+            verification_notes: a.verification_notes,
+            verified_by: a.verified_by,
+            verified_at: a.verified_at,
+        })
+        .collect();
+
+    AchievementSummary {
+        registration_id,
+        claimed_points,
+        verified_points,
+        achievements,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn achievement(
+        id: i32,
+        level: &str,
+        rank: Option<i32>,
+        participation: &str,
+    ) -> RegistrationAchievement {
+        RegistrationAchievement {
+            id,
+            registration_id: 1,
+            competition_name: "Lomba".to_string(),
+            level: level.to_string(),
+            rank,
+            year: 2023,
+            participation: participation.to_string(),
+            document_id: None,
+            verification_status: "approved".to_string(),
+            verification_notes: None,
+            verified_by: None,
+            verified_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_points_for_uses_rank_participant_and_team_multiplier() {
+        let table = AchievementPointTable::default();
+
+        assert_eq!(table.points_for("national", Some(1), "individual"), 80.0);
+        assert_eq!(table.points_for("national", Some(7), "individual"), 30.0);
+        assert_eq!(table.points_for("national", None, "individual"), 30.0);
+        assert_eq!(table.points_for("province", Some(1), "team"), 48.0);
+        assert_eq!(table.points_for("unknown", Some(1), "individual"), 0.0);
+    }
+
+    #[test]
+    fn test_count_points_applies_best_n_and_caps() {
+        let table = AchievementPointTable::from_config(Some(&json!({
+            "points": {
+                "school": {"1": 20, "participant": 5},
+                "regency": {"1": 40, "2": 30}
+            },
+            "level_caps": {"school": 25},
+            "best_n": 3,
+            "max_points": 80
+        })))
+        .unwrap();
+
+        let achievements = vec![
+            achievement(1, "school", Some(1), "individual"),
+            achievement(2, "school", Some(1), "individual"),
+            achievement(3, "regency", Some(1), "individual"),
+            achievement(4, "regency", Some(2), "individual"),
+        ];
+
+        let (total, counted) = count_points(&table, &achievements);
+
+        // Regency 40 + 30, then school 20 hits max_points (10 left), 4th exceeds best_n
+        assert_eq!(total, 80.0);
+        assert_eq!(counted[&3], 40.0);
+        assert_eq!(counted[&4], 30.0);
+        assert_eq!(counted[&1], 10.0);
+        assert_eq!(counted[&2], 0.0);
+
+        let schools = vec![
+            achievement(1, "school", Some(1), "individual"),
+            achievement(2, "school", Some(1), "individual"),
+        ];
+        assert_eq!(count_points(&table, &schools).0, 25.0);
+    }
+
+    #[test]
+    fn test_point_table_rejects_invalid_config() {
+        assert!(AchievementPointTable::from_config(None).is_ok());
+        assert!(AchievementPointTable::from_config(Some(
+            &json!({"points": {"galaxy": {"1": 10}}})
+        ))
+        .is_err());
+        assert!(AchievementPointTable::from_config(Some(
+            &json!({"points": {"school": {"0": 10}}})
+        ))
+        .is_err());
+        assert!(AchievementPointTable::from_config(Some(
+            &json!({"points": {}, "team_multiplier": 2})
+        ))
+        .is_err());
+        assert!(
+            AchievementPointTable::from_config(Some(&json!({"points": {}, "bonus": 1}))).is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_achievements() {
+        let achievements = vec![NewAchievement {
+            competition_name: " ".to_string(),
+            level: "city".to_string(),
+            rank: Some(0),
+            year: 2030,
+            participation: "solo".to_string(),
+            document_id: None,
+        }];
+
+        let fields: Vec<String> = validate_achievements(&achievements, 2024)
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+
+        assert_eq!(
+            fields,
+            vec![
+                "achievements[0].competition_name",
+                "achievements[0].level",
+                "achievements[0].rank",
+                "achievements[0].year",
+                "achievements[0].participation",
+            ]
+        );
+    }
+}
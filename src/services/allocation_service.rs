@@ -0,0 +1,941 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use chrono::{DateTime, Utc};
+
+use crate::models::allocation::{
+    AllocationChoice, AllocationResult, AllocationRound, NewAllocationResult, RegistrationOutcome,
+};
+use crate::models::period::{Level, RegistrationPath};
+use crate::models::registration::Registration;
+use crate::repositories::allocation_repo::AllocationRepository;
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::repositories::school_repo::SchoolRepository;
+use crate::services::announcement_service::QUOTA_FULL_REASON;
+use crate::utils::error::{AppError, AppResult};
+
+pub struct AllocationService {
+    allocation_repo: AllocationRepository,
+    registration_repo: RegistrationRepository,
+    period_repo: PeriodRepository,
+    school_repo: SchoolRepository,
+}
+
+impl AllocationService {
+    pub fn new(
+        allocation_repo: AllocationRepository,
+        registration_repo: RegistrationRepository,
+        period_repo: PeriodRepository,
+        school_repo: SchoolRepository,
+    ) -> Self {
+        Self {
+            allocation_repo,
+            registration_repo,
+            period_repo,
+            school_repo,
+        }
+    }
+
+    /// Create a new allocation round over the periods of several schools
+    pub async fn create_round(
+        &self,
+        name: String,
+        academic_year: String,
+        level: String,
+        choice_limit: i32,
+        period_ids: Vec<i32>,
+        admin_id: i32,
+    ) -> AppResult<AllocationRound> {
+        let level = Level::from_str(&level)
+            .ok_or_else(|| AppError::Validation("Invalid level. Must be SD, SMP, SMA, or SMK".to_string()))?;
+
+        if choice_limit < 1 {
+            return Err(AppError::Validation(
+                "Choice limit must be at least 1".to_string(),
+            ));
+        }
+
+        self.validate_round_periods(None, &academic_year, level.as_str(), &period_ids)
+            .await?;
+
+        let round = self
+            .allocation_repo
+            .create_round(&name, &academic_year, level.as_str(), choice_limit, admin_id)
+            .await?;
+
+        self.allocation_repo.set_periods(round.id, &period_ids).await?;
+
+        Ok(round)
+    }
+
+    /// Replace the participating periods of a draft round
+    pub async fn update_round_periods(&self, round_id: i32, period_ids: Vec<i32>) -> AppResult<()> {
+        let round = self.get_round(round_id).await?;
+
+        if round.status != "draft" {
+            return Err(AppError::Validation(
+                "Can only change periods of draft rounds".to_string(),
+            ));
+        }
+
+        self.validate_round_periods(Some(round.id), &round.academic_year, &round.level, &period_ids)
+            .await?;
+
+        self.allocation_repo.set_periods(round.id, &period_ids).await
+    }
+
+    pub async fn get_round(&self, round_id: i32) -> AppResult<AllocationRound> {
+        self.allocation_repo
+            .find_by_id(round_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Allocation round not found".to_string()))
+    }
+
+    pub async fn get_round_period_ids(&self, round_id: i32) -> AppResult<Vec<i32>> {
+        self.allocation_repo.find_period_ids(round_id).await
+    }
+
+    pub async fn list_rounds(&self, status: Option<String>) -> AppResult<Vec<AllocationRound>> {
+        self.allocation_repo.find_all(status).await
+    }
+
+    /// Open a draft round so parents can submit their ranked choices
+    pub async fn open_round(&self, round_id: i32) -> AppResult<AllocationRound> {
+        let round = self.get_round(round_id).await?;
+
+        if round.status != "draft" {
+            return Err(AppError::Validation(
+                "Only draft rounds can be opened".to_string(),
+            ));
+        }
+
+        let period_ids = self.allocation_repo.find_period_ids(round_id).await?;
+        if period_ids.len() < 2 {
+            return Err(AppError::Validation(
+                "A round needs at least two participating periods".to_string(),
+            ));
+        }
+
+        self.allocation_repo.update_status(round_id, "open").await
+    }
+
+    /// Close an open round; choices can no longer be changed
+    pub async fn close_round(&self, round_id: i32) -> AppResult<AllocationRound> {
+        let round = self.get_round(round_id).await?;
+
+        if round.status != "open" {
+            return Err(AppError::Validation(
+                "Only open rounds can be closed".to_string(),
+            ));
+        }
+
+        self.allocation_repo.update_status(round_id, "closed").await
+    }
+
+    /// Submit (replace) the ranked school choices of a student
+    /// Each choice is an existing registration of the parent at a different school in the round
+    pub async fn submit_choices(
+        &self,
+        round_id: i32,
+        user_id: i32,
+        student_nisn: String,
+        registration_ids: Vec<i32>,
+    ) -> AppResult<Vec<AllocationChoice>> {
+        let round = self.get_round(round_id).await?;
+
+        if round.status != "open" {
+            return Err(AppError::Validation(
+                "Choices can only be submitted while the round is open".to_string(),
+            ));
+        }
+
+        if registration_ids.is_empty() {
+            return Err(AppError::Validation(
+                "At least one choice is required".to_string(),
+            ));
+        }
+
+        if registration_ids.len() > round.choice_limit as usize {
+            return Err(AppError::Validation(format!(
+                "At most {} choices are allowed in this round",
+                round.choice_limit
+            )));
+        }
+
+        let period_ids: HashSet<i32> = self
+            .allocation_repo
+            .find_period_ids(round_id)
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut seen_registrations = HashSet::new();
+        let mut seen_schools = HashSet::new();
+
+        for registration_id in &registration_ids {
+            if !seen_registrations.insert(*registration_id) {
+                return Err(AppError::Validation(
+                    "Each registration can only be chosen once".to_string(),
+                ));
+            }
+
+            let registration = self
+                .registration_repo
+                .find_by_id(*registration_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Registration {} not found", registration_id)))?;
+
+            if registration.user_id != user_id {
+                return Err(AppError::Forbidden(
+                    "You can only choose your own registrations".to_string(),
+                ));
+            }
+
+            if registration.student_nisn != student_nisn {
+                return Err(AppError::Validation(format!(
+                    "Registration {} belongs to a different student",
+                    registration_id
+                )));
+            }
+
+            if !period_ids.contains(&registration.period_id) {
+                return Err(AppError::Validation(format!(
+                    "Registration {} is not part of this allocation round",
+                    registration_id
+                )));
+            }
+
+            if registration.status == "rejected" || registration.status == "expired" {
+                return Err(AppError::Validation(format!(
+                    "Registration {} is no longer eligible",
+                    registration_id
+                )));
+            }
+
+            if !seen_schools.insert(registration.school_id) {
+                return Err(AppError::Validation(
+                    "Each school can only be chosen once".to_string(),
+                ));
+            }
+        }
+
+        self.allocation_repo
+            .replace_choices(round_id, user_id, &student_nisn, &registration_ids)
+            .await
+    }
+
+    pub async fn get_choices(
+        &self,
+        round_id: i32,
+        student_nisn: &str,
+    ) -> AppResult<Vec<AllocationChoice>> {
+        let _ = self.get_round(round_id).await?;
+
+        self.allocation_repo
+            .find_choices_by_student(round_id, student_nisn)
+            .await
+    }
+
+    /// Run the centralized allocation for a closed round
+    /// Students propose to their choices in preference order (student-proposing deferred
+    /// acceptance); every path keeps its best applicants up to its quota. Each student
+    /// ends up with at most one seat.
+    pub async fn run_allocation(&self, round_id: i32, admin_id: i32) -> AppResult<AllocationSummary> {
+        let round = self.get_round(round_id).await?;
+
+        if round.status != "closed" {
+            return Err(AppError::Validation(
+                "Round must be closed before running allocation".to_string(),
+            ));
+        }
+
+        let period_ids = self.allocation_repo.find_period_ids(round_id).await?;
+
+        // Paths, quotas and display names
+        let mut paths: HashMap<i32, RegistrationPath> = HashMap::new();
+        let mut fallbacks: HashMap<i32, Vec<(i32, f64)>> = HashMap::new();
+        let mut school_names: HashMap<i32, String> = HashMap::new();
+
+        for period_id in &period_ids {
+            let period = self
+                .period_repo
+                .find_by_id(*period_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+
+            if let Some(school) = self.school_repo.find_by_id(period.school_id).await? {
+                school_names.insert(school.id, school.name);
+            }
+
+            for path in self.period_repo.find_paths_by_period(*period_id).await? {
+                paths.insert(path.id, path);
+            }
+
+            for fallback in self
+                .registration_repo
+                .find_fallback_paths_by_period(*period_id)
+                .await?
+            {
+                if let Some(score) = fallback.selection_score {
+                    fallbacks
+                        .entry(fallback.registration_id)
+                        .or_default()
+                        .push((fallback.path_id, score));
+                }
+            }
+        }
+
+        let quotas: HashMap<i32, i32> = paths.iter().map(|(id, path)| (*id, path.quota)).collect();
+
+        // Group registrations and choices by student
+        let registrations = self.registration_repo.find_by_periods(&period_ids).await?;
+        let choices = self.allocation_repo.find_choices_by_round(round_id).await?;
+
+        let mut ranks: HashMap<i32, i32> = HashMap::new();
+        for choice in &choices {
+            ranks.insert(choice.registration_id, choice.choice_rank);
+        }
+
+        let mut by_student: HashMap<String, Vec<&Registration>> = HashMap::new();
+        for registration in &registrations {
+            if registration.status == "verified" || ranks.contains_key(&registration.id) {
+                by_student
+                    .entry(registration.student_nisn.clone())
+                    .or_default()
+                    .push(registration);
+            }
+        }
+
+        let mut nisns: Vec<String> = by_student.keys().cloned().collect();
+        nisns.sort();
+
+        let mut students = Vec::with_capacity(nisns.len());
+        let mut student_registrations = Vec::with_capacity(nisns.len());
+
+        for nisn in &nisns {
+            let mut regs = by_student.remove(nisn).unwrap_or_default();
+
+            // Ranked choices first, then remaining registrations by submission time
+            regs.sort_by_key(|r| (ranks.get(&r.id).copied().unwrap_or(i32::MAX), r.created_at, r.id));
+
+            let mut preferences = Vec::new();
+            for (index, registration) in regs.iter().enumerate() {
+                let choice_rank = (index + 1) as i32;
+                let eligible = registration.status == "verified" && registration.selection_score.is_some();
+
+                if !eligible {
+                    continue;
+                }
+
+                preferences.push(ProgramChoice {
+                    choice_rank,
+                    registration_id: registration.id,
+                    path_id: registration.path_id,
+                    score: registration.selection_score.unwrap_or_default(),
+                    submitted_at: registration.created_at,
+                });
+
+                for (path_id, score) in fallbacks.get(&registration.id).into_iter().flatten() {
+                    preferences.push(ProgramChoice {
+                        choice_rank,
+                        registration_id: registration.id,
+                        path_id: *path_id,
+                        score: *score,
+                        submitted_at: registration.created_at,
+                    });
+                }
+            }
+
+            students.push(StudentPreferences { choices: preferences });
+            student_registrations.push(regs);
+        }
+
+        // Run deferred acceptance
+        let seats = deferred_acceptance(&quotas, &students);
+        let cutoffs = path_cutoffs(&quotas, &students, &seats);
+
+        // Build results with explanations
+        let mut results = Vec::with_capacity(students.len());
+        let mut total_placed = 0;
+
+        for (index, student) in students.iter().enumerate() {
+            let regs = &student_registrations[index];
+            let seat = seats[index].as_ref();
+            let seat_choice = seat.map(|s| &student.choices[s.choice_index]);
+
+            let mut details = Vec::new();
+            let mut reasons = Vec::new();
+
+            for (position, registration) in regs.iter().enumerate() {
+                let choice_rank = (position + 1) as i32;
+                let school_name = school_names
+                    .get(&registration.school_id)
+                    .cloned()
+                    .unwrap_or_default();
+
+                let proposals: Vec<(usize, &ProgramChoice)> = student
+                    .choices
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| c.registration_id == registration.id)
+                    .collect();
+
+                if proposals.is_empty() {
+                    let path_name = paths
+                        .get(&registration.path_id)
+                        .map(|p| p.name.clone())
+                        .unwrap_or_default();
+                    reasons.push(format!(
+                        "Pilihan ke-{} {} - {}: pendaftaran belum terverifikasi atau belum memiliki skor.",
+                        choice_rank, school_name, path_name
+                    ));
+                    details.push(ChoiceExplanation {
+                        choice_rank,
+                        registration_id: registration.id,
+                        school_id: registration.school_id,
+                        school_name: school_name.clone(),
+                        path_id: registration.path_id,
+                        path_name,
+                        selection_score: registration.selection_score,
+                        cutoff_score: None,
+                        outcome: "not_eligible".to_string(),
+                    });
+                    continue;
+                }
+
+                for (choice_index, choice) in proposals {
+                    let path_name = paths
+                        .get(&choice.path_id)
+                        .map(|p| p.name.clone())
+                        .unwrap_or_default();
+                    let cutoff = cutoffs.get(&choice.path_id).copied();
+
+                    let outcome = match seat {
+                        Some(s) if s.choice_index == choice_index => "accepted",
+                        Some(s) if choice_index > s.choice_index => "not_considered",
+                        _ => "rejected_quota",
+                    };
+
+                    if outcome == "rejected_quota" {
+                        reasons.push(match cutoff {
+                            Some(c) if choice.score < c => format!(
+                                "Pilihan ke-{} {} - {}: kuota penuh, skor {:.2} di bawah skor terendah yang diterima {:.2}.",
+                                choice_rank, school_name, path_name, choice.score, c
+                            ),
+                            Some(c) => format!(
+                                "Pilihan ke-{} {} - {}: kuota penuh, skor {:.2} sama dengan skor terendah yang diterima {:.2} namun kalah urutan waktu pendaftaran.",
+                                choice_rank, school_name, path_name, choice.score, c
+                            ),
+                            None => format!(
+                                "Pilihan ke-{} {} - {}: jalur tidak memiliki kuota.",
+                                choice_rank, school_name, path_name
+                            ),
+                        });
+                    }
+
+                    details.push(ChoiceExplanation {
+                        choice_rank,
+                        registration_id: registration.id,
+                        school_id: registration.school_id,
+                        school_name: school_name.clone(),
+                        path_id: choice.path_id,
+                        path_name,
+                        selection_score: Some(choice.score),
+                        cutoff_score: cutoff,
+                        outcome: outcome.to_string(),
+                    });
+                }
+            }
+
+            let first = regs.first().copied();
+            let student_nisn = first.map(|r| r.student_nisn.clone()).unwrap_or_default();
+            let student_name = first.map(|r| r.student_name.clone()).unwrap_or_default();
+
+            let result = match seat_choice {
+                Some(choice) => {
+                    total_placed += 1;
+                    let school_id = regs
+                        .iter()
+                        .find(|r| r.id == choice.registration_id)
+                        .map(|r| r.school_id);
+                    let school_name = school_id
+                        .and_then(|id| school_names.get(&id).cloned())
+                        .unwrap_or_default();
+                    let path_name = paths
+                        .get(&choice.path_id)
+                        .map(|p| p.name.clone())
+                        .unwrap_or_default();
+
+                    let mut explanation = format!(
+                        "Diterima di pilihan ke-{}: {} - {} (skor {:.2}).",
+                        choice.choice_rank, school_name, path_name, choice.score
+                    );
+                    for reason in &reasons {
+                        explanation.push(' ');
+                        explanation.push_str(reason);
+                    }
+
+                    NewAllocationResult {
+                        student_nisn,
+                        student_name,
+                        status: "placed".to_string(),
+                        registration_id: Some(choice.registration_id),
+                        school_id,
+                        path_id: Some(choice.path_id),
+                        choice_rank: Some(choice.choice_rank),
+                        selection_score: Some(choice.score),
+                        explanation,
+                        details: serde_json::to_value(&details).unwrap_or_default(),
+                    }
+                }
+                None => {
+                    let mut explanation = "Tidak mendapatkan kursi di semua pilihan.".to_string();
+                    for reason in &reasons {
+                        explanation.push(' ');
+                        explanation.push_str(reason);
+                    }
+
+                    NewAllocationResult {
+                        student_nisn,
+                        student_name,
+                        status: "unplaced".to_string(),
+                        registration_id: None,
+                        school_id: None,
+                        path_id: None,
+                        choice_rank: None,
+                        selection_score: None,
+                        explanation,
+                        details: serde_json::to_value(&details).unwrap_or_default(),
+                    }
+                }
+            };
+
+            results.push(result);
+        }
+
+        // Outcomes of the verified registrations
+        let mut outcomes = Vec::new();
+        for (index, student) in students.iter().enumerate() {
+            let seat = seats[index].as_ref();
+            let placed_registration = seat.map(|s| student.choices[s.choice_index].registration_id);
+
+            for registration in &student_registrations[index] {
+                if registration.status != "verified" {
+                    continue;
+                }
+
+                let outcome = match seat {
+                    Some(s) if Some(registration.id) == placed_registration => RegistrationOutcome {
+                        registration_id: registration.id,
+                        status: "accepted",
+                        accepted_path_id: Some(student.choices[s.choice_index].path_id),
+                        ranking: Some(s.ranking),
+                        rejection_reason: None,
+                        waitlisted: false,
+                    },
+                    Some(_) => RegistrationOutcome {
+                        registration_id: registration.id,
+                        status: "rejected",
+                        accepted_path_id: None,
+                        ranking: None,
+                        rejection_reason: Some("Diterima di sekolah pilihan lain pada alokasi terpusat."),
+                        waitlisted: false,
+                    },
+                    None => RegistrationOutcome {
+                        registration_id: registration.id,
+                        status: "rejected",
+                        accepted_path_id: None,
+                        ranking: None,
+                        rejection_reason: Some(QUOTA_FULL_REASON),
+                        waitlisted: true,
+                    },
+                };
+                outcomes.push(outcome);
+            }
+        }
+
+        self.allocation_repo
+            .store_allocation(round_id, &results, &outcomes)
+            .await?;
+
+        tracing::info!(
+            "Allocation round {} completed by admin {}. Placed: {}, Unplaced: {}",
+            round_id,
+            admin_id,
+            total_placed,
+            students.len() - total_placed
+        );
+
+        // TODO: Log to audit_logs
+
+        Ok(AllocationSummary {
+            round_id,
+            total_students: students.len() as i64,
+            placed: total_placed as i64,
+            unplaced: (students.len() - total_placed) as i64,
+        })
+    }
+
+    pub async fn list_results(
+        &self,
+        round_id: i32,
+        status: Option<String>,
+        school_id: Option<i32>,
+        page: i64,
+        page_size: i64,
+    ) -> AppResult<(Vec<AllocationResult>, i64)> {
+        let _ = self.get_round(round_id).await?;
+
+        let offset = (page - 1) * page_size;
+        let results = self
+            .allocation_repo
+            .find_results(round_id, status.clone(), school_id, page_size, offset)
+            .await?;
+        let total = self
+            .allocation_repo
+            .count_results(round_id, status, school_id)
+            .await?;
+
+        Ok((results, total))
+    }
+
+    /// Get the outcome of a single student
+    /// Parents can only see results of students they registered
+    pub async fn get_student_result(
+        &self,
+        round_id: i32,
+        student_nisn: &str,
+        user_id: i32,
+        role: &str,
+    ) -> AppResult<AllocationResult> {
+        let round = self.get_round(round_id).await?;
+
+        if round.status != "allocated" {
+            return Err(AppError::Validation(
+                "Allocation has not been run yet".to_string(),
+            ));
+        }
+
+        if role == "parent" {
+            let registrations = self.registration_repo.find_by_user(user_id, 1000, 0).await?;
+            if !registrations.iter().any(|r| r.student_nisn == student_nisn) {
+                return Err(AppError::Forbidden(
+                    "You don't have permission to view this result".to_string(),
+                ));
+            }
+        }
+
+        self.allocation_repo
+            .find_result_by_student(round_id, student_nisn)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Allocation result not found".to_string()))
+    }
+
+    async fn validate_round_periods(
+        &self,
+        round_id: Option<i32>,
+        academic_year: &str,
+        level: &str,
+        period_ids: &[i32],
+    ) -> AppResult<()> {
+        let mut seen = HashSet::new();
+
+        for period_id in period_ids {
+            if !seen.insert(*period_id) {
+                return Err(AppError::Validation(
+                    "Each period can only be added once".to_string(),
+                ));
+            }
+
+            let period = self
+                .period_repo
+                .find_by_id(*period_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Period {} not found", period_id)))?;
+
+            if period.academic_year != academic_year || !period.level.eq_ignore_ascii_case(level) {
+                return Err(AppError::Validation(format!(
+                    "Period {} does not match the round academic year and level",
+                    period_id
+                )));
+            }
+
+            // Majors are allocated by the school's own selection only
+            if !self.period_repo.find_majors_by_period(*period_id).await?.is_empty() {
+                return Err(AppError::Validation(format!(
+                    "Period {} has majors and cannot join a centralized allocation round",
+                    period_id
+                )));
+            }
+
+            if let Some(existing) = self.allocation_repo.find_by_period(*period_id).await? {
+                if Some(existing.id) != round_id {
+                    return Err(AppError::Conflict(format!(
+                        "Period {} already belongs to allocation round {}",
+                        period_id, existing.id
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// One path a student applies to, in preference order
+#[derive(Debug, Clone)]
+pub struct ProgramChoice {
+    /// Rank of the school choice this path belongs to
+    pub choice_rank: i32,
+    pub registration_id: i32,
+    pub path_id: i32,
+    pub score: f64,
+    pub submitted_at: DateTime<Utc>,
+}
+
+/// Flattened preference list of a student (school choices with their fallback paths)
+#[derive(Debug, Clone)]
+pub struct StudentPreferences {
+    pub choices: Vec<ProgramChoice>,
+}
+
+/// Seat held by a student at the end of the allocation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Seat {
+    /// Index into the student's choices
+    pub choice_index: usize,
+    /// Position within the accepted path
+    pub ranking: i32,
+}
+
+fn compare_proposals(a: &ProgramChoice, b: &ProgramChoice) -> Ordering {
+    b.score
+        .partial_cmp(&a.score)
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| a.submitted_at.cmp(&b.submitted_at))
+        .then_with(|| a.registration_id.cmp(&b.registration_id))
+}
+
+/// Student-proposing deferred acceptance.
+/// Every path tentatively holds its best proposals up to its quota (by score, then
+/// registration time); displaced students propose to their next choice until all
+/// are held or out of choices. The result is stable and gives every student at most one seat.
+pub fn deferred_acceptance(
+    quotas: &HashMap<i32, i32>,
+    students: &[StudentPreferences],
+) -> Vec<Option<Seat>> {
+    let mut next_choice = vec![0usize; students.len()];
+    let mut held: HashMap<i32, Vec<(usize, usize)>> = HashMap::new();
+    let mut queue: VecDeque<usize> = (0..students.len()).collect();
+
+    while let Some(student) = queue.pop_front() {
+        while let Some(choice) = students[student].choices.get(next_choice[student]) {
+            let choice_index = next_choice[student];
+            next_choice[student] += 1;
+
+            let quota = quotas.get(&choice.path_id).copied().unwrap_or(0);
+            if quota <= 0 {
+                continue;
+            }
+
+            let holders = held.entry(choice.path_id).or_default();
+            holders.push((student, choice_index));
+            holders.sort_by(|(sa, ca), (sb, cb)| {
+                compare_proposals(&students[*sa].choices[*ca], &students[*sb].choices[*cb])
+            });
+
+            if holders.len() > quota as usize {
+                let (displaced, _) = holders.pop().unwrap_or((student, choice_index));
+                if displaced == student {
+                    continue;
+                }
+                queue.push_back(displaced);
+            }
+            break;
+        }
+    }
+
+    let mut seats = vec![None; students.len()];
+    for holders in held.values() {
+        for (position, (student, choice_index)) in holders.iter().enumerate() {
+            seats[*student] = Some(Seat {
+                choice_index: *choice_index,
+                ranking: position as i32 + 1,
+            });
+        }
+    }
+
+    seats
+}
+
+/// Lowest accepted score of every path that filled its quota
+pub fn path_cutoffs(
+    quotas: &HashMap<i32, i32>,
+    students: &[StudentPreferences],
+    seats: &[Option<Seat>],
+) -> HashMap<i32, f64> {
+    let mut accepted: HashMap<i32, Vec<f64>> = HashMap::new();
+    for (student, seat) in seats.iter().enumerate() {
+        if let Some(seat) = seat {
+            let choice = &students[student].choices[seat.choice_index];
+            accepted.entry(choice.path_id).or_default().push(choice.score);
+        }
+    }
+
+    accepted
+        .into_iter()
+        .filter(|(path_id, scores)| quotas.get(path_id).is_some_and(|q| scores.len() >= *q as usize))
+        .map(|(path_id, scores)| (path_id, scores.into_iter().fold(f64::INFINITY, f64::min)))
+        .collect()
+}
+
+/// Penjelasan hasil untuk satu pilihan
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
+pub struct ChoiceExplanation {
+    /// Urutan pilihan sekolah
+    #[schema(example = 1)]
+    pub choice_rank: i32,
+
+    /// ID pendaftaran
+    #[schema(example = 1)]
+    pub registration_id: i32,
+
+    /// ID sekolah
+    #[schema(example = 1)]
+    pub school_id: i32,
+
+    /// Nama sekolah
+    #[schema(example = "SMP Negeri 1 Jakarta")]
+    pub school_name: String,
+
+    /// ID jalur
+    #[schema(example = 1)]
+    pub path_id: i32,
+
+    /// Nama jalur
+    #[schema(example = "Jalur Zonasi")]
+    pub path_name: String,
+
+    /// Skor di jalur ini
+    #[schema(example = 80.5)]
+    pub selection_score: Option<f64>,
+
+    /// Skor terendah yang diterima di jalur ini (jika kuota penuh)
+    #[schema(example = 85.0)]
+    pub cutoff_score: Option<f64>,
+
+    /// Hasil (accepted/rejected_quota/not_considered/not_eligible)
+    #[schema(example = "rejected_quota")]
+    pub outcome: String,
+}
+
+/// Ringkasan hasil alokasi terpusat
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct AllocationSummary {
+    /// ID putaran alokasi
+    #[schema(example = 1)]
+    pub round_id: i32,
+
+    /// Total siswa yang dialokasikan
+    #[schema(example = 500)]
+    pub total_students: i64,
+
+    /// Total siswa yang mendapat kursi
+    #[schema(example = 420)]
+    pub placed: i64,
+
+    /// Total siswa yang tidak mendapat kursi
+    #[schema(example = 80)]
+    pub unplaced: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn choice(registration_id: i32, path_id: i32, score: f64) -> ProgramChoice {
+        ProgramChoice {
+            choice_rank: 1,
+            registration_id,
+            path_id,
+            score,
+            submitted_at: Utc.with_ymd_and_hms(2024, 6, 1, 8, 0, registration_id as u32).unwrap(),
+        }
+    }
+
+    fn student(choices: Vec<ProgramChoice>) -> StudentPreferences {
+        StudentPreferences { choices }
+    }
+
+    fn placed_path(students: &[StudentPreferences], seats: &[Option<Seat>], index: usize) -> Option<i32> {
+        seats[index]
+            .as_ref()
+            .map(|seat| students[index].choices[seat.choice_index].path_id)
+    }
+
+    #[test]
+    fn test_strong_student_gets_only_one_seat() {
+        // Student 0 is the best everywhere but only takes their first choice
+        let quotas = HashMap::from([(10, 1), (20, 1), (30, 1)]);
+        let students = vec![
+            student(vec![choice(1, 10, 95.0), choice(2, 20, 95.0), choice(3, 30, 95.0)]),
+            student(vec![choice(4, 20, 80.0), choice(5, 10, 80.0)]),
+            student(vec![choice(6, 30, 70.0)]),
+        ];
+
+        let seats = deferred_acceptance(&quotas, &students);
+
+        assert_eq!(placed_path(&students, &seats, 0), Some(10));
+        assert_eq!(placed_path(&students, &seats, 1), Some(20));
+        assert_eq!(placed_path(&students, &seats, 2), Some(30));
+    }
+
+    #[test]
+    fn test_displaced_student_moves_to_next_choice() {
+        let quotas = HashMap::from([(10, 1), (20, 1)]);
+        let students = vec![
+            // Proposes to 10 first and is later displaced by a stronger applicant
+            student(vec![choice(1, 10, 70.0), choice(2, 20, 70.0)]),
+            student(vec![choice(3, 10, 90.0)]),
+            student(vec![choice(4, 20, 60.0)]),
+        ];
+
+        let seats = deferred_acceptance(&quotas, &students);
+
+        assert_eq!(placed_path(&students, &seats, 1), Some(10));
+        assert_eq!(placed_path(&students, &seats, 0), Some(20));
+        assert_eq!(placed_path(&students, &seats, 2), None);
+    }
+
+    #[test]
+    fn test_ranking_and_cutoffs() {
+        let quotas = HashMap::from([(10, 2), (20, 5)]);
+        let students = vec![
+            student(vec![choice(1, 10, 70.0)]),
+            student(vec![choice(2, 10, 90.0)]),
+            student(vec![choice(3, 10, 60.0), choice(4, 20, 55.0)]),
+        ];
+
+        let seats = deferred_acceptance(&quotas, &students);
+        let cutoffs = path_cutoffs(&quotas, &students, &seats);
+
+        assert_eq!(seats[1], Some(Seat { choice_index: 0, ranking: 1 }));
+        assert_eq!(seats[0], Some(Seat { choice_index: 0, ranking: 2 }));
+        assert_eq!(seats[2], Some(Seat { choice_index: 1, ranking: 1 }));
+        assert_eq!(cutoffs.get(&10), Some(&70.0));
+        // Path 20 did not fill its quota, so there is no cutoff
+        assert_eq!(cutoffs.get(&20), None);
+    }
+
+    #[test]
+    fn test_path_without_quota_is_skipped() {
+        let quotas = HashMap::from([(10, 0), (20, 1)]);
+        let students = vec![student(vec![choice(1, 10, 90.0), choice(2, 20, 50.0)])];
+
+        let seats = deferred_acceptance(&quotas, &students);
+
+        assert_eq!(placed_path(&students, &seats, 0), Some(20));
+    }
+}
@@ -1,9 +1,20 @@
-use chrono::Utc;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
+use chrono::{DateTime, Utc};
+
+use crate::models::period::PeriodStage;
 use crate::models::registration::Registration;
 use crate::repositories::period_repo::PeriodRepository;
 use crate::repositories::registration_repo::RegistrationRepository;
+use crate::services::appeal_service::AppealStats;
+use crate::services::major_service::eligible_major_choices;
 use crate::utils::error::{AppError, AppResult};
+use crate::utils::timezone::local_date;
+
+/// Reason stored on registrations left out of the quota; these form the
+/// waitlist for seats freed by withdrawals
+pub const QUOTA_FULL_REASON: &str = "Quota penuh. Anda berada di luar kuota yang tersedia.";
 
 pub struct AnnouncementService {
     registration_repo: RegistrationRepository,
@@ -22,9 +33,17 @@ impl AnnouncementService {
     }
 
     /// Run selection process for a period
-    /// Select top N registrations per path (N = quota)
-    /// Update status to Accepted for selected, Rejected for non-selected
-    pub async fn run_selection(&self, period_id: i32, admin_id: i32) -> AppResult<SelectionResult> {
+    /// Paths are processed in their configured selection order. Each path accepts
+    /// the top N applicants (N = quota); applicants that fall outside the quota are
+    /// re-ranked in their next fallback path. Whoever is left without a seat is rejected.
+    /// Periods with stages select one stage at a time, by default the first stage
+    /// that has not been announced yet.
+    pub async fn run_selection(
+        &self,
+        period_id: i32,
+        stage_id: Option<i32>,
+        admin_id: i32,
+    ) -> AppResult<SelectionResult> {
         // Check if period exists
         let period = self
             .period_repo
@@ -39,62 +58,257 @@ impl AnnouncementService {
             ));
         }
 
-        // Get all paths for this period
+        // Periods in a centralized allocation round are allocated by the round instead
+        let in_allocation_round: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM allocation_round_periods WHERE period_id = $1)",
+        )
+        .bind(period_id)
+        .fetch_one(&self.registration_repo.pool)
+        .await?;
+
+        if in_allocation_round {
+            return Err(AppError::Validation(
+                "Period is part of a centralized allocation round. Run the round allocation instead".to_string(),
+            ));
+        }
+
+        // Get all paths for this period (already sorted by selection order)
         let paths = self.period_repo.find_paths_by_period(period_id).await?;
 
+        let stages = self.period_repo.find_stages_by_period(period_id).await?;
+        let stage = select_stage(&stages, stage_id)?;
+
+        let (path_quotas, stage_path_ids) = match stage {
+            Some(stage) => {
+                if stage.announcement_date.is_some() {
+                    return Err(AppError::Validation(format!(
+                        "{} has already been announced",
+                        stage.name
+                    )));
+                }
+
+                // Seats left by earlier stages carry forward, so they must be final
+                if let Some(open) = stages
+                    .iter()
+                    .find(|s| s.stage_number < stage.stage_number && s.announcement_date.is_none())
+                {
+                    return Err(AppError::Validation(format!(
+                        "Announce {} before running the selection of {}",
+                        open.name, stage.name
+                    )));
+                }
+
+                let stage_paths: Vec<(i32, i32)> = paths
+                    .iter()
+                    .filter(|p| p.stage_id == Some(stage.id))
+                    .map(|p| (p.id, p.quota))
+                    .collect();
+
+                if stage_paths.is_empty() {
+                    return Err(AppError::Validation(format!(
+                        "{} has no registration paths",
+                        stage.name
+                    )));
+                }
+
+                let earlier_stage_ids: Vec<i32> = stages
+                    .iter()
+                    .filter(|s| s.stage_number < stage.stage_number)
+                    .map(|s| s.id)
+                    .collect();
+                let earlier_paths: Vec<(i32, i32)> = paths
+                    .iter()
+                    .filter(|p| p.stage_id.is_some_and(|id| earlier_stage_ids.contains(&id)))
+                    .map(|p| (p.id, p.quota))
+                    .collect();
+                let earlier_path_ids: Vec<i32> = earlier_paths.iter().map(|(id, _)| *id).collect();
+
+                let accepted: HashMap<i32, i64> = self
+                    .registration_repo
+                    .count_accepted_by_path(period_id, &earlier_path_ids)
+                    .await?
+                    .into_iter()
+                    .collect();
+
+                let stage_path_ids: Vec<i32> = stage_paths.iter().map(|(id, _)| *id).collect();
+                (
+                    stage_quotas(&stage_paths, &earlier_paths, &accepted),
+                    Some(stage_path_ids),
+                )
+            }
+            None => (paths.iter().map(|p| (p.id, p.quota)).collect(), None),
+        };
+
+        // Get all verified registrations with rankings
+        let registrations = sqlx::query_as::<_, Registration>(
+            r#"
+            SELECT * FROM registrations 
+            WHERE period_id = $1 
+              AND status = 'verified'
+              AND selection_score IS NOT NULL
+              AND ranking IS NOT NULL
+              AND ($2::INTEGER[] IS NULL OR path_id = ANY($2))
+            "#,
+        )
+        .bind(period_id)
+        .bind(&stage_path_ids)
+        .fetch_all(&self.registration_repo.pool)
+        .await?;
+
+        // Students accepted in an earlier stage are excluded from later ones
+        let accepted_earlier: HashSet<String> = match stage_path_ids {
+            Some(ref ids) => self
+                .registration_repo
+                .find_accepted_nisns_outside_paths(period_id, ids)
+                .await?
+                .into_iter()
+                .collect(),
+            None => HashSet::new(),
+        };
+
+        // Group scored fallback paths by registration (already sorted by priority)
+        let mut fallbacks: HashMap<i32, Vec<(i32, f64)>> = HashMap::new();
+        for fallback in self
+            .registration_repo
+            .find_fallback_paths_by_period(period_id)
+            .await?
+        {
+            if let Some(score) = fallback.selection_score {
+                fallbacks
+                    .entry(fallback.registration_id)
+                    .or_default()
+                    .push((fallback.path_id, score));
+            }
+        }
+
+        // Majors (SMK): applicants are placed in the first chosen major they
+        // qualify for that still has capacity
+        let majors = self.period_repo.find_majors_by_period(period_id).await?;
+        let mut major_capacities: HashMap<i32, i32> =
+            majors.iter().map(|m| (m.id, m.capacity)).collect();
+        if let (Some(ref ids), false) = (&stage_path_ids, majors.is_empty()) {
+            for (major_id, taken) in self
+                .registration_repo
+                .count_accepted_by_major_outside_paths(period_id, ids)
+                .await?
+            {
+                if let Some(capacity) = major_capacities.get_mut(&major_id) {
+                    *capacity = (*capacity - taken as i32).max(0);
+                }
+            }
+        }
+        let mut eligible_majors = if majors.is_empty() {
+            HashMap::new()
+        } else {
+            let choices = self
+                .registration_repo
+                .find_major_choices_by_period(period_id)
+                .await?;
+
+            let mut subject_averages: HashMap<i32, HashMap<String, f64>> = HashMap::new();
+            for (registration_id, subject, average) in self
+                .registration_repo
+                .find_subject_averages_by_period(period_id)
+                .await?
+            {
+                subject_averages
+                    .entry(registration_id)
+                    .or_default()
+                    .insert(subject, average);
+            }
+
+            let mut checks: HashMap<i32, HashMap<String, bool>> = HashMap::new();
+            for check in self
+                .registration_repo
+                .find_requirement_checks_by_period(period_id)
+                .await?
+            {
+                checks
+                    .entry(check.registration_id)
+                    .or_default()
+                    .insert(check.requirement_key, check.passed);
+            }
+
+            eligible_major_choices(&majors, &choices, &subject_averages, &checks)?
+        };
+
+        let candidates: Vec<SelectionCandidate> = registrations
+            .iter()
+            .filter(|registration| !accepted_earlier.contains(&registration.student_nisn))
+            .map(|registration| {
+                let mut choices = vec![(
+                    registration.path_id,
+                    registration.selection_score.unwrap_or_default(),
+                )];
+                choices.extend(fallbacks.remove(&registration.id).unwrap_or_default());
+
+                SelectionCandidate {
+                    registration_id: registration.id,
+                    submitted_at: registration.created_at,
+                    choices,
+                    major_choices: eligible_majors.remove(&registration.id).unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        let without_eligible_major: HashSet<i32> = candidates
+            .iter()
+            .filter(|c| !majors.is_empty() && c.major_choices.is_empty())
+            .map(|c| c.registration_id)
+            .collect();
+
+        let placements = allocate_with_majors(&path_quotas, &major_capacities, &candidates);
+
         let mut total_accepted = 0;
         let mut total_rejected = 0;
 
-        // Run selection for each path
-        for path in paths {
-            // Get all verified registrations with rankings for this path
-            let registrations = sqlx::query_as::<_, Registration>(
-                r#"
-                SELECT * FROM registrations 
-                WHERE period_id = $1 
-                  AND path_id = $2 
-                  AND status = 'verified'
-                  AND ranking IS NOT NULL
-                ORDER BY ranking ASC
-                "#,
-            )
-            .bind(period_id)
-            .bind(path.id)
-            .fetch_all(&self.registration_repo.pool)
-            .await?;
-
-            // Select top N (N = quota)
-            for (index, registration) in registrations.iter().enumerate() {
-                let new_status = if (index as i32) < path.quota {
-                    "accepted"
-                } else {
-                    "rejected"
-                };
-
-                // Update status
-                self.registration_repo
-                    .update_status(
-                        registration.id,
-                        new_status,
-                        if new_status == "rejected" {
-                            Some("Quota penuh. Anda berada di luar kuota yang tersedia.")
+        for registration in &registrations {
+            match placements.get(&registration.id) {
+                Some(placement) => {
+                    self.registration_repo
+                        .set_selection_outcome(
+                            registration.id,
+                            "accepted",
+                            Some(placement.path_id),
+                            placement.major_id,
+                            Some(placement.ranking),
+                            None,
+                            false,
+                        )
+                        .await?;
+                    total_accepted += 1;
+                }
+                None => {
+                    // Only applicants left out by the quota wait for freed seats
+                    let (reason, waitlisted) =
+                        if accepted_earlier.contains(&registration.student_nisn) {
+                            ("Sudah diterima pada tahap sebelumnya.", false)
+                        } else if without_eligible_major.contains(&registration.id) {
+                            ("Tidak memenuhi persyaratan jurusan pilihan.", false)
                         } else {
-                            None
-                        },
-                    )
-                    .await?;
+                            (QUOTA_FULL_REASON, true)
+                        };
 
-                if new_status == "accepted" {
-                    total_accepted += 1;
-                } else {
+                    self.registration_repo
+                        .set_selection_outcome(
+                            registration.id,
+                            "rejected",
+                            None,
+                            None,
+                            None,
+                            Some(reason),
+                            waitlisted,
+                        )
+                        .await?;
                     total_rejected += 1;
                 }
             }
         }
 
         tracing::info!(
-            "Selection completed for period {} by admin {}. Accepted: {}, Rejected: {}",
+            "Selection completed for period {}{} by admin {}. Accepted: {}, Rejected: {}",
             period_id,
+            stage.map(|s| format!(" ({})", s.name)).unwrap_or_default(),
             admin_id,
             total_accepted,
             total_rejected
@@ -108,8 +322,14 @@ impl AnnouncementService {
         })
     }
 
-    /// Announce results - send notifications to all registrations
-    pub async fn announce_results(&self, period_id: i32, admin_id: i32) -> AppResult<AnnouncementResult> {
+    /// Publish the selection results. Without an admin the announcement was
+    /// triggered by the period lifecycle scheduler.
+    pub async fn announce_results(
+        &self,
+        period_id: i32,
+        stage_id: Option<i32>,
+        admin_id: Option<i32>,
+    ) -> AppResult<AnnouncementResult> {
         // Check if period exists
         let period = self
             .period_repo
@@ -117,14 +337,31 @@ impl AnnouncementService {
             .await?
             .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
 
+        let stages = self.period_repo.find_stages_by_period(period_id).await?;
+        let stage = select_stage(&stages, stage_id)?;
+        let stage_path_ids: Option<Vec<i32>> = match stage {
+            Some(stage) => Some(
+                self.period_repo
+                    .find_paths_by_period(period_id)
+                    .await?
+                    .into_iter()
+                    .filter(|p| p.stage_id == Some(stage.id))
+                    .map(|p| p.id)
+                    .collect(),
+            ),
+            None => None,
+        };
+
         // Check if selection has been run (there should be accepted/rejected registrations)
         let accepted_count: i64 = sqlx::query_scalar(
             r#"
             SELECT COUNT(*) FROM registrations 
             WHERE period_id = $1 AND status = 'accepted'
+              AND ($2::INTEGER[] IS NULL OR path_id = ANY($2))
             "#,
         )
         .bind(period_id)
+        .bind(&stage_path_ids)
         .fetch_one(&self.registration_repo.pool)
         .await?;
 
@@ -134,40 +371,59 @@ impl AnnouncementService {
             ));
         }
 
-        // Update announcement_date in period
-        sqlx::query(
-            r#"
-            UPDATE periods 
-            SET announcement_date = $2, updated_at = NOW()
-            WHERE id = $1
-            "#,
-        )
-        .bind(period_id)
-        .bind(Utc::now())
-        .execute(&self.registration_repo.pool)
-        .await?;
+        // Update announcement_date, dated at the school. The period counts as
+        // announced once its last stage is.
+        let timezone = self.period_repo.find_school_timezone(period.school_id).await?;
+        let today = local_date(Utc::now(), timezone);
+        if let Some(stage) = stage {
+            self.period_repo.set_stage_announced(stage.id, today).await?;
+        }
+
+        let is_last_stage = match stage {
+            Some(stage) => stages.iter().all(|s| s.stage_number <= stage.stage_number),
+            None => true,
+        };
+        if is_last_stage {
+            sqlx::query(
+                r#"
+                UPDATE periods 
+                SET announcement_date = $2, updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(period_id)
+            .bind(today)
+            .execute(&self.registration_repo.pool)
+            .await?;
+        }
 
         // Get all accepted registrations
         let accepted_registrations = sqlx::query_as::<_, Registration>(
             r#"
             SELECT * FROM registrations 
             WHERE period_id = $1 AND status = 'accepted'
+              AND ($2::INTEGER[] IS NULL OR path_id = ANY($2))
             "#,
         )
         .bind(period_id)
+        .bind(&stage_path_ids)
         .fetch_all(&self.registration_repo.pool)
         .await?;
+        let accepted_registrations = notification_recipients(accepted_registrations);
 
         // Get all rejected registrations
         let rejected_registrations = sqlx::query_as::<_, Registration>(
             r#"
             SELECT * FROM registrations 
             WHERE period_id = $1 AND status = 'rejected'
+              AND ($2::INTEGER[] IS NULL OR path_id = ANY($2))
             "#,
         )
         .bind(period_id)
+        .bind(&stage_path_ids)
         .fetch_all(&self.registration_repo.pool)
         .await?;
+        let rejected_registrations = notification_recipients(rejected_registrations);
 
         // TODO: Send acceptance emails
         for registration in &accepted_registrations {
@@ -187,10 +443,16 @@ impl AnnouncementService {
             // TODO: Implement email sending
         }
 
+        let announced_by = match admin_id {
+            Some(admin_id) => format!("admin {}", admin_id),
+            None => "scheduler".to_string(),
+        };
+
         tracing::info!(
-            "Results announced for period {} by admin {}. Notifications sent: {} accepted, {} rejected",
+            "Results announced for period {}{} by {}. Notifications sent: {} accepted, {} rejected",
             period_id,
-            admin_id,
+            stage.map(|s| format!(" ({})", s.name)).unwrap_or_default(),
+            announced_by,
             accepted_registrations.len(),
             rejected_registrations.len()
         );
@@ -232,12 +494,6 @@ impl AnnouncementService {
             .await?
             .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
 
-        if period.announcement_date.is_none() {
-            return Err(AppError::Validation(
-                "Results have not been announced yet".to_string(),
-            ));
-        }
-
         // Get path info
         let path = self
             .period_repo
@@ -245,17 +501,60 @@ impl AnnouncementService {
             .await?
             .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
 
+        // Paths of a stage are announced together with their stage
+        let announcement_date = match path.stage_id {
+            Some(stage_id) => self
+                .period_repo
+                .find_stage_by_id(stage_id)
+                .await?
+                .and_then(|stage| stage.announcement_date),
+            None => period.announcement_date,
+        };
+
+        if announcement_date.is_none() {
+            return Err(AppError::Validation(
+                "Results have not been announced yet".to_string(),
+            ));
+        }
+
+        let timezone = self.period_repo.find_school_timezone(period.school_id).await?;
+
+        // Get accepted path info (may be a fallback path)
+        let accepted_path_name = match registration.accepted_path_id {
+            Some(accepted_path_id) if accepted_path_id == path.id => Some(path.name.clone()),
+            Some(accepted_path_id) => self
+                .period_repo
+                .find_path_by_id(accepted_path_id)
+                .await?
+                .map(|p| p.name),
+            None => None,
+        };
+
+        let accepted_major_name = match registration.accepted_major_id {
+            Some(major_id) => self
+                .period_repo
+                .find_major_by_id(major_id)
+                .await?
+                .map(|m| m.name),
+            None => None,
+        };
+
         Ok(ResultCheckResponse {
             registration_number: registration.registration_number.unwrap_or_default(),
             student_name: registration.student_name,
             student_nisn: registration.student_nisn,
             path_name: path.name,
+            accepted_path_name,
+            accepted_major_name,
             selection_score: registration.selection_score,
             ranking: registration.ranking,
             status: registration.status,
             rejection_reason: registration.rejection_reason,
-            announcement_date: period.announcement_date,
+            announcement_date,
             reenrollment_deadline: period.reenrollment_deadline,
+            reenrollment_closes_at: period
+                .reenrollment_closes_at
+                .map(|at| at.with_timezone(&timezone).fixed_offset()),
         })
     }
 
@@ -296,7 +595,7 @@ impl AnnouncementService {
 
         for path in paths {
             let path_accepted: i64 = sqlx::query_scalar(
-                "SELECT COUNT(*) FROM registrations WHERE period_id = $1 AND path_id = $2 AND status = 'accepted'",
+                "SELECT COUNT(*) FROM registrations WHERE period_id = $1 AND accepted_path_id = $2 AND status = 'accepted'",
             )
             .bind(period_id)
             .bind(path.id)
@@ -321,16 +620,259 @@ impl AnnouncementService {
             });
         }
 
+        let majors = self.period_repo.find_majors_by_period(period_id).await?;
+        let mut major_summaries = Vec::new();
+
+        for major in majors {
+            let major_accepted: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM registrations WHERE period_id = $1 AND accepted_major_id = $2 AND status = 'accepted'",
+            )
+            .bind(period_id)
+            .bind(major.id)
+            .fetch_one(&self.registration_repo.pool)
+            .await?;
+
+            major_summaries.push(MajorSelectionSummary {
+                major_id: major.id,
+                code: major.code,
+                name: major.name,
+                capacity: major.capacity,
+                accepted: major_accepted,
+                remaining_capacity: (major.capacity as i64 - major_accepted).max(0),
+            });
+        }
+
+        let appeals = sqlx::query_as::<_, AppealStats>(
+            r#"
+            SELECT COUNT(*) AS total,
+                   COUNT(*) FILTER (WHERE a.status = 'submitted') AS submitted,
+                   COUNT(*) FILTER (WHERE a.status = 'in_review') AS in_review,
+                   COUNT(*) FILTER (WHERE a.status = 'upheld') AS upheld,
+                   COUNT(*) FILTER (WHERE a.status = 'dismissed') AS dismissed,
+                   COUNT(*) FILTER (WHERE a.score_after IS NOT NULL) AS rescored,
+                   COUNT(*) FILTER (
+                       WHERE a.score_after IS NOT NULL AND a.score_after IS DISTINCT FROM a.score_before
+                   ) AS score_changed,
+                   (AVG(EXTRACT(EPOCH FROM (a.resolved_at - a.created_at))) / 3600)::DOUBLE PRECISION
+                       AS average_resolution_hours
+            FROM appeals a
+            JOIN registrations r ON r.id = a.registration_id
+            WHERE r.period_id = $1
+            "#,
+        )
+        .bind(period_id)
+        .fetch_one(&self.registration_repo.pool)
+        .await?;
+
         Ok(SelectionSummary {
             period_id,
             verified: verified_count,
             accepted: accepted_count,
             rejected: rejected_count,
             paths: path_summaries,
+            majors: major_summaries,
+            appeals,
         })
     }
 }
 
+/// Applicant considered in selection with their ranked path choices
+#[derive(Debug, Clone)]
+pub struct SelectionCandidate {
+    pub registration_id: i32,
+    pub submitted_at: DateTime<Utc>,
+    /// (path_id, score) pairs: primary path first, then fallbacks by priority
+    pub choices: Vec<(i32, f64)>,
+    /// Majors the applicant chose and qualifies for, by priority
+    pub major_choices: Vec<i32>,
+}
+
+/// Seat assigned to an applicant by the selection
+#[derive(Debug, Clone, PartialEq)]
+pub struct Placement {
+    pub path_id: i32,
+    pub major_id: Option<i32>,
+    pub ranking: i32,
+}
+
+/// Stage to select or announce: the requested one, or else the first stage
+/// that has not been announced yet. Periods without stages yield `None`.
+pub fn select_stage(
+    stages: &[PeriodStage],
+    stage_id: Option<i32>,
+) -> AppResult<Option<&PeriodStage>> {
+    if let Some(stage_id) = stage_id {
+        return stages
+            .iter()
+            .find(|s| s.id == stage_id)
+            .map(Some)
+            .ok_or_else(|| AppError::NotFound("Stage not found".to_string()));
+    }
+
+    if stages.is_empty() {
+        return Ok(None);
+    }
+
+    stages
+        .iter()
+        .filter(|s| s.announcement_date.is_none())
+        .min_by_key(|s| s.stage_number)
+        .map(Some)
+        .ok_or_else(|| {
+            AppError::Validation("All stages of this period have been announced".to_string())
+        })
+}
+
+/// Registrations whose parents are still notified, leaving out withdrawn ones
+pub fn notification_recipients(registrations: Vec<Registration>) -> Vec<Registration> {
+    registrations
+        .into_iter()
+        .filter(Registration::receives_notifications)
+        .collect()
+}
+
+/// Quotas of a stage's paths (path_id, quota). Seats that earlier stages'
+/// paths (path_id, quota) left unfilled, given their accepted counts, carry
+/// forward to the first path of the stage in selection order. Leftovers are
+/// the earlier stages' total quota minus everyone they accepted, so seats
+/// carried into one stage and filled there are not carried again.
+pub fn stage_quotas(
+    stage_paths: &[(i32, i32)],
+    earlier_paths: &[(i32, i32)],
+    accepted: &HashMap<i32, i64>,
+) -> Vec<(i32, i32)> {
+    let total_quota: i64 = earlier_paths.iter().map(|(_, quota)| *quota as i64).sum();
+    let total_accepted: i64 = earlier_paths
+        .iter()
+        .map(|(path_id, _)| accepted.get(path_id).copied().unwrap_or(0))
+        .sum();
+    let remaining = (total_quota - total_accepted).max(0);
+
+    stage_paths
+        .iter()
+        .enumerate()
+        .map(|(index, (path_id, quota))| {
+            if index == 0 {
+                (*path_id, quota + remaining as i32)
+            } else {
+                (*path_id, *quota)
+            }
+        })
+        .collect()
+}
+
+/// Allocate seats path by path in the given order (path_id, quota).
+/// Applicants outside a path's quota move on to their next choice; choices
+/// pointing at a path that was already processed are skipped.
+/// Ties are broken by earlier registration, then by registration id.
+pub fn allocate_by_path_order(
+    paths: &[(i32, i32)],
+    candidates: &[SelectionCandidate],
+) -> HashMap<i32, Placement> {
+    allocate_with_majors(paths, &HashMap::new(), candidates)
+}
+
+/// Same as `allocate_by_path_order`, additionally placing every accepted
+/// applicant in a major (major_id -> capacity). Within a path, applicants in
+/// score order take their first eligible major that still has capacity;
+/// those without one move on to their next path choice. An empty capacity
+/// map disables majors.
+pub fn allocate_with_majors(
+    paths: &[(i32, i32)],
+    major_capacities: &HashMap<i32, i32>,
+    candidates: &[SelectionCandidate],
+) -> HashMap<i32, Placement> {
+    let order: HashMap<i32, usize> = paths
+        .iter()
+        .enumerate()
+        .map(|(index, (path_id, _))| (*path_id, index))
+        .collect();
+
+    let mut remaining_capacity = major_capacities.clone();
+    let mut next_choice = vec![0usize; candidates.len()];
+    let mut placements = HashMap::new();
+
+    for (path_index, (path_id, quota)) in paths.iter().enumerate() {
+        let mut pool: Vec<(usize, f64)> = Vec::new();
+
+        for (candidate_index, candidate) in candidates.iter().enumerate() {
+            if placements.contains_key(&candidate.registration_id) {
+                continue;
+            }
+
+            // Skip choices for unknown or already processed paths
+            let cursor = &mut next_choice[candidate_index];
+            while let Some((choice_path, _)) = candidate.choices.get(*cursor) {
+                match order.get(choice_path) {
+                    Some(&index) if index >= path_index => break,
+                    _ => *cursor += 1,
+                }
+            }
+
+            if let Some((choice_path, score)) = candidate.choices.get(*cursor) {
+                if choice_path == path_id {
+                    pool.push((candidate_index, *score));
+                }
+            }
+        }
+
+        pool.sort_by(|(a, score_a), (b, score_b)| {
+            score_b
+                .partial_cmp(score_a)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| candidates[*a].submitted_at.cmp(&candidates[*b].submitted_at))
+                .then_with(|| {
+                    candidates[*a]
+                        .registration_id
+                        .cmp(&candidates[*b].registration_id)
+                })
+        });
+
+        let mut accepted = 0;
+        for (candidate_index, _) in pool {
+            if accepted >= *quota {
+                next_choice[candidate_index] += 1;
+                continue;
+            }
+
+            let major_id = if major_capacities.is_empty() {
+                None
+            } else {
+                let major = candidates[candidate_index]
+                    .major_choices
+                    .iter()
+                    .copied()
+                    .find(|major_id| remaining_capacity.get(major_id).copied().unwrap_or(0) > 0);
+
+                match major {
+                    Some(major_id) => {
+                        if let Some(capacity) = remaining_capacity.get_mut(&major_id) {
+                            *capacity -= 1;
+                        }
+                        Some(major_id)
+                    }
+                    None => {
+                        next_choice[candidate_index] += 1;
+                        continue;
+                    }
+                }
+            };
+
+            accepted += 1;
+            placements.insert(
+                candidates[candidate_index].registration_id,
+                Placement {
+                    path_id: *path_id,
+                    major_id,
+                    ranking: accepted,
+                },
+            );
+        }
+    }
+
+    placements
+}
+
 /// Hasil proses seleksi
 #[derive(Debug, serde::Serialize, utoipa::ToSchema)]
 pub struct SelectionResult {
@@ -378,6 +920,14 @@ pub struct ResultCheckResponse {
     #[schema(example = "Jalur Zonasi")]
     pub path_name: String,
     
+    /// Nama jalur tempat diterima (bisa berbeda jika diterima di jalur cadangan)
+    #[schema(example = "Jalur Zonasi")]
+    pub accepted_path_name: Option<String>,
+    
+    /// Nama jurusan tempat diterima (SMK)
+    #[schema(example = "Teknik Komputer dan Jaringan")]
+    pub accepted_major_name: Option<String>,
+    
     /// Skor seleksi
     #[schema(example = 85.5)]
     pub selection_score: Option<f64>,
@@ -401,6 +951,10 @@ pub struct ResultCheckResponse {
     /// Batas waktu daftar ulang
     #[schema(value_type = Option<String>, example = "2024-08-15")]
     pub reenrollment_deadline: Option<chrono::NaiveDate>,
+    
+    /// Waktu berakhirnya daftar ulang menurut zona waktu sekolah
+    #[schema(value_type = Option<String>, example = "2024-08-15T23:59:59+08:00")]
+    pub reenrollment_closes_at: Option<chrono::DateTime<chrono::FixedOffset>>,
 }
 
 /// Ringkasan seleksi
@@ -424,6 +978,12 @@ pub struct SelectionSummary {
     
     /// Ringkasan per jalur
     pub paths: Vec<PathSelectionSummary>,
+    
+    /// Ringkasan per jurusan (SMK)
+    pub majors: Vec<MajorSelectionSummary>,
+    
+    /// Statistik sanggahan
+    pub appeals: AppealStats,
 }
 
 /// Ringkasan seleksi per jalur
@@ -453,3 +1013,203 @@ pub struct PathSelectionSummary {
     #[schema(example = 20)]
     pub remaining_quota: i64,
 }
+
+/// Ringkasan seleksi per jurusan
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct MajorSelectionSummary {
+    /// ID jurusan
+    #[schema(example = 3)]
+    pub major_id: i32,
+    
+    /// Kode jurusan
+    #[schema(example = "TKJ")]
+    pub code: String,
+    
+    /// Nama jurusan
+    #[schema(example = "Teknik Komputer dan Jaringan")]
+    pub name: String,
+    
+    /// Daya tampung jurusan
+    #[schema(example = 72)]
+    pub capacity: i32,
+    
+    /// Total diterima
+    #[schema(example = 70)]
+    pub accepted: i64,
+    
+    /// Sisa daya tampung
+    #[schema(example = 2)]
+    pub remaining_capacity: i64,
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn candidate(registration_id: i32, choices: Vec<(i32, f64)>) -> SelectionCandidate {
+        SelectionCandidate {
+            registration_id,
+            submitted_at: Utc.with_ymd_and_hms(2024, 6, 1, 8, 0, registration_id as u32).unwrap(),
+            choices,
+            major_choices: vec![],
+        }
+    }
+
+    #[test]
+    fn test_allocate_without_fallback_rejects_out_of_quota() {
+        let paths = vec![(1, 2)];
+        let candidates = vec![
+            candidate(1, vec![(1, 90.0)]),
+            candidate(2, vec![(1, 80.0)]),
+            candidate(3, vec![(1, 70.0)]),
+        ];
+
+        let placements = allocate_by_path_order(&paths, &candidates);
+
+        assert_eq!(placements.len(), 2);
+        assert_eq!(placements[&1], Placement { path_id: 1, major_id: None, ranking: 1 });
+        assert_eq!(placements[&2], Placement { path_id: 1, major_id: None, ranking: 2 });
+        assert!(!placements.contains_key(&3));
+    }
+
+    #[test]
+    fn test_allocate_moves_out_of_quota_applicant_to_fallback() {
+        // Prestasi (1) is processed before zonasi (2)
+        let paths = vec![(1, 1), (2, 2)];
+        let candidates = vec![
+            candidate(1, vec![(1, 95.0)]),
+            candidate(2, vec![(1, 85.0), (2, 60.0)]),
+            candidate(3, vec![(2, 70.0)]),
+            candidate(4, vec![(2, 50.0)]),
+        ];
+
+        let placements = allocate_by_path_order(&paths, &candidates);
+
+        assert_eq!(placements[&1], Placement { path_id: 1, major_id: None, ranking: 1 });
+        assert_eq!(placements[&3], Placement { path_id: 2, major_id: None, ranking: 1 });
+        assert_eq!(placements[&2], Placement { path_id: 2, major_id: None, ranking: 2 });
+        assert!(!placements.contains_key(&4));
+    }
+
+    #[test]
+    fn test_allocate_skips_fallback_to_already_processed_path() {
+        let paths = vec![(1, 1), (2, 1)];
+        let candidates = vec![
+            candidate(1, vec![(2, 90.0)]),
+            candidate(2, vec![(2, 80.0), (1, 99.0)]),
+        ];
+
+        let placements = allocate_by_path_order(&paths, &candidates);
+
+        assert_eq!(placements[&1], Placement { path_id: 2, major_id: None, ranking: 1 });
+        assert!(!placements.contains_key(&2));
+    }
+
+    #[test]
+    fn test_allocate_breaks_ties_by_registration_time() {
+        let paths = vec![(1, 1)];
+        let candidates = vec![candidate(2, vec![(1, 80.0)]), candidate(1, vec![(1, 80.0)])];
+
+        let placements = allocate_by_path_order(&paths, &candidates);
+
+        assert_eq!(placements.len(), 1);
+        assert!(placements.contains_key(&1));
+    }
+
+    #[test]
+    fn test_allocate_with_majors_respects_capacity_and_criteria() {
+        // One path with 3 seats, major 10 has a single seat, major 11 two
+        let paths = vec![(1, 3), (2, 5)];
+        let capacities = HashMap::from([(10, 1), (11, 2)]);
+
+        let mut first = candidate(1, vec![(1, 90.0)]);
+        first.major_choices = vec![10, 11];
+        let mut second = candidate(2, vec![(1, 85.0)]);
+        second.major_choices = vec![10, 11];
+        // Qualifies only for major 10, which is full by now: falls back to path 2
+        let mut third = candidate(3, vec![(1, 80.0), (2, 50.0)]);
+        third.major_choices = vec![10];
+        // Qualifies for no major at all
+        let fourth = candidate(4, vec![(1, 75.0)]);
+        let mut fifth = candidate(5, vec![(1, 70.0)]);
+        fifth.major_choices = vec![11];
+
+        let placements =
+            allocate_with_majors(&paths, &capacities, &[first, second, third, fourth, fifth]);
+
+        assert_eq!(placements[&1], Placement { path_id: 1, major_id: Some(10), ranking: 1 });
+        assert_eq!(placements[&2], Placement { path_id: 1, major_id: Some(11), ranking: 2 });
+        assert_eq!(placements[&5], Placement { path_id: 1, major_id: Some(11), ranking: 3 });
+        assert!(!placements.contains_key(&3));
+        assert!(!placements.contains_key(&4));
+    }
+
+    fn stage(id: i32, stage_number: i32, announced: bool) -> PeriodStage {
+        PeriodStage {
+            id,
+            period_id: 1,
+            stage_number,
+            name: format!("Tahap {}", stage_number),
+            registration_start: chrono::NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            registration_end: chrono::NaiveDate::from_ymd_opt(2024, 6, 10).unwrap(),
+            registration_opens_at: Utc.with_ymd_and_hms(2024, 5, 31, 17, 0, 0).unwrap(),
+            registration_closes_at: Utc.with_ymd_and_hms(2024, 6, 10, 16, 59, 59).unwrap(),
+            announcement_at: None,
+            announcement_date: announced.then(|| chrono::NaiveDate::from_ymd_opt(2024, 6, 15).unwrap()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_withdrawn_registrations_are_not_notified() {
+        let registration = |id: i32, status: &str| Registration {
+            id,
+            status: status.to_string(),
+            ..Default::default()
+        };
+        let registrations = vec![
+            registration(1, "accepted"),
+            registration(2, "withdrawn"),
+            registration(3, "rejected"),
+        ];
+
+        let ids: Vec<i32> = notification_recipients(registrations).iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_stage_selection_and_carried_over_quota() {
+        let stages = vec![stage(7, 1, true), stage(8, 2, false)];
+        assert_eq!(select_stage(&stages, None).unwrap().unwrap().id, 8);
+        assert_eq!(select_stage(&stages, Some(7)).unwrap().unwrap().id, 7);
+        assert!(select_stage(&stages, Some(99)).is_err());
+        assert!(select_stage(&[stage(7, 1, true)], None).is_err());
+        assert!(select_stage(&[], None).unwrap().is_none());
+
+        // Stage 1 filled 8 of 10 zonasi seats and all 5 prestasi seats
+        let accepted = HashMap::from([(1, 8), (2, 5)]);
+        assert_eq!(
+            stage_quotas(&[(3, 4), (4, 2)], &[(1, 10), (2, 5)], &accepted),
+            vec![(3, 6), (4, 2)]
+        );
+        assert_eq!(stage_quotas(&[(3, 4)], &[], &HashMap::new()), vec![(3, 4)]);
+
+        // Stage 2 used the 2 carried-over seats: 6 accepted on path 3, 1 of 2
+        // on path 4. Only the one seat left on path 4 carries into stage 3.
+        let accepted = HashMap::from([(1, 8), (2, 5), (3, 6), (4, 1)]);
+        assert_eq!(
+            stage_quotas(&[(5, 3)], &[(1, 10), (2, 5), (3, 4), (4, 2)], &accepted),
+            vec![(5, 4)]
+        );
+
+        // Stage 2 left its carried-over seats unused: all three carry on
+        let accepted = HashMap::from([(1, 8), (2, 5), (3, 4), (4, 1)]);
+        assert_eq!(
+            stage_quotas(&[(5, 3)], &[(1, 10), (2, 5), (3, 4), (4, 2)], &accepted),
+            vec![(5, 6)]
+        );
+    }
+}
@@ -0,0 +1,502 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+
+use crate::models::appeal::{
+    Appeal, AppealCategory, AppealDetail, AppealStatus, NewAppealDocument,
+};
+use crate::models::period::Period;
+use crate::models::registration::Registration;
+use crate::repositories::appeal_repo::AppealRepository;
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::services::document_service::{DocumentService, DOCUMENT_MIME_TYPES, MAX_DOCUMENT_BYTES};
+use crate::services::selection_service::SelectionService;
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::timezone::{format_local, local_instant};
+
+/// Evidence files per appeal; their size and type follow the limits of
+/// registration documents
+const MAX_EVIDENCE_FILES: usize = 5;
+
+pub struct AppealService {
+    appeal_repo: AppealRepository,
+    registration_repo: RegistrationRepository,
+    period_repo: PeriodRepository,
+    selection_service: SelectionService,
+    document_service: DocumentService,
+}
+
+impl AppealService {
+    pub fn new(
+        appeal_repo: AppealRepository,
+        registration_repo: RegistrationRepository,
+        period_repo: PeriodRepository,
+        selection_service: SelectionService,
+        document_service: DocumentService,
+    ) -> Self {
+        Self {
+            appeal_repo,
+            registration_repo,
+            period_repo,
+            selection_service,
+            document_service,
+        }
+    }
+
+    /// File an appeal against the announced result of a registration. Appeals
+    /// are accepted until the period's appeal window after the announcement
+    /// closes, and a registration has at most one unresolved appeal.
+    pub async fn file_appeal(
+        &self,
+        registration_id: i32,
+        filed_by: i32,
+        category: String,
+        reason: String,
+        documents: Vec<NewAppealDocument>,
+    ) -> AppResult<AppealDetail> {
+        if AppealCategory::from_str(&category).is_none() {
+            return Err(AppError::Validation(
+                "Appeal category must be 'score', 'distance' or 'other'".to_string(),
+            ));
+        }
+
+        let reason = reason.trim().to_string();
+        if reason.is_empty() {
+            return Err(AppError::Validation("Appeal reason is required".to_string()));
+        }
+
+        validate_evidence(&documents)?;
+        for document in &documents {
+            self.document_service.ensure_in_bucket(&document.file_url)?;
+        }
+
+        let registration = self.get_registration(registration_id).await?;
+        if registration.status != "accepted" && registration.status != "rejected" {
+            return Err(AppError::Validation(
+                "Can only appeal accepted or rejected registrations".to_string(),
+            ));
+        }
+
+        let period = self
+            .period_repo
+            .find_by_id(registration.period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+
+        let announcement_date = self.announcement_date(&registration, &period).await?;
+        let timezone = self.period_repo.find_school_timezone(period.school_id).await?;
+        check_appeal_window(&period, announcement_date, Utc::now(), timezone)?;
+
+        let (appeal, documents) = self
+            .appeal_repo
+            .create_appeal(registration_id, filed_by, &category, &reason, &documents)
+            .await?;
+
+        tracing::info!(
+            "Appeal {} ({}) filed on registration {} by user {}",
+            appeal.id,
+            category,
+            registration_id,
+            filed_by
+        );
+
+        // Evidence stays hidden until the malware scan finds it clean
+        if !documents.is_empty() {
+            self.document_service
+                .spawn_evidence_scan(documents.iter().map(|d| d.id).collect());
+        }
+
+        let events = self.appeal_repo.find_events(appeal.id).await?;
+
+        Ok(AppealDetail {
+            appeal,
+            documents,
+            events,
+        })
+    }
+
+    pub async fn list_registration_appeals(&self, registration_id: i32) -> AppResult<Vec<Appeal>> {
+        let _ = self.get_registration(registration_id).await?;
+
+        self.appeal_repo.find_by_registration(registration_id).await
+    }
+
+    pub async fn list_appeals(
+        &self,
+        school_id: Option<i32>,
+        period_id: Option<i32>,
+        status: Option<String>,
+        page: i64,
+        page_size: i64,
+    ) -> AppResult<(Vec<Appeal>, i64)> {
+        let offset = (page - 1) * page_size;
+
+        let appeals = self
+            .appeal_repo
+            .find_appeals(school_id, period_id, status.clone(), page_size, offset)
+            .await?;
+
+        let total = self
+            .appeal_repo
+            .count_appeals(school_id, period_id, status)
+            .await?;
+
+        Ok((appeals, total))
+    }
+
+    /// Appeal with its evidence and decision log. School admins only see
+    /// appeals on their school's registrations.
+    pub async fn get_appeal(&self, id: i32, school_id: Option<i32>) -> AppResult<AppealDetail> {
+        let (appeal, _) = self.find_appeal(id, school_id).await?;
+
+        let documents = self.appeal_repo.find_documents(id).await?;
+        let events = self.appeal_repo.find_events(id).await?;
+
+        Ok(AppealDetail {
+            appeal,
+            documents,
+            events,
+        })
+    }
+
+    /// Registration an appeal was filed on
+    pub async fn get_appeal_registration(&self, id: i32) -> AppResult<Registration> {
+        let (_, registration) = self.find_appeal(id, None).await?;
+
+        Ok(registration)
+    }
+
+    /// Take a submitted appeal into review
+    pub async fn triage_appeal(
+        &self,
+        id: i32,
+        school_id: Option<i32>,
+        notes: Option<String>,
+        admin_id: i32,
+    ) -> AppResult<AppealDetail> {
+        let (appeal, _) = self.find_appeal(id, school_id).await?;
+
+        if appeal.status != AppealStatus::Submitted.as_str() {
+            return Err(AppError::Validation(
+                "Only submitted appeals can be taken into review".to_string(),
+            ));
+        }
+
+        self.appeal_repo
+            .triage(id, notes.as_deref(), admin_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict("Appeal status changed, please try again".to_string())
+            })?;
+
+        tracing::info!("Appeal {} taken into review by admin {}", id, admin_id);
+
+        self.get_appeal(id, school_id).await
+    }
+
+    /// Recalculate the registration's selection score. The score before the
+    /// first re-score is kept so the appeal shows the full change.
+    pub async fn rescore_appeal(
+        &self,
+        id: i32,
+        school_id: Option<i32>,
+        admin_id: i32,
+    ) -> AppResult<AppealDetail> {
+        let (appeal, registration) = self.find_appeal(id, school_id).await?;
+
+        if AppealStatus::from_str(&appeal.status).is_none_or(|s| s.is_resolved()) {
+            return Err(AppError::Validation(
+                "Appeal has already been resolved".to_string(),
+            ));
+        }
+
+        let rescore = self
+            .selection_service
+            .rescore_registration(registration.id)
+            .await?;
+
+        let details = serde_json::to_value(&rescore)
+            .map_err(|e| AppError::Internal(format!("Failed to serialize re-score: {}", e)))?;
+
+        self.appeal_repo
+            .record_rescore(id, rescore.score_before, rescore.score_after, &details, admin_id)
+            .await?;
+
+        tracing::info!(
+            "Appeal {}: registration {} re-scored by admin {}, now at position {} of quota {}",
+            id,
+            registration.id,
+            admin_id,
+            rescore.position,
+            rescore.quota
+        );
+
+        self.get_appeal(id, school_id).await
+    }
+
+    /// Decide an appeal with a response to the parent
+    pub async fn resolve_appeal(
+        &self,
+        id: i32,
+        school_id: Option<i32>,
+        status: String,
+        response: String,
+        admin_id: i32,
+    ) -> AppResult<AppealDetail> {
+        match AppealStatus::from_str(&status) {
+            Some(s) if s.is_resolved() => {}
+            _ => {
+                return Err(AppError::Validation(
+                    "Decision must be 'upheld' or 'dismissed'".to_string(),
+                ))
+            }
+        }
+
+        let response = response.trim().to_string();
+        if response.is_empty() {
+            return Err(AppError::Validation("Response is required".to_string()));
+        }
+
+        let (appeal, registration) = self.find_appeal(id, school_id).await?;
+
+        if AppealStatus::from_str(&appeal.status).is_none_or(|s| s.is_resolved()) {
+            return Err(AppError::Validation(
+                "Appeal has already been resolved".to_string(),
+            ));
+        }
+
+        self.appeal_repo
+            .resolve(id, &status, &response, admin_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict("Appeal status changed, please try again".to_string())
+            })?;
+
+        if registration.receives_notifications() {
+            // TODO: Send the decision to the parent by email
+        }
+
+        tracing::info!("Appeal {} {} by admin {}", id, status, admin_id);
+
+        self.get_appeal(id, school_id).await
+    }
+
+    async fn find_appeal(
+        &self,
+        id: i32,
+        school_id: Option<i32>,
+    ) -> AppResult<(Appeal, Registration)> {
+        let appeal = self
+            .appeal_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Appeal not found".to_string()))?;
+
+        let registration = self.get_registration(appeal.registration_id).await?;
+
+        if let Some(school_id) = school_id {
+            if registration.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have permission to access this appeal".to_string(),
+                ));
+            }
+        }
+
+        Ok((appeal, registration))
+    }
+
+    async fn get_registration(&self, id: i32) -> AppResult<Registration> {
+        self.registration_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))
+    }
+
+    /// Paths of a stage are announced together with their stage
+    async fn announcement_date(
+        &self,
+        registration: &Registration,
+        period: &Period,
+    ) -> AppResult<Option<NaiveDate>> {
+        let path = self
+            .period_repo
+            .find_path_by_id(registration.path_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+
+        match path.stage_id {
+            Some(stage_id) => Ok(self
+                .period_repo
+                .find_stage_by_id(stage_id)
+                .await?
+                .and_then(|stage| stage.announcement_date)),
+            None => Ok(period.announcement_date),
+        }
+    }
+}
+
+/// Last instant appeals are accepted: the period's closing time at the school,
+/// `window_days` after the announcement. None when appeals are disabled.
+pub fn appeal_closes_at(
+    announcement_date: NaiveDate,
+    window_days: i32,
+    closing_time: NaiveTime,
+    timezone: Tz,
+) -> Option<DateTime<Utc>> {
+    if window_days <= 0 {
+        return None;
+    }
+
+    let last_day = announcement_date + Duration::days(window_days as i64);
+    Some(local_instant(last_day, closing_time, timezone))
+}
+
+pub fn check_appeal_window(
+    period: &Period,
+    announcement_date: Option<NaiveDate>,
+    now: DateTime<Utc>,
+    timezone: Tz,
+) -> AppResult<()> {
+    let announcement_date = announcement_date.ok_or_else(|| {
+        AppError::Validation("Results have not been announced yet".to_string())
+    })?;
+
+    let closes_at = appeal_closes_at(
+        announcement_date,
+        period.appeal_window_days,
+        period.closing_time,
+        timezone,
+    )
+    .ok_or_else(|| AppError::Validation("Appeals are not accepted for this period".to_string()))?;
+
+    if now > closes_at {
+        return Err(AppError::Validation(format!(
+            "Appeal window closed at {}",
+            format_local(closes_at, timezone)
+        )));
+    }
+
+    Ok(())
+}
+
+fn validate_evidence(documents: &[NewAppealDocument]) -> AppResult<()> {
+    if documents.len() > MAX_EVIDENCE_FILES {
+        return Err(AppError::Validation(format!(
+            "At most {} evidence files can be attached",
+            MAX_EVIDENCE_FILES
+        )));
+    }
+
+    for document in documents {
+        if document.file_size > MAX_DOCUMENT_BYTES {
+            return Err(AppError::Validation(format!(
+                "{}: file size must not exceed 2MB",
+                document.file_name
+            )));
+        }
+
+        if !DOCUMENT_MIME_TYPES.contains(&document.mime_type.as_str()) {
+            return Err(AppError::Validation(format!(
+                "{}: file type must be JPEG, PNG, or PDF",
+                document.file_name
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Statistik sanggahan dalam satu periode
+#[derive(Debug, Default, serde::Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct AppealStats {
+    /// Total sanggahan
+    #[schema(example = 12)]
+    pub total: i64,
+
+    /// Sanggahan yang belum ditinjau
+    #[schema(example = 2)]
+    pub submitted: i64,
+
+    /// Sanggahan yang sedang ditinjau
+    #[schema(example = 3)]
+    pub in_review: i64,
+
+    /// Sanggahan yang dikabulkan
+    #[schema(example = 4)]
+    pub upheld: i64,
+
+    /// Sanggahan yang ditolak
+    #[schema(example = 3)]
+    pub dismissed: i64,
+
+    /// Sanggahan yang skornya dihitung ulang
+    #[schema(example = 5)]
+    pub rescored: i64,
+
+    /// Sanggahan yang skornya berubah setelah dihitung ulang
+    #[schema(example = 2)]
+    pub score_changed: i64,
+
+    /// Rata-rata lama penyelesaian dalam jam
+    #[schema(example = 26.5)]
+    pub average_resolution_hours: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period(appeal_window_days: i32) -> Period {
+        Period {
+            status: "active".to_string(),
+            announcement_date: NaiveDate::from_ymd_opt(2024, 7, 5),
+            closing_time: NaiveTime::from_hms_opt(23, 59, 59).unwrap(),
+            appeal_window_days,
+            ..Default::default()
+        }
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn evidence(file_size: i64, mime_type: &str) -> NewAppealDocument {
+        NewAppealDocument {
+            file_url: "https://storage.example.com/appeals/bukti.pdf".to_string(),
+            file_name: "bukti.pdf".to_string(),
+            file_size,
+            mime_type: mime_type.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_appeal_window() {
+        let wita = crate::utils::timezone::parse_timezone("Asia/Makassar").unwrap();
+        let announced = NaiveDate::from_ymd_opt(2024, 7, 5);
+        let period = period(3);
+        let check = |now: &str| check_appeal_window(&period, announced, at(now), wita);
+
+        // Open until 08-07-2024 23:59:59 WITA
+        assert!(check("2024-07-05T02:00:00Z").is_ok());
+        assert!(check("2024-07-08T15:59:59Z").is_ok());
+
+        match check("2024-07-08T16:00:00Z") {
+            Err(AppError::Validation(message)) => {
+                assert_eq!(message, "Appeal window closed at 08-07-2024 23:59 WITA")
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        assert!(check_appeal_window(&period, None, at("2024-07-05T02:00:00Z"), wita).is_err());
+        assert!(check_appeal_window(&self::period(0), announced, at("2024-07-05T02:00:00Z"), wita).is_err());
+    }
+
+    #[test]
+    fn test_validate_evidence() {
+        assert!(validate_evidence(&[]).is_ok());
+        assert!(validate_evidence(&[evidence(1024, "application/pdf")]).is_ok());
+        assert!(validate_evidence(&[evidence(3 * 1024 * 1024, "application/pdf")]).is_err());
+        assert!(validate_evidence(&[evidence(1024, "application/zip")]).is_err());
+        assert!(validate_evidence(&vec![evidence(1024, "image/png"); 6]).is_err());
+    }
+}
@@ -0,0 +1,538 @@
+use crate::models::assistance::{
+    AssistanceList, AssistanceMatchDetail, AssistanceMatchStatus, AssistanceProgram,
+    NewBeneficiary,
+};
+use crate::repositories::assistance_repo::AssistanceRepository;
+use crate::repositories::region_repo::RegionRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::utils::error::{AppError, AppResult, FieldError};
+
+/// Row errors reported back for one import, the rest is summarized
+const MAX_IMPORT_ERRORS: usize = 50;
+
+const NIK_COLUMNS: &[&str] = &["nik", "no_nik", "nik_penerima"];
+const KIP_COLUMNS: &[&str] = &["kip_number", "no_kip", "nomor_kip", "kip"];
+const NAME_COLUMNS: &[&str] = &["name", "nama", "nama_penerima"];
+
+/// Owner of an imported list; region lists apply to every school below the region
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListOwner {
+    School(i32),
+    Region(i32),
+}
+
+/// Lists an admin may see and manage
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListScope {
+    All,
+    School(i32),
+    Regions(Vec<i32>),
+}
+
+impl ListScope {
+    fn contains(&self, list: &AssistanceList) -> bool {
+        match self {
+            ListScope::All => true,
+            ListScope::School(school_id) => list.school_id == Some(*school_id),
+            ListScope::Regions(region_ids) => list
+                .region_id
+                .map(|id| region_ids.contains(&id))
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Hasil impor daftar penerima bantuan
+#[derive(Debug, Clone)]
+pub struct ImportResult {
+    pub list: AssistanceList,
+    pub new_matches: u64,
+}
+
+pub struct AssistanceService {
+    assistance_repo: AssistanceRepository,
+    registration_repo: RegistrationRepository,
+    region_repo: RegionRepository,
+}
+
+impl AssistanceService {
+    pub fn new(
+        assistance_repo: AssistanceRepository,
+        registration_repo: RegistrationRepository,
+        region_repo: RegionRepository,
+    ) -> Self {
+        Self {
+            assistance_repo,
+            registration_repo,
+            region_repo,
+        }
+    }
+
+    /// Lists visible to a user: super admins see all, school admins their
+    /// school's lists and region admins those of their region and sub-regions
+    pub async fn resolve_scope(
+        &self,
+        role: &str,
+        school_id: Option<i32>,
+        region_id: Option<i32>,
+    ) -> AppResult<ListScope> {
+        match role {
+            "super_admin" => Ok(ListScope::All),
+            "school_admin" => school_id.map(ListScope::School).ok_or_else(|| {
+                AppError::Authentication("User must be associated with a school".to_string())
+            }),
+            "region_admin" => {
+                let region_id = region_id.ok_or_else(|| {
+                    AppError::Forbidden("User is not assigned to a region".to_string())
+                })?;
+                let scope = self.region_repo.find_scope_ids(region_id).await?;
+                Ok(ListScope::Regions(scope))
+            }
+            _ => Err(AppError::Forbidden(
+                "You don't have permission to manage assistance lists".to_string(),
+            )),
+        }
+    }
+
+    /// Owner of a new list. School admins always import for their own school.
+    pub fn resolve_owner(
+        scope: &ListScope,
+        school_id: Option<i32>,
+        region_id: Option<i32>,
+    ) -> AppResult<ListOwner> {
+        let owner = match (scope, school_id, region_id) {
+            (ListScope::School(own), None, None) => ListOwner::School(*own),
+            (_, Some(school_id), None) => ListOwner::School(school_id),
+            (_, None, Some(region_id)) => ListOwner::Region(region_id),
+            _ => {
+                return Err(AppError::Validation(
+                    "Provide either school_id or region_id".to_string(),
+                ))
+            }
+        };
+
+        let allowed = match (scope, owner) {
+            (ListScope::All, _) => true,
+            (ListScope::School(own), ListOwner::School(school_id)) => *own == school_id,
+            (ListScope::Regions(ids), ListOwner::Region(region_id)) => ids.contains(&region_id),
+            _ => false,
+        };
+
+        if !allowed {
+            return Err(AppError::Forbidden(
+                "You can't import lists for this school or region".to_string(),
+            ));
+        }
+
+        Ok(owner)
+    }
+
+    /// Import a beneficiary list and match the afirmasi registrations it covers
+    pub async fn import_list(
+        &self,
+        owner: ListOwner,
+        program: String,
+        name: String,
+        content: &str,
+        imported_by: i32,
+    ) -> AppResult<ImportResult> {
+        if AssistanceProgram::from_str(&program).is_none() {
+            return Err(AppError::Validation(
+                "Program must be 'dtks', 'pkh', or 'kip'".to_string(),
+            ));
+        }
+
+        if name.trim().is_empty() {
+            return Err(AppError::Validation("List name is required".to_string()));
+        }
+
+        let beneficiaries = parse_beneficiary_csv(content).map_err(AppError::FieldValidation)?;
+
+        if beneficiaries.is_empty() {
+            return Err(AppError::Validation(
+                "CSV does not contain any beneficiary".to_string(),
+            ));
+        }
+
+        let (school_id, region_id) = match owner {
+            ListOwner::School(id) => (Some(id), None),
+            ListOwner::Region(id) => (None, Some(id)),
+        };
+
+        let list = self
+            .assistance_repo
+            .create_list(
+                &program,
+                school_id,
+                region_id,
+                name.trim(),
+                imported_by,
+                &beneficiaries,
+            )
+            .await?;
+
+        let school_ids = match owner {
+            ListOwner::School(id) => vec![id],
+            ListOwner::Region(id) => {
+                let region_ids = self.region_repo.find_scope_ids(id).await?;
+                self.assistance_repo
+                    .find_school_ids_in_regions(&region_ids)
+                    .await?
+            }
+        };
+
+        let registration_ids = self
+            .assistance_repo
+            .find_afirmasi_registration_ids(&school_ids)
+            .await?;
+
+        let mut new_matches = 0;
+        for registration_id in registration_ids {
+            new_matches += self.assistance_repo.match_registration(registration_id).await?;
+        }
+
+        tracing::info!(
+            "Imported {} list {} with {} rows by user {}, {} new match(es)",
+            program,
+            list.id,
+            list.row_count,
+            imported_by,
+            new_matches
+        );
+
+        Ok(ImportResult { list, new_matches })
+    }
+
+    pub async fn list_lists(
+        &self,
+        scope: &ListScope,
+        program: Option<String>,
+    ) -> AppResult<Vec<AssistanceList>> {
+        match scope {
+            ListScope::All => self.assistance_repo.find_lists(None, None, program).await,
+            ListScope::School(school_id) => {
+                self.assistance_repo
+                    .find_lists(Some(*school_id), None, program)
+                    .await
+            }
+            ListScope::Regions(region_ids) => {
+                self.assistance_repo
+                    .find_lists(None, Some(region_ids), program)
+                    .await
+            }
+        }
+    }
+
+    /// Delete a list; matches found in it are removed and the verified
+    /// programs of the affected registrations recomputed
+    pub async fn delete_list(&self, id: i32, scope: &ListScope) -> AppResult<()> {
+        let list = self
+            .assistance_repo
+            .find_list_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Assistance list not found".to_string()))?;
+
+        if !scope.contains(&list) {
+            return Err(AppError::Forbidden(
+                "You don't have permission to delete this list".to_string(),
+            ));
+        }
+
+        let registration_ids = self.assistance_repo.find_matched_registration_ids(id).await?;
+
+        self.assistance_repo.delete_list(id).await?;
+
+        for registration_id in registration_ids {
+            self.assistance_repo
+                .refresh_verified_programs(registration_id)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Match a registration against the lists currently imported
+    pub async fn match_registration(&self, registration_id: i32) -> AppResult<u64> {
+        self.assistance_repo.match_registration(registration_id).await
+    }
+
+    pub async fn list_matches(
+        &self,
+        registration_id: i32,
+        school_id: Option<i32>,
+    ) -> AppResult<Vec<AssistanceMatchDetail>> {
+        self.check_registration_scope(registration_id, school_id)
+            .await?;
+
+        self.assistance_repo
+            .find_matches_by_registration(registration_id)
+            .await
+    }
+
+    /// Confirm or reject a match. Only verified matches grant afirmasi bonuses.
+    pub async fn review_match(
+        &self,
+        id: i32,
+        status: String,
+        notes: Option<String>,
+        school_id: Option<i32>,
+        verified_by: i32,
+    ) -> AppResult<Vec<AssistanceMatchDetail>> {
+        match AssistanceMatchStatus::from_str(&status) {
+            Some(AssistanceMatchStatus::Verified) | Some(AssistanceMatchStatus::Rejected) => {}
+            _ => {
+                return Err(AppError::Validation(
+                    "Review status must be 'verified' or 'rejected'".to_string(),
+                ))
+            }
+        }
+
+        let assistance_match = self
+            .assistance_repo
+            .find_match_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Assistance match not found".to_string()))?;
+
+        let registration_id = assistance_match.registration_id;
+        self.check_registration_scope(registration_id, school_id)
+            .await?;
+
+        self.assistance_repo
+            .review_match(id, &status, notes.as_deref(), verified_by)
+            .await?;
+
+        let programs = self
+            .assistance_repo
+            .refresh_verified_programs(registration_id)
+            .await?;
+
+        tracing::info!(
+            "Assistance match {} marked as {} by admin {}, verified programs of registration {}: {:?}",
+            id,
+            status,
+            verified_by,
+            registration_id,
+            programs
+        );
+
+        self.assistance_repo
+            .find_matches_by_registration(registration_id)
+            .await
+    }
+
+    async fn check_registration_scope(
+        &self,
+        registration_id: i32,
+        school_id: Option<i32>,
+    ) -> AppResult<()> {
+        let registration = self
+            .registration_repo
+            .find_by_id(registration_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))?;
+
+        if let Some(school_id) = school_id {
+            if registration.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have permission to access this registration".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn normalize_header(header: &str) -> String {
+    header
+        .trim()
+        .trim_start_matches('\u{feff}')
+        .to_lowercase()
+        .replace([' ', '-'], "_")
+}
+
+/// Spreadsheet exports often keep numbers as text with a leading apostrophe
+fn normalize_identifier(value: &str) -> Option<String> {
+    let value: String = value
+        .trim()
+        .trim_start_matches('\'')
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+
+    (!value.is_empty()).then_some(value)
+}
+
+/// Parse a beneficiary CSV export. The header row must contain a NIK or KIP
+/// number column (`nik`, `kip_number` and common aliases); `nama` is optional.
+/// Comma and semicolon separated files are accepted.
+pub fn parse_beneficiary_csv(content: &str) -> Result<Vec<NewBeneficiary>, Vec<FieldError>> {
+    let header_line = content.lines().next().unwrap_or_default();
+    let delimiter = if header_line.contains(';') && !header_line.contains(',') {
+        b';'
+    } else {
+        b','
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let headers: Vec<String> = match reader.headers() {
+        Ok(headers) => headers.iter().map(normalize_header).collect(),
+        Err(e) => return Err(vec![FieldError::new("csv", format!("Invalid CSV: {}", e))]),
+    };
+
+    let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.as_str()));
+    let nik_column = column(NIK_COLUMNS);
+    let kip_column = column(KIP_COLUMNS);
+    let name_column = column(NAME_COLUMNS);
+
+    if nik_column.is_none() && kip_column.is_none() {
+        return Err(vec![FieldError::new(
+            "csv",
+            "CSV header must contain a 'nik' or 'kip_number' column",
+        )]);
+    }
+
+    let mut beneficiaries = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, record) in reader.records().enumerate() {
+        // Line 1 is the header
+        let line = index + 2;
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                errors.push(FieldError::new(&format!("csv.line[{}]", line), e.to_string()));
+                continue;
+            }
+        };
+
+        let value = |column: Option<usize>| column.and_then(|c| record.get(c));
+        let nik = value(nik_column).and_then(normalize_identifier);
+        let kip_number = value(kip_column).and_then(normalize_identifier);
+        let name = value(name_column)
+            .map(|n| n.trim())
+            .filter(|n| !n.is_empty())
+            .map(|n| n.chars().take(255).collect::<String>());
+
+        if nik.is_none() && kip_number.is_none() {
+            // Blank lines at the end of exports
+            if record.iter().all(|v| v.trim().is_empty()) {
+                continue;
+            }
+
+            errors.push(FieldError::new(
+                &format!("csv.line[{}]", line),
+                "Row has neither a NIK nor a KIP number",
+            ));
+            continue;
+        }
+
+        if let Some(ref nik) = nik {
+            if nik.len() != 16 || !nik.chars().all(|c| c.is_ascii_digit()) {
+                errors.push(FieldError::new(
+                    &format!("csv.line[{}].nik", line),
+                    "NIK must be 16 digits",
+                ));
+                continue;
+            }
+        }
+
+        if kip_number.as_ref().map(|k| k.len() > 30).unwrap_or(false) {
+            errors.push(FieldError::new(
+                &format!("csv.line[{}].kip_number", line),
+                "KIP number must be at most 30 characters",
+            ));
+            continue;
+        }
+
+        beneficiaries.push(NewBeneficiary {
+            nik,
+            kip_number,
+            name,
+        });
+    }
+
+    if !errors.is_empty() {
+        let total = errors.len();
+        errors.truncate(MAX_IMPORT_ERRORS);
+        if total > MAX_IMPORT_ERRORS {
+            errors.push(FieldError::new(
+                "csv",
+                format!("{} more invalid rows", total - MAX_IMPORT_ERRORS),
+            ));
+        }
+        return Err(errors);
+    }
+
+    Ok(beneficiaries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_beneficiary_csv_with_aliases_and_semicolons() {
+        let content = "\u{feff}No;Nama Penerima;NIK;No KIP\n\
+                       1;Siti Aminah;'3201015501100001;\n\
+                       2;Budi;;KIP 1234 5678\n\
+                       ;;;\n";
+
+        let beneficiaries = parse_beneficiary_csv(content).unwrap();
+
+        assert_eq!(
+            beneficiaries,
+            vec![
+                NewBeneficiary {
+                    nik: Some("3201015501100001".to_string()),
+                    kip_number: None,
+                    name: Some("Siti Aminah".to_string()),
+                },
+                NewBeneficiary {
+                    nik: None,
+                    kip_number: Some("KIP12345678".to_string()),
+                    name: Some("Budi".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_beneficiary_csv_reports_invalid_rows() {
+        let content = "nik,nama\n123,Salah\n,Tanpa NIK\n3201015501100001,Benar\n";
+
+        let fields: Vec<String> = parse_beneficiary_csv(content)
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+
+        assert_eq!(fields, vec!["csv.line[2].nik", "csv.line[3]"]);
+
+        let missing_column = parse_beneficiary_csv("nama,alamat\nBudi,Bandung\n").unwrap_err();
+        assert_eq!(missing_column[0].field, "csv");
+    }
+
+    #[test]
+    fn test_resolve_owner_checks_scope() {
+        let school = ListScope::School(1);
+        let region = ListScope::Regions(vec![10, 11]);
+
+        assert_eq!(
+            AssistanceService::resolve_owner(&school, None, None).unwrap(),
+            ListOwner::School(1)
+        );
+        assert!(AssistanceService::resolve_owner(&school, Some(2), None).is_err());
+        assert!(AssistanceService::resolve_owner(&school, None, Some(10)).is_err());
+        assert_eq!(
+            AssistanceService::resolve_owner(&region, None, Some(11)).unwrap(),
+            ListOwner::Region(11)
+        );
+        assert!(AssistanceService::resolve_owner(&region, None, Some(12)).is_err());
+        assert!(AssistanceService::resolve_owner(&ListScope::All, Some(1), Some(10)).is_err());
+    }
+}
@@ -40,6 +40,7 @@ impl AuthService {
                 req.phone.as_deref(),
                 req.nik.as_deref(),
                 "parent",
+                None,
             )
             .await?;
 
@@ -94,6 +95,7 @@ impl AuthService {
             user.email.clone(),
             user.role.clone(),
             user.school_id,
+            user.region_id,
             &self.config.jwt_secret,
             self.config.jwt_expiration_hours,
         )?;
@@ -104,6 +106,7 @@ impl AuthService {
             user.email.clone(),
             user.role.clone(),
             user.school_id,
+            user.region_id,
             &self.config.jwt_secret,
         )?;
 
@@ -144,6 +147,7 @@ impl AuthService {
             user.email,
             user.role,
             user.school_id,
+            user.region_id,
             &self.config.jwt_secret,
             self.config.jwt_expiration_hours,
         )?;
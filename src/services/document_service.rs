@@ -0,0 +1,608 @@
+use std::sync::Arc;
+
+use serde_json::json;
+
+use crate::models::appeal::AppealDocument;
+use crate::models::audit_log::{AuditAction, NewAuditLog};
+use crate::models::duplicate::DocumentDuplicate;
+use crate::models::registration::{Document, DocumentArtifact, NewDocumentArtifact};
+use crate::repositories::appeal_repo::AppealRepository;
+use crate::repositories::audit_repo::AuditRepository;
+use crate::repositories::document_repo::DocumentRepository;
+use crate::repositories::duplicate_repo::DuplicateRepository;
+use crate::services::duplicate_service::{detect_document_duplicates, PERCEPTUAL_MATCH_DISTANCE};
+use crate::utils::document_processing::{
+    content_hash, decode_image, is_image, pdf_info, perceptual_hash, recompress_photo,
+    render_preview, render_thumbnail, sanitize_image, PdfInfo, RenderedImage,
+};
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::scanner::{ScanVerdict, Scanner};
+use crate::utils::storage::{sibling_path, Storage};
+
+/// Largest document accepted on upload
+pub const MAX_DOCUMENT_BYTES: i64 = 2 * 1024 * 1024;
+
+/// File types accepted on upload
+pub const DOCUMENT_MIME_TYPES: [&str; 4] = ["image/jpeg", "image/png", "image/jpg", "application/pdf"];
+
+/// Minutes a document may stay pending before the scheduler retries it
+const STALE_PENDING_MINUTES: i32 = 10;
+
+/// Documents retried per scheduler run
+const STALE_PENDING_BATCH: i64 = 20;
+
+/// Pipeline runs before an unfinished document is marked failed
+const MAX_PROCESSING_ATTEMPTS: i32 = 5;
+
+/// Verification notes of documents rejected by the malware scan
+const INFECTED_NOTES: &str =
+    "Berkas terdeteksi mengandung malware dan ditolak. Silakan unggah ulang berkas yang bersih.";
+
+/// Oversized photo re-encoded to fit the upload limit
+#[derive(Debug, Clone)]
+pub struct RecompressedPhoto {
+    pub file_url: String,
+    pub file_size: i64,
+}
+
+/// Result of processing an image, before anything is stored
+struct ProcessedImage {
+    original: Vec<u8>,
+    mime_type: String,
+    reoriented: bool,
+    width: u32,
+    height: u32,
+    thumbnail: RenderedImage,
+    preview: RenderedImage,
+}
+
+pub struct DocumentService {
+    document_repo: DocumentRepository,
+    audit_repo: AuditRepository,
+    duplicate_repo: DuplicateRepository,
+    appeal_repo: AppealRepository,
+    storage: Storage,
+    /// None when malware scanning is disabled
+    scanner: Option<Arc<dyn Scanner>>,
+    recompress_oversized_photos: bool,
+}
+
+impl DocumentService {
+    pub fn new(
+        document_repo: DocumentRepository,
+        audit_repo: AuditRepository,
+        duplicate_repo: DuplicateRepository,
+        appeal_repo: AppealRepository,
+        storage: Storage,
+        scanner: Option<Arc<dyn Scanner>>,
+        recompress_oversized_photos: bool,
+    ) -> Self {
+        Self {
+            document_repo,
+            audit_repo,
+            duplicate_repo,
+            appeal_repo,
+            storage,
+            scanner,
+            recompress_oversized_photos,
+        }
+    }
+
+    /// Refuse files stored outside the document bucket
+    pub fn ensure_in_bucket(&self, file_url: &str) -> AppResult<()> {
+        self.storage.bucket_path(file_url).map(|_| ())
+    }
+
+    /// Re-encode a photo over the upload limit as a smaller JPEG stored next
+    /// to it. None when recompression is disabled, the file is not a photo or
+    /// it cannot be made small enough.
+    pub async fn recompress_oversized(
+        &self,
+        file_url: &str,
+        mime_type: &str,
+    ) -> AppResult<Option<RecompressedPhoto>> {
+        if !self.recompress_oversized_photos || !is_image(mime_type) {
+            return Ok(None);
+        }
+
+        let bytes = self.storage.fetch(file_url).await?;
+        let rendered = tokio::task::spawn_blocking(move || {
+            let decoded = decode_image(&bytes)?;
+            recompress_photo(&decoded.image, MAX_DOCUMENT_BYTES as usize)
+        })
+        .await
+        .map_err(join_error)??;
+
+        let Some(rendered) = rendered else {
+            return Ok(None);
+        };
+
+        let path = sibling_path(&self.storage.bucket_path(file_url)?, "compressed", "jpg");
+        let file_size = rendered.bytes.len() as i64;
+        let file_url = self.storage.put(&path, rendered.bytes, "image/jpeg").await?;
+
+        Ok(Some(RecompressedPhoto { file_url, file_size }))
+    }
+
+    /// Process a document in the background; failures are recorded on the document
+    pub fn spawn_processing(&self, document_id: i32) {
+        let service = self.detached();
+
+        tokio::spawn(async move {
+            if let Err(e) = service.process_document(document_id).await {
+                tracing::warn!("Could not process document {}: {}", document_id, e);
+            }
+        });
+    }
+
+    /// Scan appeal evidence in the background; failures are recorded on the file
+    pub fn spawn_evidence_scan(&self, document_ids: Vec<i32>) {
+        let service = self.detached();
+
+        tokio::spawn(async move {
+            for document_id in document_ids {
+                if let Err(e) = service.scan_evidence(document_id).await {
+                    tracing::warn!("Could not scan appeal document {}: {}", document_id, e);
+                }
+            }
+        });
+    }
+
+    /// Copy of the service for a background task
+    fn detached(&self) -> DocumentService {
+        DocumentService::new(
+            DocumentRepository::new(self.document_repo.pool.clone()),
+            AuditRepository::new(self.document_repo.pool.clone()),
+            DuplicateRepository::new(self.document_repo.pool.clone()),
+            AppealRepository::new(self.document_repo.pool.clone()),
+            self.storage.clone(),
+            self.scanner.clone(),
+            self.recompress_oversized_photos,
+        )
+    }
+
+    /// Scan a quarantined document for malware and compare its hashes with
+    /// other registrations' documents, then strip metadata from images and
+    /// derive their thumbnail and preview, or read the page count and first
+    /// page of PDFs
+    pub async fn process_document(&self, document_id: i32) -> AppResult<Document> {
+        let document = self
+            .document_repo
+            .find_by_id(document_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Document not found".to_string()))?;
+
+        let document = match self.run_pipeline(&document).await {
+            Ok(document) => document,
+            Err(e) => {
+                tracing::warn!("Processing document {} failed: {}", document.id, e);
+                self.document_repo
+                    .record_failed(document.id, &e.to_string())
+                    .await?
+            }
+        };
+
+        // Retries back off until the last attempt gives up on the document
+        if !document.is_unfinished() {
+            return Ok(document);
+        }
+
+        let document = self
+            .document_repo
+            .record_attempt(document.id, STALE_PENDING_MINUTES, MAX_PROCESSING_ATTEMPTS)
+            .await?;
+        if !document.is_unfinished() {
+            tracing::error!(
+                "Gave up on document {} of registration {} after {} attempts",
+                document.id,
+                document.registration_id,
+                document.processing_attempts
+            );
+        }
+
+        Ok(document)
+    }
+
+    /// Retry documents and appeal evidence whose scan or background
+    /// processing never finished
+    pub async fn process_stale(&self) -> AppResult<usize> {
+        let ids = self
+            .document_repo
+            .find_stale_pending(STALE_PENDING_MINUTES, STALE_PENDING_BATCH)
+            .await?;
+
+        for id in &ids {
+            self.process_document(*id).await?;
+        }
+
+        let evidence_ids = self
+            .appeal_repo
+            .find_stale_unscanned(STALE_PENDING_MINUTES, STALE_PENDING_BATCH)
+            .await?;
+
+        for id in &evidence_ids {
+            self.scan_evidence(*id).await?;
+        }
+
+        Ok(ids.len() + evidence_ids.len())
+    }
+
+    /// Scan an appeal evidence file. It stays hidden until found clean;
+    /// infected files are removed, failures are retried with backoff.
+    pub async fn scan_evidence(&self, document_id: i32) -> AppResult<AppealDocument> {
+        let document = self
+            .appeal_repo
+            .find_document_by_id(document_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Appeal document not found".to_string()))?;
+
+        if document.scan_status != "pending" {
+            return Ok(document);
+        }
+
+        let Some(scanner) = &self.scanner else {
+            return self
+                .appeal_repo
+                .record_document_scan(document.id, "skipped", None)
+                .await;
+        };
+
+        let verdict = match self.storage.fetch(&document.file_url).await {
+            Ok(bytes) => scanner.scan(&bytes).await,
+            Err(e) => Err(e),
+        };
+
+        match verdict {
+            Ok(ScanVerdict::Clean) => {
+                self.appeal_repo
+                    .record_document_scan(document.id, "clean", None)
+                    .await
+            }
+            Ok(ScanVerdict::Infected { signature }) => {
+                tracing::error!(
+                    "Evidence {} of appeal {} is infected: {}",
+                    document.id,
+                    document.appeal_id,
+                    signature
+                );
+
+                let infected = self
+                    .appeal_repo
+                    .record_document_scan(document.id, "infected", Some(&signature))
+                    .await?;
+
+                if let Some(path) = self.storage.object_path(&document.file_url) {
+                    if let Err(e) = self.storage.delete(&path).await {
+                        tracing::warn!("Could not delete infected file {}: {}", path, e);
+                    }
+                }
+
+                Ok(infected)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "{} could not scan appeal document {}: {}",
+                    scanner.name(),
+                    document.id,
+                    e
+                );
+                self.appeal_repo
+                    .record_document_scan_error(
+                        document.id,
+                        &e.to_string(),
+                        STALE_PENDING_MINUTES,
+                        MAX_PROCESSING_ATTEMPTS,
+                    )
+                    .await
+            }
+        }
+    }
+
+    pub async fn find_artifacts(&self, document_ids: &[i32]) -> AppResult<Vec<DocumentArtifact>> {
+        if document_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.document_repo.find_artifacts(document_ids).await
+    }
+
+    /// Matches of the documents with other registrations' documents
+    pub async fn find_duplicates(&self, document_ids: &[i32]) -> AppResult<Vec<DocumentDuplicate>> {
+        if document_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.duplicate_repo.find_document_duplicates(document_ids).await
+    }
+
+    async fn run_pipeline(&self, document: &Document) -> AppResult<Document> {
+        let bytes = self.storage.fetch(&document.file_url).await?;
+
+        // Nothing is derived from files that are quarantined or infected
+        let document = &self.scan(document, &bytes).await?;
+        if !document.is_servable() {
+            return Ok(document.clone());
+        }
+
+        let document = &match document.content_hash {
+            Some(_) => document.clone(),
+            None => self.check_duplicates(document, &bytes).await?,
+        };
+        if !matches!(document.processing_status.as_str(), "pending" | "failed")
+        {
+            return Ok(document.clone());
+        }
+
+        if is_image(&document.mime_type) {
+            let mime_type = document.mime_type.clone();
+            let processed = tokio::task::spawn_blocking(move || process_image(bytes, mime_type))
+                .await
+                .map_err(join_error)??;
+
+            return self.store_image(document, processed).await;
+        }
+
+        if document.mime_type == "application/pdf" {
+            let info = tokio::task::spawn_blocking(move || pdf_info(&bytes))
+                .await
+                .map_err(join_error)??;
+
+            return self
+                .document_repo
+                .record_processed(
+                    document.id,
+                    &document.file_url,
+                    document.file_size,
+                    &document.mime_type,
+                    Some(info.page_count as i32),
+                    &pdf_metadata(&info),
+                    &[],
+                )
+                .await;
+        }
+
+        Err(AppError::Validation(format!(
+            "Unsupported document type {}",
+            document.mime_type
+        )))
+    }
+
+    /// Release the document from quarantine or reject it. Scanner failures
+    /// keep it quarantined until the scheduler retries.
+    async fn scan(&self, document: &Document, bytes: &[u8]) -> AppResult<Document> {
+        if document.scan_status != "pending" {
+            return Ok(document.clone());
+        }
+
+        let Some(scanner) = &self.scanner else {
+            return self.document_repo.record_scan(document.id, "skipped").await;
+        };
+
+        match scanner.scan(bytes).await {
+            Ok(ScanVerdict::Clean) => self.document_repo.record_scan(document.id, "clean").await,
+            Ok(ScanVerdict::Infected { signature }) => self.reject_infected(document, &signature).await,
+            Err(e) => {
+                tracing::warn!("{} could not scan document {}: {}", scanner.name(), document.id, e);
+                self.document_repo
+                    .record_scan_error(document.id, &e.to_string())
+                    .await
+            }
+        }
+    }
+
+    /// Record the document's hashes and flag other students' registrations
+    /// whose documents match
+    async fn check_duplicates(&self, document: &Document, bytes: &[u8]) -> AppResult<Document> {
+        let content_hash = content_hash(bytes);
+        let perceptual_hash = if is_image(&document.mime_type) {
+            let bytes = bytes.to_vec();
+            tokio::task::spawn_blocking(move || {
+                decode_image(&bytes).ok().map(|d| perceptual_hash(&d.image))
+            })
+            .await
+            .map_err(join_error)?
+        } else {
+            None
+        };
+
+        let document = self
+            .document_repo
+            .record_hashes(document.id, &content_hash, perceptual_hash.map(|h| h as i64))
+            .await?;
+
+        let candidates = self
+            .duplicate_repo
+            .find_document_candidates(
+                document.id,
+                &content_hash,
+                perceptual_hash.map(|h| h as i64),
+                PERCEPTUAL_MATCH_DISTANCE as i32,
+            )
+            .await?;
+
+        let matches: Vec<(i32, i32, String)> =
+            detect_document_duplicates(&content_hash, perceptual_hash, &candidates)
+                .into_iter()
+                .map(|m| (m.registration_id, m.document_id, m.match_type.as_str().to_string()))
+                .collect();
+        if matches.is_empty() {
+            return Ok(document);
+        }
+
+        let flags = self
+            .duplicate_repo
+            .create_document_flags(document.registration_id, document.id, &matches)
+            .await?;
+        if !flags.is_empty() {
+            tracing::warn!(
+                "Document {} of registration {} matches documents of {} other registration(s)",
+                document.id,
+                document.registration_id,
+                flags.len()
+            );
+        }
+
+        Ok(document)
+    }
+
+    /// Reject an infected document, remove its file and log it
+    async fn reject_infected(&self, document: &Document, signature: &str) -> AppResult<Document> {
+        tracing::error!(
+            "Document {} of registration {} is infected: {}",
+            document.id,
+            document.registration_id,
+            signature
+        );
+
+        let infected = self
+            .document_repo
+            .record_infected(document.id, signature, INFECTED_NOTES)
+            .await?;
+
+        if let Some(path) = self.storage.object_path(&document.file_url) {
+            if let Err(e) = self.storage.delete(&path).await {
+                tracing::warn!("Could not delete infected file {}: {}", path, e);
+            }
+        }
+
+        let school_id = self.document_repo.find_school_id(document.id).await?;
+        self.audit_repo
+            .create(&NewAuditLog {
+                school_id,
+                user_id: None,
+                entity_type: "document",
+                entity_id: document.id,
+                action: AuditAction::Reject,
+                old_value: Some(json!({
+                    "scan_status": document.scan_status,
+                    "verification_status": document.verification_status,
+                })),
+                new_value: Some(json!({
+                    "scan_status": infected.scan_status,
+                    "scan_signature": signature,
+                    "verification_status": infected.verification_status,
+                })),
+            })
+            .await?;
+
+        Ok(infected)
+    }
+
+    /// Replace the original with its sanitized copy and store the derived
+    /// images next to it
+    async fn store_image(&self, document: &Document, processed: ProcessedImage) -> AppResult<Document> {
+        let path = self.storage.bucket_path(&document.file_url)?;
+
+        let file_size = processed.original.len() as i64;
+        let file_url = self
+            .storage
+            .put(&path, processed.original, &processed.mime_type)
+            .await?;
+
+        let mut artifacts = Vec::new();
+        for (kind, rendered) in [("thumbnail", processed.thumbnail), ("preview", processed.preview)] {
+            let file_size = rendered.bytes.len() as i64;
+            let file_url = self
+                .storage
+                .put(&sibling_path(&path, kind, "jpg"), rendered.bytes, "image/jpeg")
+                .await?;
+
+            artifacts.push(NewDocumentArtifact {
+                kind,
+                file_url,
+                file_size,
+                mime_type: "image/jpeg".to_string(),
+                width: rendered.width as i32,
+                height: rendered.height as i32,
+            });
+        }
+
+        let metadata = json!({
+            "width": processed.width,
+            "height": processed.height,
+            "metadata_stripped": true,
+            "reoriented": processed.reoriented,
+        });
+
+        self.document_repo
+            .record_processed(
+                document.id,
+                &file_url,
+                file_size,
+                &processed.mime_type,
+                None,
+                &metadata,
+                &artifacts,
+            )
+            .await
+    }
+}
+
+fn process_image(bytes: Vec<u8>, mime_type: String) -> AppResult<ProcessedImage> {
+    let decoded = decode_image(&bytes)?;
+    let original = sanitize_image(&bytes, &mime_type, &decoded)?;
+
+    // Reoriented images are re-encoded as JPEG
+    let mime_type = if decoded.reoriented {
+        "image/jpeg".to_string()
+    } else {
+        mime_type
+    };
+
+    Ok(ProcessedImage {
+        original,
+        mime_type,
+        reoriented: decoded.reoriented,
+        width: decoded.image.width(),
+        height: decoded.image.height(),
+        thumbnail: render_thumbnail(&decoded.image)?,
+        preview: render_preview(&decoded.image)?,
+    })
+}
+
+fn pdf_metadata(info: &PdfInfo) -> serde_json::Value {
+    match &info.first_page {
+        Some(page) => json!({
+            "first_page": {
+                "width": page.width,
+                "height": page.height,
+                "rotation": page.rotation,
+                "orientation": page.orientation(),
+            }
+        }),
+        None => json!({}),
+    }
+}
+
+fn join_error(e: tokio::task::JoinError) -> AppError {
+    AppError::Internal(format!("Document processing task failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::document_processing::PdfPage;
+
+    #[test]
+    fn test_pdf_metadata() {
+        let info = PdfInfo {
+            page_count: 3,
+            first_page: Some(PdfPage {
+                width: 842.0,
+                height: 595.0,
+                rotation: 90,
+            }),
+        };
+
+        let metadata = pdf_metadata(&info);
+        assert_eq!(metadata["first_page"]["width"], 842.0);
+        assert_eq!(metadata["first_page"]["rotation"], 90);
+        assert_eq!(metadata["first_page"]["orientation"], "landscape");
+
+        let empty = PdfInfo {
+            page_count: 0,
+            first_page: None,
+        };
+        assert_eq!(pdf_metadata(&empty), json!({}));
+    }
+}
@@ -0,0 +1,576 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::Utc;
+
+use crate::models::duplicate::{
+    DocumentCluster, DocumentFlagEdge, DocumentHashCandidate, DuplicateFlag, DuplicateFlagDetail,
+    DuplicateFlagStatus, DuplicateMatchType, DuplicatePolicy,
+};
+use crate::models::period::Period;
+use crate::models::registration::Registration;
+use crate::repositories::duplicate_repo::DuplicateRepository;
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::services::registration_service::{follow_up_withdrawal, plan_withdrawal};
+use crate::utils::document_processing::hamming_distance;
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::timezone::format_local;
+
+/// Reason stored on registrations withdrawn because the student enrolled elsewhere
+const WITHDRAWN_REASON: &str = "Siswa telah melakukan daftar ulang di sekolah lain.";
+
+/// Largest number of differing perceptual hash bits for two images to count
+/// as the same scan
+pub const PERCEPTUAL_MATCH_DISTANCE: u32 = 6;
+
+/// Another registration sharing the student NISN or parent NIK
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateMatch {
+    pub registration_id: i32,
+    pub school_id: i32,
+    pub match_type: DuplicateMatchType,
+}
+
+/// Document of another registration matching an uploaded document
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentMatch {
+    pub registration_id: i32,
+    pub document_id: i32,
+    pub match_type: DuplicateMatchType,
+}
+
+pub struct DuplicateService {
+    duplicate_repo: DuplicateRepository,
+    registration_repo: RegistrationRepository,
+    period_repo: PeriodRepository,
+}
+
+impl DuplicateService {
+    pub fn new(
+        duplicate_repo: DuplicateRepository,
+        registration_repo: RegistrationRepository,
+        period_repo: PeriodRepository,
+    ) -> Self {
+        Self {
+            duplicate_repo,
+            registration_repo,
+            period_repo,
+        }
+    }
+
+    /// Look for duplicates of a registration across all schools and apply the
+    /// period's policy. Returns the matches that should be flagged for review.
+    pub async fn check_registration(
+        &self,
+        period: &Period,
+        student_nisn: &str,
+        parent_nik: &str,
+        registration_id: Option<i32>,
+    ) -> AppResult<Vec<DuplicateMatch>> {
+        let policy =
+            DuplicatePolicy::from_str(&period.duplicate_policy).unwrap_or(DuplicatePolicy::Warn);
+
+        if policy == DuplicatePolicy::Allow {
+            return Ok(Vec::new());
+        }
+
+        let candidates = self
+            .duplicate_repo
+            .find_candidates(&period.academic_year, student_nisn, parent_nik, registration_id)
+            .await?;
+
+        let matches = detect_duplicates(student_nisn, parent_nik, &candidates);
+
+        apply_policy(&policy, matches)
+    }
+
+    pub async fn record_flags(
+        &self,
+        registration_id: i32,
+        matches: &[DuplicateMatch],
+    ) -> AppResult<Vec<DuplicateFlag>> {
+        if matches.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let entries: Vec<(i32, String)> = matches
+            .iter()
+            .map(|m| (m.registration_id, m.match_type.as_str().to_string()))
+            .collect();
+
+        let flags = self
+            .duplicate_repo
+            .create_flags(registration_id, &entries)
+            .await?;
+
+        if !flags.is_empty() {
+            tracing::warn!(
+                "Registration {} flagged as possible duplicate of {} registration(s)",
+                registration_id,
+                flags.len()
+            );
+        }
+
+        Ok(flags)
+    }
+
+    /// Suspicious clusters of a period: documents linked by hash matches
+    /// where at least one registration belongs to the period, largest first
+    pub async fn document_clusters(
+        &self,
+        period_id: i32,
+        school_id: Option<i32>,
+    ) -> AppResult<Vec<DocumentCluster>> {
+        let period = self
+            .period_repo
+            .find_by_id(period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+
+        if let Some(school_id) = school_id {
+            if period.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have permission to access this period".to_string(),
+                ));
+            }
+        }
+
+        let edges = self.duplicate_repo.find_document_flag_edges(period_id).await?;
+        let components = cluster_documents(&edges);
+
+        let ids: Vec<i32> = components.iter().flatten().copied().collect();
+        let mut documents: BTreeMap<i32, _> = self
+            .duplicate_repo
+            .find_cluster_documents(&ids)
+            .await?
+            .into_iter()
+            .map(|d| (d.document_id, d))
+            .collect();
+
+        Ok(components
+            .into_iter()
+            .map(|members| {
+                let cluster_edges: Vec<&DocumentFlagEdge> = edges
+                    .iter()
+                    .filter(|e| members.binary_search(&e.document_id).is_ok())
+                    .collect();
+                let match_types: BTreeSet<String> =
+                    cluster_edges.iter().map(|e| e.match_type.clone()).collect();
+
+                DocumentCluster {
+                    documents: members.iter().filter_map(|id| documents.remove(id)).collect(),
+                    match_types: match_types.into_iter().collect(),
+                    open_flags: cluster_edges
+                        .iter()
+                        .filter(|e| e.status == DuplicateFlagStatus::Open.as_str())
+                        .count() as i64,
+                }
+            })
+            .collect())
+    }
+
+    pub async fn list_flags(
+        &self,
+        school_id: Option<i32>,
+        status: Option<String>,
+        match_type: Option<String>,
+        page: i64,
+        page_size: i64,
+    ) -> AppResult<(Vec<DuplicateFlagDetail>, i64)> {
+        let offset = (page - 1) * page_size;
+
+        let flags = self
+            .duplicate_repo
+            .find_flags(school_id, status.clone(), match_type.clone(), page_size, offset)
+            .await?;
+
+        let total = self
+            .duplicate_repo
+            .count_flags(school_id, status, match_type)
+            .await?;
+
+        Ok((flags, total))
+    }
+
+    /// School admins only see flags involving one of their registrations
+    pub async fn get_flag(&self, id: i32, school_id: Option<i32>) -> AppResult<DuplicateFlagDetail> {
+        let flag = self
+            .duplicate_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Duplicate flag not found".to_string()))?;
+
+        if let Some(school_id) = school_id {
+            if flag.registration_school_id != school_id && flag.matched_school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have permission to access this duplicate flag".to_string(),
+                ));
+            }
+        }
+
+        Ok(flag)
+    }
+
+    pub async fn review_flag(
+        &self,
+        id: i32,
+        school_id: Option<i32>,
+        status: String,
+        notes: Option<String>,
+        reviewed_by: i32,
+    ) -> AppResult<DuplicateFlagDetail> {
+        match DuplicateFlagStatus::from_str(&status) {
+            Some(DuplicateFlagStatus::Dismissed) | Some(DuplicateFlagStatus::Confirmed) => {}
+            _ => {
+                return Err(AppError::Validation(
+                    "Review status must be 'dismissed' or 'confirmed'".to_string(),
+                ))
+            }
+        }
+
+        let flag = self.get_flag(id, school_id).await?;
+
+        if flag.flag.status != DuplicateFlagStatus::Open.as_str() {
+            return Err(AppError::Validation(
+                "Duplicate flag has already been reviewed".to_string(),
+            ));
+        }
+
+        self.duplicate_repo
+            .review_flag(id, &status, notes.as_deref(), reviewed_by)
+            .await?;
+
+        tracing::info!(
+            "Duplicate flag {} marked as {} by admin {}",
+            id,
+            status,
+            reviewed_by
+        );
+
+        self.get_flag(id, school_id).await
+    }
+
+    /// Re-enroll an accepted student. Their other registrations in the same
+    /// academic year are withdrawn so the seats can go to other applicants.
+    pub async fn enroll_registration(
+        &self,
+        id: i32,
+        school_id: Option<i32>,
+        admin_id: i32,
+    ) -> AppResult<EnrollmentResult> {
+        let registration = self
+            .registration_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))?;
+
+        if let Some(school_id) = school_id {
+            if registration.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have permission to enroll this registration".to_string(),
+                ));
+            }
+        }
+
+        if registration.status != "accepted" {
+            return Err(AppError::Validation(
+                "Can only enroll registrations in accepted status".to_string(),
+            ));
+        }
+
+        let period = self
+            .period_repo
+            .find_by_id(registration.period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+
+        if let Some(closes_at) = period.reenrollment_closes_at {
+            if Utc::now() > closes_at {
+                let timezone = self.period_repo.find_school_timezone(period.school_id).await?;
+                return Err(AppError::Validation(format!(
+                    "Re-enrollment closed at {}",
+                    format_local(closes_at, timezone)
+                )));
+            }
+        }
+
+        let others = self
+            .duplicate_repo
+            .find_active_by_nisn(&period.academic_year, &registration.student_nisn, id)
+            .await?;
+
+        if others.iter().any(|r| r.status == "enrolled") {
+            return Err(AppError::Conflict(
+                "Student is already enrolled at another school".to_string(),
+            ));
+        }
+
+        // Withdrawals follow each period's refund and waitlist policies
+        let mut withdrawals = Vec::with_capacity(others.len());
+        for other in others {
+            let plan = plan_withdrawal(&self.period_repo, &other).await?;
+            withdrawals.push((other, plan));
+        }
+
+        let (enrolled, withdrawn) = self
+            .duplicate_repo
+            .enroll_registration(id, &withdrawals, WITHDRAWN_REASON, admin_id)
+            .await?;
+
+        tracing::info!(
+            "Registration {} enrolled by admin {}, {} other registration(s) withdrawn",
+            id,
+            admin_id,
+            withdrawn.len()
+        );
+        for result in &withdrawn {
+            follow_up_withdrawal(result);
+        }
+
+        Ok(EnrollmentResult {
+            registration: enrolled,
+            withdrawn_registration_ids: withdrawn.iter().map(|r| r.registration.id).collect(),
+        })
+    }
+}
+
+/// Hasil daftar ulang
+#[derive(Debug, Clone)]
+pub struct EnrollmentResult {
+    pub registration: Registration,
+    pub withdrawn_registration_ids: Vec<i32>,
+}
+
+/// A NISN match means the same student; a parent NIK match on a different
+/// NISN may be a sibling or a fabricated child and is left for review.
+pub fn detect_duplicates(
+    student_nisn: &str,
+    parent_nik: &str,
+    candidates: &[Registration],
+) -> Vec<DuplicateMatch> {
+    candidates
+        .iter()
+        .filter_map(|candidate| {
+            let match_type = if candidate.student_nisn == student_nisn {
+                DuplicateMatchType::Nisn
+            } else if candidate.parent_nik == parent_nik {
+                DuplicateMatchType::ParentNik
+            } else {
+                return None;
+            };
+
+            Some(DuplicateMatch {
+                registration_id: candidate.id,
+                school_id: candidate.school_id,
+                match_type,
+            })
+        })
+        .collect()
+}
+
+/// Identical files match by content; images also match when their perceptual
+/// hashes are close. One match per registration and kind is kept.
+pub fn detect_document_duplicates(
+    content_hash: &str,
+    perceptual_hash: Option<u64>,
+    candidates: &[DocumentHashCandidate],
+) -> Vec<DocumentMatch> {
+    let mut matches: Vec<DocumentMatch> = Vec::new();
+
+    for candidate in candidates {
+        let match_type = if candidate.content_hash.as_deref() == Some(content_hash) {
+            DuplicateMatchType::DocumentContent
+        } else if let (Some(hash), Some(other)) = (perceptual_hash, candidate.perceptual_hash) {
+            if hamming_distance(hash, other as u64) > PERCEPTUAL_MATCH_DISTANCE {
+                continue;
+            }
+            DuplicateMatchType::DocumentImage
+        } else {
+            continue;
+        };
+
+        let seen = matches
+            .iter()
+            .any(|m| m.registration_id == candidate.registration_id && m.match_type == match_type);
+        if !seen {
+            matches.push(DocumentMatch {
+                registration_id: candidate.registration_id,
+                document_id: candidate.document_id,
+                match_type,
+            });
+        }
+    }
+
+    matches
+}
+
+/// Group documents connected by matches. Members are sorted by id and
+/// clusters by size, largest first.
+pub fn cluster_documents(edges: &[DocumentFlagEdge]) -> Vec<Vec<i32>> {
+    fn root(parents: &mut BTreeMap<i32, i32>, id: i32) -> i32 {
+        let parent = *parents.entry(id).or_insert(id);
+        if parent == id {
+            return id;
+        }
+        let found = root(parents, parent);
+        parents.insert(id, found);
+        found
+    }
+
+    let mut parents = BTreeMap::new();
+    for edge in edges {
+        let a = root(&mut parents, edge.document_id);
+        let b = root(&mut parents, edge.matched_document_id);
+        if a != b {
+            parents.insert(a.max(b), a.min(b));
+        }
+    }
+
+    let ids: Vec<i32> = parents.keys().copied().collect();
+    let mut clusters: BTreeMap<i32, Vec<i32>> = BTreeMap::new();
+    for id in ids {
+        let cluster = root(&mut parents, id);
+        clusters.entry(cluster).or_default().push(id);
+    }
+
+    let mut clusters: Vec<Vec<i32>> = clusters.into_values().collect();
+    clusters.sort_by(|a, b| b.len().cmp(&a.len()).then(a[0].cmp(&b[0])));
+    clusters
+}
+
+/// `block` rejects NISN duplicates outright; parent NIK matches are only ever
+/// flagged since siblings legitimately share a parent.
+pub fn apply_policy(
+    policy: &DuplicatePolicy,
+    matches: Vec<DuplicateMatch>,
+) -> AppResult<Vec<DuplicateMatch>> {
+    match policy {
+        DuplicatePolicy::Allow => Ok(Vec::new()),
+        DuplicatePolicy::Block
+            if matches
+                .iter()
+                .any(|m| m.match_type == DuplicateMatchType::Nisn) =>
+        {
+            Err(AppError::Conflict(
+                "Student with this NISN already has an active registration in this academic year"
+                    .to_string(),
+            ))
+        }
+        _ => Ok(matches),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registration(id: i32, school_id: i32, nisn: &str, nik: &str) -> Registration {
+        Registration {
+            id,
+            school_id,
+            student_nisn: nisn.to_string(),
+            parent_nik: nik.to_string(),
+            status: "submitted".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_detect_duplicates_by_nisn_and_parent_nik() {
+        let candidates = vec![
+            registration(1, 10, "0012345678", "3201010101010001"),
+            registration(2, 11, "0099999999", "3201010101010001"),
+            registration(3, 12, "0011111111", "3201010101010999"),
+        ];
+
+        let matches = detect_duplicates("0012345678", "3201010101010001", &candidates);
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].registration_id, 1);
+        assert_eq!(matches[0].match_type, DuplicateMatchType::Nisn);
+        assert_eq!(matches[1].registration_id, 2);
+        assert_eq!(matches[1].match_type, DuplicateMatchType::ParentNik);
+    }
+
+    #[test]
+    fn test_detect_document_duplicates() {
+        let candidate = |document_id: i32, registration_id: i32, content: &str, perceptual: Option<u64>| {
+            DocumentHashCandidate {
+                document_id,
+                registration_id,
+                content_hash: Some(content.to_string()),
+                perceptual_hash: perceptual.map(|h| h as i64),
+            }
+        };
+        let hash = 0xF0F0_0000_FFFF_1234u64;
+        let candidates = vec![
+            candidate(1, 10, "abc", None),
+            candidate(2, 10, "abc", None),
+            candidate(3, 11, "def", Some(hash ^ 0b101)),
+            candidate(4, 12, "ghi", Some(!hash)),
+            candidate(5, 13, "jkl", None),
+        ];
+
+        let matches = detect_document_duplicates("abc", Some(hash), &candidates);
+
+        assert_eq!(
+            matches,
+            vec![
+                DocumentMatch {
+                    registration_id: 10,
+                    document_id: 1,
+                    match_type: DuplicateMatchType::DocumentContent,
+                },
+                DocumentMatch {
+                    registration_id: 11,
+                    document_id: 3,
+                    match_type: DuplicateMatchType::DocumentImage,
+                },
+            ]
+        );
+
+        // PDFs only match by content
+        let matches = detect_document_duplicates("zzz", None, &candidates);
+        assert!(matches.is_empty());
+    }
+
+    #[test]
+    fn test_cluster_documents() {
+        let edge = |document_id: i32, matched_document_id: i32| DocumentFlagEdge {
+            document_id,
+            matched_document_id,
+            match_type: "document_content".to_string(),
+            status: "open".to_string(),
+        };
+
+        let clusters = cluster_documents(&[edge(5, 2), edge(9, 7), edge(2, 8), edge(8, 5)]);
+
+        assert_eq!(clusters, vec![vec![2, 5, 8], vec![7, 9]]);
+        assert!(cluster_documents(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_block_policy_rejects_nisn_duplicates_only() {
+        let nisn = vec![DuplicateMatch {
+            registration_id: 1,
+            school_id: 10,
+            match_type: DuplicateMatchType::Nisn,
+        }];
+        let parent_nik = vec![DuplicateMatch {
+            registration_id: 2,
+            school_id: 11,
+            match_type: DuplicateMatchType::ParentNik,
+        }];
+
+        assert!(matches!(
+            apply_policy(&DuplicatePolicy::Block, nisn.clone()),
+            Err(AppError::Conflict(_))
+        ));
+        assert_eq!(
+            apply_policy(&DuplicatePolicy::Block, parent_nik.clone()).unwrap(),
+            parent_nik
+        );
+        assert_eq!(apply_policy(&DuplicatePolicy::Warn, nisn.clone()).unwrap(), nisn);
+        assert!(apply_policy(&DuplicatePolicy::Allow, nisn).unwrap().is_empty());
+    }
+}
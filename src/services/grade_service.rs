@@ -0,0 +1,522 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::models::registration::{Registration, RegistrationGrade};
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::utils::error::{AppError, AppResult, FieldError};
+
+/// Grade entered by the applicant for one subject in one semester
+#[derive(Debug, Clone)]
+pub struct GradeInput {
+    pub semester: i32,
+    pub subject: String,
+    pub score: f64,
+}
+
+/// Subjects and semesters a path averages, from `rapor_subjects` and
+/// `rapor_semesters` in its scoring_config. Unset means all entered ones.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RaporRequirements {
+    pub subjects: Option<Vec<String>>,
+    pub semesters: Option<Vec<i32>>,
+}
+
+impl RaporRequirements {
+    pub fn from_scoring_config(scoring_config: &Value) -> Self {
+        let subjects = scoring_config
+            .get("rapor_subjects")
+            .and_then(|v| v.as_array())
+            .map(|subjects| {
+                subjects
+                    .iter()
+                    .filter_map(|s| s.as_str())
+                    .map(normalize_subject)
+                    .collect()
+            });
+
+        let semesters = scoring_config
+            .get("rapor_semesters")
+            .and_then(|v| v.as_array())
+            .map(|semesters| {
+                semesters
+                    .iter()
+                    .filter_map(|s| s.as_i64())
+                    .map(|s| s as i32)
+                    .collect()
+            });
+
+        Self {
+            subjects,
+            semesters,
+        }
+    }
+}
+
+pub struct GradeService {
+    registration_repo: RegistrationRepository,
+    period_repo: PeriodRepository,
+}
+
+impl GradeService {
+    pub fn new(registration_repo: RegistrationRepository, period_repo: PeriodRepository) -> Self {
+        Self {
+            registration_repo,
+            period_repo,
+        }
+    }
+
+    async fn get_registration(&self, id: i32) -> AppResult<Registration> {
+        self.registration_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))
+    }
+
+    /// The average is computed with the configuration of the primary path
+    async fn requirements(&self, registration: &Registration) -> AppResult<RaporRequirements> {
+        let path = self
+            .period_repo
+            .find_path_by_id(registration.path_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+
+        Ok(RaporRequirements::from_scoring_config(&path.scoring_config))
+    }
+
+    pub async fn get_summary(&self, registration_id: i32) -> AppResult<GradeSummary> {
+        let registration = self.get_registration(registration_id).await?;
+        let requirements = self.requirements(&registration).await?;
+        let grades = self
+            .registration_repo
+            .find_grades_by_registration(registration_id)
+            .await?;
+
+        Ok(build_summary(registration_id, &requirements, &grades))
+    }
+
+    pub async fn save_grades(
+        &self,
+        registration_id: i32,
+        grades: Vec<GradeInput>,
+    ) -> AppResult<GradeSummary> {
+        let registration = self.get_registration(registration_id).await?;
+
+        if registration.status != "draft" {
+            return Err(AppError::Validation(
+                "Can only update grades of registrations in draft status".to_string(),
+            ));
+        }
+
+        let errors = validate_grades(&grades);
+        if !errors.is_empty() {
+            return Err(AppError::FieldValidation(errors));
+        }
+
+        let requirements = self.requirements(&registration).await?;
+        let entries: Vec<(i32, String, f64)> = grades
+            .into_iter()
+            .map(|g| (g.semester, normalize_subject(&g.subject), g.score))
+            .collect();
+
+        let (rapor_average, _) = compute_rapor_average(&entries, &requirements);
+
+        let saved = self
+            .registration_repo
+            .replace_grades(registration_id, &entries, rapor_average)
+            .await?;
+
+        Ok(build_summary(registration_id, &requirements, &saved))
+    }
+
+    pub async fn verify_semester(
+        &self,
+        registration_id: i32,
+        semester: i32,
+        document_id: Option<i32>,
+        school_id: Option<i32>,
+        admin_id: i32,
+    ) -> AppResult<GradeSummary> {
+        let registration = self.get_registration(registration_id).await?;
+
+        if let Some(school_id) = school_id {
+            if registration.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have permission to verify this registration".to_string(),
+                ));
+            }
+        }
+
+        if !["submitted", "verified"].contains(&registration.status.as_str()) {
+            return Err(AppError::Validation(
+                "Can only verify grades of submitted registrations".to_string(),
+            ));
+        }
+
+        if let Some(document_id) = document_id {
+            let document = self
+                .registration_repo
+                .find_document_by_id(document_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Document not found".to_string()))?;
+
+            if document.registration_id != registration_id || document.document_type != "rapor" {
+                return Err(AppError::Validation(
+                    "Document must be a rapor of this registration".to_string(),
+                ));
+            }
+        }
+
+        let updated = self
+            .registration_repo
+            .verify_grade_semester(registration_id, semester, document_id, admin_id)
+            .await?;
+
+        if updated == 0 {
+            return Err(AppError::NotFound(format!(
+                "No grades entered for semester {}",
+                semester
+            )));
+        }
+
+        tracing::info!(
+            "Semester {} grades of registration {} verified by admin {}",
+            semester,
+            registration_id,
+            admin_id
+        );
+
+        self.get_summary(registration_id).await
+    }
+}
+
+/// Ringkasan nilai rapor pendaftaran
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GradeSummary {
+    /// ID pendaftaran
+    #[schema(example = 1)]
+    pub registration_id: i32,
+
+    /// Rata-rata rapor yang dihitung server (kosong bila nilai belum lengkap)
+    #[schema(example = 86.25)]
+    pub rapor_average: Option<f64>,
+
+    /// Mata pelajaran yang dihitung (kosong berarti semua yang diisi)
+    #[schema(example = json!(["matematika", "bahasa_indonesia"]))]
+    pub required_subjects: Option<Vec<String>>,
+
+    /// Semester yang dihitung (kosong berarti semua yang diisi)
+    #[schema(example = json!([1, 2, 3, 4, 5]))]
+    pub required_semesters: Option<Vec<i32>>,
+
+    /// Nilai yang masih harus diisi
+    pub missing: Vec<MissingGrade>,
+
+    /// Nilai per semester
+    pub semesters: Vec<SemesterGrades>,
+}
+
+/// Nilai yang belum diisi
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct MissingGrade {
+    /// Semester
+    #[schema(example = 5)]
+    pub semester: i32,
+
+    /// Mata pelajaran
+    #[schema(example = "matematika")]
+    pub subject: String,
+}
+
+/// Nilai rapor dalam satu semester
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SemesterGrades {
+    /// Semester
+    #[schema(example = 1)]
+    pub semester: i32,
+
+    /// Rata-rata semester
+    #[schema(example = 85.5)]
+    pub average: f64,
+
+    /// Sudah dicocokkan admin dengan dokumen rapor
+    #[schema(example = true)]
+    pub verified: bool,
+
+    /// ID admin yang memverifikasi
+    #[schema(example = 2)]
+    pub verified_by: Option<i32>,
+
+    /// Waktu verifikasi
+    #[schema(value_type = Option<String>, example = "2024-06-10T08:00:00Z")]
+    pub verified_at: Option<DateTime<Utc>>,
+
+    /// ID dokumen rapor yang dicocokkan
+    #[schema(example = 3)]
+    pub document_id: Option<i32>,
+
+    /// Nilai per mata pelajaran
+    pub grades: Vec<SubjectGrade>,
+}
+
+/// Nilai satu mata pelajaran
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SubjectGrade {
+    /// Mata pelajaran
+    #[schema(example = "matematika")]
+    pub subject: String,
+
+    /// Nilai (0-100)
+    #[schema(example = 88.0)]
+    pub score: f64,
+}
+
+/// Subjects are stored lowercase with underscores ("Bahasa Indonesia" -> "bahasa_indonesia")
+pub fn normalize_subject(subject: &str) -> String {
+    subject
+        .trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+pub fn validate_grades(grades: &[GradeInput]) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let mut seen = BTreeSet::new();
+
+    for (index, grade) in grades.iter().enumerate() {
+        let field = |name: &str| format!("grades[{}].{}", index, name);
+
+        if !(1..=12).contains(&grade.semester) {
+            errors.push(FieldError::new(
+                &field("semester"),
+                "Must be between 1 and 12",
+            ));
+        }
+
+        let subject = normalize_subject(&grade.subject);
+        if subject.is_empty() || subject.len() > 50 {
+            errors.push(FieldError::new(
+                &field("subject"),
+                "Must be between 1 and 50 characters",
+            ));
+        } else if !seen.insert((grade.semester, subject)) {
+            errors.push(FieldError::new(
+                &field("subject"),
+                "Is entered more than once for this semester",
+            ));
+        }
+
+        if !(0.0..=100.0).contains(&grade.score) {
+            errors.push(FieldError::new(
+                &field("score"),
+                "Must be between 0 and 100",
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Average of the required subject/semester grades. Returns no average while
+/// any required grade is missing, together with the missing ones.
+pub fn compute_rapor_average(
+    grades: &[(i32, String, f64)],
+    requirements: &RaporRequirements,
+) -> (Option<f64>, Vec<MissingGrade>) {
+    let semesters: BTreeSet<i32> = match &requirements.semesters {
+        Some(semesters) => semesters.iter().copied().collect(),
+        None => grades.iter().map(|(semester, _, _)| *semester).collect(),
+    };
+
+    let subjects: BTreeSet<String> = match &requirements.subjects {
+        Some(subjects) => subjects.iter().cloned().collect(),
+        None => grades
+            .iter()
+            .map(|(_, subject, _)| subject.clone())
+            .collect(),
+    };
+
+    let scores: BTreeMap<(i32, &str), f64> = grades
+        .iter()
+        .map(|(semester, subject, score)| ((*semester, subject.as_str()), *score))
+        .collect();
+
+    let mut missing = Vec::new();
+    let mut total = 0.0;
+    let mut count = 0;
+
+    for semester in &semesters {
+        for subject in &subjects {
+            match scores.get(&(*semester, subject.as_str())) {
+                Some(score) => {
+                    total += score;
+                    count += 1;
+                }
+                None => missing.push(MissingGrade {
+                    semester: *semester,
+                    subject: subject.clone(),
+                }),
+            }
+        }
+    }
+
+    if count == 0 || !missing.is_empty() {
+        return (None, missing);
+    }
+
+    (Some(total / count as f64), missing)
+}
+
+fn build_summary(
+    registration_id: i32,
+    requirements: &RaporRequirements,
+    grades: &[RegistrationGrade],
+) -> GradeSummary {
+    let entries: Vec<(i32, String, f64)> = grades
+        .iter()
+        .map(|g| (g.semester, g.subject.clone(), g.score))
+        .collect();
+    let (rapor_average, missing) = compute_rapor_average(&entries, requirements);
+
+    let mut by_semester: BTreeMap<i32, Vec<&RegistrationGrade>> = BTreeMap::new();
+    for grade in grades {
+        by_semester.entry(grade.semester).or_default().push(grade);
+    }
+
+    let semesters = by_semester
+        .into_iter()
+        .map(|(semester, grades)| {
+            let average = grades.iter().map(|g| g.score).sum::<f64>() / grades.len() as f64;
+            let verified = grades.iter().all(|g| g.verified_at.is_some());
+
+            SemesterGrades {
+                semester,
+                average,
+                verified,
+                verified_by: grades[0].verified_by,
+                verified_at: grades[0].verified_at,
+                document_id: grades[0].document_id,
+                grades: grades
+                    .iter()
+                    .map(|g| SubjectGrade {
+                        subject: g.subject.clone(),
+                        score: g.score,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    GradeSummary {
+        registration_id,
+        rapor_average,
+        required_subjects: requirements.subjects.clone(),
+        required_semesters: requirements.semesters.clone(),
+        missing,
+        semesters,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn grade(semester: i32, subject: &str, score: f64) -> (i32, String, f64) {
+        (semester, subject.to_string(), score)
+    }
+
+    #[test]
+    fn test_compute_rapor_average_uses_configured_subjects_and_semesters() {
+        let requirements = RaporRequirements::from_scoring_config(&json!({
+            "rapor_subjects": ["Matematika", "Bahasa Indonesia"],
+            "rapor_semesters": [1, 2]
+        }));
+
+        let grades = vec![
+            grade(1, "matematika", 80.0),
+            grade(1, "bahasa_indonesia", 90.0),
+            grade(2, "matematika", 70.0),
+            grade(2, "bahasa_indonesia", 100.0),
+            // Not configured, ignored
+            grade(2, "seni_budaya", 10.0),
+            grade(3, "matematika", 10.0),
+        ];
+
+        let (average, missing) = compute_rapor_average(&grades, &requirements);
+
+        assert_eq!(average, Some(85.0));
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn test_compute_rapor_average_reports_missing_grades() {
+        let requirements = RaporRequirements {
+            subjects: Some(vec!["matematika".to_string(), "ipa".to_string()]),
+            semesters: None,
+        };
+
+        let grades = vec![
+            grade(1, "matematika", 80.0),
+            grade(1, "ipa", 90.0),
+            grade(2, "matematika", 70.0),
+        ];
+
+        let (average, missing) = compute_rapor_average(&grades, &requirements);
+
+        assert_eq!(average, None);
+        assert_eq!(
+            missing,
+            vec![MissingGrade {
+                semester: 2,
+                subject: "ipa".to_string()
+            }]
+        );
+        assert_eq!(
+            compute_rapor_average(&[], &RaporRequirements::default()),
+            (None, vec![])
+        );
+    }
+
+    #[test]
+    fn test_validate_grades() {
+        let grades = vec![
+            GradeInput {
+                semester: 1,
+                subject: "Matematika".to_string(),
+                score: 80.0,
+            },
+            GradeInput {
+                semester: 1,
+                subject: " matematika ".to_string(),
+                score: 85.0,
+            },
+            GradeInput {
+                semester: 13,
+                subject: "".to_string(),
+                score: 101.0,
+            },
+        ];
+
+        let fields: Vec<String> = validate_grades(&grades)
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+
+        assert_eq!(
+            fields,
+            vec![
+                "grades[1].subject",
+                "grades[2].semester",
+                "grades[2].subject",
+                "grades[2].score",
+            ]
+        );
+    }
+}
@@ -0,0 +1,470 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::models::major::{
+    is_requirement_key, MajorCriterion, PeriodMajor, RegistrationMajorChoice,
+    RegistrationRequirementCheck,
+};
+use crate::models::registration::Registration;
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::services::grade_service::normalize_subject;
+use crate::utils::error::{AppError, AppResult, FieldError};
+
+/// Maximum number of ranked major choices per registration
+pub const MAX_MAJOR_CHOICES: usize = 3;
+
+pub struct MajorService {
+    registration_repo: RegistrationRepository,
+    period_repo: PeriodRepository,
+}
+
+impl MajorService {
+    pub fn new(registration_repo: RegistrationRepository, period_repo: PeriodRepository) -> Self {
+        Self {
+            registration_repo,
+            period_repo,
+        }
+    }
+
+    async fn get_registration(&self, id: i32) -> AppResult<Registration> {
+        self.registration_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))
+    }
+
+    pub async fn get_summary(&self, registration_id: i32) -> AppResult<MajorChoiceSummary> {
+        let registration = self.get_registration(registration_id).await?;
+        self.build_summary(&registration).await
+    }
+
+    /// Replace the ranked major choices of a draft registration
+    pub async fn save_choices(
+        &self,
+        registration_id: i32,
+        major_ids: Vec<i32>,
+    ) -> AppResult<MajorChoiceSummary> {
+        let registration = self.get_registration(registration_id).await?;
+
+        if registration.status != "draft" {
+            return Err(AppError::Validation(
+                "Can only update major choices of registrations in draft status".to_string(),
+            ));
+        }
+
+        let majors = self
+            .period_repo
+            .find_majors_by_period(registration.period_id)
+            .await?;
+
+        if majors.is_empty() {
+            return Err(AppError::Validation(
+                "This period has no majors to choose from".to_string(),
+            ));
+        }
+
+        let period_major_ids: Vec<i32> = majors.iter().map(|m| m.id).collect();
+        let errors = validate_major_choices(&major_ids, &period_major_ids);
+        if !errors.is_empty() {
+            return Err(AppError::FieldValidation(errors));
+        }
+
+        self.registration_repo
+            .replace_major_choices(registration_id, &major_ids)
+            .await?;
+
+        self.build_summary(&registration).await
+    }
+
+    /// Record the verifier's result of a major requirement such as a color-blindness test
+    pub async fn record_requirement_check(
+        &self,
+        registration_id: i32,
+        requirement_key: String,
+        passed: bool,
+        notes: Option<String>,
+        school_id: Option<i32>,
+        admin_id: i32,
+    ) -> AppResult<MajorChoiceSummary> {
+        let registration = self.get_registration(registration_id).await?;
+
+        if let Some(school_id) = school_id {
+            if registration.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have permission to verify this registration".to_string(),
+                ));
+            }
+        }
+
+        if !["submitted", "verified"].contains(&registration.status.as_str()) {
+            return Err(AppError::Validation(
+                "Can only check requirements of submitted registrations".to_string(),
+            ));
+        }
+
+        if !is_requirement_key(&requirement_key) {
+            return Err(AppError::Validation("Invalid requirement key".to_string()));
+        }
+
+        // Only requirements of the chosen majors can be recorded
+        let chosen = self.chosen_majors(&registration).await?;
+        let required = chosen.iter().any(|(_, criteria)| {
+            criteria.iter().any(|criterion| {
+                matches!(criterion, MajorCriterion::Requirement { key, .. } if *key == requirement_key)
+            })
+        });
+
+        if !required {
+            return Err(AppError::Validation(format!(
+                "None of the chosen majors requires '{}'",
+                requirement_key
+            )));
+        }
+
+        self.registration_repo
+            .upsert_requirement_check(
+                registration_id,
+                &requirement_key,
+                passed,
+                notes.as_deref(),
+                admin_id,
+            )
+            .await?;
+
+        tracing::info!(
+            "Requirement '{}' of registration {} marked as {} by admin {}",
+            requirement_key,
+            registration_id,
+            if passed { "passed" } else { "failed" },
+            admin_id
+        );
+
+        self.build_summary(&registration).await
+    }
+
+    /// Chosen majors of a registration with their parsed criteria, by priority
+    async fn chosen_majors(
+        &self,
+        registration: &Registration,
+    ) -> AppResult<Vec<(PeriodMajor, Vec<MajorCriterion>)>> {
+        let majors: HashMap<i32, PeriodMajor> = self
+            .period_repo
+            .find_majors_by_period(registration.period_id)
+            .await?
+            .into_iter()
+            .map(|major| (major.id, major))
+            .collect();
+
+        let mut chosen = Vec::new();
+        for choice in self
+            .registration_repo
+            .find_major_choices_by_registration(registration.id)
+            .await?
+        {
+            if let Some(major) = majors.get(&choice.major_id) {
+                let criteria = MajorCriterion::parse_list(&major.criteria)
+                    .map_err(|e| AppError::Internal(format!("Major {}: {}", major.id, e)))?;
+                chosen.push((major.clone(), criteria));
+            }
+        }
+
+        Ok(chosen)
+    }
+
+    async fn build_summary(&self, registration: &Registration) -> AppResult<MajorChoiceSummary> {
+        let chosen = self.chosen_majors(registration).await?;
+
+        let grades = self
+            .registration_repo
+            .find_grades_by_registration(registration.id)
+            .await?;
+        let subject_averages = average_by_subject(
+            grades
+                .iter()
+                .map(|grade| (grade.subject.as_str(), grade.score)),
+        );
+
+        let checks = self
+            .registration_repo
+            .find_requirement_checks_by_registration(registration.id)
+            .await?;
+        let check_results = check_map(&checks);
+
+        let choices = chosen
+            .into_iter()
+            .enumerate()
+            .map(|(index, (major, criteria))| {
+                let criteria = evaluate_criteria(&criteria, &subject_averages, &check_results);
+                MajorChoiceResult {
+                    major_id: major.id,
+                    code: major.code,
+                    name: major.name,
+                    priority: index as i32 + 1,
+                    eligible: criteria.iter().all(|c| c.met == Some(true)),
+                    criteria,
+                }
+            })
+            .collect();
+
+        Ok(MajorChoiceSummary {
+            registration_id: registration.id,
+            accepted_major_id: registration.accepted_major_id,
+            choices,
+        })
+    }
+}
+
+/// Pilihan jurusan pendaftaran
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MajorChoiceSummary {
+    /// ID pendaftaran
+    #[schema(example = 1)]
+    pub registration_id: i32,
+
+    /// ID jurusan tempat siswa diterima (setelah seleksi)
+    #[schema(example = 3)]
+    pub accepted_major_id: Option<i32>,
+
+    /// Pilihan jurusan sesuai urutan prioritas
+    pub choices: Vec<MajorChoiceResult>,
+}
+
+/// Pilihan jurusan beserta pemenuhan persyaratannya
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MajorChoiceResult {
+    /// ID jurusan
+    #[schema(example = 3)]
+    pub major_id: i32,
+
+    /// Kode jurusan
+    #[schema(example = "TKJ")]
+    pub code: String,
+
+    /// Nama jurusan
+    #[schema(example = "Teknik Komputer dan Jaringan")]
+    pub name: String,
+
+    /// Urutan prioritas (1 = pilihan pertama)
+    #[schema(example = 1)]
+    pub priority: i32,
+
+    /// Semua persyaratan jurusan terpenuhi
+    #[schema(example = false)]
+    pub eligible: bool,
+
+    /// Hasil pemeriksaan setiap persyaratan
+    pub criteria: Vec<CriterionResult>,
+}
+
+/// Hasil pemeriksaan satu persyaratan jurusan
+#[derive(Debug, Serialize, ToSchema, PartialEq)]
+pub struct CriterionResult {
+    /// Jenis persyaratan (subject_grade/requirement)
+    #[schema(example = "requirement")]
+    pub criterion_type: String,
+
+    /// Mata pelajaran atau kunci persyaratan
+    #[schema(example = "color_vision")]
+    pub key: String,
+
+    /// Keterangan persyaratan
+    #[schema(example = "Tidak buta warna")]
+    pub label: String,
+
+    /// Terpenuhi (kosong jika belum diperiksa verifikator)
+    #[schema(example = true)]
+    pub met: Option<bool>,
+}
+
+pub fn validate_major_choices(major_ids: &[i32], period_major_ids: &[i32]) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if major_ids.is_empty() {
+        errors.push(FieldError::new("major_ids", "Choose at least one major"));
+    }
+
+    if major_ids.len() > MAX_MAJOR_CHOICES {
+        errors.push(FieldError::new(
+            "major_ids",
+            format!("At most {} majors can be chosen", MAX_MAJOR_CHOICES),
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    for (index, major_id) in major_ids.iter().enumerate() {
+        let field = format!("major_ids[{}]", index);
+
+        if !period_major_ids.contains(major_id) {
+            errors.push(FieldError::new(
+                &field,
+                "Major does not belong to this period",
+            ));
+        } else if !seen.insert(*major_id) {
+            errors.push(FieldError::new(
+                &field,
+                "Each major can only be chosen once",
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Average score per (normalized) subject over all semesters
+pub fn average_by_subject<'a>(
+    grades: impl Iterator<Item = (&'a str, f64)>,
+) -> HashMap<String, f64> {
+    let mut sums: HashMap<String, (f64, u32)> = HashMap::new();
+    for (subject, score) in grades {
+        let entry = sums.entry(normalize_subject(subject)).or_default();
+        entry.0 += score;
+        entry.1 += 1;
+    }
+
+    sums.into_iter()
+        .map(|(subject, (sum, count))| (subject, sum / count as f64))
+        .collect()
+}
+
+pub fn check_map(checks: &[RegistrationRequirementCheck]) -> HashMap<String, bool> {
+    checks
+        .iter()
+        .map(|check| (check.requirement_key.clone(), check.passed))
+        .collect()
+}
+
+/// Subject grades are checked against the entered rapor grades; requirements
+/// stay unmet until a verifier recorded a passing result.
+pub fn evaluate_criteria(
+    criteria: &[MajorCriterion],
+    subject_averages: &HashMap<String, f64>,
+    checks: &HashMap<String, bool>,
+) -> Vec<CriterionResult> {
+    criteria
+        .iter()
+        .map(|criterion| match criterion {
+            MajorCriterion::SubjectGrade {
+                subject,
+                min_average,
+            } => CriterionResult {
+                criterion_type: "subject_grade".to_string(),
+                key: subject.clone(),
+                label: format!("Rata-rata {} minimal {}", subject, min_average),
+                met: Some(
+                    subject_averages
+                        .get(&normalize_subject(subject))
+                        .map(|average| average >= min_average)
+                        .unwrap_or(false),
+                ),
+            },
+            MajorCriterion::Requirement { key, label } => CriterionResult {
+                criterion_type: "requirement".to_string(),
+                key: key.clone(),
+                label: label.clone(),
+                met: checks.get(key).copied(),
+            },
+        })
+        .collect()
+}
+
+/// Majors each registration of a period qualifies for, in preference order.
+/// Majors whose criteria are not all met are left out.
+pub fn eligible_major_choices(
+    majors: &[PeriodMajor],
+    choices: &[RegistrationMajorChoice],
+    subject_averages: &HashMap<i32, HashMap<String, f64>>,
+    checks: &HashMap<i32, HashMap<String, bool>>,
+) -> AppResult<HashMap<i32, Vec<i32>>> {
+    let mut criteria_by_major = HashMap::new();
+    for major in majors {
+        let criteria = MajorCriterion::parse_list(&major.criteria)
+            .map_err(|e| AppError::Internal(format!("Major {}: {}", major.id, e)))?;
+        criteria_by_major.insert(major.id, criteria);
+    }
+
+    let no_averages = HashMap::new();
+    let no_checks = HashMap::new();
+    let mut eligible: HashMap<i32, Vec<i32>> = HashMap::new();
+
+    for choice in choices {
+        let Some(criteria) = criteria_by_major.get(&choice.major_id) else {
+            continue;
+        };
+
+        let results = evaluate_criteria(
+            criteria,
+            subject_averages
+                .get(&choice.registration_id)
+                .unwrap_or(&no_averages),
+            checks.get(&choice.registration_id).unwrap_or(&no_checks),
+        );
+
+        let entry = eligible.entry(choice.registration_id).or_default();
+        if results.iter().all(|result| result.met == Some(true)) {
+            entry.push(choice.major_id);
+        }
+    }
+
+    Ok(eligible)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_major_choices() {
+        assert!(validate_major_choices(&[2, 1], &[1, 2, 3]).is_empty());
+
+        let fields: Vec<String> = validate_major_choices(&[1, 4, 1], &[1, 2, 3])
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert_eq!(fields, vec!["major_ids[1]", "major_ids[2]"]);
+
+        assert_eq!(validate_major_choices(&[], &[1]).len(), 1);
+        assert_eq!(
+            validate_major_choices(&[1, 2, 3, 4], &[1, 2, 3, 4]).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_evaluate_criteria() {
+        let criteria = vec![
+            MajorCriterion::SubjectGrade {
+                subject: "matematika".to_string(),
+                min_average: 75.0,
+            },
+            MajorCriterion::Requirement {
+                key: "color_vision".to_string(),
+                label: "Tidak buta warna".to_string(),
+            },
+        ];
+
+        let averages = average_by_subject(
+            [("Matematika", 70.0), ("matematika", 84.0), ("IPA", 60.0)].into_iter(),
+        );
+        assert_eq!(averages["matematika"], 77.0);
+
+        // Requirement not checked yet
+        let results = evaluate_criteria(&criteria, &averages, &HashMap::new());
+        assert_eq!(results[0].met, Some(true));
+        assert_eq!(results[1].met, None);
+
+        let failed = HashMap::from([("color_vision".to_string(), false)]);
+        assert_eq!(
+            evaluate_criteria(&criteria, &averages, &failed)[1].met,
+            Some(false)
+        );
+
+        // Missing grades do not meet a subject requirement
+        assert_eq!(
+            evaluate_criteria(&criteria, &HashMap::new(), &HashMap::new())[0].met,
+            Some(false)
+        );
+    }
+}
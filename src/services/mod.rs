@@ -1,9 +1,19 @@
+pub mod allocation_service;
 pub mod announcement_service;
+pub mod appeal_service;
+pub mod document_service;
 pub mod auth_service;
+pub mod duplicate_service;
+pub mod grade_service;
+pub mod major_service;
+pub mod achievement_service;
+pub mod assistance_service;
 pub mod period_service;
+pub mod region_service;
 pub mod registration_service;
 pub mod school_service;
 pub mod scoring_service;
 pub mod selection_service;
+pub mod test_service;
 pub mod user_service;
 pub mod verification_service;
@@ -1,13 +1,67 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
 
-use crate::models::period::{Period, RegistrationPath};
+use crate::models::achievement::AchievementPointTable;
+use crate::models::age_rule::AgeRules;
+use crate::models::duplicate::DuplicatePolicy;
+use crate::models::major::{MajorCriterion, PeriodMajor};
+use crate::models::period::{
+    DocumentRejectionPolicy, Level, Period, PeriodDeadlines, PeriodStage, RefundPolicy, RegistrationPath, WaitlistPolicy,
+};
+use crate::models::registration::DocumentType;
 use crate::repositories::period_repo::PeriodRepository;
+use crate::services::grade_service::normalize_subject;
 use crate::utils::error::{AppError, AppResult};
+use crate::utils::json_schema;
+use crate::utils::timezone::{format_local, local_instant};
+
+/// Days after the announcement during which parents may file an appeal
+const DEFAULT_APPEAL_WINDOW_DAYS: i32 = 3;
+const MAX_APPEAL_WINDOW_DAYS: i32 = 30;
 
 pub struct PeriodService {
     period_repo: PeriodRepository,
 }
 
+/// Draft created by cloning a period, with what to review before activation
+#[derive(Debug)]
+pub struct ClonedPeriod {
+    pub period: Period,
+    pub paths: Vec<RegistrationPath>,
+    pub stages: Vec<PeriodStage>,
+    pub majors: Vec<PeriodMajor>,
+    pub warnings: Vec<String>,
+}
+
+/// How the dates of a cloned period move into the new academic year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateShift {
+    /// Same calendar days, N years later (29 February becomes 28 February)
+    Years(i32),
+    /// Same offsets from a new start date
+    Days(i64),
+}
+
+impl DateShift {
+    pub fn apply(&self, date: NaiveDate) -> NaiveDate {
+        match *self {
+            DateShift::Years(years) => {
+                let year = date.year() + years;
+                NaiveDate::from_ymd_opt(year, date.month(), date.day())
+                    .or_else(|| NaiveDate::from_ymd_opt(year, date.month(), date.day() - 1))
+                    .unwrap_or(date)
+            }
+            DateShift::Days(days) => date + chrono::Duration::days(days),
+        }
+    }
+
+    /// Move an instant keeping its wall-clock time at the school
+    pub fn apply_instant(&self, instant: DateTime<Utc>, timezone: Tz) -> DateTime<Utc> {
+        let local = instant.with_timezone(&timezone).naive_local();
+        local_instant(self.apply(local.date()), local.time(), timezone)
+    }
+}
+
 impl PeriodService {
     pub fn new(period_repo: PeriodRepository) -> Self {
         Self { period_repo }
@@ -20,7 +74,19 @@ impl PeriodService {
         level: String,
         start_date: NaiveDate,
         end_date: NaiveDate,
+        registration_start: Option<NaiveDate>,
+        registration_end: Option<NaiveDate>,
+        announcement_at: Option<DateTime<Utc>>,
         reenrollment_deadline: Option<NaiveDate>,
+        closing_time: Option<NaiveTime>,
+        duplicate_policy: Option<String>,
+        waitlist_policy: Option<String>,
+        refund_policy: Option<String>,
+        appeal_window_days: Option<i32>,
+        document_rejection_policy: Option<String>,
+        achievement_point_table: Option<serde_json::Value>,
+        age_rules: Option<serde_json::Value>,
+        auto_lifecycle: Option<bool>,
     ) -> AppResult<Period> {
         // Validate dates
         if end_date <= start_date {
@@ -29,6 +95,29 @@ impl PeriodService {
             ));
         }
 
+        // The registration window defaults to the whole period
+        let registration_start = registration_start.unwrap_or(start_date);
+        let registration_end = registration_end.unwrap_or(end_date);
+
+        // Deadlines are entered in the school's local time
+        let timezone = self.period_repo.find_school_timezone(school_id).await?;
+        let deadlines = period_deadlines(
+            timezone,
+            registration_start,
+            registration_end,
+            reenrollment_deadline,
+            closing_time.unwrap_or_else(default_closing_time),
+        );
+
+        validate_registration_window(
+            start_date,
+            end_date,
+            registration_start,
+            registration_end,
+            &deadlines,
+            announcement_at,
+        )?;
+
         if let Some(deadline) = reenrollment_deadline {
             if deadline <= end_date {
                 return Err(AppError::Validation(
@@ -37,6 +126,30 @@ impl PeriodService {
             }
         }
 
+        let duplicate_policy = duplicate_policy.unwrap_or_else(|| "warn".to_string());
+        validate_duplicate_policy(&duplicate_policy)?;
+
+        let waitlist_policy = waitlist_policy.unwrap_or_else(|| "none".to_string());
+        validate_waitlist_policy(&waitlist_policy)?;
+
+        let refund_policy = refund_policy.unwrap_or_else(|| "none".to_string());
+        validate_refund_policy(&refund_policy)?;
+
+        let appeal_window_days = appeal_window_days.unwrap_or(DEFAULT_APPEAL_WINDOW_DAYS);
+        validate_appeal_window(appeal_window_days)?;
+
+        let document_rejection_policy =
+            document_rejection_policy.unwrap_or_else(|| "revision".to_string());
+        validate_document_rejection_policy(&document_rejection_policy)?;
+
+        if let Some(ref table) = achievement_point_table {
+            validate_achievement_point_table(table)?;
+        }
+
+        if let Some(ref rules) = age_rules {
+            validate_age_rules(rules)?;
+        }
+
         // Check if there's already an active period for this school/year/level
         if let Some(_) = self
             .period_repo
@@ -57,7 +170,19 @@ impl PeriodService {
                 &level,
                 start_date,
                 end_date,
+                registration_start,
+                registration_end,
+                announcement_at,
                 reenrollment_deadline,
+                &duplicate_policy,
+                &waitlist_policy,
+                &refund_policy,
+                appeal_window_days,
+                &document_rejection_policy,
+                achievement_point_table.as_ref(),
+                age_rules.as_ref(),
+                auto_lifecycle.unwrap_or(true),
+                &deadlines,
             )
             .await?;
 
@@ -107,8 +232,20 @@ impl PeriodService {
         id: i32,
         start_date: Option<NaiveDate>,
         end_date: Option<NaiveDate>,
+        registration_start: Option<NaiveDate>,
+        registration_end: Option<NaiveDate>,
         announcement_date: Option<NaiveDate>,
+        announcement_at: Option<DateTime<Utc>>,
         reenrollment_deadline: Option<NaiveDate>,
+        closing_time: Option<NaiveTime>,
+        duplicate_policy: Option<String>,
+        waitlist_policy: Option<String>,
+        refund_policy: Option<String>,
+        appeal_window_days: Option<i32>,
+        document_rejection_policy: Option<String>,
+        achievement_point_table: Option<serde_json::Value>,
+        age_rules: Option<serde_json::Value>,
+        auto_lifecycle: Option<bool>,
     ) -> AppResult<Period> {
         // Check if period exists
         let period = self.get_period(id).await?;
@@ -129,12 +266,110 @@ impl PeriodService {
             }
         }
 
+        let registration_start = registration_start.unwrap_or(period.registration_start);
+        let registration_end = registration_end.unwrap_or(period.registration_end);
+
+        let timezone = self.period_repo.find_school_timezone(period.school_id).await?;
+        let deadlines = period_deadlines(
+            timezone,
+            registration_start,
+            registration_end,
+            reenrollment_deadline.or(period.reenrollment_deadline),
+            closing_time.unwrap_or(period.closing_time),
+        );
+
+        validate_registration_window(
+            start_date.unwrap_or(period.start_date),
+            end_date.unwrap_or(period.end_date),
+            registration_start,
+            registration_end,
+            &deadlines,
+            announcement_at.or(period.announcement_at),
+        )?;
+
+        if let Some(ref policy) = duplicate_policy {
+            validate_duplicate_policy(policy)?;
+        }
+
+        if let Some(ref policy) = waitlist_policy {
+            validate_waitlist_policy(policy)?;
+        }
+
+        if let Some(ref policy) = refund_policy {
+            validate_refund_policy(policy)?;
+        }
+
+        if let Some(days) = appeal_window_days {
+            validate_appeal_window(days)?;
+        }
+
+        if let Some(ref policy) = document_rejection_policy {
+            validate_document_rejection_policy(policy)?;
+        }
+
+        if let Some(ref table) = achievement_point_table {
+            validate_achievement_point_table(table)?;
+        }
+
+        if let Some(ref rules) = age_rules {
+            validate_age_rules(rules)?;
+        }
+
+        // Stages must still fit the registration window
+        let stages = self.period_repo.find_stages_by_period(id).await?;
+        let schedules: Vec<StageSchedule> = stages
+            .iter()
+            .map(|stage| {
+                StageSchedule::resolve(
+                    timezone,
+                    deadlines.closing_time,
+                    stage.registration_start,
+                    stage.registration_end,
+                    stage.announcement_at,
+                )
+            })
+            .collect();
+        validate_stage_schedule(registration_start, registration_end, &schedules)?;
+
         // Update period
         let updated_period = self
             .period_repo
-            .update_period(id, start_date, end_date, announcement_date, reenrollment_deadline)
+            .update_period(
+                id,
+                start_date,
+                end_date,
+                Some(registration_start),
+                Some(registration_end),
+                announcement_date,
+                announcement_at,
+                reenrollment_deadline,
+                duplicate_policy.as_deref(),
+                waitlist_policy.as_deref(),
+                refund_policy.as_deref(),
+                appeal_window_days,
+                document_rejection_policy.as_deref(),
+                achievement_point_table.as_ref(),
+                age_rules.as_ref(),
+                auto_lifecycle,
+                &deadlines,
+            )
             .await?;
 
+        // The closing time may have changed
+        for (stage, schedule) in stages.iter().zip(&schedules) {
+            self.period_repo
+                .update_stage(
+                    stage.id,
+                    &stage.name,
+                    schedule.registration_start,
+                    schedule.registration_end,
+                    schedule.registration_opens_at,
+                    schedule.registration_closes_at,
+                    schedule.announcement_at,
+                )
+                .await?;
+        }
+
         Ok(updated_period)
     }
 
@@ -194,6 +429,242 @@ impl PeriodService {
         Ok(())
     }
 
+    /// Copy a period with its paths, majors and stages into a new academic year
+    /// as a draft. Dates move by the difference between the academic years, or
+    /// keep their offsets from `start_date` when given.
+    pub async fn clone_period(
+        &self,
+        source_id: i32,
+        school_id: i32,
+        academic_year: String,
+        start_date: Option<NaiveDate>,
+    ) -> AppResult<ClonedPeriod> {
+        let source = self.get_period(source_id).await?;
+
+        if source.school_id != school_id {
+            return Err(AppError::Forbidden(
+                "You don't have access to this period".to_string(),
+            ));
+        }
+
+        let academic_year = academic_year.trim().to_string();
+        if academic_year == source.academic_year {
+            return Err(AppError::Validation(
+                "Academic year of the clone must differ from the source period".to_string(),
+            ));
+        }
+
+        let shift = match start_date {
+            Some(start_date) => DateShift::Days((start_date - source.start_date).num_days()),
+            None => academic_year_offset(&source.academic_year, &academic_year)
+                .map(DateShift::Years)
+                .ok_or_else(|| {
+                    AppError::Validation(
+                        "Academic year must have the format YYYY/YYYY, or give a start date"
+                            .to_string(),
+                    )
+                })?,
+        };
+
+        // A school has one period per academic year and level
+        let existing = self
+            .period_repo
+            .count_by_school(
+                school_id,
+                None,
+                Some(academic_year.clone()),
+                Some(source.level.clone()),
+            )
+            .await?;
+        if existing > 0 {
+            return Err(AppError::Conflict(format!(
+                "A {} period for {} already exists",
+                source.level, academic_year
+            )));
+        }
+
+        let timezone = self.period_repo.find_school_timezone(school_id).await?;
+        let mut warnings = Vec::new();
+
+        // An explicit reference date belongs to the old academic year
+        let age_rules = match source.age_rules {
+            Some(ref rules) => {
+                let mut rules = AgeRules::from_config(rules).map_err(AppError::Validation)?;
+                if let Some(reference_date) = rules.reference_date {
+                    let shifted = shift.apply(reference_date);
+                    rules.reference_date = Some(shifted);
+                    warnings.push(format!(
+                        "Age reference date moved to {}; check it against this year's regulation",
+                        shifted.format("%d-%m-%Y")
+                    ));
+                }
+                Some(serde_json::to_value(rules).map_err(|e| AppError::Internal(e.to_string()))?)
+            }
+            None => None,
+        };
+
+        let period = self
+            .create_period(
+                school_id,
+                academic_year,
+                source.level.clone(),
+                shift.apply(source.start_date),
+                shift.apply(source.end_date),
+                Some(shift.apply(source.registration_start)),
+                Some(shift.apply(source.registration_end)),
+                source
+                    .announcement_at
+                    .map(|at| shift.apply_instant(at, timezone)),
+                source.reenrollment_deadline.map(|d| shift.apply(d)),
+                Some(source.closing_time),
+                Some(source.duplicate_policy.clone()),
+                Some(source.waitlist_policy.clone()),
+                Some(source.refund_policy.clone()),
+                Some(source.appeal_window_days),
+                Some(source.document_rejection_policy.clone()),
+                source.achievement_point_table.clone(),
+                age_rules,
+                Some(source.auto_lifecycle),
+            )
+            .await?;
+
+        // Do not leave a half-copied draft behind
+        let (paths, stages, majors) = match self
+            .copy_period_contents(&source, period.id, shift, timezone, &mut warnings)
+            .await
+        {
+            Ok(contents) => contents,
+            Err(e) => {
+                self.period_repo.delete_period(period.id).await?;
+                return Err(e);
+            }
+        };
+
+        let period = self.period_repo.set_cloned_from(period.id, source.id).await?;
+
+        if period.registration_opens_at <= Utc::now() {
+            warnings.push(format!(
+                "Registration opens at {}, which has already passed; update the dates before activation",
+                format_local(period.registration_opens_at, timezone)
+            ));
+        } else if period.auto_lifecycle {
+            warnings.push(format!(
+                "The period activates automatically at {}",
+                format_local(period.registration_opens_at, timezone)
+            ));
+        }
+
+        if period.announcement_at.is_none() && stages.is_empty() {
+            warnings.push("No announcement time is set".to_string());
+        }
+
+        for path in paths.iter().filter(|p| p.required_documents.is_empty()) {
+            warnings.push(format!("Path '{}' has no required documents", path.name));
+        }
+
+        if self.period_repo.has_test_sessions(source.id).await? {
+            warnings.push("Test sessions are not copied; schedule them for the new period".to_string());
+        }
+
+        if self.period_repo.is_in_allocation_round(source.id).await? {
+            warnings.push(
+                "The source period is part of an allocation round; add the new period to a round"
+                    .to_string(),
+            );
+        }
+
+        tracing::info!(
+            "Period {} cloned from period {} with {} warnings",
+            period.id,
+            source.id,
+            warnings.len()
+        );
+
+        Ok(ClonedPeriod {
+            period,
+            paths,
+            stages,
+            majors,
+            warnings,
+        })
+    }
+
+    async fn copy_period_contents(
+        &self,
+        source: &Period,
+        period_id: i32,
+        shift: DateShift,
+        timezone: Tz,
+        warnings: &mut Vec<String>,
+    ) -> AppResult<(Vec<RegistrationPath>, Vec<PeriodStage>, Vec<PeriodMajor>)> {
+        let mut path_ids = std::collections::HashMap::new();
+        for path in self.period_repo.find_paths_by_period(source.id).await? {
+            let copy = self
+                .create_path(
+                    period_id,
+                    path.path_type,
+                    path.name,
+                    path.quota,
+                    path.description,
+                    path.scoring_config,
+                    path.selection_order,
+                    path.path_data_schema,
+                    path.required_documents,
+                )
+                .await?;
+            path_ids.insert(path.id, copy.id);
+        }
+
+        let mut majors = Vec::new();
+        for major in self.period_repo.find_majors_by_period(source.id).await? {
+            majors.push(
+                self.create_major(
+                    period_id,
+                    major.code,
+                    major.name,
+                    major.capacity,
+                    major.description,
+                    Some(major.criteria),
+                )
+                .await?,
+            );
+        }
+
+        let source_paths = self.period_repo.find_paths_by_period(source.id).await?;
+        let mut stages = Vec::new();
+        for stage in self.period_repo.find_stages_by_period(source.id).await? {
+            let stage_path_ids: Vec<i32> = source_paths
+                .iter()
+                .filter(|p| p.stage_id == Some(stage.id))
+                .filter_map(|p| path_ids.get(&p.id).copied())
+                .collect();
+
+            if stage_path_ids.is_empty() {
+                warnings.push(format!("{} has no paths and was not copied", stage.name));
+                continue;
+            }
+
+            let (copy, _) = self
+                .create_stage(
+                    period_id,
+                    stage.name,
+                    shift.apply(stage.registration_start),
+                    shift.apply(stage.registration_end),
+                    stage
+                        .announcement_at
+                        .map(|at| shift.apply_instant(at, timezone)),
+                    stage_path_ids,
+                )
+                .await?;
+            stages.push(copy);
+        }
+
+        // Stage assignments changed the paths
+        let paths = self.period_repo.find_paths_by_period(period_id).await?;
+
+        Ok((paths, stages, majors))
+    }
+
     // Registration Path methods
     pub async fn create_path(
         &self,
@@ -203,6 +674,9 @@ impl PeriodService {
         quota: i32,
         description: Option<String>,
         scoring_config: serde_json::Value,
+        selection_order: i32,
+        path_data_schema: Option<serde_json::Value>,
+        required_documents: Vec<String>,
     ) -> AppResult<RegistrationPath> {
         // Check if period exists
         let period = self.get_period(period_id).await?;
@@ -219,6 +693,12 @@ impl PeriodService {
             return Err(AppError::Validation("Quota must be greater than 0".to_string()));
         }
 
+        if let Some(ref schema) = path_data_schema {
+            validate_path_data_schema(schema)?;
+        }
+
+        let required_documents = normalize_required_documents(required_documents)?;
+
         // Create path
         let path = self
             .period_repo
@@ -229,6 +709,9 @@ impl PeriodService {
                 quota,
                 description.as_deref(),
                 scoring_config,
+                selection_order,
+                path_data_schema,
+                &required_documents,
             )
             .await?;
 
@@ -258,6 +741,9 @@ impl PeriodService {
         quota: Option<i32>,
         description: Option<String>,
         scoring_config: Option<serde_json::Value>,
+        selection_order: Option<i32>,
+        path_data_schema: Option<serde_json::Value>,
+        required_documents: Option<Vec<String>>,
     ) -> AppResult<RegistrationPath> {
         // Check if path exists
         let path = self.get_path(id).await?;
@@ -277,6 +763,14 @@ impl PeriodService {
             }
         }
 
+        if let Some(ref schema) = path_data_schema {
+            validate_path_data_schema(schema)?;
+        }
+
+        let required_documents = required_documents
+            .map(normalize_required_documents)
+            .transpose()?;
+
         // Update path
         let updated_path = self
             .period_repo
@@ -286,6 +780,9 @@ impl PeriodService {
                 quota,
                 description.as_deref(),
                 scoring_config,
+                selection_order,
+                path_data_schema,
+                required_documents.as_deref(),
             )
             .await?;
 
@@ -309,4 +806,723 @@ impl PeriodService {
 
         Ok(())
     }
+
+    // Major methods
+    pub async fn create_major(
+        &self,
+        period_id: i32,
+        code: String,
+        name: String,
+        capacity: i32,
+        description: Option<String>,
+        criteria: Option<serde_json::Value>,
+    ) -> AppResult<PeriodMajor> {
+        let period = self.get_period(period_id).await?;
+
+        // Majors are the competency programs of SMK admissions
+        if !matches!(Level::from_str(&period.level), Some(Level::SMK)) {
+            return Err(AppError::Validation(
+                "Majors can only be added to SMK periods".to_string(),
+            ));
+        }
+
+        if period.status != "draft" {
+            return Err(AppError::Validation(
+                "Can only add majors to periods in draft status".to_string(),
+            ));
+        }
+
+        if self.period_repo.is_in_allocation_round(period_id).await? {
+            return Err(AppError::Validation(
+                "Periods in a centralized allocation round cannot have majors".to_string(),
+            ));
+        }
+
+        let code = code.trim().to_uppercase();
+        if code.is_empty() || code.len() > 20 {
+            return Err(AppError::Validation(
+                "Major code must be 1-20 characters".to_string(),
+            ));
+        }
+
+        if name.trim().is_empty() {
+            return Err(AppError::Validation("Major name is required".to_string()));
+        }
+
+        if capacity <= 0 {
+            return Err(AppError::Validation("Capacity must be greater than 0".to_string()));
+        }
+
+        if self
+            .period_repo
+            .find_major_by_code(period_id, &code)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Conflict(format!(
+                "Major with code '{}' already exists in this period",
+                code
+            )));
+        }
+
+        let criteria = normalize_major_criteria(criteria.unwrap_or_else(|| serde_json::json!([])))?;
+
+        self.period_repo
+            .create_major(
+                period_id,
+                &code,
+                name.trim(),
+                capacity,
+                description.as_deref(),
+                &criteria,
+            )
+            .await
+    }
+
+    pub async fn get_majors_by_period(&self, period_id: i32) -> AppResult<Vec<PeriodMajor>> {
+        let _ = self.get_period(period_id).await?;
+
+        self.period_repo.find_majors_by_period(period_id).await
+    }
+
+    pub async fn get_major(&self, id: i32) -> AppResult<PeriodMajor> {
+        self.period_repo
+            .find_major_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Major not found".to_string()))
+    }
+
+    pub async fn update_major(
+        &self,
+        id: i32,
+        name: Option<String>,
+        capacity: Option<i32>,
+        description: Option<String>,
+        criteria: Option<serde_json::Value>,
+    ) -> AppResult<PeriodMajor> {
+        let major = self.get_major(id).await?;
+
+        let period = self.get_period(major.period_id).await?;
+        if period.status != "draft" {
+            return Err(AppError::Validation(
+                "Can only update majors for periods in draft status".to_string(),
+            ));
+        }
+
+        if let Some(c) = capacity {
+            if c <= 0 {
+                return Err(AppError::Validation("Capacity must be greater than 0".to_string()));
+            }
+        }
+
+        let criteria = criteria.map(normalize_major_criteria).transpose()?;
+
+        self.period_repo
+            .update_major(
+                id,
+                name.as_deref(),
+                capacity,
+                description.as_deref(),
+                criteria.as_ref(),
+            )
+            .await
+    }
+
+    pub async fn delete_major(&self, id: i32) -> AppResult<()> {
+        let major = self.get_major(id).await?;
+
+        let period = self.get_period(major.period_id).await?;
+        if period.status != "draft" {
+            return Err(AppError::Validation(
+                "Can only delete majors for periods in draft status".to_string(),
+            ));
+        }
+
+        self.period_repo.delete_major(id).await
+    }
+
+    // Stage methods
+    pub async fn create_stage(
+        &self,
+        period_id: i32,
+        name: String,
+        registration_start: NaiveDate,
+        registration_end: NaiveDate,
+        announcement_at: Option<DateTime<Utc>>,
+        path_ids: Vec<i32>,
+    ) -> AppResult<(PeriodStage, Vec<RegistrationPath>)> {
+        let period = self.get_period(period_id).await?;
+
+        if period.status != "draft" {
+            return Err(AppError::Validation(
+                "Can only add stages to periods in draft status".to_string(),
+            ));
+        }
+
+        if name.trim().is_empty() {
+            return Err(AppError::Validation("Stage name is required".to_string()));
+        }
+
+        let stages = self.period_repo.find_stages_by_period(period_id).await?;
+        let timezone = self.period_repo.find_school_timezone(period.school_id).await?;
+
+        // New stages are appended after the existing ones
+        let mut schedules: Vec<StageSchedule> = stages.iter().map(StageSchedule::from_stage).collect();
+        let schedule = StageSchedule::resolve(
+            timezone,
+            period.closing_time,
+            registration_start,
+            registration_end,
+            announcement_at,
+        );
+        schedules.push(schedule);
+        validate_stage_schedule(period.registration_start, period.registration_end, &schedules)?;
+
+        self.validate_stage_paths(period_id, None, &path_ids).await?;
+
+        let stage_number = stages.last().map(|s| s.stage_number).unwrap_or(0) + 1;
+        let stage = self
+            .period_repo
+            .create_stage(
+                period_id,
+                stage_number,
+                name.trim(),
+                schedule.registration_start,
+                schedule.registration_end,
+                schedule.registration_opens_at,
+                schedule.registration_closes_at,
+                schedule.announcement_at,
+            )
+            .await?;
+
+        let paths = self
+            .period_repo
+            .set_stage_paths(stage.id, period_id, &path_ids)
+            .await?;
+
+        Ok((stage, paths))
+    }
+
+    pub async fn get_stages_by_period(
+        &self,
+        period_id: i32,
+    ) -> AppResult<(Vec<PeriodStage>, Vec<RegistrationPath>)> {
+        let _ = self.get_period(period_id).await?;
+
+        let stages = self.period_repo.find_stages_by_period(period_id).await?;
+        let paths = self.period_repo.find_paths_by_period(period_id).await?;
+
+        Ok((stages, paths))
+    }
+
+    pub async fn get_stage(&self, id: i32) -> AppResult<PeriodStage> {
+        self.period_repo
+            .find_stage_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Stage not found".to_string()))
+    }
+
+    pub async fn update_stage(
+        &self,
+        id: i32,
+        name: Option<String>,
+        registration_start: Option<NaiveDate>,
+        registration_end: Option<NaiveDate>,
+        announcement_at: Option<DateTime<Utc>>,
+        path_ids: Option<Vec<i32>>,
+    ) -> AppResult<(PeriodStage, Vec<RegistrationPath>)> {
+        let stage = self.get_stage(id).await?;
+
+        let period = self.get_period(stage.period_id).await?;
+        if period.status != "draft" {
+            return Err(AppError::Validation(
+                "Can only update stages for periods in draft status".to_string(),
+            ));
+        }
+
+        let name = name.unwrap_or_else(|| stage.name.clone());
+        if name.trim().is_empty() {
+            return Err(AppError::Validation("Stage name is required".to_string()));
+        }
+
+        let timezone = self.period_repo.find_school_timezone(period.school_id).await?;
+        let schedule = StageSchedule::resolve(
+            timezone,
+            period.closing_time,
+            registration_start.unwrap_or(stage.registration_start),
+            registration_end.unwrap_or(stage.registration_end),
+            announcement_at.or(stage.announcement_at),
+        );
+
+        let schedules: Vec<StageSchedule> = self
+            .period_repo
+            .find_stages_by_period(stage.period_id)
+            .await?
+            .iter()
+            .map(|s| if s.id == id { schedule } else { StageSchedule::from_stage(s) })
+            .collect();
+        validate_stage_schedule(period.registration_start, period.registration_end, &schedules)?;
+
+        if let Some(ref path_ids) = path_ids {
+            self.validate_stage_paths(stage.period_id, Some(id), path_ids).await?;
+        }
+
+        let updated_stage = self
+            .period_repo
+            .update_stage(
+                id,
+                name.trim(),
+                schedule.registration_start,
+                schedule.registration_end,
+                schedule.registration_opens_at,
+                schedule.registration_closes_at,
+                schedule.announcement_at,
+            )
+            .await?;
+
+        if let Some(path_ids) = path_ids {
+            self.period_repo
+                .set_stage_paths(id, stage.period_id, &path_ids)
+                .await?;
+        }
+
+        let paths = self
+            .period_repo
+            .find_paths_by_period(stage.period_id)
+            .await?
+            .into_iter()
+            .filter(|p| p.stage_id == Some(id))
+            .collect();
+
+        Ok((updated_stage, paths))
+    }
+
+    pub async fn delete_stage(&self, id: i32) -> AppResult<()> {
+        let stage = self.get_stage(id).await?;
+
+        let period = self.get_period(stage.period_id).await?;
+        if period.status != "draft" {
+            return Err(AppError::Validation(
+                "Can only delete stages for periods in draft status".to_string(),
+            ));
+        }
+
+        // Later stages cannot be renumbered once students know them
+        let stages = self.period_repo.find_stages_by_period(stage.period_id).await?;
+        if stages.last().map(|s| s.id) != Some(id) {
+            return Err(AppError::Validation(
+                "Only the last stage of a period can be deleted".to_string(),
+            ));
+        }
+
+        self.period_repo.delete_stage(id).await
+    }
+
+    /// Paths of a stage must belong to the period and not run in another stage
+    async fn validate_stage_paths(
+        &self,
+        period_id: i32,
+        stage_id: Option<i32>,
+        path_ids: &[i32],
+    ) -> AppResult<()> {
+        if path_ids.is_empty() {
+            return Err(AppError::Validation(
+                "A stage needs at least one registration path".to_string(),
+            ));
+        }
+
+        let paths = self.period_repo.find_paths_by_period(period_id).await?;
+        for path_id in path_ids {
+            let path = paths
+                .iter()
+                .find(|p| p.id == *path_id)
+                .ok_or_else(|| {
+                    AppError::Validation(format!(
+                        "Registration path {} does not belong to this period",
+                        path_id
+                    ))
+                })?;
+
+            if path.stage_id.is_some() && path.stage_id != stage_id {
+                return Err(AppError::Conflict(format!(
+                    "Registration path '{}' already runs in another stage",
+                    path.name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Window and announcement of a stage resolved in the school's timezone
+#[derive(Debug, Clone, Copy)]
+struct StageSchedule {
+    registration_start: NaiveDate,
+    registration_end: NaiveDate,
+    registration_opens_at: DateTime<Utc>,
+    registration_closes_at: DateTime<Utc>,
+    announcement_at: Option<DateTime<Utc>>,
+}
+
+impl StageSchedule {
+    fn resolve(
+        timezone: Tz,
+        closing_time: NaiveTime,
+        registration_start: NaiveDate,
+        registration_end: NaiveDate,
+        announcement_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            registration_start,
+            registration_end,
+            registration_opens_at: local_instant(registration_start, NaiveTime::MIN, timezone),
+            registration_closes_at: local_instant(registration_end, closing_time, timezone),
+            announcement_at,
+        }
+    }
+
+    fn from_stage(stage: &PeriodStage) -> Self {
+        Self {
+            registration_start: stage.registration_start,
+            registration_end: stage.registration_end,
+            registration_opens_at: stage.registration_opens_at,
+            registration_closes_at: stage.registration_closes_at,
+            announcement_at: stage.announcement_at,
+        }
+    }
+}
+
+/// Stages run one after another within the registration window of the
+/// period, and each stage announces after its own window closed
+fn validate_stage_schedule(
+    registration_start: NaiveDate,
+    registration_end: NaiveDate,
+    stages: &[StageSchedule],
+) -> AppResult<()> {
+    let mut previous: Option<&StageSchedule> = None;
+
+    for (index, stage) in stages.iter().enumerate() {
+        let number = index + 1;
+
+        if stage.registration_end < stage.registration_start {
+            return Err(AppError::Validation(format!(
+                "Stage {}: registration end must not be before registration start",
+                number
+            )));
+        }
+
+        if stage.registration_start < registration_start || stage.registration_end > registration_end {
+            return Err(AppError::Validation(format!(
+                "Stage {}: window must lie within the registration window of the period",
+                number
+            )));
+        }
+
+        if let Some(previous) = previous {
+            if stage.registration_start <= previous.registration_end {
+                return Err(AppError::Validation(format!(
+                    "Stage {} must start after stage {} ended",
+                    number,
+                    number - 1
+                )));
+            }
+        }
+
+        if let Some(announcement_at) = stage.announcement_at {
+            if announcement_at <= stage.registration_closes_at {
+                return Err(AppError::Validation(format!(
+                    "Stage {}: announcement time must be after the stage closed",
+                    number
+                )));
+            }
+        }
+
+        previous = Some(stage);
+    }
+
+    Ok(())
+}
+
+/// Validate major criteria and store subjects the way rapor grades are stored
+fn normalize_major_criteria(criteria: serde_json::Value) -> AppResult<serde_json::Value> {
+    let criteria = MajorCriterion::parse_list(&criteria)
+        .map_err(AppError::Validation)?
+        .into_iter()
+        .map(|criterion| match criterion {
+            MajorCriterion::SubjectGrade {
+                subject,
+                min_average,
+            } => MajorCriterion::SubjectGrade {
+                subject: normalize_subject(&subject),
+                min_average,
+            },
+            requirement => requirement,
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_value(criteria).map_err(|e| AppError::Internal(e.to_string()))
+}
+
+fn default_closing_time() -> NaiveTime {
+    NaiveTime::from_hms_opt(23, 59, 59).expect("valid closing time")
+}
+
+/// Resolve the period dates to instants in the school's timezone. The window
+/// opens at midnight of the first day and deadlines close at the closing time.
+pub fn period_deadlines(
+    timezone: Tz,
+    registration_start: NaiveDate,
+    registration_end: NaiveDate,
+    reenrollment_deadline: Option<NaiveDate>,
+    closing_time: NaiveTime,
+) -> PeriodDeadlines {
+    PeriodDeadlines {
+        closing_time,
+        registration_opens_at: local_instant(registration_start, NaiveTime::MIN, timezone),
+        registration_closes_at: local_instant(registration_end, closing_time, timezone),
+        reenrollment_closes_at: reenrollment_deadline
+            .map(|deadline| local_instant(deadline, closing_time, timezone)),
+    }
+}
+
+/// The registration window must lie within the period and results can only
+/// be published automatically after it closed
+fn validate_registration_window(
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    registration_start: NaiveDate,
+    registration_end: NaiveDate,
+    deadlines: &PeriodDeadlines,
+    announcement_at: Option<DateTime<Utc>>,
+) -> AppResult<()> {
+    if registration_end < registration_start {
+        return Err(AppError::Validation(
+            "Registration end must not be before registration start".to_string(),
+        ));
+    }
+
+    if registration_start < start_date || registration_end > end_date {
+        return Err(AppError::Validation(
+            "Registration window must lie within the period dates".to_string(),
+        ));
+    }
+
+    if let Some(announcement_at) = announcement_at {
+        if announcement_at <= deadlines.registration_closes_at {
+            return Err(AppError::Validation(
+                "Announcement time must be after the registration end".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_duplicate_policy(policy: &str) -> AppResult<()> {
+    if DuplicatePolicy::from_str(policy).is_none() {
+        return Err(AppError::Validation(
+            "Duplicate policy must be 'block', 'warn', or 'allow'".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_waitlist_policy(policy: &str) -> AppResult<()> {
+    if WaitlistPolicy::from_str(policy).is_none() {
+        return Err(AppError::Validation(
+            "Waitlist policy must be 'none' or 'promote'".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_refund_policy(policy: &str) -> AppResult<()> {
+    if RefundPolicy::from_str(policy).is_none() {
+        return Err(AppError::Validation(
+            "Refund policy must be 'none', 'full', or 'before_announcement'".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_document_rejection_policy(policy: &str) -> AppResult<()> {
+    if DocumentRejectionPolicy::from_str(policy).is_none() {
+        return Err(AppError::Validation(
+            "Document rejection policy must be 'revision' or 'reject'".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn validate_appeal_window(days: i32) -> AppResult<()> {
+    if !(0..=MAX_APPEAL_WINDOW_DAYS).contains(&days) {
+        return Err(AppError::Validation(format!(
+            "Appeal window must be between 0 and {} days",
+            MAX_APPEAL_WINDOW_DAYS
+        )));
+    }
+
+    Ok(())
+}
+
+fn validate_achievement_point_table(table: &serde_json::Value) -> AppResult<()> {
+    AchievementPointTable::from_config(Some(table))
+        .map(|_| ())
+        .map_err(AppError::Validation)
+}
+
+fn validate_age_rules(rules: &serde_json::Value) -> AppResult<()> {
+    AgeRules::from_config(rules)
+        .map(|_| ())
+        .map_err(AppError::Validation)
+}
+
+/// Check, lowercase and de-duplicate the document types a path requires
+fn normalize_required_documents(documents: Vec<String>) -> AppResult<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::new();
+
+    for document in documents {
+        let document = document.trim().to_lowercase();
+        if DocumentType::from_str(&document).is_none() {
+            return Err(AppError::Validation(format!(
+                "Unknown document type: {}",
+                document
+            )));
+        }
+
+        if !normalized.contains(&document) {
+            normalized.push(document);
+        }
+    }
+
+    Ok(normalized)
+}
+
+/// Years between two academic years in the YYYY/YYYY format
+pub fn academic_year_offset(from: &str, to: &str) -> Option<i32> {
+    let start_year = |academic_year: &str| -> Option<i32> {
+        let (start, end) = academic_year.trim().split_once('/')?;
+        let start: i32 = start.parse().ok()?;
+        let end: i32 = end.parse().ok()?;
+        (end == start + 1).then_some(start)
+    };
+
+    Some(start_year(to)? - start_year(from)?)
+}
+
+fn validate_path_data_schema(schema: &serde_json::Value) -> AppResult<()> {
+    json_schema::check_schema(schema)
+        .map_err(|e| AppError::Validation(format!("Invalid path_data_schema: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::timezone::parse_timezone;
+
+    #[test]
+    fn test_period_deadlines_in_school_timezone() {
+        let wita = parse_timezone("Asia/Makassar").unwrap();
+        let deadlines = period_deadlines(
+            wita,
+            NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 2, 28).unwrap(),
+            Some(NaiveDate::from_ymd_opt(2025, 3, 15).unwrap()),
+            NaiveTime::from_hms_opt(16, 0, 0).unwrap(),
+        );
+
+        assert_eq!(deadlines.registration_opens_at.to_rfc3339(), "2025-01-31T16:00:00+00:00");
+        assert_eq!(deadlines.registration_closes_at.to_rfc3339(), "2025-02-28T08:00:00+00:00");
+        assert_eq!(
+            deadlines.reenrollment_closes_at.map(|at| at.to_rfc3339()),
+            Some("2025-03-15T08:00:00+00:00".to_string())
+        );
+
+        // Announcing before the window closed in school time is rejected
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        let registration_start = NaiveDate::from_ymd_opt(2025, 2, 1).unwrap();
+        let registration_end = NaiveDate::from_ymd_opt(2025, 2, 28).unwrap();
+        let early = "2025-02-28T07:00:00Z".parse().unwrap();
+        let late = "2025-02-28T09:00:00Z".parse().unwrap();
+
+        assert!(validate_registration_window(
+            start, end, registration_start, registration_end, &deadlines, Some(early)
+        )
+        .is_err());
+        assert!(validate_registration_window(
+            start, end, registration_start, registration_end, &deadlines, Some(late)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_stage_schedule() {
+        let wib = parse_timezone("Asia/Jakarta").unwrap();
+        let closing = NaiveTime::from_hms_opt(23, 59, 59).unwrap();
+        let date = |day| NaiveDate::from_ymd_opt(2025, 6, day).unwrap();
+        let stage = |start, end, announcement: Option<&str>| {
+            StageSchedule::resolve(
+                wib,
+                closing,
+                date(start),
+                date(end),
+                announcement.map(|a| a.parse().unwrap()),
+            )
+        };
+
+        let tahap_1 = stage(2, 6, Some("2025-06-09T01:00:00Z"));
+        let tahap_2 = stage(10, 14, None);
+        assert!(validate_stage_schedule(date(1), date(20), &[tahap_1, tahap_2]).is_ok());
+
+        // Overlapping, outside the period window, announced before closing
+        let overlapping = stage(6, 14, None);
+        assert!(validate_stage_schedule(date(1), date(20), &[tahap_1, overlapping]).is_err());
+        assert!(validate_stage_schedule(date(3), date(20), &[tahap_1, tahap_2]).is_err());
+
+        let early = stage(2, 6, Some("2025-06-06T16:00:00Z"));
+        assert!(validate_stage_schedule(date(1), date(20), &[early]).is_err());
+    }
+
+    #[test]
+    fn test_clone_date_shift() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(academic_year_offset("2024/2025", "2025/2026"), Some(1));
+        assert_eq!(academic_year_offset("2024/2025", "2026/2027"), Some(2));
+        assert_eq!(academic_year_offset("2024/2025", "2025"), None);
+        assert_eq!(academic_year_offset("2024/2025", "2025/2027"), None);
+
+        let next_year = DateShift::Years(1);
+        assert_eq!(next_year.apply(date(2024, 6, 1)), date(2025, 6, 1));
+        assert_eq!(next_year.apply(date(2024, 2, 29)), date(2025, 2, 28));
+
+        // Re-anchoring on a new start date keeps the offsets
+        let reanchored = DateShift::Days((date(2025, 5, 5) - date(2024, 5, 1)).num_days());
+        assert_eq!(reanchored.apply(date(2024, 6, 1)), date(2025, 6, 5));
+
+        // Announcements keep their wall-clock time at the school
+        let wita = parse_timezone("Asia/Makassar").unwrap();
+        let announcement: DateTime<Utc> = "2024-07-05T01:00:00Z".parse().unwrap();
+        assert_eq!(
+            next_year.apply_instant(announcement, wita).to_rfc3339(),
+            "2025-07-05T01:00:00+00:00"
+        );
+
+        assert_eq!(
+            normalize_required_documents(vec![
+                " Kartu_Keluarga ".into(),
+                "akta_kelahiran".into(),
+                "kartu_keluarga".into()
+            ])
+            .unwrap(),
+            vec!["kartu_keluarga".to_string(), "akta_kelahiran".to_string()]
+        );
+        assert!(normalize_required_documents(vec!["kk".into()]).is_err());
+    }
 }
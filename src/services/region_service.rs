@@ -0,0 +1,672 @@
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::NaiveDate;
+
+use crate::models::region::{Region, RegionLevel};
+use crate::models::school::School;
+use crate::repositories::region_repo::RegionRepository;
+use crate::utils::error::{AppError, AppResult};
+
+pub struct RegionService {
+    region_repo: RegionRepository,
+}
+
+impl RegionService {
+    pub fn new(region_repo: RegionRepository) -> Self {
+        Self { region_repo }
+    }
+
+    pub async fn create_region(
+        &self,
+        parent_id: Option<i32>,
+        level: String,
+        code: String,
+        name: String,
+    ) -> AppResult<Region> {
+        let level = RegionLevel::from_str(&level).ok_or_else(|| {
+            AppError::Validation("Invalid level. Must be province or regency".to_string())
+        })?;
+
+        match (&level, parent_id) {
+            (RegionLevel::Province, Some(_)) => {
+                return Err(AppError::Validation(
+                    "A province cannot have a parent region".to_string(),
+                ));
+            }
+            (RegionLevel::Regency, None) => {
+                return Err(AppError::Validation(
+                    "A regency must belong to a province".to_string(),
+                ));
+            }
+            (RegionLevel::Regency, Some(parent_id)) => {
+                let parent = self.get_region(parent_id).await?;
+                if parent.level != RegionLevel::Province.as_str() {
+                    return Err(AppError::Validation(
+                        "Parent region must be a province".to_string(),
+                    ));
+                }
+            }
+            (RegionLevel::Province, None) => {}
+        }
+
+        self.region_repo
+            .create_region(parent_id, level.as_str(), &code, &name)
+            .await
+    }
+
+    pub async fn update_region(
+        &self,
+        id: i32,
+        code: Option<String>,
+        name: Option<String>,
+    ) -> AppResult<Region> {
+        let _ = self.get_region(id).await?;
+
+        self.region_repo
+            .update_region(id, code.as_deref(), name.as_deref())
+            .await
+    }
+
+    pub async fn get_region(&self, id: i32) -> AppResult<Region> {
+        self.region_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Region not found".to_string()))
+    }
+
+    /// List regions visible to the user
+    /// Super admin sees every region, region admin only their region and its sub-regions
+    pub async fn list_regions(
+        &self,
+        role: &str,
+        user_region_id: Option<i32>,
+        parent_id: Option<i32>,
+        level: Option<String>,
+    ) -> AppResult<Vec<Region>> {
+        let regions = self.region_repo.find_all(parent_id, level).await?;
+
+        if role == "super_admin" {
+            return Ok(regions);
+        }
+
+        let scope: HashSet<i32> = match user_region_id {
+            Some(region_id) => self.region_repo.find_scope_ids(region_id).await?.into_iter().collect(),
+            None => HashSet::new(),
+        };
+
+        Ok(regions.into_iter().filter(|r| scope.contains(&r.id)).collect())
+    }
+
+    /// Resolve the region IDs (region + sub-regions) a user may query for `region_id`
+    pub async fn resolve_scope(
+        &self,
+        role: &str,
+        user_region_id: Option<i32>,
+        region_id: i32,
+    ) -> AppResult<Vec<i32>> {
+        let _ = self.get_region(region_id).await?;
+
+        if role != "super_admin" {
+            let user_region_id = user_region_id.ok_or_else(|| {
+                AppError::Forbidden("User is not assigned to a region".to_string())
+            })?;
+
+            let allowed = self.region_repo.find_scope_ids(user_region_id).await?;
+            if !allowed.contains(&region_id) {
+                return Err(AppError::Forbidden(
+                    "You don't have access to this region".to_string(),
+                ));
+            }
+        }
+
+        self.region_repo.find_scope_ids(region_id).await
+    }
+
+    pub async fn list_schools(&self, scope: &[i32]) -> AppResult<Vec<School>> {
+        self.region_repo.find_schools(scope).await
+    }
+
+    /// Aggregated registration and selection statistics for all schools in the region
+    pub async fn get_stats(
+        &self,
+        region: &Region,
+        scope: &[i32],
+        academic_year: Option<String>,
+        level: Option<String>,
+    ) -> AppResult<RegionStats> {
+        let schools = sqlx::query_as::<_, SchoolRegistrationStats>(
+            r#"
+            SELECT s.id AS school_id,
+                   s.name AS school_name,
+                   s.region_id,
+                   COALESCE((
+                       SELECT SUM(rp.quota) FROM registration_paths rp
+                       JOIN periods qp ON qp.id = rp.period_id
+                       WHERE qp.school_id = s.id
+                         AND ($2::VARCHAR IS NULL OR qp.academic_year = $2)
+                         AND ($3::VARCHAR IS NULL OR qp.level = $3)
+                   ), 0)::BIGINT AS quota,
+                   COUNT(r.id) AS total,
+                   COUNT(r.id) FILTER (WHERE r.status = 'submitted') AS submitted,
+                   COUNT(r.id) FILTER (WHERE r.status = 'verified') AS verified,
+                   COUNT(r.id) FILTER (WHERE r.status = 'rejected') AS rejected,
+                   COUNT(r.id) FILTER (WHERE r.status = 'accepted') AS accepted,
+                   COUNT(r.id) FILTER (WHERE r.status = 'enrolled') AS enrolled
+            FROM schools s
+            LEFT JOIN periods p ON p.school_id = s.id
+                 AND ($2::VARCHAR IS NULL OR p.academic_year = $2)
+                 AND ($3::VARCHAR IS NULL OR p.level = $3)
+            LEFT JOIN registrations r ON r.period_id = p.id
+            WHERE s.region_id = ANY($1)
+            GROUP BY s.id, s.name, s.region_id
+            ORDER BY s.name
+            "#,
+        )
+        .bind(scope)
+        .bind(&academic_year)
+        .bind(&level)
+        .fetch_all(&self.region_repo.pool)
+        .await?;
+
+        let mut stats = RegionStats {
+            region_id: region.id,
+            region_name: region.name.clone(),
+            total_schools: schools.len() as i64,
+            quota: 0,
+            total: 0,
+            submitted: 0,
+            verified: 0,
+            rejected: 0,
+            accepted: 0,
+            enrolled: 0,
+            schools: Vec::new(),
+        };
+
+        for school in &schools {
+            stats.quota += school.quota;
+            stats.total += school.total;
+            stats.submitted += school.submitted;
+            stats.verified += school.verified;
+            stats.rejected += school.rejected;
+            stats.accepted += school.accepted;
+            stats.enrolled += school.enrolled;
+        }
+        stats.schools = schools;
+
+        Ok(stats)
+    }
+
+    /// Students (by NISN) with active registrations at more than one school in the region
+    pub async fn find_duplicates(
+        &self,
+        scope: &[i32],
+        academic_year: Option<String>,
+        level: Option<String>,
+    ) -> AppResult<Vec<DuplicateStudent>> {
+        let rows = sqlx::query_as::<_, DuplicateRegistration>(
+            r#"
+            SELECT r.id AS registration_id,
+                   r.student_nisn,
+                   r.student_name,
+                   r.school_id,
+                   s.name AS school_name,
+                   r.period_id,
+                   r.status
+            FROM registrations r
+            JOIN schools s ON s.id = r.school_id
+            JOIN periods p ON p.id = r.period_id
+            WHERE s.region_id = ANY($1)
+              AND ($2::VARCHAR IS NULL OR p.academic_year = $2)
+              AND ($3::VARCHAR IS NULL OR p.level = $3)
+              AND r.status NOT IN ('draft', 'rejected', 'expired')
+            ORDER BY r.student_nisn, r.created_at
+            "#,
+        )
+        .bind(scope)
+        .bind(&academic_year)
+        .bind(&level)
+        .fetch_all(&self.region_repo.pool)
+        .await?;
+
+        Ok(group_duplicates(rows))
+    }
+
+    /// Period calendar of all schools in the region with coordination warnings
+    pub async fn get_calendar(
+        &self,
+        scope: &[i32],
+        academic_year: Option<String>,
+        level: Option<String>,
+    ) -> AppResult<RegionCalendar> {
+        let periods = sqlx::query_as::<_, CalendarEntry>(
+            r#"
+            SELECT p.id AS period_id,
+                   p.school_id,
+                   s.name AS school_name,
+                   p.academic_year,
+                   p.level,
+                   p.status,
+                   p.registration_start,
+                   p.registration_end,
+                   p.announcement_date,
+                   p.reenrollment_deadline
+            FROM periods p
+            JOIN schools s ON s.id = p.school_id
+            WHERE s.region_id = ANY($1)
+              AND ($2::VARCHAR IS NULL OR p.academic_year = $2)
+              AND ($3::VARCHAR IS NULL OR p.level = $3)
+            ORDER BY p.registration_start, s.name
+            "#,
+        )
+        .bind(scope)
+        .bind(&academic_year)
+        .bind(&level)
+        .fetch_all(&self.region_repo.pool)
+        .await?;
+
+        let conflicts = find_calendar_conflicts(&periods);
+
+        Ok(RegionCalendar { periods, conflicts })
+    }
+}
+
+/// Group registrations by NISN, keeping students registered at more than one school
+pub fn group_duplicates(rows: Vec<DuplicateRegistration>) -> Vec<DuplicateStudent> {
+    let mut grouped: BTreeMap<String, Vec<DuplicateRegistration>> = BTreeMap::new();
+    for row in rows {
+        grouped.entry(row.student_nisn.clone()).or_default().push(row);
+    }
+
+    grouped
+        .into_iter()
+        .filter_map(|(student_nisn, registrations)| {
+            let schools: HashSet<i32> = registrations.iter().map(|r| r.school_id).collect();
+            if schools.len() < 2 {
+                return None;
+            }
+
+            let accepted = registrations
+                .iter()
+                .filter(|r| r.status == "accepted" || r.status == "enrolled")
+                .count();
+
+            Some(DuplicateStudent {
+                student_nisn,
+                student_name: registrations[0].student_name.clone(),
+                school_count: schools.len() as i64,
+                multiple_accepted: accepted > 1,
+                registrations,
+            })
+        })
+        .collect()
+}
+
+/// Detect schedules that work against each other within the same academic year and level:
+/// - a school announcing before another school has closed registration
+/// - a re-enrollment deadline falling before another school's announcement
+pub fn find_calendar_conflicts(periods: &[CalendarEntry]) -> Vec<CalendarConflict> {
+    let mut conflicts = Vec::new();
+
+    for period in periods {
+        for other in periods {
+            if other.period_id == period.period_id
+                || other.school_id == period.school_id
+                || other.academic_year != period.academic_year
+                || !other.level.eq_ignore_ascii_case(&period.level)
+            {
+                continue;
+            }
+
+            if let Some(announcement) = period.announcement_date {
+                if announcement < other.registration_end {
+                    conflicts.push(CalendarConflict {
+                        period_id: period.period_id,
+                        school_id: period.school_id,
+                        other_period_id: other.period_id,
+                        other_school_id: other.school_id,
+                        kind: "announcement_before_close".to_string(),
+                        message: format!(
+                            "{} mengumumkan hasil ({}) sebelum pendaftaran {} ditutup ({})",
+                            period.school_name, announcement, other.school_name, other.registration_end
+                        ),
+                    });
+                }
+            }
+
+            if let (Some(deadline), Some(announcement)) =
+                (period.reenrollment_deadline, other.announcement_date)
+            {
+                if deadline < announcement {
+                    conflicts.push(CalendarConflict {
+                        period_id: period.period_id,
+                        school_id: period.school_id,
+                        other_period_id: other.period_id,
+                        other_school_id: other.school_id,
+                        kind: "reenrollment_before_announcement".to_string(),
+                        message: format!(
+                            "Batas daftar ulang {} ({}) sebelum pengumuman {} ({})",
+                            period.school_name, deadline, other.school_name, announcement
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    conflicts
+}
+
+/// Statistik pendaftaran per sekolah
+#[derive(Debug, Clone, sqlx::FromRow, serde::Serialize, utoipa::ToSchema)]
+pub struct SchoolRegistrationStats {
+    /// ID sekolah
+    #[schema(example = 1)]
+    pub school_id: i32,
+
+    /// Nama sekolah
+    #[schema(example = "SMP Negeri 1 Bandung")]
+    pub school_name: String,
+
+    /// ID wilayah sekolah
+    #[schema(example = 2)]
+    pub region_id: Option<i32>,
+
+    /// Total kuota semua jalur
+    #[schema(example = 320)]
+    pub quota: i64,
+
+    /// Total pendaftaran
+    #[schema(example = 500)]
+    pub total: i64,
+
+    /// Menunggu verifikasi
+    #[schema(example = 40)]
+    pub submitted: i64,
+
+    /// Terverifikasi
+    #[schema(example = 300)]
+    pub verified: i64,
+
+    /// Ditolak
+    #[schema(example = 20)]
+    pub rejected: i64,
+
+    /// Diterima
+    #[schema(example = 120)]
+    pub accepted: i64,
+
+    /// Sudah daftar ulang
+    #[schema(example = 100)]
+    pub enrolled: i64,
+}
+
+/// Statistik gabungan wilayah
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct RegionStats {
+    /// ID wilayah
+    #[schema(example = 2)]
+    pub region_id: i32,
+
+    /// Nama wilayah
+    #[schema(example = "Kota Bandung")]
+    pub region_name: String,
+
+    /// Jumlah sekolah
+    #[schema(example = 25)]
+    pub total_schools: i64,
+
+    /// Total kuota
+    #[schema(example = 8000)]
+    pub quota: i64,
+
+    /// Total pendaftaran
+    #[schema(example = 12000)]
+    pub total: i64,
+
+    /// Menunggu verifikasi
+    #[schema(example = 1000)]
+    pub submitted: i64,
+
+    /// Terverifikasi
+    #[schema(example = 7000)]
+    pub verified: i64,
+
+    /// Ditolak
+    #[schema(example = 500)]
+    pub rejected: i64,
+
+    /// Diterima
+    #[schema(example = 3000)]
+    pub accepted: i64,
+
+    /// Sudah daftar ulang
+    #[schema(example = 2500)]
+    pub enrolled: i64,
+
+    /// Statistik per sekolah
+    pub schools: Vec<SchoolRegistrationStats>,
+}
+
+/// Pendaftaran siswa yang terdaftar di lebih dari satu sekolah
+#[derive(Debug, Clone, sqlx::FromRow, serde::Serialize, utoipa::ToSchema)]
+pub struct DuplicateRegistration {
+    /// ID pendaftaran
+    #[schema(example = 10)]
+    pub registration_id: i32,
+
+    /// NISN siswa
+    #[schema(example = "0012345678")]
+    pub student_nisn: String,
+
+    /// Nama siswa
+    #[schema(example = "Ahmad Fauzi")]
+    pub student_name: String,
+
+    /// ID sekolah
+    #[schema(example = 1)]
+    pub school_id: i32,
+
+    /// Nama sekolah
+    #[schema(example = "SMP Negeri 1 Bandung")]
+    pub school_name: String,
+
+    /// ID periode
+    #[schema(example = 3)]
+    pub period_id: i32,
+
+    /// Status pendaftaran
+    #[schema(example = "accepted")]
+    pub status: String,
+}
+
+/// Siswa dengan pendaftaran di beberapa sekolah
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct DuplicateStudent {
+    /// NISN siswa
+    #[schema(example = "0012345678")]
+    pub student_nisn: String,
+
+    /// Nama siswa
+    #[schema(example = "Ahmad Fauzi")]
+    pub student_name: String,
+
+    /// Jumlah sekolah yang didaftar
+    #[schema(example = 3)]
+    pub school_count: i64,
+
+    /// Diterima di lebih dari satu sekolah
+    #[schema(example = true)]
+    pub multiple_accepted: bool,
+
+    /// Daftar pendaftaran
+    pub registrations: Vec<DuplicateRegistration>,
+}
+
+/// Jadwal periode sekolah
+#[derive(Debug, Clone, sqlx::FromRow, serde::Serialize, utoipa::ToSchema)]
+pub struct CalendarEntry {
+    /// ID periode
+    #[schema(example = 3)]
+    pub period_id: i32,
+
+    /// ID sekolah
+    #[schema(example = 1)]
+    pub school_id: i32,
+
+    /// Nama sekolah
+    #[schema(example = "SMP Negeri 1 Bandung")]
+    pub school_name: String,
+
+    /// Tahun ajaran
+    #[schema(example = "2024/2025")]
+    pub academic_year: String,
+
+    /// Jenjang
+    #[schema(example = "SMP")]
+    pub level: String,
+
+    /// Status periode
+    #[schema(example = "active")]
+    pub status: String,
+
+    /// Mulai pendaftaran
+    #[schema(value_type = String, example = "2024-06-01")]
+    pub registration_start: NaiveDate,
+
+    /// Akhir pendaftaran
+    #[schema(value_type = String, example = "2024-06-15")]
+    pub registration_end: NaiveDate,
+
+    /// Tanggal pengumuman
+    #[schema(value_type = Option<String>, example = "2024-06-25")]
+    pub announcement_date: Option<NaiveDate>,
+
+    /// Batas daftar ulang
+    #[schema(value_type = Option<String>, example = "2024-07-05")]
+    pub reenrollment_deadline: Option<NaiveDate>,
+}
+
+/// Jadwal yang saling bertabrakan antar sekolah
+#[derive(Debug, Clone, serde::Serialize, utoipa::ToSchema)]
+pub struct CalendarConflict {
+    /// ID periode yang bermasalah
+    #[schema(example = 3)]
+    pub period_id: i32,
+
+    /// ID sekolah yang bermasalah
+    #[schema(example = 1)]
+    pub school_id: i32,
+
+    /// ID periode pembanding
+    #[schema(example = 4)]
+    pub other_period_id: i32,
+
+    /// ID sekolah pembanding
+    #[schema(example = 2)]
+    pub other_school_id: i32,
+
+    /// Jenis (announcement_before_close/reenrollment_before_announcement)
+    #[schema(example = "announcement_before_close")]
+    pub kind: String,
+
+    /// Keterangan
+    #[schema(example = "SMP Negeri 1 mengumumkan hasil (2024-06-20) sebelum pendaftaran SMP Negeri 2 ditutup (2024-06-22)")]
+    pub message: String,
+}
+
+/// Kalender periode wilayah
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct RegionCalendar {
+    /// Periode semua sekolah di wilayah
+    pub periods: Vec<CalendarEntry>,
+
+    /// Jadwal yang perlu dikoordinasikan
+    pub conflicts: Vec<CalendarConflict>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registration(id: i32, nisn: &str, school_id: i32, status: &str) -> DuplicateRegistration {
+        DuplicateRegistration {
+            registration_id: id,
+            student_nisn: nisn.to_string(),
+            student_name: format!("Siswa {}", nisn),
+            school_id,
+            school_name: format!("Sekolah {}", school_id),
+            period_id: school_id,
+            status: status.to_string(),
+        }
+    }
+
+    fn period(id: i32, school_id: i32, end: (u32, u32), announcement: Option<(u32, u32)>) -> CalendarEntry {
+        let date = |(m, d): (u32, u32)| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        CalendarEntry {
+            period_id: id,
+            school_id,
+            school_name: format!("Sekolah {}", school_id),
+            academic_year: "2024/2025".to_string(),
+            level: "SMP".to_string(),
+            status: "active".to_string(),
+            registration_start: date((6, 1)),
+            registration_end: date(end),
+            announcement_date: announcement.map(date),
+            reenrollment_deadline: None,
+        }
+    }
+
+    #[test]
+    fn test_group_duplicates_across_schools() {
+        let rows = vec![
+            registration(1, "0011111111", 1, "accepted"),
+            registration(2, "0011111111", 2, "accepted"),
+            registration(3, "0022222222", 1, "verified"),
+            registration(4, "0033333333", 1, "submitted"),
+            registration(5, "0033333333", 3, "verified"),
+        ];
+
+        let duplicates = group_duplicates(rows);
+
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates[0].student_nisn, "0011111111");
+        assert!(duplicates[0].multiple_accepted);
+        assert_eq!(duplicates[1].student_nisn, "0033333333");
+        assert!(!duplicates[1].multiple_accepted);
+    }
+
+    #[test]
+    fn test_same_school_is_not_a_duplicate() {
+        let rows = vec![
+            registration(1, "0011111111", 1, "verified"),
+            registration(2, "0011111111", 1, "submitted"),
+        ];
+
+        assert!(group_duplicates(rows).is_empty());
+    }
+
+    #[test]
+    fn test_announcement_before_other_school_closes() {
+        let periods = vec![
+            period(1, 1, (6, 10), Some((6, 12))),
+            period(2, 2, (6, 15), Some((6, 20))),
+        ];
+
+        let conflicts = find_calendar_conflicts(&periods);
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].period_id, 1);
+        assert_eq!(conflicts[0].other_period_id, 2);
+        assert_eq!(conflicts[0].kind, "announcement_before_close");
+    }
+
+    #[test]
+    fn test_aligned_calendar_has_no_conflicts() {
+        let periods = vec![
+            period(1, 1, (6, 15), Some((6, 20))),
+            period(2, 2, (6, 15), Some((6, 20))),
+        ];
+
+        assert!(find_calendar_conflicts(&periods).is_empty());
+    }
+}
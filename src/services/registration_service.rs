@@ -1,20 +1,53 @@
 use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
 
-use crate::models::registration::{Document, Registration};
+use crate::models::age_rule::AgeRules;
+use crate::models::period::{Period, PeriodStage, RefundPolicy, RegistrationPath, WaitlistPolicy};
+use crate::models::registration::{
+    Document, DocumentDetail, DocumentReplacement, FieldChange, Registration, RegistrationFallbackPath,
+    RegistrationRevision, RegistrationStatus, RegistrationStatusChange, RevisionChanges,
+    RevisionField, WithdrawalPlan, WithdrawalResult,
+};
 use crate::repositories::period_repo::PeriodRepository;
 use crate::repositories::registration_repo::RegistrationRepository;
-use crate::utils::error::{AppError, AppResult};
+use crate::services::assistance_service::AssistanceService;
+use crate::services::document_service::{DocumentService, DOCUMENT_MIME_TYPES, MAX_DOCUMENT_BYTES};
+use crate::services::duplicate_service::DuplicateService;
+use crate::services::scoring_service::path_data_schema;
+use crate::utils::error::{AppError, AppResult, FieldError};
+use crate::utils::json_schema;
+use crate::utils::timezone::format_local;
+use crate::utils::validation::{validate_age, validate_identity, IdentityInput};
+
+/// Fallback path chosen by the applicant, with the extra data that path requires
+#[derive(Debug, Clone)]
+pub struct FallbackPathInput {
+    pub path_id: i32,
+    pub path_data: serde_json::Value,
+}
 
 pub struct RegistrationService {
     registration_repo: RegistrationRepository,
     period_repo: PeriodRepository,
+    duplicate_service: DuplicateService,
+    assistance_service: AssistanceService,
+    document_service: DocumentService,
 }
 
 impl RegistrationService {
-    pub fn new(registration_repo: RegistrationRepository, period_repo: PeriodRepository) -> Self {
+    pub fn new(
+        registration_repo: RegistrationRepository,
+        period_repo: PeriodRepository,
+        duplicate_service: DuplicateService,
+        assistance_service: AssistanceService,
+        document_service: DocumentService,
+    ) -> Self {
         Self {
             registration_repo,
             period_repo,
+            duplicate_service,
+            assistance_service,
+            document_service,
         }
     }
 
@@ -33,6 +66,8 @@ impl RegistrationService {
         student_address: String,
         student_phone: Option<String>,
         student_email: Option<String>,
+        student_nik: Option<String>,
+        student_region_code: Option<String>,
         parent_name: String,
         parent_nik: String,
         parent_phone: String,
@@ -42,6 +77,7 @@ impl RegistrationService {
         previous_school_npsn: Option<String>,
         previous_school_address: Option<String>,
         path_data: serde_json::Value,
+        fallback_paths: Vec<FallbackPathInput>,
     ) -> AppResult<Registration> {
         // Validate period exists and is active
         let period = self
@@ -50,12 +86,6 @@ impl RegistrationService {
             .await?
             .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
 
-        if period.status != "active" {
-            return Err(AppError::Validation(
-                "Period is not active. Registration is closed.".to_string(),
-            ));
-        }
-
         // Validate path exists and belongs to period
         let path = self
             .period_repo
@@ -69,20 +99,52 @@ impl RegistrationService {
             ));
         }
 
-        // Validate NISN format (16 digits)
-        if student_nisn.len() != 10 {
+        // Paths of a stage (tahap) are open during the window of that stage
+        let stage = self.find_path_stage(&path).await?;
+        let timezone = self.period_repo.find_school_timezone(period.school_id).await?;
+        check_registration_window(&period, stage.as_ref(), None, Utc::now(), timezone)?;
+
+        // Students accepted in an earlier stage cannot take part in later ones
+        if stage.is_some()
+            && self
+                .registration_repo
+                .is_accepted_in_period(period_id, student_nisn.trim())
+                .await?
+        {
             return Err(AppError::Validation(
-                "NISN must be 10 digits".to_string(),
+                "Student has already been accepted in an earlier stage of this period".to_string(),
             ));
         }
 
-        // Validate NIK format (16 digits)
-        if parent_nik.len() != 16 {
-            return Err(AppError::Validation(
-                "NIK must be 16 digits".to_string(),
-            ));
+        let fallback_path_models = self.validate_fallback_paths(&path, &fallback_paths).await?;
+
+        // Validate NISN/NIK structure and consistency with the form data
+        let mut errors = validate_identity(&IdentityInput {
+            student_nisn: &student_nisn,
+            student_nik: student_nik.as_deref(),
+            student_birth_date,
+            student_gender: &student_gender,
+            student_region_code: student_region_code.as_deref(),
+            parent_nik: &parent_nik,
+        });
+        errors.extend(validate_age(&age_rules(&period)?, student_birth_date));
+
+        let fallbacks: Vec<(RegistrationPath, serde_json::Value)> = fallback_path_models
+            .into_iter()
+            .zip(fallback_paths.iter().map(|f| f.path_data.clone()))
+            .collect();
+        errors.extend(path_data_errors(&path, &path_data, &fallbacks));
+
+        if !errors.is_empty() {
+            return Err(AppError::FieldValidation(errors));
         }
 
+        // Detect registrations of the same student/parent at other schools
+        let duplicates = self
+            .duplicate_service
+            .check_registration(&period, &student_nisn, &parent_nik, None)
+            .await?;
+
         // Convert NaiveDate to DateTime<Utc>
         let student_birth_datetime = student_birth_date
             .and_time(NaiveTime::from_hms_opt(0, 0, 0).unwrap())
@@ -105,6 +167,8 @@ impl RegistrationService {
                 &student_address,
                 student_phone.as_deref(),
                 student_email.as_deref(),
+                student_nik.as_deref(),
+                student_region_code.as_deref(),
                 &parent_name,
                 &parent_nik,
                 &parent_phone,
@@ -117,9 +181,120 @@ impl RegistrationService {
             )
             .await?;
 
+        if !fallback_paths.is_empty() {
+            self.save_fallback_paths(registration.id, fallback_paths).await?;
+        }
+
+        self.duplicate_service
+            .record_flags(registration.id, &duplicates)
+            .await?;
+
         Ok(registration)
     }
 
+    /// Fallback paths must belong to the same period and be processed by
+    /// selection after the primary path, in the order they are listed.
+    async fn validate_fallback_paths(
+        &self,
+        primary_path: &RegistrationPath,
+        fallback_paths: &[FallbackPathInput],
+    ) -> AppResult<Vec<RegistrationPath>> {
+        let mut previous = primary_path.clone();
+        let mut seen = vec![primary_path.id];
+        let mut paths = Vec::with_capacity(fallback_paths.len());
+
+        for fallback in fallback_paths {
+            if seen.contains(&fallback.path_id) {
+                return Err(AppError::Validation(
+                    "Fallback paths must be distinct and differ from the primary path".to_string(),
+                ));
+            }
+
+            let path = self
+                .period_repo
+                .find_path_by_id(fallback.path_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Fallback path not found".to_string()))?;
+
+            if path.period_id != primary_path.period_id {
+                return Err(AppError::Validation(
+                    "Fallback path does not belong to this period".to_string(),
+                ));
+            }
+
+            if path.stage_id != primary_path.stage_id {
+                return Err(AppError::Validation(
+                    "Fallback paths must run in the same stage as the primary path".to_string(),
+                ));
+            }
+
+            if !fallback.path_data.is_object() {
+                return Err(AppError::Validation(
+                    "Fallback path data must be a JSON object".to_string(),
+                ));
+            }
+
+            if (path.selection_order, path.id) <= (previous.selection_order, previous.id) {
+                return Err(AppError::Validation(format!(
+                    "Fallback path '{}' is selected before '{}'. Fallback paths must follow the selection order",
+                    path.name, previous.name
+                )));
+            }
+
+            seen.push(path.id);
+            previous = path.clone();
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+
+    async fn save_fallback_paths(
+        &self,
+        registration_id: i32,
+        fallback_paths: Vec<FallbackPathInput>,
+    ) -> AppResult<Vec<RegistrationFallbackPath>> {
+        let entries: Vec<(i32, serde_json::Value)> = fallback_paths
+            .into_iter()
+            .map(|f| (f.path_id, f.path_data))
+            .collect();
+
+        self.registration_repo
+            .replace_fallback_paths(registration_id, &entries)
+            .await
+    }
+
+    /// Stored fallback paths of a registration with their path_data
+    async fn load_fallbacks(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<(RegistrationPath, serde_json::Value)>> {
+        let mut fallbacks = Vec::new();
+
+        for fallback in self
+            .registration_repo
+            .find_fallback_paths_by_registration(registration_id)
+            .await?
+        {
+            if let Some(path) = self.period_repo.find_path_by_id(fallback.path_id).await? {
+                fallbacks.push((path, fallback.path_data));
+            }
+        }
+
+        Ok(fallbacks)
+    }
+
+    pub async fn list_fallback_paths(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<RegistrationFallbackPath>> {
+        let _ = self.get_registration(registration_id).await?;
+
+        self.registration_repo
+            .find_fallback_paths_by_registration(registration_id)
+            .await
+    }
+
     pub async fn get_registration(&self, id: i32) -> AppResult<Registration> {
         self.registration_repo
             .find_by_id(id)
@@ -178,21 +353,116 @@ impl RegistrationService {
         student_address: Option<String>,
         student_phone: Option<String>,
         student_email: Option<String>,
+        student_nik: Option<String>,
+        student_region_code: Option<String>,
         parent_name: Option<String>,
         parent_nik: Option<String>,
         parent_phone: Option<String>,
         parent_occupation: Option<String>,
         parent_income: Option<String>,
         path_data: Option<serde_json::Value>,
+        fallback_paths: Option<Vec<FallbackPathInput>>,
     ) -> AppResult<Registration> {
         // Check if registration exists
         let registration = self.get_registration(id).await?;
 
-        // Only allow updates if status is draft
-        if registration.status != "draft" {
-            return Err(AppError::Validation(
-                "Can only update registrations in draft status".to_string(),
-            ));
+        // Drafts can be edited within the registration window; registrations sent
+        // back for revision only in the fields the verifier flagged
+        match registration.status.as_str() {
+            "draft" => self.ensure_window_open(&registration).await?,
+            "revision_requested" => {
+                let revision = self.open_revision(id).await?;
+                let provided: Vec<&str> = [
+                    ("student_name", student_name.is_some()),
+                    ("student_gender", student_gender.is_some()),
+                    ("student_birth_place", student_birth_place.is_some()),
+                    ("student_birth_date", student_birth_date.is_some()),
+                    ("student_religion", student_religion.is_some()),
+                    ("student_address", student_address.is_some()),
+                    ("student_phone", student_phone.is_some()),
+                    ("student_email", student_email.is_some()),
+                    ("student_nik", student_nik.is_some()),
+                    ("student_region_code", student_region_code.is_some()),
+                    ("parent_name", parent_name.is_some()),
+                    ("parent_nik", parent_nik.is_some()),
+                    ("parent_phone", parent_phone.is_some()),
+                    ("parent_occupation", parent_occupation.is_some()),
+                    ("parent_income", parent_income.is_some()),
+                    ("path_data", path_data.is_some()),
+                    ("fallback_paths", fallback_paths.is_some()),
+                ]
+                .into_iter()
+                .filter(|(_, given)| *given)
+                .map(|(field, _)| field)
+                .collect();
+
+                let errors: Vec<FieldError> = unflagged_fields(&provided, &revision.flagged_fields())
+                    .into_iter()
+                    .map(|field| FieldError::new(field, "This field was not flagged for revision"))
+                    .collect();
+                if !errors.is_empty() {
+                    return Err(AppError::FieldValidation(errors));
+                }
+            }
+            _ => {
+                return Err(AppError::Validation(
+                    "Can only update registrations in draft status or sent back for revision"
+                        .to_string(),
+                ));
+            }
+        }
+
+        let primary_path = self
+            .period_repo
+            .find_path_by_id(registration.path_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+
+        let fallbacks = match fallback_paths {
+            Some(ref fallback_paths) => self
+                .validate_fallback_paths(&primary_path, fallback_paths)
+                .await?
+                .into_iter()
+                .zip(fallback_paths.iter().map(|f| f.path_data.clone()))
+                .collect(),
+            None => self.load_fallbacks(id).await?,
+        };
+
+        let period = self
+            .period_repo
+            .find_by_id(registration.period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+
+        // Re-check identity fields against the merged registration data
+        let birth_date =
+            student_birth_date.unwrap_or_else(|| registration.student_birth_date.date_naive());
+        let mut errors = validate_identity(&IdentityInput {
+            student_nisn: &registration.student_nisn,
+            student_nik: student_nik.as_deref().or(registration.student_nik.as_deref()),
+            student_birth_date: birth_date,
+            student_gender: student_gender
+                .as_deref()
+                .unwrap_or(&registration.student_gender),
+            student_region_code: student_region_code
+                .as_deref()
+                .or(registration.student_region_code.as_deref()),
+            parent_nik: parent_nik.as_deref().unwrap_or(&registration.parent_nik),
+        });
+        errors.extend(validate_age(&age_rules(&period)?, birth_date));
+
+        errors.extend(path_data_errors(
+            &primary_path,
+            path_data.as_ref().unwrap_or(&registration.path_data),
+            &fallbacks,
+        ));
+
+        if !errors.is_empty() {
+            return Err(AppError::FieldValidation(errors));
+        }
+
+        if let Some(fallback_paths) = fallback_paths {
+            self.save_fallback_paths(id, fallback_paths).await?;
         }
 
         // Convert NaiveDate to DateTime<Utc> if provided
@@ -214,6 +484,8 @@ impl RegistrationService {
                 student_address.as_deref(),
                 student_phone.as_deref(),
                 student_email.as_deref(),
+                student_nik.as_deref(),
+                student_region_code.as_deref(),
                 parent_name.as_deref(),
                 parent_nik.as_deref(),
                 parent_phone.as_deref(),
@@ -230,6 +502,10 @@ impl RegistrationService {
         // Check if registration exists
         let registration = self.get_registration(id).await?;
 
+        if registration.status == "revision_requested" {
+            return self.resubmit_revision(registration).await;
+        }
+
         // Only allow submission if status is draft
         if registration.status != "draft" {
             return Err(AppError::Validation(
@@ -237,6 +513,8 @@ impl RegistrationService {
             ));
         }
 
+        self.ensure_window_open(&registration).await?;
+
         // Validate completeness - check if required documents are uploaded
         let documents = self
             .registration_repo
@@ -249,6 +527,84 @@ impl RegistrationService {
             ));
         }
 
+        // Path data must still satisfy the path schemas
+        let primary_path = self
+            .period_repo
+            .find_path_by_id(registration.path_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+
+        let period = self
+            .period_repo
+            .find_by_id(registration.period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+
+        let fallbacks = self.load_fallbacks(id).await?;
+        let mut errors = path_data_errors(&primary_path, &registration.path_data, &fallbacks);
+
+        // The student must still meet the age rules of the period
+        errors.extend(validate_age(
+            &age_rules(&period)?,
+            registration.student_birth_date.date_naive(),
+        ));
+
+        // Prestasi is scored on the server-computed rapor average
+        if primary_path.path_type == "prestasi" && registration.rapor_average.is_none() {
+            errors.push(FieldError::new(
+                "grades",
+                "Rapor grades for all required subjects and semesters must be entered",
+            ));
+        }
+
+        // Every achievement must be linked to its certificate for verification
+        let achievements = self
+            .registration_repo
+            .find_achievements_by_registration(id)
+            .await?;
+
+        for (index, achievement) in achievements.iter().enumerate() {
+            if achievement.document_id.is_none() {
+                errors.push(FieldError::new(
+                    &format!("achievements[{}].document_id", index),
+                    "Upload the sertifikat_prestasi document of this achievement",
+                ));
+            }
+        }
+
+        // SMK periods with majors need at least one major choice
+        if !self
+            .period_repo
+            .find_majors_by_period(period.id)
+            .await?
+            .is_empty()
+            && self
+                .registration_repo
+                .find_major_choices_by_registration(id)
+                .await?
+                .is_empty()
+        {
+            errors.push(FieldError::new(
+                "major_choices",
+                "Choose at least one major before submitting",
+            ));
+        }
+
+        if !errors.is_empty() {
+            return Err(AppError::FieldValidation(errors));
+        }
+
+        // Re-check duplicates, other schools may have received registrations since creation
+        let duplicates = self
+            .duplicate_service
+            .check_registration(
+                &period,
+                &registration.student_nisn,
+                &registration.parent_nik,
+                Some(id),
+            )
+            .await?;
+
         // Generate registration number
         let registration_number = self
             .registration_repo
@@ -267,6 +623,16 @@ impl RegistrationService {
             .update_status(id, "submitted", None)
             .await?;
 
+        self.duplicate_service.record_flags(id, &duplicates).await?;
+
+        // Afirmasi applicants are checked against the imported assistance lists
+        let is_afirmasi = primary_path.path_type == "afirmasi"
+            || fallbacks.iter().any(|(path, _)| path.path_type == "afirmasi");
+
+        if is_afirmasi {
+            self.assistance_service.match_registration(id).await?;
+        }
+
         // TODO: Send notification email
 
         Ok(submitted_registration)
@@ -277,33 +643,73 @@ impl RegistrationService {
         &self,
         registration_id: i32,
         document_type: String,
-        file_url: String,
-        file_name: String,
-        file_size: i64,
-        mime_type: String,
+        mut file_url: String,
+        mut file_name: String,
+        mut file_size: i64,
+        mut mime_type: String,
     ) -> AppResult<Document> {
         // Check if registration exists
         let registration = self.get_registration(registration_id).await?;
 
-        // Only allow document upload if status is draft
-        if registration.status != "draft" {
-            return Err(AppError::Validation(
-                "Can only upload documents for registrations in draft status".to_string(),
-            ));
+        // Drafts accept any document; revisions only replacements of flagged documents
+        match registration.status.as_str() {
+            "draft" => self.ensure_window_open(&registration).await?,
+            "revision_requested" => {
+                let revision = self.open_revision(registration_id).await?;
+                let replaces_flagged = revision.flagged_documents().iter().any(|flagged| {
+                    revision.flagged_document_type(flagged.document_id).as_deref()
+                        == Some(document_type.as_str())
+                });
+
+                if !replaces_flagged {
+                    return Err(AppError::Validation(format!(
+                        "No {} document was flagged for revision",
+                        document_type
+                    )));
+                }
+            }
+            _ => {
+                return Err(AppError::Validation(
+                    "Can only upload documents for registrations in draft status".to_string(),
+                ));
+            }
         }
 
-        // Validate file size (max 2MB)
-        if file_size > 2 * 1024 * 1024 {
+        // Validate mime type
+        if !DOCUMENT_MIME_TYPES.contains(&mime_type.as_str()) {
             return Err(AppError::Validation(
-                "File size must not exceed 2MB".to_string(),
+                "File type must be JPEG, PNG, or PDF".to_string(),
             ));
         }
 
-        // Validate mime type
-        let allowed_types = vec!["image/jpeg", "image/png", "image/jpg", "application/pdf"];
-        if !allowed_types.contains(&mime_type.as_str()) {
+        // Only files uploaded to the document bucket are accepted
+        self.document_service.ensure_in_bucket(&file_url)?;
+
+        // Shrink oversized photos before enforcing the size limit
+        let mut original_file_size = None;
+        if file_size > MAX_DOCUMENT_BYTES {
+            let recompressed = self
+                .document_service
+                .recompress_oversized(&file_url, &mime_type)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Could not recompress {}: {}", file_url, e);
+                    None
+                });
+
+            if let Some(photo) = recompressed {
+                original_file_size = Some(file_size);
+                file_url = photo.file_url;
+                file_size = photo.file_size;
+                mime_type = "image/jpeg".to_string();
+                file_name = jpeg_file_name(&file_name);
+            }
+        }
+
+        // Validate file size (max 2MB)
+        if file_size > MAX_DOCUMENT_BYTES {
             return Err(AppError::Validation(
-                "File type must be JPEG, PNG, or PDF".to_string(),
+                "File size must not exceed 2MB".to_string(),
             ));
         }
 
@@ -317,13 +723,209 @@ impl RegistrationService {
                 &file_name,
                 file_size,
                 &mime_type,
+                original_file_size,
             )
             .await?;
 
+        // Strip metadata and render thumbnails in the background
+        self.document_service.spawn_processing(document.id);
+
         Ok(document)
     }
 
-    pub async fn list_documents(&self, registration_id: i32) -> AppResult<Vec<Document>> {
+    /// Give a draft registration more time after the registration window closed
+    pub async fn extend_deadline(
+        &self,
+        id: i32,
+        until: DateTime<Utc>,
+        reason: String,
+        school_id: Option<i32>,
+        admin_id: i32,
+    ) -> AppResult<Registration> {
+        let registration = self.get_registration(id).await?;
+
+        if let Some(school_id) = school_id {
+            if registration.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have access to this registration".to_string(),
+                ));
+            }
+        }
+
+        if registration.status != "draft" {
+            return Err(AppError::Validation(
+                "Can only extend the deadline of registrations in draft status".to_string(),
+            ));
+        }
+
+        if until <= Utc::now() {
+            return Err(AppError::Validation(
+                "Extended deadline must be in the future".to_string(),
+            ));
+        }
+
+        if reason.trim().is_empty() {
+            return Err(AppError::Validation(
+                "Reason for the extension is required".to_string(),
+            ));
+        }
+
+        self.registration_repo
+            .set_deadline_extension(id, until, reason.trim(), admin_id)
+            .await
+    }
+
+    async fn ensure_window_open(&self, registration: &Registration) -> AppResult<()> {
+        let period = self
+            .period_repo
+            .find_by_id(registration.period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+
+        let path = self
+            .period_repo
+            .find_path_by_id(registration.path_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+        let stage = self.find_path_stage(&path).await?;
+
+        let timezone = self.period_repo.find_school_timezone(period.school_id).await?;
+        check_registration_window(
+            &period,
+            stage.as_ref(),
+            registration.deadline_extended_until,
+            Utc::now(),
+            timezone,
+        )
+    }
+
+    async fn open_revision(&self, registration_id: i32) -> AppResult<RegistrationRevision> {
+        self.registration_repo
+            .find_open_revision(registration_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Revision request not found".to_string()))
+    }
+
+    /// Revision requests of a registration, latest first
+    pub async fn list_revisions(&self, registration_id: i32) -> AppResult<Vec<RegistrationRevision>> {
+        let _ = self.get_registration(registration_id).await?;
+
+        self.registration_repo
+            .find_revisions_by_registration(registration_id)
+            .await
+    }
+
+    /// Send a revised registration back to the verification queue together
+    /// with what changed. Flagged documents must have been replaced.
+    async fn resubmit_revision(&self, registration: Registration) -> AppResult<Registration> {
+        let revision = self.open_revision(registration.id).await?;
+        let documents = self
+            .registration_repo
+            .find_documents_by_registration(registration.id)
+            .await?;
+
+        let changes = revision_changes(&revision, &registration, &documents);
+
+        let errors: Vec<FieldError> = changes
+            .documents
+            .iter()
+            .filter(|d| d.replaced_by.is_empty())
+            .map(|d| {
+                FieldError::new(
+                    &format!("documents.{}", d.document_id),
+                    format!("Upload a new {} to replace the flagged document", d.document_type),
+                )
+            })
+            .collect();
+        if !errors.is_empty() {
+            return Err(AppError::FieldValidation(errors));
+        }
+
+        let changes =
+            serde_json::to_value(&changes).map_err(|e| AppError::Internal(e.to_string()))?;
+        let resubmitted = self
+            .registration_repo
+            .complete_revision(revision.id, registration.id, &changes)
+            .await?;
+
+        tracing::info!(
+            "Registration {} resubmitted after revision {}",
+            registration.id,
+            revision.id
+        );
+
+        Ok(resubmitted)
+    }
+
+    /// Withdraw a registration on the parent's request. An accepted seat is
+    /// freed and, under the period's waitlist policy, given to the next
+    /// applicant; payments follow the refund policy. Withdrawn registrations
+    /// are left out of announcements and reminders.
+    pub async fn withdraw_registration(
+        &self,
+        id: i32,
+        reason: String,
+        withdrawn_by: i32,
+    ) -> AppResult<WithdrawalResult> {
+        let reason = reason.trim().to_string();
+        if reason.is_empty() {
+            return Err(AppError::Validation(
+                "Withdrawal reason is required".to_string(),
+            ));
+        }
+
+        let registration = self.get_registration(id).await?;
+        let status = RegistrationStatus::from_str(&registration.status)
+            .ok_or_else(|| AppError::Internal(format!("Unknown status {}", registration.status)))?;
+        if status.is_final() {
+            return Err(AppError::Validation(format!(
+                "Cannot withdraw a registration in {} status",
+                registration.status
+            )));
+        }
+
+        let plan = plan_withdrawal(&self.period_repo, &registration).await?;
+
+        // The freed seat goes to the next applicant on the waitlist in the
+        // same transaction
+        let result = self
+            .registration_repo
+            .withdraw_registration(&registration, &plan, &reason, withdrawn_by)
+            .await?
+            .ok_or_else(|| {
+                AppError::Conflict("Registration status changed, please try again".to_string())
+            })?;
+
+        tracing::info!(
+            "Registration {} withdrawn from {} by user {}: {}",
+            id,
+            registration.status,
+            withdrawn_by,
+            reason
+        );
+        follow_up_withdrawal(&result);
+
+        Ok(result)
+    }
+
+    /// Status changes of a registration, oldest first
+    pub async fn list_status_history(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<RegistrationStatusChange>> {
+        let _ = self.get_registration(registration_id).await?;
+
+        self.registration_repo.find_status_history(registration_id).await
+    }
+
+    async fn find_path_stage(&self, path: &RegistrationPath) -> AppResult<Option<PeriodStage>> {
+        match path.stage_id {
+            Some(stage_id) => self.period_repo.find_stage_by_id(stage_id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn list_documents(&self, registration_id: i32) -> AppResult<Vec<DocumentDetail>> {
         // Check if registration exists
         let _ = self.get_registration(registration_id).await?;
 
@@ -332,7 +934,26 @@ impl RegistrationService {
             .find_documents_by_registration(registration_id)
             .await?;
 
-        Ok(documents)
+        let ids: Vec<i32> = documents.iter().map(|d| d.id).collect();
+        let artifacts = self.document_service.find_artifacts(&ids).await?;
+        let duplicates = self.document_service.find_duplicates(&ids).await?;
+
+        Ok(documents
+            .into_iter()
+            .map(|document| DocumentDetail {
+                artifacts: artifacts
+                    .iter()
+                    .filter(|a| a.document_id == document.id)
+                    .cloned()
+                    .collect(),
+                duplicates: duplicates
+                    .iter()
+                    .filter(|d| d.document_id == document.id)
+                    .cloned()
+                    .collect(),
+                document,
+            })
+            .collect())
     }
 
     pub async fn delete_document(&self, id: i32, user_id: i32) -> AppResult<()> {
@@ -351,8 +972,19 @@ impl RegistrationService {
             ));
         }
 
-        // Only allow deletion if registration is in draft status
-        if registration.status != "draft" {
+        // Only allow deletion if registration is in draft status, or of
+        // documents flagged for revision
+        let flagged = if registration.status == "revision_requested" {
+            self.open_revision(registration.id)
+                .await?
+                .flagged_documents()
+                .iter()
+                .any(|d| d.document_id == id)
+        } else {
+            false
+        };
+
+        if registration.status != "draft" && !flagged {
             return Err(AppError::Validation(
                 "Can only delete documents for registrations in draft status".to_string(),
             ));
@@ -366,3 +998,451 @@ impl RegistrationService {
         Ok(())
     }
 }
+
+/// Registration fields a verifier can flag for revision
+pub const REVISABLE_FIELDS: &[&str] = &[
+    "student_name",
+    "student_gender",
+    "student_birth_place",
+    "student_birth_date",
+    "student_religion",
+    "student_address",
+    "student_phone",
+    "student_email",
+    "student_nik",
+    "student_region_code",
+    "parent_name",
+    "parent_nik",
+    "parent_phone",
+    "parent_occupation",
+    "parent_income",
+    "path_data",
+];
+
+/// Current value of a revisable field, None for unknown fields
+pub fn revisable_field_value(registration: &Registration, field: &str) -> Option<serde_json::Value> {
+    use serde_json::json;
+
+    let value = match field {
+        "student_name" => json!(registration.student_name),
+        "student_gender" => json!(registration.student_gender),
+        "student_birth_place" => json!(registration.student_birth_place),
+        "student_birth_date" => json!(registration.student_birth_date.date_naive()),
+        "student_religion" => json!(registration.student_religion),
+        "student_address" => json!(registration.student_address),
+        "student_phone" => json!(registration.student_phone),
+        "student_email" => json!(registration.student_email),
+        "student_nik" => json!(registration.student_nik),
+        "student_region_code" => json!(registration.student_region_code),
+        "parent_name" => json!(registration.parent_name),
+        "parent_nik" => json!(registration.parent_nik),
+        "parent_phone" => json!(registration.parent_phone),
+        "parent_occupation" => json!(registration.parent_occupation),
+        "parent_income" => json!(registration.parent_income),
+        "path_data" => registration.path_data.clone(),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+/// Fields in `provided` that the revision request did not flag
+pub fn unflagged_fields<'a>(provided: &[&'a str], flagged: &[RevisionField]) -> Vec<&'a str> {
+    provided
+        .iter()
+        .copied()
+        .filter(|field| !flagged.iter().any(|f| f.field == *field))
+        .collect()
+}
+
+/// Flagged fields that differ from the snapshot taken at the request, and the
+/// documents uploaded since then to replace each flagged document
+pub fn revision_changes(
+    revision: &RegistrationRevision,
+    registration: &Registration,
+    documents: &[Document],
+) -> RevisionChanges {
+    let fields = revision
+        .flagged_fields()
+        .into_iter()
+        .filter_map(|flagged| {
+            let before = revision.snapshot["fields"][flagged.field.as_str()].clone();
+            let after = revisable_field_value(registration, &flagged.field)?;
+            (before != after).then_some(FieldChange {
+                field: flagged.field,
+                before,
+                after,
+            })
+        })
+        .collect();
+
+    let documents = revision
+        .flagged_documents()
+        .into_iter()
+        .map(|flagged| {
+            let document_type = revision
+                .flagged_document_type(flagged.document_id)
+                .unwrap_or_default();
+            let replaced_by = documents
+                .iter()
+                .filter(|d| {
+                    d.id != flagged.document_id
+                        && d.document_type == document_type
+                        && d.created_at > revision.created_at
+                })
+                .map(|d| d.id)
+                .collect();
+
+            DocumentReplacement {
+                document_id: flagged.document_id,
+                document_type,
+                replaced_by,
+            }
+        })
+        .collect();
+
+    RevisionChanges { fields, documents }
+}
+
+/// Refund and waitlist promotion due when the registration is withdrawn,
+/// following its period's policies. Results are announced per stage when the
+/// path belongs to one.
+pub async fn plan_withdrawal(
+    period_repo: &PeriodRepository,
+    registration: &Registration,
+) -> AppResult<WithdrawalPlan> {
+    let period = period_repo
+        .find_by_id(registration.period_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+
+    let path = period_repo
+        .find_path_by_id(registration.path_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+    let stage = match path.stage_id {
+        Some(stage_id) => period_repo.find_stage_by_id(stage_id).await?,
+        None => None,
+    };
+    let announced = match stage {
+        Some(stage) => stage.announcement_date.is_some(),
+        None => period.announcement_date.is_some(),
+    };
+    let refund_policy = RefundPolicy::from_str(&period.refund_policy).unwrap_or(RefundPolicy::None);
+
+    let promotes = registration.status == RegistrationStatus::Accepted.as_str()
+        && WaitlistPolicy::from_str(&period.waitlist_policy) == Some(WaitlistPolicy::Promote);
+
+    Ok(WithdrawalPlan {
+        refund: refund_due(&refund_policy, announced),
+        promote_path_id: registration.accepted_path_id.filter(|_| promotes),
+    })
+}
+
+/// Refunds and the waitlist promotion that follow a stored withdrawal
+pub fn follow_up_withdrawal(result: &WithdrawalResult) {
+    for payment_id in &result.refunded_payment_ids {
+        // TODO: Issue the refund through the payment gateway
+        tracing::info!(
+            "Payment {} of registration {} marked for refund",
+            payment_id,
+            result.registration.id
+        );
+    }
+    if let Some(ref promoted) = result.promoted {
+        // TODO: Send acceptance email
+        tracing::info!(
+            "Registration {} promoted from the waitlist into the seat of registration {}",
+            promoted.id,
+            result.registration.id
+        );
+    }
+}
+
+/// Whether payments are refunded on withdrawal, given whether the results of
+/// the registration's period or stage are already announced
+pub fn refund_due(policy: &RefundPolicy, announced: bool) -> bool {
+    match policy {
+        RefundPolicy::None => false,
+        RefundPolicy::Full => true,
+        RefundPolicy::BeforeAnnouncement => !announced,
+    }
+}
+
+/// File name of a photo after it was recompressed as JPEG
+pub fn jpeg_file_name(file_name: &str) -> String {
+    let stem = match file_name.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => file_name,
+    };
+
+    format!("{}.jpg", stem)
+}
+
+/// Registrations can only be changed while the period is active and within the
+/// registration window of the period or of the path's stage, or until a
+/// deadline extension granted by an admin.
+pub fn check_registration_window(
+    period: &Period,
+    stage: Option<&PeriodStage>,
+    extended_until: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    timezone: Tz,
+) -> AppResult<()> {
+    if period.status != "active" {
+        return Err(AppError::Validation(
+            "Period is not active. Registration is closed.".to_string(),
+        ));
+    }
+
+    let (opens_at, closes_at) = match stage {
+        Some(stage) => (stage.registration_opens_at, stage.registration_closes_at),
+        None => (period.registration_opens_at, period.registration_closes_at),
+    };
+
+    if now < opens_at {
+        return Err(AppError::Validation(format!(
+            "Registration opens at {}",
+            format_local(opens_at, timezone)
+        )));
+    }
+
+    if now > closes_at && extended_until.is_none_or(|until| now > until) {
+        return Err(AppError::Validation(format!(
+            "Registration closed at {}",
+            format_local(closes_at, timezone)
+        )));
+    }
+
+    Ok(())
+}
+
+/// Fallback path_data is merged over the primary path_data, both when it is
+/// validated and when it is scored.
+pub fn merge_path_data(base: &serde_json::Value, extra: &serde_json::Value) -> serde_json::Value {
+    let mut merged = base.clone();
+    if let (Some(merged), Some(extra)) = (merged.as_object_mut(), extra.as_object()) {
+        for (key, value) in extra {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+
+    merged
+}
+
+/// Validate path_data against the primary path schema and, merged with their
+/// own data, against the schema of each fallback path.
+pub fn path_data_errors(
+    primary_path: &RegistrationPath,
+    path_data: &serde_json::Value,
+    fallbacks: &[(RegistrationPath, serde_json::Value)],
+) -> Vec<FieldError> {
+    let mut errors = json_schema::validate(&path_data_schema(primary_path), path_data, "path_data");
+
+    for (index, (path, fallback_data)) in fallbacks.iter().enumerate() {
+        let merged = merge_path_data(path_data, fallback_data);
+        errors.extend(json_schema::validate(
+            &path_data_schema(path),
+            &merged,
+            &format!("fallback_paths[{}].path_data", index),
+        ));
+    }
+
+    errors
+}
+
+/// Age rules of the period; the stored rules were validated when the period was saved
+pub fn age_rules(period: &Period) -> AppResult<AgeRules> {
+    AgeRules::for_period(period).map_err(|e| {
+        AppError::Internal(format!("Invalid age rules of period {}: {}", period.id, e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn period(status: &str) -> Period {
+        Period {
+            status: status.to_string(),
+            registration_opens_at: "2024-05-31T17:00:00Z".parse().unwrap(),
+            registration_closes_at: "2024-06-30T16:59:59Z".parse().unwrap(),
+            ..Default::default()
+        }
+    }
+
+    fn at(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn wib() -> Tz {
+        crate::utils::timezone::parse_timezone("Asia/Jakarta").unwrap()
+    }
+
+    #[test]
+    fn test_registration_window() {
+        let active = period("active");
+        let check = |now: &str| check_registration_window(&active, None, None, at(now), wib());
+
+        assert!(check("2024-05-31T17:00:00Z").is_ok());
+        assert!(check("2024-06-30T16:59:59Z").is_ok());
+        assert!(check("2024-05-31T16:59:59Z").is_err());
+
+        match check("2024-06-30T17:00:00Z") {
+            Err(AppError::Validation(message)) => {
+                assert_eq!(message, "Registration closed at 30-06-2024 23:59 WIB")
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let draft = period("draft");
+        assert!(check_registration_window(&draft, None, None, at("2024-06-10T00:00:00Z"), wib()).is_err());
+    }
+
+    #[test]
+    fn test_stage_window_replaces_period_window() {
+        let active = period("active");
+        let tahap_2 = PeriodStage {
+            id: 2,
+            period_id: 1,
+            stage_number: 2,
+            name: "Tahap 2".to_string(),
+            registration_start: NaiveDate::from_ymd_opt(2024, 6, 20).unwrap(),
+            registration_end: NaiveDate::from_ymd_opt(2024, 6, 25).unwrap(),
+            registration_opens_at: "2024-06-19T17:00:00Z".parse().unwrap(),
+            registration_closes_at: "2024-06-25T16:59:59Z".parse().unwrap(),
+            announcement_at: None,
+            announcement_date: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let check = |now: &str| {
+            check_registration_window(&active, Some(&tahap_2), None, at(now), wib())
+        };
+
+        assert!(check("2024-06-10T00:00:00Z").is_err());
+        assert!(check("2024-06-20T00:00:00Z").is_ok());
+        assert!(check("2024-06-26T00:00:00Z").is_err());
+    }
+
+    #[test]
+    fn test_deadline_extension_reopens_window() {
+        let active = period("active");
+        let until = Some(at("2024-07-03T16:59:59Z"));
+
+        assert!(check_registration_window(&active, None, until, at("2024-07-02T00:00:00Z"), wib()).is_ok());
+        assert!(check_registration_window(&active, None, until, at("2024-07-03T17:00:00Z"), wib()).is_err());
+
+        let closed = period("closed");
+        assert!(check_registration_window(&closed, None, until, at("2024-07-02T00:00:00Z"), wib()).is_err());
+    }
+
+    #[test]
+    fn test_revision_changes() {
+        let requested_at: DateTime<Utc> = "2024-06-10T03:00:00Z".parse().unwrap();
+        let revision = RegistrationRevision {
+            id: 1,
+            registration_id: 5,
+            requested_by: 2,
+            notes: None,
+            fields: serde_json::json!([
+                {"field": "parent_nik", "note": "NIK tidak sesuai KK"},
+                {"field": "student_address", "note": "Lengkapi RT/RW"}
+            ]),
+            documents: serde_json::json!([{"document_id": 10, "note": "Foto KK buram"}]),
+            snapshot: serde_json::json!({
+                "fields": {"parent_nik": "3273010101800001", "student_address": "Jl. Merdeka 1"},
+                "documents": {"10": {"document_type": "kartu_keluarga", "file_name": "kk.jpg"}}
+            }),
+            changes: None,
+            resubmitted_at: None,
+            created_at: requested_at,
+        };
+
+        let registration = Registration {
+            parent_nik: "3273010101800002".to_string(),
+            student_address: "Jl. Merdeka 1".to_string(),
+            ..Default::default()
+        };
+
+        let document = |id: i32, document_type: &str, created_at: &str| Document {
+            id,
+            registration_id: 5,
+            document_type: document_type.to_string(),
+            file_url: format!("/uploads/{}.jpg", id),
+            file_name: format!("{}.jpg", id),
+            file_size: 1024,
+            mime_type: "image/jpeg".to_string(),
+            verification_status: "pending".to_string(),
+            verification_notes: None,
+            verified_by: None,
+            verified_at: None,
+            processing_status: "processed".to_string(),
+            processing_error: None,
+            processed_at: None,
+            original_file_size: None,
+            page_count: None,
+            processing_metadata: None,
+            scan_status: "clean".to_string(),
+            scan_signature: None,
+            scan_error: None,
+            scanned_at: None,
+            content_hash: None,
+            perceptual_hash: None,
+            processing_attempts: 0,
+            next_attempt_at: None,
+            created_at: created_at.parse().unwrap(),
+            updated_at: created_at.parse().unwrap(),
+        };
+        let documents = vec![
+            document(10, "kartu_keluarga", "2024-06-01T03:00:00Z"),
+            document(11, "akta_kelahiran", "2024-06-11T03:00:00Z"),
+        ];
+
+        // Only the changed field is reported, the blurry KK was not replaced yet
+        let changes = revision_changes(&revision, &registration, &documents);
+        assert_eq!(
+            changes.fields,
+            vec![FieldChange {
+                field: "parent_nik".to_string(),
+                before: serde_json::json!("3273010101800001"),
+                after: serde_json::json!("3273010101800002"),
+            }]
+        );
+        assert!(changes.documents[0].replaced_by.is_empty());
+
+        let mut documents = documents;
+        documents.push(document(12, "kartu_keluarga", "2024-06-11T04:00:00Z"));
+        let changes = revision_changes(&revision, &registration, &documents);
+        assert_eq!(changes.documents[0].replaced_by, vec![12]);
+
+        assert_eq!(
+            unflagged_fields(&["parent_nik", "student_name"], &revision.flagged_fields()),
+            vec!["student_name"]
+        );
+    }
+
+    #[test]
+    fn test_withdrawal_rules() {
+        // Withdrawal is allowed from any non-final status
+        for status in ["draft", "submitted", "revision_requested", "verified", "accepted"] {
+            assert!(!RegistrationStatus::from_str(status).unwrap().is_final());
+        }
+        for status in ["rejected", "enrolled", "expired", "withdrawn"] {
+            assert!(RegistrationStatus::from_str(status).unwrap().is_final());
+        }
+
+        assert!(!refund_due(&RefundPolicy::None, false));
+        assert!(refund_due(&RefundPolicy::Full, true));
+        assert!(refund_due(&RefundPolicy::BeforeAnnouncement, false));
+        assert!(!refund_due(&RefundPolicy::BeforeAnnouncement, true));
+    }
+
+    #[test]
+    fn test_jpeg_file_name() {
+        assert_eq!(jpeg_file_name("kartu_keluarga.png"), "kartu_keluarga.jpg");
+        assert_eq!(jpeg_file_name("akta.kelahiran.jpeg"), "akta.kelahiran.jpg");
+        assert_eq!(jpeg_file_name("rapor"), "rapor.jpg");
+        assert_eq!(jpeg_file_name(".png"), ".png.jpg");
+    }
+}
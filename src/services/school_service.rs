@@ -1,6 +1,7 @@
 use crate::models::school::School;
 use crate::repositories::school_repo::SchoolRepository;
 use crate::utils::error::{AppError, AppResult};
+use crate::utils::timezone::{parse_timezone, DEFAULT_TIMEZONE};
 
 pub struct SchoolService {
     school_repo: SchoolRepository,
@@ -20,7 +21,11 @@ impl SchoolService {
         phone: Option<String>,
         email: Option<String>,
         logo_url: Option<String>,
+        region_id: Option<i32>,
+        timezone: Option<String>,
     ) -> AppResult<School> {
+        let timezone = parse_timezone(timezone.as_deref().unwrap_or(DEFAULT_TIMEZONE))?;
+
         // Check if NPSN already exists
         if let Some(_) = self.school_repo.find_by_npsn(&npsn).await? {
             return Err(AppError::Conflict("NPSN already registered".to_string()));
@@ -41,6 +46,8 @@ impl SchoolService {
                 address.as_deref().unwrap_or(""),
                 phone.as_deref(),
                 email.as_deref(),
+                region_id,
+                timezone.name(),
             )
             .await?;
 
@@ -84,10 +91,14 @@ impl SchoolService {
         phone: Option<String>,
         email: Option<String>,
         logo_url: Option<String>,
+        region_id: Option<i32>,
+        timezone: Option<String>,
     ) -> AppResult<School> {
         // Check if school exists
         let _school = self.get_school(id).await?;
 
+        let timezone = timezone.as_deref().map(parse_timezone).transpose()?;
+
         // Update school
         let updated_school = self
             .school_repo
@@ -97,6 +108,8 @@ impl SchoolService {
                 address.as_deref(),
                 phone.as_deref(),
                 email.as_deref(),
+                region_id,
+                timezone.map(|tz| tz.name()),
             )
             .await?;
 
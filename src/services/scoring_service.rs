@@ -1,5 +1,6 @@
-use serde_json::Value;
+use serde_json::{json, Value};
 
+use crate::models::period::RegistrationPath;
 use crate::models::registration::Registration;
 use crate::utils::error::{AppError, AppResult};
 
@@ -41,19 +42,17 @@ impl ScoringService {
 
     /// Calculate score for Prestasi path (based on academic achievement)
     /// Formula: (rapor_average * rapor_weight) + (achievement_points * achievement_weight)
+    /// The rapor average is the one computed from the registration grades and
+    /// the achievement points those of the approved achievements.
     pub fn calculate_prestasi_score(
         &self,
         registration: &Registration,
         scoring_config: &Value,
     ) -> AppResult<f64> {
-        // Extract rapor average from path_data
-        let rapor_average = registration
-            .path_data
-            .get("rapor_average")
-            .and_then(|v| v.as_f64())
-            .ok_or_else(|| {
-                AppError::Validation("Rapor average is required for Prestasi path".to_string())
-            })?;
+        // Rapor average computed by the server from the entered grades
+        let rapor_average = registration.rapor_average.ok_or_else(|| {
+            AppError::Validation("Rapor grades are required for Prestasi path".to_string())
+        })?;
 
         // Validate rapor average (0-100)
         if rapor_average < 0.0 || rapor_average > 100.0 {
@@ -62,12 +61,8 @@ impl ScoringService {
             ));
         }
 
-        // Extract achievement points (optional)
-        let achievement_points = registration
-            .path_data
-            .get("achievement_points")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
+        // Points of the verified achievements (optional)
+        let achievement_points = registration.achievement_points.unwrap_or(0.0);
 
         // Get weights from scoring_config
         let rapor_weight = scoring_config
@@ -88,6 +83,8 @@ impl ScoringService {
 
     /// Calculate score for Afirmasi path (based on criteria)
     /// Formula: base_score + bonus points for meeting criteria
+    /// KIP and poor family bonuses require a verified match in an imported
+    /// social-assistance list; self-declared flags in path_data are ignored.
     pub fn calculate_afirmasi_score(
         &self,
         registration: &Registration,
@@ -95,26 +92,24 @@ impl ScoringService {
     ) -> AppResult<f64> {
         let mut score = 50.0; // Base score
 
-        // Check if has KIP (Kartu Indonesia Pintar)
-        if let Some(has_kip) = registration.path_data.get("has_kip").and_then(|v| v.as_bool()) {
-            if has_kip {
-                let kip_bonus = scoring_config
-                    .get("kip_bonus")
-                    .and_then(|v| v.as_f64())
-                    .unwrap_or(20.0);
-                score += kip_bonus;
-            }
+        let programs = &registration.verified_assistance_programs;
+
+        // Check if has a verified KIP (Kartu Indonesia Pintar) match
+        if programs.iter().any(|p| p == "kip") {
+            let kip_bonus = scoring_config
+                .get("kip_bonus")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(20.0);
+            score += kip_bonus;
         }
 
-        // Check if from poor family
-        if let Some(is_poor) = registration.path_data.get("is_poor_family").and_then(|v| v.as_bool()) {
-            if is_poor {
-                let poor_bonus = scoring_config
-                    .get("poor_family_bonus")
-                    .and_then(|v| v.as_f64())
-                    .unwrap_or(15.0);
-                score += poor_bonus;
-            }
+        // Check if from poor family (verified DTKS or PKH match)
+        if programs.iter().any(|p| p == "dtks" || p == "pkh") {
+            let poor_bonus = scoring_config
+                .get("poor_family_bonus")
+                .and_then(|v| v.as_f64())
+                .unwrap_or(15.0);
+            score += poor_bonus;
         }
 
         // Check if has disability
@@ -128,8 +123,8 @@ impl ScoringService {
             }
         }
 
-        // Add rapor score if available
-        if let Some(rapor_average) = registration.path_data.get("rapor_average").and_then(|v| v.as_f64()) {
+        // Add rapor score if grades were entered
+        if let Some(rapor_average) = registration.rapor_average {
             let rapor_weight = scoring_config
                 .get("rapor_weight")
                 .and_then(|v| v.as_f64())
@@ -189,7 +184,7 @@ impl ScoringService {
         path_type: &str,
         scoring_config: &Value,
     ) -> AppResult<f64> {
-        match path_type {
+        let score = match path_type {
             "zonasi" => self.calculate_zonasi_score(registration, scoring_config),
             "prestasi" => self.calculate_prestasi_score(registration, scoring_config),
             "afirmasi" => self.calculate_afirmasi_score(registration, scoring_config),
@@ -198,7 +193,74 @@ impl ScoringService {
                 "Unknown path type: {}",
                 path_type
             ))),
+        }?;
+
+        self.apply_test_score(score, registration, scoring_config)
+    }
+
+    /// Blend the entrance test score into the path score
+    /// Formula: path_score * (1 - test_weight) + test_score * test_weight
+    /// Applicants without a recorded test score get 0 for the test component.
+    pub fn apply_test_score(
+        &self,
+        score: f64,
+        registration: &Registration,
+        scoring_config: &Value,
+    ) -> AppResult<f64> {
+        let test_weight = scoring_config
+            .get("test_weight")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+
+        if test_weight == 0.0 {
+            return Ok(score);
         }
+
+        if !(0.0..=1.0).contains(&test_weight) {
+            return Err(AppError::Validation(
+                "test_weight must be between 0 and 1".to_string(),
+            ));
+        }
+
+        let test_score = registration.test_score.unwrap_or(0.0);
+
+        Ok(score * (1.0 - test_weight) + test_score * test_weight)
+    }
+}
+
+/// Schema applicants' path_data is validated against: the one configured on
+/// the path, or the keys read by the scoring of its path type.
+pub fn path_data_schema(path: &RegistrationPath) -> Value {
+    path.path_data_schema
+        .clone()
+        .unwrap_or_else(|| default_path_data_schema(&path.path_type))
+}
+
+pub fn default_path_data_schema(path_type: &str) -> Value {
+    match path_type {
+        "zonasi" => json!({
+            "type": "object",
+            "required": ["distance_km"],
+            "properties": {
+                "distance_km": {"type": "number", "minimum": 0, "title": "Jarak rumah ke sekolah (km)"}
+            }
+        }),
+        "prestasi" => json!({"type": "object", "properties": {}}),
+        "afirmasi" => json!({
+            "type": "object",
+            "properties": {
+                "kip_number": {"type": "string", "maxLength": 30, "title": "Nomor KIP"},
+                "has_disability": {"type": "boolean", "title": "Penyandang disabilitas"}
+            }
+        }),
+        "perpindahan_tugas" => json!({
+            "type": "object",
+            "properties": {
+                "has_transfer_letter": {"type": "boolean", "title": "Memiliki surat pindah"},
+                "has_parent_assignment": {"type": "boolean", "title": "Memiliki surat tugas orang tua"}
+            }
+        }),
+        _ => json!({"type": "object", "properties": {}}),
     }
 }
 
@@ -227,6 +289,8 @@ mod tests {
             student_address: "Test Address".to_string(),
             student_phone: None,
             student_email: None,
+            student_nik: None,
+            student_region_code: None,
             parent_name: "Test Parent".to_string(),
             parent_nik: "1234567890123456".to_string(),
             parent_phone: "081234567890".to_string(),
@@ -238,8 +302,21 @@ mod tests {
             path_data: json!({"distance_km": 2.5}),
             selection_score: None,
             ranking: None,
+            accepted_path_id: None,
+            accepted_major_id: None,
+            rapor_average: None,
+            achievement_points: None,
+            test_score: None,
+            verified_assistance_programs: vec![],
             status: "verified".to_string(),
             rejection_reason: None,
+            waitlisted: false,
+            deadline_extended_until: None,
+            deadline_extended_by: None,
+            deadline_extension_reason: None,
+            submitted_at: None,
+            assigned_verifier_id: None,
+            assigned_at: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
         };
@@ -255,10 +332,8 @@ mod tests {
         let service = ScoringService::new();
         
         let registration = Registration {
-            path_data: json!({
-                "rapor_average": 85.0,
-                "achievement_points": 10.0
-            }),
+            rapor_average: Some(85.0),
+            achievement_points: Some(10.0),
             // ... other fields
             ..Default::default()
         };
@@ -272,4 +347,75 @@ mod tests {
         // (85 * 0.7) + (10 * 0.3) = 59.5 + 3.0 = 62.5
         assert_eq!(score, 62.5);
     }
+
+    #[test]
+    fn test_afirmasi_bonuses_require_verified_matches() {
+        let service = ScoringService::new();
+
+        let self_declared = Registration {
+            path_data: json!({"has_kip": true, "is_poor_family": true}),
+            ..Default::default()
+        };
+        let verified = Registration {
+            verified_assistance_programs: vec!["kip".to_string(), "pkh".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            service.calculate_afirmasi_score(&self_declared, &json!({})).unwrap(),
+            50.0
+        );
+        // 50 + 20 (KIP) + 15 (PKH)
+        assert_eq!(
+            service.calculate_afirmasi_score(&verified, &json!({})).unwrap(),
+            85.0
+        );
+    }
+
+    #[test]
+    fn test_prestasi_score_ignores_self_reported_rapor_average() {
+        let service = ScoringService::new();
+
+        let registration = Registration {
+            path_data: json!({"rapor_average": 95.0}),
+            ..Default::default()
+        };
+
+        assert!(service
+            .calculate_prestasi_score(&registration, &json!({}))
+            .is_err());
+    }
+
+    #[test]
+    fn test_test_score_is_weighted_into_path_score() {
+        let service = ScoringService::new();
+
+        let registration = Registration {
+            path_data: json!({"distance_km": 5.0}),
+            test_score: Some(60.0),
+            ..Default::default()
+        };
+        let untested = Registration {
+            path_data: json!({"distance_km": 5.0}),
+            ..Default::default()
+        };
+        let config = json!({"distance_weight": 2.0, "test_weight": 0.25});
+
+        // 90 * 0.75 + 60 * 0.25
+        assert_eq!(
+            service.calculate_score(&registration, "zonasi", &config).unwrap(),
+            82.5
+        );
+        assert_eq!(
+            service.calculate_score(&untested, "zonasi", &config).unwrap(),
+            67.5
+        );
+        // Without a test weight the path score is unchanged
+        assert_eq!(
+            service
+                .calculate_score(&registration, "zonasi", &json!({"distance_weight": 2.0}))
+                .unwrap(),
+            90.0
+        );
+    }
 }
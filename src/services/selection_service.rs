@@ -1,6 +1,10 @@
+use std::collections::HashMap;
+
+use crate::models::age_rule::AgeRules;
 use crate::models::registration::Registration;
 use crate::repositories::period_repo::PeriodRepository;
 use crate::repositories::registration_repo::RegistrationRepository;
+use crate::services::registration_service::{age_rules, merge_path_data};
 use crate::services::scoring_service::ScoringService;
 use crate::utils::error::{AppError, AppResult};
 
@@ -22,7 +26,8 @@ impl SelectionService {
         }
     }
 
-    /// Calculate scores for all verified registrations in a period
+    /// Calculate scores for all verified registrations in a period.
+    /// The age component of the period's age rules is added to the path score.
     pub async fn calculate_scores_for_period(&self, period_id: i32) -> AppResult<usize> {
         // Check if period exists
         let period = self
@@ -31,6 +36,8 @@ impl SelectionService {
             .await?
             .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
 
+        let age_rules = age_rules(&period)?;
+
         // Get all paths for this period
         let paths = self.period_repo.find_paths_by_period(period_id).await?;
 
@@ -55,7 +62,8 @@ impl SelectionService {
             for registration in registrations {
                 let score = self
                     .scoring_service
-                    .calculate_score(&registration, &path.path_type, &path.scoring_config)?;
+                    .calculate_score(&registration, &path.path_type, &path.scoring_config)?
+                    + age_score(&age_rules, &registration);
 
                 // Update registration with calculated score
                 sqlx::query(
@@ -74,15 +82,165 @@ impl SelectionService {
             }
         }
 
+        // Calculate fallback path scores so fallen-through applicants can be re-ranked
+        let fallback_calculated = self
+            .calculate_fallback_scores(period_id, period.school_id, &age_rules)
+            .await?;
+
         tracing::info!(
-            "Calculated scores for {} registrations in period {}",
+            "Calculated scores for {} registrations ({} fallback paths) in period {}",
             total_calculated,
+            fallback_calculated,
             period_id
         );
 
         Ok(total_calculated)
     }
 
+    /// Calculate scores of verified registrations in each of their fallback paths.
+    /// The fallback path_data is merged over the primary path_data before scoring.
+    /// A fallback the applicant does not qualify for is left without a score
+    /// and will be skipped during selection.
+    async fn calculate_fallback_scores(
+        &self,
+        period_id: i32,
+        school_id: i32,
+        age_rules: &AgeRules,
+    ) -> AppResult<usize> {
+        let fallback_paths = self
+            .registration_repo
+            .find_fallback_paths_by_period(period_id)
+            .await?;
+
+        if fallback_paths.is_empty() {
+            return Ok(0);
+        }
+
+        let paths: HashMap<i32, _> = self
+            .period_repo
+            .find_paths_by_period(period_id)
+            .await?
+            .into_iter()
+            .map(|path| (path.id, path))
+            .collect();
+
+        let registrations: HashMap<i32, Registration> = self
+            .registration_repo
+            .find_by_school(
+                school_id,
+                10000, // Large limit to get all
+                0,
+                Some("verified".to_string()),
+                Some(period_id),
+                None,
+            )
+            .await?
+            .into_iter()
+            .map(|registration| (registration.id, registration))
+            .collect();
+
+        let mut total_calculated = 0;
+
+        for fallback in fallback_paths {
+            let (Some(registration), Some(path)) = (
+                registrations.get(&fallback.registration_id),
+                paths.get(&fallback.path_id),
+            ) else {
+                continue;
+            };
+
+            let mut candidate = registration.clone();
+            candidate.path_data = merge_path_data(&registration.path_data, &fallback.path_data);
+
+            let score = self
+                .scoring_service
+                .calculate_score(&candidate, &path.path_type, &path.scoring_config)
+                .ok()
+                .map(|score| score + age_score(age_rules, registration));
+
+            self.registration_repo
+                .update_fallback_score(fallback.id, score)
+                .await?;
+
+            if score.is_some() {
+                total_calculated += 1;
+            }
+        }
+
+        Ok(total_calculated)
+    }
+
+    /// Recalculate the selection score of one registration in its primary
+    /// path, e.g. after an appeal. Seats are not reassigned; the position the
+    /// new score would take among the path's scored applicants is reported.
+    pub async fn rescore_registration(&self, registration_id: i32) -> AppResult<Rescore> {
+        let registration = self
+            .registration_repo
+            .find_by_id(registration_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))?;
+
+        let period = self
+            .period_repo
+            .find_by_id(registration.period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+
+        let path = self
+            .period_repo
+            .find_path_by_id(registration.path_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+
+        let score = self
+            .scoring_service
+            .calculate_score(&registration, &path.path_type, &path.scoring_config)?
+            + age_score(&age_rules(&period)?, &registration);
+
+        sqlx::query(
+            r#"
+            UPDATE registrations 
+            SET selection_score = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(registration.id)
+        .bind(score)
+        .execute(&self.registration_repo.pool)
+        .await?;
+
+        let higher: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM registrations 
+            WHERE period_id = $1 
+              AND path_id = $2 
+              AND id <> $3
+              AND status IN ('verified', 'accepted', 'rejected', 'enrolled')
+              AND selection_score > $4
+            "#,
+        )
+        .bind(registration.period_id)
+        .bind(registration.path_id)
+        .bind(registration.id)
+        .bind(score)
+        .fetch_one(&self.registration_repo.pool)
+        .await?;
+
+        tracing::info!(
+            "Registration {} rescored: {:?} -> {}",
+            registration.id,
+            registration.selection_score,
+            score
+        );
+
+        Ok(Rescore {
+            score_before: registration.selection_score,
+            score_after: score,
+            position: higher + 1,
+            quota: path.quota,
+        })
+    }
+
     /// Update rankings for all registrations in a period
     /// Rankings are calculated per path, ordered by selection_score DESC
     pub async fn update_rankings(&self, period_id: i32) -> AppResult<usize> {
@@ -260,6 +418,16 @@ impl SelectionService {
     }
 }
 
+/// Outcome of recalculating one registration's score
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Rescore {
+    pub score_before: Option<f64>,
+    pub score_after: f64,
+    /// Position of the new score in the path, 1 being the highest
+    pub position: i64,
+    pub quota: i32,
+}
+
 /// Statistik ranking per jalur pendaftaran
 #[derive(Debug, serde::Serialize, utoipa::ToSchema)]
 pub struct PathRankingStats {
@@ -295,3 +463,10 @@ pub struct PathRankingStats {
     #[schema(example = 80.5)]
     pub average_score: Option<f64>,
 }
+
+/// Priority bonus and age points of the registration under the period's age rules
+fn age_score(age_rules: &AgeRules, registration: &Registration) -> f64 {
+    age_rules
+        .evaluate(registration.student_birth_date.date_naive())
+        .score
+}
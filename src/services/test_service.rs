@@ -0,0 +1,802 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::models::period::Period;
+use crate::models::registration::Registration;
+use crate::models::test_session::{
+    TestBooking, TestBookingDetail, TestResultInput, TestSession, TestSessionWithBookings,
+    TEST_TYPES,
+};
+use crate::repositories::period_repo::PeriodRepository;
+use crate::repositories::registration_repo::RegistrationRepository;
+use crate::repositories::school_repo::SchoolRepository;
+use crate::repositories::test_repo::TestRepository;
+use crate::utils::error::{AppError, AppResult, FieldError};
+use crate::utils::timezone::school_timezone;
+
+pub struct TestService {
+    test_repo: TestRepository,
+    registration_repo: RegistrationRepository,
+    period_repo: PeriodRepository,
+    school_repo: SchoolRepository,
+}
+
+impl TestService {
+    pub fn new(
+        test_repo: TestRepository,
+        registration_repo: RegistrationRepository,
+        period_repo: PeriodRepository,
+        school_repo: SchoolRepository,
+    ) -> Self {
+        Self {
+            test_repo,
+            registration_repo,
+            period_repo,
+            school_repo,
+        }
+    }
+
+    async fn get_period(&self, period_id: i32, school_id: Option<i32>) -> AppResult<Period> {
+        let period = self
+            .period_repo
+            .find_by_id(period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+
+        if let Some(school_id) = school_id {
+            if period.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have permission to manage tests of this period".to_string(),
+                ));
+            }
+        }
+
+        Ok(period)
+    }
+
+    async fn get_registration(&self, id: i32) -> AppResult<Registration> {
+        self.registration_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))
+    }
+
+    pub async fn get_session(&self, id: i32) -> AppResult<TestSessionWithBookings> {
+        self.test_repo
+            .find_session_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Test session not found".to_string()))
+    }
+
+    /// Session checked against the school of the admin
+    async fn get_managed_session(
+        &self,
+        id: i32,
+        school_id: Option<i32>,
+    ) -> AppResult<TestSessionWithBookings> {
+        let session = self.get_session(id).await?;
+        self.get_period(session.session.period_id, school_id)
+            .await?;
+        Ok(session)
+    }
+
+    pub async fn create_session(
+        &self,
+        period_id: i32,
+        path_id: Option<i32>,
+        test_type: String,
+        name: String,
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+        room: String,
+        capacity: i32,
+        school_id: Option<i32>,
+    ) -> AppResult<TestSession> {
+        let period = self.get_period(period_id, school_id).await?;
+
+        if period.status == "closed" {
+            return Err(AppError::Validation(
+                "Cannot schedule tests for closed periods".to_string(),
+            ));
+        }
+
+        if let Some(path_id) = path_id {
+            let path = self
+                .period_repo
+                .find_path_by_id(path_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+
+            if path.period_id != period_id {
+                return Err(AppError::Validation(
+                    "Path does not belong to this period".to_string(),
+                ));
+            }
+        }
+
+        let errors = validate_session(&test_type, &name, starts_at, ends_at, &room, capacity);
+        if !errors.is_empty() {
+            return Err(AppError::FieldValidation(errors));
+        }
+
+        self.test_repo
+            .create_session(
+                period_id,
+                path_id,
+                &test_type,
+                name.trim(),
+                starts_at,
+                ends_at,
+                room.trim(),
+                capacity,
+            )
+            .await
+    }
+
+    pub async fn list_sessions(
+        &self,
+        period_id: i32,
+        path_id: Option<i32>,
+        test_type: Option<String>,
+    ) -> AppResult<Vec<TestSessionWithBookings>> {
+        self.get_period(period_id, None).await?;
+
+        self.test_repo
+            .find_sessions_by_period(period_id, path_id, test_type.as_deref())
+            .await
+    }
+
+    pub async fn update_session(
+        &self,
+        id: i32,
+        name: Option<String>,
+        starts_at: Option<DateTime<Utc>>,
+        ends_at: Option<DateTime<Utc>>,
+        room: Option<String>,
+        capacity: Option<i32>,
+        school_id: Option<i32>,
+    ) -> AppResult<TestSession> {
+        let current = self.get_managed_session(id, school_id).await?;
+        let session = &current.session;
+
+        let errors = validate_session(
+            &session.test_type,
+            name.as_deref().unwrap_or(&session.name),
+            starts_at.unwrap_or(session.starts_at),
+            ends_at.unwrap_or(session.ends_at),
+            room.as_deref().unwrap_or(&session.room),
+            capacity.unwrap_or(session.capacity),
+        );
+        if !errors.is_empty() {
+            return Err(AppError::FieldValidation(errors));
+        }
+
+        if let Some(capacity) = capacity {
+            if (capacity as i64) < current.booked {
+                return Err(AppError::Validation(format!(
+                    "Capacity cannot be lower than the {} seats already booked",
+                    current.booked
+                )));
+            }
+        }
+
+        self.test_repo
+            .update_session(
+                id,
+                name.as_deref().map(str::trim),
+                starts_at,
+                ends_at,
+                room.as_deref().map(str::trim),
+                capacity,
+            )
+            .await
+    }
+
+    pub async fn delete_session(&self, id: i32, school_id: Option<i32>) -> AppResult<()> {
+        let session = self.get_managed_session(id, school_id).await?;
+
+        if session.booked > 0 {
+            return Err(AppError::Validation(
+                "Cannot delete a test session that already has bookings".to_string(),
+            ));
+        }
+
+        self.test_repo.delete_session(id).await
+    }
+
+    pub async fn get_session_bookings(
+        &self,
+        session_id: i32,
+        school_id: Option<i32>,
+    ) -> AppResult<Vec<TestBookingDetail>> {
+        self.get_managed_session(session_id, school_id).await?;
+
+        self.test_repo.find_bookings_by_session(session_id).await
+    }
+
+    pub async fn get_registration_bookings(
+        &self,
+        registration_id: i32,
+    ) -> AppResult<Vec<TestBooking>> {
+        self.test_repo
+            .find_bookings_by_registration(registration_id)
+            .await
+    }
+
+    /// Book a seat in an upcoming session. An earlier booking of the same
+    /// test type is moved, unless its result was already recorded.
+    pub async fn book_session(
+        &self,
+        registration_id: i32,
+        session_id: i32,
+    ) -> AppResult<TestBooking> {
+        let registration = self.get_registration(registration_id).await?;
+
+        if !["draft", "submitted", "verified"].contains(&registration.status.as_str()) {
+            return Err(AppError::Validation(
+                "Tests can no longer be booked for this registration".to_string(),
+            ));
+        }
+
+        let session = self.get_session(session_id).await?.session;
+
+        if session.period_id != registration.period_id
+            || session
+                .path_id
+                .map(|p| p != registration.path_id)
+                .unwrap_or(false)
+        {
+            return Err(AppError::Validation(
+                "This test session is not available for the registration's path".to_string(),
+            ));
+        }
+
+        if session.starts_at <= Utc::now() {
+            return Err(AppError::Validation(
+                "Can only book sessions that have not started yet".to_string(),
+            ));
+        }
+
+        let bookings = self
+            .test_repo
+            .find_bookings_by_registration(registration_id)
+            .await?;
+
+        if let Some(existing) = bookings.iter().find(|b| b.test_type == session.test_type) {
+            if existing.session_id == session_id {
+                return Ok(existing.clone());
+            }
+            if existing.recorded_at.is_some() {
+                return Err(AppError::Validation(
+                    "The result of this test has already been recorded".to_string(),
+                ));
+            }
+        }
+
+        self.test_repo
+            .book(session_id, registration_id)
+            .await?
+            .ok_or_else(|| AppError::Conflict("Test session is full".to_string()))
+    }
+
+    pub async fn cancel_booking(&self, registration_id: i32, session_id: i32) -> AppResult<()> {
+        let session = self.get_session(session_id).await?.session;
+
+        if session.starts_at <= Utc::now() {
+            return Err(AppError::Validation(
+                "Cannot cancel a booking of a session that has started".to_string(),
+            ));
+        }
+
+        if !self
+            .test_repo
+            .delete_booking(session_id, registration_id)
+            .await?
+        {
+            return Err(AppError::NotFound("Booking not found".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Assign applicants without a booking of the test type to the earliest
+    /// upcoming sessions with free seats
+    pub async fn auto_assign(
+        &self,
+        period_id: i32,
+        test_type: String,
+        path_id: Option<i32>,
+        school_id: Option<i32>,
+    ) -> AppResult<AutoAssignResult> {
+        self.get_period(period_id, school_id).await?;
+
+        if !TEST_TYPES.contains(&test_type.as_str()) {
+            return Err(AppError::Validation("Invalid test type".to_string()));
+        }
+
+        let now = Utc::now();
+        let sessions: Vec<SeatPool> = self
+            .test_repo
+            .find_sessions_by_period(period_id, path_id, Some(&test_type))
+            .await?
+            .into_iter()
+            .filter(|s| s.session.starts_at > now)
+            .map(|s| SeatPool {
+                session_id: s.session.id,
+                path_id: s.session.path_id,
+                remaining: (s.session.capacity as i64 - s.booked).max(0),
+            })
+            .collect();
+
+        let registrations = self
+            .test_repo
+            .find_unbooked_registrations(period_id, path_id, &test_type)
+            .await?;
+
+        let mut assigned = 0;
+        for (registration_id, session_id) in assign_seats(&sessions, &registrations) {
+            // Seats may have been taken by parents in the meantime
+            if self
+                .test_repo
+                .book(session_id, registration_id)
+                .await?
+                .is_some()
+            {
+                assigned += 1;
+            }
+        }
+
+        tracing::info!(
+            "Auto-assigned {} of {} registrations to {} sessions in period {}",
+            assigned,
+            registrations.len(),
+            test_type,
+            period_id
+        );
+
+        Ok(AutoAssignResult {
+            assigned,
+            unassigned: registrations.len() as i32 - assigned,
+        })
+    }
+
+    /// Record attendance and scores of a session in bulk
+    pub async fn record_results(
+        &self,
+        session_id: i32,
+        results: Vec<TestResultInput>,
+        school_id: Option<i32>,
+        admin_id: i32,
+    ) -> AppResult<Vec<TestBookingDetail>> {
+        let session = self
+            .get_managed_session(session_id, school_id)
+            .await?
+            .session;
+
+        if session.starts_at > Utc::now() {
+            return Err(AppError::Validation(
+                "Results can only be recorded once the session has started".to_string(),
+            ));
+        }
+
+        let booked: HashSet<i32> = self
+            .test_repo
+            .find_bookings_by_session(session_id)
+            .await?
+            .into_iter()
+            .map(|b| b.booking.registration_id)
+            .collect();
+
+        let errors = validate_results(&results, &booked);
+        if !errors.is_empty() {
+            return Err(AppError::FieldValidation(errors));
+        }
+
+        self.test_repo
+            .record_results(session_id, &results, admin_id)
+            .await?;
+
+        tracing::info!(
+            "Results of {} applicants recorded for test session {} by admin {}",
+            results.len(),
+            session_id,
+            admin_id
+        );
+
+        self.test_repo.find_bookings_by_session(session_id).await
+    }
+
+    pub async fn get_test_card(&self, registration_id: i32) -> AppResult<TestCard> {
+        let registration = self.get_registration(registration_id).await?;
+
+        let school = self
+            .school_repo
+            .find_by_id(registration.school_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("School not found".to_string()))?;
+
+        let path = self
+            .period_repo
+            .find_path_by_id(registration.path_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+
+        let mut sessions = Vec::new();
+        for booking in self
+            .test_repo
+            .find_bookings_by_registration(registration_id)
+            .await?
+        {
+            let session = self.get_session(booking.session_id).await?.session;
+            sessions.push(TestCardSession {
+                test_type: session.test_type,
+                name: session.name,
+                starts_at: session.starts_at,
+                ends_at: session.ends_at,
+                room: session.room,
+                seat_number: booking.seat_number,
+            });
+        }
+
+        if sessions.is_empty() {
+            return Err(AppError::NotFound(
+                "No test has been booked for this registration".to_string(),
+            ));
+        }
+
+        Ok(TestCard {
+            registration_number: registration.registration_number,
+            student_name: registration.student_name,
+            student_nisn: registration.student_nisn,
+            school_name: school.name,
+            timezone: school.timezone,
+            path_name: path.name,
+            sessions,
+        })
+    }
+}
+
+/// Kartu peserta tes
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TestCard {
+    /// Nomor pendaftaran
+    #[schema(example = "PPDB-2024-001")]
+    pub registration_number: Option<String>,
+
+    /// Nama siswa
+    #[schema(example = "Ahmad Fauzi")]
+    pub student_name: String,
+
+    /// NISN siswa
+    #[schema(example = "0012345678")]
+    pub student_nisn: String,
+
+    /// Nama sekolah
+    #[schema(example = "SMA Negeri 1 Bandung")]
+    pub school_name: String,
+
+    /// Zona waktu sekolah, dipakai untuk menampilkan jadwal tes
+    #[schema(example = "Asia/Jakarta")]
+    pub timezone: String,
+
+    /// Nama jalur
+    #[schema(example = "Jalur Prestasi")]
+    pub path_name: String,
+
+    /// Jadwal tes yang sudah dipesan
+    pub sessions: Vec<TestCardSession>,
+}
+
+/// Jadwal tes pada kartu peserta
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TestCardSession {
+    /// Jenis tes (written/interview)
+    #[schema(example = "written")]
+    pub test_type: String,
+
+    /// Nama sesi
+    #[schema(example = "Tes Tertulis Sesi 1")]
+    pub name: String,
+
+    /// Waktu mulai
+    #[schema(value_type = String, example = "2024-06-20T08:00:00Z")]
+    pub starts_at: DateTime<Utc>,
+
+    /// Waktu selesai
+    #[schema(value_type = String, example = "2024-06-20T10:00:00Z")]
+    pub ends_at: DateTime<Utc>,
+
+    /// Ruangan
+    #[schema(example = "Ruang 12")]
+    pub room: String,
+
+    /// Nomor kursi
+    #[schema(example = 7)]
+    pub seat_number: i32,
+}
+
+/// Hasil penjadwalan otomatis
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AutoAssignResult {
+    /// Jumlah pendaftar yang mendapat jadwal
+    #[schema(example = 120)]
+    pub assigned: i32,
+
+    /// Jumlah pendaftar yang belum mendapat jadwal karena kursi habis
+    #[schema(example = 4)]
+    pub unassigned: i32,
+}
+
+/// Free seats of an upcoming session
+#[derive(Debug, Clone)]
+pub struct SeatPool {
+    pub session_id: i32,
+    pub path_id: Option<i32>,
+    pub remaining: i64,
+}
+
+/// Fill sessions in order: each (registration_id, path_id) takes the first
+/// session open to its path that still has a free seat
+pub fn assign_seats(sessions: &[SeatPool], registrations: &[(i32, i32)]) -> Vec<(i32, i32)> {
+    let mut remaining: Vec<i64> = sessions.iter().map(|s| s.remaining).collect();
+    let mut assignments = Vec::new();
+
+    for (registration_id, path_id) in registrations {
+        let session = sessions.iter().enumerate().find(|(index, session)| {
+            remaining[*index] > 0 && session.path_id.map(|p| p == *path_id).unwrap_or(true)
+        });
+
+        if let Some((index, session)) = session {
+            remaining[index] -= 1;
+            assignments.push((*registration_id, session.session_id));
+        }
+    }
+
+    assignments
+}
+
+pub fn validate_session(
+    test_type: &str,
+    name: &str,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    room: &str,
+    capacity: i32,
+) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if !TEST_TYPES.contains(&test_type) {
+        errors.push(FieldError::new(
+            "test_type",
+            "Test type must be written or interview",
+        ));
+    }
+
+    if name.trim().is_empty() {
+        errors.push(FieldError::new("name", "Session name is required"));
+    }
+
+    if ends_at <= starts_at {
+        errors.push(FieldError::new(
+            "ends_at",
+            "Session must end after it starts",
+        ));
+    }
+
+    if room.trim().is_empty() {
+        errors.push(FieldError::new("room", "Room is required"));
+    }
+
+    if capacity <= 0 {
+        errors.push(FieldError::new(
+            "capacity",
+            "Capacity must be greater than 0",
+        ));
+    }
+
+    errors
+}
+
+pub fn validate_results(results: &[TestResultInput], booked: &HashSet<i32>) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+
+    if results.is_empty() {
+        errors.push(FieldError::new(
+            "results",
+            "At least one result is required",
+        ));
+    }
+
+    for (index, result) in results.iter().enumerate() {
+        let field = |name: &str| format!("results[{}].{}", index, name);
+
+        if !booked.contains(&result.registration_id) {
+            errors.push(FieldError::new(
+                &field("registration_id"),
+                "Registration has no booking in this session",
+            ));
+        } else if !seen.insert(result.registration_id) {
+            errors.push(FieldError::new(
+                &field("registration_id"),
+                "Registration is listed more than once",
+            ));
+        }
+
+        match result.score {
+            Some(_) if !result.attended => errors.push(FieldError::new(
+                &field("score"),
+                "Absent applicants cannot have a score",
+            )),
+            Some(score) if !(0.0..=100.0).contains(&score) => errors.push(FieldError::new(
+                &field("score"),
+                "Score must be between 0 and 100",
+            )),
+            _ => {}
+        }
+    }
+
+    errors
+}
+
+/// Escape text for the printable test card
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Printable HTML version of the test card
+pub fn render_test_card_html(card: &TestCard) -> String {
+    let timezone = school_timezone(&card.timezone);
+
+    let mut rows = String::new();
+    for session in &card.sessions {
+        let test_type = match session.test_type.as_str() {
+            "interview" => "Wawancara",
+            _ => "Tes Tertulis",
+        };
+
+        rows.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{} - {}</td><td>{}</td><td>{}</td></tr>\n",
+            test_type,
+            escape_html(&session.name),
+            session.starts_at.with_timezone(&timezone).format("%d-%m-%Y %H:%M"),
+            session.ends_at.with_timezone(&timezone).format("%H:%M %Z"),
+            escape_html(&session.room),
+            session.seat_number
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="id">
+<head>
+<meta charset="utf-8">
+<title>Kartu Peserta Tes - {student_name}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ border: 1px solid #000; padding: 4px 8px; text-align: left; }}
+@media print {{ body {{ margin: 0; }} }}
+</style>
+</head>
+<body>
+<h1>Kartu Peserta Tes</h1>
+<h2>{school_name}</h2>
+<p>Nomor pendaftaran: {registration_number}<br>
+Nama: {student_name}<br>
+NISN: {student_nisn}<br>
+Jalur: {path_name}</p>
+<table>
+<tr><th>Jenis</th><th>Sesi</th><th>Waktu (UTC)</th><th>Ruang</th><th>Kursi</th></tr>
+{rows}</table>
+<p>Bawa kartu ini dan kartu identitas saat mengikuti tes.</p>
+</body>
+</html>
+"#,
+        student_name = escape_html(&card.student_name),
+        school_name = escape_html(&card.school_name),
+        registration_number = escape_html(card.registration_number.as_deref().unwrap_or("-")),
+        student_nisn = escape_html(&card.student_nisn),
+        path_name = escape_html(&card.path_name),
+        rows = rows
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_assign_seats_respects_capacity_and_path() {
+        let sessions = vec![
+            SeatPool {
+                session_id: 1,
+                path_id: Some(10),
+                remaining: 1,
+            },
+            SeatPool {
+                session_id: 2,
+                path_id: None,
+                remaining: 2,
+            },
+        ];
+        let registrations = vec![(100, 10), (101, 20), (102, 10), (103, 20)];
+
+        let assignments = assign_seats(&sessions, &registrations);
+
+        assert_eq!(assignments, vec![(100, 1), (101, 2), (102, 2)]);
+    }
+
+    #[test]
+    fn test_validate_results() {
+        let booked = HashSet::from([1, 2]);
+        let result = |registration_id, attended, score| TestResultInput {
+            registration_id,
+            attended,
+            score,
+            notes: None,
+        };
+
+        assert!(validate_results(
+            &[result(1, true, Some(80.0)), result(2, false, None)],
+            &booked
+        )
+        .is_empty());
+
+        let fields: Vec<String> = validate_results(
+            &[
+                result(3, true, Some(80.0)),
+                result(1, false, Some(50.0)),
+                result(1, true, Some(120.0)),
+            ],
+            &booked,
+        )
+        .into_iter()
+        .map(|e| e.field)
+        .collect();
+
+        assert_eq!(
+            fields,
+            vec![
+                "results[0].registration_id",
+                "results[1].score",
+                "results[2].registration_id",
+                "results[2].score"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_test_card_escapes_html() {
+        let card = TestCard {
+            registration_number: Some("PPDB-2024-001".to_string()),
+            student_name: "<script>".to_string(),
+            student_nisn: "0012345678".to_string(),
+            school_name: "SMA Negeri 1".to_string(),
+            timezone: "Asia/Makassar".to_string(),
+            path_name: "Jalur Prestasi".to_string(),
+            sessions: vec![TestCardSession {
+                test_type: "interview".to_string(),
+                name: "Wawancara Sesi 1".to_string(),
+                starts_at: Utc.with_ymd_and_hms(2024, 6, 20, 8, 0, 0).unwrap(),
+                ends_at: Utc.with_ymd_and_hms(2024, 6, 20, 10, 0, 0).unwrap(),
+                room: "Ruang 12".to_string(),
+                seat_number: 7,
+            }],
+        };
+
+        let html = render_test_card_html(&card);
+
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("20-06-2024 16:00 - 18:00 WITA"));
+    }
+}
@@ -21,6 +21,7 @@ impl UserService {
         phone: Option<String>,
         nik: Option<String>,
         role: String,
+        region_id: Option<i32>,
     ) -> AppResult<User> {
         // Check if email already exists
         if let Some(_) = self.user_repo.find_by_email(&email).await? {
@@ -41,6 +42,7 @@ impl UserService {
                 phone.as_deref(),
                 nik.as_deref(),
                 &role,
+                region_id,
             )
             .await?;
 
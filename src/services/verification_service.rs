@@ -1,14 +1,51 @@
-use crate::models::registration::Registration;
+use std::collections::{HashMap, HashSet};
+
+use crate::models::age_rule::{AgeEvaluation, AgeRules};
+use crate::models::audit_log::{AuditAction, NewAuditLog};
+use crate::models::period::DocumentRejectionPolicy;
+use crate::models::registration::{
+    Document, Registration, RegistrationRevision, RevisionDocument, RevisionField,
+};
+use crate::models::verification::{
+    AssignmentStrategy, BulkAction, BulkTarget, BulkVerificationItem, BulkVerificationJob,
+    LockReleaseReason, VerificationLock,
+};
+use crate::repositories::audit_repo::AuditRepository;
+use crate::repositories::period_repo::PeriodRepository;
 use crate::repositories::registration_repo::RegistrationRepository;
-use crate::utils::error::{AppError, AppResult};
+use crate::repositories::verification_repo::VerificationRepository;
+use crate::services::registration_service::{age_rules, revisable_field_value};
+use crate::utils::error::{AppError, AppResult, FieldError};
+use crate::utils::timezone::format_local;
+
+/// Most registrations or documents a single bulk action may cover
+pub const MAX_BULK_ITEMS: usize = 1000;
+/// Bulk jobs up to this size run within the request, larger ones in the background
+const INLINE_BULK_ITEMS: usize = 50;
+/// Minutes a worker holds a bulk job without finishing an item before the
+/// scheduler hands the job to another worker
+const BULK_JOB_LEASE_MINUTES: i32 = 5;
 
 pub struct VerificationService {
     registration_repo: RegistrationRepository,
+    period_repo: PeriodRepository,
+    verification_repo: VerificationRepository,
+    audit_repo: AuditRepository,
 }
 
 impl VerificationService {
-    pub fn new(registration_repo: RegistrationRepository) -> Self {
-        Self { registration_repo }
+    pub fn new(
+        registration_repo: RegistrationRepository,
+        period_repo: PeriodRepository,
+        verification_repo: VerificationRepository,
+        audit_repo: AuditRepository,
+    ) -> Self {
+        Self {
+            registration_repo,
+            period_repo,
+            verification_repo,
+            audit_repo,
+        }
     }
 
     pub async fn get_pending_verifications(
@@ -18,7 +55,7 @@ impl VerificationService {
         page_size: i64,
         period_id: Option<i32>,
         path_id: Option<i32>,
-    ) -> AppResult<(Vec<Registration>, i64)> {
+    ) -> AppResult<(Vec<PendingVerification>, i64)> {
         let offset = (page - 1) * page_size;
 
         // Get submitted registrations
@@ -39,7 +76,50 @@ impl VerificationService {
             .count_by_school(school_id, Some("submitted".to_string()), period_id, path_id)
             .await?;
 
-        Ok((registrations, total))
+        // Resubmitted registrations show what changed since the revision request
+        let ids: Vec<i32> = registrations.iter().map(|r| r.id).collect();
+        let mut resubmissions: HashMap<i32, RegistrationRevision> = self
+            .registration_repo
+            .find_latest_resubmissions(&ids)
+            .await?
+            .into_iter()
+            .map(|revision| (revision.registration_id, revision))
+            .collect();
+
+        // Show who is working on each registration
+        let mut claims: HashMap<i32, VerificationLock> = self
+            .verification_repo
+            .find_active_locks(&ids)
+            .await?
+            .into_iter()
+            .map(|lock| (lock.registration_id, lock))
+            .collect();
+
+        // Report each student's age against the rules of their period
+        let mut rules_by_period: HashMap<i32, AgeRules> = HashMap::new();
+        let mut checked = Vec::with_capacity(registrations.len());
+
+        for registration in registrations {
+            if !rules_by_period.contains_key(&registration.period_id) {
+                let period = self
+                    .period_repo
+                    .find_by_id(registration.period_id)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+                rules_by_period.insert(period.id, age_rules(&period)?);
+            }
+
+            let evaluation = rules_by_period[&registration.period_id]
+                .evaluate(registration.student_birth_date.date_naive());
+            checked.push(PendingVerification {
+                resubmission: resubmissions.remove(&registration.id),
+                claim: claims.remove(&registration.id),
+                registration,
+                age_check: evaluation,
+            });
+        }
+
+        Ok((checked, total))
     }
 
     pub async fn verify_registration(&self, id: i32, admin_id: i32) -> AppResult<Registration> {
@@ -57,6 +137,8 @@ impl VerificationService {
             ));
         }
 
+        self.ensure_not_claimed_by_other(&registration, admin_id).await?;
+
         // Check if all documents are uploaded
         let documents = self
             .registration_repo
@@ -69,11 +151,23 @@ impl VerificationService {
             ));
         }
 
+        // The path's required documents must all be approved first
+        let path = self
+            .period_repo
+            .find_path_by_id(registration.path_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+        let check = check_required_documents(&path.required_documents, &documents);
+        if let Some(problem) = check.blocking_reason() {
+            return Err(AppError::Validation(problem));
+        }
+
         // Update status to verified
         let verified_registration = self
             .registration_repo
             .update_status(id, "verified", None)
             .await?;
+        self.verification_repo.complete(id, admin_id).await?;
 
         // TODO: Send approval notification email
         tracing::info!(
@@ -82,7 +176,14 @@ impl VerificationService {
             admin_id
         );
 
-        // TODO: Log to audit_logs
+        self.audit_repo
+            .create(&registration_audit(
+                &registration,
+                admin_id,
+                AuditAction::Verify,
+                serde_json::json!({ "status": verified_registration.status }),
+            ))
+            .await?;
 
         Ok(verified_registration)
     }
@@ -107,6 +208,8 @@ impl VerificationService {
             ));
         }
 
+        self.ensure_not_claimed_by_other(&registration, admin_id).await?;
+
         // Validate reason
         if reason.trim().is_empty() {
             return Err(AppError::Validation(
@@ -119,6 +222,7 @@ impl VerificationService {
             .registration_repo
             .update_status(id, "rejected", Some(&reason))
             .await?;
+        self.verification_repo.complete(id, admin_id).await?;
 
         // TODO: Send rejection notification email with reason
         tracing::info!(
@@ -128,18 +232,184 @@ impl VerificationService {
             reason
         );
 
-        // TODO: Log to audit_logs
+        self.audit_repo
+            .create(&registration_audit(
+                &registration,
+                admin_id,
+                AuditAction::Reject,
+                serde_json::json!({ "status": rejected_registration.status, "reason": reason }),
+            ))
+            .await?;
 
         Ok(rejected_registration)
     }
 
+    /// Send a submitted registration back to the parent to correct the flagged
+    /// fields and documents instead of rejecting it
+    pub async fn request_revision(
+        &self,
+        id: i32,
+        notes: Option<String>,
+        fields: Vec<RevisionField>,
+        documents: Vec<RevisionDocument>,
+        school_id: Option<i32>,
+        admin_id: i32,
+    ) -> AppResult<(Registration, RegistrationRevision)> {
+        let registration = self
+            .registration_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))?;
+
+        if let Some(school_id) = school_id {
+            if registration.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have access to this registration".to_string(),
+                ));
+            }
+        }
+
+        if registration.status != "submitted" {
+            return Err(AppError::Validation(
+                "Can only request revisions of registrations in submitted status".to_string(),
+            ));
+        }
+
+        if fields.is_empty() && documents.is_empty() {
+            return Err(AppError::Validation(
+                "Flag at least one field or document to revise".to_string(),
+            ));
+        }
+
+        self.ensure_not_claimed_by_other(&registration, admin_id).await?;
+
+        let mut errors = Vec::new();
+        let mut field_values = serde_json::Map::new();
+        for (index, flagged) in fields.iter().enumerate() {
+            match revisable_field_value(&registration, &flagged.field) {
+                Some(value) => {
+                    field_values.insert(flagged.field.clone(), value);
+                }
+                None => errors.push(FieldError::new(
+                    &format!("fields[{}].field", index),
+                    format!("Field '{}' cannot be revised", flagged.field),
+                )),
+            }
+            if flagged.note.trim().is_empty() {
+                errors.push(FieldError::new(
+                    &format!("fields[{}].note", index),
+                    "Explain what needs to be corrected",
+                ));
+            }
+        }
+
+        let registration_documents = self
+            .registration_repo
+            .find_documents_by_registration(id)
+            .await?;
+        let mut document_values = serde_json::Map::new();
+        for (index, flagged) in documents.iter().enumerate() {
+            match registration_documents.iter().find(|d| d.id == flagged.document_id) {
+                Some(document) => {
+                    document_values.insert(
+                        document.id.to_string(),
+                        serde_json::json!({
+                            "document_type": document.document_type,
+                            "file_name": document.file_name,
+                        }),
+                    );
+                }
+                None => errors.push(FieldError::new(
+                    &format!("documents[{}].document_id", index),
+                    "Document does not belong to this registration",
+                )),
+            }
+            if flagged.note.trim().is_empty() {
+                errors.push(FieldError::new(
+                    &format!("documents[{}].note", index),
+                    "Explain what needs to be corrected",
+                ));
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(AppError::FieldValidation(errors));
+        }
+
+        // Flagged documents are rejected with the verifier's note
+        for flagged in &documents {
+            self.registration_repo
+                .update_document_verification(
+                    flagged.document_id,
+                    "rejected",
+                    Some(&flagged.note),
+                    admin_id,
+                )
+                .await?;
+        }
+
+        let flagged_fields =
+            serde_json::to_value(&fields).map_err(|e| AppError::Internal(e.to_string()))?;
+        let flagged_documents =
+            serde_json::to_value(&documents).map_err(|e| AppError::Internal(e.to_string()))?;
+        let snapshot = serde_json::json!({
+            "fields": field_values,
+            "documents": document_values,
+        });
+        let (registration, revision) = self
+            .registration_repo
+            .create_revision(
+                id,
+                admin_id,
+                notes.as_deref().map(str::trim).filter(|n| !n.is_empty()),
+                &flagged_fields,
+                &flagged_documents,
+                &snapshot,
+            )
+            .await?;
+        self.verification_repo.complete(id, admin_id).await?;
+
+        // TODO: Send revision notification email with the notes
+        tracing::info!(
+            "Revision {} requested for registration {} by admin {}: {} fields, {} documents",
+            revision.id,
+            id,
+            admin_id,
+            fields.len(),
+            documents.len()
+        );
+
+        self.audit_repo
+            .create(&NewAuditLog {
+                school_id: Some(registration.school_id),
+                user_id: Some(admin_id),
+                entity_type: "registration",
+                entity_id: id,
+                action: AuditAction::RequestRevision,
+                old_value: Some(serde_json::json!({ "status": "submitted" })),
+                new_value: Some(serde_json::json!({
+                    "status": registration.status,
+                    "revision_id": revision.id,
+                    "fields": flagged_fields,
+                    "documents": flagged_documents,
+                })),
+            })
+            .await?;
+
+        Ok((registration, revision))
+    }
+
+    /// Approve or reject a document. Rejecting one of the path's required
+    /// documents decides the registration by the period's policy: it is sent
+    /// back for revision or rejected outright.
     pub async fn verify_document(
         &self,
         document_id: i32,
         verification_status: String,
         verification_notes: Option<String>,
+        school_id: Option<i32>,
         admin_id: i32,
-    ) -> AppResult<()> {
+    ) -> AppResult<DocumentVerification> {
         // Validate verification status
         if verification_status != "approved" && verification_status != "rejected" {
             return Err(AppError::Validation(
@@ -154,13 +424,81 @@ impl VerificationService {
             .await?
             .ok_or_else(|| AppError::NotFound("Document not found".to_string()))?;
 
+        let registration = self
+            .find_school_registration(document.registration_id, school_id)
+            .await?;
+
+        if registration.status != "submitted" {
+            return Err(AppError::Validation(
+                "Can only verify documents of registrations in submitted status".to_string(),
+            ));
+        }
+
+        // Verifiers never judge a file they could not be shown
+        match document.scan_status.as_str() {
+            "pending" => {
+                return Err(AppError::Conflict(
+                    "Document is still being scanned for malware".to_string(),
+                ));
+            }
+            "infected" => {
+                return Err(AppError::Conflict(
+                    "Document was rejected by the malware scan".to_string(),
+                ));
+            }
+            "failed" => {
+                return Err(AppError::Conflict(
+                    "Document could not be scanned for malware and must be uploaded again"
+                        .to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        self.ensure_not_claimed_by_other(&registration, admin_id)
+            .await?;
+
+        let path = self
+            .period_repo
+            .find_path_by_id(registration.path_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+        let required = path.required_documents.contains(&document.document_type);
+
+        let notes = verification_notes
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty());
+        if verification_status == "rejected" && required && notes.is_none() {
+            return Err(AppError::Validation(
+                "Explain why the required document is rejected".to_string(),
+            ));
+        }
+
         // Update document verification status
-        self.registration_repo
-            .update_document_verification(
-                document_id,
-                &verification_status,
-                verification_notes.as_deref(),
-            )
+        let previous_status = document.verification_status.clone();
+        let document = self
+            .registration_repo
+            .update_document_verification(document_id, &verification_status, notes, admin_id)
+            .await?;
+
+        self.audit_repo
+            .create(&NewAuditLog {
+                school_id: Some(registration.school_id),
+                user_id: Some(admin_id),
+                entity_type: "document",
+                entity_id: document_id,
+                action: if verification_status == "approved" {
+                    AuditAction::Approve
+                } else {
+                    AuditAction::Reject
+                },
+                old_value: Some(serde_json::json!({ "verification_status": previous_status })),
+                new_value: Some(serde_json::json!({
+                    "verification_status": document.verification_status,
+                    "verification_notes": document.verification_notes,
+                })),
+            })
             .await?;
 
         tracing::info!(
@@ -170,11 +508,666 @@ impl VerificationService {
             admin_id
         );
 
-        // TODO: Log to audit_logs
+        if verification_status != "rejected" || !required {
+            return Ok(DocumentVerification {
+                document,
+                registration,
+                outcome: None,
+            });
+        }
+
+        // A rejected required document decides the registration
+        let period = self
+            .period_repo
+            .find_by_id(registration.period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+        let policy = DocumentRejectionPolicy::from_str(&period.document_rejection_policy)
+            .unwrap_or(DocumentRejectionPolicy::Revision);
+        let note = notes.unwrap_or_default().to_string();
+
+        let registration = match policy {
+            DocumentRejectionPolicy::Revision => {
+                let (registration, _) = self
+                    .request_revision(
+                        registration.id,
+                        None,
+                        vec![],
+                        vec![RevisionDocument {
+                            document_id,
+                            note,
+                        }],
+                        school_id,
+                        admin_id,
+                    )
+                    .await?;
+                registration
+            }
+            DocumentRejectionPolicy::Reject => {
+                self.reject_registration(
+                    registration.id,
+                    format!(
+                        "Required document {} was rejected: {}",
+                        document.document_type, note
+                    ),
+                    admin_id,
+                )
+                .await?
+            }
+        };
+
+        Ok(DocumentVerification {
+            document,
+            registration,
+            outcome: Some(policy),
+        })
+    }
+
+    /// Claim the next registration waiting for verification, with a lock that
+    /// keeps other verifiers off it for `lock_minutes`
+    pub async fn claim_next(
+        &self,
+        school_id: i32,
+        verifier_id: i32,
+        period_id: Option<i32>,
+        path_id: Option<i32>,
+        lock_minutes: i64,
+    ) -> AppResult<(Registration, VerificationLock)> {
+        let (registration, lock) = self
+            .verification_repo
+            .claim_next(school_id, verifier_id, period_id, path_id, lock_minutes)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound("No registrations waiting for verification".to_string())
+            })?;
+
+        tracing::info!(
+            "Registration {} claimed by verifier {} until {}",
+            registration.id,
+            verifier_id,
+            lock.expires_at
+        );
+
+        Ok((registration, lock))
+    }
+
+    /// Claim a specific submitted registration, or extend one's own claim on it
+    pub async fn claim_registration(
+        &self,
+        id: i32,
+        school_id: Option<i32>,
+        verifier_id: i32,
+        lock_minutes: i64,
+    ) -> AppResult<VerificationLock> {
+        let registration = self.find_school_registration(id, school_id).await?;
+
+        if registration.status != "submitted" {
+            return Err(AppError::Validation(
+                "Can only claim registrations in submitted status".to_string(),
+            ));
+        }
+
+        let lock = self
+            .verification_repo
+            .claim(id, verifier_id, lock_minutes)
+            .await?;
+
+        tracing::info!(
+            "Registration {} claimed by verifier {} until {}",
+            id,
+            verifier_id,
+            lock.expires_at
+        );
+
+        Ok(lock)
+    }
+
+    /// Give up a claim. Super admins may release another verifier's claim.
+    pub async fn release_claim(
+        &self,
+        id: i32,
+        school_id: Option<i32>,
+        verifier_id: i32,
+        any_holder: bool,
+    ) -> AppResult<()> {
+        let _ = self.find_school_registration(id, school_id).await?;
+
+        let holder = if any_holder { None } else { Some(verifier_id) };
+        let released = self
+            .verification_repo
+            .release(id, holder, LockReleaseReason::Released.as_str())
+            .await?;
+
+        if !released {
+            return Err(AppError::NotFound(
+                "No active claim on this registration".to_string(),
+            ));
+        }
+
+        tracing::info!("Claim on registration {} released by {}", id, verifier_id);
+
+        Ok(())
+    }
+
+    /// Distribute the period's unassigned submitted registrations over verifiers,
+    /// either a whole path to one verifier or round-robin, oldest first
+    pub async fn assign_registrations(
+        &self,
+        school_id: i32,
+        period_id: i32,
+        strategy: String,
+        path_id: Option<i32>,
+        verifier_ids: Vec<i32>,
+        admin_id: i32,
+    ) -> AppResult<AssignmentSummary> {
+        let strategy = AssignmentStrategy::from_str(&strategy).ok_or_else(|| {
+            AppError::Validation("Strategy must be 'path' or 'round_robin'".to_string())
+        })?;
+
+        let mut seen = HashSet::new();
+        let mut verifier_ids = verifier_ids;
+        verifier_ids.retain(|id| seen.insert(*id));
+        match strategy {
+            AssignmentStrategy::Path if path_id.is_none() || verifier_ids.len() != 1 => {
+                return Err(AppError::Validation(
+                    "Assigning by path needs a path and exactly one verifier".to_string(),
+                ));
+            }
+            _ if verifier_ids.is_empty() => {
+                return Err(AppError::Validation(
+                    "Select at least one verifier".to_string(),
+                ));
+            }
+            _ => {}
+        }
+
+        let period = self
+            .period_repo
+            .find_by_id(period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+        if period.school_id != school_id {
+            return Err(AppError::Forbidden(
+                "You don't have access to this period".to_string(),
+            ));
+        }
+
+        if let Some(path_id) = path_id {
+            let path = self
+                .period_repo
+                .find_path_by_id(path_id)
+                .await?
+                .ok_or_else(|| AppError::NotFound("Registration path not found".to_string()))?;
+            if path.period_id != period_id {
+                return Err(AppError::Validation(
+                    "Path does not belong to this period".to_string(),
+                ));
+            }
+        }
+
+        let known = self
+            .verification_repo
+            .find_school_verifiers(school_id, &verifier_ids)
+            .await?;
+        if let Some(unknown) = verifier_ids.iter().find(|id| !known.contains(id)) {
+            return Err(AppError::Validation(format!(
+                "User {} is not a verifier of this school",
+                unknown
+            )));
+        }
+
+        let registration_ids = self
+            .verification_repo
+            .find_unassigned(school_id, period_id, path_id)
+            .await?;
+        let assignments = deal_round_robin(&registration_ids, &verifier_ids);
+        let assigned = self.verification_repo.assign(&assignments).await?;
+
+        let mut per_verifier: HashMap<i32, i64> = HashMap::new();
+        for (_, verifier_id) in &assignments {
+            *per_verifier.entry(*verifier_id).or_default() += 1;
+        }
+
+        tracing::info!(
+            "{} registrations of period {} assigned {} by admin {}",
+            assigned,
+            period_id,
+            strategy.as_str(),
+            admin_id
+        );
+
+        Ok(AssignmentSummary {
+            assigned: assigned as i64,
+            verifiers: verifier_ids
+                .into_iter()
+                .map(|verifier_id| VerifierAssignment {
+                    verifier_id,
+                    assigned: per_verifier.get(&verifier_id).copied().unwrap_or(0),
+                })
+                .collect(),
+        })
+    }
+
+    /// Backlog size and age of a period with the throughput of each verifier
+    pub async fn get_queue_metrics(
+        &self,
+        school_id: i32,
+        period_id: i32,
+    ) -> AppResult<VerificationQueueMetrics> {
+        let period = self
+            .period_repo
+            .find_by_id(period_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+        if period.school_id != school_id {
+            return Err(AppError::Forbidden(
+                "You don't have access to this period".to_string(),
+            ));
+        }
+
+        let backlog = sqlx::query_as::<_, VerificationBacklog>(
+            r#"
+            SELECT COUNT(*) AS waiting,
+                   COUNT(*) FILTER (WHERE r.assigned_verifier_id IS NULL) AS unassigned,
+                   COUNT(*) FILTER (WHERE EXISTS (
+                       SELECT 1 FROM verification_locks l
+                       WHERE l.registration_id = r.id
+                         AND l.released_at IS NULL
+                         AND l.expires_at > NOW()
+                   )) AS claimed,
+                   (MAX(EXTRACT(EPOCH FROM (NOW() - r.submitted_at))) / 3600)::DOUBLE PRECISION
+                       AS oldest_waiting_hours,
+                   (AVG(EXTRACT(EPOCH FROM (NOW() - r.submitted_at))) / 3600)::DOUBLE PRECISION
+                       AS average_waiting_hours
+            FROM registrations r
+            WHERE r.school_id = $1 AND r.period_id = $2 AND r.status = 'submitted'
+            "#,
+        )
+        .bind(school_id)
+        .bind(period_id)
+        .fetch_one(&self.verification_repo.pool)
+        .await?;
+
+        let verifiers = sqlx::query_as::<_, VerifierThroughput>(
+            r#"
+            SELECT u.id AS verifier_id,
+                   u.full_name AS verifier_name,
+                   (SELECT COUNT(*) FROM registrations r
+                    WHERE r.assigned_verifier_id = u.id
+                      AND r.period_id = $2
+                      AND r.status = 'submitted') AS assigned,
+                   COUNT(l.id) FILTER (
+                       WHERE l.released_at IS NULL AND l.expires_at > NOW()
+                   ) AS claimed,
+                   COUNT(l.id) FILTER (WHERE l.release_reason = 'completed') AS completed,
+                   COUNT(l.id) FILTER (WHERE l.release_reason = 'expired') AS expired,
+                   (AVG(EXTRACT(EPOCH FROM (l.released_at - l.locked_at)))
+                       FILTER (WHERE l.release_reason = 'completed') / 60)::DOUBLE PRECISION
+                       AS average_handling_minutes
+            FROM users u
+            LEFT JOIN verification_locks l ON l.verifier_id = u.id
+                 AND l.registration_id IN (SELECT id FROM registrations WHERE period_id = $2)
+            WHERE u.school_id = $1 AND u.role = 'school_admin'
+            GROUP BY u.id, u.full_name
+            ORDER BY completed DESC, u.full_name
+            "#,
+        )
+        .bind(school_id)
+        .bind(period_id)
+        .fetch_all(&self.verification_repo.pool)
+        .await?;
+
+        Ok(VerificationQueueMetrics {
+            period_id,
+            backlog,
+            verifiers,
+        })
+    }
+
+    /// Create a job applying one action to an explicit set of registrations
+    /// or documents, or to those matching a period filter
+    pub async fn create_bulk_job(
+        &self,
+        school_id: i32,
+        admin_id: i32,
+        target: BulkTarget,
+        action: String,
+        ids: Option<Vec<i32>>,
+        filter: BulkFilter,
+        notes: Option<String>,
+    ) -> AppResult<BulkVerificationJob> {
+        let action = BulkAction::from_str(&action)
+            .filter(|a| a.applies_to(target))
+            .ok_or_else(|| {
+                AppError::Validation(match target {
+                    BulkTarget::Registration => {
+                        "Action must be 'verify', 'reject', or 'request_revision'".to_string()
+                    }
+                    BulkTarget::Document => "Action must be 'approve' or 'reject'".to_string(),
+                })
+            })?;
+
+        let notes = notes
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty());
+        if action == BulkAction::Reject && notes.as_deref().is_none_or(|n| n.len() < 10) {
+            return Err(AppError::Validation(
+                "Reason must be at least 10 characters".to_string(),
+            ));
+        }
+
+        let entity_ids = match (ids, filter.period_id) {
+            (Some(ids), None) => dedup_ids(ids),
+            (None, Some(period_id)) => {
+                let period = self
+                    .period_repo
+                    .find_by_id(period_id)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("Period not found".to_string()))?;
+                if period.school_id != school_id {
+                    return Err(AppError::Forbidden(
+                        "You don't have access to this period".to_string(),
+                    ));
+                }
+
+                match target {
+                    BulkTarget::Registration => {
+                        self.verification_repo
+                            .find_submitted_registration_ids(school_id, period_id, filter.path_id)
+                            .await?
+                    }
+                    BulkTarget::Document => {
+                        self.verification_repo
+                            .find_pending_document_ids(
+                                school_id,
+                                period_id,
+                                filter.path_id,
+                                filter.document_type.as_deref(),
+                            )
+                            .await?
+                    }
+                }
+            }
+            _ => {
+                return Err(AppError::Validation(
+                    "Give either explicit ids or a period filter".to_string(),
+                ))
+            }
+        };
+
+        if entity_ids.is_empty() {
+            return Err(AppError::Validation("Nothing matches the selection".to_string()));
+        }
+        if entity_ids.len() > MAX_BULK_ITEMS {
+            return Err(AppError::Validation(format!(
+                "A bulk action covers at most {} items, {} selected",
+                MAX_BULK_ITEMS,
+                entity_ids.len()
+            )));
+        }
+
+        let job = self
+            .verification_repo
+            .create_bulk_job(
+                school_id,
+                admin_id,
+                target.as_str(),
+                action.as_str(),
+                notes.as_deref(),
+                &entity_ids,
+            )
+            .await?;
+
+        tracing::info!(
+            "Bulk job {} created by admin {}: {} {} items",
+            job.id,
+            admin_id,
+            job.action,
+            job.total
+        );
+
+        Ok(job)
+    }
+
+    /// Run small jobs right away and hand larger ones to a background task
+    pub async fn dispatch_bulk_job(self, job: BulkVerificationJob) -> AppResult<BulkVerificationJob> {
+        if job.total as usize <= INLINE_BULK_ITEMS {
+            return self.run_bulk_job(job.id).await;
+        }
+
+        let job_id = job.id;
+        tokio::spawn(async move {
+            if let Err(e) = self.run_bulk_job(job_id).await {
+                tracing::warn!("Bulk job {} failed: {}", job_id, e);
+            }
+        });
+
+        Ok(job)
+    }
+
+    /// Resume jobs abandoned by a worker that stopped, for example on a
+    /// restart, in a background task. Returns the number of jobs found.
+    pub async fn resume_abandoned_bulk_jobs(self) -> AppResult<usize> {
+        let job_ids = self
+            .verification_repo
+            .find_abandoned_bulk_jobs(BULK_JOB_LEASE_MINUTES)
+            .await?;
+        if job_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let count = job_ids.len();
+        tokio::spawn(async move {
+            for job_id in job_ids {
+                if let Err(e) = self.run_bulk_job(job_id).await {
+                    tracing::warn!("Bulk job {} failed: {}", job_id, e);
+                }
+            }
+        });
+
+        Ok(count)
+    }
+
+    /// Apply the job's action to each pending item, recording per item
+    /// whether it succeeded. Every item goes through the single-item checks.
+    /// Items finished before an interruption are not applied again.
+    pub async fn run_bulk_job(&self, job_id: i32) -> AppResult<BulkVerificationJob> {
+        let job = match self
+            .verification_repo
+            .claim_bulk_job(job_id, BULK_JOB_LEASE_MINUTES)
+            .await?
+        {
+            Some(job) => job,
+            None => {
+                return self
+                    .verification_repo
+                    .find_bulk_job(job_id)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("Bulk job not found".to_string()))
+            }
+        };
+
+        if let Err(e) = self.run_bulk_items(&job).await {
+            self.verification_repo.finish_bulk_job(job.id, "failed").await?;
+            return Err(e);
+        }
+
+        let job = self
+            .verification_repo
+            .finish_bulk_job(job.id, "completed")
+            .await?;
+
+        tracing::info!(
+            "Bulk job {} completed: {} succeeded, {} failed",
+            job.id,
+            job.succeeded,
+            job.failed
+        );
+
+        Ok(job)
+    }
+
+    async fn run_bulk_items(&self, job: &BulkVerificationJob) -> AppResult<()> {
+        let target = BulkTarget::from_str(&job.target)
+            .ok_or_else(|| AppError::Internal(format!("Unknown bulk target {}", job.target)))?;
+        let action = BulkAction::from_str(&job.action)
+            .ok_or_else(|| AppError::Internal(format!("Unknown bulk action {}", job.action)))?;
+
+        let items = self.verification_repo.find_bulk_items(job.id).await?;
+        for item in items.iter().filter(|i| i.status == "pending") {
+            let error = match self.apply_bulk_item(job, target, action, item.entity_id).await {
+                Ok(()) => None,
+                Err(e) => Some(bulk_item_error(&e)),
+            };
+            self.verification_repo
+                .finish_bulk_item(item.id, error.as_deref(), BULK_JOB_LEASE_MINUTES)
+                .await?;
+        }
 
         Ok(())
     }
 
+    async fn apply_bulk_item(
+        &self,
+        job: &BulkVerificationJob,
+        target: BulkTarget,
+        action: BulkAction,
+        entity_id: i32,
+    ) -> AppResult<()> {
+        let school_id = Some(job.school_id);
+        let admin_id = job.requested_by;
+
+        if target == BulkTarget::Document {
+            let status = if action == BulkAction::Approve { "approved" } else { "rejected" };
+            self.verify_document(
+                entity_id,
+                status.to_string(),
+                job.notes.clone(),
+                school_id,
+                admin_id,
+            )
+            .await?;
+            return Ok(());
+        }
+
+        self.find_school_registration(entity_id, school_id).await?;
+        match action {
+            BulkAction::Verify => {
+                self.verify_registration(entity_id, admin_id).await?;
+            }
+            BulkAction::Reject => {
+                self.reject_registration(entity_id, job.notes.clone().unwrap_or_default(), admin_id)
+                    .await?;
+            }
+            BulkAction::RequestRevision => {
+                // Send back every document rejected so far
+                let flagged: Vec<RevisionDocument> = self
+                    .registration_repo
+                    .find_documents_by_registration(entity_id)
+                    .await?
+                    .into_iter()
+                    .filter(|d| d.verification_status == "rejected")
+                    .map(|d| RevisionDocument {
+                        document_id: d.id,
+                        note: d
+                            .verification_notes
+                            .or_else(|| job.notes.clone())
+                            .unwrap_or_default(),
+                    })
+                    .collect();
+                if flagged.is_empty() {
+                    return Err(AppError::Validation(
+                        "No rejected documents to send back".to_string(),
+                    ));
+                }
+
+                self.request_revision(
+                    entity_id,
+                    job.notes.clone(),
+                    vec![],
+                    flagged,
+                    school_id,
+                    admin_id,
+                )
+                .await?;
+            }
+            BulkAction::Approve => {
+                return Err(AppError::Validation(
+                    "Registrations cannot be approved, verify them instead".to_string(),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Bulk job with its per-item report
+    pub async fn get_bulk_job(
+        &self,
+        job_id: i32,
+        school_id: Option<i32>,
+    ) -> AppResult<(BulkVerificationJob, Vec<BulkVerificationItem>)> {
+        let job = self
+            .verification_repo
+            .find_bulk_job(job_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Bulk job not found".to_string()))?;
+
+        if let Some(school_id) = school_id {
+            if job.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have access to this bulk job".to_string(),
+                ));
+            }
+        }
+
+        let items = self.verification_repo.find_bulk_items(job.id).await?;
+
+        Ok((job, items))
+    }
+
+    /// Decisions on a registration claimed by someone else are refused until
+    /// the claim is released or expires
+    async fn ensure_not_claimed_by_other(
+        &self,
+        registration: &Registration,
+        verifier_id: i32,
+    ) -> AppResult<()> {
+        if let Some(lock) = self.verification_repo.find_active_lock(registration.id).await? {
+            if lock.verifier_id != verifier_id {
+                let timezone = self.period_repo.find_school_timezone(registration.school_id).await?;
+                return Err(AppError::Conflict(format!(
+                    "Registration is being verified by another verifier until {}",
+                    format_local(lock.expires_at, timezone)
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn find_school_registration(
+        &self,
+        id: i32,
+        school_id: Option<i32>,
+    ) -> AppResult<Registration> {
+        let registration = self
+            .registration_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Registration not found".to_string()))?;
+
+        if let Some(school_id) = school_id {
+            if registration.school_id != school_id {
+                return Err(AppError::Forbidden(
+                    "You don't have access to this registration".to_string(),
+                ));
+            }
+        }
+
+        Ok(registration)
+    }
+
     pub async fn get_verification_statistics(
         &self,
         school_id: i32,
@@ -195,6 +1188,16 @@ impl VerificationService {
             .count_by_school(school_id, Some("rejected".to_string()), period_id, None)
             .await?;
 
+        let revision_requested_count = self
+            .registration_repo
+            .count_by_school(
+                school_id,
+                Some("revision_requested".to_string()),
+                period_id,
+                None,
+            )
+            .await?;
+
         let total_count = self
             .registration_repo
             .count_by_school(school_id, None, period_id, None)
@@ -205,11 +1208,150 @@ impl VerificationService {
             submitted: submitted_count,
             verified: verified_count,
             rejected: rejected_count,
+            revision_requested: revision_requested_count,
             pending: submitted_count,
         })
     }
 }
 
+/// Registration waiting for verification
+#[derive(Debug)]
+pub struct PendingVerification {
+    pub registration: Registration,
+    pub age_check: AgeEvaluation,
+    /// Latest revision, when the registration was resubmitted after one
+    pub resubmission: Option<RegistrationRevision>,
+    /// Active claim of a verifier working on the registration
+    pub claim: Option<VerificationLock>,
+}
+
+/// Selection of a bulk action by period instead of explicit ids
+#[derive(Debug, Default)]
+pub struct BulkFilter {
+    pub period_id: Option<i32>,
+    pub path_id: Option<i32>,
+    /// Only for documents
+    pub document_type: Option<String>,
+}
+
+/// Drop repeated ids, keeping the first occurrence's position
+pub fn dedup_ids(ids: Vec<i32>) -> Vec<i32> {
+    let mut seen = HashSet::new();
+    ids.into_iter().filter(|id| seen.insert(*id)).collect()
+}
+
+/// Reason an item failed, as shown in the bulk report
+pub fn bulk_item_error(error: &AppError) -> String {
+    match error {
+        AppError::FieldValidation(errors) => errors
+            .iter()
+            .map(|e| format!("{}: {}", e.field, e.message))
+            .collect::<Vec<_>>()
+            .join("; "),
+        // Details are logged, not reported
+        AppError::Database(e) => {
+            tracing::error!("Bulk item failed: {:?}", e);
+            "Database error".to_string()
+        }
+        _ => error.to_string(),
+    }
+}
+
+/// Audit entry of a decision on a submitted registration
+fn registration_audit(
+    registration: &Registration,
+    admin_id: i32,
+    action: AuditAction,
+    new_value: serde_json::Value,
+) -> NewAuditLog {
+    NewAuditLog {
+        school_id: Some(registration.school_id),
+        user_id: Some(admin_id),
+        entity_type: "registration",
+        entity_id: registration.id,
+        action,
+        old_value: Some(serde_json::json!({ "status": registration.status })),
+        new_value: Some(new_value),
+    }
+}
+
+/// Document decision and the registration it left behind
+#[derive(Debug)]
+pub struct DocumentVerification {
+    pub document: Document,
+    pub registration: Registration,
+    /// Set when a rejected required document decided the registration
+    pub outcome: Option<DocumentRejectionPolicy>,
+}
+
+/// Required document types grouped by the state of their latest upload
+#[derive(Debug, Default, PartialEq)]
+pub struct RequiredDocumentCheck {
+    pub missing: Vec<String>,
+    pub pending: Vec<String>,
+    pub rejected: Vec<String>,
+}
+
+impl RequiredDocumentCheck {
+    /// Why the registration cannot be verified yet, if anything blocks it
+    pub fn blocking_reason(&self) -> Option<String> {
+        if !self.missing.is_empty() {
+            return Some(format!(
+                "Required documents are missing: {}",
+                self.missing.join(", ")
+            ));
+        }
+        if !self.pending.is_empty() {
+            return Some(format!(
+                "Required documents are still pending verification: {}",
+                self.pending.join(", ")
+            ));
+        }
+        if !self.rejected.is_empty() {
+            return Some(format!(
+                "Required documents were rejected: {}",
+                self.rejected.join(", ")
+            ));
+        }
+        None
+    }
+}
+
+/// Judge each required document type by its latest upload, so a replacement
+/// uploaded during a revision supersedes the rejected original
+pub fn check_required_documents(required: &[String], documents: &[Document]) -> RequiredDocumentCheck {
+    let mut check = RequiredDocumentCheck::default();
+
+    for document_type in required {
+        let latest = documents
+            .iter()
+            .filter(|d| &d.document_type == document_type)
+            .max_by_key(|d| (d.created_at, d.id));
+
+        match latest.map(|d| d.verification_status.as_str()) {
+            None => check.missing.push(document_type.clone()),
+            Some("approved") => {}
+            Some("rejected") => check.rejected.push(document_type.clone()),
+            Some(_) => check.pending.push(document_type.clone()),
+        }
+    }
+
+    check
+}
+
+/// Deal registrations out to verifiers in turn, as (registration_id, verifier_id)
+pub fn deal_round_robin(registration_ids: &[i32], verifier_ids: &[i32]) -> Vec<(i32, i32)> {
+    if verifier_ids.is_empty() {
+        return Vec::new();
+    }
+
+    registration_ids
+        .iter()
+        .enumerate()
+        .map(|(index, id)| (*id, verifier_ids[index % verifier_ids.len()]))
+        .collect()
+}
+
 /// Statistik verifikasi
 #[derive(Debug, serde::Serialize, utoipa::ToSchema)]
 pub struct VerificationStats {
@@ -229,7 +1371,224 @@ pub struct VerificationStats {
     #[schema(example = 10)]
     pub rejected: i64,
     
+    /// Total yang dikembalikan untuk perbaikan
+    #[schema(example = 5)]
+    pub revision_requested: i64,
+    
     /// Total yang menunggu verifikasi
     #[schema(example = 30)]
     pub pending: i64,
 }
+
+/// Hasil pembagian pendaftaran ke verifikator
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct AssignmentSummary {
+    /// Total pendaftaran yang dibagikan
+    #[schema(example = 120)]
+    pub assigned: i64,
+
+    /// Jumlah pendaftaran per verifikator
+    pub verifiers: Vec<VerifierAssignment>,
+}
+
+/// Jumlah pendaftaran yang dibagikan ke satu verifikator
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct VerifierAssignment {
+    /// ID verifikator
+    #[schema(example = 2)]
+    pub verifier_id: i32,
+
+    /// Jumlah pendaftaran
+    #[schema(example = 40)]
+    pub assigned: i64,
+}
+
+/// Metrik antrean verifikasi satu periode
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct VerificationQueueMetrics {
+    /// ID periode
+    #[schema(example = 1)]
+    pub period_id: i32,
+
+    /// Antrean pendaftaran yang menunggu verifikasi
+    pub backlog: VerificationBacklog,
+
+    /// Kinerja per verifikator
+    pub verifiers: Vec<VerifierThroughput>,
+}
+
+/// Antrean pendaftaran yang menunggu verifikasi
+#[derive(Debug, Clone, sqlx::FromRow, serde::Serialize, utoipa::ToSchema)]
+pub struct VerificationBacklog {
+    /// Total yang menunggu verifikasi
+    #[schema(example = 85)]
+    pub waiting: i64,
+
+    /// Belum dibagikan ke verifikator
+    #[schema(example = 20)]
+    pub unassigned: i64,
+
+    /// Sedang dikerjakan verifikator
+    #[schema(example = 4)]
+    pub claimed: i64,
+
+    /// Lama menunggu pendaftaran terlama dalam jam
+    #[schema(example = 52.5)]
+    pub oldest_waiting_hours: Option<f64>,
+
+    /// Rata-rata lama menunggu dalam jam
+    #[schema(example = 18.2)]
+    pub average_waiting_hours: Option<f64>,
+}
+
+/// Kinerja satu verifikator dalam satu periode
+#[derive(Debug, Clone, sqlx::FromRow, serde::Serialize, utoipa::ToSchema)]
+pub struct VerifierThroughput {
+    /// ID verifikator
+    #[schema(example = 2)]
+    pub verifier_id: i32,
+
+    /// Nama verifikator
+    #[schema(example = "Siti Rahma")]
+    pub verifier_name: String,
+
+    /// Pendaftaran yang dibagikan dan masih menunggu
+    #[schema(example = 12)]
+    pub assigned: i64,
+
+    /// Pendaftaran yang sedang dikerjakan
+    #[schema(example = 1)]
+    pub claimed: i64,
+
+    /// Pendaftaran yang sudah diputuskan
+    #[schema(example = 64)]
+    pub completed: i64,
+
+    /// Klaim yang habis waktu sebelum diputuskan
+    #[schema(example = 3)]
+    pub expired: i64,
+
+    /// Rata-rata lama pengerjaan dalam menit
+    #[schema(example = 6.5)]
+    pub average_handling_minutes: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deal_round_robin() {
+        assert_eq!(
+            deal_round_robin(&[10, 11, 12, 13, 14], &[2, 3]),
+            vec![(10, 2), (11, 3), (12, 2), (13, 3), (14, 2)]
+        );
+        assert_eq!(deal_round_robin(&[10, 11], &[2]), vec![(10, 2), (11, 2)]);
+        assert!(deal_round_robin(&[10], &[]).is_empty());
+        assert!(deal_round_robin(&[], &[2, 3]).is_empty());
+    }
+
+    #[test]
+    fn test_check_required_documents() {
+        let document = |id: i32, document_type: &str, status: &str, created_at: &str| Document {
+            id,
+            registration_id: 5,
+            document_type: document_type.to_string(),
+            file_url: format!("/uploads/{}.pdf", id),
+            file_name: format!("{}.pdf", id),
+            file_size: 1024,
+            mime_type: "application/pdf".to_string(),
+            verification_status: status.to_string(),
+            verification_notes: None,
+            verified_by: None,
+            verified_at: None,
+            processing_status: "processed".to_string(),
+            processing_error: None,
+            processed_at: None,
+            original_file_size: None,
+            page_count: None,
+            processing_metadata: None,
+            scan_status: "clean".to_string(),
+            scan_signature: None,
+            scan_error: None,
+            scanned_at: None,
+            content_hash: None,
+            perceptual_hash: None,
+            processing_attempts: 0,
+            next_attempt_at: None,
+            created_at: created_at.parse().unwrap(),
+            updated_at: created_at.parse().unwrap(),
+        };
+        let required = vec![
+            "kartu_keluarga".to_string(),
+            "akta_kelahiran".to_string(),
+            "ijazah".to_string(),
+        ];
+
+        let documents = vec![
+            document(1, "kartu_keluarga", "approved", "2024-06-01T03:00:00Z"),
+            document(2, "akta_kelahiran", "pending", "2024-06-01T03:00:00Z"),
+            document(3, "foto", "rejected", "2024-06-01T03:00:00Z"),
+        ];
+        let check = check_required_documents(&required, &documents);
+        assert_eq!(check.missing, vec!["ijazah".to_string()]);
+        assert_eq!(check.pending, vec!["akta_kelahiran".to_string()]);
+        assert!(check.rejected.is_empty());
+        assert!(check.blocking_reason().unwrap().contains("missing"));
+
+        // A replacement uploaded during a revision supersedes the rejected one
+        let documents = vec![
+            document(1, "kartu_keluarga", "approved", "2024-06-01T03:00:00Z"),
+            document(2, "akta_kelahiran", "rejected", "2024-06-01T03:00:00Z"),
+            document(4, "akta_kelahiran", "approved", "2024-06-05T03:00:00Z"),
+            document(5, "ijazah", "approved", "2024-06-01T03:00:00Z"),
+        ];
+        let check = check_required_documents(&required, &documents);
+        assert_eq!(check, RequiredDocumentCheck::default());
+        assert!(check.blocking_reason().is_none());
+
+        let documents = vec![
+            document(1, "kartu_keluarga", "approved", "2024-06-01T03:00:00Z"),
+            document(2, "akta_kelahiran", "approved", "2024-06-01T03:00:00Z"),
+            document(5, "ijazah", "rejected", "2024-06-01T03:00:00Z"),
+        ];
+        let check = check_required_documents(&required, &documents);
+        assert_eq!(check.rejected, vec!["ijazah".to_string()]);
+        assert!(check.blocking_reason().unwrap().contains("rejected"));
+
+        // Paths without required documents never block
+        assert!(check_required_documents(&[], &documents)
+            .blocking_reason()
+            .is_none());
+    }
+
+    #[test]
+    fn test_dedup_ids() {
+        assert_eq!(dedup_ids(vec![5, 3, 5, 1, 3]), vec![5, 3, 1]);
+        assert!(dedup_ids(vec![]).is_empty());
+    }
+
+    #[test]
+    fn test_bulk_action_applies_to() {
+        assert!(BulkAction::Verify.applies_to(BulkTarget::Registration));
+        assert!(BulkAction::RequestRevision.applies_to(BulkTarget::Registration));
+        assert!(!BulkAction::Approve.applies_to(BulkTarget::Registration));
+        assert!(BulkAction::Approve.applies_to(BulkTarget::Document));
+        assert!(BulkAction::Reject.applies_to(BulkTarget::Document));
+        assert!(!BulkAction::Verify.applies_to(BulkTarget::Document));
+    }
+
+    #[test]
+    fn test_bulk_item_error() {
+        assert_eq!(
+            bulk_item_error(&AppError::Validation("Required documents are missing: ijazah".into())),
+            "Validation error: Required documents are missing: ijazah"
+        );
+        assert_eq!(
+            bulk_item_error(&AppError::FieldValidation(vec![
+                FieldError::new("documents[0].note", "Explain what needs to be corrected"),
+            ])),
+            "documents[0].note: Explain what needs to be corrected"
+        );
+    }
+}
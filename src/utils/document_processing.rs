@@ -0,0 +1,498 @@
+use std::io::Cursor;
+
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageReader};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::utils::error::{AppError, AppResult};
+
+/// Longest side of the thumbnail shown in verification lists
+pub const THUMBNAIL_SIZE: u32 = 320;
+/// Longest side of the preview shown when a verifier opens a document
+pub const PREVIEW_SIZE: u32 = 1280;
+
+const THUMBNAIL_QUALITY: u8 = 75;
+const PREVIEW_QUALITY: u8 = 82;
+/// Quality used when a photo has to be re-encoded to apply its orientation
+const REENCODE_QUALITY: u8 = 90;
+
+/// Largest side and JPEG quality tried in turn when shrinking a photo
+const RECOMPRESSION_STEPS: [(u32, u8); 4] = [(2560, 85), (2048, 80), (1600, 75), (1280, 70)];
+
+/// Image decoded with its camera orientation applied
+pub struct DecodedImage {
+    pub image: DynamicImage,
+    /// Whether the file asked for a rotation or flip, which stripping its
+    /// metadata would lose
+    pub reoriented: bool,
+}
+
+/// JPEG rendition of an image
+pub struct RenderedImage {
+    pub bytes: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Page count and first page of a PDF
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PdfInfo {
+    pub page_count: u32,
+    pub first_page: Option<PdfPage>,
+}
+
+/// Size of a PDF page in points as it renders, after its rotation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PdfPage {
+    pub width: f32,
+    pub height: f32,
+    pub rotation: i64,
+}
+
+impl PdfPage {
+    pub fn orientation(&self) -> &str {
+        if self.width > self.height {
+            "landscape"
+        } else {
+            "portrait"
+        }
+    }
+}
+
+pub fn is_image(mime_type: &str) -> bool {
+    matches!(mime_type, "image/jpeg" | "image/jpg" | "image/png")
+}
+
+pub fn decode_image(bytes: &[u8]) -> AppResult<DecodedImage> {
+    let mut decoder = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(image_error)?
+        .into_decoder()
+        .map_err(image_error)?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+
+    let mut image = DynamicImage::from_decoder(decoder).map_err(image_error)?;
+    image.apply_orientation(orientation);
+
+    Ok(DecodedImage {
+        image,
+        reoriented: orientation != Orientation::NoTransforms,
+    })
+}
+
+/// Encode the image as JPEG, scaled down to fit a square of `max_side`
+pub fn render_jpeg(image: &DynamicImage, max_side: u32, quality: u8) -> AppResult<RenderedImage> {
+    let scaled = if image.width() > max_side || image.height() > max_side {
+        image.resize(max_side, max_side, FilterType::Triangle)
+    } else {
+        image.clone()
+    };
+    let rgb = scaled.to_rgb8();
+
+    let mut bytes = Vec::new();
+    JpegEncoder::new_with_quality(&mut bytes, quality)
+        .encode_image(&rgb)
+        .map_err(image_error)?;
+
+    Ok(RenderedImage {
+        bytes,
+        width: rgb.width(),
+        height: rgb.height(),
+    })
+}
+
+pub fn render_thumbnail(image: &DynamicImage) -> AppResult<RenderedImage> {
+    render_jpeg(image, THUMBNAIL_SIZE, THUMBNAIL_QUALITY)
+}
+
+pub fn render_preview(image: &DynamicImage) -> AppResult<RenderedImage> {
+    render_jpeg(image, PREVIEW_SIZE, PREVIEW_QUALITY)
+}
+
+/// Original image without its metadata. Files whose orientation lives in
+/// the EXIF data are re-encoded upright; others are stripped losslessly.
+pub fn sanitize_image(bytes: &[u8], mime_type: &str, decoded: &DecodedImage) -> AppResult<Vec<u8>> {
+    if decoded.reoriented {
+        return render_jpeg(&decoded.image, u32::MAX, REENCODE_QUALITY).map(|r| r.bytes);
+    }
+
+    Ok(strip_metadata(bytes, mime_type))
+}
+
+/// Re-encode a photo as JPEG, shrinking it step by step until it fits
+/// `max_bytes`. None when even the smallest step is too large.
+pub fn recompress_photo(image: &DynamicImage, max_bytes: usize) -> AppResult<Option<RenderedImage>> {
+    for (max_side, quality) in RECOMPRESSION_STEPS {
+        let rendered = render_jpeg(image, max_side, quality)?;
+        if rendered.bytes.len() <= max_bytes {
+            return Ok(Some(rendered));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Remove EXIF, XMP and text metadata (GPS location, camera details) from a
+/// JPEG or PNG without re-encoding. Files that cannot be parsed are kept.
+pub fn strip_metadata(bytes: &[u8], mime_type: &str) -> Vec<u8> {
+    let stripped = match mime_type {
+        "image/jpeg" | "image/jpg" => strip_jpeg_metadata(bytes),
+        "image/png" => strip_png_metadata(bytes),
+        _ => None,
+    };
+
+    stripped.unwrap_or_else(|| bytes.to_vec())
+}
+
+/// Drop APP1 (EXIF/XMP) and APP13 (IPTC) segments before the image data
+fn strip_jpeg_metadata(bytes: &[u8]) -> Option<Vec<u8>> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(bytes.len());
+    output.extend_from_slice(&bytes[..2]);
+    let mut position = 2;
+
+    loop {
+        if bytes.get(position) != Some(&0xFF) {
+            return None;
+        }
+        let marker = *bytes.get(position + 1)?;
+
+        // Start of scan: the rest is image data
+        if marker == 0xDA {
+            output.extend_from_slice(&bytes[position..]);
+            return Some(output);
+        }
+
+        let length = u16::from_be_bytes([*bytes.get(position + 2)?, *bytes.get(position + 3)?]) as usize;
+        let end = position + 2 + length;
+        if length < 2 || end > bytes.len() {
+            return None;
+        }
+
+        if marker != 0xE1 && marker != 0xED {
+            output.extend_from_slice(&bytes[position..end]);
+        }
+        position = end;
+    }
+}
+
+/// Drop eXIf and text chunks
+fn strip_png_metadata(bytes: &[u8]) -> Option<Vec<u8>> {
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    if !bytes.starts_with(&SIGNATURE) {
+        return None;
+    }
+
+    let mut output = Vec::with_capacity(bytes.len());
+    output.extend_from_slice(&SIGNATURE);
+    let mut position = SIGNATURE.len();
+
+    while position < bytes.len() {
+        let length = u32::from_be_bytes(bytes.get(position..position + 4)?.try_into().ok()?) as usize;
+        let chunk_type = bytes.get(position + 4..position + 8)?;
+        // Length, type, data and CRC
+        let end = position + 12 + length;
+        if end > bytes.len() {
+            return None;
+        }
+
+        if !matches!(chunk_type, b"eXIf" | b"tEXt" | b"zTXt" | b"iTXt") {
+            output.extend_from_slice(&bytes[position..end]);
+        }
+        position = end;
+    }
+
+    Some(output)
+}
+
+pub fn pdf_info(bytes: &[u8]) -> AppResult<PdfInfo> {
+    let document = lopdf::Document::load_mem(bytes)
+        .map_err(|e| AppError::Validation(format!("Could not read PDF: {}", e)))?;
+
+    let pages = document.get_pages();
+    let first_page = pages
+        .values()
+        .next()
+        .and_then(|page_id| pdf_page(&document, *page_id));
+
+    Ok(PdfInfo {
+        page_count: pages.len() as u32,
+        first_page,
+    })
+}
+
+fn pdf_page(document: &lopdf::Document, page_id: lopdf::ObjectId) -> Option<PdfPage> {
+    let media_box = inherited_attribute(document, page_id, b"MediaBox")?;
+    let corners = media_box
+        .as_array()
+        .ok()?
+        .iter()
+        .map(|value| document.dereference(value).ok()?.1.as_float().ok())
+        .collect::<Option<Vec<f32>>>()?;
+    if corners.len() != 4 {
+        return None;
+    }
+
+    let width = (corners[2] - corners[0]).abs();
+    let height = (corners[3] - corners[1]).abs();
+    let rotation = inherited_attribute(document, page_id, b"Rotate")
+        .and_then(|r| r.as_i64().ok())
+        .unwrap_or(0)
+        .rem_euclid(360);
+
+    // Quarter turns swap the rendered width and height
+    let (width, height) = if rotation % 180 == 90 {
+        (height, width)
+    } else {
+        (width, height)
+    };
+
+    Some(PdfPage {
+        width,
+        height,
+        rotation,
+    })
+}
+
+/// Page attribute, looked up through the page tree when the page inherits it
+fn inherited_attribute<'a>(
+    document: &'a lopdf::Document,
+    page_id: lopdf::ObjectId,
+    key: &[u8],
+) -> Option<&'a lopdf::Object> {
+    let mut node_id = page_id;
+    // Bounded in case of a cyclic page tree
+    for _ in 0..32 {
+        let node = document.get_dictionary(node_id).ok()?;
+        if let Ok(value) = node.get(key) {
+            return document.dereference(value).ok().map(|(_, object)| object);
+        }
+        node_id = node.get(b"Parent").ok()?.as_reference().ok()?;
+    }
+
+    None
+}
+
+/// SHA-256 of a file as uploaded, in hex
+pub fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// 64-bit difference hash: each bit tells whether a pixel of a 9x8 grayscale
+/// thumbnail is brighter than its right neighbour. Rescaled or recompressed
+/// copies of an image hash within a few bits of each other.
+pub fn perceptual_hash(image: &DynamicImage) -> u64 {
+    let small = image.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+
+    hash
+}
+
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+fn image_error<E: std::fmt::Display>(e: E) -> AppError {
+    AppError::Validation(format!("Could not read image: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, Rgb, RgbImage};
+    use lopdf::{dictionary, Object};
+
+    fn noisy_image(width: u32, height: u32) -> DynamicImage {
+        // Noise compresses badly, like a photo of a paper document
+        let mut seed: u32 = 7;
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |_, _| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let [r, g, b, _] = seed.to_be_bytes();
+            Rgb([r, g, b])
+        }))
+    }
+
+    fn encode(image: &DynamicImage, format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, format).unwrap();
+        bytes.into_inner()
+    }
+
+    /// JPEG with an EXIF segment carrying the given orientation
+    fn jpeg_with_exif(image: &DynamicImage, orientation: u16) -> Vec<u8> {
+        let jpeg = encode(image, ImageFormat::Jpeg);
+        let mut exif = b"Exif\0\0MM\0\x2A\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01".to_vec();
+        exif.extend_from_slice(&orientation.to_be_bytes());
+        exif.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+
+        let mut bytes = jpeg[..2].to_vec();
+        bytes.extend_from_slice(&[0xFF, 0xE1]);
+        bytes.extend_from_slice(&((exif.len() + 2) as u16).to_be_bytes());
+        bytes.extend_from_slice(&exif);
+        bytes.extend_from_slice(&jpeg[2..]);
+        bytes
+    }
+
+    #[test]
+    fn test_strip_jpeg_metadata() {
+        let image = noisy_image(40, 20);
+        let bytes = jpeg_with_exif(&image, 1);
+        assert!(bytes.windows(4).any(|w| w == b"Exif"));
+
+        let stripped = strip_metadata(&bytes, "image/jpeg");
+        assert!(!stripped.windows(4).any(|w| w == b"Exif"));
+        assert_eq!(stripped, encode(&image, ImageFormat::Jpeg));
+
+        let decoded = decode_image(&stripped).unwrap();
+        assert_eq!((decoded.image.width(), decoded.image.height()), (40, 20));
+
+        // Unparseable files are kept as they are
+        assert_eq!(strip_metadata(b"not a jpeg", "image/jpeg"), b"not a jpeg".to_vec());
+    }
+
+    #[test]
+    fn test_strip_png_metadata() {
+        let png = encode(&noisy_image(8, 8), ImageFormat::Png);
+
+        // Insert a tEXt chunk after IHDR (8 byte signature + 25 byte chunk)
+        let mut bytes = png[..33].to_vec();
+        let text = b"Author\0Parent";
+        bytes.extend_from_slice(&(text.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(b"tEXt");
+        bytes.extend_from_slice(text);
+        bytes.extend_from_slice(&[0, 0, 0, 0]);
+        bytes.extend_from_slice(&png[33..]);
+
+        assert_eq!(strip_metadata(&bytes, "image/png"), png);
+    }
+
+    #[test]
+    fn test_decode_applies_orientation() {
+        let image = noisy_image(40, 20);
+
+        // Orientation 6: rotate 90 degrees clockwise
+        let decoded = decode_image(&jpeg_with_exif(&image, 6)).unwrap();
+        assert!(decoded.reoriented);
+        assert_eq!((decoded.image.width(), decoded.image.height()), (20, 40));
+
+        let bytes = jpeg_with_exif(&image, 6);
+        let sanitized = sanitize_image(&bytes, "image/jpeg", &decoded).unwrap();
+        assert!(!sanitized.windows(4).any(|w| w == b"Exif"));
+        let upright = decode_image(&sanitized).unwrap();
+        assert!(!upright.reoriented);
+        assert_eq!((upright.image.width(), upright.image.height()), (20, 40));
+    }
+
+    #[test]
+    fn test_render_thumbnail_and_preview() {
+        let image = noisy_image(2000, 1000);
+
+        let thumbnail = render_thumbnail(&image).unwrap();
+        assert_eq!((thumbnail.width, thumbnail.height), (THUMBNAIL_SIZE, THUMBNAIL_SIZE / 2));
+        let preview = render_preview(&image).unwrap();
+        assert_eq!((preview.width, preview.height), (PREVIEW_SIZE, PREVIEW_SIZE / 2));
+        assert!(thumbnail.bytes.len() < preview.bytes.len());
+
+        // Small images are never scaled up
+        let small = render_thumbnail(&noisy_image(100, 50)).unwrap();
+        assert_eq!((small.width, small.height), (100, 50));
+    }
+
+    #[test]
+    fn test_recompress_photo() {
+        let image = noisy_image(600, 400);
+        let high = render_jpeg(&image, u32::MAX, 85).unwrap().bytes.len();
+        let low = render_jpeg(&image, u32::MAX, 75).unwrap().bytes.len();
+        assert!(low < high);
+
+        // Quality drops step by step until the photo fits
+        let recompressed = recompress_photo(&image, low).unwrap().unwrap();
+        assert_eq!(recompressed.bytes.len(), low);
+        assert_eq!((recompressed.width, recompressed.height), (600, 400));
+
+        // Nothing fits a tiny limit
+        assert!(recompress_photo(&image, 1024).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(
+            content_hash(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_perceptual_hash() {
+        let scan = DynamicImage::ImageRgb8(RgbImage::from_fn(400, 300, |x, y| {
+            let v = 128.0 + 60.0 * (x as f32 / 37.0).sin() + 60.0 * (y as f32 / 23.0).cos();
+            Rgb([v as u8, (v / 2.0) as u8, 255 - v as u8])
+        }));
+        let hash = perceptual_hash(&scan);
+
+        // A smaller, recompressed copy of the same scan
+        let copy = render_jpeg(&scan, 200, 60).unwrap();
+        let copy = decode_image(&copy.bytes).unwrap().image;
+        assert!(hamming_distance(hash, perceptual_hash(&copy)) <= 6);
+
+        // A different document
+        let other = perceptual_hash(&scan.fliph());
+        assert!(hamming_distance(hash, other) > 10);
+    }
+
+    #[test]
+    fn test_pdf_info() {
+        let mut document = lopdf::Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let first = document.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+            "Rotate" => 90,
+        });
+        let second = document.add_object(dictionary! {
+            "Type" => "Page",
+            "Parent" => pages_id,
+        });
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Kids" => vec![first.into(), second.into()],
+                "Count" => 2,
+                // A4 portrait, inherited by both pages
+                "MediaBox" => vec![0.into(), 0.into(), 595.into(), 842.into()],
+            }),
+        );
+        let catalog_id = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+        });
+        document.trailer.set("Root", catalog_id);
+        let mut bytes = Vec::new();
+        document.save_to(&mut bytes).unwrap();
+
+        let info = pdf_info(&bytes).unwrap();
+        assert_eq!(info.page_count, 2);
+        let first_page = info.first_page.unwrap();
+        assert_eq!((first_page.width, first_page.height), (842.0, 595.0));
+        assert_eq!(first_page.rotation, 90);
+        assert_eq!(first_page.orientation(), "landscape");
+
+        assert!(pdf_info(b"%PDF-1.5 broken").is_err());
+    }
+}
@@ -3,9 +3,26 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Serialize;
 use serde_json::json;
 use thiserror::Error;
 
+/// Validation problem attached to a single request field
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Database error: {0}")]
@@ -14,6 +31,9 @@ pub enum AppError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    #[error("Validation error: {} invalid field(s)", .0.len())]
+    FieldValidation(Vec<FieldError>),
+
     #[error("Authentication error: {0}")]
     Authentication(String),
 
@@ -44,6 +64,14 @@ impl IntoResponse for AppError {
                 (StatusCode::INTERNAL_SERVER_ERROR, detail)
             }
             AppError::Validation(ref msg) => (StatusCode::BAD_REQUEST, msg.to_string()),
+            AppError::FieldValidation(ref fields) => {
+                let body = Json(json!({
+                    "error": "Validation failed",
+                    "fields": fields,
+                }));
+
+                return (StatusCode::BAD_REQUEST, body).into_response();
+            }
             AppError::Authentication(ref msg) => (StatusCode::UNAUTHORIZED, msg.to_string()),
             AppError::Authorization(ref msg) => (StatusCode::FORBIDDEN, msg.to_string()),
             AppError::Forbidden(ref msg) => (StatusCode::FORBIDDEN, msg.to_string()),
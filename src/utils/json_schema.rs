@@ -0,0 +1,288 @@
+//! Minimal JSON Schema subset used to describe `path_data` of a registration path.
+//!
+//! Supported keywords: an object with `properties`, `required` and
+//! `additionalProperties`; per property `type` (string/number/integer/boolean),
+//! `minimum`, `maximum`, `minLength`, `maxLength`, `enum`, plus the
+//! informational `title` and `description` used by the frontend form.
+
+use serde_json::{Map, Value};
+
+use crate::utils::error::FieldError;
+
+const PROPERTY_TYPES: &[&str] = &["string", "number", "integer", "boolean"];
+
+/// Check that a schema only uses the supported keywords
+pub fn check_schema(schema: &Value) -> Result<(), String> {
+    let schema = schema
+        .as_object()
+        .ok_or_else(|| "Schema must be a JSON object".to_string())?;
+
+    if let Some(schema_type) = schema.get("type") {
+        if schema_type != "object" {
+            return Err("Schema type must be 'object'".to_string());
+        }
+    }
+
+    let empty = Map::new();
+    let properties = match schema.get("properties") {
+        Some(Value::Object(properties)) => properties,
+        Some(_) => return Err("Schema 'properties' must be an object".to_string()),
+        None => &empty,
+    };
+
+    for (key, property) in properties {
+        check_property(key, property)?;
+    }
+
+    if let Some(required) = schema.get("required") {
+        let required = required
+            .as_array()
+            .ok_or_else(|| "Schema 'required' must be an array".to_string())?;
+
+        for key in required {
+            let key = key
+                .as_str()
+                .ok_or_else(|| "Schema 'required' must only contain strings".to_string())?;
+
+            if !properties.contains_key(key) {
+                return Err(format!("Required key '{}' is not defined in properties", key));
+            }
+        }
+    }
+
+    if let Some(additional) = schema.get("additionalProperties") {
+        if !additional.is_boolean() {
+            return Err("Schema 'additionalProperties' must be a boolean".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+fn check_property(key: &str, property: &Value) -> Result<(), String> {
+    let property = property
+        .as_object()
+        .ok_or_else(|| format!("Property '{}' must be an object", key))?;
+
+    let property_type = property
+        .get("type")
+        .and_then(|t| t.as_str())
+        .filter(|t| PROPERTY_TYPES.contains(t))
+        .ok_or_else(|| {
+            format!(
+                "Property '{}' must have a type of string, number, integer or boolean",
+                key
+            )
+        })?;
+
+    for bound in ["minimum", "maximum"] {
+        if let Some(value) = property.get(bound) {
+            if !value.is_number() || !matches!(property_type, "number" | "integer") {
+                return Err(format!(
+                    "Property '{}' {} must be a number on a numeric property",
+                    key, bound
+                ));
+            }
+        }
+    }
+
+    for bound in ["minLength", "maxLength"] {
+        if let Some(value) = property.get(bound) {
+            if !value.is_u64() || property_type != "string" {
+                return Err(format!(
+                    "Property '{}' {} must be a non-negative integer on a string property",
+                    key, bound
+                ));
+            }
+        }
+    }
+
+    if let Some(options) = property.get("enum") {
+        let options = options
+            .as_array()
+            .filter(|options| !options.is_empty())
+            .ok_or_else(|| format!("Property '{}' enum must be a non-empty array", key))?;
+
+        if options.iter().any(|option| !matches_type(property_type, option)) {
+            return Err(format!(
+                "Property '{}' enum values must be of type {}",
+                key, property_type
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn matches_type(property_type: &str, value: &Value) -> bool {
+    match property_type {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().map(|v| v.fract() == 0.0).unwrap_or(false)
+        }
+        "boolean" => value.is_boolean(),
+        _ => false,
+    }
+}
+
+/// Validate `value` against a schema accepted by [`check_schema`]. Errors are
+/// reported per key as `<prefix>.<key>`.
+pub fn validate(schema: &Value, value: &Value, prefix: &str) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    let Some(data) = value.as_object() else {
+        errors.push(FieldError::new(prefix, "Must be a JSON object"));
+        return errors;
+    };
+
+    let empty = Map::new();
+    let properties = schema
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .unwrap_or(&empty);
+
+    let field = |key: &str| format!("{}.{}", prefix, key);
+
+    if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+        for key in required.iter().filter_map(|k| k.as_str()) {
+            if data.get(key).map(|v| v.is_null()).unwrap_or(true) {
+                errors.push(FieldError::new(&field(key), "Is required"));
+            }
+        }
+    }
+
+    for (key, property) in properties {
+        let Some(value) = data.get(key).filter(|v| !v.is_null()) else {
+            continue;
+        };
+
+        if let Some(message) = validate_property(property, value) {
+            errors.push(FieldError::new(&field(key), message));
+        }
+    }
+
+    if schema.get("additionalProperties") == Some(&Value::Bool(false)) {
+        for key in data.keys().filter(|key| !properties.contains_key(*key)) {
+            errors.push(FieldError::new(&field(key), "Is not an allowed field"));
+        }
+    }
+
+    errors
+}
+
+fn validate_property(property: &Value, value: &Value) -> Option<String> {
+    let property_type = property.get("type").and_then(|t| t.as_str()).unwrap_or("string");
+
+    if !matches_type(property_type, value) {
+        return Some(format!("Must be of type {}", property_type));
+    }
+
+    if let Some(options) = property.get("enum").and_then(|e| e.as_array()) {
+        if !options.contains(value) {
+            let allowed: Vec<String> = options.iter().map(|o| o.to_string()).collect();
+            return Some(format!("Must be one of {}", allowed.join(", ")));
+        }
+    }
+
+    if let Some(number) = value.as_f64() {
+        if let Some(minimum) = property.get("minimum").and_then(|m| m.as_f64()) {
+            if number < minimum {
+                return Some(format!("Must be at least {}", minimum));
+            }
+        }
+
+        if let Some(maximum) = property.get("maximum").and_then(|m| m.as_f64()) {
+            if number > maximum {
+                return Some(format!("Must be at most {}", maximum));
+            }
+        }
+    }
+
+    if let Some(text) = value.as_str() {
+        let length = text.chars().count() as u64;
+
+        if let Some(min_length) = property.get("minLength").and_then(|m| m.as_u64()) {
+            if length < min_length {
+                return Some(format!("Must be at least {} characters", min_length));
+            }
+        }
+
+        if let Some(max_length) = property.get("maxLength").and_then(|m| m.as_u64()) {
+            if length > max_length {
+                return Some(format!("Must be at most {} characters", max_length));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["rapor_average"],
+            "properties": {
+                "rapor_average": {"type": "number", "minimum": 0, "maximum": 100},
+                "semesters": {"type": "integer", "minimum": 1},
+                "track": {"type": "string", "enum": ["ipa", "ips"]},
+                "has_kip": {"type": "boolean"}
+            },
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn test_check_schema_rejects_unsupported_definitions() {
+        assert!(check_schema(&schema()).is_ok());
+        assert!(check_schema(&json!([])).is_err());
+        assert!(check_schema(&json!({"properties": {"a": {"type": "array"}}})).is_err());
+        assert!(check_schema(&json!({"properties": {}, "required": ["a"]})).is_err());
+        assert!(check_schema(&json!({"properties": {"a": {"type": "string", "minimum": 1}}})).is_err());
+        assert!(check_schema(&json!({"properties": {"a": {"type": "integer", "enum": ["x"]}}})).is_err());
+    }
+
+    #[test]
+    fn test_validate_accepts_matching_data() {
+        let data = json!({"rapor_average": 87.5, "semesters": 5, "track": "ipa", "has_kip": false});
+
+        assert!(validate(&schema(), &data, "path_data").is_empty());
+    }
+
+    #[test]
+    fn test_validate_reports_field_errors() {
+        let data = json!({
+            "semesters": 2.5,
+            "track": "bahasa",
+            "has_kip": "yes",
+            "unknown": 1
+        });
+
+        let mut fields: Vec<String> = validate(&schema(), &data, "path_data")
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        fields.sort();
+
+        assert_eq!(
+            fields,
+            vec![
+                "path_data.has_kip",
+                "path_data.rapor_average",
+                "path_data.semesters",
+                "path_data.track",
+                "path_data.unknown",
+            ]
+        );
+
+        let out_of_range = validate(&schema(), &json!({"rapor_average": 101}), "path_data");
+        assert_eq!(out_of_range[0].message, "Must be at most 100");
+    }
+}
@@ -10,6 +10,8 @@ pub struct Claims {
     pub email: String,
     pub role: String,
     pub school_id: Option<i32>,
+    #[serde(default)]
+    pub region_id: Option<i32>,
     pub exp: i64,           // expiration timestamp
     pub iat: i64,           // issued at timestamp
     pub token_type: String, // "access" or "refresh"
@@ -20,6 +22,7 @@ pub fn generate_token(
     email: String,
     role: String,
     school_id: Option<i32>,
+    region_id: Option<i32>,
     secret: &str,
     expiration_hours: i64,
 ) -> AppResult<String> {
@@ -28,6 +31,7 @@ pub fn generate_token(
         email,
         role,
         school_id,
+        region_id,
         secret,
         expiration_hours,
         "access",
@@ -39,6 +43,7 @@ pub fn generate_token_with_type(
     email: String,
     role: String,
     school_id: Option<i32>,
+    region_id: Option<i32>,
     secret: &str,
     expiration_hours: i64,
     token_type: &str,
@@ -51,6 +56,7 @@ pub fn generate_token_with_type(
         email,
         role,
         school_id,
+        region_id,
         exp: exp.timestamp(),
         iat: now.timestamp(),
         token_type: token_type.to_string(),
@@ -69,6 +75,7 @@ pub fn generate_refresh_token(
     email: String,
     role: String,
     school_id: Option<i32>,
+    region_id: Option<i32>,
     secret: &str,
 ) -> AppResult<String> {
     // Refresh token expires in 7 days
@@ -77,6 +84,7 @@ pub fn generate_refresh_token(
         email,
         role,
         school_id,
+        region_id,
         secret,
         24 * 7, // 7 days
         "refresh",
@@ -1,4 +1,9 @@
+pub mod document_processing;
 pub mod error;
+pub mod json_schema;
 pub mod jwt;
 pub mod password;
+pub mod scanner;
+pub mod storage;
+pub mod timezone;
 pub mod validation;
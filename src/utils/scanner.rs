@@ -0,0 +1,195 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::config::Config;
+use crate::utils::error::{AppError, AppResult};
+
+/// Size of the chunks streamed to clamd
+const CLAMD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Longest reply read from clamd
+const CLAMD_MAX_REPLY: usize = 4096;
+
+/// Outcome of scanning a file
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanVerdict {
+    Clean,
+    Infected { signature: String },
+}
+
+/// Malware scanner for uploaded documents
+#[async_trait]
+pub trait Scanner: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn scan(&self, bytes: &[u8]) -> AppResult<ScanVerdict>;
+}
+
+/// Scanner configured for this deployment, None when scanning is disabled
+pub fn scanner_from_config(config: &Config) -> Option<Arc<dyn Scanner>> {
+    match config.malware_scanner.as_str() {
+        "clamd" => Some(Arc::new(ClamdScanner::new(
+            config.clamd_address.clone(),
+            Duration::from_secs(config.clamd_timeout_secs),
+        ))),
+        "none" => None,
+        // Config::from_env refuses any other value
+        other => {
+            tracing::error!("Unknown malware scanner '{}', scanning is disabled", other);
+            None
+        }
+    }
+}
+
+/// ClamAV daemon reached over TCP with the INSTREAM command
+pub struct ClamdScanner {
+    address: String,
+    timeout: Duration,
+}
+
+impl ClamdScanner {
+    pub fn new(address: String, timeout: Duration) -> Self {
+        Self { address, timeout }
+    }
+
+    async fn instream(&self, bytes: &[u8]) -> AppResult<String> {
+        let mut stream = TcpStream::connect(&self.address)
+            .await
+            .map_err(clamd_error)?;
+
+        stream.write_all(b"zINSTREAM\0").await.map_err(clamd_error)?;
+        for chunk in bytes.chunks(CLAMD_CHUNK_SIZE) {
+            stream
+                .write_all(&(chunk.len() as u32).to_be_bytes())
+                .await
+                .map_err(clamd_error)?;
+            stream.write_all(chunk).await.map_err(clamd_error)?;
+        }
+        stream.write_all(&0u32.to_be_bytes()).await.map_err(clamd_error)?;
+        stream.flush().await.map_err(clamd_error)?;
+
+        // The reply ends with a NUL byte, or the daemon closes the connection
+        let mut reply = Vec::new();
+        let mut buffer = [0u8; 512];
+        loop {
+            let read = stream.read(&mut buffer).await.map_err(clamd_error)?;
+            if read == 0 {
+                break;
+            }
+            reply.extend_from_slice(&buffer[..read]);
+            if reply.contains(&0) || reply.len() > CLAMD_MAX_REPLY {
+                break;
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&reply).into_owned())
+    }
+}
+
+#[async_trait]
+impl Scanner for ClamdScanner {
+    fn name(&self) -> &str {
+        "clamd"
+    }
+
+    async fn scan(&self, bytes: &[u8]) -> AppResult<ScanVerdict> {
+        let reply = tokio::time::timeout(self.timeout, self.instream(bytes))
+            .await
+            .map_err(|_| AppError::Internal("clamd did not answer in time".to_string()))??;
+
+        parse_clamd_reply(&reply)
+    }
+}
+
+/// Verdict of a clamd reply such as `stream: OK` or
+/// `stream: Win.Test.EICAR_HDB-1 FOUND`
+pub fn parse_clamd_reply(reply: &str) -> AppResult<ScanVerdict> {
+    let reply = reply.split('\0').next().unwrap_or_default().trim();
+    let result = reply.strip_prefix("stream:").unwrap_or(reply).trim();
+
+    if result == "OK" {
+        return Ok(ScanVerdict::Clean);
+    }
+
+    if let Some(signature) = result.strip_suffix(" FOUND") {
+        return Ok(ScanVerdict::Infected {
+            signature: signature.trim().to_string(),
+        });
+    }
+
+    Err(AppError::Internal(format!("clamd could not scan the file: {}", reply)))
+}
+
+fn clamd_error(e: std::io::Error) -> AppError {
+    AppError::Internal(format!("clamd request failed: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_parse_clamd_reply() {
+        assert_eq!(parse_clamd_reply("stream: OK\0").unwrap(), ScanVerdict::Clean);
+        assert_eq!(
+            parse_clamd_reply("stream: Win.Test.EICAR_HDB-1 FOUND\0").unwrap(),
+            ScanVerdict::Infected {
+                signature: "Win.Test.EICAR_HDB-1".to_string()
+            }
+        );
+        assert!(parse_clamd_reply("INSTREAM size limit exceeded. ERROR\0").is_err());
+        assert!(parse_clamd_reply("").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_clamd_instream() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        // Minimal clamd: read the command and chunks, flag files containing "EICAR"
+        let daemon = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut command = [0u8; 10];
+            socket.read_exact(&mut command).await.unwrap();
+            assert_eq!(&command, b"zINSTREAM\0");
+
+            let mut received = Vec::new();
+            loop {
+                let length = socket.read_u32().await.unwrap() as usize;
+                if length == 0 {
+                    break;
+                }
+                let mut chunk = vec![0u8; length];
+                socket.read_exact(&mut chunk).await.unwrap();
+                received.extend_from_slice(&chunk);
+            }
+
+            let infected = received.windows(5).any(|w| w == b"EICAR");
+            let reply: &[u8] = if infected {
+                b"stream: Eicar-Signature FOUND\0"
+            } else {
+                b"stream: OK\0"
+            };
+            socket.write_all(reply).await.unwrap();
+            received.len()
+        });
+
+        let scanner = ClamdScanner::new(address, Duration::from_secs(5));
+        let mut file = vec![b'a'; CLAMD_CHUNK_SIZE + 10];
+        file.extend_from_slice(b"EICAR");
+
+        let verdict = scanner.scan(&file).await.unwrap();
+        assert_eq!(
+            verdict,
+            ScanVerdict::Infected {
+                signature: "Eicar-Signature".to_string()
+            }
+        );
+        assert_eq!(daemon.await.unwrap(), file.len());
+    }
+}
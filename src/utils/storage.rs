@@ -0,0 +1,203 @@
+use reqwest::header::CONTENT_TYPE;
+
+use crate::config::Config;
+use crate::utils::error::{AppError, AppResult};
+
+/// Largest file the document pipeline downloads
+pub const MAX_DOWNLOAD_BYTES: usize = 20 * 1024 * 1024;
+
+/// Client for the Supabase Storage bucket holding uploaded documents
+#[derive(Clone)]
+pub struct Storage {
+    client: reqwest::Client,
+    base_url: String,
+    service_key: String,
+    bucket: String,
+}
+
+impl Storage {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: config.supabase_url.trim_end_matches('/').to_string(),
+            service_key: config.supabase_service_key.clone(),
+            bucket: config.document_bucket.clone(),
+        }
+    }
+
+    /// Path of a file inside the document bucket, None for files stored elsewhere
+    pub fn object_path(&self, url: &str) -> Option<String> {
+        object_path(&self.base_url, &self.bucket, url)
+    }
+
+    pub fn public_url(&self, path: &str) -> String {
+        format!(
+            "{}/storage/v1/object/public/{}/{}",
+            self.base_url, self.bucket, path
+        )
+    }
+
+    /// Path of a file inside the document bucket; files stored anywhere else
+    /// are refused so the server never requests arbitrary URLs
+    pub fn bucket_path(&self, url: &str) -> AppResult<String> {
+        self.object_path(url).ok_or_else(|| {
+            AppError::Validation("File must be stored in the document bucket".to_string())
+        })
+    }
+
+    /// Download a file from the document bucket
+    pub async fn fetch(&self, url: &str) -> AppResult<Vec<u8>> {
+        let path = self.bucket_path(url)?;
+
+        let response = self
+            .client
+            .get(self.object_url(&path))
+            .bearer_auth(&self.service_key)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(storage_error)?;
+        if response
+            .content_length()
+            .is_some_and(|length| length as usize > MAX_DOWNLOAD_BYTES)
+        {
+            return Err(AppError::Validation("File is too large to process".to_string()));
+        }
+
+        let bytes = response.bytes().await.map_err(storage_error)?;
+        if bytes.len() > MAX_DOWNLOAD_BYTES {
+            return Err(AppError::Validation("File is too large to process".to_string()));
+        }
+
+        Ok(bytes.to_vec())
+    }
+
+    /// Store a file in the document bucket, replacing any file at the path,
+    /// and return its URL
+    pub async fn put(&self, path: &str, bytes: Vec<u8>, content_type: &str) -> AppResult<String> {
+        self.client
+            .post(self.object_url(path))
+            .bearer_auth(&self.service_key)
+            .header("x-upsert", "true")
+            .header(CONTENT_TYPE, content_type)
+            .body(bytes)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(storage_error)?;
+
+        Ok(self.public_url(path))
+    }
+
+    pub async fn delete(&self, path: &str) -> AppResult<()> {
+        self.client
+            .delete(self.object_url(path))
+            .bearer_auth(&self.service_key)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(storage_error)?;
+
+        Ok(())
+    }
+
+    fn object_url(&self, path: &str) -> String {
+        format!("{}/storage/v1/object/{}/{}", self.base_url, self.bucket, path)
+    }
+}
+
+fn storage_error(e: reqwest::Error) -> AppError {
+    AppError::Internal(format!("Storage request failed: {}", e))
+}
+
+/// Object path of a Supabase Storage URL (public, authenticated or signed)
+/// within the bucket
+fn object_path(base_url: &str, bucket: &str, url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix(base_url)?
+        .strip_prefix("/storage/v1/object/")?;
+    let rest = ["public/", "authenticated/", "sign/"]
+        .iter()
+        .find_map(|access| rest.strip_prefix(access))
+        .unwrap_or(rest);
+    let path = rest.strip_prefix(bucket)?.strip_prefix('/')?;
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    // Dot segments would let the request leave the bucket
+    let escapes = path.split('/').any(|segment| segment == "..");
+    (!path.is_empty() && !escapes).then(|| path.to_string())
+}
+
+/// Path of a file derived from another, next to it:
+/// `docs/12/kk.png` with `thumbnail` and `jpg` gives `docs/12/kk.thumbnail.jpg`
+pub fn sibling_path(path: &str, suffix: &str, extension: &str) -> String {
+    let (directory, file_name) = match path.rsplit_once('/') {
+        Some((directory, file_name)) => (Some(directory), file_name),
+        None => (None, path),
+    };
+    let stem = match file_name.rsplit_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => file_name,
+    };
+
+    match directory {
+        Some(directory) => format!("{}/{}.{}.{}", directory, stem, suffix, extension),
+        None => format!("{}.{}.{}", stem, suffix, extension),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_path() {
+        let base = "https://abc.supabase.co";
+        assert_eq!(
+            object_path(
+                base,
+                "documents",
+                "https://abc.supabase.co/storage/v1/object/public/documents/regs/5/kk.jpg"
+            ),
+            Some("regs/5/kk.jpg".to_string())
+        );
+        assert_eq!(
+            object_path(
+                base,
+                "documents",
+                "https://abc.supabase.co/storage/v1/object/sign/documents/regs/5/kk.pdf?token=x"
+            ),
+            Some("regs/5/kk.pdf".to_string())
+        );
+        assert_eq!(
+            object_path(base, "documents", "https://abc.supabase.co/storage/v1/object/documents/kk.png"),
+            Some("kk.png".to_string())
+        );
+        assert_eq!(
+            object_path(base, "documents", "https://abc.supabase.co/storage/v1/object/public/avatars/kk.jpg"),
+            None
+        );
+        assert_eq!(
+            object_path(base, "documents", "https://storage.example.com/documents/kk.jpg"),
+            None
+        );
+        assert_eq!(
+            object_path(
+                base,
+                "documents",
+                "https://abc.supabase.co/storage/v1/object/public/documents/../avatars/kk.jpg"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sibling_path() {
+        assert_eq!(
+            sibling_path("regs/5/kk.png", "thumbnail", "jpg"),
+            "regs/5/kk.thumbnail.jpg"
+        );
+        assert_eq!(sibling_path("kk", "preview", "jpg"), "kk.preview.jpg");
+        assert_eq!(sibling_path("regs/.hidden", "preview", "jpg"), "regs/.hidden.preview.jpg");
+    }
+}
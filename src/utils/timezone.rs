@@ -0,0 +1,113 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::utils::error::{AppError, AppResult};
+
+/// Timezone of schools that did not configure one (WIB)
+pub const DEFAULT_TIMEZONE: &str = "Asia/Jakarta";
+
+/// Parse an IANA timezone name given by an admin
+pub fn parse_timezone(name: &str) -> AppResult<Tz> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| AppError::Validation(format!("Unknown timezone: {}", name)))
+}
+
+/// Timezone stored for a school; values are validated when saved, so an
+/// unknown name falls back to the default
+pub fn school_timezone(name: &str) -> Tz {
+    name.parse::<Tz>()
+        .unwrap_or_else(|_| DEFAULT_TIMEZONE.parse().expect("default timezone"))
+}
+
+/// The instant a school-local date and time refer to. Times repeated by a
+/// clock change resolve to the first occurrence; times skipped by one are
+/// moved forward by the length of the gap.
+pub fn local_instant(date: NaiveDate, time: NaiveTime, tz: Tz) -> DateTime<Utc> {
+    let local = date.and_time(time);
+    if let Some(instant) = tz.from_local_datetime(&local).earliest() {
+        return instant.with_timezone(&Utc);
+    }
+
+    let before = tz.offset_from_utc_datetime(&(local - Duration::days(1))).fix();
+    let after = tz.offset_from_utc_datetime(&(local + Duration::days(1))).fix();
+    let gap = Duration::seconds((after.local_minus_utc() - before.local_minus_utc()) as i64);
+
+    tz.from_local_datetime(&(local + gap))
+        .earliest()
+        .map(|instant| instant.with_timezone(&Utc))
+        // The wall time read with the offset in force before the gap
+        .unwrap_or_else(|| {
+            Utc.from_utc_datetime(&(local - Duration::seconds(before.local_minus_utc() as i64)))
+        })
+}
+
+/// Calendar date of an instant at the school
+pub fn local_date(instant: DateTime<Utc>, tz: Tz) -> NaiveDate {
+    instant.with_timezone(&tz).date_naive()
+}
+
+/// Instant formatted for display at the school, e.g. "28-02-2025 23:59 WITA"
+pub fn format_local(instant: DateTime<Utc>, tz: Tz) -> String {
+    instant
+        .with_timezone(&tz)
+        .format("%d-%m-%Y %H:%M %Z")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_deadline_instants() {
+        let date = NaiveDate::from_ymd_opt(2025, 2, 28).unwrap();
+        let time = NaiveTime::from_hms_opt(23, 59, 59).unwrap();
+
+        let wib = local_instant(date, time, parse_timezone("Asia/Jakarta").unwrap());
+        let wita = local_instant(date, time, parse_timezone("Asia/Makassar").unwrap());
+        let wit = local_instant(date, time, parse_timezone("Asia/Jayapura").unwrap());
+
+        assert_eq!(wib.to_rfc3339(), "2025-02-28T16:59:59+00:00");
+        assert_eq!(wib - wita, chrono::Duration::hours(1));
+        assert_eq!(wib - wit, chrono::Duration::hours(2));
+    }
+
+    #[test]
+    fn test_local_instants_around_clock_changes() {
+        let berlin = parse_timezone("Europe/Berlin").unwrap();
+        let at = |date: (i32, u32, u32), time: (u32, u32)| {
+            local_instant(
+                NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap(),
+                NaiveTime::from_hms_opt(time.0, time.1, 0).unwrap(),
+                berlin,
+            )
+            .to_rfc3339()
+        };
+
+        // 02:00-03:00 is skipped when summer time starts; 02:30 means 03:30 CEST
+        assert_eq!(at((2025, 3, 30), (2, 30)), "2025-03-30T01:30:00+00:00");
+        assert_eq!(at((2025, 3, 30), (3, 30)), "2025-03-30T01:30:00+00:00");
+        assert_eq!(at((2025, 3, 30), (1, 59)), "2025-03-30T00:59:00+00:00");
+
+        // 02:00-03:00 happens twice when it ends; the first one counts
+        assert_eq!(at((2025, 10, 26), (2, 30)), "2025-10-26T00:30:00+00:00");
+    }
+
+    #[test]
+    fn test_local_date_and_display() {
+        let tz = parse_timezone("Asia/Makassar").unwrap();
+        let instant: DateTime<Utc> = "2025-02-28T16:30:00Z".parse().unwrap();
+
+        assert_eq!(
+            local_date(instant, tz),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()
+        );
+        assert_eq!(format_local(instant, tz), "01-03-2025 00:30 WITA");
+        assert!(parse_timezone("Asia/Bandung").is_err());
+        assert_eq!(
+            school_timezone("invalid"),
+            parse_timezone(DEFAULT_TIMEZONE).unwrap()
+        );
+    }
+}
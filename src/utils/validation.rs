@@ -1 +1,387 @@
-// Validation utilities - Coming soon
+use chrono::{Datelike, NaiveDate, Utc};
+
+use crate::models::age_rule::AgeRules;
+use crate::utils::error::FieldError;
+
+/// Kemendagri province codes (first two digits of a NIK)
+const PROVINCE_CODES: &[u32] = &[
+    11, 12, 13, 14, 15, 16, 17, 18, 19, 21, 31, 32, 33, 34, 35, 36, 51, 52, 53, 61, 62, 63, 64,
+    65, 71, 72, 73, 74, 75, 76, 81, 82, 91, 92, 93, 94, 95, 96,
+];
+
+/// Women have 40 added to the birth day encoded in their NIK
+const FEMALE_DAY_OFFSET: u32 = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gender {
+    Male,
+    Female,
+}
+
+impl Gender {
+    /// Accepts the registration form values (L/P, LAKI-LAKI/PEREMPUAN)
+    pub fn from_form(value: &str) -> Option<Self> {
+        match value.trim().to_uppercase().as_str() {
+            "L" | "LAKI-LAKI" => Some(Gender::Male),
+            "P" | "PEREMPUAN" => Some(Gender::Female),
+            _ => None,
+        }
+    }
+}
+
+/// Data encoded in a 16-digit NIK: PP KK CC DDMMYY SSSS
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedNik {
+    pub province_code: String,
+    pub regency_code: String,
+    pub district_code: String,
+    pub birth_day: u32,
+    pub birth_month: u32,
+    /// Last two digits of the birth year; the century is not encoded
+    pub birth_year: u32,
+    pub gender: Gender,
+    pub serial: String,
+}
+
+impl ParsedNik {
+    /// District-level region code (6 digits)
+    pub fn region_code(&self) -> String {
+        format!(
+            "{}{}{}",
+            self.province_code, self.regency_code, self.district_code
+        )
+    }
+
+    /// Birth date with the century resolved so it is not in the future
+    pub fn birth_date(&self) -> Option<NaiveDate> {
+        let current_year = Utc::now().year() as u32;
+        let mut year = current_year - current_year % 100 + self.birth_year;
+        if year > current_year {
+            year -= 100;
+        }
+
+        NaiveDate::from_ymd_opt(year as i32, self.birth_month, self.birth_day)
+    }
+}
+
+pub fn parse_nik(nik: &str) -> Result<ParsedNik, String> {
+    if nik.len() != 16 || !nik.chars().all(|c| c.is_ascii_digit()) {
+        return Err("NIK must be 16 digits".to_string());
+    }
+
+    let number = |range: std::ops::Range<usize>| nik[range].parse::<u32>().unwrap_or(0);
+
+    if !PROVINCE_CODES.contains(&number(0..2)) {
+        return Err(format!("NIK has an unknown province code '{}'", &nik[0..2]));
+    }
+
+    if number(2..4) == 0 {
+        return Err("NIK has an invalid regency code".to_string());
+    }
+
+    if number(4..6) == 0 {
+        return Err("NIK has an invalid district code".to_string());
+    }
+
+    let encoded_day = number(6..8);
+    let (birth_day, gender) = if encoded_day > FEMALE_DAY_OFFSET {
+        (encoded_day - FEMALE_DAY_OFFSET, Gender::Female)
+    } else {
+        (encoded_day, Gender::Male)
+    };
+
+    let parsed = ParsedNik {
+        province_code: nik[0..2].to_string(),
+        regency_code: nik[2..4].to_string(),
+        district_code: nik[4..6].to_string(),
+        birth_day,
+        birth_month: number(8..10),
+        birth_year: number(10..12),
+        gender,
+        serial: nik[12..16].to_string(),
+    };
+
+    if parsed.birth_date().is_none() {
+        return Err("NIK has an invalid encoded birth date".to_string());
+    }
+
+    if number(12..16) == 0 {
+        return Err("NIK has an invalid serial number".to_string());
+    }
+
+    Ok(parsed)
+}
+
+pub fn validate_nisn(nisn: &str) -> Result<(), String> {
+    if nisn.len() != 10 || !nisn.chars().all(|c| c.is_ascii_digit()) {
+        return Err("NISN must be 10 digits".to_string());
+    }
+
+    Ok(())
+}
+
+/// Identity fields of a registration that are checked together
+#[derive(Debug, Clone)]
+pub struct IdentityInput<'a> {
+    pub student_nisn: &'a str,
+    pub student_nik: Option<&'a str>,
+    pub student_birth_date: NaiveDate,
+    pub student_gender: &'a str,
+    pub student_region_code: Option<&'a str>,
+    pub parent_nik: &'a str,
+}
+
+/// Checks NISN/NIK formats and cross-checks the student NIK against the
+/// birth date, gender and address region entered on the form.
+pub fn validate_identity(input: &IdentityInput) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if let Err(message) = validate_nisn(input.student_nisn) {
+        errors.push(FieldError::new("student_nisn", message));
+    }
+
+    if let Err(message) = parse_nik(input.parent_nik) {
+        errors.push(FieldError::new("parent_nik", message));
+    }
+
+    let region_code = input
+        .student_region_code
+        .map(|code| code.chars().filter(|c| c.is_ascii_digit()).collect::<String>());
+
+    if let Some(ref code) = region_code {
+        if code.len() < 2 {
+            errors.push(FieldError::new(
+                "student_region_code",
+                "Region code must contain at least the 2-digit province code",
+            ));
+        }
+    }
+
+    let Some(student_nik) = input.student_nik else {
+        return errors;
+    };
+
+    let parsed = match parse_nik(student_nik) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            errors.push(FieldError::new("student_nik", message));
+            return errors;
+        }
+    };
+
+    if student_nik == input.parent_nik {
+        errors.push(FieldError::new(
+            "student_nik",
+            "Student NIK must differ from parent NIK",
+        ));
+    }
+
+    let birth_date = input.student_birth_date;
+    if parsed.birth_day != birth_date.day()
+        || parsed.birth_month != birth_date.month()
+        || parsed.birth_year != birth_date.year() as u32 % 100
+    {
+        errors.push(FieldError::new(
+            "student_birth_date",
+            format!(
+                "Birth date does not match the date encoded in student NIK ({:02}-{:02}-{:02})",
+                parsed.birth_day, parsed.birth_month, parsed.birth_year
+            ),
+        ));
+    }
+
+    if let Some(gender) = Gender::from_form(input.student_gender) {
+        if gender != parsed.gender {
+            errors.push(FieldError::new(
+                "student_gender",
+                "Gender does not match the gender encoded in student NIK",
+            ));
+        }
+    }
+
+    if let Some(code) = region_code.filter(|code| code.len() >= 2) {
+        let nik_region = parsed.region_code();
+        let length = code.len().min(nik_region.len());
+        if code[..length] != nik_region[..length] {
+            errors.push(FieldError::new(
+                "student_region_code",
+                format!(
+                    "Address region does not match the region encoded in student NIK ({})",
+                    &nik_region[..length]
+                ),
+            ));
+        }
+    }
+
+    errors
+}
+
+/// Checks the student's age on the reference date against the period's age rules
+pub fn validate_age(rules: &AgeRules, birth_date: NaiveDate) -> Vec<FieldError> {
+    let evaluation = rules.evaluate(birth_date);
+    let reference_date = evaluation.reference_date;
+
+    let Some(age) = evaluation.age else {
+        return vec![FieldError::new(
+            "student_birth_date",
+            format!("Birth date must be before the age reference date {}", reference_date),
+        )];
+    };
+
+    let mut errors = Vec::new();
+
+    if let Some(min_age) = rules.min_age.filter(|_| !evaluation.meets_minimum) {
+        errors.push(FieldError::new(
+            "student_birth_date",
+            format!(
+                "Student must be at least {} old on {} (age on that date: {})",
+                min_age, reference_date, age
+            ),
+        ));
+    }
+
+    if let Some(max_age) = rules.max_age.filter(|_| !evaluation.meets_maximum) {
+        errors.push(FieldError::new(
+            "student_birth_date",
+            format!(
+                "Student must be at most {} old on {} (age on that date: {})",
+                max_age, reference_date, age
+            ),
+        ));
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input<'a>(student_nik: Option<&'a str>) -> IdentityInput<'a> {
+        IdentityInput {
+            student_nisn: "0101234567",
+            student_nik,
+            student_birth_date: NaiveDate::from_ymd_opt(2010, 1, 15).unwrap(),
+            student_gender: "P",
+            student_region_code: Some("32.01"),
+            parent_nik: "3201011203800002",
+        }
+    }
+
+    #[test]
+    fn test_parse_nik_decodes_female_birth_date() {
+        let parsed = parse_nik("3201015501100001").unwrap();
+
+        assert_eq!(parsed.province_code, "32");
+        assert_eq!(parsed.regency_code, "01");
+        assert_eq!(parsed.district_code, "01");
+        assert_eq!(parsed.gender, Gender::Female);
+        assert_eq!(parsed.birth_date(), NaiveDate::from_ymd_opt(2010, 1, 15));
+        assert_eq!(parsed.serial, "0001");
+    }
+
+    #[test]
+    fn test_parse_nik_rejects_malformed_numbers() {
+        assert!(parse_nik("320101550110000").is_err());
+        assert!(parse_nik("32010155011000A1").is_err());
+        assert!(parse_nik("9901015501100001").is_err());
+        assert!(parse_nik("3201017501100001").is_err());
+        assert!(parse_nik("3201013002100001").is_err());
+        assert!(parse_nik("3201015501100000").is_err());
+    }
+
+    #[test]
+    fn test_validate_identity_accepts_consistent_data() {
+        assert!(validate_identity(&input(Some("3201015501100001"))).is_empty());
+        assert!(validate_identity(&input(None)).is_empty());
+    }
+
+    #[test]
+    fn test_validate_identity_reports_each_mismatch() {
+        // Male, born 16-01-2010, registered in another province
+        let errors = validate_identity(&input(Some("3301011601100001")));
+        let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+
+        assert_eq!(
+            fields,
+            vec!["student_birth_date", "student_gender", "student_region_code"]
+        );
+
+        let mut bad = input(None);
+        bad.student_nisn = "12345";
+        bad.parent_nik = "1234";
+        let fields: Vec<String> = validate_identity(&bad)
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+
+        assert_eq!(fields, vec!["student_nisn", "parent_nik"]);
+    }
+
+    #[test]
+    fn test_validate_age_applies_level_defaults() {
+        let mut sd = AgeRules::for_level("SD");
+        sd.reference_date = NaiveDate::from_ymd_opt(2024, 7, 1);
+
+        // 6 years exactly on the reference date
+        assert!(validate_age(&sd, NaiveDate::from_ymd_opt(2018, 7, 1).unwrap()).is_empty());
+
+        // One day short of 6 years
+        let errors = validate_age(&sd, NaiveDate::from_ymd_opt(2018, 7, 2).unwrap());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("5 years 11 months"));
+
+        let mut smp = AgeRules::for_level("SMP");
+        smp.reference_date = NaiveDate::from_ymd_opt(2024, 7, 1);
+
+        // Still 15 years old, turns 16 the next day
+        assert!(validate_age(&smp, NaiveDate::from_ymd_opt(2008, 7, 2).unwrap()).is_empty());
+        assert_eq!(
+            validate_age(&smp, NaiveDate::from_ymd_opt(2008, 7, 1).unwrap()).len(),
+            1
+        );
+
+        // Born after the reference date
+        assert_eq!(
+            validate_age(&smp, NaiveDate::from_ymd_opt(2024, 8, 1).unwrap()).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_age_rules_priority_and_score() {
+        let rules = AgeRules::from_config(&serde_json::json!({
+            "reference_date": "2024-07-01",
+            "min_age": {"years": 6},
+            "priority_age": {"years": 7},
+            "priority_bonus": 10,
+            "points_per_month": 0.5,
+            "max_age_points": 8
+        }))
+        .unwrap();
+
+        // 6 years 4 months: no priority, 4 months above the minimum
+        let young = rules.evaluate(NaiveDate::from_ymd_opt(2018, 3, 1).unwrap());
+        assert!(young.is_eligible());
+        assert!(!young.priority);
+        assert_eq!(young.score, 2.0);
+
+        // 7 years 10 months: priority, 22 months above the minimum capped at 8 points
+        let old = rules.evaluate(NaiveDate::from_ymd_opt(2016, 9, 1).unwrap());
+        assert!(old.priority);
+        assert_eq!(old.score, 18.0);
+
+        // Ineligible applicants get no age points
+        let too_young = rules.evaluate(NaiveDate::from_ymd_opt(2019, 1, 1).unwrap());
+        assert!(!too_young.is_eligible());
+        assert_eq!(too_young.score, 0.0);
+
+        assert!(AgeRules::from_config(&serde_json::json!({
+            "min_age": {"years": 7},
+            "max_age": {"years": 6}
+        }))
+        .is_err());
+        assert!(AgeRules::from_config(&serde_json::json!({"min_age": {"years": 6, "months": 12}})).is_err());
+        assert!(AgeRules::from_config(&serde_json::json!({"max_age": 15})).is_err());
+    }
+}
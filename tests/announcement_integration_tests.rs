@@ -73,7 +73,7 @@ async fn setup_complete_selection_flow(
                 "student_address": "Jl. Announcement Test",
                 "student_email": format!("announce{}@test.com", i),
                 "parent_name": "Parent Announce",
-                "parent_nik": format!("200000000000000{}", i),
+                "parent_nik": format!("320102150580{:04}", i + 1),
                 "parent_phone": format!("0820000000{:02}", i),
                 "path_data": {
                     "distance_km": (i as f64 * 0.5) + 1.0
@@ -89,7 +89,7 @@ async fn setup_complete_selection_flow(
             &format!("/api/v1/registrations/{}/documents", registration_id),
             json!({
                 "document_type": "birth_certificate",
-                "file_url": "https://example.com/birth_cert.pdf",
+                "file_url": ctx.document_url("birth_cert.pdf"),
                 "file_name": "birth_certificate.pdf",
                 "file_size": 102400,
                 "mime_type": "application/pdf"
@@ -10,11 +10,22 @@ fn to_naive_date(dt: chrono::DateTime<Utc>) -> chrono::NaiveDate {
     dt.date_naive()
 }
 
-// Helper function to setup period and path
+// Helper function to setup period and path with an open registration window
 async fn setup_period_and_path(ctx: &TestContext, school_admin_token: &str) -> (i32, i32) {
-    let start_date = to_naive_date(Utc::now() + Duration::days(30));
-    let end_date = to_naive_date(Utc::now() + Duration::days(120));
+    let start_date = to_naive_date(Utc::now() - Duration::days(1));
+    let end_date = to_naive_date(Utc::now() + Duration::days(90));
+
+    setup_period_with_window(ctx, school_admin_token, start_date, end_date, None).await
+}
 
+// Helper function to setup period and path with the given dates
+async fn setup_period_with_window(
+    ctx: &TestContext,
+    school_admin_token: &str,
+    start_date: chrono::NaiveDate,
+    end_date: chrono::NaiveDate,
+    registration_end: Option<chrono::NaiveDate>,
+) -> (i32, i32) {
     let (status, response) = ctx.post(
         "/api/v1/periods",
         json!({
@@ -22,6 +33,7 @@ async fn setup_period_and_path(ctx: &TestContext, school_admin_token: &str) -> (
             "level": "SMA",
             "start_date": start_date,
             "end_date": end_date,
+            "registration_end": registration_end,
             "paths": [
                 {
                     "path_type": "zonasi",
@@ -83,7 +95,7 @@ async fn test_registration_create_success() {
             "student_address": "Jl. Test No. 123",
             "student_email": "student@test.com",
             "parent_name": "Parent Test",
-            "parent_nik": "1234567890123456",
+            "parent_nik": "3201011505800001",
             "parent_phone": "081234567890",
             "path_data": {
                 "distance_km": 2.5
@@ -99,6 +111,137 @@ async fn test_registration_create_success() {
     ctx.cleanup_test_data().await;
 }
 
+#[tokio::test]
+async fn test_registration_create_invalid_parent_nik() {
+    let ctx = TestContext::new().await;
+    ctx.cleanup_test_data().await;
+
+    let super_admin = ctx.create_super_admin().await;
+    let school_id = ctx.create_test_school(&super_admin.access_token, "TEST308").await;
+    let school_admin = ctx.create_school_admin(school_id, "admin_nik@test.com").await;
+    let parent = ctx.create_parent(school_id, "parent_nik@test.com").await;
+
+    let (period_id, path_id) = setup_period_and_path(&ctx, &school_admin.access_token).await;
+
+    let birth_date = (Utc::now() - Duration::days(365 * 15)).date_naive();
+
+    // Unknown province code "99"
+    let (status, response) = ctx.post(
+        "/api/v1/registrations",
+        json!({
+            "period_id": period_id,
+            "path_id": path_id,
+            "student_nisn": "8888888888",
+            "student_name": "Invalid NIK Student",
+            "student_gender": "L",
+            "student_birth_place": "Jakarta",
+            "student_birth_date": birth_date,
+            "student_religion": "Islam",
+            "student_address": "Jl. Test No. 123",
+            "parent_name": "Parent Invalid",
+            "parent_nik": "9901011505800001",
+            "parent_phone": "081234567890",
+            "path_data": {
+                "distance_km": 2.5
+            }
+        }),
+        Some(&parent.access_token)
+    ).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST, "Response: {:?}", response);
+    let fields = response["fields"]
+        .as_array()
+        .unwrap_or_else(|| panic!("Expected field errors, got: {:?}", response));
+    assert!(fields.iter().any(|f| f["field"] == "parent_nik"), "Response: {:?}", response);
+
+    ctx.cleanup_test_data().await;
+}
+
+async fn create_registration_in_period(
+    ctx: &TestContext,
+    parent_token: &str,
+    period_id: i32,
+    path_id: i32,
+) -> (StatusCode, serde_json::Value) {
+    let birth_date = (Utc::now() - Duration::days(365 * 15)).date_naive();
+
+    ctx.post(
+        "/api/v1/registrations",
+        json!({
+            "period_id": period_id,
+            "path_id": path_id,
+            "student_nisn": "9999999999",
+            "student_name": "Window Student",
+            "student_gender": "P",
+            "student_birth_place": "Bandung",
+            "student_birth_date": birth_date,
+            "student_religion": "Islam",
+            "student_address": "Jl. Test No. 123",
+            "parent_name": "Parent Window",
+            "parent_nik": "3273011003800009",
+            "parent_phone": "081234567890",
+            "path_data": {
+                "distance_km": 2.5
+            }
+        }),
+        Some(parent_token)
+    ).await
+}
+
+#[tokio::test]
+async fn test_registration_create_before_window_opens() {
+    let ctx = TestContext::new().await;
+    ctx.cleanup_test_data().await;
+
+    let super_admin = ctx.create_super_admin().await;
+    let school_id = ctx.create_test_school(&super_admin.access_token, "TEST309").await;
+    let school_admin = ctx.create_school_admin(school_id, "admin_early@test.com").await;
+    let parent = ctx.create_parent(school_id, "parent_early@test.com").await;
+
+    let start_date = to_naive_date(Utc::now() + Duration::days(30));
+    let end_date = to_naive_date(Utc::now() + Duration::days(120));
+    let (period_id, path_id) =
+        setup_period_with_window(&ctx, &school_admin.access_token, start_date, end_date, None).await;
+
+    let (status, response) =
+        create_registration_in_period(&ctx, &parent.access_token, period_id, path_id).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST, "Response: {:?}", response);
+    assert!(response["error"].as_str().unwrap().contains("Registration opens at"));
+
+    ctx.cleanup_test_data().await;
+}
+
+#[tokio::test]
+async fn test_registration_create_after_window_closes() {
+    let ctx = TestContext::new().await;
+    ctx.cleanup_test_data().await;
+
+    let super_admin = ctx.create_super_admin().await;
+    let school_id = ctx.create_test_school(&super_admin.access_token, "TEST310").await;
+    let school_admin = ctx.create_school_admin(school_id, "admin_late@test.com").await;
+    let parent = ctx.create_parent(school_id, "parent_late@test.com").await;
+
+    let start_date = to_naive_date(Utc::now() - Duration::days(30));
+    let end_date = to_naive_date(Utc::now() + Duration::days(60));
+    let registration_end = to_naive_date(Utc::now() - Duration::days(2));
+    let (period_id, path_id) = setup_period_with_window(
+        &ctx,
+        &school_admin.access_token,
+        start_date,
+        end_date,
+        Some(registration_end),
+    ).await;
+
+    let (status, response) =
+        create_registration_in_period(&ctx, &parent.access_token, period_id, path_id).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST, "Response: {:?}", response);
+    assert!(response["error"].as_str().unwrap().contains("Registration closed at"));
+
+    ctx.cleanup_test_data().await;
+}
+
 // ============================================================================
 // List Registration Tests
 // ============================================================================
@@ -132,7 +275,7 @@ async fn test_registration_list_parent_only_sees_own() {
             "student_address": "Address 1",
             "student_email": "student1@test.com",
             "parent_name": "Parent 1",
-            "parent_nik": "1111111111111111",
+            "parent_nik": "3201021203810002",
             "parent_phone": "081111111111",
             "path_data": {}
         }),
@@ -154,7 +297,7 @@ async fn test_registration_list_parent_only_sees_own() {
             "student_address": "Address 2",
             "student_email": "student2@test.com",
             "parent_name": "Parent 2",
-            "parent_nik": "2222222222222222",
+            "parent_nik": "3273015208820003",
             "parent_phone": "082222222222",
             "path_data": {}
         }),
@@ -207,7 +350,7 @@ async fn test_registration_update() {
             "student_address": "Old Address",
             "student_email": "student3@test.com",
             "parent_name": "Parent 3",
-            "parent_nik": "3333333333333333",
+            "parent_nik": "3171040107830004",
             "parent_phone": "083333333333",
             "path_data": {}
         }),
@@ -261,7 +404,7 @@ async fn test_registration_submit() {
             "student_address": "Address 4",
             "student_email": "student4@test.com",
             "parent_name": "Parent 4",
-            "parent_nik": "4444444444444444",
+            "parent_nik": "3171054410840005",
             "parent_phone": "084444444444",
             "path_data": {}
         }),
@@ -275,7 +418,7 @@ async fn test_registration_submit() {
         &format!("/api/v1/registrations/{}/documents", registration_id),
         json!({
             "document_type": "birth_certificate",
-            "file_url": "https://example.com/birth_cert.pdf",
+            "file_url": ctx.document_url("birth_cert.pdf"),
             "file_name": "birth_certificate.pdf",
             "file_size": 102400,
             "mime_type": "application/pdf"
@@ -327,7 +470,7 @@ async fn test_registration_upload_document() {
             "student_address": "Address 5",
             "student_email": "student5@test.com",
             "parent_name": "Parent 5",
-            "parent_nik": "5555555555555555",
+            "parent_nik": "3374012006850006",
             "parent_phone": "085555555555",
             "path_data": {}
         }),
@@ -340,7 +483,7 @@ async fn test_registration_upload_document() {
         &format!("/api/v1/registrations/{}/documents", registration_id),
         json!({
             "document_type": "birth_certificate",
-            "file_url": "https://example.com/birth_cert.pdf",
+            "file_url": ctx.document_url("birth_cert.pdf"),
             "file_name": "birth_certificate.pdf",
             "file_size": 102400,
             "mime_type": "application/pdf"
@@ -352,6 +495,21 @@ async fn test_registration_upload_document() {
     assert_eq!(response["document_type"], "birth_certificate");
     assert_eq!(response["verification_status"], "pending");
 
+    // Files outside the document bucket are refused
+    let (status, response) = ctx.post(
+        &format!("/api/v1/registrations/{}/documents", registration_id),
+        json!({
+            "document_type": "family_card",
+            "file_url": "http://169.254.169.254/latest/meta-data/kk.pdf",
+            "file_name": "family_card.pdf",
+            "file_size": 102400,
+            "mime_type": "application/pdf"
+        }),
+        Some(&parent.access_token)
+    ).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST, "Response: {:?}", response);
+
     ctx.cleanup_test_data().await;
 }
 
@@ -382,7 +540,7 @@ async fn test_registration_list_documents() {
             "student_address": "Address 6",
             "student_email": "student6@test.com",
             "parent_name": "Parent 6",
-            "parent_nik": "6666666666666666",
+            "parent_nik": "3578030911860007",
             "parent_phone": "086666666666",
             "path_data": {}
         }),
@@ -396,7 +554,7 @@ async fn test_registration_list_documents() {
         &format!("/api/v1/registrations/{}/documents", registration_id),
         json!({
             "document_type": "birth_certificate",
-            "file_url": "https://example.com/birth_cert.pdf",
+            "file_url": ctx.document_url("birth_cert.pdf"),
             "file_name": "birth_certificate.pdf",
             "file_size": 102400,
             "mime_type": "application/pdf"
@@ -408,7 +566,7 @@ async fn test_registration_list_documents() {
         &format!("/api/v1/registrations/{}/documents", registration_id),
         json!({
             "document_type": "family_card",
-            "file_url": "https://example.com/family_card.pdf",
+            "file_url": ctx.document_url("family_card.pdf"),
             "file_name": "family_card.pdf",
             "file_size": 204800,
             "mime_type": "application/pdf"
@@ -461,7 +619,7 @@ async fn test_registration_parent_cannot_see_others() {
             "student_address": "Address 7",
             "student_email": "student7@test.com",
             "parent_name": "Parent 7",
-            "parent_nik": "7777777777777777",
+            "parent_nik": "3578066502870008",
             "parent_phone": "087777777777",
             "path_data": {}
         }),
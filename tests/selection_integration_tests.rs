@@ -72,7 +72,7 @@ async fn setup_verified_registrations(
                 "student_address": "Jl. Selection Test",
                 "student_email": format!("select{}@test.com", i),
                 "parent_name": "Parent Select",
-                "parent_nik": format!("100000000000000{}", i),
+                "parent_nik": format!("320101150580{:04}", i + 1),
                 "parent_phone": format!("0810000000{:02}", i),
                 "path_data": {
                     "distance_km": (i as f64 * 0.5) + 1.0
@@ -88,7 +88,7 @@ async fn setup_verified_registrations(
             &format!("/api/v1/registrations/{}/documents", registration_id),
             json!({
                 "document_type": "birth_certificate",
-                "file_url": "https://example.com/birth_cert.pdf",
+                "file_url": ctx.document_url("birth_cert.pdf"),
                 "file_name": "birth_certificate.pdf",
                 "file_size": 102400,
                 "mime_type": "application/pdf"
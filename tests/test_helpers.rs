@@ -18,6 +18,7 @@ static TEST_DB: Lazy<Arc<Mutex<Option<sqlx::PgPool>>>> = Lazy::new(|| Arc::new(M
 pub struct TestContext {
     pub app: Router,
     pub db: sqlx::PgPool,
+    pub config: Config,
 }
 
 #[derive(Debug, Deserialize)]
@@ -94,7 +95,17 @@ impl TestContext {
 
         let app = Router::new().nest("/api/v1", api::routes(app_state));
 
-        TestContext { app, db }
+        TestContext { app, db, config }
+    }
+
+    /// Public URL of a file in the document bucket
+    pub fn document_url(&self, file_name: &str) -> String {
+        format!(
+            "{}/storage/v1/object/public/{}/tests/{}",
+            self.config.supabase_url.trim_end_matches('/'),
+            self.config.document_bucket,
+            file_name
+        )
     }
 
     pub async fn request(&self, method: Method, uri: &str, body: Option<Value>, auth_token: Option<&str>) -> (StatusCode, Value) {
@@ -69,7 +69,7 @@ async fn setup_registration_for_verification(
             "student_address": "Jl. Verify Test",
             "student_email": "verify@test.com",
             "parent_name": "Parent Verify",
-            "parent_nik": "9999999999999999",
+            "parent_nik": "3201011505800009",
             "parent_phone": "089999999999",
             "path_data": {}
         }),
@@ -85,7 +85,7 @@ async fn setup_registration_for_verification(
         &format!("/api/v1/registrations/{}/documents", registration_id),
         json!({
             "document_type": "birth_certificate",
-            "file_url": "https://example.com/birth_cert.pdf",
+            "file_url": ctx.document_url("birth_cert.pdf"),
             "file_name": "birth_certificate.pdf",
             "file_size": 102400,
             "mime_type": "application/pdf"
@@ -286,7 +286,7 @@ async fn test_verification_verify_document() {
             "student_name": "Test Doc", "student_gender": "L", "student_birth_place": "Jakarta",
             "student_birth_date": birth_date, "student_religion": "Islam",
             "student_address": "Jl. Test", "student_email": "doc@test.com",
-            "parent_name": "Parent Doc", "parent_nik": "8888888888888888",
+            "parent_name": "Parent Doc", "parent_nik": "3201015208810010",
             "parent_phone": "088888888888", "path_data": {}
         }),
         Some(&parent.access_token)
@@ -299,7 +299,7 @@ async fn test_verification_verify_document() {
         &format!("/api/v1/registrations/{}/documents", registration_id),
         json!({
             "document_type": "family_card",
-            "file_url": "https://example.com/kk.pdf",
+            "file_url": ctx.document_url("kk.pdf"),
             "file_name": "kk.pdf",
             "file_size": 102400,
             "mime_type": "application/pdf"
@@ -364,7 +364,7 @@ async fn test_verification_reject_document() {
             "student_name": "Test Reject Doc", "student_gender": "L", "student_birth_place": "Jakarta",
             "student_birth_date": birth_date, "student_religion": "Islam",
             "student_address": "Jl. Test", "student_email": "rejectdoc@test.com",
-            "parent_name": "Parent Reject", "parent_nik": "7777777777777777",
+            "parent_name": "Parent Reject", "parent_nik": "3201010107820011",
             "parent_phone": "087777777777", "path_data": {}
         }),
         Some(&parent.access_token)
@@ -377,7 +377,7 @@ async fn test_verification_reject_document() {
         &format!("/api/v1/registrations/{}/documents", registration_id),
         json!({
             "document_type": "rapor",
-            "file_url": "https://example.com/rapor.pdf",
+            "file_url": ctx.document_url("rapor.pdf"),
             "file_name": "rapor.pdf",
             "file_size": 102400,
             "mime_type": "application/pdf"